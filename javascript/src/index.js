@@ -1,7 +1,13 @@
 // @ts-check
 
 const Workbook = require('./models/workbook');
-const { Sheet, ArrayFormulaSheetValue } = require('./models/sheet');
+const Template = require('./models/template');
+const {
+  Sheet,
+  ArrayFormulaSheetValue,
+  ConditionalFormatGroupSheetValue,
+  CsvImportSheetValue,
+} = require('./models/sheet');
 const Color = require('./models/color');
 const Format = require('./models/format');
 const Formula = require('./models/formula');
@@ -31,6 +37,7 @@ const { Table, TableFunction, TableColumn } = require('./models/table');
 module.exports = {
   colorUtils,
   Workbook,
+  Template,
   Sheet,
   Color,
   Format,
@@ -39,6 +46,8 @@ module.exports = {
   Border,
   DiagonalBorder,
   ArrayFormulaSheetValue,
+  ConditionalFormatGroupSheetValue,
+  CsvImportSheetValue,
   ConditionalFormatTwoColorScale,
   ConditionalFormatThreeColorScale,
   ConditionalFormatAverage,
@@ -1,11 +1,15 @@
 // @ts-check
 const Color = require('./color');
 
+/** @typedef {import('./color').ColorValue} ColorValue */
+
 /**
  *@typedef {(
  *    "none"|
  *    "thin"|
  *    "medium"|
+ *    "dashed"|
+ *    "dotted"|
  *    "thick"|
  *    "double"|
  *    "hair"|
@@ -30,12 +34,12 @@ const Color = require('./color');
  * @class Border
  * @classdesc Represents a border
  * @property {BorderType} style - The style of the border
- * @property {Color} color - The color of the border
+ * @property {ColorValue} color - The color of the border
  */
 class Border {
   /**
    * @param {BorderType} [style] - The style of the border
-   * @param {Color} [color] - The color of the border
+   * @param {ColorValue} [color] - The color of the border
    */
   constructor(style, color) {
     if (!color) {
@@ -51,7 +55,7 @@ class Border {
     this.style = style;
     /**
      * The color of the border
-     * @type {Color}
+     * @type {ColorValue}
      */
     this.color = color;
   }
@@ -62,12 +66,12 @@ class Border {
  * @classdesc Represents a diagonal border
  * @property {DiagonalBorderType} dStyle - The style of the border
  * @property {BorderType} style - The style of the border
- * @property {Color} color - The color of the border
+ * @property {ColorValue} color - The color of the border
  */
 class DiagonalBorder extends Border {
   /**
    * @param {BorderType} [style] - The style of the border
-   * @param {Color} [color] - The color of the border
+   * @param {ColorValue} [color] - The color of the border
    * @param {DiagonalBorderType} [dStyle] - The style of the border
    */
   constructor(style, color, dStyle) {
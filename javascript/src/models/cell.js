@@ -3,42 +3,51 @@ const Format = require('./format');
 const Link = require('./link');
 const Formula = require('./formula');
 
+/** @typedef {import('./format').FormatValue} FormatValue */
+
 /**
- * @typedef {(number|string|Link|Formula)} CellValue
- * @typedef {("number"|"string"|"link"|"date"|"formula")} CellType
+ * @typedef {(number|string|boolean|Link|Formula)} CellValue
+ * @typedef {("number"|"string"|"boolean"|"link"|"date"|"formula")} CellType
  */
 
 /**
  *
  * @class Cell
  * @classdesc Represents a cell in the grid
- * @property {number} col - The column index of the cell
- * @property {number} row - The row index of the cell
+ * @property {number} [col] - The column index of the cell (unset when given by `cell`)
+ * @property {number} [row] - The row index of the cell (unset when given by `cell`)
+ * @property {string} [cell] - The cell in A1 notation ("B2"), in place of `row` and `col`
  * @property {CellValue} value - The value of the cell
  * @property {false} merged - Whether the cell is part of a merged cell
  * @property {CellType} [celType] - The type of the cell.
- * @property {Format} [format] - The format of the cell
+ * @property {FormatValue} [format] - The format of the cell
  */
 class Cell {
   /**
    * @param {Object} opts - Options for the cell
-   * @param {number} opts.col - The column index of the cell
-   * @param {number} opts.row - The row index of the cell
+   * @param {number} [opts.col] - The column index of the cell
+   * @param {number} [opts.row] - The row index of the cell
+   * @param {string} [opts.cell] - The cell in A1 notation ("B2"), in place of `row` and `col`
    * @param {CellValue} opts.value - The value of the cell
    * @param {CellType} [opts.cellType] - The type of the cell
-   * @param {Format} [opts.format] - The format of the cell
+   * @param {FormatValue} [opts.format] - The format of the cell
    */
   constructor(opts) {
     /**
      * The column index of the cell
-     * @type {number}
+     * @type {number|undefined}
      */
     this.col = opts.col;
     /**
      * The row index of the cell
-     * @type {number}
+     * @type {number|undefined}
      */
     this.row = opts.row;
+    /**
+     * The cell in A1 notation
+     * @type {string|undefined}
+     */
+    this.cell = opts.cell;
     /**
      * The value of the cell
      * @type {CellValue}
@@ -53,7 +62,7 @@ class Cell {
 
     /**
      * The format of the cell
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      */
     this.format = opts.format ?? undefined;
 
@@ -1,11 +1,25 @@
 // @ts-check
 
+/**
+ * A theme color, as shown in the top block of Excel's color picker.
+ * @typedef {Object} ThemeColor
+ * @property {number} theme - The theme color index (0-9)
+ * @property {number} [shade=0] - The shade/tint row (0-5)
+ */
+
+/**
+ * A color given as a {@link Color}, a {@link ThemeColor}, a hex string
+ * (`'#1F4E79'` or `'1F4E79'`), a named color (`'red'`, `'navy'`, ...) or
+ * `'automatic'`.
+ * @typedef {Color | ThemeColor | string} ColorValue
+ */
+
 /**
  * @class Color
  * @classdesc Represents a color
- * @property {number} red - The red value of the color
- * @property {number} green - The green value of the color
- * @property {number} blue - The blue value of the color
+ * @property {number} red - The red value of the color (0-255)
+ * @property {number} green - The green value of the color (0-255)
+ * @property {number} blue - The blue value of the color (0-255)
  */
 class Color {
   /**
@@ -4,6 +4,9 @@ const Color = require('./color');
 const Format = require('./format');
 const Formula = require('./formula');
 
+/** @typedef {import('./color').ColorValue} ColorValue */
+/** @typedef {import('./format').FormatValue} FormatValue */
+
 //Enums
 /**
  * @typedef {(
@@ -145,7 +148,12 @@ const Formula = require('./formula');
  * "text" |
  * "top"
  * )} ConditionalFormatClassType
- *
+ */
+
+// Generated ids start high so they don't clash with ids set by hand
+let nextConditionalFormatId = 2 ** 31;
+
+/**
  * @class ConditionalFormat
  * @description Represents a generic conditional format.
  * @abstract
@@ -162,10 +170,11 @@ class ConditionalFormat {
    */
   constructor(type, multiRange, stopIfTrue) {
     /**
+     * The id of the conditional format. Two conditional formats of a workbook can
+     * only share an id when they are the same rule.
      * @type {number}
-     * @default Math.floor(Math.random() * 1000000)
      */
-    this.id = Math.floor(Math.random() * 1000000);
+    this.id = nextConditionalFormatId++;
 
     /**
      * @type {ConditionalFormatClassType}
@@ -200,6 +209,17 @@ class ConditionalFormat {
   setStopIfTrue(stopIfTrue) {
     this.stopIfTrue = stopIfTrue;
   }
+
+  /**
+   * A copy of the conditional format with an id of its own, to change without
+   * changing the original.
+   * @returns {this}
+   */
+  clone() {
+    const copy = Object.assign(Object.create(Object.getPrototypeOf(this)), this);
+    copy.id = nextConditionalFormatId++;
+    return copy;
+  }
 }
 
 /**
@@ -213,8 +233,8 @@ class ConditionalFormat {
  * @classdesc Represents a 2 Color Scale conditional format.
  * Used to represent a Cell style conditional format in Excel. A 2 Color Scale Cell conditional format shows a per cell color gradient from the minimum value to the maximum value.
  * @extends ConditionalFormat
- * @property {Color} [minColor] - The color for the minimum value.(If not set, Excel will use the default color for the minimum value)
- * @property {Color} [maxColor] - The color for the maximum value.(If not set, Excel will use the default color for the maximum value)
+ * @property {ColorValue} [minColor] - The color for the minimum value.(If not set, Excel will use the default color for the minimum value)
+ * @property {ColorValue} [maxColor] - The color for the maximum value.(If not set, Excel will use the default color for the maximum value)
  * @property {ConditionalFormatTwoColorScaleRule} [minRule] - The rule for the minimum value.
  * @property {ConditionalFormatTwoColorScaleRule} [maxRule] - The rule for the maximum value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
@@ -223,8 +243,8 @@ class ConditionalFormat {
 class ConditionalFormatTwoColorScale extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
-   * @param {Color} [options.minColor] - The color for the minimum value.
-   * @param {Color} [options.maxColor] - The color for the maximum value.
+   * @param {ColorValue} [options.minColor] - The color for the minimum value.
+   * @param {ColorValue} [options.maxColor] - The color for the maximum value.
    * @param {ConditionalFormatTypeRule} [options.minRule] - The rule for the minimum value.
    * @param {ConditionalFormatTypeRule} [options.maxRule] - The rule for the maximum value.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
@@ -233,13 +253,13 @@ class ConditionalFormatTwoColorScale extends ConditionalFormat {
   constructor(options = {}) {
     super('twoColorScale', options.multiRange, options.stopIfTrue);
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.minColor = options.minColor;
 
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.maxColor = options.maxColor;
@@ -258,14 +278,14 @@ class ConditionalFormatTwoColorScale extends ConditionalFormat {
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setMinColor(color) {
     this.minColor = color;
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setMaxColor(color) {
     this.maxColor = color;
@@ -305,15 +325,15 @@ class ConditionalFormatTwoColorScale extends ConditionalFormat {
  * @classdesc Represents a 3 Color Scale conditional format.
  * Used to represent a Cell style conditional format in Excel. A 3 Color Scale Cell conditional format shows a per cell color gradient from the minimum value to the maximum value.
  * @extends ConditionalFormatTwoColorScale
- * @property {Color} [midColor] - The color for the mid value.(If not set, Excel will use the default color for the mid value)
+ * @property {ColorValue} [midColor] - The color for the mid value.(If not set, Excel will use the default color for the mid value)
  * @property {ConditionalFormatTwoColorScaleRule} [midRule] - The rule for the mid value.
  */
 class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorScale {
   /**
    * @param {Object} [options] - The options object
-   * @param {Color} [options.minColor] - The color for the minimum value.
-   * @param {Color} [options.midColor] - The color for the mid value.
-   * @param {Color} [options.maxColor] - The color for the maximum value.
+   * @param {ColorValue} [options.minColor] - The color for the minimum value.
+   * @param {ColorValue} [options.midColor] - The color for the mid value.
+   * @param {ColorValue} [options.maxColor] - The color for the maximum value.
    * @param {ConditionalFormatTypeRule} [options.minRule] - The rule for the minimum value.
    * @param {ConditionalFormatTypeRule} [options.midRule] - The rule for the maximum value.
    * @param {ConditionalFormatTypeRule} [options.maxRule] - The rule for the maximum value.
@@ -328,7 +348,7 @@ class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorScale {
     this.type = 'threeColorScale';
 
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.midColor = options.midColor;
@@ -341,7 +361,7 @@ class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorScale {
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setMidColor(color) {
     this.midColor = color;
@@ -360,7 +380,7 @@ class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorScale {
  * @classdesc Represents an Average/Standard Deviation style conditional format
  * Is used to represent a Average or Standard Deviation style conditional format in Excel
  * @property {ConditionalFormatAverageRule} rule - The rule for the average value.(default: 'aboveAverage')
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -368,7 +388,7 @@ class ConditionalFormatAverage extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
    * @param {ConditionalFormatAverageRule} [options.rule] - The rule for the average value.(default: 'aboveAverage')
-   * @param {Format} [options.format] - The format for the average value.
+   * @param {FormatValue} [options.format] - The format for the average value.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -381,7 +401,7 @@ class ConditionalFormatAverage extends ConditionalFormat {
     this.rule = options.rule || 'aboveAverage';
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -395,7 +415,7 @@ class ConditionalFormatAverage extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -407,7 +427,7 @@ class ConditionalFormatAverage extends ConditionalFormat {
  * @classdesc Represents a a Blank/Non-blank conditional format.
  * @extends ConditionalFormat
  * @property {boolean} invert - Inverts the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -415,7 +435,7 @@ class ConditionalFormatBlank extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
    * @param {boolean} [options.invert] - Inverts the conditional format.
-   * @param {Format} [options.format] - The format for the average value.
+   * @param {FormatValue} [options.format] - The format for the average value.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -429,7 +449,7 @@ class ConditionalFormatBlank extends ConditionalFormat {
     this.invert = options.invert || false;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
 
@@ -444,7 +464,7 @@ class ConditionalFormatBlank extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -456,7 +476,7 @@ class ConditionalFormatBlank extends ConditionalFormat {
  * @classdesc Represents a cell style conditional format.
  * @extends ConditionalFormat
  * @property {ConditionalFormatCellRule} rule - The rule for the cell.
- * @property {Format} [format] - The format for the cell.
+ * @property {FormatValue} [format] - The format for the cell.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -464,7 +484,7 @@ class ConditionalFormatCell extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
    * @param {ConditionalFormatCellRule} [options.rule] - The rule for the cell.
-   * @param {Format} [options.format] - The format for the cell.
+   * @param {FormatValue} [options.format] - The format for the cell.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -476,7 +496,7 @@ class ConditionalFormatCell extends ConditionalFormat {
     this.rule = options.rule;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -490,7 +510,7 @@ class ConditionalFormatCell extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -501,17 +521,17 @@ class ConditionalFormatCell extends ConditionalFormat {
  * @class ConditionalFormatDataBar
  * @classdesc Represents a Data Bar style conditional format.
  * @extends ConditionalFormat
- * @property {Color} [axisColor] - The color of the axis.
+ * @property {ColorValue} [axisColor] - The color of the axis.
  * @property {ConditionalFormatDataBarAxisPosition} [axisPosition] - The position of the axis.
  * @property {boolean} [barOnly] - Show only the bar.
- * @property {Color} [borderColor] - The color of the border.
+ * @property {ColorValue} [borderColor] - The color of the border.
  * @property {boolean} [borderOff] - Turn off the border.
  * @property {ConditionalFormatDataBarDirection} [direction] - The direction of the data bar.
- * @property {Color} [fillColor] - The color of the fill.
+ * @property {ColorValue} [fillColor] - The color of the fill.
  * @property {ConditionalFormatTypeRule} [maxRule] - The rule for the maximum value.
  * @property {ConditionalFormatTypeRule} [minRule] - The rule for the minimum value.
- * @property {Color} [negativeBorderColor] - The color of the negative border.
- * @property {Color} [negativeFillColor] - The color of the negative fill.
+ * @property {ColorValue} [negativeBorderColor] - The color of the negative border.
+ * @property {ColorValue} [negativeFillColor] - The color of the negative fill.
  * @property {boolean} [solidFill] - Show a solid fill.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
@@ -519,17 +539,17 @@ class ConditionalFormatCell extends ConditionalFormat {
 class ConditionalFormatDataBar extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
-   * @param {Color} [options.axisColor] - The color of the axis.
+   * @param {ColorValue} [options.axisColor] - The color of the axis.
    * @param {ConditionalFormatDataBarAxisPosition} [options.axisPosition] - The position of the axis.
    * @param {boolean} [options.barOnly] - Show only the bar.
-   * @param {Color} [options.borderColor] - The color of the border.
+   * @param {ColorValue} [options.borderColor] - The color of the border.
    * @param {boolean} [options.borderOff] - Turn off the border.
    * @param {ConditionalFormatDataBarDirection} [options.direction] - The direction of the data bar.
-   * @param {Color} [options.fillColor] - The color of the fill.
+   * @param {ColorValue} [options.fillColor] - The color of the fill.
    * @param {ConditionalFormatTypeRule} [options.maxRule] - The rule for the maximum value.
    * @param {ConditionalFormatTypeRule} [options.minRule] - The rule for the minimum value.
-   * @param {Color} [options.negativeBorderColor] - The color of the negative border.
-   * @param {Color} [options.negativeFillColor] - The color of the negative fill.
+   * @param {ColorValue} [options.negativeBorderColor] - The color of the negative border.
+   * @param {ColorValue} [options.negativeFillColor] - The color of the negative fill.
    * @param {boolean} [options.solidFill] - Show a solid fill.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
@@ -537,7 +557,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
   constructor(options = {}) {
     super('dataBar', options.multiRange, options.stopIfTrue);
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.axisColor = options.axisColor;
@@ -555,7 +575,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
     this.barOnly = options.barOnly;
 
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.borderColor = options.borderColor;
@@ -573,7 +593,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
     this.direction = options.direction;
 
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.fillColor = options.fillColor;
@@ -591,13 +611,13 @@ class ConditionalFormatDataBar extends ConditionalFormat {
     this.minRule = options.minRule;
 
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.negativeBorderColor = options.negativeBorderColor;
 
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
     this.negativeFillColor = options.negativeFillColor;
@@ -610,7 +630,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setAxisColor(color) {
     this.axisColor = color;
@@ -631,7 +651,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setBorderColor(color) {
     this.borderColor = color;
@@ -652,7 +672,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setFillColor(color) {
     this.fillColor = color;
@@ -673,14 +693,14 @@ class ConditionalFormatDataBar extends ConditionalFormat {
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setNegativeBorderColor(color) {
     this.negativeBorderColor = color;
   }
 
   /**
-   * @param {Color} color
+   * @param {ColorValue} color
    */
   setNegativeFillColor(color) {
     this.negativeFillColor = color;
@@ -698,7 +718,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
  * @class ConditionalFormatDate
  * @classdesc Represents a Date style conditional format.
  * @extends ConditionalFormat
- * @property {Format} [format] - The format for the date.
+ * @property {FormatValue} [format] - The format for the date.
  * @property {ConditionalFormatDateRule} [rule] - The rule for the date.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
@@ -706,7 +726,7 @@ class ConditionalFormatDataBar extends ConditionalFormat {
 class ConditionalFormatDate extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
-   * @param {Format} [options.format] - The format for the date.
+   * @param {FormatValue} [options.format] - The format for the date.
    * @param {ConditionalFormatDateRule} [options.rule] - The rule for the date.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
@@ -714,7 +734,7 @@ class ConditionalFormatDate extends ConditionalFormat {
   constructor(options = {}) {
     super('date', options.multiRange, options.stopIfTrue);
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -726,7 +746,7 @@ class ConditionalFormatDate extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -745,7 +765,7 @@ class ConditionalFormatDate extends ConditionalFormat {
  * @classdesc Represents a Duplicate/Unique conditional format.
  * @extends ConditionalFormat
  * @property {boolean} invert - Inverts the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -753,7 +773,7 @@ class ConditionalFormatDuplicate extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
    * @param {boolean} [options.invert] - Inverts the conditional format.
-   * @param {Format} [options.format] - The format for the average value.
+   * @param {FormatValue} [options.format] - The format for the average value.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -766,7 +786,7 @@ class ConditionalFormatDuplicate extends ConditionalFormat {
     this.invert = options.invert || false;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -780,7 +800,7 @@ class ConditionalFormatDuplicate extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -792,7 +812,7 @@ class ConditionalFormatDuplicate extends ConditionalFormat {
  * @classdesc Represents an Error style conditional format.
  * @extends ConditionalFormat
  * @property {boolean} invert - Inverts the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -800,7 +820,7 @@ class ConditionalFormatError extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
    * @param {boolean} [options.invert] - Inverts the conditional format.
-   * @param {Format} [options.format] - The format for the average value.
+   * @param {FormatValue} [options.format] - The format for the average value.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -814,7 +834,7 @@ class ConditionalFormatError extends ConditionalFormat {
     this.invert = options.invert || false;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -822,7 +842,7 @@ class ConditionalFormatError extends ConditionalFormat {
 
   /**
    * Sets the format
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -842,7 +862,7 @@ class ConditionalFormatError extends ConditionalFormat {
  * @classdesc Represents a Formula style conditional format.
  * @extends ConditionalFormat
  * @property {Formula} [formula] - The formula(non-dynamic) for the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -850,7 +870,7 @@ class ConditionalFormatFormula extends ConditionalFormat {
   /**
    * @param {Object} [options] - The options object
    * @param {Formula} [options.formula] - The formula(non-dynamic) for the conditional format.
-   * @param {Format} [options.format] - The format for the average value.
+   * @param {FormatValue} [options.format] - The format for the average value.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -863,7 +883,7 @@ class ConditionalFormatFormula extends ConditionalFormat {
     this.formula = options.formula;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -877,7 +897,7 @@ class ConditionalFormatFormula extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -1065,7 +1085,7 @@ class ConditionalFormatIconSet extends ConditionalFormat {
  * @classdesc Represents a Text style conditional format.
  * @extends ConditionalFormat
  * @property {ConditionalFormatTextRule} rule - The rule for the text.
- * @property {Format} [format] - The format for the text.
+ * @property {FormatValue} [format] - The format for the text.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -1073,7 +1093,7 @@ class ConditionalFormatText extends ConditionalFormat {
   /**
    * @param {Object} options - The options object
    * @param {ConditionalFormatTextRule} options.rule - The rule for the text.
-   * @param {Format} [options.format] - The format for the text.
+   * @param {FormatValue} [options.format] - The format for the text.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -1085,7 +1105,7 @@ class ConditionalFormatText extends ConditionalFormat {
     this.rule = options.rule;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -1099,7 +1119,7 @@ class ConditionalFormatText extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
@@ -1111,7 +1131,7 @@ class ConditionalFormatText extends ConditionalFormat {
  * @classdesc Represents a Top style conditional format.
  * @extends ConditionalFormat
  * @property {ConditionalFormatTopRule} rule - The rule for the top values.
- * @property {Format} [format] - The format for the top values.
+ * @property {FormatValue} [format] - The format for the top values.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -1119,7 +1139,7 @@ class ConditionalFormatTop extends ConditionalFormat {
   /**
    * @param {Object} options - The options object
    * @param {ConditionalFormatTopRule} options.rule - The rule for the top values.
-   * @param {Format} [options.format] - The format for the top values.
+   * @param {FormatValue} [options.format] - The format for the top values.
    * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
    * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
    */
@@ -1131,7 +1151,7 @@ class ConditionalFormatTop extends ConditionalFormat {
     this.rule = options.rule;
 
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
     this.format = options.format;
@@ -1145,7 +1165,7 @@ class ConditionalFormatTop extends ConditionalFormat {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    */
   setFormat(format) {
     this.format = format;
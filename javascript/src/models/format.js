@@ -3,6 +3,8 @@
 const { Border, DiagonalBorder } = require('./border');
 const Color = require('./color');
 
+/** @typedef {import('./color').ColorValue} ColorValue */
+
 /**
  * @typedef {(
  *   "general"|
@@ -49,14 +51,39 @@ const Color = require('./color');
  *   "singleAccounting"|
  *   "doubleAccounting"
  * )} FormatUnderline - The underline style
+ *
+ * @typedef {(
+ *   "top"|
+ *   "bottom"|
+ *   "center"|
+ *   "justify"|
+ *   "distributed"
+ * )} FormatVerticalAlign - The vertical alignment of the cell
+ *
+ * @typedef {(
+ *   "none"|
+ *   "superscript"|
+ *   "subscript"
+ * )} FormatScript - The font script
+ *
+ * @typedef {(
+ *   "context"|
+ *   "leftToRight"|
+ *   "rightToLeft"
+ * )} FormatReadingDirection - The reading direction of the text
+ *
+ * @typedef {(
+ *   "major"|
+ *   "minor"
+ * )} FormatThemeFont - The theme font (headings or body)
  */
 
 /**
  * @class Format
  * @classdesc Represents a format
- * @property {number} id - the id of the format (do not set this directly)
+ * @property {number} id - The id of the format. Formats are deduplicated by their properties, the id only has to be unique per distinct format
  * @property {FormatAlign|undefined} [align=undefined] - The alignment of the cell
- * @property {Color} [backgroundColor] - The background color of the cell
+ * @property {ColorValue} [backgroundColor] - The background color of the cell
  * @property {boolean} [bold] - If the font is bold
  * @property {Border} [leftBorder] - The left border of the cell
  * @property {Border} [rightBorder] - The right border of the cell
@@ -64,13 +91,13 @@ const Color = require('./color');
  * @property {Border} [bottomBorder] - The bottom border of the cell
  * @property {DiagonalBorder} [diagonalBorder] - The diagonal border of the cell
  * @property {number} [charset] - The charset of the font
- * @property {Color} [fontColor] - The color of the font
+ * @property {ColorValue} [fontColor] - The color of the font
  * @property {number} [fontFamily] - The family of the font
  * @property {string} [fontName] - The name of the font
  * @property {string} [fontScheme] - The font scheme
  * @property {number} [fontSize] - The font size
  * @property {boolean} [strikeThrough] - If the font is strike through
- * @property {Color} [foregroundColor] - The foreground color
+ * @property {ColorValue} [foregroundColor] - The foreground color
  * @property {boolean} [hidden] - If the format is hidden
  * @property {boolean} [hyperlink] - If the format is hyperlinked
  * @property {number} [indent] - The indent level
@@ -80,14 +107,31 @@ const Color = require('./color');
  * @property {number} [numFmtId] - The number format id
  * @property {FormatPattern} [pattern] - The pattern
  * @property {FormatUnderline} [underline] - The underline style
+ * @property {FormatVerticalAlign} [verticalAlign] - The vertical alignment of the cell
+ * @property {boolean} [textWrap] - If the text wraps
+ * @property {number} [rotation] - The text rotation (-90 to 90, or 270 for stacked text)
+ * @property {boolean} [shrink] - If the text shrinks to fit the cell
+ * @property {FormatScript} [fontScript] - The font script
+ * @property {FormatReadingDirection} [readingDirection] - The reading direction
+ * @property {boolean} [quotePrefix] - If the value is prefixed with a quote
+ * @property {FormatThemeFont} [themeFont] - The theme font
  *
  */
+/**
+ * A format, or the id of a format added to the workbook with `Workbook.addFormat`.
+ * @typedef {Format | number} FormatValue
+ */
+
+// Generated ids start high so they don't clash with ids set by hand
+let nextFormatId = 2 ** 31;
+
 class Format {
   /**
    *
    * @param {Object} [options] - The options object
+   * @param {number} [options.id] - The id of the format (assigned automatically if not set)
    * @param {FormatAlign} [options.align] - The alignment of the cell
-   * @param {Color} [options.backgroundColor] - The background color of the cell
+   * @param {ColorValue} [options.backgroundColor] - The background color of the cell
    * @param {boolean} [options.bold] - If the font is bold
    * @param {Border} [options.leftBorder] - The left border of the cell
    * @param {Border} [options.rightBorder] - The right border of the cell
@@ -95,13 +139,13 @@ class Format {
    * @param {Border} [options.bottomBorder] - The bottom border of the cell
    * @param {DiagonalBorder} [options.diagonalBorder] - The diagonal border of the cell
    * @param {number} [options.charset] - The charset of the font
-   * @param {Color} [options.fontColor] - The color of the font
+   * @param {ColorValue} [options.fontColor] - The color of the font
    * @param {number} [options.fontFamily] - The family of the font
    * @param {string} [options.fontName] - The name of the font
    * @param {string} [options.fontScheme] - The font scheme
    * @param {number} [options.fontSize] - The font size
    * @param {boolean} [options.strikeThrough] - If the font is strike through
-   * @param {Color} [options.foregroundColor] - The foreground color
+   * @param {ColorValue} [options.foregroundColor] - The foreground color
    * @param {boolean} [options.hidden] - If the format is hidden
    * @param {boolean} [options.hyperlink] - If the format is hyperlinked
    * @param {number} [options.indent] - The indent level
@@ -111,8 +155,17 @@ class Format {
    * @param {number} [options.numFmtId] - The number format id
    * @param {FormatPattern} [options.pattern] - The pattern
    * @param {FormatUnderline} [options.underline] - The underline style
+   * @param {FormatVerticalAlign} [options.verticalAlign] - The vertical alignment of the cell
+   * @param {boolean} [options.textWrap] - If the text wraps
+   * @param {number} [options.rotation] - The text rotation (-90 to 90, or 270 for stacked text)
+   * @param {boolean} [options.shrink] - If the text shrinks to fit the cell
+   * @param {FormatScript} [options.fontScript] - The font script
+   * @param {FormatReadingDirection} [options.readingDirection] - The reading direction
+   * @param {boolean} [options.quotePrefix] - If the value is prefixed with a quote
+   * @param {FormatThemeFont} [options.themeFont] - The theme font
    */
   constructor({
+    id,
     align,
     backgroundColor,
     bold,
@@ -138,8 +191,20 @@ class Format {
     numFmtId,
     pattern,
     underline,
+    verticalAlign,
+    textWrap,
+    rotation,
+    shrink,
+    fontScript,
+    readingDirection,
+    quotePrefix,
+    themeFont,
   } = {}) {
-    this.id = Math.floor(Math.random() * 1_000_000);
+    /**
+     * The id of the format
+     * @type {number}
+     */
+    this.id = id ?? nextFormatId++;
     /**
      * The alignment of the cell
      * @type {?FormatAlign}
@@ -149,7 +214,7 @@ class Format {
 
     /**
      * The background color of the cell
-     * @type {?Color}
+     * @type {?ColorValue}
      * @default undefined
      */
     this.backgroundColor = backgroundColor;
@@ -205,7 +270,7 @@ class Format {
 
     /**
      * The font color of the cell
-     * @type {?Color}
+     * @type {?ColorValue}
      * @default undefined
      */
     this.fontColor = fontColor;
@@ -247,7 +312,7 @@ class Format {
 
     /**
      * The foreground color of the cell
-     * @type {?Color}
+     * @type {?ColorValue}
      * @default undefined
      */
     this.foregroundColor = foregroundColor;
@@ -314,6 +379,62 @@ class Format {
      * @default undefined
      */
     this.underline = underline;
+
+    /**
+     * The vertical alignment of the cell
+     * @type {?FormatVerticalAlign}
+     * @default undefined
+     */
+    this.verticalAlign = verticalAlign;
+
+    /**
+     * If the text wraps
+     * @type {?boolean}
+     * @default undefined
+     */
+    this.textWrap = textWrap;
+
+    /**
+     * The text rotation (-90 to 90, or 270 for stacked text)
+     * @type {?number}
+     * @default undefined
+     */
+    this.rotation = rotation;
+
+    /**
+     * If the text shrinks to fit the cell
+     * @type {?boolean}
+     * @default undefined
+     */
+    this.shrink = shrink;
+
+    /**
+     * The font script
+     * @type {?FormatScript}
+     * @default undefined
+     */
+    this.fontScript = fontScript;
+
+    /**
+     * The reading direction
+     * @type {?FormatReadingDirection}
+     * @default undefined
+     */
+    this.readingDirection = readingDirection;
+
+    /**
+     * If the value is prefixed with a quote
+     * @type {?boolean}
+     * @default undefined
+     */
+    this.quotePrefix = quotePrefix;
+
+    /**
+     * The theme font
+     * @type {?FormatThemeFont}
+     * @default undefined
+     */
+    this.themeFont = themeFont;
   }
 
   /**
@@ -327,7 +448,7 @@ class Format {
 
   /**
    * Sets the font color
-   * @param {Color} color - The color of the font
+   * @param {ColorValue} color - The color of the font
    * @returns {void}
    */
   setBackgroundColor(color) {
@@ -411,7 +532,7 @@ class Format {
 
   /**
    * Sets the font color
-   * @param {Color} color - The color of the font
+   * @param {ColorValue} color - The color of the font
    * @returns {void}
    */
   setFontColor(color) {
@@ -464,7 +585,7 @@ class Format {
 
   /**
    * Set the foreground color property
-   * @param {Color} color - The color
+   * @param {ColorValue} color - The color
    * @returns {void}
    */
   setForegroundColor(color) {
@@ -551,6 +672,78 @@ class Format {
   setUnderline(underline) {
     this.underline = underline;
   }
+
+  /**
+   * Sets the vertical alignment of the cell
+   * @param {FormatVerticalAlign} verticalAlign - The vertical alignment of the cell
+   * @returns {void}
+   */
+  setVerticalAlignment(verticalAlign) {
+    this.verticalAlign = verticalAlign;
+  }
+
+  /**
+   * Set if the text wraps
+   * @param {boolean} textWrap - If the text wraps
+   * @returns {void}
+   */
+  setTextWrap(textWrap) {
+    this.textWrap = textWrap;
+  }
+
+  /**
+   * Set the text rotation
+   * @param {number} rotation - The angle (-90 to 90), or 270 for vertically stacked text
+   * @returns {void}
+   */
+  setRotation(rotation) {
+    this.rotation = rotation;
+  }
+
+  /**
+   * Set if the text shrinks to fit the cell
+   * @param {boolean} shrink - If the text shrinks to fit the cell
+   * @returns {void}
+   */
+  setShrink(shrink) {
+    this.shrink = shrink;
+  }
+
+  /**
+   * Set the font script (superscript/subscript)
+   * @param {FormatScript} fontScript - The font script
+   * @returns {void}
+   */
+  setFontScript(fontScript) {
+    this.fontScript = fontScript;
+  }
+
+  /**
+   * Set the reading direction of the text
+   * @param {FormatReadingDirection} readingDirection - The reading direction
+   * @returns {void}
+   */
+  setReadingDirection(readingDirection) {
+    this.readingDirection = readingDirection;
+  }
+
+  /**
+   * Set if the value is prefixed with a quote, so Excel shows it as text
+   * @param {boolean} quotePrefix - If the value is prefixed with a quote
+   * @returns {void}
+   */
+  setQuotePrefix(quotePrefix) {
+    this.quotePrefix = quotePrefix;
+  }
+
+  /**
+   * Set the theme font, headings ("major") or body ("minor")
+   * @param {FormatThemeFont} themeFont - The theme font
+   * @returns {void}
+   */
+  setThemeFont(themeFont) {
+    this.themeFont = themeFont;
+  }
 }
 
 module.exports = Format;
@@ -1,10 +1,16 @@
 // @ts-check
 
+/**
+ * The cached result of a formula. Numbers, booleans and strings keep their type in the file,
+ * a string naming an Excel error such as `'#N/A'` or `'#DIV/0!'` is written as that error.
+ * @typedef {number|boolean|string} FormulaResultValue
+ */
+
 /**
  * @class Formula
  * @classdesc Represents a worksheet formula.
  * @property {string} formula - The formula string.
- * @property {string} [result] - The result of the formula.
+ * @property {FormulaResultValue} [result] - The cached result of the formula.
  * @property {boolean} [useFutureFunctions=false] - Enable the use of newer Excel future functions in the formula
  * @property {boolean} [useTableFunctions=false] - Enable backward compatible formulas in table.
  * @property {boolean} [dynamic=false] - Enable the use of dynamic arrays in the formula
@@ -13,7 +19,7 @@ class Formula {
   /**
    * @param {Object} opts - Options for the formula.
    * @param {string} opts.formula - The formula string.
-   * @param {string} [opts.result] - The result of the formula.
+   * @param {FormulaResultValue} [opts.result] - The cached result of the formula, shown by readers that don't recalculate.
    * @param {boolean} [opts.useFutureFunctions=false] - Enable the use of newer Excel future functions in the formula
    * @param {boolean} [opts.useTableFunctions=false] - Enable backward compatible formulas in table.
    * @param {boolean} [opts.dynamic=false] - Enable the use of dynamic arrays in the formula
@@ -25,8 +31,8 @@ class Formula {
      */
     this.formula = opts.formula;
     /**
-     * The result of the formula.
-     * @type {?string|undefined}
+     * The cached result of the formula.
+     * @type {?FormulaResultValue|undefined}
      */
     this.result = opts.result;
     /**
@@ -48,8 +54,8 @@ class Formula {
   }
 
   /**
-   * Set the result of the formula.
-   * @param {string} result - The result of the formula.
+   * Set the cached result of the formula.
+   * @param {FormulaResultValue} result - The result of the formula.
    */
   setResult(result) {
     this.result = result;
@@ -3,9 +3,11 @@ const Format = require('./format');
 const Link = require('./link');
 const Formula = require('./formula');
 
+/** @typedef {import('./format').FormatValue} FormatValue */
+
 /**
- * @typedef {(number|string|Link|Formula)} CellValue
- * @typedef {("number"|"string"|"link"|"date"|"formula")} CellType
+ * @typedef {(number|string|boolean|Link|Formula)} CellValue
+ * @typedef {("number"|"string"|"boolean"|"link"|"date"|"formula")} CellType
  */
 
 /**
@@ -16,44 +18,51 @@ const Formula = require('./formula');
  * @property {number} firstCol - The first column index of the merged cell
  * @property {number} lastRow - The last row index of the merged cell
  * @property {number} lastCol - The last column index of the merged cell
+ * @property {string} [range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
  * @property {CellValue} value - The value of the cell
  * @property {true} merged - Whether the cell is part of a merged cell
- * @property {Format} format - The format of the cell
+ * @property {FormatValue} format - The format of the cell
  * @property {CellType} [celType] - The type of the cell.
  */
 class MergedCell {
   /**
    * @param {Object} opts - Options for the cell
-   * @param {number} opts.firstRow - The first row index of the merged cell
-   * @param {number} opts.firstCol - The first column index of the merged cell
-   * @param {number} opts.lastRow - The last row index of the merged cell
-   * @param {number} opts.lastCol - The last column index of the merged cell
+   * @param {number} [opts.firstRow] - The first row index of the merged cell
+   * @param {number} [opts.firstCol] - The first column index of the merged cell
+   * @param {number} [opts.lastRow] - The last row index of the merged cell
+   * @param {number} [opts.lastCol] - The last column index of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {CellValue} opts.value - The value of the cell
-   * @param {Format} opts.format - The format of the cell
+   * @param {FormatValue} opts.format - The format of the cell
    * @param {CellType} [opts.cellType] - The type of the cell
 
    */
   constructor(opts) {
     /**
      * The first row index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstRow = opts.firstRow;
     /**
      * The first column index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstCol = opts.firstCol;
     /**
      * The last row index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastRow = opts.lastRow;
     /**
      * The last column index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastCol = opts.lastCol;
+    /**
+     * The merged range in A1 notation
+     * @type {string|undefined}
+     */
+    this.range = opts.range ?? undefined;
     /**
      * The value of the merged cell
      * @type {CellValue}
@@ -68,7 +77,7 @@ class MergedCell {
 
     /**
      * The format of the cell
-     * @type {Format}
+     * @type {FormatValue}
      */
     this.format = opts.format;
 
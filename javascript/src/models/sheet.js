@@ -7,6 +7,9 @@ const Link = require('./link');
 const Formula = require('./formula');
 const { Table } = require('./table');
 const { ConditionalFormat } = require('./conditional_format');
+const { Border } = require('./border');
+
+/** @typedef {import('./format').FormatValue} FormatValue */
 
 /**
  * @typedef {Object} SizeConfig
@@ -17,7 +20,7 @@ const { ConditionalFormat } = require('./conditional_format');
 /**
  * @typedef {Object} RowCellConfig
  * @property {number} index - The index of the row/column, 0-based
- * @property {Format} [format] - The format of the cell (will be overwritten by the cell format)
+ * @property {FormatValue} [format] - The format of the cell (will be overwritten by the cell format)
  * @property {SizeConfig} [size] - The height/width of the row/column
  * @property {boolean} [hidden] - Whether the row is hidden
  */
@@ -29,82 +32,207 @@ const { ConditionalFormat } = require('./conditional_format');
  * @property {number} lastRow - The last row of the range
  * @property {number} firstColumn - The first column of the range
  * @property {number} lastColumn - The last column of the range
- * @property {ConditionalFormat} format - The format of the range
+ * @property {ConditionalFormat|number} format - The format of the range, or the id of a conditional format added to the workbook
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ * @property {string} [table] - The name of a table of the sheet, to format one of its columns instead of a range
+ * @property {string} [column] - The header of the table column
+ * @property {boolean} [includeTotalRow] - Whether the total row of the table is formatted too
+ * @property {number} [priority] - The order the format is evaluated in, lowest first
  *
  */
 class ConditionalFormatSheetValue {
   /**
-   * @param {number} firstRow - The first row of the range
-   * @param {number} lastRow - The last row of the range
-   * @param {number} firstColumn - The first column of the range
-   * @param {number} lastColumn - The last column of the range
-   * @param {ConditionalFormat} format - The format of the range
+   * @param {number|undefined} firstRow - The first row of the range
+   * @param {number|undefined} lastRow - The last row of the range
+   * @param {number|undefined} firstColumn - The first column of the range
+   * @param {number|undefined} lastColumn - The last column of the range
+   * @param {ConditionalFormat|number} format - The format of the range, or the id of a conditional format added to the workbook
+   * @param {string} [range] - The range in A1 notation, instead of the indexes
+   * @param {{table: string, column: string, includeTotalRow?: boolean}} [tableColumn] - A table column, instead of a range
+   * @param {number} [priority] - The order the format is evaluated in, lowest first
    */
-  constructor(firstRow, lastRow, firstColumn, lastColumn, format) {
+  constructor(firstRow, lastRow, firstColumn, lastColumn, format, range, tableColumn, priority) {
     /**
      * The first row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstRow = firstRow;
     /**
      * The last row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastRow = lastRow;
     /**
      * The first column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstColumn = firstColumn;
     /**
      * The last column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastColumn = lastColumn;
     /**
-     * The format of the range
-     * @type {ConditionalFormat}
+     * The format of the range, or the id of a conditional format added to the workbook
+     * @type {ConditionalFormat|number}
      */
     this.format = format;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    this.range = range;
+    /**
+     * The name of the table whose column is formatted
+     * @type {string|undefined}
+     */
+    this.table = tableColumn?.table;
+    /**
+     * The header of the formatted table column
+     * @type {string|undefined}
+     */
+    this.column = tableColumn?.column;
+    /**
+     * Whether the total row of the table is formatted too
+     * @type {boolean|undefined}
+     */
+    this.includeTotalRow = tableColumn?.includeTotalRow;
+    /**
+     * The order the format is evaluated in, lowest first
+     * @type {number|undefined}
+     */
+    this.priority = priority;
   }
 }
 
 /**
- * @class TableSheetValue
- * @classdesc Represents the values of a table sheet.
+ * @class ConditionalFormatGroupSheetValue
+ * @classdesc Represents an ordered list of conditional formats over the same cells.
  * @property {number} firstRow - The first row of the range
  * @property {number} lastRow - The last row of the range
  * @property {number} firstColumn - The first column of the range
  * @property {number} lastColumn - The last column of the range
+ * @property {(ConditionalFormat|number)[]} rules - The rules, in the order they are evaluated
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ * @property {number} [priority] - The order the group is evaluated in, lowest first
+ */
+class ConditionalFormatGroupSheetValue {
+  /**
+   * @param {Object} opts - The options for the group
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstColumn] - The first column of the range
+   * @param {number} [opts.lastColumn] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation, instead of the indexes
+   * @param {string} [opts.multiRange] - Several ranges separated by spaces ("B3:D6 I3:K6"), instead of the range
+   * @param {(ConditionalFormat|number)[]} opts.rules - The rules, in the order they are evaluated,
+   * or the ids of conditional formats added to the workbook
+   * @param {boolean} [opts.stopIfTrue=false] - Whether the first rule that is true stops the ones after it,
+   * for the rules that don't set `stopIfTrue` themselves
+   * @param {number} [opts.priority] - The order the group is evaluated in, lowest first
+   * @throws {Error} - The group has no rules
+   */
+  constructor(opts) {
+    const { firstRow, lastRow, firstColumn, lastColumn, multiRange, stopIfTrue } = opts;
+    if (!opts.rules || opts.rules.length === 0) {
+      throw new Error('A conditional format group needs at least one rule');
+    }
+    /**
+     * The first row of the range
+     * @type {number|undefined}
+     */
+    this.firstRow = firstRow;
+    /**
+     * The last row of the range
+     * @type {number|undefined}
+     */
+    this.lastRow = lastRow;
+    /**
+     * The first column of the range
+     * @type {number|undefined}
+     */
+    this.firstColumn = firstColumn;
+    /**
+     * The last column of the range
+     * @type {number|undefined}
+     */
+    this.lastColumn = lastColumn;
+    /**
+     * The range in A1 notation, the first of the multi range when only that is given
+     * @type {string|undefined}
+     */
+    this.range = opts.range ?? (firstRow === undefined ? multiRange?.trim().split(/\s+/)[0] : undefined);
+    /**
+     * The rules, in the order they are evaluated. The rules the group settings
+     * change are copies, so the formats it was given stay as they are.
+     * @type {(ConditionalFormat|number)[]}
+     */
+    this.rules = opts.rules.map((rule) => {
+      if (typeof rule === 'number') {
+        return rule;
+      }
+      const setMultiRange = multiRange !== undefined && rule.multiRange !== multiRange;
+      const setStopIfTrue = stopIfTrue && rule.stopIfTrue === undefined;
+      if (!setMultiRange && !setStopIfTrue) {
+        return rule;
+      }
+      const copy = rule.clone();
+      if (setMultiRange) {
+        copy.multiRange = multiRange;
+      }
+      if (setStopIfTrue) {
+        copy.stopIfTrue = true;
+      }
+      return copy;
+    });
+    /**
+     * The order the group is evaluated in, lowest first
+     * @type {number|undefined}
+     */
+    this.priority = opts.priority;
+  }
+}
+
+/**
+ * @class TableSheetValue
+ * @classdesc Represents the values of a table sheet.
+ * @property {number} firstRow - The first row of the range
+ * @property {number} [lastRow] - The last row of the range (computed when `data` is set)
+ * @property {number} firstColumn - The first column of the range
+ * @property {number} [lastColumn] - The last column of the range (computed when `data` is set)
  * @property {Table} table - The table of the range
+ * @property {Object[]} [data] - The records written as the table rows
+ * @property {string} [range] - The range in A1 notation, or its top left cell when `data` is set
  */
 class TableSheetValue {
   /**
-   * @param {number} firstRow - The first row of the range
-   * @param {number} lastRow - The last row of the range
-   * @param {number} firstColumn - The first column of the range
-   * @param {number} lastColumn - The last column of the range
+   * @param {number|undefined} firstRow - The first row of the range
+   * @param {number|undefined} lastRow - The last row of the range
+   * @param {number|undefined} firstColumn - The first column of the range
+   * @param {number|undefined} lastColumn - The last column of the range
    * @param {Table} table - The table of the range
+   * @param {Object[]} [data] - The records written as the table rows
+   * @param {string} [range] - The range in A1 notation, or its top left cell when `data` is set
    */
-  constructor(firstRow, lastRow, firstColumn, lastColumn, table) {
+  constructor(firstRow, lastRow, firstColumn, lastColumn, table, data, range) {
     /**
      * The first row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstRow = firstRow;
     /**
      * The last row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastRow = lastRow;
     /**
      * The first column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstColumn = firstColumn;
     /**
      * The last column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastColumn = lastColumn;
     /**
@@ -112,6 +240,16 @@ class TableSheetValue {
      * @type {Table}
      */
     this.table = table;
+    /**
+     * The records written as the table rows
+     * @type {Object[]|undefined}
+     */
+    this.data = data;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    this.range = range;
   }
 }
 
@@ -123,38 +261,40 @@ class TableSheetValue {
  * @property {number} firstColumn - The first column of the range
  * @property {number} lastColumn - The last column of the range
  * @property {Formula} formula - The formula of the range
- * @property {Format} [format] - The format of the range
+ * @property {FormatValue} [format] - The format of the range
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
  */
 class ArrayFormulaSheetValue {
   /**
    * @param {Object} opts - The options for the array formula
-   * @param {number} opts.firstRow - The first row of the range
-   * @param {number} opts.lastRow - The last row of the range
-   * @param {number} opts.firstColumn - The first column of the range
-   * @param {number} opts.lastColumn - The last column of the range
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstColumn] - The first column of the range
+   * @param {number} [opts.lastColumn] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("B2:B10"), instead of the indexes
    * @param {Formula} opts.formula - The formula of the range
-   * @param {Format} [opts.format] - The format of the range
+   * @param {FormatValue} [opts.format] - The format of the range
    */
   constructor(opts) {
     const { firstRow, lastRow, firstColumn, lastColumn, formula } = opts;
     /**
      * The first row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstRow = firstRow;
     /**
      * The last row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastRow = lastRow;
     /**
      * The first column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.firstColumn = firstColumn;
     /**
      * The last column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
     this.lastColumn = lastColumn;
     /**
@@ -165,9 +305,355 @@ class ArrayFormulaSheetValue {
 
     /**
      * The format of the range
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      */
     this.format = opts.format ?? undefined;
+
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    this.range = opts.range ?? undefined;
+  }
+}
+
+/**
+ * What to do when the same cell is written more than once:
+ * - `error`: saving throws an error naming the cell
+ * - `lastWins`: the last write is kept, the default
+ * - `firstWins`: the first write is kept
+ * - `mergeFormat`: the last value is kept, with its format layered over the earlier formats
+ * @typedef {("error"|"lastWins"|"firstWins"|"mergeFormat")} DuplicateCellPolicy
+ */
+
+/**
+ * @typedef {(Float64Array|Int32Array|number[]|string[]|Date[])} ColumnValues
+ */
+
+/**
+ * @class ColumnBlockSheetValue
+ * @classdesc Represents a block of columns written from a starting cell.
+ * @property {number} [row] - The first row of the block (unset when given by `cell`)
+ * @property {number} [col] - The first column of the block (unset when given by `cell`)
+ * @property {string} [cell] - The top left cell of the block in A1 notation ("B2"), in place of `row` and `col`
+ * @property {ColumnValues[]} columns - The values of each column
+ * @property {Array.<FormatValue|undefined>} [formats] - The format of each column
+ */
+class ColumnBlockSheetValue {
+  /**
+   * @param {Object} opts - The options for the column block
+   * @param {number} [opts.row] - The first row of the block
+   * @param {number} [opts.col] - The first column of the block
+   * @param {string} [opts.cell] - The top left cell of the block in A1 notation ("B2"), in place of `row` and `col`
+   * @param {ColumnValues[]} opts.columns - The values of each column
+   * @param {Array.<FormatValue|undefined>} [opts.formats] - The format of each column
+   */
+  constructor(opts) {
+    /**
+     * The first row of the block
+     * @type {number|undefined}
+     */
+    this.row = opts.row;
+    /**
+     * The first column of the block
+     * @type {number|undefined}
+     */
+    this.col = opts.col;
+    /**
+     * The top left cell of the block in A1 notation
+     * @type {string|undefined}
+     */
+    this.cell = opts.cell;
+    /**
+     * The values of each column
+     * @type {ColumnValues[]}
+     */
+    this.columns = opts.columns;
+    /**
+     * The format of each column
+     * @type {Array.<FormatValue|undefined>|undefined}
+     */
+    this.formats = opts.formats ?? undefined;
+  }
+}
+
+/**
+ * @class RangeFormatSheetValue
+ * @classdesc Represents a format applied to a rectangular range of cells.
+ * @property {number} firstRow - The first row of the range
+ * @property {number} lastRow - The last row of the range
+ * @property {number} firstCol - The first column of the range
+ * @property {number} lastCol - The last column of the range
+ * @property {FormatValue} [format] - The format of every cell in the range
+ * @property {Border} [outerBorder] - The border drawn around the range
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ */
+class RangeFormatSheetValue {
+  /**
+   * @param {Object} opts - The options for the range format
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstCol] - The first column of the range
+   * @param {number} [opts.lastCol] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("B2:F40"), instead of the indexes
+   * @param {FormatValue} [opts.format] - The format of every cell in the range
+   * @param {Border} [opts.outerBorder] - The border drawn around the range
+   */
+  constructor(opts) {
+    /**
+     * The first row of the range
+     * @type {number|undefined}
+     */
+    this.firstRow = opts.firstRow;
+    /**
+     * The last row of the range
+     * @type {number|undefined}
+     */
+    this.lastRow = opts.lastRow;
+    /**
+     * The first column of the range
+     * @type {number|undefined}
+     */
+    this.firstCol = opts.firstCol;
+    /**
+     * The last column of the range
+     * @type {number|undefined}
+     */
+    this.lastCol = opts.lastCol;
+    /**
+     * The format of every cell in the range
+     * @type {FormatValue|undefined}
+     */
+    this.format = opts.format ?? undefined;
+    /**
+     * The border drawn around the range
+     * @type {Border|undefined}
+     */
+    this.outerBorder = opts.outerBorder ?? undefined;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    this.range = opts.range ?? undefined;
+  }
+}
+
+/**
+ * @class FillFormulaSheetValue
+ * @classdesc Represents a formula filled over a range, like dragging Excel's fill handle.
+ * @property {number} firstRow - The first row of the range
+ * @property {number} lastRow - The last row of the range
+ * @property {number} firstCol - The first column of the range
+ * @property {number} lastCol - The last column of the range
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ * @property {Formula} formula - The formula as written in the anchor cell
+ * @property {string} [anchor] - The cell the formula is written for, in A1 notation
+ * @property {number} [anchorRow] - The row the formula is written for
+ * @property {number} [anchorCol] - The column the formula is written for
+ * @property {FormatValue} [format] - The format of every cell in the range
+ */
+class FillFormulaSheetValue {
+  /**
+   * @param {Object} opts - The options for the fill formula
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstCol] - The first column of the range
+   * @param {number} [opts.lastCol] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("D2:D100"), instead of the indexes
+   * @param {Formula} opts.formula - The formula as written in the anchor cell
+   * @param {string} [opts.anchor] - The cell the formula is written for, in A1 notation
+   * @param {number} [opts.anchorRow] - The row the formula is written for
+   * @param {number} [opts.anchorCol] - The column the formula is written for
+   * @param {FormatValue} [opts.format] - The format of every cell in the range
+   */
+  constructor(opts) {
+    /**
+     * The first row of the range
+     * @type {number|undefined}
+     */
+    this.firstRow = opts.firstRow;
+    /**
+     * The last row of the range
+     * @type {number|undefined}
+     */
+    this.lastRow = opts.lastRow;
+    /**
+     * The first column of the range
+     * @type {number|undefined}
+     */
+    this.firstCol = opts.firstCol;
+    /**
+     * The last column of the range
+     * @type {number|undefined}
+     */
+    this.lastCol = opts.lastCol;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    this.range = opts.range ?? undefined;
+    /**
+     * The formula as written in the anchor cell
+     * @type {Formula}
+     */
+    this.formula = opts.formula;
+    /**
+     * The cell the formula is written for, in A1 notation
+     * @type {string|undefined}
+     */
+    this.anchor = opts.anchor ?? undefined;
+    /**
+     * The row the formula is written for
+     * @type {number|undefined}
+     */
+    this.anchorRow = opts.anchorRow ?? undefined;
+    /**
+     * The column the formula is written for
+     * @type {number|undefined}
+     */
+    this.anchorCol = opts.anchorCol ?? undefined;
+    /**
+     * The format of every cell in the range
+     * @type {FormatValue|undefined}
+     */
+    this.format = opts.format ?? undefined;
+  }
+}
+
+/**
+ * The type a CSV column is written as. `auto` recognizes numbers, booleans, ISO dates
+ * (`2024-03-15`, `2024-03-15T09:30:00`) and formulas (`=A1*2`) that parse, and writes the rest as strings.
+ * Fields of a `formula` column must be valid formulas.
+ * @typedef {'auto'|'string'|'number'|'boolean'|'date'|'formula'} CsvColumnType
+ */
+
+/**
+ * @class CsvImportSheetValue
+ * @classdesc Represents delimited text written into the sheet from a cell on, one record per row.
+ * @property {number|undefined} row - The first row
+ * @property {number|undefined} col - The first column
+ * @property {string|undefined} cell - The top left cell in A1 notation, in place of `row` and `col`
+ * @property {Buffer|string} source - The text as a Buffer, or the path of its file
+ * @property {string} delimiter - The field delimiter
+ * @property {string} quote - The quote of fields, empty to read quotes as text
+ * @property {'utf8'|'utf16le'|'utf16be'|'latin1'} encoding - The encoding of the text
+ * @property {boolean} header - Whether the first record is a header row
+ * @property {boolean} detectTypes - Whether the types of columns without a hint are detected
+ * @property {CsvColumnType[]|Record<string, CsvColumnType>|undefined} types - The type of each column
+ * @property {Array.<FormatValue|undefined>|Record<string, FormatValue>|undefined} formats - The format of each column
+ * @property {FormatValue|undefined} headerFormat - The format of the header row
+ * @property {FormatValue} dateFormat - The format of dates without a time or a column format
+ * @property {FormatValue} dateTimeFormat - The format of dates with a time and without a column format
+ * @property {Table|undefined} table - The table created over the imported range
+ * @property {number|undefined} cellIndex - How many cells of the sheet were written before the import
+ */
+class CsvImportSheetValue {
+  /**
+   * @param {Object} opts - The options for the import
+   * @param {number} [opts.row] - The first row, 0 by default
+   * @param {number} [opts.col] - The first column, 0 by default
+   * @param {string} [opts.cell] - The top left cell in A1 notation ("B2"), in place of `row` and `col`
+   * @param {Buffer|string} opts.source - The text as a Buffer, or the path of its file.
+   * A string is always a path: pass text held in a string as `Buffer.from(text)`
+   * @param {string} [opts.delimiter] - The field delimiter, `,` by default and `\t` for TSV
+   * @param {string} [opts.quote] - The quote of fields, `"` by default, empty to read quotes as text
+   * @param {'utf8'|'utf16le'|'utf16be'|'latin1'} [opts.encoding] - The encoding of the text, `utf8` by default.
+   * A byte order mark wins over it
+   * @param {boolean} [opts.header] - Whether the first record is a header row, true by default
+   * @param {boolean} [opts.detectTypes] - Whether the types of columns without a hint are detected,
+   * true by default. Without it they are written as strings
+   * @param {CsvColumnType[]|Record<string, CsvColumnType>} [opts.types] - The type of each column,
+   * in their order or by header
+   * @param {Array.<FormatValue|undefined>|Record<string, FormatValue>} [opts.formats] - The format of
+   * each column, in their order or by header
+   * @param {FormatValue} [opts.headerFormat] - The format of the header row
+   * @param {FormatValue} [opts.dateFormat] - The format of dates without a time, `yyyy-mm-dd` by default
+   * @param {FormatValue} [opts.dateTimeFormat] - The format of dates with a time, `yyyy-mm-dd hh:mm:ss` by default
+   * @param {Table} [opts.table] - The table created over the imported range. Without columns, it takes
+   * the headers of the text
+   */
+  constructor(opts) {
+    /**
+     * The first row
+     * @type {number|undefined}
+     */
+    this.row = opts.cell === undefined ? opts.row ?? 0 : undefined;
+    /**
+     * The first column
+     * @type {number|undefined}
+     */
+    this.col = opts.cell === undefined ? opts.col ?? 0 : undefined;
+    /**
+     * The top left cell in A1 notation
+     * @type {string|undefined}
+     */
+    this.cell = opts.cell;
+    /**
+     * The text as a Buffer, or the path of its file
+     * @type {Buffer|string}
+     */
+    this.source = opts.source;
+    /**
+     * The field delimiter
+     * @type {string}
+     */
+    this.delimiter = opts.delimiter ?? ',';
+    /**
+     * The quote of fields, empty to read quotes as text
+     * @type {string}
+     */
+    this.quote = opts.quote ?? '"';
+    /**
+     * The encoding of the text
+     * @type {'utf8'|'utf16le'|'utf16be'|'latin1'}
+     */
+    this.encoding = opts.encoding ?? 'utf8';
+    /**
+     * Whether the first record is a header row
+     * @type {boolean}
+     */
+    this.header = opts.header ?? true;
+    /**
+     * Whether the types of columns without a hint are detected
+     * @type {boolean}
+     */
+    this.detectTypes = opts.detectTypes ?? true;
+    /**
+     * The type of each column
+     * @type {CsvColumnType[]|Record<string, CsvColumnType>|undefined}
+     */
+    this.types = opts.types ?? undefined;
+    /**
+     * The format of each column
+     * @type {Array.<FormatValue|undefined>|Record<string, FormatValue>|undefined}
+     */
+    this.formats = opts.formats ?? undefined;
+    /**
+     * The format of the header row
+     * @type {FormatValue|undefined}
+     */
+    this.headerFormat = opts.headerFormat ?? undefined;
+    /**
+     * The format of dates without a time or a column format
+     * @type {FormatValue}
+     */
+    this.dateFormat = opts.dateFormat ?? new Format({ numFmt: 'yyyy-mm-dd' });
+    /**
+     * The format of dates with a time and without a column format
+     * @type {FormatValue}
+     */
+    this.dateTimeFormat = opts.dateTimeFormat ?? new Format({ numFmt: 'yyyy-mm-dd hh:mm:ss' });
+    /**
+     * The table created over the imported range
+     * @type {Table|undefined}
+     */
+    this.table = opts.table ?? undefined;
+    /**
+     * How many cells of the sheet were written before the import, which places
+     * the imported cells among them for the `duplicateCellPolicy`
+     * @type {number|undefined}
+     */
+    this.cellIndex = undefined;
   }
 }
 
@@ -177,11 +663,17 @@ class ArrayFormulaSheetValue {
  * @classdesc A sheet is a collection of cells.
  * @property {string} name - The name of the sheet
  * @property {Array.<Cell|MergedCell>} cells - The cells in the sheet
- * @property {ConditionalFormatSheetValue[]} conditionalFormats - The conditional format values of the sheet
+ * @property {(ConditionalFormatSheetValue|ConditionalFormatGroupSheetValue)[]} conditionalFormats - The conditional format values of the sheet
  * @property {ArrayFormulaSheetValue[]} arrayFormulas - The array formulas of the sheet
  * @property {TableSheetValue[]} tables - The tables of the sheet
  * @property {RowCellConfig[]} rowConfigs - The rows of the sheet
  * @property {RowCellConfig[]} columnConfigs - The columns of the sheet
+ * @property {ColumnBlockSheetValue[]} columnBlocks - The column blocks of the sheet
+ * @property {RangeFormatSheetValue[]} rangeFormats - The range formats of the sheet
+ * @property {FillFormulaSheetValue[]} fillFormulas - The fill formulas of the sheet
+ * @property {CsvImportSheetValue[]} csvImports - The CSV imports of the sheet
+ * @property {boolean} composeFormats - Whether cell formats are layered over the row, column and table column formats
+ * @property {DuplicateCellPolicy} duplicateCellPolicy - What to do when the same cell is written more than once
  */
 class Sheet {
   /**
@@ -231,6 +723,112 @@ class Sheet {
      * @default []
      * */
     this.tables = [];
+
+    /**
+     * The column blocks of the sheet
+     * @type {ColumnBlockSheetValue[]}
+     * @default []
+     * */
+    this.columnBlocks = [];
+
+    /**
+     * The range formats of the sheet
+     * @type {RangeFormatSheetValue[]}
+     * @default []
+     * */
+    this.rangeFormats = [];
+
+    /**
+     * The fill formulas of the sheet
+     * @type {FillFormulaSheetValue[]}
+     * @default []
+     * */
+    this.fillFormulas = [];
+
+    /**
+     * The CSV imports of the sheet
+     * @type {CsvImportSheetValue[]}
+     * @default []
+     * */
+    this.csvImports = [];
+
+    /**
+     * Whether cell formats are layered over the row, column and table column formats
+     * @type {boolean}
+     * @default false
+     * */
+    this.composeFormats = false;
+
+    /**
+     * What to do when the same cell is written more than once
+     * @type {DuplicateCellPolicy}
+     * @default 'lastWins'
+     * */
+    this.duplicateCellPolicy = 'lastWins';
+  }
+
+  /**
+   * Layers each cell's format over the formats of its column, row and table column.
+   * Properties set on the later layers win, in this order: column, row, table column, cell.
+   * A bold cell in a currency column is then written bold and as currency.
+   * @param {boolean} [compose=true] - Whether to compose the formats
+   * @returns {void}
+   */
+  setComposeFormats(compose = true) {
+    this.composeFormats = compose;
+  }
+
+  /**
+   * Sets what to do when the same cell is written more than once.
+   * By default the last write is kept. With `error` saving throws an error naming the cell instead.
+   * With `mergeFormat` a module can write the values and another one the formats of the same cells.
+   * @param {DuplicateCellPolicy} policy - The policy for duplicate writes
+   * @returns {void}
+   */
+  setDuplicateCellPolicy(policy) {
+    this.duplicateCellPolicy = policy;
+  }
+
+  /**
+   * Formats a rectangular range without writing a cell object per cell.
+   * The range format is layered under the format of each cell written inside it,
+   * and later ranges win over earlier ones. Empty cells are written as formatted blanks.
+   * `outerBorder` draws a box around the range, only on the outer edges of the edge and corner cells.
+   * Whole row and column ranges ("C:C", "3:3") only format the cells written inside them.
+   * @param {Object} opts - The options for the range format
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstCol] - The first column of the range
+   * @param {number} [opts.lastCol] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("B2:F40"), instead of the indexes
+   * @param {FormatValue} [opts.format] - The format of every cell in the range
+   * @param {Border|{style: import('./border').BorderType, color?: import('./color').ColorValue}} [opts.outerBorder] - The border drawn around the range
+   * @returns {void}
+   * @throws {Error} - Invalid range
+   * @throws {Error} - Neither a format nor an outer border is given
+   */
+  formatRange(opts) {
+    const { firstRow, lastRow, firstCol, lastCol, range, format } = opts;
+    if (firstRow < 0 || firstCol < 0 || firstRow > lastRow || firstCol > lastCol) {
+      throw new Error('Invalid range');
+    }
+    if (!format && !opts.outerBorder) {
+      throw new Error('A range format needs a format or an outer border');
+    }
+    let outerBorder = opts.outerBorder;
+    if (outerBorder && !(outerBorder instanceof Border)) {
+      outerBorder = new Border(outerBorder.style, outerBorder.color);
+    }
+    const rangeFormat = new RangeFormatSheetValue({
+      firstRow,
+      lastRow,
+      firstCol,
+      lastCol,
+      range,
+      format,
+      outerBorder,
+    });
+    this.rangeFormats.push(rangeFormat);
   }
 
   /**
@@ -255,52 +853,125 @@ class Sheet {
   /**
    * Adds a conditional format to the sheet
    * @param {Object} opts - The options for the conditional format
-   * @param {number} opts.firstRow - The first row of the range
-   * @param {number} opts.lastRow - The last row of the range
-   * @param {number} opts.firstColumn - The first column of the range
-   * @param {number} opts.lastColumn - The last column of the range
-   * @param {ConditionalFormat} opts.format - The format of the range
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstColumn] - The first column of the range
+   * @param {number} [opts.lastColumn] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("B2:B40"), instead of the indexes
+   * @param {string} [opts.table] - The name of a table of this sheet, to format the data of one of its columns
+   * instead of a range. The cells are found when the workbook is written, so they follow the table as it grows.
+   * @param {string} [opts.column] - The header of the table column to format
+   * @param {boolean} [opts.includeTotalRow=false] - Whether the total row of the table is formatted too
+   * @param {ConditionalFormat|number} opts.format - The format of the range, or the id of a conditional format
+   * added to the workbook
+   * @param {number} [opts.priority] - The order the format is evaluated in, lowest first. Formats with a priority
+   * come before the ones without, which keep the order they were added in. Two formats can't share a priority.
    * @returns {void}
+   * @throws {Error} - `table` is set without `column`
    */
   addConditionalFormat(opts) {
-    const { firstRow, lastRow, firstColumn, lastColumn, format } = opts;
+    const { firstRow, lastRow, firstColumn, lastColumn, format, range, table, column, includeTotalRow, priority } =
+      opts;
+    if (table !== undefined && column === undefined) {
+      throw new Error('A table conditional format needs a column');
+    }
+    const tableColumn = table === undefined ? undefined : { table, column, includeTotalRow };
     const conditionalSheetValue = new ConditionalFormatSheetValue(
       firstRow,
       lastRow,
       firstColumn,
       lastColumn,
       format,
+      range,
+      tableColumn,
+      priority,
     );
     this.conditionalFormats.push(conditionalSheetValue);
   }
 
+  /**
+   * Adds an ordered list of conditional formats over the same range, evaluated one after the other.
+   * The group takes one place in the priority order of the sheet.
+   * @param {Object} opts - The options for the group
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstColumn] - The first column of the range
+   * @param {number} [opts.lastColumn] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("B2:B40"), instead of the indexes
+   * @param {string} [opts.multiRange] - Several ranges separated by spaces ("B3:D6 I3:K6"), instead of the range
+   * @param {(ConditionalFormat|number)[]} opts.rules - The rules, in the order they are evaluated,
+   * or the ids of conditional formats added to the workbook
+   * @param {boolean} [opts.stopIfTrue=false] - Whether the first rule that is true stops the ones after it,
+   * for the rules that don't set `stopIfTrue` themselves
+   * @param {number} [opts.priority] - The order the group is evaluated in, see `addConditionalFormat`
+   * @returns {void}
+   * @throws {Error} - The group has no rules
+   */
+  addConditionalFormatGroup(opts) {
+    this.conditionalFormats.push(new ConditionalFormatGroupSheetValue(opts));
+  }
+
   /**
    * @param {Object} opts - The options for the array formula
-   * @param {number} opts.firstRow - The first row of the range
-   * @param {number} opts.lastRow - The last row of the range
-   * @param {number} opts.firstColumn - The first column of the range
-   * @param {number} opts.lastColumn - The last column of the range
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstColumn] - The first column of the range
+   * @param {number} [opts.lastColumn] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("B2:B10"), instead of the indexes
    * @param {Formula} opts.formula - The formula of the range
-   * @param {Format} [opts.format] - The format of the range
+   * @param {FormatValue} [opts.format] - The format of the range
    */
   addArrayFormula(opts) {
     const arrayFormula = new ArrayFormulaSheetValue(opts);
     this.arrayFormulas.push(arrayFormula);
   }
 
+  /**
+   * Fills a formula over a range, the way Excel's fill handle does.
+   * The formula is written as it would be in the anchor cell, the first cell of the range by default.
+   * Every other cell gets it with the relative references moved by its distance from the anchor,
+   * while the `$` anchored columns and rows stay fixed. References moved off the sheet become `#REF!`.
+   * @param {Object} opts - The options for the fill formula
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstCol] - The first column of the range
+   * @param {number} [opts.lastCol] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("D2:D100"), instead of the indexes
+   * @param {Formula|string} opts.formula - The formula as written in the anchor cell
+   * @param {string} [opts.anchor] - The cell the formula is written for, in A1 notation
+   * @param {number} [opts.anchorRow] - The row the formula is written for
+   * @param {number} [opts.anchorCol] - The column the formula is written for
+   * @param {FormatValue} [opts.format] - The format of every cell in the range
+   * @returns {void}
+   * @throws {Error} - Invalid range
+   */
+  fillFormula(opts) {
+    const { firstRow, lastRow, firstCol, lastCol } = opts;
+    if (firstRow < 0 || firstCol < 0 || firstRow > lastRow || firstCol > lastCol) {
+      throw new Error('Invalid range');
+    }
+    let formula = opts.formula;
+    if (typeof formula === 'string') {
+      formula = new Formula({ formula });
+    }
+    const fillFormula = new FillFormulaSheetValue({ ...opts, formula });
+    this.fillFormulas.push(fillFormula);
+  }
+
   /**
    * Adds a table to the sheet
    * @param {Object} opts - The options for the table
-   * @param {number} opts.firstRow - The first row of the range
-   * @param {number} opts.lastRow - The last row of the range
-   * @param {number} opts.firstColumn - The first column of the range
-   * @param {number} opts.lastColumn - The last column of the range
+   * @param {number} [opts.firstRow] - The first row of the range
+   * @param {number} [opts.lastRow] - The last row of the range
+   * @param {number} [opts.firstColumn] - The first column of the range
+   * @param {number} [opts.lastColumn] - The last column of the range
+   * @param {string} [opts.range] - The range in A1 notation ("A1:D20"), instead of the indexes
    * @param {Table} opts.table - The table of the range
    * @throws {Error} - Invalid table range
    * @returns {void}
    */
   addTable(opts) {
-    const { firstRow, lastRow, firstColumn, lastColumn, table } = opts;
+    const { firstRow, lastRow, firstColumn, lastColumn, range, table } = opts;
     if (firstRow > lastRow || firstColumn > lastColumn) {
       throw new Error('Invalid table range');
     }
@@ -310,23 +981,134 @@ class Sheet {
       firstColumn,
       lastColumn,
       table,
+      undefined,
+      range,
     );
     this.tables.push(tableSheetValue);
   }
 
   /**
-   * Writes a cell to the sheet
+   * Adds a table filled from an array of records.
+   * The headers come from the table columns (`key`, or `header` when no key is set),
+   * or from the record keys when the table has no columns.
+   * The last row and column are computed from the data, including the total row.
+   * Values of columns without a `cellType` are written by their type: numbers, strings,
+   * booleans and Dates. Objects and arrays need a column `cellType`.
+   * @param {Object} opts - The options for the table
+   * @param {number} [opts.firstRow] - The first row of the table (the header row)
+   * @param {number} [opts.firstColumn] - The first column of the table
+   * @param {string} [opts.range] - The top left cell of the table in A1 notation ("B2"), instead of the indexes
+   * @param {Object[]} opts.data - The records written as the table rows
+   * @param {Table} [opts.table] - The table definition
+   * @throws {Error} - Invalid table position
+   * @returns {void}
+   */
+  addTableFromData(opts) {
+    const { firstRow, firstColumn, range, data } = opts;
+    if (firstRow < 0 || firstColumn < 0) {
+      throw new Error('Invalid table position');
+    }
+    const table = opts.table ?? new Table({});
+    const tableSheetValue = new TableSheetValue(
+      firstRow,
+      undefined,
+      firstColumn,
+      undefined,
+      table,
+      data,
+      range,
+    );
+    this.tables.push(tableSheetValue);
+  }
+
+  /**
+   * Writes a block of columns to the sheet, starting at the given cell.
+   * Float64Array and Int32Array columns are read directly from their buffers,
+   * which is much faster than writing one cell at a time. The values of an
+   * array column must all be numbers, strings or Dates, and numbers must be finite.
+   * @param {Object} opts - The options for the column block
+   * @param {number} [opts.row] - The first row of the block
+   * @param {number} [opts.col] - The first column of the block
+   * @param {string} [opts.cell] - The top left cell of the block in A1 notation ("B2"), in place of `row` and `col`
+   * @param {ColumnValues[]} opts.columns - The values of each column
+   * @param {Array.<FormatValue|undefined>} [opts.formats] - The format of each column
+   * @returns {void}
+   * @throws {Error} - col > 16_383 or col < 0
+   * @throws {Error} - row > 1_048_575 or row < 0
+   */
+  writeColumns(opts) {
+    const { row, col, cell } = opts;
+    if (cell === undefined) {
+      if (col > 16_383 || col < 0) {
+        throw new Error('Invalid column index');
+      }
+      if (row > 1_048_575 || row < 0) {
+        throw new Error('Invalid row index');
+      }
+    }
+    const columnBlock = new ColumnBlockSheetValue(opts);
+    this.columnBlocks.push(columnBlock);
+  }
+
+  /**
+   * Writes delimited text, like a CSV or TSV file, into the sheet from the given cell on.
+   * The text is read and parsed when the workbook is saved, and written one record per row.
+   * Empty fields are left blank, and fields that don't match their column type are written as strings.
+   * @param {Object} opts - The options for the import
+   * @param {number} [opts.row] - The first row, 0 by default
+   * @param {number} [opts.col] - The first column, 0 by default
+   * @param {string} [opts.cell] - The top left cell in A1 notation ("B2"), in place of `row` and `col`
+   * @param {Buffer|string} opts.source - The text as a Buffer, or the path of its file.
+   * A string is always a path: pass text held in a string as `Buffer.from(text)`
+   * @param {string} [opts.delimiter] - The field delimiter, `,` by default and `\t` for TSV
+   * @param {string} [opts.quote] - The quote of fields, `"` by default, empty to read quotes as text
+   * @param {'utf8'|'utf16le'|'utf16be'|'latin1'} [opts.encoding] - The encoding of the text, `utf8` by default
+   * @param {boolean} [opts.header] - Whether the first record is a header row, true by default
+   * @param {boolean} [opts.detectTypes] - Whether the types of columns without a hint are detected, true by default
+   * @param {CsvColumnType[]|Record<string, CsvColumnType>} [opts.types] - The type of each column, in their order or by header
+   * @param {Array.<FormatValue|undefined>|Record<string, FormatValue>} [opts.formats] - The format of each column, in their order or by header
+   * @param {FormatValue} [opts.headerFormat] - The format of the header row
+   * @param {FormatValue} [opts.dateFormat] - The format of dates without a time
+   * @param {FormatValue} [opts.dateTimeFormat] - The format of dates with a time
+   * @param {Table} [opts.table] - The table created over the imported range
+   * @returns {void}
+   * @throws {Error} - col > 16_383 or col < 0
+   * @throws {Error} - row > 1_048_575 or row < 0
+   */
+  importCsv(opts) {
+    const { row = 0, col = 0, cell } = opts;
+    if (cell === undefined) {
+      if (col > 16_383 || col < 0) {
+        throw new Error('Invalid column index');
+      }
+      if (row > 1_048_575 || row < 0) {
+        throw new Error('Invalid row index');
+      }
+    }
+    const csvImport = new CsvImportSheetValue(opts);
+    csvImport.cellIndex = this.cells.length;
+    this.csvImports.push(csvImport);
+  }
+
+  /**
+   * Writes a cell to the sheet.
+   * The cell can also be given in A1 notation, followed by the remaining arguments:
+   * `writeCell('B2', value, cellType, format)`
    *
-   * @param {number} row - the cell row
-   * @param {number} col - the cell col
+   * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+   * @param {number|any} col - the cell col
    * @param {string|number|Link|Date|Formula|any} value - The value of the cell.
-   * @param {("number"|"string"|"link"|"date"|"formula")} [cellType] - The type of the cell(if not provider .toString() will be used)
-   * @param {Format} [format] - The format of the cell
+   * @param {("number"|"string"|"boolean"|"link"|"date"|"formula")} [cellType] - The type of the cell(if not provider .toString() will be used)
+   * @param {FormatValue} [format] - The format of the cell
    * @returns {void}
    * @throws {Error} - col > 65_535 or col < 0
    * @throws {Error} - row > 1_048_577 or row < 0
    */
   writeCell(row, col, value, cellType, format) {
+    if (typeof row === 'string') {
+      this.cells.push(new Cell({ cell: row, value: col, cellType: value, format: cellType }));
+      return;
+    }
     if (col > 65_535 || col < 0) {
       throw new Error('Invalid column index');
     }
@@ -346,13 +1128,14 @@ class Sheet {
   /**
    * Writes a merged cell to the sheet
    * @param {Object} opts - The options for the merged cell
-   * @param {number} opts.firstRow - The first row of the merged cell
-   * @param {number} opts.lastRow - The last row of the merged cell
-   * @param {number} opts.firstCol - The first column of the merged cell
-   * @param {number} opts.lastCol - The last column of the merged cell
+   * @param {number} [opts.firstRow] - The first row of the merged cell
+   * @param {number} [opts.lastRow] - The last row of the merged cell
+   * @param {number} [opts.firstCol] - The first column of the merged cell
+   * @param {number} [opts.lastCol] - The last column of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {string|number|Link|Date|Formula|any} opts.value - The value of the merged cell.
-   * @param {Format} opts.format - The format of the merged cell
-   * @param {("number"|"string"|"link"|"date"|"formula")} [opts.cellType] - The type of the merged cell(if not provider .toString() will be used)
+   * @param {FormatValue} opts.format - The format of the merged cell
+   * @param {("number"|"string"|"boolean"|"link"|"date"|"formula")} [opts.cellType] - The type of the merged cell(if not provider .toString() will be used)
    * @returns {void}
    * @throws {Error} - firstCol > 65_535 or firstCol < 0
    * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -363,28 +1146,30 @@ class Sheet {
    * @throws {Error} - firstCol === lastCol && firstRow === lastRow
    */
   writeMergedCell(opts) {
-    const { firstRow, lastRow, firstCol, lastCol, value, cellType, format } =
+    const { firstRow, lastRow, firstCol, lastCol, range, value, cellType, format } =
       opts;
-    if (firstCol > 65_535 || firstCol < 0) {
-      throw new Error('Invalid first column index');
-    }
-    if (lastCol > 65_535 || lastCol < 0) {
-      throw new Error('Invalid last column index');
-    }
-    if (firstRow > 1_048_577 || firstRow < 0) {
-      throw new Error('Invalid first row index');
-    }
-    if (lastRow > 1_048_577 || lastRow < 0) {
-      throw new Error('Invalid last row index');
-    }
-    if (firstCol > lastCol) {
-      throw new Error('First column index cannot be greater than last column index');
-    }
-    if (firstRow > lastRow) {
-      throw new Error('First row index cannot be greater than last row index');
-    }
-    if (firstCol === lastCol && firstRow === lastRow) {
-      throw new Error('First column index and first row index cannot be equal to last column index and last row index');
+    if (range === undefined) {
+      if (firstCol > 65_535 || firstCol < 0) {
+        throw new Error('Invalid first column index');
+      }
+      if (lastCol > 65_535 || lastCol < 0) {
+        throw new Error('Invalid last column index');
+      }
+      if (firstRow > 1_048_577 || firstRow < 0) {
+        throw new Error('Invalid first row index');
+      }
+      if (lastRow > 1_048_577 || lastRow < 0) {
+        throw new Error('Invalid last row index');
+      }
+      if (firstCol > lastCol) {
+        throw new Error('First column index cannot be greater than last column index');
+      }
+      if (firstRow > lastRow) {
+        throw new Error('First row index cannot be greater than last row index');
+      }
+      if (firstCol === lastCol && firstRow === lastRow) {
+        throw new Error('First column index and first row index cannot be equal to last column index and last row index');
+      }
     }
 
     const mergedCell = new MergedCell({
@@ -392,6 +1177,7 @@ class Sheet {
       lastRow,
       firstCol,
       lastCol,
+      range,
       value,
       cellType,
       format,
@@ -399,30 +1185,35 @@ class Sheet {
     this.cells.push(mergedCell);
   }
 
-
   /**
    * writes a string value to a cell
-   * @param {number} row - the cell row
-   * @param {number} col - the cell col
+   * The cell can also be given in A1 notation, followed by the value and format: `writeString('B2', value, format)`
+   * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+   * @param {number|any} col - the cell col
    * @param {string} value - The value to write to the cell
-   * @param {Format} [format] - The format of the cell
+   * @param {FormatValue} [format] - The format of the cell
    * @returns {void}
    * @throws {Error} - col > 65_535 or col < 0
    * @throws {Error} - row > 1_048_577 or row < 0
    */
   writeString(row, col, value, format) {
+    if (typeof row === 'string') {
+      this.writeCell(row, col, 'string', value);
+      return;
+    }
     this.writeCell(row, col, value, 'string', format);
   }
 
   /**
    * writes a string value to a merged cell
    * @param {Object} opts - The options for the merged cell
-   * @param {number} opts.firstRow - The first row of the merged cell
-   * @param {number} opts.lastRow - The last row of the merged cell
-   * @param {number} opts.firstCol - The first column of the merged cell
-   * @param {number} opts.lastCol - The last column of the merged cell
+   * @param {number} [opts.firstRow] - The first row of the merged cell
+   * @param {number} [opts.lastRow] - The last row of the merged cell
+   * @param {number} [opts.firstCol] - The first column of the merged cell
+   * @param {number} [opts.lastCol] - The last column of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {string} opts.value - The value of the merged cell.
-   * @param {Format} opts.format - The format of the merged cell
+   * @param {FormatValue} opts.format - The format of the merged cell
    * @returns {void}
    * @throws {Error} - firstCol > 65_535 or firstCol < 0
    * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -433,12 +1224,13 @@ class Sheet {
    * @throws {Error} - firstCol === lastCol && firstRow === lastRow
    */
   writeMergedString(opts) {
-    const { firstRow, lastRow, firstCol, lastCol, value, format } = opts;
+    const { firstRow, lastRow, firstCol, lastCol, range, value, format } = opts;
     this.writeMergedCell({
       firstRow,
       lastRow,
       firstCol,
       lastCol,
+      range,
       value,
       cellType: 'string',
       format,
@@ -447,15 +1239,20 @@ class Sheet {
 
   /**
    * writes a number value to a cell
-   * @param {number} row - the cell row
-   * @param {number} col - the cell col
+   * The cell can also be given in A1 notation, followed by the value and format: `writeNumber('B2', value, format)`
+   * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+   * @param {number|any} col - the cell col
    * @param {number} value - The value to write to the cell
-   * @param {Format} [format] - The format of the cell
+   * @param {FormatValue} [format] - The format of the cell
    * @returns {void}
    * @throws {Error} - col > 65_535 or col < 0
    * @throws {Error} - row > 1_048_577 or row < 0
    */
   writeNumber(row, col, value, format) {
+    if (typeof row === 'string') {
+      this.writeCell(row, col, isNaN(col) ? 'string' : 'number', value);
+      return;
+    }
     if (!isNaN(value)) {
       this.writeCell(row, col, value, 'number', format);
       return;
@@ -466,12 +1263,13 @@ class Sheet {
   /**
    * writes a number value to a merged cell
    * @param {Object} opts - The options for the merged cell
-   * @param {number} opts.firstRow - The first row of the merged cell
-   * @param {number} opts.lastRow - The last row of the merged cell
-   * @param {number} opts.firstCol - The first column of the merged cell
-   * @param {number} opts.lastCol - The last column of the merged cell
+   * @param {number} [opts.firstRow] - The first row of the merged cell
+   * @param {number} [opts.lastRow] - The last row of the merged cell
+   * @param {number} [opts.firstCol] - The first column of the merged cell
+   * @param {number} [opts.lastCol] - The last column of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {number} opts.value - The value of the merged cell.
-   * @param {Format} opts.format - The format of the merged cell
+   * @param {FormatValue} opts.format - The format of the merged cell
    * @returns {void}
    * @throws {Error} - firstCol > 65_535 or firstCol < 0
    * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -482,13 +1280,14 @@ class Sheet {
    * @throws {Error} - firstCol === lastCol && firstRow === lastRow
    */
   writeMergedNumber(opts) {
-    const { firstRow, lastRow, firstCol, lastCol, value, format } = opts;
+    const { firstRow, lastRow, firstCol, lastCol, range, value, format } = opts;
     if (!isNaN(value)) {
       this.writeMergedCell({
         firstRow,
         lastRow,
         firstCol,
         lastCol,
+        range,
         value,
         cellType: 'number',
         format,
@@ -500,6 +1299,7 @@ class Sheet {
       lastRow,
       firstCol,
       lastCol,
+      range,
       value,
       cellType: 'string',
       format,
@@ -508,27 +1308,33 @@ class Sheet {
 
   /**
    * writes a link value to a cell
-   * @param {number} row - the cell row
-   * @param {number} col - the cell col
+   * The cell can also be given in A1 notation, followed by the value and format: `writeLink('B2', value, format)`
+   * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+   * @param {number|any} col - the cell col
    * @param {Link} value - The value to write to the cell
-   * @param {Format} [format] - The format of the cell
+   * @param {FormatValue} [format] - The format of the cell
    * @returns {void}
    * @throws {Error} - col > 65_535 or col < 0
    * @throws {Error} - row > 1_048_577 or row < 0
    */
   writeLink(row, col, value, format) {
+    if (typeof row === 'string') {
+      this.writeCell(row, col, 'link', value);
+      return;
+    }
     this.writeCell(row, col, value, 'link', format);
   }
 
   /**
    * writes a link value to a merged cell
    * @param {Object} opts - The options for the merged cell
-   * @param {number} opts.firstRow - The first row of the merged cell
-   * @param {number} opts.lastRow - The last row of the merged cell
-   * @param {number} opts.firstCol - The first column of the merged cell
-   * @param {number} opts.lastCol - The last column of the merged cell
+   * @param {number} [opts.firstRow] - The first row of the merged cell
+   * @param {number} [opts.lastRow] - The last row of the merged cell
+   * @param {number} [opts.firstCol] - The first column of the merged cell
+   * @param {number} [opts.lastCol] - The last column of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {Link} opts.value - The value of the merged cell.
-   * @param {Format} opts.format - The format of the merged cell
+   * @param {FormatValue} opts.format - The format of the merged cell
    * @returns {void}
    * @throws {Error} - firstCol > 65_535 or firstCol < 0
    * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -539,12 +1345,13 @@ class Sheet {
    * @throws {Error} - firstCol === lastCol && firstRow === lastRow
    */
   writeMergedLink(opts) {
-    const { firstRow, lastRow, firstCol, lastCol, value, format } = opts;
+    const { firstRow, lastRow, firstCol, lastCol, range, value, format } = opts;
     this.writeMergedCell({
       firstRow,
       lastRow,
       firstCol,
       lastCol,
+      range,
       value,
       cellType: 'link',
       format,
@@ -554,27 +1361,33 @@ class Sheet {
 
   /**
    * writes a date value to a cell
-   * @param {number} row - the cell row
-   * @param {number} col - the cell col
+   * The cell can also be given in A1 notation, followed by the value and format: `writeDate('B2', value, format)`
+   * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+   * @param {number|any} col - the cell col
    * @param {Date} value - The value to write to the cell
-   * @param {Format} [format] - The format of the cell
+   * @param {FormatValue} [format] - The format of the cell
    * @returns {void}
    * @throws {Error} - col > 65_535 or col < 0
    * @throws {Error} - row > 1_048_577 or row < 0
    */
   writeDate(row, col, value, format) {
+    if (typeof row === 'string') {
+      this.writeCell(row, col, 'date', value);
+      return;
+    }
     this.writeCell(row, col, value, 'date', format);
   }
 
   /**
    * writes a date value to a merged cell
    * @param {Object} opts - The options for the merged cell
-   * @param {number} opts.firstRow - The first row of the merged cell
-   * @param {number} opts.lastRow - The last row of the merged cell
-   * @param {number} opts.firstCol - The first column of the merged cell
-   * @param {number} opts.lastCol - The last column of the merged cell
+   * @param {number} [opts.firstRow] - The first row of the merged cell
+   * @param {number} [opts.lastRow] - The last row of the merged cell
+   * @param {number} [opts.firstCol] - The first column of the merged cell
+   * @param {number} [opts.lastCol] - The last column of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {Date} opts.value - The value of the merged cell.
-   * @param {Format} opts.format - The format of the merged cell
+   * @param {FormatValue} opts.format - The format of the merged cell
    * @returns {void}
    * @throws {Error} - firstCol > 65_535 or firstCol < 0
    * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -585,12 +1398,13 @@ class Sheet {
    * @throws {Error} - firstCol === lastCol && firstRow === lastRow
    */
   writeMergedDate(opts) {
-    const { firstRow, lastRow, firstCol, lastCol, value, format } = opts;
+    const { firstRow, lastRow, firstCol, lastCol, range, value, format } = opts;
     this.writeMergedCell({
       firstRow,
       lastRow,
       firstCol,
       lastCol,
+      range,
       value,
       cellType: 'date',
       format,
@@ -600,25 +1414,31 @@ class Sheet {
 
   /**
    * writes a formula value to a cell
-   * @param {number} row - the cell row
-   * @param {number} col - the cell col
+   * The cell can also be given in A1 notation, followed by the value and format: `writeFormula('B2', value, format)`
+   * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+   * @param {number|any} col - the cell col
    * @param {Formula} value - The value to write to the cell
-   * @param {Format} [format] - The format of the cell
+   * @param {FormatValue} [format] - The format of the cell
    * @returns {void}
    */
   writeFormula(row, col, value, format) {
+    if (typeof row === 'string') {
+      this.writeCell(row, col, 'formula', value);
+      return;
+    }
     this.writeCell(row, col, value, 'formula', format);
   }
 
   /**
    * writes a formula value to a merged cell
    * @param {Object} opts - The options for the merged cell
-   * @param {number} opts.firstRow - The first row of the merged cell
-   * @param {number} opts.lastRow - The last row of the merged cell
-   * @param {number} opts.firstCol - The first column of the merged cell
-   * @param {number} opts.lastCol - The last column of the merged cell
+   * @param {number} [opts.firstRow] - The first row of the merged cell
+   * @param {number} [opts.lastRow] - The last row of the merged cell
+   * @param {number} [opts.firstCol] - The first column of the merged cell
+   * @param {number} [opts.lastCol] - The last column of the merged cell
+   * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
    * @param {Formula} opts.value - The value of the merged cell.
-   * @param {Format} opts.format - The format of the merged cell
+   * @param {FormatValue} opts.format - The format of the merged cell
    * @returns {void}
    * @throws {Error} - firstCol > 65_535 or firstCol < 0
    * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -629,12 +1449,13 @@ class Sheet {
    * @throws {Error} - firstCol === lastCol && firstRow === lastRow
    */
   writeMergedFormula(opts) {
-    const { firstRow, lastRow, firstCol, lastCol, value, format } = opts;
+    const { firstRow, lastRow, firstCol, lastCol, range, value, format } = opts;
     this.writeMergedCell({
       firstRow,
       lastRow,
       firstCol,
       lastCol,
+      range,
       value,
       cellType: 'formula',
       format,
@@ -644,8 +1465,8 @@ class Sheet {
   /**
    *
    * @typedef {Object} FormatOptions
-   * @property {Format} [headerFormat] - The format of the header cells
-   * @property {Format} [cellFormat] - The format of the data cells
+   * @property {FormatValue} [headerFormat] - The format of the header cells
+   * @property {FormatValue} [cellFormat] - The format of the data cells
    * @property {Object.<string, Format>} [columnFormats] - The format of the cells in the columns
    * Writes the sheet based on the provided array of objects
    * For performance reasons the headers will be generated based on the first object
@@ -697,4 +1518,9 @@ class Sheet {
   }
 }
 
-module.exports = { Sheet, ArrayFormulaSheetValue };
+module.exports = {
+  Sheet,
+  ArrayFormulaSheetValue,
+  ConditionalFormatGroupSheetValue,
+  CsvImportSheetValue,
+};
@@ -3,6 +3,8 @@
 const Formula = require('./formula');
 const Format = require('./format');
 
+/** @typedef {import('./format').FormatValue} FormatValue */
+
 /**
  * @typedef {(
  * "none"|
@@ -117,29 +119,33 @@ class TableFunction {
 /**
  * @class
  * @classdesc defines a table column
- * @property {Format} [format] - column format
+ * @property {FormatValue} [format] - column format
  * @property {Formula} [formula] - column formula
  * @property {string} [header] - column header
- * @property {Format} [headerFormat] - column header format
+ * @property {FormatValue} [headerFormat] - column header format
  * @property {TableFunction} [totalFunction] - column total function
  * @property {string} [totalLabel] - column total label
+ * @property {string} [key] - record key read by `Sheet.addTableFromData` (defaults to the header)
+ * @property {("number"|"string"|"link"|"date"|"formula")} [cellType] - cell type used by `Sheet.addTableFromData`
  */
 class TableColumn {
   /**
    * @param {Object} obj
-   * @param {Format} [obj.format]
+   * @param {FormatValue} [obj.format]
    * @param {Formula} [obj.formula]
    * @param {string} [obj.header]
-   * @param {Format} [obj.headerFormat]
+   * @param {FormatValue} [obj.headerFormat]
    * @param {Object} [obj.totalFunction]
    * @param {TableFunctionType} obj.totalFunction.type
    * @param {Formula} [obj.totalFunction.formula]
    * @param {string} [obj.totalLabel]
+   * @param {string} [obj.key]
+   * @param {("number"|"string"|"link"|"date"|"formula")} [obj.cellType]
    * @throws {Error} if totalFunction is not valid
    */
   constructor(obj) {
     /**
-     * @type {Format|undefined|null}
+     * @type {FormatValue|undefined|null}
      * @default undefined
      */
     this.format = obj.format;
@@ -157,7 +163,7 @@ class TableColumn {
     this.header = obj.header;
 
     /**
-     * @type {Format|undefined|null}
+     * @type {FormatValue|undefined|null}
      * @default undefined
      */
     this.headerFormat = obj.headerFormat;
@@ -175,10 +181,22 @@ class TableColumn {
      * @default undefined
      */
     this.totalLabel = obj.totalLabel;
+
+    /**
+     * @type {string|undefined|null}
+     * @default undefined
+     */
+    this.key = obj.key;
+
+    /**
+     * @type {("number"|"string"|"link"|"date"|"formula")|undefined|null}
+     * @default undefined
+     */
+    this.cellType = obj.cellType;
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    * @returns {void}
    * Set the format for a table column
    */
@@ -211,7 +229,7 @@ class TableColumn {
   }
 
   /**
-   * @param {Format} format
+   * @param {FormatValue} format
    * @returns {void}
    * Set the header format for a table column
    */
@@ -0,0 +1,167 @@
+const funcs = require('../../../native/node-xlsxwriter.node');
+const saveTemplateToBuffer = funcs.saveTemplateToBuffer;
+const saveTemplateToBufferSync = funcs.saveTemplateToBufferSync;
+const saveTemplateToFile = funcs.saveTemplateToFile;
+const saveTemplateToFileSync = funcs.saveTemplateToFileSync;
+// @ts-check
+
+/**
+ * A value written into a template. `null` and `undefined` clear the cell, and dates are
+ * written as serial numbers, to be shown by the number format of the cell.
+ * @typedef {string|number|boolean|Date|import('./formula')|null|undefined} TemplateValue
+ */
+
+/**
+ * A value for a cell of a template sheet.
+ * @typedef {Object} TemplateCell
+ * @property {string} sheet - The name of the sheet
+ * @property {number} [row] - The row of the cell, 0-based
+ * @property {number} [col] - The column of the cell, 0-based
+ * @property {string} [cell] - The cell in A1 notation ("B2"), in place of `row` and `col`
+ * @property {TemplateValue} value - The value of the cell
+ */
+
+/**
+ * The rows of a table of a template.
+ * @typedef {Object} TemplateTable
+ * @property {string} name - The name of the table
+ * @property {(TemplateValue[]|Record<string, TemplateValue>)[]} data - The rows, as values in the order of
+ * the columns or as objects keyed by the column headers
+ */
+
+/**
+ * @class Template
+ * @classdesc An existing xlsx file to write values into. Only the sheets, shared strings and
+ * tables that change are written again, so charts, images, pivot tables, macros and everything
+ * else the workbook model doesn't know are kept as they are, and cells keep their styles.
+ * @property {Buffer|string} source - The content of the file, or its path
+ * @property {TemplateCell[]} cells - The values written into cells
+ * @property {TemplateTable[]} tables - The rows written into tables
+ * @property {{name: string, value: TemplateValue}[]} definedNames - The values written into defined names
+ * @property {Record<string, any>|undefined} data - The data placeholders are replaced with
+ */
+class Template {
+  /**
+   * @param {Buffer|string} source - The content of the xlsx file, or its path
+   */
+  constructor(source) {
+    /**
+     * The content of the file, or its path
+     * @type {Buffer|string}
+     */
+    this.source = source;
+    /**
+     * The values written into cells
+     * @type {TemplateCell[]}
+     */
+    this.cells = [];
+    /**
+     * The rows written into tables
+     * @type {TemplateTable[]}
+     */
+    this.tables = [];
+    /**
+     * The values written into defined names
+     * @type {{name: string, value: TemplateValue}[]}
+     */
+    this.definedNames = [];
+    /**
+     * The data placeholders are replaced with
+     * @type {Record<string, any>|undefined}
+     */
+    this.data = undefined;
+  }
+
+  /**
+   * Writes a value into a cell of a sheet, which keeps its style.
+   * The cell can also be given in A1 notation: `writeCell('Invoice', 'B2', value)`
+   * @param {string} sheet - The name of the sheet
+   * @param {number|string} row - The row of the cell, 0-based, or the cell in A1 notation ("B2")
+   * @param {number|TemplateValue} col - The column of the cell, 0-based
+   * @param {TemplateValue} [value] - The value of the cell
+   * @returns {void}
+   */
+  writeCell(sheet, row, col, value) {
+    if (typeof row === 'string') {
+      this.cells.push({ sheet, cell: row, value: /** @type {TemplateValue} */ (col) });
+      return;
+    }
+    this.cells.push({ sheet, row, col: /** @type {number} */ (col), value });
+  }
+
+  /**
+   * Writes the rows of a table below its header, and resizes the table to them. Rows the table
+   * no longer has are cleared and the total row moves below the last row. Cells below a
+   * growing table are overwritten. Columns without a value take the formula of the column when
+   * it is calculated, and new rows take the styles of the first row.
+   * @param {string} name - The name of the table
+   * @param {(TemplateValue[]|Record<string, TemplateValue>)[]} data - The rows, as values in the
+   * order of the columns or as objects keyed by the column headers
+   * @returns {void}
+   */
+  writeTable(name, data) {
+    this.tables.push({ name, data });
+  }
+
+  /**
+   * Writes a value into the first cell of the range a defined name refers to.
+   * @param {string} name - The defined name
+   * @param {TemplateValue} value - The value of the cell
+   * @returns {void}
+   */
+  writeDefinedName(name, value) {
+    this.definedNames.push({ name, value });
+  }
+
+  /**
+   * Sets the data the `{{placeholder}}` tokens of the template's strings are replaced with.
+   * Placeholders name values by their path, like `{{customer.name}}` or `{{lines.0.price}}`,
+   * and the ones without data are left as they are. A cell holding nothing but a placeholder
+   * takes a number, boolean, date or formula with its type.
+   * @param {Record<string, any>} data - The data
+   * @returns {void}
+   */
+  setData(data) {
+    this.data = data;
+  }
+
+  /**
+   * Writes the filled template as a buffer.(using a child process for the asynchronous operation)
+   * @returns {Promise<Buffer>}
+   * @throws {Error} The template can't be read, or names a sheet, table or defined name it doesn't have.
+   */
+  async saveToBuffer() {
+    return saveTemplateToBuffer(this);
+  }
+
+  /**
+   * Writes the filled template as a buffer.
+   * @returns {Buffer}
+   * @throws {Error} The template can't be read, or names a sheet, table or defined name it doesn't have.
+   */
+  saveToBufferSync() {
+    return saveTemplateToBufferSync(this);
+  }
+
+  /**
+   * Writes the filled template to a file.(using a child process for the asynchronous operation)
+   * @param {string} path - The path of the file
+   * @returns {Promise<void>}
+   * @throws {Error} The template can't be read, or names a sheet, table or defined name it doesn't have.
+   */
+  async saveToFile(path) {
+    return saveTemplateToFile(this, path);
+  }
+
+  /**
+   * Writes the filled template to a file.
+   * @param {string} path - The path of the file
+   * @returns {void}
+   * @throws {Error} The template can't be read, or names a sheet, table or defined name it doesn't have.
+   */
+  saveToFileSync(path) {
+    return saveTemplateToFileSync(this, path);
+  }
+}
+
+module.exports = Template;
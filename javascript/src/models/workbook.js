@@ -5,14 +5,117 @@ const saveToFileSync = funcs.saveToFileSync;
 const saveToFile = funcs.saveToFile;
 const saveToBase64 = funcs.saveToBase64;
 const saveToBase64Sync = funcs.saveToBase64Sync;
+const uniqueFormatCount = funcs.uniqueFormatCount;
+const evaluateConditionalFormats = funcs.evaluateConditionalFormats;
+const readFromBuffer = funcs.readFromBuffer;
+const readFromBufferSync = funcs.readFromBufferSync;
+const readFromFile = funcs.readFromFile;
+const readFromFileSync = funcs.readFromFileSync;
+const exportSheet = funcs.exportSheet;
+const exportSheetSync = funcs.exportSheetSync;
+const exportSheetToFile = funcs.exportSheetToFile;
+const exportSheetToFileSync = funcs.exportSheetToFileSync;
+const renderToHtml = funcs.renderToHtml;
+const renderToHtmlSync = funcs.renderToHtmlSync;
+const saveToOdsBuffer = funcs.saveToOdsBuffer;
+const saveToOdsBufferSync = funcs.saveToOdsBufferSync;
+const saveToOdsFile = funcs.saveToOdsFile;
+const saveToOdsFileSync = funcs.saveToOdsFileSync;
+const saveToOdsBase64 = funcs.saveToOdsBase64;
+const saveToOdsBase64Sync = funcs.saveToOdsBase64Sync;
 // @ts-check
 
 const { Sheet } = require('./sheet');
+const Format = require('./format');
+const Formula = require('./formula');
+const Link = require('./link');
+const { Table, TableColumn } = require('./table');
+const conditionalFormats = require('./conditional_format');
+const { ConditionalFormat, ConditionalFormatCustomIcon } = conditionalFormats;
+
+/** @type {Record<string, new (options: any) => ConditionalFormat>} */
+const conditionalFormatClasses = {
+  twoColorScale: conditionalFormats.ConditionalFormatTwoColorScale,
+  threeColorScale: conditionalFormats.ConditionalFormatThreeColorScale,
+  average: conditionalFormats.ConditionalFormatAverage,
+  blank: conditionalFormats.ConditionalFormatBlank,
+  cell: conditionalFormats.ConditionalFormatCell,
+  dataBar: conditionalFormats.ConditionalFormatDataBar,
+  date: conditionalFormats.ConditionalFormatDate,
+  duplicate: conditionalFormats.ConditionalFormatDuplicate,
+  error: conditionalFormats.ConditionalFormatError,
+  formula: conditionalFormats.ConditionalFormatFormula,
+  iconSet: conditionalFormats.ConditionalFormatIconSet,
+  text: conditionalFormats.ConditionalFormatText,
+  top: conditionalFormats.ConditionalFormatTop,
+};
+
+/**
+ * A data bar drawn in a cell, over the part of its width between `start` and `end` (0 to 1).
+ * @typedef {Object} DataBarPreview
+ * @property {number} start - Where the bar starts
+ * @property {number} end - Where the bar ends
+ * @property {string} color - The fill of the bar, as `#RRGGBB`
+ * @property {boolean} showValue - Whether the value is shown with the bar
+ */
+
+/**
+ * An icon shown in a cell.
+ * @typedef {Object} IconPreview
+ * @property {string} iconType - The icon set the icon comes from, like `threeTrafficLights`
+ * @property {number} index - The index of the icon in its set
+ * @property {boolean} showValue - Whether the value is shown with the icon
+ */
+
+/**
+ * What the conditional formats show in a cell.
+ * @typedef {Object} ConditionalFormatCellPreview
+ * @property {number} row - The row of the cell, 0-based
+ * @property {number} col - The column of the cell, 0-based
+ * @property {Format} [format] - The formats of the matching rules, the highest priority on top
+ * @property {string} [color] - The fill of a color scale, as `#RRGGBB`
+ * @property {DataBarPreview} [dataBar] - The data bar of the cell
+ * @property {IconPreview} [icon] - The icon of the cell
+ */
+
+/**
+ * @typedef {Object} ConditionalFormatSheetPreview
+ * @property {string} name - The name of the sheet
+ * @property {ConditionalFormatCellPreview[]} cells - The cells a conditional format applies to, row by row
+ */
+
+/**
+ * How a sheet is written as CSV, TSV or JSON.
+ * @typedef {Object} SheetExportOptions
+ * @property {'formatted'|'raw'} [values='formatted'] - Numbers and dates as Excel shows them through their number format, or as they are stored: numbers in full and dates in ISO 8601
+ * @property {'results'|'formulas'} [formulas='results'] - Formula cells as their result, computed when it is missing, or as their formula
+ * @property {'first'|'repeat'} [mergedCells='first'] - Whether the value of a merged range is only in its first cell, or in all of them
+ * @property {string} [delimiter] - The field delimiter, `,` for CSV and a tab for TSV
+ * @property {string} [quote='"'] - The character fields are quoted with
+ * @property {'minimal'|'all'|'nonnumeric'|'none'} [quoting='minimal'] - Which fields are quoted: the ones that need it, all of them, all but numbers, or none
+ * @property {string} [lineEnding='\r\n'] - What ends each record
+ * @property {boolean} [bom=false] - Whether the text starts with a byte order mark, for Excel to read it as UTF-8
+ * @property {boolean} [header=false] - For JSON, whether rows are objects keyed by the values of the first row, instead of arrays
+ */
+
+/**
+ * How a sheet is rendered as HTML.
+ * @typedef {Object} HtmlOptions
+ * @property {boolean} [document=false] - Whether to write a whole HTML document titled after the sheet, instead of the table alone
+ * @property {boolean} [gridLines=false] - Whether cells without borders of their own show Excel's light gray grid lines
+ */
+
 /**
  *
  * @class Workbook
  * @classdesc Represents a workbook
  * @property {Sheet[]} sheets - The sheets in the workbook
+ * @property {Format[]} formats - The formats shared by all sheets
+ * @property {ConditionalFormat[]} conditionalFormats - The conditional formats shared by all sheets
+ * @property {{name: string, formula: string}[]} definedNames - The names defined in the workbook
+ * @property {boolean} validateFormulas - Whether formulas are checked before the workbook is written
+ * @property {string[]} formulaWarnings - The formulas of the last save that call unknown functions
+ * @property {boolean} evaluateFormulas - Whether the results of formulas are computed before the workbook is written
  */
 class Workbook {
   constructor() {
@@ -21,6 +124,104 @@ class Workbook {
      * @type {Sheet[]}
      */
     this.sheets = [];
+    /**
+     * The formats shared by all sheets
+     * @type {Format[]}
+     */
+    this.formats = [];
+    /**
+     * The conditional formats shared by all sheets
+     * @type {ConditionalFormat[]}
+     */
+    this.conditionalFormats = [];
+    /**
+     * The names defined in the workbook
+     * @type {{name: string, formula: string}[]}
+     */
+    this.definedNames = [];
+    /**
+     * Whether formulas are checked before the workbook is written
+     * @type {boolean}
+     * @default true
+     */
+    this.validateFormulas = true;
+    /**
+     * The formulas of the last save that call unknown functions, like VBA or
+     * add-in functions, as `Sheet1!A2 =MYUDF(1): Unknown function MYUDF`.
+     * They are written as they are
+     * @type {string[]}
+     */
+    this.formulaWarnings = [];
+    /**
+     * Whether the results of formulas are computed before the workbook is written
+     * @type {boolean}
+     * @default false
+     */
+    this.evaluateFormulas = false;
+  }
+
+  /**
+   * Sets whether the formulas of the cells are checked before the workbook is written.
+   * Checked formulas must be valid and refer to sheets of the workbook, otherwise saving
+   * throws an error naming each cell. Calls to functions that are neither built in nor
+   * defined names are written as they are and listed in `formulaWarnings`. Functions added
+   * after Excel 2007, like `XLOOKUP`, get the `_xlfn.` prefix Excel expects without setting
+   * `useFutureFunctions`.
+   * @param {boolean} [validate=true] - Whether to check the formulas
+   * @returns {void}
+   */
+  setValidateFormulas(validate = true) {
+    this.validateFormulas = validate;
+  }
+
+  /**
+   * Sets whether the results of formulas are computed from the cells of the workbook
+   * and stored with them, so that readers which don't recalculate, like previews and
+   * parsers, show values. Arithmetic, comparisons, references to other sheets and common
+   * functions are understood: aggregates such as `SUM`, `AVERAGE` and `COUNTIF`, logic such as
+   * `IF` and `IFERROR`, lookups such as `VLOOKUP`, `INDEX`, `MATCH` and `XLOOKUP`, text
+   * functions and date functions. Formulas using anything else, and formulas that already have
+   * a `result`, are written as before and Excel computes them when the file is opened.
+   * @param {boolean} [evaluate=true] - Whether to compute the results
+   * @returns {void}
+   */
+  setEvaluateFormulas(evaluate = true) {
+    this.evaluateFormulas = evaluate;
+  }
+
+  /**
+   * Defines a name in the workbook. A name given as `Sheet1!name` is scoped to that sheet.
+   * Formulas can refer to the name, and call it when it holds a `LAMBDA`.
+   * @param {string} name - The name
+   * @param {string} formula - What the name refers to, like `=Sheet1!$A$1:$A$9` or `=LAMBDA(x,x*1.2)`
+   * @returns {void}
+   */
+  defineName(name, formula) {
+    this.definedNames.push({ name, formula });
+  }
+
+  /**
+   * Adds a format shared by all sheets. Cells, rows, columns, tables and
+   * conditional formats of any sheet can then use the returned id in place
+   * of the format.
+   * @param {Format} format - The format to be added
+   * @returns {number} The id of the format
+   */
+  addFormat(format) {
+    this.formats.push(format);
+    return format.id;
+  }
+
+  /**
+   * Adds a conditional format shared by all sheets. It is read once, and
+   * `addConditionalFormat` and `addConditionalFormatGroup` of any sheet can
+   * use the returned id in place of the conditional format.
+   * @param {ConditionalFormat} conditionalFormat - The conditional format to be added
+   * @returns {number} The id of the conditional format
+   */
+  addConditionalFormat(conditionalFormat) {
+    this.conditionalFormats.push(conditionalFormat);
+    return conditionalFormat.id;
   }
 
   /**
@@ -119,6 +320,400 @@ class Workbook {
   saveToBase64Sync() {
     return saveToBase64Sync(this);
   }
+
+  /**
+   * Creates an OpenDocument spreadsheet (.ods) of the workbook as a buffer.
+   * Formulas are written in OpenFormula, or in the syntax of Excel when they
+   * use defined names, table references or spill ranges.
+   * @returns {Promise<Buffer>}
+   * @throws {Error}
+   */
+  async saveToOdsBuffer() {
+    return saveToOdsBuffer(this);
+  }
+
+  /**
+   * Creates an OpenDocument spreadsheet (.ods) of the workbook as a buffer.
+   * @returns {Buffer}
+   * @throws {Error}
+   */
+  saveToOdsBufferSync() {
+    return saveToOdsBufferSync(this);
+  }
+
+  /**
+   * Writes the workbook to an OpenDocument spreadsheet (.ods) file.
+   * @param {string} path - The path of the file.
+   * @returns {Promise<void>}
+   * @throws {Error}
+   */
+  async saveToOdsFile(path) {
+    return saveToOdsFile(this, path);
+  }
+
+  /**
+   * Writes the workbook to an OpenDocument spreadsheet (.ods) file.
+   * @param {string} path - The path of the file.
+   * @returns {void}
+   * @throws {Error}
+   */
+  saveToOdsFileSync(path) {
+    return saveToOdsFileSync(this, path);
+  }
+
+  /**
+   * Writes the workbook as an OpenDocument spreadsheet (.ods) to a base64 string.
+   * @returns {Promise<string>}
+   * @throws {Error}
+   */
+  async saveToOdsBase64() {
+    return saveToOdsBase64(this);
+  }
+
+  /**
+   * Writes the workbook as an OpenDocument spreadsheet (.ods) to a base64 string.
+   * @returns {string}
+   * @throws {Error}
+   */
+  saveToOdsBase64Sync() {
+    return saveToOdsBase64Sync(this);
+  }
+
+  /**
+   * Writes a sheet as CSV text, row by row from `A1` to the last written cell.
+   * Empty cells are empty fields, and booleans are `TRUE` or `FALSE`.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {SheetExportOptions} [options] - How values are written
+   * @returns {Promise<string>}
+   * @throws {Error} The sheet doesn't exist or the options are invalid.
+   */
+  async saveSheetToCsv(sheet = 0, options = {}) {
+    return exportSheet(this, exportOptions(this, sheet, options, 'csv'));
+  }
+
+  /**
+   * Writes a sheet as CSV text, see `saveSheetToCsv`.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {SheetExportOptions} [options] - How values are written
+   * @returns {string}
+   * @throws {Error} The sheet doesn't exist or the options are invalid.
+   */
+  saveSheetToCsvSync(sheet = 0, options = {}) {
+    return exportSheetSync(this, exportOptions(this, sheet, options, 'csv'));
+  }
+
+  /**
+   * Writes a sheet as tab separated text, see `saveSheetToCsv`.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {SheetExportOptions} [options] - How values are written
+   * @returns {Promise<string>}
+   * @throws {Error} The sheet doesn't exist or the options are invalid.
+   */
+  async saveSheetToTsv(sheet = 0, options = {}) {
+    return exportSheet(this, exportOptions(this, sheet, options, 'tsv'));
+  }
+
+  /**
+   * Writes a sheet as tab separated text, see `saveSheetToCsv`.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {SheetExportOptions} [options] - How values are written
+   * @returns {string}
+   * @throws {Error} The sheet doesn't exist or the options are invalid.
+   */
+  saveSheetToTsvSync(sheet = 0, options = {}) {
+    return exportSheetSync(this, exportOptions(this, sheet, options, 'tsv'));
+  }
+
+  /**
+   * Writes a sheet as a JSON array of rows, from `A1` to the last written cell. Rows are
+   * arrays of values, or objects keyed by the first row with `header`, and empty cells
+   * are `null`. Formatted numbers are strings, raw ones numbers.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {SheetExportOptions} [options] - How values are written
+   * @returns {Promise<string>}
+   * @throws {Error} The sheet doesn't exist or the options are invalid.
+   */
+  async saveSheetToJson(sheet = 0, options = {}) {
+    return exportSheet(this, exportOptions(this, sheet, options, 'json'));
+  }
+
+  /**
+   * Writes a sheet as a JSON array of rows, see `saveSheetToJson`.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {SheetExportOptions} [options] - How values are written
+   * @returns {string}
+   * @throws {Error} The sheet doesn't exist or the options are invalid.
+   */
+  saveSheetToJsonSync(sheet = 0, options = {}) {
+    return exportSheetSync(this, exportOptions(this, sheet, options, 'json'));
+  }
+
+  /**
+   * Writes a sheet to a CSV, TSV or JSON file, chosen by `format` or else by the
+   * extension of the path. See `saveSheetToCsv` and `saveSheetToJson`.
+   * @param {Sheet|string|number} sheet - The sheet, its name or its index
+   * @param {string} path - The path of the file
+   * @param {SheetExportOptions & {format?: 'csv'|'tsv'|'json'}} [options] - How values are written
+   * @returns {Promise<void>}
+   * @throws {Error} The sheet doesn't exist, the options are invalid or the file can't be written.
+   */
+  async saveSheetToFile(sheet, path, options = {}) {
+    return exportSheetToFile(this, path, exportOptions(this, sheet, options, exportFormat(path)));
+  }
+
+  /**
+   * Writes a sheet to a CSV, TSV or JSON file, see `saveSheetToFile`.
+   * @param {Sheet|string|number} sheet - The sheet, its name or its index
+   * @param {string} path - The path of the file
+   * @param {SheetExportOptions & {format?: 'csv'|'tsv'|'json'}} [options] - How values are written
+   * @returns {void}
+   * @throws {Error} The sheet doesn't exist, the options are invalid or the file can't be written.
+   */
+  saveSheetToFileSync(sheet, path, options = {}) {
+    return exportSheetToFileSync(this, path, exportOptions(this, sheet, options, exportFormat(path)));
+  }
+
+  /**
+   * Renders a sheet as an HTML table, from `A1` to the last written cell. Merged cells span
+   * rows and columns, hidden rows and columns are left out, and the fonts, fills, borders,
+   * alignment, column widths, row heights and table styles are written as inline styles, so
+   * the table can go straight into an email body. Values are shown through their number
+   * formats, and formulas as their results, computed when they are missing.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {HtmlOptions} [options] - How the sheet is rendered
+   * @returns {Promise<string>}
+   * @throws {Error} The sheet doesn't exist or the workbook can't be written.
+   */
+  async renderToHtml(sheet = 0, options = {}) {
+    return renderToHtml(this, exportOptions(this, sheet, options, 'html'));
+  }
+
+  /**
+   * Renders a sheet as an HTML table, see `renderToHtml`.
+   * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+   * @param {HtmlOptions} [options] - How the sheet is rendered
+   * @returns {string}
+   * @throws {Error} The sheet doesn't exist or the workbook can't be written.
+   */
+  renderToHtmlSync(sheet = 0, options = {}) {
+    return renderToHtmlSync(this, exportOptions(this, sheet, options, 'html'));
+  }
+
+  /**
+   * Evaluates the conditional formats of every sheet against the values of its cells, for
+   * previews and exports that don't compute them like Excel does. Formulas are computed where
+   * they can be, see `setEvaluateFormulas`, and cells whose value is unknown match no rule.
+   * The date rules compare with `today`, and are left out when it is `null`.
+   * @param {Object} [options] - The options object
+   * @param {Date|null} [options.today=new Date()] - The current date, its UTC day is used
+   * @returns {ConditionalFormatSheetPreview[]}
+   * @throws {Error} The workbook can't be written.
+   */
+  evaluateConditionalFormats({ today = new Date() } = {}) {
+    /** @type {Map<number, ConditionalFormat>} */
+    const rules = new Map();
+    for (const conditionalFormat of this.conditionalFormats) {
+      rules.set(conditionalFormat.id, conditionalFormat);
+    }
+    for (const sheet of this.sheets) {
+      for (const value of sheet.conditionalFormats) {
+        for (const rule of 'rules' in value ? value.rules : [value.format]) {
+          if (typeof rule !== 'number') {
+            rules.set(rule.id, rule);
+          }
+        }
+      }
+    }
+    /** @type {Map<number, Format>} */
+    const formats = new Map(this.formats.map((format) => [format.id, format]));
+
+    /** @type {{name: string, cells: (Omit<ConditionalFormatCellPreview, 'format'> & {rules: number[]})[]}[]} */
+    const sheets = evaluateConditionalFormats(this, today ?? undefined);
+    return sheets.map(({ name, cells }) => ({
+      name,
+      cells: cells.map(({ rules: ids, ...cell }) => {
+        // Properties of higher priority rules win over the same ones of lower rules
+        const layers = ids
+          .map((id) => rules.get(id)?.format)
+          .map((format) => (typeof format === 'number' ? formats.get(format) : format))
+          .filter((format) => format !== undefined)
+          .reverse();
+        if (layers.length === 0) {
+          return cell;
+        }
+        /** @type {Record<string, any>} */
+        const properties = {};
+        for (const layer of layers) {
+          for (const [key, value] of Object.entries(layer)) {
+            if (key !== 'id' && value !== undefined) {
+              properties[key] = value;
+            }
+          }
+        }
+        return { ...cell, format: new Format(properties) };
+      }),
+    }));
+  }
+
+  /**
+   * Reads an xlsx file into a workbook, to change it and write it again.
+   * Cells, merged cells, formats, row heights, column widths, tables and conditional
+   * formats are read where the workbook can express them, and the rest of the file,
+   * like charts, images and comments, is left out. Error cells become formulas of
+   * their value, and formatted empty cells become range formats.
+   * @param {Buffer} buffer - The content of the file
+   * @returns {Promise<Workbook>}
+   * @throws {Error} The buffer is not an xlsx file.
+   */
+  static async readFromBuffer(buffer) {
+    return fromModel(await readFromBuffer(buffer));
+  }
+
+  /**
+   * Reads an xlsx file into a workbook, see `readFromBuffer`.
+   * @param {Buffer} buffer - The content of the file
+   * @returns {Workbook}
+   * @throws {Error} The buffer is not an xlsx file.
+   */
+  static readFromBufferSync(buffer) {
+    return fromModel(readFromBufferSync(buffer));
+  }
+
+  /**
+   * Reads an xlsx file into a workbook, see `readFromBuffer`.(using a child process for the asynchronous operation)
+   * @param {string} path - The path of the file
+   * @returns {Promise<Workbook>}
+   * @throws {Error} The file can't be read or is not an xlsx file.
+   */
+  static async readFromFile(path) {
+    return fromModel(await readFromFile(path));
+  }
+
+  /**
+   * Reads an xlsx file into a workbook, see `readFromBuffer`.
+   * @param {string} path - The path of the file
+   * @returns {Workbook}
+   * @throws {Error} The file can't be read or is not an xlsx file.
+   */
+  static readFromFileSync(path) {
+    return fromModel(readFromFileSync(path));
+  }
+
+  /**
+   * Counts the distinct formats used in the workbook. Formats with the same
+   * properties are only counted once. Excel can't load more than 64000 of them.
+   * @returns {number}
+   * @throws {Error} Two different formats share the same id.
+   */
+  uniqueFormatCount() {
+    return uniqueFormatCount(this);
+  }
+}
+
+/**
+ * The options of a sheet export or rendering, with the index of the sheet.
+ * @param {Workbook} workbook
+ * @param {Sheet|string|number} sheet
+ * @param {Object} options
+ * @param {string} format - The format when the options don't give one
+ */
+function exportOptions(workbook, sheet, options, format) {
+  const index =
+    typeof sheet === 'number'
+      ? sheet
+      : workbook.sheets.findIndex((value) => (typeof sheet === 'string' ? value.name === sheet : value === sheet));
+  if (!Number.isInteger(index) || index < 0 || index >= workbook.sheets.length) {
+    throw new Error(`Sheet ${typeof sheet === 'object' ? sheet.name : sheet} is not in the workbook`);
+  }
+  return { format, ...options, sheet: index };
+}
+
+/**
+ * The export format a path asks for by its extension, CSV by default.
+ * @param {string} path
+ */
+function exportFormat(path) {
+  const extension = path.slice(path.lastIndexOf('.') + 1).toLowerCase();
+  return extension === 'tsv' || extension === 'json' ? extension : 'csv';
+}
+
+/**
+ * Builds a workbook from what the native reader returns: plain objects in the shape the
+ * writers accept, referring to the formats by their id.
+ * @param {any} model
+ * @returns {Workbook}
+ */
+function fromModel(model) {
+  const workbook = new Workbook();
+  /** @type {Map<number, Format>} */
+  const formats = new Map();
+  for (const { id, ...options } of model.formats) {
+    const format = new Format(options);
+    formats.set(id, format);
+    workbook.addFormat(format);
+  }
+  const format = (/** @type {number|undefined} */ id) => (id === undefined ? undefined : formats.get(id));
+  const formula = (/** @type {any} */ options) => (options === undefined ? undefined : new Formula(options));
+
+  for (const data of model.sheets) {
+    const sheet = new Sheet(data.name);
+    for (const cell of data.cells) {
+      let value = cell.value;
+      if (cell.cellType === 'formula') {
+        value = new Formula(value);
+      } else if (cell.cellType === 'link') {
+        value = new Link(value.url, value.text, value.tip);
+      }
+      if (cell.merged) {
+        sheet.writeMergedCell({ ...cell, value, format: format(cell.format) });
+      } else {
+        sheet.writeCell(cell.row, cell.col, value, cell.cellType, format(cell.format));
+      }
+    }
+    for (const config of data.rowConfigs) {
+      sheet.addRowConfig({ ...config, format: format(config.format) });
+    }
+    for (const config of data.columnConfigs) {
+      sheet.addColumnConfig({ ...config, format: format(config.format) });
+    }
+    for (const rangeFormat of data.rangeFormats) {
+      sheet.formatRange({ ...rangeFormat, format: format(rangeFormat.format) });
+    }
+    for (const arrayFormula of data.arrayFormulas) {
+      sheet.addArrayFormula({
+        ...arrayFormula,
+        formula: new Formula(arrayFormula.formula),
+        format: format(arrayFormula.format),
+      });
+    }
+    for (const { table, ...range } of data.tables) {
+      const columns = table.columns.map(
+        (/** @type {any} */ column) =>
+          new TableColumn({
+            ...column,
+            format: format(column.format),
+            headerFormat: format(column.headerFormat),
+            formula: formula(column.formula),
+            totalFunction: column.totalFunction && {
+              ...column.totalFunction,
+              formula: formula(column.totalFunction.formula),
+            },
+          }),
+      );
+      sheet.addTable({ ...range, table: new Table({ ...table, columns }) });
+    }
+    for (const { range, priority, format: { id, type, ...options } } of data.conditionalFormats) {
+      options.format = format(options.format);
+      if (type === 'formula') {
+        options.formula = new Formula(options.formula);
+      } else if (type === 'iconSet') {
+        options.icons = options.icons.map((/** @type {any} */ icon) => new ConditionalFormatCustomIcon(icon));
+      }
+      sheet.addConditionalFormat({ range, priority, format: new conditionalFormatClasses[type](options) });
+    }
+    workbook.pushSheet(sheet);
+  }
+  return workbook;
 }
 
 module.exports = Workbook;
@@ -0,0 +1,64 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format, Formula, Table, ConditionalFormatBlank } = require('../src/index');
+const findRootDir = require('./util');
+const { readEntry } = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+const fs = require('fs');
+
+test('save to file with A1 ranges everywhere', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const bold = new Format({ bold: true });
+
+  sheet.writeString('$A$1', 'title', bold);
+  sheet.writeNumber('B2', 42);
+  sheet.writeCell('C2', 'text', 'string');
+  sheet.writeMergedString({ range: 'A3:C3', value: 'merged', format: bold });
+  sheet.addArrayFormula({ range: 'E1:E3', formula: new Formula({ formula: '=B2*2' }) });
+  sheet.addConditionalFormat({ range: 'B:B', format: new ConditionalFormatBlank({ format: bold }) });
+  sheet.addTable({ range: 'G1:H4', table: new Table({}) });
+  sheet.addTableFromData({ range: 'J1', data: [{ a: 1, b: 2 }] });
+  sheet.writeColumns({ cell: 'M1', columns: [new Float64Array([1, 2, 3])] });
+  sheet.formatRange({ range: '5:5', format: new Format({ italic: true }) });
+  sheet.writeString('B5', 'italic');
+
+  await workbook.saveToFile(`${path}/a1_ranges.xlsx`);
+  const buffer = fs.readFileSync(`${path}/a1_ranges.xlsx`);
+  const strings = [...readEntry(buffer, 'xl/sharedStrings.xml').matchAll(/<t>([^<]*)<\/t>/g)].map((match) => match[1]);
+  assert.deepStrictEqual(strings, ['title', 'Column1', 'Column2', 'a', 'b', 'text', 'merged', 'italic']);
+
+  const xml = readEntry(buffer, 'xl/worksheets/sheet1.xml');
+  assert.match(xml, /<dimension ref="A1:M5"\/>/);
+  assert.match(xml, /<c r="A1" s="1" t="s"><v>0<\/v><\/c><c r="E1"><f t="array" ref="E1:E3">B2\*2<\/f>/);
+  assert.match(xml, /<c r="J1" t="s"><v>3<\/v><\/c><c r="K1" t="s"><v>4<\/v><\/c><c r="M1"><v>1<\/v><\/c><\/row>/);
+  assert.match(xml, /<c r="B2"><v>42<\/v><\/c><c r="C2" t="s"><v>5<\/v><\/c>/);
+  assert.match(xml, /<c r="J2"><v>1<\/v><\/c><c r="K2"><v>2<\/v><\/c><c r="M2"><v>2<\/v><\/c><\/row>/);
+  assert.match(xml, /<c r="A3" s="1" t="s"><v>6<\/v><\/c><c r="B3" s="1"\/><c r="C3" s="1"\/>.*<c r="M3"><v>3<\/v><\/c><\/row>/);
+  assert.match(xml, /<row r="5" spans="1:13"><c r="B5" s="2" t="s"><v>7<\/v><\/c><\/row>/);
+  assert.match(xml, /<mergeCell ref="A3:C3"\/>/);
+  assert.match(xml, /<conditionalFormatting sqref="B1:B1048576">/);
+  assert.match(readEntry(buffer, 'xl/tables/table1.xml'), /ref="G1:H4"/);
+  assert.match(readEntry(buffer, 'xl/tables/table2.xml'), /ref="J1:K2"/);
+  assert.match(readEntry(buffer, 'xl/styles.xml'), /<cellXfs count="3">.*<xf numFmtId="0" fontId="2" fillId="0" borderId="0" xfId="0" applyFont="1"\/><\/cellXfs>/);
+});
+
+test('invalid A1 ranges are rejected', (t) => {
+  for (const range of ['B2:', 'A0', 'XFE1', 'A1:B', '1:A', 'B2:A1', 'A1048577']) {
+    const workbook = new Workbook();
+    workbook.addSheet().formatRange({ range, format: new Format({ bold: true }) });
+    assert.throws(() => workbook.saveToBufferSync(), /Invalid range/, range);
+  }
+});
+
+test('merged cells and single cells need the right kind of range', (t) => {
+  const merged = new Workbook();
+  merged.addSheet().writeMergedString({ range: 'B2', value: 'one cell', format: new Format({}) });
+  assert.throws(() => merged.saveToBufferSync(), /more than one cell/);
+
+  const single = new Workbook();
+  single.addSheet().writeString('B2:C3', 'area');
+  assert.throws(() => single.saveToBufferSync(), /not a single cell/);
+});
@@ -0,0 +1,48 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format, Color } = require('../src/index');
+const findRootDir = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+const fs = require('fs');
+
+test('save to file with hex, named, theme and automatic colors', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  const rgb = new Format({ fontColor: new Color({ red: 31, green: 78, blue: 121 }) });
+  const hex = new Format({ fontColor: '#1F4E79' });
+  const bareHex = new Format({ backgroundColor: 'c6efce' });
+  const named = new Format({ fontColor: 'Navy' });
+  const theme = new Format({ backgroundColor: { theme: 4, shade: 2 } });
+  const automatic = new Format({ fontColor: 'automatic' });
+
+  sheet.writeString(0, 0, 'rgb', rgb);
+  sheet.writeString(0, 1, 'hex', hex);
+  sheet.writeString(0, 2, 'bare hex', bareHex);
+  sheet.writeString(0, 3, 'named', named);
+  sheet.writeString(0, 4, 'theme', theme);
+  sheet.writeString(0, 5, 'automatic', automatic);
+
+  await workbook.saveToFile(`${path}/colors.xlsx`);
+  assert(fs.existsSync(`${path}/colors.xlsx`), 'file exists');
+});
+
+test('color rejects out of range components', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeString(0, 0, 'red', new Format({ fontColor: new Color({ red: 300 }) }));
+
+  assert.throws(() => workbook.saveToBufferSync());
+});
+
+test('color rejects unknown names and theme indexes', (t) => {
+  const unknown = new Workbook();
+  unknown.addSheet().writeString(0, 0, 'x', new Format({ fontColor: 'teal-ish' }));
+  assert.throws(() => unknown.saveToBufferSync());
+
+  const theme = new Workbook();
+  theme.addSheet().writeString(0, 0, 'x', new Format({ fontColor: { theme: 12 } }));
+  assert.throws(() => theme.saveToBufferSync());
+});
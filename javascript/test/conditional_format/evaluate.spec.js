@@ -0,0 +1,218 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const {
+  Workbook,
+  Format,
+  Formula,
+  ConditionalFormatAverage,
+  ConditionalFormatBlank,
+  ConditionalFormatCell,
+  ConditionalFormatCustomIcon,
+  ConditionalFormatDataBar,
+  ConditionalFormatDate,
+  ConditionalFormatDuplicate,
+  ConditionalFormatFormula,
+  ConditionalFormatIconSet,
+  ConditionalFormatText,
+  ConditionalFormatThreeColorScale,
+  ConditionalFormatTop,
+  ConditionalFormatTwoColorScale,
+} = require('../../src/index');
+
+const bold = new Format({ bold: true });
+
+/**
+ * The cells of the first sheet a rule formats, in A1 notation.
+ * @param {Workbook} workbook
+ * @param {Object} [options]
+ * @param {Date|null} [options.today]
+ * @returns {string[]}
+ */
+function formatted(workbook, options) {
+  const [sheet] = workbook.evaluateConditionalFormats(options);
+  return sheet.cells
+    .filter((cell) => cell.format !== undefined)
+    .map((cell) => `${String.fromCharCode(65 + cell.col)}${cell.row + 1}`);
+}
+
+/**
+ * A workbook with the numbers in A1 and down.
+ * @param {(number|string)[]} values
+ * @returns {Workbook}
+ */
+function column(values) {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  values.forEach((value, row) =>
+    typeof value === 'number' ? sheet.writeNumber(row, 0, value) : sheet.writeString(row, 0, value),
+  );
+  return workbook;
+}
+
+test('cell and text rules compare the values of the cells', (t) => {
+  const workbook = column([1, 5, 10, 'abc', 'xabc"']);
+  const sheet = workbook.sheets[0];
+  sheet.addConditionalFormat({
+    range: 'A1:A5',
+    format: new ConditionalFormatCell({ format: bold, rule: { type: 'between', value: 10, optionalValue: 4 } }),
+  });
+  sheet.addConditionalFormat({
+    range: 'A1:A5',
+    format: new ConditionalFormatText({ format: bold, rule: { type: 'contains', value: 'BC"' } }),
+  });
+  assert.deepStrictEqual(formatted(workbook), ['A2', 'A3', 'A5']);
+});
+
+test('formula rules move their relative references with the cell', (t) => {
+  const workbook = column([1, 2, 3, 4]);
+  const sheet = workbook.sheets[0];
+  [2, 2, 0, 9].forEach((value, row) => sheet.writeNumber(row, 1, value));
+  sheet.addConditionalFormat({
+    range: 'A1:A4',
+    format: new ConditionalFormatFormula({ format: bold, formula: new Formula({ formula: '=A1>=$B1' }) }),
+  });
+  assert.deepStrictEqual(formatted(workbook), ['A2', 'A3']);
+});
+
+test('formulas of the cells are computed first', (t) => {
+  const workbook = column([3, 4]);
+  const sheet = workbook.sheets[0];
+  sheet.writeFormula(2, 0, new Formula({ formula: '=A1*A2' }));
+  sheet.writeFormula(3, 0, new Formula({ formula: '=UNKNOWN.FUNCTION(1)' }));
+  workbook.setValidateFormulas(false);
+  sheet.addConditionalFormat({
+    range: 'A1:A4',
+    format: new ConditionalFormatCell({ format: bold, rule: { type: 'greaterThan', value: 3 } }),
+  });
+  assert.deepStrictEqual(formatted(workbook), ['A2', 'A3']);
+});
+
+test('blank, duplicate, average and top rules', (t) => {
+  const workbook = column([1, 7, 7, 2, 9]);
+  const sheet = workbook.sheets[0];
+  const add = (/** @type {string} */ range, /** @type {any} */ format) => sheet.addConditionalFormat({ range, format });
+  add('B1:B2', new ConditionalFormatBlank({ format: bold }));
+  add('C1:C1', new ConditionalFormatBlank({ format: bold, invert: true }));
+  assert.deepStrictEqual(formatted(workbook), ['B1', 'B2']);
+
+  sheet.conditionalFormats = [];
+  add('A1:A5', new ConditionalFormatDuplicate({ format: bold }));
+  assert.deepStrictEqual(formatted(workbook), ['A2', 'A3']);
+
+  sheet.conditionalFormats = [];
+  add('A1:A5', new ConditionalFormatAverage({ format: bold, rule: 'belowAverage' }));
+  assert.deepStrictEqual(formatted(workbook), ['A1', 'A4']);
+
+  sheet.conditionalFormats = [];
+  add('A1:A5', new ConditionalFormatTop({ format: bold, rule: { type: 'top', value: 2 } }));
+  assert.deepStrictEqual(formatted(workbook), ['A2', 'A3', 'A5']);
+
+  sheet.conditionalFormats = [];
+  add('A1:A5', new ConditionalFormatTop({ format: bold, rule: { type: 'bottomPercent', value: 20 } }));
+  assert.deepStrictEqual(formatted(workbook), ['A1']);
+});
+
+test('date rules compare with the given day', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeDate(0, 0, new Date(Date.UTC(2024, 2, 14)));
+  sheet.writeDate(1, 0, new Date(Date.UTC(2024, 2, 15, 18)));
+  sheet.writeDate(2, 0, new Date(Date.UTC(2024, 1, 20)));
+  sheet.addConditionalFormat({ range: 'A1:A3', format: new ConditionalFormatDate({ format: bold, rule: 'today' }) });
+  sheet.addConditionalFormat({ range: 'A1:A3', format: new ConditionalFormatDate({ format: bold, rule: 'lastMonth' }) });
+  assert.deepStrictEqual(formatted(workbook, { today: new Date(Date.UTC(2024, 2, 15, 9)) }), ['A2', 'A3']);
+  assert.deepStrictEqual(formatted(workbook, { today: null }), []);
+});
+
+test('the formats of matching rules are layered by priority', (t) => {
+  const workbook = column([5]);
+  const sheet = workbook.sheets[0];
+  const red = workbook.addFormat(new Format({ fontColor: '#FF0000', italic: true }));
+  sheet.addConditionalFormat({
+    range: 'A1',
+    format: new ConditionalFormatCell({ format: red, rule: { type: 'greaterThan', value: 1 } }),
+  });
+  sheet.addConditionalFormat({
+    range: 'A1',
+    priority: 1,
+    format: new ConditionalFormatCell({
+      format: new Format({ fontColor: '#0000FF', bold: true }),
+      rule: { type: 'greaterThan', value: 2 },
+    }),
+  });
+  const [{ cells }] = workbook.evaluateConditionalFormats();
+  assert.strictEqual(cells.length, 1);
+  assert.strictEqual(cells[0].format?.fontColor, '#0000FF');
+  assert.strictEqual(cells[0].format?.bold, true);
+  assert.strictEqual(cells[0].format?.italic, true);
+});
+
+test('a rule with stopIfTrue hides the rules after it', (t) => {
+  const workbook = column([5, 0]);
+  const sheet = workbook.sheets[0];
+  sheet.addConditionalFormatGroup({
+    range: 'A1:A2',
+    rules: [
+      new ConditionalFormatCell({ format: bold, stopIfTrue: true, rule: { type: 'greaterThan', value: 1 } }),
+      new ConditionalFormatCell({ format: new Format({ italic: true }), rule: { type: 'lessThan', value: 10 } }),
+    ],
+  });
+  const [{ cells }] = workbook.evaluateConditionalFormats();
+  assert.deepStrictEqual(
+    cells.map((cell) => [cell.row, cell.format?.bold, cell.format?.italic]),
+    [
+      [0, true, undefined],
+      [1, undefined, true],
+    ],
+  );
+});
+
+test('color scales, data bars and icon sets', (t) => {
+  const workbook = column([0, 50, 100, -50]);
+  const sheet = workbook.sheets[0];
+  sheet.addConditionalFormat({
+    range: 'A1:A3',
+    format: new ConditionalFormatTwoColorScale({ minColor: '#000000', maxColor: '#FFFFFF' }),
+  });
+  sheet.addConditionalFormat({ range: 'A1:A4', format: new ConditionalFormatDataBar({}) });
+  const icon = (/** @type {number} */ value) =>
+    new ConditionalFormatCustomIcon({ iconRule: { type: 'number', value } });
+  sheet.addConditionalFormat({
+    range: 'A1:A4',
+    format: new ConditionalFormatIconSet({ iconType: 'threeArrows', icons: [icon(0), icon(0), icon(60)] }),
+  });
+  const [{ cells }] = workbook.evaluateConditionalFormats();
+
+  assert.deepStrictEqual(
+    cells.map((cell) => cell.color),
+    ['#000000', '#808080', '#FFFFFF', undefined],
+  );
+  assert.deepStrictEqual(
+    cells.map((cell) => [cell.dataBar?.start, cell.dataBar?.end]),
+    [
+      [1 / 3, 1 / 3],
+      [1 / 3, 2 / 3],
+      [1 / 3, 1],
+      [0, 1 / 3],
+    ],
+  );
+  assert.deepStrictEqual(
+    cells.map((cell) => cell.icon?.index),
+    [1, 1, 2, 0],
+  );
+  assert.strictEqual(cells[0].icon?.iconType, 'threeArrows');
+});
+
+test('a three color scale goes through its middle color', (t) => {
+  const workbook = column([0, 5, 10]);
+  workbook.sheets[0].addConditionalFormat({
+    range: 'A1:A3',
+    format: new ConditionalFormatThreeColorScale({ minColor: '#FF0000', midColor: '#00FF00', maxColor: '#0000FF' }),
+  });
+  const [{ cells }] = workbook.evaluateConditionalFormats();
+  assert.deepStrictEqual(
+    cells.map((cell) => cell.color),
+    ['#FF0000', '#00FF00', '#0000FF'],
+  );
+});
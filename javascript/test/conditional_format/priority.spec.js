@@ -0,0 +1,99 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const {
+  Workbook,
+  ConditionalFormatCell,
+  ConditionalFormatBlank,
+  ConditionalFormatDataBar,
+  Format,
+} = require('../../src/index');
+const { readEntry } = require('../util');
+
+/**
+ * @param {number} value
+ * @returns {ConditionalFormatCell}
+ */
+const greaterThan = (value) =>
+  new ConditionalFormatCell({
+    format: new Format({ bold: true }),
+    rule: { type: 'greaterThan', value },
+  });
+
+/**
+ * The conditional format rules of the first sheet, in priority order.
+ * @param {Workbook} workbook
+ * @returns {string[]}
+ */
+function rules(workbook) {
+  const xml = readEntry(workbook.saveToBufferSync(), 'xl/worksheets/sheet1.xml');
+  const rules = [];
+  for (const [, sqref, body] of xml.matchAll(/<conditionalFormatting sqref="([^"]+)">(.*?)<\/conditionalFormatting>/g)) {
+    for (const [, attributes] of body.matchAll(/<cfRule ([^>]*)>/g)) {
+      const priority = Number(/priority="(\d+)"/.exec(attributes)?.[1]);
+      const type = /type="(\w+)"/.exec(attributes)?.[1];
+      const stop = attributes.includes('stopIfTrue="1"') ? ' stop' : '';
+      rules.push({ priority, rule: `${sqref} ${type}${stop}` });
+    }
+  }
+  rules.sort((a, b) => a.priority - b.priority);
+  assert.deepStrictEqual(
+    rules.map((rule) => rule.priority),
+    rules.map((_, index) => index + 1),
+  );
+  return rules.map((rule) => rule.rule);
+}
+
+test('conditional formats keep the order they are added in', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addConditionalFormat({ range: 'B1:B5', format: greaterThan(1) });
+  sheet.addConditionalFormat({ range: 'A1:A5', format: new ConditionalFormatBlank() });
+  sheet.addConditionalFormat({ range: 'B1:B5', format: new ConditionalFormatBlank() });
+  assert.deepStrictEqual(rules(workbook), ['B1:B5 cellIs', 'A1:A5 containsBlanks', 'B1:B5 containsBlanks']);
+});
+
+test('formats with a priority come first', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addConditionalFormat({ range: 'A1:A5', format: greaterThan(1) });
+  sheet.addConditionalFormat({ range: 'C1:C5', format: greaterThan(2), priority: 20 });
+  sheet.addConditionalFormat({ range: 'B1:B5', format: new ConditionalFormatDataBar(), priority: 10 });
+  assert.deepStrictEqual(rules(workbook), ['B1:B5 dataBar', 'C1:C5 cellIs', 'A1:A5 cellIs']);
+});
+
+test('a group evaluates its rules in order', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addConditionalFormat({ range: 'A1:A5', format: greaterThan(1) });
+  const last = greaterThan(10);
+  last.stopIfTrue = false;
+  sheet.addConditionalFormatGroup({
+    multiRange: 'C1:C5 E1:E5',
+    rules: [greaterThan(100), new ConditionalFormatBlank(), last],
+    stopIfTrue: true,
+    priority: 1,
+  });
+  assert.deepStrictEqual(rules(workbook), [
+    'C1:C5 E1:E5 cellIs stop',
+    'C1:C5 E1:E5 containsBlanks stop',
+    'C1:C5 E1:E5 cellIs',
+    'A1:A5 cellIs',
+  ]);
+  assert.strictEqual(last.multiRange, undefined);
+});
+
+test('priorities are checked', (t) => {
+  let workbook = new Workbook();
+  let sheet = workbook.addSheet();
+  sheet.addConditionalFormat({ range: 'A1:A5', format: greaterThan(1), priority: 2 });
+  sheet.addConditionalFormatGroup({ range: 'B1:B5', rules: [greaterThan(1)], priority: 2 });
+  assert.throws(() => workbook.saveToBufferSync(), /Two conditional formats have the priority 2/);
+
+  workbook = new Workbook();
+  sheet = workbook.addSheet();
+  sheet.addConditionalFormat({ range: 'A1:A5', format: greaterThan(1), priority: 0.5 });
+  assert.throws(() => workbook.saveToBufferSync(), /Invalid conditional format priority: 0.5/);
+
+  assert.throws(() => sheet.addConditionalFormatGroup({ range: 'A1', rules: [] }), /at least one rule/);
+});
@@ -0,0 +1,70 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, ConditionalFormatCell, ConditionalFormatBlank, Format } = require('../../src/index');
+const { readEntry } = require('../util');
+
+const red = new Format({ fontColor: { red: 255, green: 0, blue: 0 } });
+
+/**
+ * @param {number} value
+ * @returns {ConditionalFormatCell}
+ */
+const lessThan = (value) => new ConditionalFormatCell({ format: red, rule: { type: 'lessThan', value } });
+
+/**
+ * @param {Buffer} buffer
+ * @param {number} sheet
+ * @returns {string[]}
+ */
+function rules(buffer, sheet) {
+  const xml = readEntry(buffer, `xl/worksheets/sheet${sheet}.xml`);
+  return [...xml.matchAll(/<conditionalFormatting sqref="([^"]+)"><cfRule type="(\w+)"[^>]*><formula>([^<]*)</g)].map(
+    (match) => `${match[1]} ${match[2]} ${match[3]}`,
+  );
+}
+
+test('a conditional format added to the workbook is used by id on every sheet', (t) => {
+  const workbook = new Workbook();
+  const negative = workbook.addConditionalFormat(lessThan(0));
+  const first = workbook.addSheet();
+  const second = workbook.addSheet();
+  first.addConditionalFormat({ range: 'B2:B10', format: negative });
+  second.addConditionalFormat({ range: 'C1:C5', format: negative });
+  second.addConditionalFormatGroup({ range: 'D1:D5', rules: [negative, new ConditionalFormatBlank()] });
+
+  const buffer = workbook.saveToBufferSync();
+  assert.deepStrictEqual(rules(buffer, 1), ['B2:B10 cellIs 0']);
+  assert.deepStrictEqual(rules(buffer, 2), ['C1:C5 cellIs 0', 'D1:D5 cellIs 0']);
+});
+
+test('the same rule can be given under one id more than once', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const first = lessThan(0);
+  const second = lessThan(0);
+  second.id = first.id;
+  sheet.addConditionalFormat({ range: 'A1:A5', format: first });
+  workbook.addSheet().addConditionalFormat({ range: 'A1:A5', format: second });
+  assert.doesNotThrow(() => workbook.saveToBufferSync());
+});
+
+test('different rules under one id are an error', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const first = lessThan(0);
+  const second = lessThan(10);
+  second.id = first.id;
+  sheet.addConditionalFormat({ range: 'A1:A5', format: first });
+  workbook.addSheet().addConditionalFormat({ range: 'A1:A5', format: second });
+  assert.throws(
+    () => workbook.saveToBufferSync(),
+    new RegExp(`Conditional format id ${first.id} is used by two different conditional formats`),
+  );
+});
+
+test('an id that was not added to the workbook is an error', (t) => {
+  const workbook = new Workbook();
+  workbook.addSheet().addConditionalFormat({ range: 'A1:A5', format: 42 });
+  assert.throws(() => workbook.saveToBufferSync(), /Conditional format id 42 is not declared on the workbook/);
+});
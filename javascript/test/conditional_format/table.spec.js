@@ -0,0 +1,66 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, ConditionalFormatCell, Format, Table, TableColumn } = require('../../src/index');
+const { readEntry } = require('../util');
+
+/**
+ * A sheet with a "Sales" table in B2:D5, with a total row.
+ * @returns {{ workbook: Workbook, sheet: import('../../src/index').Sheet }}
+ */
+function salesWorkbook() {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addTableFromData({
+    firstRow: 1,
+    firstColumn: 1,
+    data: [
+      { product: 'Apples', margin: 0.2, cost: 10 },
+      { product: 'Pears', margin: -0.1, cost: 12 },
+    ],
+    table: new Table({
+      name: 'Sales',
+      totalRow: true,
+      columns: [
+        new TableColumn({ key: 'product', header: 'Product' }),
+        new TableColumn({ key: 'margin', header: 'Margin' }),
+        new TableColumn({ key: 'cost', header: 'Cost', totalFunction: { type: 'sum' } }),
+      ],
+    }),
+  });
+  return { workbook, sheet };
+}
+
+const negative = () =>
+  new ConditionalFormatCell({
+    format: new Format({ fontColor: { red: 255, green: 0, blue: 0 } }),
+    rule: { type: 'lessThan', value: 0 },
+  });
+
+/**
+ * @param {Workbook} workbook
+ * @returns {string[]}
+ */
+function formattedRanges(workbook) {
+  const xml = readEntry(workbook.saveToBufferSync(), 'xl/worksheets/sheet1.xml');
+  return [...xml.matchAll(/<conditionalFormatting sqref="([^"]+)"/g)].map((match) => match[1]);
+}
+
+test('a conditional format on a table column covers its data rows', (t) => {
+  const { workbook, sheet } = salesWorkbook();
+  sheet.addConditionalFormat({ table: 'sales', column: 'margin', format: negative() });
+  sheet.addConditionalFormat({ table: 'Sales', column: 'Cost', includeTotalRow: true, format: negative() });
+  assert.deepStrictEqual(formattedRanges(workbook), ['C3:C4', 'D3:D5']);
+});
+
+test('a missing table or column is an error', (t) => {
+  let { workbook, sheet } = salesWorkbook();
+  sheet.addConditionalFormat({ table: 'Costs', column: 'Margin', format: negative() });
+  assert.throws(() => workbook.saveToBufferSync(), /Table 'Costs' not found/);
+
+  ({ workbook, sheet } = salesWorkbook());
+  sheet.addConditionalFormat({ table: 'Sales', column: 'Price', format: negative() });
+  assert.throws(() => workbook.saveToBufferSync(), /Table 'Sales' has no column 'Price'/);
+
+  assert.throws(() => sheet.addConditionalFormat({ table: 'Sales', format: negative() }), /needs a column/);
+});
@@ -0,0 +1,211 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const fs = require('fs');
+const { Workbook, Sheet, Format, Table, TableColumn } = require('../src/index');
+const findRootDir = require('./util');
+const { readEntry } = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+
+/**
+ * The values of the cells of a sheet, by row and column.
+ * @param {Sheet} sheet
+ * @param {number} rows
+ * @param {number} cols
+ */
+function values(sheet, rows, cols) {
+  const value = (/** @type {number} */ row, /** @type {number} */ col) =>
+    sheet.cells.find((cell) => !cell.merged && cell.row === row && cell.col === col)?.value;
+  return [...Array(rows).keys()].map((row) => [...Array(cols).keys()].map((col) => value(row, col)));
+}
+
+const orders = [
+  'id,zip,amount,paid,shipped,note',
+  '1,02134,12.50,true,2024-03-15,"Fragile, ""glass"""',
+  '2,90210,-3e2,FALSE,2024-03-16T09:30:00,=B3&"x"',
+  '3,,"1,000",yes,soon,"two',
+  'lines"',
+].join('\r\n');
+
+test('fields are written with their detected types', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.importCsv({ source: Buffer.from(orders) });
+  const buffer = workbook.saveToBufferSync();
+
+  const [read] = Workbook.readFromBufferSync(buffer).sheets;
+  const [header, first, second, third] = values(read, 4, 6);
+  assert.deepStrictEqual(header, ['id', 'zip', 'amount', 'paid', 'shipped', 'note']);
+  assert.deepStrictEqual(first, [1, '02134', 12.5, true, new Date(Date.UTC(2024, 2, 15)), 'Fragile, "glass"']);
+  assert.deepStrictEqual(second.slice(0, 5), [2, 90210, -300, false, new Date(Date.UTC(2024, 2, 16, 9, 30))]);
+  assert.strictEqual(second[5].formula, '=B3&"x"');
+  assert.deepStrictEqual(third, [3, undefined, '1,000', 'yes', 'soon', 'two\r\nlines']);
+
+  const styles = readEntry(buffer, 'xl/styles.xml');
+  assert.match(styles, /formatCode="yyyy-mm-dd"/);
+  assert.match(styles, /formatCode="yyyy-mm-dd hh:mm:ss"/);
+});
+
+test('type hints, formats and a table over the imported range', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const amount = new Format({ numFmt: '0.00' });
+  sheet.importCsv({
+    source: Buffer.from(orders),
+    cell: 'B2',
+    types: { ID: 'string', amount: 'number', paid: 'boolean' },
+    formats: [undefined, undefined, amount],
+    headerFormat: new Format({ bold: true }),
+    table: new Table({ name: 'Orders', totalRow: true }),
+  });
+  const buffer = workbook.saveToBufferSync();
+
+  const [read] = Workbook.readFromBufferSync(buffer).sheets;
+  const rows = values(read, 5, 7).map((row) => row.slice(1));
+  assert.deepStrictEqual(rows[1].slice(0, 4), ['id', 'zip', 'amount', 'paid']);
+  assert.deepStrictEqual(rows[2].slice(0, 4), ['1', '02134', 12.5, true]);
+  assert.deepStrictEqual(rows[4].slice(0, 4), ['3', undefined, 1000, true]);
+  const cell = read.cells.find((cell) => cell.row === 2 && cell.col === 3);
+  assert.strictEqual(cell?.format?.numFmt, '0.00');
+  assert.strictEqual(read.cells.find((cell) => cell.row === 1 && cell.col === 1)?.format?.bold, true);
+
+  const table = readEntry(buffer, 'xl/tables/table1.xml');
+  assert.match(table, /name="Orders"/);
+  assert.match(table, /ref="B2:G6"/);
+  assert.match(table, /<tableColumn id="1" name="id"/);
+});
+
+test('TSV files in other encodings, without a header', async (t) => {
+  const text = 'name\tcity\nJosé\tSão Paulo\n';
+  const utf16 = Buffer.concat([Buffer.from([0xff, 0xfe]), Buffer.from(text, 'utf16le')]);
+  fs.writeFileSync(`${path}/import.tsv`, utf16);
+
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.importCsv({ source: `${path}/import.tsv`, delimiter: '\t', header: false });
+  sheet.importCsv({
+    source: Buffer.from(text, 'latin1'),
+    row: 0,
+    col: 3,
+    delimiter: '\t',
+    encoding: 'latin1',
+    header: false,
+    table: new Table({ columns: [new TableColumn({ header: 'A' }), new TableColumn({ header: 'B' })] }),
+  });
+  const buffer = await workbook.saveToBuffer();
+
+  const [read] = Workbook.readFromBufferSync(buffer).sheets;
+  assert.deepStrictEqual(values(read, 3, 5), [
+    ['name', 'city', undefined, 'A', 'B'],
+    ['José', 'São Paulo', undefined, 'name', 'city'],
+    [undefined, undefined, undefined, 'José', 'São Paulo'],
+  ]);
+});
+
+test('invalid imports are rejected', async (t) => {
+  const sheet = new Sheet('Sheet1');
+  assert.throws(() => sheet.importCsv({ source: '', row: -1 }), /Invalid row index/);
+
+  const save = (/** @type {Object} */ opts) => {
+    const workbook = new Workbook();
+    workbook.addSheet().importCsv({ source: Buffer.from('a;b'), ...opts });
+    return workbook;
+  };
+  assert.throws(() => save({ delimiter: ';;' }).saveToBufferSync(), /delimiter must be one character/);
+  assert.throws(() => save({ types: ['money'] }).saveToBufferSync(), /Unknown CSV column type "money"/);
+  assert.throws(() => save({ encoding: 'ebcdic' }).saveToBufferSync(), /Unknown encoding "ebcdic"/);
+  assert.throws(() => save({ source: Buffer.from([0xff, 0x41]) }).saveToBufferSync(), /Invalid UTF-8/);
+  await assert.rejects(
+    save({ source: `${path}/does_not_exist.csv` }).saveToBuffer(),
+    /Cannot read CSV file .*does_not_exist.csv/,
+  );
+  // A string is a path, even when it holds CSV text
+  assert.throws(() => save({ source: 'a,b\n1,2' }).saveToBufferSync(), /Cannot read CSV file a,b/);
+});
+
+test('imported cells are checked like written cells', (t) => {
+  const csv = Buffer.from('a,b\n1,2\n3,4');
+  /** @param {(sheet: Sheet) => void} write */
+  const save = (write) => {
+    const workbook = new Workbook();
+    const sheet = workbook.addSheet();
+    sheet.setDuplicateCellPolicy('error');
+    write(sheet);
+    return () => workbook.saveToBufferSync();
+  };
+
+  assert.throws(
+    save((sheet) => {
+      sheet.writeString('B2', 'x');
+      sheet.importCsv({ source: csv });
+    }),
+    /cell B2 is written twice/,
+  );
+  assert.throws(
+    save((sheet) => {
+      sheet.importCsv({ source: csv });
+      sheet.importCsv({ source: csv, cell: 'B3' });
+    }),
+    /cell B3 is written twice/,
+  );
+  assert.throws(
+    save((sheet) => {
+      sheet.writeMergedString({ range: 'B2:C3', value: 'merged', format: new Format({}) });
+      sheet.importCsv({ source: csv });
+    }),
+    /cell B2 overlaps merged range B2:C3/,
+  );
+  assert.throws(
+    save((sheet) => {
+      sheet.addTableFromData({ range: 'A2', data: [{ x: 1 }] });
+      sheet.importCsv({ source: csv });
+    }),
+    /cell A2 overlaps table A2:A3/,
+  );
+  assert.throws(
+    save((sheet) => {
+      sheet.importCsv({ source: csv, table: new Table({}) });
+      sheet.addTable({ range: 'B3:C5', table: new Table({}) });
+    }),
+    /CSV table A1:B3 overlaps table B3:C5/,
+  );
+});
+
+test('imported cells follow the duplicate cell policy in the order they are written', (t) => {
+  const csv = Buffer.from('a,b\n1,2');
+  /**
+   * @param {import('../src/models/sheet').DuplicateCellPolicy} policy
+   * @param {boolean} cellFirst
+   */
+  const b2 = (policy, cellFirst) => {
+    const workbook = new Workbook();
+    const sheet = workbook.addSheet();
+    sheet.setDuplicateCellPolicy(policy);
+    if (cellFirst) sheet.writeString('B2', 'cell');
+    sheet.importCsv({ source: csv });
+    if (!cellFirst) sheet.writeString('B2', 'cell');
+    const [read] = Workbook.readFromBufferSync(workbook.saveToBufferSync()).sheets;
+    return values(read, 2, 2)[1][1];
+  };
+
+  assert.strictEqual(b2('lastWins', true), 2);
+  assert.strictEqual(b2('lastWins', false), 'cell');
+  assert.strictEqual(b2('firstWins', true), 'cell');
+  assert.strictEqual(b2('firstWins', false), 2);
+});
+
+test('fields after a = are only formulas when they parse', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.importCsv({ source: Buffer.from('a\n==== x ====\n=\n"=SUM(1,2)"\n=(1+') });
+  const buffer = workbook.saveToBufferSync();
+  assert.match(readEntry(buffer, 'xl/worksheets/sheet1.xml'), /<c r="A4"><f>SUM\(1,2\)<\/f>/);
+  const [read] = Workbook.readFromBufferSync(buffer).sheets;
+  const [a2, a3, , a5] = values(read, 5, 1).slice(1).map(([value]) => value);
+  assert.deepStrictEqual([a2, a3, a5], ['==== x ====', '=', '=(1+']);
+
+  const formulas = new Workbook();
+  formulas.addSheet().importCsv({ source: Buffer.from('a\nSUM(1;2)'), types: ['formula'] });
+  assert.throws(() => formulas.saveToBufferSync(), /CSV field at A2 is not a valid formula: Unexpected ';'/);
+});
@@ -0,0 +1,54 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format } = require('../src/index');
+
+const bold = new Format({ bold: true });
+const currency = new Format({ numFmt: '$#,##0.00' });
+
+/**
+ * @param {import('../src/models/sheet').DuplicateCellPolicy} policy
+ */
+function writeTwice(policy) {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.setDuplicateCellPolicy(policy);
+  sheet.writeNumber('A1', 1, currency);
+  sheet.writeNumber('A1', 2, bold);
+  return workbook;
+}
+
+test('the last write wins by default', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  assert.strictEqual(sheet.duplicateCellPolicy, 'lastWins');
+  sheet.writeString(0, 0, 'first');
+  sheet.writeString(0, 0, 'second');
+  const [read] = Workbook.readFromBufferSync(workbook.saveToBufferSync()).sheets;
+  assert.deepStrictEqual(
+    read.cells.map((cell) => cell.value),
+    ['second'],
+  );
+});
+
+test('duplicate writes are an error with the error policy', (t) => {
+  assert.throws(() => writeTwice('error').saveToBufferSync(), /cell A1 is written twice/);
+});
+
+test('lastWins and firstWins keep a single write', (t) => {
+  assert(writeTwice('lastWins').saveToBufferSync().length > 0);
+  assert(writeTwice('firstWins').saveToBufferSync().length > 0);
+  assert.strictEqual(writeTwice('lastWins').uniqueFormatCount(), 2);
+});
+
+test('mergeFormat layers the formats of both writes', (t) => {
+  const workbook = writeTwice('mergeFormat');
+  // currency, bold and currency + bold
+  assert.strictEqual(workbook.uniqueFormatCount(), 3);
+  assert(workbook.saveToBufferSync().length > 0);
+});
+
+test('unknown policies are rejected', (t) => {
+  // @ts-ignore
+  assert.throws(() => writeTwice('random').saveToBufferSync(), /Invalid duplicateCellPolicy: random/);
+});
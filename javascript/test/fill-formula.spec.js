@@ -0,0 +1,64 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Formula, Format } = require('../src/index');
+const { readEntry } = require('./util');
+
+/**
+ * @param {Workbook} workbook
+ * @returns {string[]}
+ */
+function formulas(workbook) {
+  const xml = readEntry(workbook.saveToBufferSync(), 'xl/worksheets/sheet1.xml');
+  return [...xml.matchAll(/<f>(.*?)<\/f>/g)].map((match) => match[1]);
+}
+
+test('filling down moves relative rows', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.fillFormula({ range: 'D2:D4', formula: '=B2*C2' });
+  assert.deepStrictEqual(formulas(workbook), ['B2*C2', 'B3*C3', 'B4*C4']);
+});
+
+test('anchored parts stay fixed', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  workbook.addSheet();
+  sheet.fillFormula({
+    firstRow: 1,
+    lastRow: 2,
+    firstCol: 1,
+    lastCol: 2,
+    formula: new Formula({ formula: '=SUM($A2:A$1)*$B$1+Sheet2!C3' }),
+  });
+  assert.deepStrictEqual(formulas(workbook), [
+    'SUM($A2:A$1)*$B$1+Sheet2!C3',
+    'SUM($A2:B$1)*$B$1+Sheet2!D3',
+    'SUM($A3:A$1)*$B$1+Sheet2!C4',
+    'SUM($A3:B$1)*$B$1+Sheet2!D4',
+  ]);
+});
+
+test('the anchor can be outside the range', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.fillFormula({ range: 'A1:A2', anchor: 'A3', formula: '=A2+"A2"+C:C' });
+  assert.deepStrictEqual(formulas(workbook), ['#REF!+"A2"+C:C', 'A1+"A2"+C:C']);
+});
+
+test('future functions and formats are kept', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const formula = new Formula({ formula: '=STDEV.S(A1:D1)', useFutureFunctions: true });
+  sheet.fillFormula({ range: 'E1:E2', formula, format: new Format({ bold: true }) });
+  assert.deepStrictEqual(formulas(workbook), ['_xlfn.STDEV.S(A1:D1)', '_xlfn.STDEV.S(A2:D2)']);
+  assert.strictEqual(workbook.uniqueFormatCount(), 1);
+});
+
+test('fill formulas take part in the overlap check', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.fillFormula({ range: 'A1:A5', formula: '=1' });
+  sheet.writeNumber('A3', 1);
+  assert.throws(() => workbook.saveToBufferSync(), /cell A3 overlaps fill formula A1:A5/);
+});
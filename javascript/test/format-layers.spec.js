@@ -0,0 +1,50 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format } = require('../src/index');
+const findRootDir = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+const fs = require('fs');
+
+test('save to file with composed row, column and cell formats', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.setComposeFormats();
+
+  sheet.addColumnConfig({ index: 1, format: new Format({ numFmt: '$#,##0.00' }) });
+  sheet.addRowConfig({ index: 2, format: new Format({ italic: true }) });
+
+  sheet.writeNumber(1, 1, 10);
+  sheet.writeNumber(2, 1, 20);
+  sheet.writeNumber(3, 1, 30, new Format({ bold: true }));
+
+  // currency, italic, bold, currency + italic, currency + bold
+  assert.strictEqual(workbook.uniqueFormatCount(), 5);
+  await workbook.saveToFile(`${path}/format_layers.xlsx`);
+  assert(fs.existsSync(`${path}/format_layers.xlsx`), 'file exists');
+});
+
+test('cell properties win over the column format', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.setComposeFormats();
+
+  sheet.addColumnConfig({ index: 0, format: new Format({ bold: true, italic: true }) });
+  const plain = new Format({ bold: false });
+  sheet.writeString(0, 0, 'not bold', plain);
+  sheet.writeColumns({ row: 1, col: 0, columns: [new Float64Array([1, 2, 3])] });
+
+  assert.strictEqual(workbook.uniqueFormatCount(), 3);
+  assert(workbook.saveToBufferSync().length > 0);
+});
+
+test('formats are not composed unless enabled', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  sheet.addColumnConfig({ index: 0, format: new Format({ numFmt: '0.0%' }) });
+  sheet.writeNumber(0, 0, 0.5, new Format({ bold: true }));
+
+  assert.strictEqual(workbook.uniqueFormatCount(), 2);
+});
@@ -0,0 +1,94 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format } = require('../src/index');
+const findRootDir = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+const fs = require('fs');
+
+test('save to file with alignment and text formats', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  const wrapped = new Format({
+    textWrap: true,
+    align: 'center',
+    verticalAlign: 'top',
+  });
+  const rotated = new Format({ rotation: 45 });
+  const stacked = new Format({ rotation: 270 });
+  const shrink = new Format({ shrink: true });
+  const superscript = new Format({ fontScript: 'superscript' });
+  const rightToLeft = new Format({ readingDirection: 'rightToLeft' });
+  const quoted = new Format({ quotePrefix: true });
+  const heading = new Format({ themeFont: 'major', fontSize: 16 });
+
+  sheet.writeString(0, 0, 'A long header that should wrap', wrapped);
+  sheet.writeString(0, 1, 'Rotated', rotated);
+  sheet.writeString(0, 2, 'Stacked', stacked);
+  sheet.writeString(0, 3, 'Shrink this text to fit', shrink);
+  sheet.writeString(0, 4, '2', superscript);
+  sheet.writeString(0, 5, 'مرحبا', rightToLeft);
+  sheet.writeString(0, 6, '=not a formula', quoted);
+  sheet.writeString(0, 7, 'Heading', heading);
+
+  await workbook.saveToFile(`${path}/format_alignment.xlsx`);
+  assert(fs.existsSync(`${path}/format_alignment.xlsx`), 'file exists');
+});
+
+test('format rejects invalid rotation', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeString(0, 0, 'Rotated', new Format({ rotation: 120 }));
+
+  assert.throws(() => workbook.saveToBufferSync());
+});
+
+test('identical formats are counted once', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const other = workbook.addSheet();
+
+  sheet.writeString(0, 0, 'a', new Format({ bold: true }));
+  sheet.writeString(0, 1, 'b', new Format({ bold: true }));
+  other.writeString(0, 0, 'c', new Format({ bold: true }));
+  other.writeString(0, 1, 'd', new Format({ id: 7, italic: true }));
+  other.writeString(0, 2, 'e', new Format({ id: 7, italic: true }));
+
+  assert.strictEqual(workbook.uniqueFormatCount(), 2);
+  assert(workbook.saveToBufferSync().length > 0);
+});
+
+test('format rejects two different formats with the same id', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeString(0, 0, 'a', new Format({ id: 1, bold: true }));
+  sheet.writeString(0, 1, 'b', new Format({ id: 1, italic: true }));
+
+  assert.throws(() => workbook.saveToBufferSync(), /Format id 1/);
+  assert.throws(() => workbook.uniqueFormatCount(), /Format id 1/);
+});
+
+test('workbook formats can be referenced by id from any sheet', async (t) => {
+  const workbook = new Workbook();
+  const bold = workbook.addFormat(new Format({ bold: true }));
+  const currency = workbook.addFormat(new Format({ numFmt: '$#,##0.00' }));
+
+  for (let i = 0; i < 3; i++) {
+    const sheet = workbook.addSheet();
+    sheet.writeString(0, 0, 'Total', bold);
+    sheet.writeNumber(0, 1, 1234.5, currency);
+  }
+
+  assert.strictEqual(workbook.uniqueFormatCount(), 2);
+  await workbook.saveToFile(`${path}/format_shared.xlsx`);
+  assert(fs.existsSync(`${path}/format_shared.xlsx`), 'file exists');
+});
+
+test('format rejects ids that were not added to the workbook', (t) => {
+  const workbook = new Workbook();
+  workbook.addSheet().writeString(0, 0, 'Total', 42);
+
+  assert.throws(() => workbook.saveToBufferSync(), /Format id 42 is not declared/);
+});
@@ -0,0 +1,123 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Formula, Sheet } = require('../src/index');
+const { readEntry } = require('./util');
+
+/**
+ * The cached result of every formula cell of a sheet, by cell.
+ * @param {Workbook} workbook
+ * @param {number} [sheet=1]
+ * @returns {Record<string, string | undefined>}
+ */
+function results(workbook, sheet = 1) {
+  const xml = readEntry(workbook.saveToBufferSync(), `xl/worksheets/sheet${sheet}.xml`);
+  /** @type {Record<string, string | undefined>} */
+  const results = {};
+  for (const match of xml.matchAll(/<c r="([A-Z]+\d+)"[^>]*><f[^>]*>.*?<\/f><v>(.*?)<\/v><\/c>/g)) {
+    results[match[1]] = match[2];
+  }
+  return results;
+}
+
+test('arithmetic and aggregates are computed', (t) => {
+  const workbook = new Workbook();
+  workbook.setEvaluateFormulas();
+  const sheet = workbook.addSheet();
+  [4, 8, 15].forEach((value, row) => sheet.writeNumber(row, 0, value));
+  sheet.writeString(3, 0, 'text');
+  sheet.writeFormula(0, 1, new Formula({ formula: '=SUM(A1:A4)' }));
+  sheet.writeFormula(1, 1, new Formula({ formula: '=AVERAGE(A:A)/2' }));
+  sheet.writeFormula(2, 1, new Formula({ formula: '=COUNT(A1:A4)&"/"&COUNTA(A1:A4)' }));
+  sheet.writeFormula(3, 1, new Formula({ formula: '=MAX(A1:A3)-MIN(A1:A3)+0.1+0.2' }));
+  sheet.writeFormula(4, 1, new Formula({ formula: '=IF(B1>20,"big","small")' }));
+  sheet.writeFormula(5, 1, new Formula({ formula: '=SUMIF(A1:A3,">5")+COUNTIF(A1:A4,"t*")' }));
+  assert.deepStrictEqual(results(workbook), {
+    B1: '27',
+    B2: '4.5',
+    B3: '3/4',
+    B4: '11.3',
+    B5: 'big',
+    B6: '24',
+  });
+});
+
+test('lookups, text and dates are computed', (t) => {
+  const workbook = new Workbook();
+  workbook.setEvaluateFormulas();
+  const sheet = workbook.addSheet();
+  sheet.writeColumns({ cell: 'A1', columns: [['x', 'y', 'z'], new Float64Array([10, 20, 30])] });
+  sheet.writeDate(3, 0, new Date(Date.UTC(2024, 0, 31)));
+  sheet.writeFormula(0, 2, new Formula({ formula: '=VLOOKUP("Y",A1:B3,2,FALSE)' }));
+  sheet.writeFormula(1, 2, new Formula({ formula: '=INDEX(B1:B3,MATCH("z",A1:A3,0))' }));
+  sheet.writeFormula(2, 2, new Formula({ formula: '=XLOOKUP("q",A1:A3,B1:B3,"none")' }));
+  sheet.writeFormula(3, 2, new Formula({ formula: '=PROPER(TRIM("  hello   world "))&LEN(A1)' }));
+  sheet.writeFormula(4, 2, new Formula({ formula: '=EOMONTH(A4,1)-DATE(2024,2,1)+MONTH(A4)' }));
+  sheet.writeFormula(5, 2, new Formula({ formula: '=ISNA(MATCH("q",A1:A3,0))' }));
+  assert.deepStrictEqual(results(workbook), {
+    C1: '20',
+    C2: '30',
+    C3: 'none',
+    C4: 'Hello World1',
+    C5: '29',
+    C6: '1',
+  });
+});
+
+test('TEXT shows values with number formats', (t) => {
+  const workbook = new Workbook();
+  workbook.setEvaluateFormulas();
+  const sheet = workbook.addSheet();
+  sheet.writeDate(0, 0, new Date(Date.UTC(2024, 2, 5)));
+  sheet.writeFormula(0, 1, new Formula({ formula: '=TEXT(1234.5,"#,##0.00")' }));
+  sheet.writeFormula(1, 1, new Formula({ formula: '=TEXT(A1,"dd/mm/yyyy")' }));
+  sheet.writeFormula(2, 1, new Formula({ formula: '=TEXT("0.256","0.0%")&" "&TEXT(-3,"0;(0)")' }));
+  sheet.writeFormula(3, 1, new Formula({ formula: '=TEXT("abc","\\[@\\]")&TEXT(TRUE,"0")' }));
+  sheet.writeFormula(4, 1, new Formula({ formula: '=TEXT(12345678,"0.0,,""M""")&TEXT(1234567,"#,##0,")' }));
+  assert.deepStrictEqual(results(workbook), {
+    B1: '1,234.50',
+    B2: '05/03/2024',
+    B3: '25.6% (3)',
+    B4: '[abc]TRUE',
+    B5: '12.3M1,235',
+  });
+});
+
+test('formulas follow other formulas and sheets', (t) => {
+  const workbook = new Workbook();
+  workbook.setEvaluateFormulas();
+  const sheet = workbook.addSheet();
+  const data = new Sheet('Data Sheet');
+  workbook.pushSheet(data);
+  data.writeNumber(0, 0, 2);
+  sheet.writeFormula(0, 0, new Formula({ formula: "='Data Sheet'!A1*3" }));
+  sheet.fillFormula({ range: 'A2:A1000', formula: '=A1+1' });
+  const computed = results(workbook);
+  assert.strictEqual(computed.A1, '6');
+  assert.strictEqual(computed.A1000, '1005');
+});
+
+test('formulas it does not understand keep the default result', (t) => {
+  const workbook = new Workbook();
+  workbook.setEvaluateFormulas();
+  const sheet = workbook.addSheet();
+  sheet.writeFormula(0, 0, new Formula({ formula: '=TODAY()' }));
+  sheet.writeFormula(1, 0, new Formula({ formula: '=A1+1' }));
+  sheet.writeFormula(2, 0, new Formula({ formula: '=A3+1' }));
+  sheet.writeFormula(3, 0, new Formula({ formula: '=1/0' }));
+  sheet.writeFormula(4, 0, new Formula({ formula: '=1+1', result: '3' }));
+  assert.deepStrictEqual(results(workbook), {
+    A1: '0',
+    A2: '0',
+    A3: '0',
+    A4: '#DIV/0!',
+    A5: '3',
+  });
+});
+
+test('results are only computed when asked for', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeFormula(0, 0, new Formula({ formula: '=1+1' }));
+  assert.deepStrictEqual(results(workbook), { A1: '0' });
+});
@@ -0,0 +1,76 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const fs = require('fs');
+const { Workbook, Formula } = require('../src/index');
+const { readEntry } = require('./util');
+const findRootDir = require('./util');
+const path = findRootDir(__dirname) + '/temp';
+
+/**
+ * The type and cached value of every formula cell of the first sheet, by cell.
+ * @param {Buffer} buffer
+ * @returns {Record<string, string[]>}
+ */
+function results(buffer) {
+  const xml = readEntry(buffer, 'xl/worksheets/sheet1.xml');
+  /** @type {Record<string, string[]>} */
+  const results = {};
+  for (const match of xml.matchAll(/<c r="([A-Z]+\d+)"([^>]*)><f[^>]*>.*?<\/f><v>(.*?)<\/v><\/c>/g)) {
+    const type = match[2].match(/ t="(\w+)"/);
+    results[match[1]] = [type ? type[1] : 'n', match[3]];
+  }
+  return results;
+}
+
+/**
+ * @returns {Workbook}
+ */
+function workbookWithResults() {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeFormula(0, 0, new Formula({ formula: '=40+2', result: 42 }));
+  sheet.writeFormula(1, 0, new Formula({ formula: '=1<2', result: true }));
+  sheet.writeFormula(2, 0, new Formula({ formula: '=1>2', result: false }));
+  sheet.writeFormula(3, 0, new Formula({ formula: '=NA()', result: '#N/A' }));
+  sheet.writeFormula(4, 0, new Formula({ formula: '="4"&"2"', result: '42' }));
+  sheet.writeFormula(5, 0, new Formula({ formula: '="a"&"<b>"', result: 'a<b>' }));
+  sheet.writeFormula(6, 0, new Formula({ formula: '=XLOOKUP(1,B1:B2,C1:C2)=1', result: true }));
+  return workbook;
+}
+
+const expected = {
+  A1: ['n', '42'],
+  A2: ['b', '1'],
+  A3: ['b', '0'],
+  A4: ['e', '#N/A'],
+  A5: ['str', '42'],
+  A6: ['str', 'a&lt;b&gt;'],
+  A7: ['b', '1'],
+};
+
+test('results keep their type', (t) => {
+  assert.deepStrictEqual(results(workbookWithResults().saveToBufferSync()), expected);
+});
+
+test('results keep their type in saved files', async (t) => {
+  const fileName = path + '/formula-result-types.xlsx';
+  await workbookWithResults().saveToFile(fileName);
+  assert.deepStrictEqual(results(fs.readFileSync(fileName)), expected);
+});
+
+test('computed results keep their type', (t) => {
+  const workbook = new Workbook();
+  workbook.setEvaluateFormulas();
+  const sheet = workbook.addSheet();
+  sheet.writeNumber(0, 0, 3);
+  sheet.fillFormula({ range: 'B1:B2', formula: '=A1>2' });
+  sheet.writeFormula(2, 1, new Formula({ formula: '=1/0' }));
+  sheet.writeFormula(3, 1, new Formula({ formula: '="0"&A1' }));
+  assert.deepStrictEqual(results(workbook.saveToBufferSync()), {
+    B1: ['b', '1'],
+    B2: ['b', '0'],
+    B3: ['e', '#DIV/0!'],
+    B4: ['str', '03'],
+  });
+});
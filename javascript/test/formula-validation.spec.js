@@ -0,0 +1,95 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Formula, Sheet } = require('../src/index');
+const { readEntry } = require('./util');
+
+/**
+ * @param {string[]} formulas
+ * @returns {Workbook}
+ */
+function workbookWith(formulas) {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  workbook.pushSheet(new Sheet("Q1 O'Brien"));
+  formulas.forEach((formula, row) => sheet.writeFormula(row, 0, new Formula({ formula })));
+  return workbook;
+}
+
+/**
+ * @param {Workbook} workbook
+ * @returns {string[]}
+ */
+function writtenFormulas(workbook) {
+  const xml = readEntry(workbook.saveToBufferSync(), 'xl/worksheets/sheet1.xml');
+  return [...xml.matchAll(/<f[^>]*>(.*?)<\/f>/g)].map((match) => match[1]);
+}
+
+test('valid formulas are written', (t) => {
+  const workbook = workbookWith([
+    '=SUM(B1:B10)*-2^2%',
+    '=IF(A1>=1,"a ""quoted"" text",{1,2;3,4})',
+    "='Q1 O''Brien'!A1+Sheet1!C:C",
+    '=LET(double,LAMBDA(x,x*2),double(B1))',
+    '=IFERROR(VLOOKUP(B1,$D$1:$E$9,2,FALSE),)',
+    '=SUM(B1:B2 B2:C2)',
+    '=LAMBDA(x,x+1)(2)*(LAMBDA(x,y,x*y))(3,4)',
+  ]);
+  assert.strictEqual(writtenFormulas(workbook).length, 7);
+});
+
+test('newer functions get their prefix automatically', (t) => {
+  const workbook = workbookWith(['=xlookup(B1,C:C,D:D)', '=CONCAT("a",STDEV.S(B1:B4))', '=SORT(B1:B4)']);
+  assert.deepStrictEqual(writtenFormulas(workbook), [
+    '_xlfn.XLOOKUP(B1,C:C,D:D)',
+    '_xlfn.CONCAT("a",_xlfn.STDEV.S(B1:B4))',
+    '_xlfn._xlws.SORT(B1:B4)',
+  ]);
+});
+
+test('errors name the cell and the problem', (t) => {
+  const workbook = workbookWith(['=SUMM(B1)', '=SUM(B1', '=SUM(B1;B2)', '=Missing!A1', '=1+', '=LAMBDA(x,x)(1']);
+  assert.throws(() => workbook.saveToBufferSync(), {
+    message: [
+      'Invalid formulas:',
+      "  Sheet1!A2 =SUM(B1: Missing ')' to close SUM",
+      "  Sheet1!A3 =SUM(B1;B2): Unexpected ';' at position 7, arguments are separated with ','",
+      "  Sheet1!A4 =Missing!A1: Unknown sheet 'Missing'",
+      '  Sheet1!A5 =1+: Unexpected end of the formula',
+      "  Sheet1!A6 =LAMBDA(x,x)(1: Missing ')' to close the call",
+    ].join('\n'),
+  });
+});
+
+test('unknown functions are written and reported as warnings', (t) => {
+  const workbook = workbookWith(['=MYUDF(1)', '=_xll.FOO(1)', '=SUMM(B1)+xlookup(B1,C:C,D:D)']);
+  assert.deepStrictEqual(writtenFormulas(workbook), ['MYUDF(1)', '_xll.FOO(1)', 'SUMM(B1)+_xlfn.XLOOKUP(B1,C:C,D:D)']);
+  assert.deepStrictEqual(workbook.formulaWarnings, [
+    'Sheet1!A1 =MYUDF(1): Unknown function MYUDF',
+    'Sheet1!A3 =SUMM(B1)+xlookup(B1,C:C,D:D): Unknown function SUMM',
+  ]);
+});
+
+test('defined names can be called', (t) => {
+  const workbook = workbookWith(['=WITHTAX(B1)', '=net(B1)']);
+  workbook.defineName('WithTax', '=LAMBDA(x,x*1.2)');
+  workbook.defineName('Sheet1!Net', '=LAMBDA(x,x/1.2)');
+  assert.deepStrictEqual(writtenFormulas(workbook), ['WITHTAX(B1)', 'net(B1)']);
+  assert.deepStrictEqual(workbook.formulaWarnings, []);
+  const xml = readEntry(workbook.saveToBufferSync(), 'xl/workbook.xml');
+  assert.match(xml, /<definedName name="WithTax">_xlfn.LAMBDA\(/);
+});
+
+test('array and fill formulas are checked too', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addArrayFormula({ range: 'A1:A3', formula: new Formula({ formula: '{=SUM(B1:D1}' }) });
+  sheet.fillFormula({ range: 'C1:C3', formula: '=B1*' });
+  assert.throws(() => workbook.saveToBufferSync(), /Sheet1!A1:A3 {=SUM\(B1:D1}: Missing '\)' to close SUM\n  Sheet1!C1:C3 =B1\*/);
+});
+
+test('validation can be turned off', (t) => {
+  const workbook = workbookWith(['=MYADDIN(B1)']);
+  workbook.setValidateFormulas(false);
+  assert.deepStrictEqual(writtenFormulas(workbook), ['MYADDIN(B1)']);
+});
@@ -0,0 +1,119 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format, Formula, Link, Table, Border } = require('../src/index');
+
+/**
+ * The cells of the rendered rows, as `[attributes, content]` pairs.
+ * @param {string} html
+ */
+function cells(html) {
+  return [...html.matchAll(/<tr[^>]*>(.*?)<\/tr>/g)].map((row) =>
+    [...row[1].matchAll(/<td([^>]*)>(.*?)<\/td>/g)].map((cell) => [cell[1], cell[2]]),
+  );
+}
+
+test('values, merged cells and sizes', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const title = new Format({ bold: true, align: 'center', fontSize: 14, backgroundColor: '#DDEBF7' });
+  sheet.writeMergedCell({ range: 'A1:C1', value: 'Q1 <report>', cellType: 'string', format: title });
+  sheet.writeNumber(1, 0, 1234.5, new Format({ numFmt: '#,##0.00', fontColor: { theme: 4 } }));
+  sheet.writeDate(1, 1, new Date(Date.UTC(2024, 2, 15)), new Format({ numFmt: 'd mmm yyyy' }));
+  sheet.writeFormula(1, 2, new Formula({ formula: '=A2*2' }));
+  sheet.writeLink(2, 0, new Link('https://example.com/?a=1&b=2', 'Site'));
+  sheet.writeString(2, 1, 'boxed', new Format({ bottomBorder: new Border('medium', '#FF0000'), italic: true }));
+  sheet.addColumnConfig({ index: 0, size: { value: 20 } });
+  sheet.addColumnConfig({ index: 2, size: { value: 100, unit: 'px' } });
+  sheet.addRowConfig({ index: 0, size: { value: 30 } });
+
+  const html = await workbook.renderToHtml();
+  assert.match(html, /^<table [^>]*style="border-collapse:collapse;table-layout:fixed;width:309px;/);
+  assert.match(html, /<colgroup><col style="width:145px"><col style="width:64px"><col style="width:100px"><\/colgroup>/);
+  assert.match(html, /<tr style="height:40px">/);
+  const [first, second, third] = cells(html);
+  assert.strictEqual(first.length, 1);
+  assert.match(first[0][0], /^ colspan="3" style=".*font-size:14pt;font-weight:bold;background-color:#DDEBF7;text-align:center"$/);
+  assert.strictEqual(first[0][1], 'Q1 &lt;report&gt;');
+  assert.deepStrictEqual(
+    second.map(([, content]) => content),
+    ['1,234.50', '15 Mar 2024', '2469'],
+  );
+  assert.match(second[0][0], /text-align:right;color:#4F81BD/);
+  assert.match(second[1][0], /text-align:right/);
+  assert.strictEqual(third[0][1], '<a href="https://example.com/?a=1&amp;b=2">Site</a>');
+  assert.match(third[1][0], /font-style:italic;border-bottom:2px solid #FF0000/);
+});
+
+test('tables, hidden lines and documents', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.name = 'Stock & prices';
+  sheet.addTableFromData({
+    firstRow: 0,
+    firstColumn: 0,
+    data: [
+      { item: 'a', qty: 1 },
+      { item: 'b', qty: 2 },
+      { item: 'c', qty: 3 },
+    ],
+    table: new Table({ name: 'Stock', style: 'light9', bandedRows: true, firstColumnHighlighted: true }),
+  });
+  sheet.writeString(5, 0, 'hidden');
+  sheet.addRowConfig({ index: 5, hidden: true });
+  sheet.writeString(0, 3, 'far');
+  sheet.addColumnConfig({ index: 2, hidden: true });
+
+  const html = workbook.renderToHtmlSync('Stock & prices', { document: true, gridLines: true });
+  assert.match(html, /^<!DOCTYPE html>\n<html>\n<head>\n<meta charset="utf-8">\n<title>Stock &amp; prices<\/title>/);
+  assert.doesNotMatch(html, />hidden</);
+  const rows = cells(html);
+  assert.strictEqual(rows.length, 5);
+  assert.deepStrictEqual(
+    rows.map((row) => row.map(([, content]) => content)),
+    [
+      ['item', 'qty', 'far'],
+      ['a', '1', ''],
+      ['b', '2', ''],
+      ['c', '3', ''],
+      ['', '', ''],
+    ],
+  );
+  // Light 9 has a header filled with the first accent, and banded rows
+  assert.match(rows[0][0][0], /border:1px solid #D4D4D4;font-weight:bold;color:#FFFFFF;background-color:#4F81BD"/);
+  assert.match(rows[1][1][0], /background-color:#DCE6F2/);
+  assert.doesNotMatch(rows[2][1][0], /background-color/);
+  assert.match(rows[2][0][0], /font-weight:bold/);
+  assert.doesNotMatch(rows[1][2][0], /background-color/);
+});
+
+test('only safe links get an anchor', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeLink(0, 0, new Link('javascript:alert(1)', 'click'));
+  sheet.writeLink(1, 0, new Link(' JavaScript:alert(1)', 'spaced'));
+  sheet.writeLink(2, 0, new Link('data:text/html,<b>x</b>', 'data'));
+  sheet.writeLink(3, 0, new Link('mailto:someone@example.com', 'mail'));
+  sheet.writeLink(4, 0, new Link('HTTP://example.com', 'web'));
+  sheet.writeLink(5, 0, new Link('internal:Sheet1!A1', 'top'));
+
+  const html = workbook.renderToHtmlSync();
+  assert.deepStrictEqual(
+    cells(html).map(([[, content]]) => content),
+    [
+      'click',
+      'spaced',
+      'data',
+      '<a href="mailto:someone@example.com">mail</a>',
+      '<a href="HTTP://example.com">web</a>',
+      '<a href="#Sheet1!A1">top</a>',
+    ],
+  );
+});
+
+test('invalid sheets are rejected', async (t) => {
+  const workbook = new Workbook();
+  workbook.addSheet();
+  assert.throws(() => workbook.renderToHtmlSync(2), /Sheet 2 is not in the workbook/);
+  await assert.rejects(workbook.renderToHtml('Missing'), /Sheet Missing is not in the workbook/);
+});
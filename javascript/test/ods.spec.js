@@ -0,0 +1,116 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const fs = require('fs');
+const { Workbook, Format, Formula, Link, Border, Table } = require('../src/index');
+const findRootDir = require('./util');
+const { readEntry, readRawEntry } = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+
+function report() {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.name = 'Q1 & Q2';
+  const title = new Format({ bold: true, align: 'center', fontName: 'Arial', backgroundColor: '#DDEBF7' });
+  sheet.writeMergedCell({ range: 'A1:C2', value: 'Report', cellType: 'string', format: title });
+  const money = new Format({ numFmt: '#,##0.00;[Red]-#,##0.00', bottomBorder: new Border('medium', '#FF0000') });
+  sheet.writeNumber(2, 0, 1234.5, money);
+  sheet.writeNumber(2, 1, 0.125, new Format({ numFmt: '0.0%' }));
+  sheet.writeDate(2, 2, new Date(Date.UTC(2024, 2, 15, 12, 30)), new Format({ numFmt: 'dd/mm/yyyy hh:mm' }));
+  sheet.writeFormula(3, 0, new Formula({ formula: '=SUM(A3:B3)+IF(TRUE,1,0)' }));
+  sheet.writeFormula(3, 1, new Formula({ formula: "='Q1 & Q2'!A3*2" }));
+  sheet.writeLink(3, 2, new Link('https://example.com/?a=1&b=2', 'Site'));
+  sheet.writeString(4, 0, '  two  spaces');
+  sheet.writeNumber(4, 1, 1.5, new Format({ numFmt: '[h]:mm' }));
+  sheet.addColumnConfig({ index: 0, size: { value: 20 } });
+  sheet.addColumnConfig({ index: 4, hidden: true });
+  sheet.addRowConfig({ index: 0, size: { value: 30 } });
+  sheet.addTableFromData({ firstRow: 6, firstColumn: 0, data: [{ qty: 1 }, { qty: 2 }], table: new Table({ name: 'Items' }) });
+  sheet.writeFormula(5, 3, new Formula({ formula: '=SUM(Items[qty])' }));
+  workbook.addSheet();
+  return workbook;
+}
+
+test('package and sheets', async (t) => {
+  const buffer = await report().saveToOdsBuffer();
+  // The mimetype comes first and uncompressed, right after its local header
+  assert.strictEqual(buffer.toString('utf8', 30, 38), 'mimetype');
+  assert.strictEqual(readRawEntry(buffer, 'mimetype').toString(), 'application/vnd.oasis.opendocument.spreadsheet');
+  assert.match(readEntry(buffer, 'META-INF/manifest.xml'), /manifest:full-path="content.xml"/);
+  assert.match(readEntry(buffer, 'styles.xml'), /<style:style style:name="Default" style:family="table-cell"\/>/);
+
+  const content = readEntry(buffer, 'content.xml');
+  const tables = [...content.matchAll(/<table:table table:name="([^"]*)"/g)].map((match) => match[1]);
+  assert.deepStrictEqual(tables, ['Q1 &amp; Q2', 'Sheet2']);
+  assert.match(
+    content,
+    /<table:table-column table:style-name="co1" table:default-cell-style-name="Default"\/><table:table-column table:number-columns-repeated="3" table:style-name="co2"/,
+  );
+  assert.match(content, /<style:style style:name="co1" style:family="table-column"><style:table-column-properties fo:break-before="auto" style:column-width="1.5104in"\/>/);
+  assert.match(content, /table:visibility="collapse"/);
+  assert.match(content, /<table:table-row table:style-name="ro1">/);
+  assert.match(content, /style:row-height="0.4167in" fo:break-before="auto" style:use-optimal-row-height="false"/);
+});
+
+test('values, merges and formulas', (t) => {
+  const content = readEntry(report().saveToOdsBufferSync(), 'content.xml');
+  assert.match(
+    content,
+    /<table:table-cell table:style-name="ce1" table:number-columns-spanned="3" table:number-rows-spanned="2" office:value-type="string"><text:p>Report<\/text:p><\/table:table-cell><table:covered-table-cell table:number-columns-repeated="2"\/>/,
+  );
+  assert.match(content, /office:value-type="float" office:value="1234.5"><text:p>1,234.50<\/text:p>/);
+  assert.match(content, /office:value-type="percentage" office:value="0.125"><text:p>12.5%<\/text:p>/);
+  assert.match(content, /office:value-type="date" office:date-value="2024-03-15T12:30:00"><text:p>15\/03\/2024 12:30<\/text:p>/);
+  assert.match(content, /office:value-type="time" office:time-value="PT36H00M00S"><text:p>36:00<\/text:p>/);
+  assert.match(
+    content,
+    /table:formula="of:=SUM\(\[.A3:.B3\]\)\+IF\(TRUE\(\);1;0\)" office:value-type="float" office:value="1235.625"/,
+  );
+  assert.match(content, /table:formula="of:=\[\$'Q1 &amp; Q2'.A3\]\*2" office:value-type="float" office:value="2469"/);
+  assert.match(content, /table:formula="msoxl:=SUM\(Items\[qty\]\)"/);
+  assert.match(
+    content,
+    /<text:p><text:a xlink:type="simple" xlink:href="https:\/\/example.com\/\?a=1&amp;b=2">Site<\/text:a><\/text:p>/,
+  );
+  assert.match(content, /<text:p><text:s text:c="2"\/>two <text:s text:c="1"\/>spaces<\/text:p>/);
+});
+
+test('formats', (t) => {
+  const content = readEntry(report().saveToOdsBufferSync(), 'content.xml');
+  assert.match(
+    content,
+    /<style:style style:name="ce1" style:family="table-cell" style:parent-style-name="Default"><style:table-cell-properties fo:background-color="#DDEBF7" style:text-align-source="fix"\/><style:paragraph-properties fo:text-align="center"\/><style:text-properties fo:font-family="'Arial'" fo:font-weight="bold"\/><\/style:style>/,
+  );
+  // The negative section is the style the cells refer to, and the positive one is mapped from it
+  assert.match(
+    content,
+    /<number:number-style style:name="N2P0"><number:number number:decimal-places="2" number:min-decimal-places="2" number:min-integer-digits="1" number:grouping="true"\/><\/number:number-style><number:number-style style:name="N2"><style:text-properties fo:color="#FF0000"\/><number:text>-<\/number:text>/,
+  );
+  assert.match(content, /<style:map style:condition="value\(\)&gt;=0" style:apply-style-name="N2P0"\/>/);
+  assert.match(content, /style:data-style-name="N2"><style:table-cell-properties fo:border-bottom="1.5pt solid #FF0000"\/>/);
+  assert.match(content, /<number:percentage-style style:name="N3"><number:number number:decimal-places="1"/);
+  assert.match(
+    content,
+    /<number:date-style style:name="N4"><number:day number:style="long"\/><number:text>\/<\/number:text><number:month number:style="long"\/>/,
+  );
+  assert.match(content, /<number:time-style style:name="N5" number:truncate-on-overflow="false"><number:hours\/>/);
+});
+
+test('files and base64', async (t) => {
+  const workbook = report();
+  await workbook.saveToOdsFile(`${path}/report.ods`);
+  workbook.saveToOdsFileSync(`${path}/report-sync.ods`);
+  const base64 = await workbook.saveToOdsBase64();
+  const buffer = Buffer.from(base64, 'base64');
+  assert.deepStrictEqual(buffer, fs.readFileSync(`${path}/report.ods`));
+  assert.deepStrictEqual(buffer, fs.readFileSync(`${path}/report-sync.ods`));
+  assert.strictEqual(workbook.saveToOdsBase64Sync(), base64);
+
+  const empty = new Workbook();
+  empty.addSheet();
+  assert.match(
+    readEntry(empty.saveToOdsBufferSync(), 'content.xml'),
+    /<table:table-row table:style-name="ro1"><table:table-cell\/><\/table:table-row>/,
+  );
+});
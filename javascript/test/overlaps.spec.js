@@ -0,0 +1,70 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format, Formula, Table } = require('../src/index');
+
+test('cells written inside a merged range are rejected', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeMergedString({ range: 'A1:C3', value: 'title', format: new Format({}) });
+  sheet.writeString('B2', 'hidden');
+
+  assert.throws(
+    () => workbook.saveToBufferSync(),
+    /Sheet "Sheet1" writes to the same cells more than once:\n {2}cell B2 overlaps merged range A1:C3/,
+  );
+});
+
+test('overlapping tables, merges and array formulas are reported with both ranges', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addTable({ range: 'A1:C5', table: new Table({}) });
+  sheet.addTable({ range: 'B3:D8', table: new Table({}) });
+  sheet.writeMergedString({ range: 'F1:G2', value: 'merged', format: new Format({}) });
+  sheet.addArrayFormula({ range: 'G2:G4', formula: new Formula({ formula: '=1' }) });
+
+  assert.throws(() => workbook.saveToBufferSync(), (error) => {
+    const message = String(error);
+    assert.match(message, /table A1:C5 overlaps table B3:D8/);
+    assert.match(message, /merged range F1:G2 overlaps array formula G2:G4/);
+    return true;
+  });
+});
+
+test('duplicate cell writes are reported', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.setDuplicateCellPolicy('error');
+  sheet.writeString(0, 0, 'first');
+  sheet.writeString(0, 0, 'second');
+  sheet.writeColumns({ cell: 'C1', columns: [['a', 'b']] });
+  sheet.writeNumber('C2', 1);
+
+  assert.throws(() => workbook.saveToBufferSync(), (error) => {
+    const message = String(error);
+    assert.match(message, /cell A1 is written twice/);
+    assert.match(message, /cell C2 overlaps column block C1:C2/);
+    return true;
+  });
+});
+
+test('cells and column blocks may fill a table', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.addTable({ range: 'A1:B4', table: new Table({}) });
+  sheet.writeString('A2', 'name');
+  sheet.writeColumns({ cell: 'B2', columns: [new Float64Array([1, 2, 3])] });
+
+  assert(workbook.saveToBufferSync().length > 0);
+});
+
+test('long conflict lists are cut short', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.setDuplicateCellPolicy('error');
+  for (let i = 0; i < 30; i++) {
+    sheet.writeNumber(0, 0, i);
+  }
+
+  assert.throws(() => workbook.saveToBufferSync(), /and 9 more$/);
+});
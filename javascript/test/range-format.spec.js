@@ -0,0 +1,51 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format, Border } = require('../src/index');
+const findRootDir = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+const fs = require('fs');
+
+test('save to file with a filled range and an outer border', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  sheet.formatRange({
+    firstRow: 1,
+    lastRow: 50,
+    firstCol: 1,
+    lastCol: 20,
+    format: new Format({ backgroundColor: 'gray' }),
+    outerBorder: new Border('thin'),
+  });
+  sheet.writeString(10, 10, 'inside', new Format({ bold: true }));
+
+  // fill, 4 edges, 4 corners, bold, bold over the fill
+  assert.strictEqual(workbook.uniqueFormatCount(), 11);
+  await workbook.saveToFile(`${path}/range_format.xlsx`);
+  assert(fs.existsSync(`${path}/range_format.xlsx`), 'file exists');
+});
+
+test('a range with only an outer border', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  sheet.formatRange({
+    firstRow: 0,
+    lastRow: 0,
+    firstCol: 0,
+    lastCol: 2,
+    outerBorder: { style: 'medium', color: 'red' },
+  });
+
+  // left end, middle and right end of a single row
+  assert.strictEqual(workbook.uniqueFormatCount(), 3);
+  assert(workbook.saveToBufferSync().length > 0);
+});
+
+test('invalid range formats are rejected', (t) => {
+  const sheet = new Workbook().addSheet();
+  assert.throws(() => sheet.formatRange({ firstRow: 2, lastRow: 1, firstCol: 0, lastCol: 0, format: new Format({}) }));
+  assert.throws(() => sheet.formatRange({ firstRow: 0, lastRow: 1, firstCol: 0, lastCol: 0 }));
+});
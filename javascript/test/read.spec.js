@@ -0,0 +1,192 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const {
+  Workbook,
+  Format,
+  Formula,
+  Link,
+  Table,
+  TableColumn,
+  ConditionalFormatCell,
+  ConditionalFormatIconSet,
+  ConditionalFormatCustomIcon,
+  ConditionalFormatThreeColorScale,
+} = require('../src/index');
+const findRootDir = require('./util');
+const { readEntry } = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+
+/**
+ * The cell of a sheet at a position.
+ * @param {import('../src/index').Sheet} sheet
+ * @param {number} row
+ * @param {number} col
+ */
+function cellAt(sheet, row, col) {
+  return sheet.cells.find((cell) => !cell.merged && cell.row === row && cell.col === col);
+}
+
+test('cells, formats and sheet layout are read back', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const bold = new Format({ bold: true, fontColor: '#FF0000', numFmt: '0.00' });
+  sheet.writeString(0, 0, 'name', bold);
+  sheet.writeNumber(1, 0, 1.5);
+  sheet.writeDate(2, 0, new Date(Date.UTC(2024, 2, 15, 12)), new Format({ numFmt: 'yyyy-mm-dd hh:mm' }));
+  sheet.writeFormula(3, 0, new Formula({ formula: '=A2*2', result: 3 }));
+  sheet.writeFormula(4, 0, new Formula({ formula: '=1>0', result: true }));
+  sheet.writeLink(5, 0, new Link('https://example.com', 'Example', 'A tip'));
+  sheet.writeMergedString({ range: 'B1:C2', value: 'merged', format: new Format({ align: 'center' }) });
+  sheet.formatRange({ range: 'E1:F1', format: new Format({ backgroundColor: '#00FF00' }) });
+  sheet.addRowConfig({ index: 7, size: { value: 30, unit: 'auto' }, hidden: true });
+  sheet.addColumnConfig({ index: 3, size: { value: 120, unit: 'px' } });
+  const other = workbook.addSheet();
+  other.writeString('A1', 'second');
+
+  const read = await Workbook.readFromBuffer(workbook.saveToBufferSync());
+  const [first, second] = read.sheets;
+  assert.strictEqual(first.name, 'Sheet1');
+  assert.strictEqual(second.name, 'Sheet2');
+  assert.strictEqual(cellAt(second, 0, 0)?.value, 'second');
+
+  const title = cellAt(first, 0, 0);
+  assert.strictEqual(title?.value, 'name');
+  assert.strictEqual(title?.cellType, 'string');
+  const format = /** @type {Format} */ (title?.format);
+  assert.strictEqual(format.bold, true);
+  assert.strictEqual(format.fontColor, '#FF0000');
+  assert.strictEqual(format.numFmt, '0.00');
+  assert(read.formats.includes(format));
+
+  assert.strictEqual(cellAt(first, 1, 0)?.value, 1.5);
+  assert.deepStrictEqual(cellAt(first, 2, 0)?.value, new Date(Date.UTC(2024, 2, 15, 12)));
+  assert.strictEqual(cellAt(first, 2, 0)?.cellType, 'date');
+
+  const formula = cellAt(first, 3, 0)?.value;
+  assert(formula instanceof Formula);
+  assert.strictEqual(formula.formula, '=A2*2');
+  assert.strictEqual(formula.result, 3);
+  assert.strictEqual(cellAt(first, 4, 0)?.value.result, true);
+
+  const link = cellAt(first, 5, 0)?.value;
+  assert(link instanceof Link);
+  assert.deepStrictEqual([link.url, link.text, link.tip], ['https://example.com', 'Example', 'A tip']);
+
+  const merged = first.cells.find((cell) => cell.merged);
+  assert.deepStrictEqual(
+    [merged?.firstRow, merged?.lastRow, merged?.firstCol, merged?.lastCol, merged?.value],
+    [0, 1, 1, 2, 'merged'],
+  );
+  assert.strictEqual(merged?.format?.align, 'center');
+
+  assert.deepStrictEqual(
+    first.rangeFormats.map((range) => [range.firstRow, range.firstCol, range.lastCol, range.format?.backgroundColor]),
+    [[0, 4, 5, '#00FF00']],
+  );
+  assert.deepStrictEqual(first.rowConfigs, [{ index: 7, size: { value: 30, unit: 'auto' }, hidden: true, format: undefined }]);
+  assert.deepStrictEqual(first.columnConfigs, [{ index: 3, size: { value: 120, unit: 'px' }, format: undefined }]);
+
+  await read.saveToFile(`${path}/read_round_trip.xlsx`);
+  const again = Workbook.readFromFileSync(`${path}/read_round_trip.xlsx`);
+  assert.strictEqual(again.sheets[0].cells.length, first.cells.length);
+});
+
+test('tables, array formulas and conditional formats are read back', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  [1, 2, 3].forEach((value, row) => sheet.writeNumber(row + 1, 0, value));
+  sheet.addTable({
+    range: 'A1:B5',
+    table: new Table({
+      name: 'Prices',
+      style: 'light9',
+      totalRow: true,
+      columns: [
+        new TableColumn({ header: 'Price', totalFunction: { type: 'sum' } }),
+        new TableColumn({ header: 'Double', formula: new Formula({ formula: '=[@Price]*2' }), totalLabel: 'x2' }),
+      ],
+    }),
+  });
+  sheet.addArrayFormula({ range: 'D1:D3', formula: new Formula({ formula: '=A2:A4*10' }) });
+  const red = new Format({ backgroundColor: '#FFC7CE' });
+  sheet.addConditionalFormat({
+    range: 'A2:A4',
+    format: new ConditionalFormatCell({ format: red, rule: { type: 'between', value: 1, optionalValue: 2 } }),
+  });
+  sheet.addConditionalFormat({
+    range: 'A2:A4',
+    format: new ConditionalFormatThreeColorScale({ minColor: '#FF0000', midColor: '#FFFF00', maxColor: '#00FF00' }),
+  });
+  const icon = (/** @type {number} */ value) =>
+    new ConditionalFormatCustomIcon({ greaterThan: true, iconRule: { type: 'percent', value } });
+  sheet.addConditionalFormat({
+    range: 'A2:A4',
+    format: new ConditionalFormatIconSet({ iconType: 'threeArrows', icons: [icon(0), icon(40), icon(80)] }),
+  });
+
+  const read = Workbook.readFromBufferSync(workbook.saveToBufferSync());
+  const [first] = read.sheets;
+
+  const [{ table, firstRow, lastRow, firstColumn, lastColumn }] = first.tables;
+  assert.deepStrictEqual([firstRow, lastRow, firstColumn, lastColumn], [0, 4, 0, 1]);
+  assert.strictEqual(table.name, 'Prices');
+  assert.strictEqual(table.style, 'light9');
+  assert.strictEqual(table.totalRow, true);
+  assert.deepStrictEqual(
+    table.columns.map((column) => [column.header, column.totalFunction?.type, column.totalLabel]),
+    [
+      ['Price', 'sum', undefined],
+      ['Double', undefined, 'x2'],
+    ],
+  );
+  assert.match(table.columns[1].formula?.formula ?? '', /^=.*#This Row.*\*2$/);
+
+  const [arrayFormula] = first.arrayFormulas;
+  assert.deepStrictEqual(
+    [arrayFormula.firstRow, arrayFormula.lastRow, arrayFormula.firstColumn, arrayFormula.formula.formula],
+    [0, 2, 3, '=A2:A4*10'],
+  );
+
+  const [cell, scale, iconSet] = first.conditionalFormats.map((value) => 'format' in value && value.format);
+  assert(cell instanceof ConditionalFormatCell);
+  assert.deepStrictEqual(cell.rule, { type: 'between', value: 1, optionalValue: 2 });
+  assert.strictEqual(/** @type {Format} */ (cell.format).backgroundColor, '#FFC7CE');
+  assert(scale instanceof ConditionalFormatThreeColorScale);
+  assert.deepStrictEqual([scale.minColor, scale.midColor, scale.maxColor], ['#FF0000', '#FFFF00', '#00FF00']);
+  assert(iconSet instanceof ConditionalFormatIconSet);
+  assert.strictEqual(iconSet.iconType, 'threeArrows');
+  assert.deepStrictEqual(
+    iconSet.icons.map((icon) => icon.iconRule?.value),
+    [0, 40, 80],
+  );
+
+  assert.doesNotThrow(() => read.saveToBufferSync());
+});
+
+test('boolean cells are read back as booleans, not formulas', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeCell(0, 0, true, 'boolean');
+  sheet.addTableFromData({ firstRow: 1, firstColumn: 0, data: [{ paid: false }] });
+
+  const readWorkbook = Workbook.readFromBufferSync(workbook.saveToBufferSync());
+  const [read] = readWorkbook.sheets;
+  assert.deepStrictEqual(
+    [cellAt(read, 0, 0), cellAt(read, 2, 0)].map((cell) => [cell?.cellType, cell?.value]),
+    [
+      ['boolean', true],
+      ['boolean', false],
+    ],
+  );
+
+  const xml = readEntry(readWorkbook.saveToBufferSync(), 'xl/worksheets/sheet1.xml');
+  assert.doesNotMatch(xml, /<f>/);
+  assert.match(xml, /<c r="A1" t="b"><v>1<\/v><\/c>/);
+});
+
+test('a buffer that is not an xlsx file is rejected', async (t) => {
+  assert.throws(() => Workbook.readFromBufferSync(Buffer.from('not a zip')));
+  await assert.rejects(Workbook.readFromFile(`${path}/does_not_exist.xlsx`));
+});
@@ -0,0 +1,110 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const fs = require('fs');
+const { Workbook, Format, Formula, Link, Table } = require('../src/index');
+const findRootDir = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+
+function orders() {
+  const workbook = new Workbook();
+  workbook.addSheet();
+  const sheet = workbook.addSheet();
+  sheet.name = 'Orders';
+  const money = new Format({ numFmt: '#,##0.00' });
+  const date = new Format({ numFmt: 'dd/mm/yyyy' });
+  sheet.writeString(0, 0, 'item');
+  sheet.writeString(0, 1, 'amount');
+  sheet.writeString(0, 2, 'date');
+  sheet.writeString(0, 3, 'total');
+  sheet.writeString(1, 0, 'Glass, "fragile"');
+  sheet.writeNumber(1, 1, 1234.5, money);
+  sheet.writeDate(1, 2, new Date(Date.UTC(2024, 2, 15)), date);
+  sheet.writeFormula(1, 3, new Formula({ formula: '=B2*2' }), money);
+  sheet.writeLink(2, 0, new Link('https://example.com', 'Site'));
+  sheet.writeNumber(2, 1, 0.125, new Format({ numFmt: '0.0%' }));
+  sheet.writeFormula(2, 2, new Formula({ formula: '=B3>0' }));
+  return workbook;
+}
+
+test('formatted and raw values', async (t) => {
+  const workbook = orders();
+  assert.strictEqual(
+    await workbook.saveSheetToCsv('Orders'),
+    [
+      'item,amount,date,total',
+      '"Glass, ""fragile""","1,234.50",15/03/2024,"2,469.00"',
+      'Site,12.5%,TRUE,',
+      '',
+    ].join('\r\n'),
+  );
+  assert.strictEqual(
+    workbook.saveSheetToTsvSync(1, { values: 'raw', formulas: 'formulas', lineEnding: '\n' }),
+    ['item\tamount\tdate\ttotal', '"Glass, ""fragile"""\t1234.5\t2024-03-15\t=B2*2', 'Site\t0.125\t=B3>0\t', ''].join(
+      '\n',
+    ),
+  );
+  assert.strictEqual(workbook.saveSheetToCsvSync(0), '');
+});
+
+test('quoting, delimiters and merged cells', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  sheet.writeMergedCell({ range: 'A1:B2', value: 'a;b', cellType: 'string', format: new Format({ bold: true }) });
+  sheet.writeNumber(0, 2, -1.5);
+  sheet.writeString(1, 2, ' padded');
+  assert.strictEqual(workbook.saveSheetToCsvSync(sheet, { delimiter: ';' }), '"a;b";;-1.5\r\n;;" padded"\r\n');
+  assert.strictEqual(
+    workbook.saveSheetToCsvSync(sheet, { mergedCells: 'repeat', quoting: 'nonnumeric', quote: "'" }),
+    "'a;b','a;b',-1.5\r\n'a;b','a;b',' padded'\r\n",
+  );
+  assert.strictEqual(
+    workbook.saveSheetToCsvSync(sheet, { quoting: 'none', bom: true, lineEnding: '\n' }),
+    '﻿a;b,,-1.5\n,, padded\n',
+  );
+  assert.strictEqual(
+    workbook.saveSheetToCsvSync(sheet, { quoting: 'all' }),
+    '"a;b",,"-1.5"\r\n,," padded"\r\n',
+  );
+});
+
+test('JSON rows and tables', (t) => {
+  const workbook = orders();
+  assert.deepStrictEqual(JSON.parse(workbook.saveSheetToJsonSync(1, { values: 'raw', header: true })), [
+    { item: 'Glass, "fragile"', amount: 1234.5, date: '2024-03-15', total: 2469 },
+    { item: 'Site', amount: 0.125, date: true, total: null },
+  ]);
+  assert.deepStrictEqual(JSON.parse(workbook.saveSheetToJsonSync('Orders'))[2], ['Site', '12.5%', true, null]);
+
+  const tables = new Workbook();
+  const sheet = tables.addSheet();
+  sheet.addTableFromData({
+    firstRow: 1,
+    firstColumn: 0,
+    data: [
+      { name: 'a', qty: 1 },
+      { name: 'b', qty: 2 },
+    ],
+    table: new Table({ name: 'Items' }),
+  });
+  assert.deepStrictEqual(JSON.parse(tables.saveSheetToJsonSync()), [
+    [null, null],
+    ['name', 'qty'],
+    ['a', '1'],
+    ['b', '2'],
+  ]);
+});
+
+test('files and invalid exports', async (t) => {
+  const workbook = orders();
+  await workbook.saveSheetToFile('Orders', `${path}/export.tsv`, { values: 'raw' });
+  assert.strictEqual(fs.readFileSync(`${path}/export.tsv`, 'utf8').split('\r\n')[2], 'Site\t0.125\tTRUE\t');
+  workbook.saveSheetToFileSync(1, `${path}/export.txt`, { format: 'json' });
+  assert.strictEqual(JSON.parse(fs.readFileSync(`${path}/export.txt`, 'utf8')).length, 3);
+
+  assert.throws(() => workbook.saveSheetToCsvSync('Missing'), /Sheet Missing is not in the workbook/);
+  assert.throws(() => workbook.saveSheetToCsvSync(1, { delimiter: ', ' }), /delimiter must be one character/);
+  // @ts-expect-error
+  await assert.rejects(workbook.saveSheetToCsv(1, { quoting: 'some' }), /Unknown CSV quoting "some"/);
+});
@@ -12,6 +12,7 @@ const { test } = require('node:test');
 const assert = require('node:assert');
 const fs = require('fs');
 const findRootDir = require('./util');
+const { readEntry } = require('./util');
 const rootPath = findRootDir(__dirname);
 const path = rootPath + '/temp';
 
@@ -257,3 +258,94 @@ test('table with table function', async (t) => {
     'file exists',
   );
 });
+
+test('table from data', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const data = [
+    { product: 'Apples', q1: 10000, q2: 5000, sold: new Date('2024-01-05') },
+    { product: 'Pears', q1: 2000, q2: 3000, sold: new Date('2024-02-05'), organic: true },
+    { product: 'Bananas', q1: 6000, q2: 6000, organic: false },
+  ];
+
+  sheet.addTableFromData({
+    firstRow: 1,
+    firstColumn: 1,
+    data,
+  });
+
+  await workbook.saveToFile(`${path}/table_from_data.xlsx`);
+  const buffer = fs.readFileSync(`${path}/table_from_data.xlsx`);
+  const strings = [...readEntry(buffer, 'xl/sharedStrings.xml').matchAll(/<t>([^<]*)<\/t>/g)].map((match) => match[1]);
+  assert.deepStrictEqual(strings, ['product', 'q1', 'q2', 'sold', 'organic', 'Apples', 'Pears', 'Bananas']);
+  assert.match(
+    readEntry(buffer, 'xl/tables/table1.xml'),
+    /ref="B2:F5".*<tableColumn id="4" name="sold"\/><tableColumn id="5" name="organic"\/>/,
+  );
+
+  const xml = readEntry(buffer, 'xl/worksheets/sheet1.xml');
+  assert.match(xml, /<row r="3" spans="2:6"><c r="B3" t="s"><v>5<\/v><\/c><c r="C3"><v>10000<\/v><\/c><c r="D3"><v>5000<\/v><\/c><c r="E3"><v>45296<\/v><\/c><\/row>/);
+  assert.match(xml, /<c r="E4"><v>45327<\/v><\/c><c r="F4" t="b"><v>1<\/v><\/c><\/row>/);
+  assert.match(xml, /<c r="D5"><v>6000<\/v><\/c><c r="F5" t="b"><v>0<\/v><\/c><\/row>/);
+});
+
+test('table from data rejects objects and arrays in records', (t) => {
+  /** @param {any[]} data */
+  const save = (data) => {
+    const workbook = new Workbook();
+    workbook.addSheet().addTableFromData({ firstRow: 0, firstColumn: 0, data });
+    return () => workbook.saveToBufferSync();
+  };
+
+  assert.throws(save([{ a: 1 }, { a: { b: 1 } }]), /Table record 1 has an object at key "a"/);
+  assert.throws(save([{ a: 1, tags: ['x'] }]), /Table record 0 has an array at key "tags"/);
+});
+
+test('table from data with column spec and total row', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+  const currency = new Format({ numFmt: '$#,##0.00' });
+  const data = [
+    { name: 'Apples', price: 1.5, stock: '10' },
+    { name: 'Pears', price: 2.25, stock: '20' },
+  ];
+
+  const table = new Table({
+    totalRow: true,
+    style: 'medium9',
+    columns: [
+      new TableColumn({ key: 'name', header: 'Product', totalLabel: 'Totals' }),
+      new TableColumn({
+        key: 'price',
+        header: 'Price',
+        format: currency,
+        totalFunction: { type: 'average' },
+      }),
+      new TableColumn({
+        header: 'stock',
+        cellType: 'number',
+        totalFunction: { type: 'sum' },
+      }),
+    ],
+  });
+
+  sheet.addTableFromData({
+    firstRow: 0,
+    firstColumn: 0,
+    data,
+    table,
+  });
+
+  await workbook.saveToFile(`${path}/table_from_data_spec.xlsx`);
+  const buffer = fs.readFileSync(`${path}/table_from_data_spec.xlsx`);
+  const strings = [...readEntry(buffer, 'xl/sharedStrings.xml').matchAll(/<t>([^<]*)<\/t>/g)].map((match) => match[1]);
+  assert.deepStrictEqual(strings, ['Product', 'Price', 'stock', 'Apples', 'Pears', 'Totals']);
+  assert.match(readEntry(buffer, 'xl/tables/table1.xml'), /ref="A1:C4" totalsRowCount="1"/);
+
+  // Stock is written as numbers from its strings, and the prices keep their format
+  const xml = readEntry(buffer, 'xl/worksheets/sheet1.xml');
+  assert.match(xml, /<row r="2" spans="1:3"><c r="A2" t="s"><v>3<\/v><\/c><c r="B2" s="1"><v>1.5<\/v><\/c><c r="C2"><v>10<\/v><\/c><\/row>/);
+  assert.match(xml, /<c r="B3" s="1"><v>2.25<\/v><\/c><c r="C3"><v>20<\/v><\/c>/);
+  assert.match(xml, /<c r="A4" t="s"><v>5<\/v><\/c><c r="B4" s="1"><f>SUBTOTAL\(101,\[Price\]\)<\/f>/);
+  assert.match(xml, /<c r="C4"><f>SUBTOTAL\(109,\[stock\]\)<\/f>/);
+});
@@ -0,0 +1,147 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Template, Sheet, Format, Formula, Table, TableColumn } = require('../src/index');
+const findRootDir = require('./util');
+const { readEntry, readRawEntry } = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+
+/**
+ * The cell of a sheet at a position.
+ * @param {Sheet} sheet
+ * @param {number} row
+ * @param {number} col
+ */
+function cellAt(sheet, row, col) {
+  return sheet.cells.find((cell) => !cell.merged && cell.row === row && cell.col === col);
+}
+
+/**
+ * An invoice with placeholders, and a table of lines with a calculated column and a total row.
+ */
+function invoiceTemplate() {
+  const workbook = new Workbook();
+  const invoice = new Sheet('Invoice');
+  workbook.pushSheet(invoice);
+  invoice.writeString('A1', 'Invoice for {{customer.name}} ({{customer.city}})');
+  invoice.writeString('A2', '{{total}}', new Format({ numFmt: '0.00' }));
+  invoice.writeString('A3', '{{date}}', new Format({ numFmt: 'yyyy-mm-dd' }));
+  invoice.writeString('A4', '{{unknown}} stays');
+  invoice.writeString('B1', 'Due', new Format({ bold: true }));
+  invoice.writeString(5, 0, 'Apples');
+  invoice.writeNumber(5, 1, 1);
+  invoice.writeNumber(5, 2, 0.5, new Format({ numFmt: '0.00' }));
+  invoice.addTable({
+    range: 'A5:D7',
+    table: new Table({
+      name: 'Lines',
+      totalRow: true,
+      columns: [
+        new TableColumn({ header: 'Item', totalLabel: 'Total' }),
+        new TableColumn({ header: 'Qty', totalFunction: { type: 'sum' } }),
+        new TableColumn({ header: 'Price' }),
+        new TableColumn({ header: 'Amount', formula: new Formula({ formula: '=[@Qty]*[@Price]' }) }),
+      ],
+    }),
+  });
+  const notes = new Sheet('Notes');
+  workbook.pushSheet(notes);
+  notes.writeString('A1', 'untouched');
+  return workbook.saveToBufferSync();
+}
+
+test('placeholders are replaced with data, typed when they fill a cell', async (t) => {
+  const template = new Template(invoiceTemplate());
+  template.setData({
+    customer: { name: 'Ada & Co', city: 'London' },
+    total: 12.5,
+    date: new Date(Date.UTC(2024, 2, 15)),
+  });
+  const buffer = await template.saveToBuffer();
+
+  const [invoice] = Workbook.readFromBufferSync(buffer).sheets;
+  assert.strictEqual(cellAt(invoice, 0, 0)?.value, 'Invoice for Ada & Co (London)');
+  assert.strictEqual(cellAt(invoice, 1, 0)?.value, 12.5);
+  assert.strictEqual(cellAt(invoice, 1, 0)?.format?.numFmt, '0.00');
+  assert.deepStrictEqual(cellAt(invoice, 2, 0)?.value, new Date(Date.UTC(2024, 2, 15)));
+  assert.strictEqual(cellAt(invoice, 3, 0)?.value, '{{unknown}} stays');
+  assert.match(readEntry(buffer, 'xl/workbook.xml'), /fullCalcOnLoad="1"/);
+});
+
+test('cells and tables are written and only the changed parts are rewritten', (t) => {
+  const source = invoiceTemplate();
+  const template = new Template(source);
+  template.writeCell('Invoice', 'B2', new Date(Date.UTC(2024, 3, 1)));
+  template.writeCell('invoice', 0, 1, 'Paid');
+  template.writeTable('Lines', [
+    ['Pears', 2, 1.25],
+    { Item: 'Plums', Qty: 3, Price: 2 },
+    { Item: 'Figs', Qty: 1, Price: 4, Amount: new Formula({ formula: '=4', result: 4 }) },
+  ]);
+  template.saveToFileSync(`${path}/template_filled.xlsx`);
+  const buffer = template.saveToBufferSync();
+
+  const [invoice] = Workbook.readFromBufferSync(buffer).sheets;
+  assert.strictEqual(cellAt(invoice, 0, 1)?.value, 'Paid');
+  assert.strictEqual(cellAt(invoice, 0, 1)?.format?.bold, true);
+  assert.deepStrictEqual(
+    [5, 6, 7].map((row) => [0, 1, 2].map((col) => cellAt(invoice, row, col)?.value)),
+    [
+      ['Pears', 2, 1.25],
+      ['Plums', 3, 2],
+      ['Figs', 1, 4],
+    ],
+  );
+  assert.strictEqual(cellAt(invoice, 6, 2)?.format?.numFmt, '0.00');
+  assert.match(cellAt(invoice, 5, 3)?.value.formula, /Qty.*Price/);
+  assert.strictEqual(cellAt(invoice, 7, 3)?.value.formula, '=4');
+  assert.strictEqual(cellAt(invoice, 8, 0)?.value, 'Total');
+  assert.match(cellAt(invoice, 8, 1)?.value.formula, /SUBTOTAL\(109/);
+
+  const table = readEntry(buffer, 'xl/tables/table1.xml');
+  assert.match(table, /<table [^>]*ref="A5:D9"/);
+  assert.match(table, /<autoFilter ref="A5:D8"/);
+  for (const part of ['xl/styles.xml', 'xl/worksheets/sheet2.xml', 'xl/sharedStrings.xml', 'docProps/app.xml']) {
+    assert.deepStrictEqual(readRawEntry(buffer, part), readRawEntry(source, part), part);
+  }
+  assert.notDeepStrictEqual(
+    readRawEntry(buffer, 'xl/worksheets/sheet1.xml'),
+    readRawEntry(source, 'xl/worksheets/sheet1.xml'),
+  );
+});
+
+test('a shorter table clears its old rows', async (t) => {
+  const template = new Template(invoiceTemplate());
+  template.writeTable('Lines', [{ Item: 'Pears', Qty: 2, Price: 1 }]);
+  const filled = new Template(await template.saveToBuffer());
+  filled.writeTable('Lines', []);
+
+  const [invoice] = Workbook.readFromBufferSync(filled.saveToBufferSync()).sheets;
+  assert.strictEqual(cellAt(invoice, 5, 0)?.value ?? '', '');
+  assert.strictEqual(cellAt(invoice, 6, 0)?.value, 'Total');
+  assert.strictEqual(cellAt(invoice, 7, 0), undefined);
+  assert.deepStrictEqual([invoice.tables[0].firstRow, invoice.tables[0].lastRow], [4, 6]);
+});
+
+test('templates are read from files and unknown names are rejected', async (t) => {
+  const workbook = new Workbook();
+  workbook.addSheet().writeString('A1', 'x');
+  await workbook.saveToFile(`${path}/template_source.xlsx`);
+
+  const template = new Template(`${path}/template_source.xlsx`);
+  template.writeCell('Sheet1', 'A2', 1);
+  await template.saveToFile(`${path}/template_from_file.xlsx`);
+  const [sheet] = (await Workbook.readFromFile(`${path}/template_from_file.xlsx`)).sheets;
+  assert.strictEqual(cellAt(sheet, 1, 0)?.value, 1);
+
+  const missing = (/** @type {(template: Template) => void} */ fill) => {
+    const template = new Template(`${path}/template_source.xlsx`);
+    fill(template);
+    return () => template.saveToBufferSync();
+  };
+  assert.throws(missing((template) => template.writeCell('Other', 'A1', 1)), /no sheet "Other"/);
+  assert.throws(missing((template) => template.writeTable('Lines', [])), /no table "Lines"/);
+  assert.throws(missing((template) => template.writeDefinedName('Total', 1)), /no defined name "Total"/);
+  await assert.rejects(new Template(`${path}/does_not_exist.xlsx`).saveToBuffer());
+});
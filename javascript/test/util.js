@@ -1,6 +1,7 @@
 // @ts-check
 const path = require('path');
 const fs = require('fs');
+const zlib = require('zlib');
 
 /**
  *
@@ -20,4 +21,54 @@ function findRootDir(currentPath) {
   return null;
 }
 
+/**
+ * Finds a file of a saved workbook, like `xl/worksheets/sheet1.xml`, as it is stored.
+ * @param {Buffer} buffer - The workbook
+ * @param {string} name - The path of the file inside the workbook
+ * @returns {{method: number, data: Buffer}}
+ */
+function findEntry(buffer, name) {
+  let offset = buffer.lastIndexOf(Buffer.from([0x50, 0x4b, 0x05, 0x06]));
+  let entries = buffer.readUInt16LE(offset + 10);
+  offset = buffer.readUInt32LE(offset + 16);
+  while (entries-- > 0) {
+    const method = buffer.readUInt16LE(offset + 10);
+    const size = buffer.readUInt32LE(offset + 20);
+    const nameLength = buffer.readUInt16LE(offset + 28);
+    const extraLength = buffer.readUInt16LE(offset + 30);
+    const commentLength = buffer.readUInt16LE(offset + 32);
+    const header = buffer.readUInt32LE(offset + 42);
+    const entryName = buffer.toString('utf8', offset + 46, offset + 46 + nameLength);
+    if (entryName === name) {
+      const start = header + 30 + buffer.readUInt16LE(header + 26) + buffer.readUInt16LE(header + 28);
+      return { method, data: buffer.subarray(start, start + size) };
+    }
+    offset += 46 + nameLength + extraLength + commentLength;
+  }
+  throw new Error(`${name} not found`);
+}
+
+/**
+ * Reads a file from a saved workbook, like `xl/worksheets/sheet1.xml`.
+ * @param {Buffer} buffer - The workbook
+ * @param {string} name - The path of the file inside the workbook
+ * @returns {string}
+ */
+function readEntry(buffer, name) {
+  const { method, data } = findEntry(buffer, name);
+  return (method === 0 ? data : zlib.inflateRawSync(data)).toString('utf8');
+}
+
+/**
+ * The bytes a file of a saved workbook is stored as, compressed or not.
+ * @param {Buffer} buffer - The workbook
+ * @param {string} name - The path of the file inside the workbook
+ * @returns {Buffer}
+ */
+function readRawEntry(buffer, name) {
+  return findEntry(buffer, name).data;
+}
+
 module.exports = findRootDir;
+module.exports.readEntry = readEntry;
+module.exports.readRawEntry = readRawEntry;
@@ -0,0 +1,102 @@
+// @ts-check
+const { test } = require('node:test');
+const assert = require('node:assert');
+const { Workbook, Format } = require('../src/index');
+const findRootDir = require('./util');
+const { readEntry } = require('./util');
+const rootPath = findRootDir(__dirname);
+const path = rootPath + '/temp';
+const fs = require('fs');
+
+test('save to file with column blocks', async (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  const size = 1_000;
+  const floats = new Float64Array(size);
+  const ints = new Int32Array(size);
+  const names = [];
+  const dates = [];
+  for (let i = 0; i < size; i++) {
+    floats[i] = i * 1.5;
+    ints[i] = i;
+    names.push(`Row ${i}`);
+    dates.push(new Date(2024, 0, 1 + (i % 365)));
+  }
+
+  sheet.writeColumns({
+    row: 1,
+    col: 0,
+    columns: [names, floats, ints, dates],
+    formats: [
+      undefined,
+      new Format({ numFmt: '0.00' }),
+      undefined,
+      new Format({ numFmt: 'yyyy-mm-dd' }),
+    ],
+  });
+
+  await workbook.saveToFile(`${path}/write_columns.xlsx`);
+  const xml = readEntry(fs.readFileSync(`${path}/write_columns.xlsx`), 'xl/worksheets/sheet1.xml');
+  assert.match(xml, /<dimension ref="A2:D1001"\/>/);
+  assert.match(xml, /<row r="2" spans="1:4"><c r="A2" t="s"><v>0<\/v><\/c><c r="B2" s="1"><v>0<\/v><\/c><c r="C2"><v>0<\/v><\/c><c r="D2" s="2"><v>45292<\/v><\/c><\/row>/);
+  assert.match(xml, /<c r="A1001" t="s"><v>999<\/v><\/c><c r="B1001" s="1"><v>1498.5<\/v><\/c><c r="C1001"><v>999<\/v><\/c>/);
+});
+
+test('column blocks write numbers, strings and dates by type', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  sheet.writeColumns({
+    cell: 'B2',
+    columns: [
+      [1, 2.5],
+      ['a', null],
+      [new Date(Date.UTC(2024, 0, 1)), new Date(Date.UTC(2024, 0, 2, 12))],
+    ],
+  });
+
+  const xml = readEntry(workbook.saveToBufferSync(), 'xl/worksheets/sheet1.xml');
+  assert.match(xml, /<c r="B2"><v>1<\/v><\/c><c r="C2" t="s"><v>0<\/v><\/c><c r="D2"><v>45292<\/v><\/c>/);
+  assert.match(xml, /<row r="3" spans="2:4"><c r="B3"><v>2.5<\/v><\/c><c r="D3"><v>45293.5<\/v><\/c><\/row>/);
+});
+
+test('column blocks reject non-finite numbers and mixed types', (t) => {
+  /** @param {any[]} columns */
+  const save = (columns) => {
+    const workbook = new Workbook();
+    workbook.addSheet().writeColumns({ row: 2, col: 1, columns });
+    return () => workbook.saveToBufferSync();
+  };
+
+  assert.throws(save([new Float64Array([1, Infinity])]), /Invalid number at row 3, column 1: inf/);
+  assert.throws(save([['x'], [1, NaN]]), /Invalid number at row 3, column 2: NaN/);
+  assert.throws(save([['a', new Date()]]), /Column 1 holds strings, found a Date at index 1 \(row 3\)/);
+  assert.throws(save([[new Date(), 'a']]), /Column 1 holds Dates, found a string at index 1/);
+  assert.throws(save([[1, '2']]), /Column 1 holds numbers, found a string at index 1/);
+  assert.throws(save([['a', { b: 1 }]]), /Column 1 holds strings, found an object at index 1/);
+  assert.throws(save([[true]]), /Column 1 must hold numbers, strings or Dates, found a boolean at index 0/);
+});
+
+test('column blocks reject invalid columns', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  sheet.writeColumns({
+    row: 0,
+    col: 0,
+    // @ts-ignore
+    columns: [new Uint8Array([1, 2, 3])],
+  });
+
+  assert.throws(() => workbook.saveToBufferSync());
+});
+
+test('column blocks reject invalid start cell', (t) => {
+  const workbook = new Workbook();
+  const sheet = workbook.addSheet();
+
+  assert.throws(() =>
+    sheet.writeColumns({ row: -1, col: 0, columns: [new Float64Array(1)] }),
+  );
+});
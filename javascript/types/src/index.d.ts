@@ -1,5 +1,6 @@
 import { colorUtils } from "./utils";
 import Workbook = require("./models/workbook");
+import Template = require("./models/template");
 import { Sheet } from "./models/sheet";
 import Color = require("./models/color");
 import Format = require("./models/format");
@@ -8,6 +9,8 @@ import Link = require("./models/link");
 import { Border } from "./models/border";
 import { DiagonalBorder } from "./models/border";
 import { ArrayFormulaSheetValue } from "./models/sheet";
+import { ConditionalFormatGroupSheetValue } from "./models/sheet";
+import { CsvImportSheetValue } from "./models/sheet";
 import { ConditionalFormatTwoColorScale } from "./models/conditional_format";
 import { ConditionalFormatThreeColorScale } from "./models/conditional_format";
 import { ConditionalFormatAverage } from "./models/conditional_format";
@@ -25,5 +28,5 @@ import { ConditionalFormatTop } from "./models/conditional_format";
 import { Table } from "./models/table";
 import { TableFunction } from "./models/table";
 import { TableColumn } from "./models/table";
-export { colorUtils, Workbook, Sheet, Color, Format, Formula, Link, Border, DiagonalBorder, ArrayFormulaSheetValue, ConditionalFormatTwoColorScale, ConditionalFormatThreeColorScale, ConditionalFormatAverage, ConditionalFormatBlank, ConditionalFormatCell, ConditionalFormatDataBar, ConditionalFormatDate, ConditionalFormatDuplicate, ConditionalFormatError, ConditionalFormatFormula, ConditionalFormatCustomIcon, ConditionalFormatIconSet, ConditionalFormatText, ConditionalFormatTop, Table, TableFunction, TableColumn };
+export { colorUtils, Workbook, Template, Sheet, Color, Format, Formula, Link, Border, DiagonalBorder, ArrayFormulaSheetValue, ConditionalFormatGroupSheetValue, CsvImportSheetValue, ConditionalFormatTwoColorScale, ConditionalFormatThreeColorScale, ConditionalFormatAverage, ConditionalFormatBlank, ConditionalFormatCell, ConditionalFormatDataBar, ConditionalFormatDate, ConditionalFormatDuplicate, ConditionalFormatError, ConditionalFormatFormula, ConditionalFormatCustomIcon, ConditionalFormatIconSet, ConditionalFormatText, ConditionalFormatTop, Table, TableFunction, TableColumn };
 //# sourceMappingURL=index.d.ts.map
\ No newline at end of file
@@ -1,7 +1,8 @@
+export type ColorValue = import("./color").ColorValue;
 /**
  * - The border style
  */
-export type BorderType = ("none" | "thin" | "medium" | "thick" | "double" | "hair" | "mediumDashed" | "dashDot" | "mediumDashDot" | "dashDotDot" | "mediumDashDotDot" | "slantDashDot");
+export type BorderType = ("none" | "thin" | "medium" | "dashed" | "dotted" | "thick" | "double" | "hair" | "mediumDashed" | "dashDot" | "mediumDashDot" | "dashDotDot" | "mediumDashDotDot" | "slantDashDot");
 /**
  * - The diagonal border style
  */
@@ -11,6 +12,8 @@ export type DiagonalBorderType = ("none" | "borderUp" | "borderDown" | "borderUp
  *    "none"|
  *    "thin"|
  *    "medium"|
+ *    "dashed"|
+ *    "dotted"|
  *    "thick"|
  *    "double"|
  *    "hair"|
@@ -34,14 +37,14 @@ export type DiagonalBorderType = ("none" | "borderUp" | "borderDown" | "borderUp
  * @class Border
  * @classdesc Represents a border
  * @property {BorderType} style - The style of the border
- * @property {Color} color - The color of the border
+ * @property {ColorValue} color - The color of the border
  */
 export class Border {
     /**
      * @param {BorderType} [style] - The style of the border
-     * @param {Color} [color] - The color of the border
+     * @param {ColorValue} [color] - The color of the border
      */
-    constructor(style?: BorderType, color?: Color);
+    constructor(style?: BorderType, color?: ColorValue);
     /**
      * The style of the border
      * @type {BorderType}
@@ -49,29 +52,28 @@ export class Border {
     style: BorderType;
     /**
      * The color of the border
-     * @type {Color}
+     * @type {ColorValue}
      */
-    color: Color;
+    color: ColorValue;
 }
 /**
  * @class DiagonalBorder
  * @classdesc Represents a diagonal border
  * @property {DiagonalBorderType} dStyle - The style of the border
  * @property {BorderType} style - The style of the border
- * @property {Color} color - The color of the border
+ * @property {ColorValue} color - The color of the border
  */
 export class DiagonalBorder extends Border {
     /**
      * @param {BorderType} [style] - The style of the border
-     * @param {Color} [color] - The color of the border
+     * @param {ColorValue} [color] - The color of the border
      * @param {DiagonalBorderType} [dStyle] - The style of the border
      */
-    constructor(style?: BorderType, color?: Color, dStyle?: DiagonalBorderType);
+    constructor(style?: BorderType, color?: ColorValue, dStyle?: DiagonalBorderType);
     /**
      * The style of the border
      * @type {DiagonalBorderType}
      */
     dStyle: DiagonalBorderType;
 }
-import Color = require("./color");
 //# sourceMappingURL=border.d.ts.map
\ No newline at end of file
@@ -1,45 +1,54 @@
 export = Cell;
+/** @typedef {import('./format').FormatValue} FormatValue */
 /**
- * @typedef {(number|string|Link|Formula)} CellValue
- * @typedef {("number"|"string"|"link"|"date"|"formula")} CellType
+ * @typedef {(number|string|boolean|Link|Formula)} CellValue
+ * @typedef {("number"|"string"|"boolean"|"link"|"date"|"formula")} CellType
  */
 /**
  *
  * @class Cell
  * @classdesc Represents a cell in the grid
- * @property {number} col - The column index of the cell
- * @property {number} row - The row index of the cell
+ * @property {number} [col] - The column index of the cell (unset when given by `cell`)
+ * @property {number} [row] - The row index of the cell (unset when given by `cell`)
+ * @property {string} [cell] - The cell in A1 notation ("B2"), in place of `row` and `col`
  * @property {CellValue} value - The value of the cell
  * @property {false} merged - Whether the cell is part of a merged cell
  * @property {CellType} [celType] - The type of the cell.
- * @property {Format} [format] - The format of the cell
+ * @property {FormatValue} [format] - The format of the cell
  */
 declare class Cell {
     /**
      * @param {Object} opts - Options for the cell
-     * @param {number} opts.col - The column index of the cell
-     * @param {number} opts.row - The row index of the cell
+     * @param {number} [opts.col] - The column index of the cell
+     * @param {number} [opts.row] - The row index of the cell
+     * @param {string} [opts.cell] - The cell in A1 notation ("B2"), in place of `row` and `col`
      * @param {CellValue} opts.value - The value of the cell
      * @param {CellType} [opts.cellType] - The type of the cell
-     * @param {Format} [opts.format] - The format of the cell
+     * @param {FormatValue} [opts.format] - The format of the cell
      */
     constructor(opts: {
-        col: number;
-        row: number;
+        col?: number;
+        row?: number;
+        cell?: string;
         value: CellValue;
         cellType?: CellType;
-        format?: Format;
+        format?: FormatValue;
     });
     /**
      * The column index of the cell
-     * @type {number}
+     * @type {number|undefined}
      */
-    col: number;
+    col: number | undefined;
     /**
      * The row index of the cell
-     * @type {number}
+     * @type {number|undefined}
      */
-    row: number;
+    row: number | undefined;
+    /**
+     * The cell in A1 notation
+     * @type {string|undefined}
+     */
+    cell: string | undefined;
     /**
      * The value of the cell
      * @type {CellValue}
@@ -52,16 +61,16 @@ declare class Cell {
     cellType: CellType | undefined;
     /**
      * The format of the cell
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
 }
 declare namespace Cell {
-    export { CellValue, CellType };
+    export { FormatValue, CellValue, CellType };
 }
-import Format = require("./format");
-type CellValue = (number | string | Link | Formula);
-type CellType = ("number" | "string" | "link" | "date" | "formula");
+type FormatValue = import("./format").FormatValue;
+type CellValue = (number | string | boolean | Link | Formula);
+type CellType = ("number" | "string" | "boolean" | "link" | "date" | "formula");
 import Link = require("./link");
 import Formula = require("./formula");
-//# sourceMappingURL=cell.d.ts.map
\ No newline at end of file
+//# sourceMappingURL=cell.d.ts.map
@@ -1,3 +1,5 @@
+export type ColorValue = import("./color").ColorValue;
+export type FormatValue = import("./format").FormatValue;
 export type ConditionalFormatEnumType = ("automatic" | "lowest" | "number" | "percent" | "formula" | "percentile" | "highest");
 export type ConditionalFormatAverageRule = ("aboveAverage" | "belowAverage" | "equalOrAboveAverage" | "equalOrBelowAverage" | "oneStandardDeviationAbove" | "oneStandardDeviationBelow" | "twoStandardDeviationsAbove" | "twoStandardDeviationsBelow" | "threeStandardDeviationsAbove" | "threeStandardDeviationsBelow");
 export type ConditionalFormatDataBarAxisPosition = ("automatic" | "midpoint" | "none");
@@ -204,8 +206,9 @@ export class ConditionalFormat {
      */
     constructor(type: ConditionalFormatClassType, multiRange?: string, stopIfTrue?: boolean);
     /**
+     * The id of the conditional format. Two conditional formats of a workbook can
+     * only share an id when they are the same rule.
      * @type {number}
-     * @default Math.floor(Math.random() * 1000000)
      */
     id: number;
     /**
@@ -232,6 +235,12 @@ export class ConditionalFormat {
      * @returns {void}
      */
     setStopIfTrue(stopIfTrue: boolean): void;
+    /**
+     * A copy of the conditional format with an id of its own, to change without
+     * changing the original.
+     * @returns {this}
+     */
+    clone(): this;
 }
 /**
  * @typedef {Object} ConditionalFormatTypeRule
@@ -244,8 +253,8 @@ export class ConditionalFormat {
  * @classdesc Represents a 2 Color Scale conditional format.
  * Used to represent a Cell style conditional format in Excel. A 2 Color Scale Cell conditional format shows a per cell color gradient from the minimum value to the maximum value.
  * @extends ConditionalFormat
- * @property {Color} [minColor] - The color for the minimum value.(If not set, Excel will use the default color for the minimum value)
- * @property {Color} [maxColor] - The color for the maximum value.(If not set, Excel will use the default color for the maximum value)
+ * @property {ColorValue} [minColor] - The color for the minimum value.(If not set, Excel will use the default color for the minimum value)
+ * @property {ColorValue} [maxColor] - The color for the maximum value.(If not set, Excel will use the default color for the maximum value)
  * @property {ConditionalFormatTwoColorScaleRule} [minRule] - The rule for the minimum value.
  * @property {ConditionalFormatTwoColorScaleRule} [maxRule] - The rule for the maximum value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
@@ -254,31 +263,31 @@ export class ConditionalFormat {
 export class ConditionalFormatTwoColorScale extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
-     * @param {Color} [options.minColor] - The color for the minimum value.
-     * @param {Color} [options.maxColor] - The color for the maximum value.
+     * @param {ColorValue} [options.minColor] - The color for the minimum value.
+     * @param {ColorValue} [options.maxColor] - The color for the maximum value.
      * @param {ConditionalFormatTypeRule} [options.minRule] - The rule for the minimum value.
      * @param {ConditionalFormatTypeRule} [options.maxRule] - The rule for the maximum value.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
-        minColor?: Color;
-        maxColor?: Color;
+        minColor?: ColorValue;
+        maxColor?: ColorValue;
         minRule?: ConditionalFormatTypeRule;
         maxRule?: ConditionalFormatTypeRule;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    minColor: Color | undefined;
+    minColor: ColorValue | undefined;
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    maxColor: Color | undefined;
+    maxColor: ColorValue | undefined;
     /**
      * @type {ConditionalFormatTypeRule|undefined}
      * @default undefined
@@ -290,13 +299,13 @@ export class ConditionalFormatTwoColorScale extends ConditionalFormat {
      */
     maxRule: ConditionalFormatTypeRule | undefined;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setMinColor(color: Color): void;
+    setMinColor(color: ColorValue): void;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setMaxColor(color: Color): void;
+    setMaxColor(color: ColorValue): void;
     /**
      * @param {ConditionalFormatTypeRule} rule
      */
@@ -311,15 +320,15 @@ export class ConditionalFormatTwoColorScale extends ConditionalFormat {
  * @classdesc Represents a 3 Color Scale conditional format.
  * Used to represent a Cell style conditional format in Excel. A 3 Color Scale Cell conditional format shows a per cell color gradient from the minimum value to the maximum value.
  * @extends ConditionalFormatTwoColorScale
- * @property {Color} [midColor] - The color for the mid value.(If not set, Excel will use the default color for the mid value)
+ * @property {ColorValue} [midColor] - The color for the mid value.(If not set, Excel will use the default color for the mid value)
  * @property {ConditionalFormatTwoColorScaleRule} [midRule] - The rule for the mid value.
  */
 export class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorScale {
     /**
      * @param {Object} [options] - The options object
-     * @param {Color} [options.minColor] - The color for the minimum value.
-     * @param {Color} [options.midColor] - The color for the mid value.
-     * @param {Color} [options.maxColor] - The color for the maximum value.
+     * @param {ColorValue} [options.minColor] - The color for the minimum value.
+     * @param {ColorValue} [options.midColor] - The color for the mid value.
+     * @param {ColorValue} [options.maxColor] - The color for the maximum value.
      * @param {ConditionalFormatTypeRule} [options.minRule] - The rule for the minimum value.
      * @param {ConditionalFormatTypeRule} [options.midRule] - The rule for the maximum value.
      * @param {ConditionalFormatTypeRule} [options.maxRule] - The rule for the maximum value.
@@ -327,9 +336,9 @@ export class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorS
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
-        minColor?: Color;
-        midColor?: Color;
-        maxColor?: Color;
+        minColor?: ColorValue;
+        midColor?: ColorValue;
+        maxColor?: ColorValue;
         minRule?: ConditionalFormatTypeRule;
         midRule?: ConditionalFormatTypeRule;
         maxRule?: ConditionalFormatTypeRule;
@@ -337,19 +346,19 @@ export class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorS
         stopIfTrue?: boolean;
     });
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    midColor: Color | undefined;
+    midColor: ColorValue | undefined;
     /**
      * @type {ConditionalFormatTypeRule|undefined}
      * @default undefined
      */
     midRule: ConditionalFormatTypeRule | undefined;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setMidColor(color: Color): void;
+    setMidColor(color: ColorValue): void;
     /**
      * @param {ConditionalFormatTypeRule} rule
      */
@@ -360,7 +369,7 @@ export class ConditionalFormatThreeColorScale extends ConditionalFormatTwoColorS
  * @classdesc Represents an Average/Standard Deviation style conditional format
  * Is used to represent a Average or Standard Deviation style conditional format in Excel
  * @property {ConditionalFormatAverageRule} rule - The rule for the average value.(default: 'aboveAverage')
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -368,13 +377,13 @@ export class ConditionalFormatAverage extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
      * @param {ConditionalFormatAverageRule} [options.rule] - The rule for the average value.(default: 'aboveAverage')
-     * @param {Format} [options.format] - The format for the average value.
+     * @param {FormatValue} [options.format] - The format for the average value.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
         rule?: ConditionalFormatAverageRule;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -384,25 +393,25 @@ export class ConditionalFormatAverage extends ConditionalFormat {
      */
     rule: ConditionalFormatAverageRule;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {ConditionalFormatAverageRule} rule
      */
     setRule(rule: ConditionalFormatAverageRule): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
 /**
  * @class ConditionalFormatBlank
  * @classdesc Represents a a Blank/Non-blank conditional format.
  * @extends ConditionalFormat
  * @property {boolean} invert - Inverts the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -410,13 +419,13 @@ export class ConditionalFormatBlank extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
      * @param {boolean} [options.invert] - Inverts the conditional format.
-     * @param {Format} [options.format] - The format for the average value.
+     * @param {FormatValue} [options.format] - The format for the average value.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
         invert?: boolean;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -426,25 +435,25 @@ export class ConditionalFormatBlank extends ConditionalFormat {
      */
     invert: boolean;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {boolean} invert
      */
     setInvert(invert: boolean): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
 /**
  * @class ConditionalFormatCell
  * @classdesc Represents a cell style conditional format.
  * @extends ConditionalFormat
  * @property {ConditionalFormatCellRule} rule - The rule for the cell.
- * @property {Format} [format] - The format for the cell.
+ * @property {FormatValue} [format] - The format for the cell.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -452,13 +461,13 @@ export class ConditionalFormatCell extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
      * @param {ConditionalFormatCellRule} [options.rule] - The rule for the cell.
-     * @param {Format} [options.format] - The format for the cell.
+     * @param {FormatValue} [options.format] - The format for the cell.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
         rule?: ConditionalFormatCellRule;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -467,34 +476,34 @@ export class ConditionalFormatCell extends ConditionalFormat {
      */
     rule: ConditionalFormatCellRule;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {ConditionalFormatCellRule} rule
      */
     setRule(rule: ConditionalFormatCellRule): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
 /**
  * @class ConditionalFormatDataBar
  * @classdesc Represents a Data Bar style conditional format.
  * @extends ConditionalFormat
- * @property {Color} [axisColor] - The color of the axis.
+ * @property {ColorValue} [axisColor] - The color of the axis.
  * @property {ConditionalFormatDataBarAxisPosition} [axisPosition] - The position of the axis.
  * @property {boolean} [barOnly] - Show only the bar.
- * @property {Color} [borderColor] - The color of the border.
+ * @property {ColorValue} [borderColor] - The color of the border.
  * @property {boolean} [borderOff] - Turn off the border.
  * @property {ConditionalFormatDataBarDirection} [direction] - The direction of the data bar.
- * @property {Color} [fillColor] - The color of the fill.
+ * @property {ColorValue} [fillColor] - The color of the fill.
  * @property {ConditionalFormatTypeRule} [maxRule] - The rule for the maximum value.
  * @property {ConditionalFormatTypeRule} [minRule] - The rule for the minimum value.
- * @property {Color} [negativeBorderColor] - The color of the negative border.
- * @property {Color} [negativeFillColor] - The color of the negative fill.
+ * @property {ColorValue} [negativeBorderColor] - The color of the negative border.
+ * @property {ColorValue} [negativeFillColor] - The color of the negative fill.
  * @property {boolean} [solidFill] - Show a solid fill.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
@@ -502,42 +511,42 @@ export class ConditionalFormatCell extends ConditionalFormat {
 export class ConditionalFormatDataBar extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
-     * @param {Color} [options.axisColor] - The color of the axis.
+     * @param {ColorValue} [options.axisColor] - The color of the axis.
      * @param {ConditionalFormatDataBarAxisPosition} [options.axisPosition] - The position of the axis.
      * @param {boolean} [options.barOnly] - Show only the bar.
-     * @param {Color} [options.borderColor] - The color of the border.
+     * @param {ColorValue} [options.borderColor] - The color of the border.
      * @param {boolean} [options.borderOff] - Turn off the border.
      * @param {ConditionalFormatDataBarDirection} [options.direction] - The direction of the data bar.
-     * @param {Color} [options.fillColor] - The color of the fill.
+     * @param {ColorValue} [options.fillColor] - The color of the fill.
      * @param {ConditionalFormatTypeRule} [options.maxRule] - The rule for the maximum value.
      * @param {ConditionalFormatTypeRule} [options.minRule] - The rule for the minimum value.
-     * @param {Color} [options.negativeBorderColor] - The color of the negative border.
-     * @param {Color} [options.negativeFillColor] - The color of the negative fill.
+     * @param {ColorValue} [options.negativeBorderColor] - The color of the negative border.
+     * @param {ColorValue} [options.negativeFillColor] - The color of the negative fill.
      * @param {boolean} [options.solidFill] - Show a solid fill.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
-        axisColor?: Color;
+        axisColor?: ColorValue;
         axisPosition?: ConditionalFormatDataBarAxisPosition;
         barOnly?: boolean;
-        borderColor?: Color;
+        borderColor?: ColorValue;
         borderOff?: boolean;
         direction?: ConditionalFormatDataBarDirection;
-        fillColor?: Color;
+        fillColor?: ColorValue;
         maxRule?: ConditionalFormatTypeRule;
         minRule?: ConditionalFormatTypeRule;
-        negativeBorderColor?: Color;
-        negativeFillColor?: Color;
+        negativeBorderColor?: ColorValue;
+        negativeFillColor?: ColorValue;
         solidFill?: boolean;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    axisColor: Color | undefined;
+    axisColor: ColorValue | undefined;
     /**
      * @type {ConditionalFormatDataBarAxisPosition|undefined}
      * @default undefined
@@ -549,10 +558,10 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
      */
     barOnly: boolean | undefined;
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    borderColor: Color | undefined;
+    borderColor: ColorValue | undefined;
     /**
      * @type {boolean|undefined}
      * @default undefined
@@ -564,10 +573,10 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
      */
     direction: ConditionalFormatDataBarDirection | undefined;
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    fillColor: Color | undefined;
+    fillColor: ColorValue | undefined;
     /**
      * @type {ConditionalFormatTypeRule|undefined}
      * @default undefined
@@ -579,24 +588,24 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
      */
     minRule: ConditionalFormatTypeRule | undefined;
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    negativeBorderColor: Color | undefined;
+    negativeBorderColor: ColorValue | undefined;
     /**
-     * @type {Color|undefined}
+     * @type {ColorValue|undefined}
      * @default undefined
      */
-    negativeFillColor: Color | undefined;
+    negativeFillColor: ColorValue | undefined;
     /**
      * @type {boolean|undefined}
      * @default undefined
      */
     solidFill: boolean | undefined;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setAxisColor(color: Color): void;
+    setAxisColor(color: ColorValue): void;
     /**
      * @param {ConditionalFormatDataBarAxisPosition} position
      */
@@ -606,9 +615,9 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
      */
     setBarOnly(barOnly: boolean): void;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setBorderColor(color: Color): void;
+    setBorderColor(color: ColorValue): void;
     /**
      * @param {boolean} borderOff
      */
@@ -618,9 +627,9 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
      */
     setDirection(direction: ConditionalFormatDataBarDirection): void;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setFillColor(color: Color): void;
+    setFillColor(color: ColorValue): void;
     /**
      * @param {ConditionalFormatTypeRule} rule
      */
@@ -630,13 +639,13 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
      */
     setMinRule(rule: ConditionalFormatTypeRule): void;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setNegativeBorderColor(color: Color): void;
+    setNegativeBorderColor(color: ColorValue): void;
     /**
-     * @param {Color} color
+     * @param {ColorValue} color
      */
-    setNegativeFillColor(color: Color): void;
+    setNegativeFillColor(color: ColorValue): void;
     /**
      * @param {boolean} solidFill
      */
@@ -646,7 +655,7 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
  * @class ConditionalFormatDate
  * @classdesc Represents a Date style conditional format.
  * @extends ConditionalFormat
- * @property {Format} [format] - The format for the date.
+ * @property {FormatValue} [format] - The format for the date.
  * @property {ConditionalFormatDateRule} [rule] - The rule for the date.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
@@ -654,30 +663,30 @@ export class ConditionalFormatDataBar extends ConditionalFormat {
 export class ConditionalFormatDate extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
-     * @param {Format} [options.format] - The format for the date.
+     * @param {FormatValue} [options.format] - The format for the date.
      * @param {ConditionalFormatDateRule} [options.rule] - The rule for the date.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
-        format?: Format;
+        format?: FormatValue;
         rule?: ConditionalFormatDateRule;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @type {ConditionalFormatDateRule|undefined}
      */
     rule: ConditionalFormatDateRule | undefined;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
     /**
      * @param {ConditionalFormatDateRule} rule
      */
@@ -688,7 +697,7 @@ export class ConditionalFormatDate extends ConditionalFormat {
  * @classdesc Represents a Duplicate/Unique conditional format.
  * @extends ConditionalFormat
  * @property {boolean} invert - Inverts the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -696,13 +705,13 @@ export class ConditionalFormatDuplicate extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
      * @param {boolean} [options.invert] - Inverts the conditional format.
-     * @param {Format} [options.format] - The format for the average value.
+     * @param {FormatValue} [options.format] - The format for the average value.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
         invert?: boolean;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -712,25 +721,25 @@ export class ConditionalFormatDuplicate extends ConditionalFormat {
      */
     invert: boolean;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {boolean} invert
      */
     setInvert(invert: boolean): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
 /**
  * @class ConditionalFormatError
  * @classdesc Represents an Error style conditional format.
  * @extends ConditionalFormat
  * @property {boolean} invert - Inverts the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -738,13 +747,13 @@ export class ConditionalFormatError extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
      * @param {boolean} [options.invert] - Inverts the conditional format.
-     * @param {Format} [options.format] - The format for the average value.
+     * @param {FormatValue} [options.format] - The format for the average value.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
         invert?: boolean;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -754,15 +763,15 @@ export class ConditionalFormatError extends ConditionalFormat {
      */
     invert: boolean;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * Sets the format
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
     /**
      * Sets the invert flag
      * @param {boolean} invert
@@ -774,7 +783,7 @@ export class ConditionalFormatError extends ConditionalFormat {
  * @classdesc Represents a Formula style conditional format.
  * @extends ConditionalFormat
  * @property {Formula} [formula] - The formula(non-dynamic) for the conditional format.
- * @property {Format} [format] - The format for the average value.
+ * @property {FormatValue} [format] - The format for the average value.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -782,13 +791,13 @@ export class ConditionalFormatFormula extends ConditionalFormat {
     /**
      * @param {Object} [options] - The options object
      * @param {Formula} [options.formula] - The formula(non-dynamic) for the conditional format.
-     * @param {Format} [options.format] - The format for the average value.
+     * @param {FormatValue} [options.format] - The format for the average value.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options?: {
         formula?: Formula;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -798,18 +807,18 @@ export class ConditionalFormatFormula extends ConditionalFormat {
      */
     formula: Formula | undefined;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {Formula} formula
      */
     setFormula(formula: Formula): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
 /**
  * @class ConditionalFormatCustomIcon
@@ -945,7 +954,7 @@ export class ConditionalFormatIconSet extends ConditionalFormat {
  * @classdesc Represents a Text style conditional format.
  * @extends ConditionalFormat
  * @property {ConditionalFormatTextRule} rule - The rule for the text.
- * @property {Format} [format] - The format for the text.
+ * @property {FormatValue} [format] - The format for the text.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -953,13 +962,13 @@ export class ConditionalFormatText extends ConditionalFormat {
     /**
      * @param {Object} options - The options object
      * @param {ConditionalFormatTextRule} options.rule - The rule for the text.
-     * @param {Format} [options.format] - The format for the text.
+     * @param {FormatValue} [options.format] - The format for the text.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options: {
         rule: ConditionalFormatTextRule;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -968,25 +977,25 @@ export class ConditionalFormatText extends ConditionalFormat {
      */
     rule: ConditionalFormatTextRule;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {ConditionalFormatTextRule} rule
      */
     setRule(rule: ConditionalFormatTextRule): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
 /**
  * @class ConditionalFormatTop
  * @classdesc Represents a Top style conditional format.
  * @extends ConditionalFormat
  * @property {ConditionalFormatTopRule} rule - The rule for the top values.
- * @property {Format} [format] - The format for the top values.
+ * @property {FormatValue} [format] - The format for the top values.
  * @property {string} [multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
  * @property {boolean} [stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
  */
@@ -994,13 +1003,13 @@ export class ConditionalFormatTop extends ConditionalFormat {
     /**
      * @param {Object} options - The options object
      * @param {ConditionalFormatTopRule} options.rule - The rule for the top values.
-     * @param {Format} [options.format] - The format for the top values.
+     * @param {FormatValue} [options.format] - The format for the top values.
      * @param {string} [options.multiRange] - Is used to extend a conditional format over non-contiguous ranges like "B3:D6 I3:K6 B9:D12 I9:K12"
      * @param {boolean} [options.stopIfTrue] - Is used to set the “Stop if true” feature of a conditional formatting rule when more than one rule is applied to a cell or a range of cells. When this parameter is set then subsequent rules are not evaluated if the current rule is true.
      */
     constructor(options: {
         rule: ConditionalFormatTopRule;
-        format?: Format;
+        format?: FormatValue;
         multiRange?: string;
         stopIfTrue?: boolean;
     });
@@ -1009,20 +1018,18 @@ export class ConditionalFormatTop extends ConditionalFormat {
      */
     rule: ConditionalFormatTopRule;
     /**
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
      * @default undefined
      */
-    format: Format | undefined;
+    format: FormatValue | undefined;
     /**
      * @param {ConditionalFormatTopRule} rule
      */
     setRule(rule: ConditionalFormatTopRule): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
 }
-import Color = require("./color");
-import Format = require("./format");
 import Formula = require("./formula");
 //# sourceMappingURL=conditional_format.d.ts.map
\ No newline at end of file
@@ -1,4 +1,5 @@
 export = Format;
+/** @typedef {import('./color').ColorValue} ColorValue */
 /**
  * @typedef {(
  *   "general"|
@@ -45,13 +46,38 @@ export = Format;
  *   "singleAccounting"|
  *   "doubleAccounting"
  * )} FormatUnderline - The underline style
+ *
+ * @typedef {(
+ *   "top"|
+ *   "bottom"|
+ *   "center"|
+ *   "justify"|
+ *   "distributed"
+ * )} FormatVerticalAlign - The vertical alignment of the cell
+ *
+ * @typedef {(
+ *   "none"|
+ *   "superscript"|
+ *   "subscript"
+ * )} FormatScript - The font script
+ *
+ * @typedef {(
+ *   "context"|
+ *   "leftToRight"|
+ *   "rightToLeft"
+ * )} FormatReadingDirection - The reading direction of the text
+ *
+ * @typedef {(
+ *   "major"|
+ *   "minor"
+ * )} FormatThemeFont - The theme font (headings or body)
  */
 /**
  * @class Format
  * @classdesc Represents a format
- * @property {number} id - the id of the format (do not set this directly)
+ * @property {number} id - The id of the format. Formats are deduplicated by their properties, the id only has to be unique per distinct format
  * @property {FormatAlign|undefined} [align=undefined] - The alignment of the cell
- * @property {Color} [backgroundColor] - The background color of the cell
+ * @property {ColorValue} [backgroundColor] - The background color of the cell
  * @property {boolean} [bold] - If the font is bold
  * @property {Border} [leftBorder] - The left border of the cell
  * @property {Border} [rightBorder] - The right border of the cell
@@ -59,13 +85,13 @@ export = Format;
  * @property {Border} [bottomBorder] - The bottom border of the cell
  * @property {DiagonalBorder} [diagonalBorder] - The diagonal border of the cell
  * @property {number} [charset] - The charset of the font
- * @property {Color} [fontColor] - The color of the font
+ * @property {ColorValue} [fontColor] - The color of the font
  * @property {number} [fontFamily] - The family of the font
  * @property {string} [fontName] - The name of the font
  * @property {string} [fontScheme] - The font scheme
  * @property {number} [fontSize] - The font size
  * @property {boolean} [strikeThrough] - If the font is strike through
- * @property {Color} [foregroundColor] - The foreground color
+ * @property {ColorValue} [foregroundColor] - The foreground color
  * @property {boolean} [hidden] - If the format is hidden
  * @property {boolean} [hyperlink] - If the format is hyperlinked
  * @property {number} [indent] - The indent level
@@ -75,14 +101,23 @@ export = Format;
  * @property {number} [numFmtId] - The number format id
  * @property {FormatPattern} [pattern] - The pattern
  * @property {FormatUnderline} [underline] - The underline style
+ * @property {FormatVerticalAlign} [verticalAlign] - The vertical alignment of the cell
+ * @property {boolean} [textWrap] - If the text wraps
+ * @property {number} [rotation] - The text rotation (-90 to 90, or 270 for stacked text)
+ * @property {boolean} [shrink] - If the text shrinks to fit the cell
+ * @property {FormatScript} [fontScript] - The font script
+ * @property {FormatReadingDirection} [readingDirection] - The reading direction
+ * @property {boolean} [quotePrefix] - If the value is prefixed with a quote
+ * @property {FormatThemeFont} [themeFont] - The theme font
  *
  */
 declare class Format {
     /**
      *
      * @param {Object} [options] - The options object
+     * @param {number} [options.id] - The id of the format (assigned automatically if not set)
      * @param {FormatAlign} [options.align] - The alignment of the cell
-     * @param {Color} [options.backgroundColor] - The background color of the cell
+     * @param {ColorValue} [options.backgroundColor] - The background color of the cell
      * @param {boolean} [options.bold] - If the font is bold
      * @param {Border} [options.leftBorder] - The left border of the cell
      * @param {Border} [options.rightBorder] - The right border of the cell
@@ -90,13 +125,13 @@ declare class Format {
      * @param {Border} [options.bottomBorder] - The bottom border of the cell
      * @param {DiagonalBorder} [options.diagonalBorder] - The diagonal border of the cell
      * @param {number} [options.charset] - The charset of the font
-     * @param {Color} [options.fontColor] - The color of the font
+     * @param {ColorValue} [options.fontColor] - The color of the font
      * @param {number} [options.fontFamily] - The family of the font
      * @param {string} [options.fontName] - The name of the font
      * @param {string} [options.fontScheme] - The font scheme
      * @param {number} [options.fontSize] - The font size
      * @param {boolean} [options.strikeThrough] - If the font is strike through
-     * @param {Color} [options.foregroundColor] - The foreground color
+     * @param {ColorValue} [options.foregroundColor] - The foreground color
      * @param {boolean} [options.hidden] - If the format is hidden
      * @param {boolean} [options.hyperlink] - If the format is hyperlinked
      * @param {number} [options.indent] - The indent level
@@ -106,10 +141,19 @@ declare class Format {
      * @param {number} [options.numFmtId] - The number format id
      * @param {FormatPattern} [options.pattern] - The pattern
      * @param {FormatUnderline} [options.underline] - The underline style
-     */
-    constructor({ align, backgroundColor, bold, leftBorder, rightBorder, topBorder, bottomBorder, diagonalBorder, charset, fontColor, fontFamily, fontName, fontScheme, fontSize, strikeThrough, foregroundColor, hidden, hyperlink, indent, italic, locked, numFmt, numFmtId, pattern, underline, }?: {
+     * @param {FormatVerticalAlign} [options.verticalAlign] - The vertical alignment of the cell
+     * @param {boolean} [options.textWrap] - If the text wraps
+     * @param {number} [options.rotation] - The text rotation (-90 to 90, or 270 for stacked text)
+     * @param {boolean} [options.shrink] - If the text shrinks to fit the cell
+     * @param {FormatScript} [options.fontScript] - The font script
+     * @param {FormatReadingDirection} [options.readingDirection] - The reading direction
+     * @param {boolean} [options.quotePrefix] - If the value is prefixed with a quote
+     * @param {FormatThemeFont} [options.themeFont] - The theme font
+     */
+    constructor({ id, align, backgroundColor, bold, leftBorder, rightBorder, topBorder, bottomBorder, diagonalBorder, charset, fontColor, fontFamily, fontName, fontScheme, fontSize, strikeThrough, foregroundColor, hidden, hyperlink, indent, italic, locked, numFmt, numFmtId, pattern, underline, verticalAlign, textWrap, rotation, shrink, fontScript, readingDirection, quotePrefix, themeFont, }?: {
+        id?: number;
         align?: FormatAlign;
-        backgroundColor?: Color;
+        backgroundColor?: ColorValue;
         bold?: boolean;
         leftBorder?: Border;
         rightBorder?: Border;
@@ -117,13 +161,13 @@ declare class Format {
         bottomBorder?: Border;
         diagonalBorder?: DiagonalBorder;
         charset?: number;
-        fontColor?: Color;
+        fontColor?: ColorValue;
         fontFamily?: number;
         fontName?: string;
         fontScheme?: string;
         fontSize?: number;
         strikeThrough?: boolean;
-        foregroundColor?: Color;
+        foregroundColor?: ColorValue;
         hidden?: boolean;
         hyperlink?: boolean;
         indent?: number;
@@ -133,7 +177,19 @@ declare class Format {
         numFmtId?: number;
         pattern?: FormatPattern;
         underline?: FormatUnderline;
+        verticalAlign?: FormatVerticalAlign;
+        textWrap?: boolean;
+        rotation?: number;
+        shrink?: boolean;
+        fontScript?: FormatScript;
+        readingDirection?: FormatReadingDirection;
+        quotePrefix?: boolean;
+        themeFont?: FormatThemeFont;
     });
+    /**
+     * The id of the format
+     * @type {number}
+     */
     id: number;
     /**
      * The alignment of the cell
@@ -143,10 +199,10 @@ declare class Format {
     align: FormatAlign | null;
     /**
      * The background color of the cell
-     * @type {?Color}
+     * @type {?ColorValue}
      * @default undefined
      */
-    backgroundColor: Color | null;
+    backgroundColor: ColorValue | null;
     /**
      * If the font is bold
      * @type {?boolean} [bold]
@@ -191,10 +247,10 @@ declare class Format {
     charset: number | null;
     /**
      * The font color of the cell
-     * @type {?Color}
+     * @type {?ColorValue}
      * @default undefined
      */
-    fontColor: Color | null;
+    fontColor: ColorValue | null;
     /**
      * The font family of the cell
      * @type {?number}
@@ -227,10 +283,10 @@ declare class Format {
     strikeThrough: boolean | null;
     /**
      * The foreground color of the cell
-     * @type {?Color}
+     * @type {?ColorValue}
      * @default undefined
      */
-    foregroundColor: Color | null;
+    foregroundColor: ColorValue | null;
     /**
      * If the cell is hidden
      * @type {?boolean}
@@ -285,6 +341,54 @@ declare class Format {
      * @default undefined
      */
     underline: string | null;
+    /**
+     * The vertical alignment of the cell
+     * @type {?FormatVerticalAlign}
+     * @default undefined
+     */
+    verticalAlign: FormatVerticalAlign | null;
+    /**
+     * If the text wraps
+     * @type {?boolean}
+     * @default undefined
+     */
+    textWrap: boolean | null;
+    /**
+     * The text rotation (-90 to 90, or 270 for stacked text)
+     * @type {?number}
+     * @default undefined
+     */
+    rotation: number | null;
+    /**
+     * If the text shrinks to fit the cell
+     * @type {?boolean}
+     * @default undefined
+     */
+    shrink: boolean | null;
+    /**
+     * The font script
+     * @type {?FormatScript}
+     * @default undefined
+     */
+    fontScript: FormatScript | null;
+    /**
+     * The reading direction
+     * @type {?FormatReadingDirection}
+     * @default undefined
+     */
+    readingDirection: FormatReadingDirection | null;
+    /**
+     * If the value is prefixed with a quote
+     * @type {?boolean}
+     * @default undefined
+     */
+    quotePrefix: boolean | null;
+    /**
+     * The theme font
+     * @type {?FormatThemeFont}
+     * @default undefined
+     */
+    themeFont: FormatThemeFont | null;
     /**
      * Sets alignment of the cell
      * @param {FormatAlign} align - The alignment of the cell
@@ -293,10 +397,10 @@ declare class Format {
     setAlignment(align: FormatAlign): void;
     /**
      * Sets the font color
-     * @param {Color} color - The color of the font
+     * @param {ColorValue} color - The color of the font
      * @returns {void}
      */
-    setBackgroundColor(color: Color): void;
+    setBackgroundColor(color: ColorValue): void;
     /**
      * Sets if the font is bold
      * @param {boolean} bold - If the font is bold
@@ -347,10 +451,10 @@ declare class Format {
     setFontCharset(charset: number): void;
     /**
      * Sets the font color
-     * @param {Color} color - The color of the font
+     * @param {ColorValue} color - The color of the font
      * @returns {void}
      */
-    setFontColor(color: Color): void;
+    setFontColor(color: ColorValue): void;
     /**
      * Sets the font family
      * @param {number} fontFamily - The family of the font
@@ -382,10 +486,10 @@ declare class Format {
     setFontStrikeThrough(): void;
     /**
      * Set the foreground color property
-     * @param {Color} color - The color
+     * @param {ColorValue} color - The color
      * @returns {void}
      */
-    setForegroundColor(color: Color): void;
+    setForegroundColor(color: ColorValue): void;
     /**
      * Set if the format is hidden
      * @param {boolean} hidden - If the format is hidden
@@ -440,11 +544,59 @@ declare class Format {
      * @returns {void}
      */
     setUnderline(underline: FormatUnderline): void;
+    /**
+     * Sets the vertical alignment of the cell
+     * @param {FormatVerticalAlign} verticalAlign - The vertical alignment of the cell
+     * @returns {void}
+     */
+    setVerticalAlignment(verticalAlign: FormatVerticalAlign): void;
+    /**
+     * Set if the text wraps
+     * @param {boolean} textWrap - If the text wraps
+     * @returns {void}
+     */
+    setTextWrap(textWrap: boolean): void;
+    /**
+     * Set the text rotation
+     * @param {number} rotation - The angle (-90 to 90), or 270 for vertically stacked text
+     * @returns {void}
+     */
+    setRotation(rotation: number): void;
+    /**
+     * Set if the text shrinks to fit the cell
+     * @param {boolean} shrink - If the text shrinks to fit the cell
+     * @returns {void}
+     */
+    setShrink(shrink: boolean): void;
+    /**
+     * Set the font script (superscript/subscript)
+     * @param {FormatScript} fontScript - The font script
+     * @returns {void}
+     */
+    setFontScript(fontScript: FormatScript): void;
+    /**
+     * Set the reading direction of the text
+     * @param {FormatReadingDirection} readingDirection - The reading direction
+     * @returns {void}
+     */
+    setReadingDirection(readingDirection: FormatReadingDirection): void;
+    /**
+     * Set if the value is prefixed with a quote, so Excel shows it as text
+     * @param {boolean} quotePrefix - If the value is prefixed with a quote
+     * @returns {void}
+     */
+    setQuotePrefix(quotePrefix: boolean): void;
+    /**
+     * Set the theme font, headings ("major") or body ("minor")
+     * @param {FormatThemeFont} themeFont - The theme font
+     * @returns {void}
+     */
+    setThemeFont(themeFont: FormatThemeFont): void;
 }
 declare namespace Format {
-    export { FormatAlign, FormatPattern, FormatUnderline };
+    export { ColorValue, FormatAlign, FormatPattern, FormatUnderline, FormatVerticalAlign, FormatScript, FormatReadingDirection, FormatThemeFont, FormatValue };
 }
-import Color = require("./color");
+type ColorValue = import("./color").ColorValue;
 import { Border } from "./border";
 import { DiagonalBorder } from "./border";
 /**
@@ -459,4 +611,24 @@ type FormatPattern = ("none" | "solid" | "mediumGray" | "darkGray" | "lightGray"
  * - The underline style
  */
 type FormatUnderline = ("none" | "single" | "double" | "singleAccounting" | "doubleAccounting");
+/**
+ * - The vertical alignment of the cell
+ */
+type FormatVerticalAlign = ("top" | "bottom" | "center" | "justify" | "distributed");
+/**
+ * - The font script
+ */
+type FormatScript = ("none" | "superscript" | "subscript");
+/**
+ * - The reading direction of the text
+ */
+type FormatReadingDirection = ("context" | "leftToRight" | "rightToLeft");
+/**
+ * - The theme font (headings or body)
+ */
+type FormatThemeFont = ("major" | "minor");
+/**
+ * A format, or the id of a format added to the workbook with `Workbook.addFormat`.
+ */
+type FormatValue = Format | number;
 //# sourceMappingURL=format.d.ts.map
\ No newline at end of file
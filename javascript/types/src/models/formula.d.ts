@@ -1,9 +1,14 @@
 export = Formula;
+/**
+ * The cached result of a formula. Numbers, booleans and strings keep their type in the file,
+ * a string naming an Excel error such as `'#N/A'` or `'#DIV/0!'` is written as that error.
+ * @typedef {number|boolean|string} FormulaResultValue
+ */
 /**
  * @class Formula
  * @classdesc Represents a worksheet formula.
  * @property {string} formula - The formula string.
- * @property {string} [result] - The result of the formula.
+ * @property {FormulaResultValue} [result] - The cached result of the formula.
  * @property {boolean} [useFutureFunctions=false] - Enable the use of newer Excel future functions in the formula
  * @property {boolean} [useTableFunctions=false] - Enable backward compatible formulas in table.
  * @property {boolean} [dynamic=false] - Enable the use of dynamic arrays in the formula
@@ -12,14 +17,14 @@ declare class Formula {
     /**
      * @param {Object} opts - Options for the formula.
      * @param {string} opts.formula - The formula string.
-     * @param {string} [opts.result] - The result of the formula.
+     * @param {FormulaResultValue} [opts.result] - The cached result of the formula, shown by readers that don't recalculate.
      * @param {boolean} [opts.useFutureFunctions=false] - Enable the use of newer Excel future functions in the formula
      * @param {boolean} [opts.useTableFunctions=false] - Enable backward compatible formulas in table.
      * @param {boolean} [opts.dynamic=false] - Enable the use of dynamic arrays in the formula
      */
     constructor(opts: {
         formula: string;
-        result?: string;
+        result?: FormulaResultValue;
         useFutureFunctions?: boolean;
         useTableFunctions?: boolean;
         dynamic?: boolean;
@@ -30,10 +35,10 @@ declare class Formula {
      */
     formula: string;
     /**
-     * The result of the formula.
-     * @type {?string|undefined}
+     * The cached result of the formula.
+     * @type {?FormulaResultValue|undefined}
      */
-    result: (string | undefined) | null;
+    result: (FormulaResultValue | undefined) | null;
     /**
      * Enable the use of newer Excel future functions in the formula
      * @type {boolean}
@@ -50,10 +55,10 @@ declare class Formula {
      */
     dynamic: boolean;
     /**
-     * Set the result of the formula.
-     * @param {string} result - The result of the formula.
+     * Set the cached result of the formula.
+     * @param {FormulaResultValue} result - The result of the formula.
      */
-    setResult(result: string): void;
+    setResult(result: FormulaResultValue): void;
     /**
      * Set the formula string.
      * @param {string} formula - The formula string.
@@ -70,4 +75,12 @@ declare class Formula {
      */
     setUseTableFunctions(useTableFunctions: boolean): void;
 }
+declare namespace Formula {
+    export { FormulaResultValue };
+}
+/**
+ * The cached result of a formula. Numbers, booleans and strings keep their type in the file,
+ * a string naming an Excel error such as `'#N/A'` or `'#DIV/0!'` is written as that error.
+ */
+type FormulaResultValue = number | boolean | string;
 //# sourceMappingURL=formula.d.ts.map
\ No newline at end of file
@@ -1,7 +1,8 @@
 export = MergedCell;
+/** @typedef {import('./format').FormatValue} FormatValue */
 /**
- * @typedef {(number|string|Link|Formula)} CellValue
- * @typedef {("number"|"string"|"link"|"date"|"formula")} CellType
+ * @typedef {(number|string|boolean|Link|Formula)} CellValue
+ * @typedef {("number"|"string"|"boolean"|"link"|"date"|"formula")} CellType
  */
 /**
  *
@@ -11,52 +12,60 @@ export = MergedCell;
  * @property {number} firstCol - The first column index of the merged cell
  * @property {number} lastRow - The last row index of the merged cell
  * @property {number} lastCol - The last column index of the merged cell
+ * @property {string} [range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
  * @property {CellValue} value - The value of the cell
  * @property {true} merged - Whether the cell is part of a merged cell
- * @property {Format} format - The format of the cell
+ * @property {FormatValue} format - The format of the cell
  * @property {CellType} [celType] - The type of the cell.
  */
 declare class MergedCell {
     /**
      * @param {Object} opts - Options for the cell
-     * @param {number} opts.firstRow - The first row index of the merged cell
-     * @param {number} opts.firstCol - The first column index of the merged cell
-     * @param {number} opts.lastRow - The last row index of the merged cell
-     * @param {number} opts.lastCol - The last column index of the merged cell
+     * @param {number} [opts.firstRow] - The first row index of the merged cell
+     * @param {number} [opts.firstCol] - The first column index of the merged cell
+     * @param {number} [opts.lastRow] - The last row index of the merged cell
+     * @param {number} [opts.lastCol] - The last column index of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {CellValue} opts.value - The value of the cell
-     * @param {Format} opts.format - The format of the cell
+     * @param {FormatValue} opts.format - The format of the cell
      * @param {CellType} [opts.cellType] - The type of the cell
-  
+
      */
     constructor(opts: {
-        firstRow: number;
-        firstCol: number;
-        lastRow: number;
-        lastCol: number;
+        firstRow?: number;
+        firstCol?: number;
+        lastRow?: number;
+        lastCol?: number;
+        range?: string;
         value: CellValue;
-        format: Format;
+        format: FormatValue;
         cellType?: CellType;
     });
     /**
      * The first row index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstRow: number;
+    firstRow: number | undefined;
     /**
      * The first column index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstCol: number;
+    firstCol: number | undefined;
     /**
      * The last row index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastRow: number;
+    lastRow: number | undefined;
     /**
      * The last column index of the merged cell
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastCol: number;
+    lastCol: number | undefined;
+    /**
+     * The merged range in A1 notation
+     * @type {string|undefined}
+     */
+    range: string | undefined;
     /**
      * The value of the merged cell
      * @type {CellValue}
@@ -69,16 +78,16 @@ declare class MergedCell {
     cellType: CellType | undefined;
     /**
      * The format of the cell
-     * @type {Format}
+     * @type {FormatValue}
      */
-    format: Format;
+    format: FormatValue;
 }
 declare namespace MergedCell {
-    export { CellValue, CellType };
+    export { FormatValue, CellValue, CellType };
 }
-import Format = require("./format");
-type CellValue = (number | string | Link | Formula);
-type CellType = ("number" | "string" | "link" | "date" | "formula");
+type FormatValue = import("./format").FormatValue;
+type CellValue = (number | string | boolean | Link | Formula);
+type CellType = ("number" | "string" | "boolean" | "link" | "date" | "formula");
 import Link = require("./link");
 import Formula = require("./formula");
-//# sourceMappingURL=merged_cell.d.ts.map
\ No newline at end of file
+//# sourceMappingURL=merged_cell.d.ts.map
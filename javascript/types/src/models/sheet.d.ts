@@ -1,3 +1,4 @@
+export type FormatValue = import("./format").FormatValue;
 export type SizeConfig = {
     /**
      * - The value of the size
@@ -16,7 +17,7 @@ export type RowCellConfig = {
     /**
      * - The format of the cell (will be overwritten by the cell format)
      */
-    format?: Format;
+    format?: FormatValue;
     /**
      * - The height/width of the row/column
      */
@@ -26,17 +27,38 @@ export type RowCellConfig = {
      */
     hidden?: boolean;
 };
+/**
+ * What to do when the same cell is written more than once:
+ * - `error`: saving throws an error naming the cell
+ * - `lastWins`: the last write is kept, the default
+ * - `firstWins`: the first write is kept
+ * - `mergeFormat`: the last value is kept, with its format layered over the earlier formats
+ */
+export type DuplicateCellPolicy = ("error" | "lastWins" | "firstWins" | "mergeFormat");
+export type ColumnValues = (Float64Array | Int32Array | number[] | string[] | Date[]);
+/**
+ * The type a CSV column is written as. `auto` recognizes numbers, booleans, ISO dates
+ * (`2024-03-15`, `2024-03-15T09:30:00`) and formulas (`=A1*2`) that parse, and writes the rest as strings.
+ * Fields of a `formula` column must be valid formulas.
+ */
+export type CsvColumnType = "auto" | "string" | "number" | "boolean" | "date" | "formula";
 /**
  *
  * @class Sheet
  * @classdesc A sheet is a collection of cells.
  * @property {string} name - The name of the sheet
  * @property {Array.<Cell|MergedCell>} cells - The cells in the sheet
- * @property {ConditionalFormatSheetValue[]} conditionalFormats - The conditional format values of the sheet
+ * @property {(ConditionalFormatSheetValue|ConditionalFormatGroupSheetValue)[]} conditionalFormats - The conditional format values of the sheet
  * @property {ArrayFormulaSheetValue[]} arrayFormulas - The array formulas of the sheet
  * @property {TableSheetValue[]} tables - The tables of the sheet
  * @property {RowCellConfig[]} rowConfigs - The rows of the sheet
  * @property {RowCellConfig[]} columnConfigs - The columns of the sheet
+ * @property {ColumnBlockSheetValue[]} columnBlocks - The column blocks of the sheet
+ * @property {RangeFormatSheetValue[]} rangeFormats - The range formats of the sheet
+ * @property {FillFormulaSheetValue[]} fillFormulas - The fill formulas of the sheet
+ * @property {CsvImportSheetValue[]} csvImports - The CSV imports of the sheet
+ * @property {boolean} composeFormats - Whether cell formats are layered over the row, column and table column formats
+ * @property {DuplicateCellPolicy} duplicateCellPolicy - What to do when the same cell is written more than once
  */
 export class Sheet {
     /**
@@ -81,6 +103,88 @@ export class Sheet {
      * @default []
      * */
     tables: TableSheetValue[];
+    /**
+     * The column blocks of the sheet
+     * @type {ColumnBlockSheetValue[]}
+     * @default []
+     * */
+    columnBlocks: ColumnBlockSheetValue[];
+    /**
+     * The range formats of the sheet
+     * @type {RangeFormatSheetValue[]}
+     * @default []
+     * */
+    rangeFormats: RangeFormatSheetValue[];
+    /**
+     * The fill formulas of the sheet
+     * @type {FillFormulaSheetValue[]}
+     * @default []
+     * */
+    fillFormulas: FillFormulaSheetValue[];
+    /**
+     * The CSV imports of the sheet
+     * @type {CsvImportSheetValue[]}
+     * @default []
+     * */
+    csvImports: CsvImportSheetValue[];
+    /**
+     * Whether cell formats are layered over the row, column and table column formats
+     * @type {boolean}
+     * @default false
+     * */
+    composeFormats: boolean;
+    /**
+     * What to do when the same cell is written more than once
+     * @type {DuplicateCellPolicy}
+     * @default 'lastWins'
+     * */
+    duplicateCellPolicy: DuplicateCellPolicy;
+    /**
+     * Layers each cell's format over the formats of its column, row and table column.
+     * Properties set on the later layers win, in this order: column, row, table column, cell.
+     * A bold cell in a currency column is then written bold and as currency.
+     * @param {boolean} [compose=true] - Whether to compose the formats
+     * @returns {void}
+     */
+    setComposeFormats(compose?: boolean): void;
+    /**
+     * Sets what to do when the same cell is written more than once.
+     * By default the last write is kept. With `error` saving throws an error naming the cell instead.
+     * With `mergeFormat` a module can write the values and another one the formats of the same cells.
+     * @param {DuplicateCellPolicy} policy - The policy for duplicate writes
+     * @returns {void}
+     */
+    setDuplicateCellPolicy(policy: DuplicateCellPolicy): void;
+    /**
+     * Formats a rectangular range without writing a cell object per cell.
+     * The range format is layered under the format of each cell written inside it,
+     * and later ranges win over earlier ones. Empty cells are written as formatted blanks.
+     * `outerBorder` draws a box around the range, only on the outer edges of the edge and corner cells.
+     * Whole row and column ranges ("C:C", "3:3") only format the cells written inside them.
+     * @param {Object} opts - The options for the range format
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstCol] - The first column of the range
+     * @param {number} [opts.lastCol] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("B2:F40"), instead of the indexes
+     * @param {FormatValue} [opts.format] - The format of every cell in the range
+     * @param {Border|{style: import('./border').BorderType, color?: import('./color').ColorValue}} [opts.outerBorder] - The border drawn around the range
+     * @returns {void}
+     * @throws {Error} - Invalid range
+     * @throws {Error} - Neither a format nor an outer border is given
+     */
+    formatRange(opts: {
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
+        format?: FormatValue;
+        outerBorder?: Border | {
+            style: import("./border").BorderType;
+            color?: import("./color").ColorValue;
+        };
+    }): void;
     /**
      * Adds a row configuration to the sheet.
      * Rows are the first ones to be processed,so if any value overlaps with the columns it will be overwritten
@@ -97,78 +201,246 @@ export class Sheet {
     /**
      * Adds a conditional format to the sheet
      * @param {Object} opts - The options for the conditional format
-     * @param {number} opts.firstRow - The first row of the range
-     * @param {number} opts.lastRow - The last row of the range
-     * @param {number} opts.firstColumn - The first column of the range
-     * @param {number} opts.lastColumn - The last column of the range
-     * @param {ConditionalFormat} opts.format - The format of the range
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstColumn] - The first column of the range
+     * @param {number} [opts.lastColumn] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("B2:B40"), instead of the indexes
+     * @param {string} [opts.table] - The name of a table of this sheet, to format the data of one of its columns
+     * instead of a range. The cells are found when the workbook is written, so they follow the table as it grows.
+     * @param {string} [opts.column] - The header of the table column to format
+     * @param {boolean} [opts.includeTotalRow=false] - Whether the total row of the table is formatted too
+     * @param {ConditionalFormat|number} opts.format - The format of the range, or the id of a conditional format
+     * added to the workbook
+     * @param {number} [opts.priority] - The order the format is evaluated in, lowest first. Formats with a priority
+     * come before the ones without, which keep the order they were added in. Two formats can't share a priority.
      * @returns {void}
+     * @throws {Error} - `table` is set without `column`
      */
     addConditionalFormat(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstColumn: number;
-        lastColumn: number;
-        format: ConditionalFormat;
+        firstRow?: number;
+        lastRow?: number;
+        firstColumn?: number;
+        lastColumn?: number;
+        range?: string;
+        table?: string;
+        column?: string;
+        includeTotalRow?: boolean;
+        format: ConditionalFormat | number;
+        priority?: number;
+    }): void;
+    /**
+     * Adds an ordered list of conditional formats over the same range, evaluated one after the other.
+     * The group takes one place in the priority order of the sheet.
+     * @param {Object} opts - The options for the group
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstColumn] - The first column of the range
+     * @param {number} [opts.lastColumn] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("B2:B40"), instead of the indexes
+     * @param {string} [opts.multiRange] - Several ranges separated by spaces ("B3:D6 I3:K6"), instead of the range
+     * @param {(ConditionalFormat|number)[]} opts.rules - The rules, in the order they are evaluated,
+     * or the ids of conditional formats added to the workbook
+     * @param {boolean} [opts.stopIfTrue=false] - Whether the first rule that is true stops the ones after it,
+     * for the rules that don't set `stopIfTrue` themselves
+     * @param {number} [opts.priority] - The order the group is evaluated in, see `addConditionalFormat`
+     * @returns {void}
+     * @throws {Error} - The group has no rules
+     */
+    addConditionalFormatGroup(opts: {
+        firstRow?: number;
+        lastRow?: number;
+        firstColumn?: number;
+        lastColumn?: number;
+        range?: string;
+        multiRange?: string;
+        rules: (ConditionalFormat | number)[];
+        stopIfTrue?: boolean;
+        priority?: number;
     }): void;
     /**
      * @param {Object} opts - The options for the array formula
-     * @param {number} opts.firstRow - The first row of the range
-     * @param {number} opts.lastRow - The last row of the range
-     * @param {number} opts.firstColumn - The first column of the range
-     * @param {number} opts.lastColumn - The last column of the range
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstColumn] - The first column of the range
+     * @param {number} [opts.lastColumn] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("B2:B10"), instead of the indexes
      * @param {Formula} opts.formula - The formula of the range
-     * @param {Format} [opts.format] - The format of the range
+     * @param {FormatValue} [opts.format] - The format of the range
      */
     addArrayFormula(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstColumn: number;
-        lastColumn: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstColumn?: number;
+        lastColumn?: number;
+        range?: string;
         formula: Formula;
-        format?: Format;
+        format?: FormatValue;
+    }): void;
+    /**
+     * Fills a formula over a range, the way Excel's fill handle does.
+     * The formula is written as it would be in the anchor cell, the first cell of the range by default.
+     * Every other cell gets it with the relative references moved by its distance from the anchor,
+     * while the `$` anchored columns and rows stay fixed. References moved off the sheet become `#REF!`.
+     * @param {Object} opts - The options for the fill formula
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstCol] - The first column of the range
+     * @param {number} [opts.lastCol] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("D2:D100"), instead of the indexes
+     * @param {Formula|string} opts.formula - The formula as written in the anchor cell
+     * @param {string} [opts.anchor] - The cell the formula is written for, in A1 notation
+     * @param {number} [opts.anchorRow] - The row the formula is written for
+     * @param {number} [opts.anchorCol] - The column the formula is written for
+     * @param {FormatValue} [opts.format] - The format of every cell in the range
+     * @returns {void}
+     * @throws {Error} - Invalid range
+     */
+    fillFormula(opts: {
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
+        formula: Formula | string;
+        anchor?: string;
+        anchorRow?: number;
+        anchorCol?: number;
+        format?: FormatValue;
     }): void;
     /**
      * Adds a table to the sheet
      * @param {Object} opts - The options for the table
-     * @param {number} opts.firstRow - The first row of the range
-     * @param {number} opts.lastRow - The last row of the range
-     * @param {number} opts.firstColumn - The first column of the range
-     * @param {number} opts.lastColumn - The last column of the range
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstColumn] - The first column of the range
+     * @param {number} [opts.lastColumn] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("A1:D20"), instead of the indexes
      * @param {Table} opts.table - The table of the range
      * @throws {Error} - Invalid table range
      * @returns {void}
      */
     addTable(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstColumn: number;
-        lastColumn: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstColumn?: number;
+        lastColumn?: number;
+        range?: string;
         table: Table;
     }): void;
     /**
-     * Writes a cell to the sheet
+     * Adds a table filled from an array of records.
+     * The headers come from the table columns (`key`, or `header` when no key is set),
+     * or from the record keys when the table has no columns.
+     * The last row and column are computed from the data, including the total row.
+     * Values of columns without a `cellType` are written by their type: numbers, strings,
+     * booleans and Dates. Objects and arrays need a column `cellType`.
+     * @param {Object} opts - The options for the table
+     * @param {number} [opts.firstRow] - The first row of the table (the header row)
+     * @param {number} [opts.firstColumn] - The first column of the table
+     * @param {string} [opts.range] - The top left cell of the table in A1 notation ("B2"), instead of the indexes
+     * @param {Object[]} opts.data - The records written as the table rows
+     * @param {Table} [opts.table] - The table definition
+     * @throws {Error} - Invalid table position
+     * @returns {void}
+     */
+    addTableFromData(opts: {
+        firstRow?: number;
+        firstColumn?: number;
+        range?: string;
+        data: any[];
+        table?: Table;
+    }): void;
+    /**
+     * Writes a block of columns to the sheet, starting at the given cell.
+     * Float64Array and Int32Array columns are read directly from their buffers,
+     * which is much faster than writing one cell at a time. The values of an
+     * array column must all be numbers, strings or Dates, and numbers must be finite.
+     * @param {Object} opts - The options for the column block
+     * @param {number} [opts.row] - The first row of the block
+     * @param {number} [opts.col] - The first column of the block
+     * @param {string} [opts.cell] - The top left cell of the block in A1 notation ("B2"), in place of `row` and `col`
+     * @param {ColumnValues[]} opts.columns - The values of each column
+     * @param {Array.<FormatValue|undefined>} [opts.formats] - The format of each column
+     * @returns {void}
+     * @throws {Error} - col > 16_383 or col < 0
+     * @throws {Error} - row > 1_048_575 or row < 0
+     */
+    writeColumns(opts: {
+        row?: number;
+        col?: number;
+        cell?: string;
+        columns: ColumnValues[];
+        formats?: Array<FormatValue | undefined>;
+    }): void;
+    /**
+     * Writes delimited text, like a CSV or TSV file, into the sheet from the given cell on.
+     * The text is read and parsed when the workbook is saved, and written one record per row.
+     * Empty fields are left blank, and fields that don't match their column type are written as strings.
+     * @param {Object} opts - The options for the import
+     * @param {number} [opts.row] - The first row, 0 by default
+     * @param {number} [opts.col] - The first column, 0 by default
+     * @param {string} [opts.cell] - The top left cell in A1 notation ("B2"), in place of `row` and `col`
+     * @param {Buffer|string} opts.source - The text as a Buffer, or the path of its file.
+     * A string is always a path: pass text held in a string as `Buffer.from(text)`
+     * @param {string} [opts.delimiter] - The field delimiter, `,` by default and `\t` for TSV
+     * @param {string} [opts.quote] - The quote of fields, `"` by default, empty to read quotes as text
+     * @param {'utf8'|'utf16le'|'utf16be'|'latin1'} [opts.encoding] - The encoding of the text, `utf8` by default
+     * @param {boolean} [opts.header] - Whether the first record is a header row, true by default
+     * @param {boolean} [opts.detectTypes] - Whether the types of columns without a hint are detected, true by default
+     * @param {CsvColumnType[]|Record<string, CsvColumnType>} [opts.types] - The type of each column, in their order or by header
+     * @param {Array.<FormatValue|undefined>|Record<string, FormatValue>} [opts.formats] - The format of each column, in their order or by header
+     * @param {FormatValue} [opts.headerFormat] - The format of the header row
+     * @param {FormatValue} [opts.dateFormat] - The format of dates without a time
+     * @param {FormatValue} [opts.dateTimeFormat] - The format of dates with a time
+     * @param {Table} [opts.table] - The table created over the imported range
+     * @returns {void}
+     * @throws {Error} - col > 16_383 or col < 0
+     * @throws {Error} - row > 1_048_575 or row < 0
+     */
+    importCsv(opts: {
+        row?: number;
+        col?: number;
+        cell?: string;
+        source: Buffer | string;
+        delimiter?: string;
+        quote?: string;
+        encoding?: "utf8" | "utf16le" | "utf16be" | "latin1";
+        header?: boolean;
+        detectTypes?: boolean;
+        types?: CsvColumnType[] | Record<string, CsvColumnType>;
+        formats?: Array<FormatValue | undefined> | Record<string, FormatValue>;
+        headerFormat?: FormatValue;
+        dateFormat?: FormatValue;
+        dateTimeFormat?: FormatValue;
+        table?: Table;
+    }): void;
+    /**
+     * Writes a cell to the sheet.
+     * The cell can also be given in A1 notation, followed by the remaining arguments:
+     * `writeCell('B2', value, cellType, format)`
      *
-     * @param {number} row - the cell row
-     * @param {number} col - the cell col
+     * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+     * @param {number|any} col - the cell col
      * @param {string|number|Link|Date|Formula|any} value - The value of the cell.
-     * @param {("number"|"string"|"link"|"date"|"formula")} [cellType] - The type of the cell(if not provider .toString() will be used)
-     * @param {Format} [format] - The format of the cell
+     * @param {("number"|"string"|"boolean"|"link"|"date"|"formula")} [cellType] - The type of the cell(if not provider .toString() will be used)
+     * @param {FormatValue} [format] - The format of the cell
      * @returns {void}
      * @throws {Error} - col > 65_535 or col < 0
      * @throws {Error} - row > 1_048_577 or row < 0
      */
-    writeCell(row: number, col: number, value: string | number | Link | Date | Formula | any, cellType?: ("number" | "string" | "link" | "date" | "formula"), format?: Format): void;
+    writeCell(row: number | string, col: number | any, value: string | number | Link | Date | Formula | any, cellType?: ("number" | "string" | "boolean" | "link" | "date" | "formula"), format?: FormatValue): void;
     /**
      * Writes a merged cell to the sheet
      * @param {Object} opts - The options for the merged cell
-     * @param {number} opts.firstRow - The first row of the merged cell
-     * @param {number} opts.lastRow - The last row of the merged cell
-     * @param {number} opts.firstCol - The first column of the merged cell
-     * @param {number} opts.lastCol - The last column of the merged cell
+     * @param {number} [opts.firstRow] - The first row of the merged cell
+     * @param {number} [opts.lastRow] - The last row of the merged cell
+     * @param {number} [opts.firstCol] - The first column of the merged cell
+     * @param {number} [opts.lastCol] - The last column of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {string|number|Link|Date|Formula|any} opts.value - The value of the merged cell.
-     * @param {Format} opts.format - The format of the merged cell
-     * @param {("number"|"string"|"link"|"date"|"formula")} [opts.cellType] - The type of the merged cell(if not provider .toString() will be used)
+     * @param {FormatValue} opts.format - The format of the merged cell
+     * @param {("number"|"string"|"boolean"|"link"|"date"|"formula")} [opts.cellType] - The type of the merged cell(if not provider .toString() will be used)
      * @returns {void}
      * @throws {Error} - firstCol > 65_535 or firstCol < 0
      * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -179,34 +451,37 @@ export class Sheet {
      * @throws {Error} - firstCol === lastCol && firstRow === lastRow
      */
     writeMergedCell(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstCol: number;
-        lastCol: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
         value: string | number | Link | Date | Formula | any;
-        format: Format;
-        cellType?: ("number" | "string" | "link" | "date" | "formula");
+        format: FormatValue;
+        cellType?: ("number" | "string" | "boolean" | "link" | "date" | "formula");
     }): void;
     /**
      * writes a string value to a cell
-     * @param {number} row - the cell row
-     * @param {number} col - the cell col
+     * The cell can also be given in A1 notation, followed by the value and format: `writeString('B2', value, format)`
+     * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+     * @param {number|any} col - the cell col
      * @param {string} value - The value to write to the cell
-     * @param {Format} [format] - The format of the cell
+     * @param {FormatValue} [format] - The format of the cell
      * @returns {void}
      * @throws {Error} - col > 65_535 or col < 0
      * @throws {Error} - row > 1_048_577 or row < 0
      */
-    writeString(row: number, col: number, value: string, format?: Format): void;
+    writeString(row: number | string, col: number | any, value: string, format?: FormatValue): void;
     /**
      * writes a string value to a merged cell
      * @param {Object} opts - The options for the merged cell
-     * @param {number} opts.firstRow - The first row of the merged cell
-     * @param {number} opts.lastRow - The last row of the merged cell
-     * @param {number} opts.firstCol - The first column of the merged cell
-     * @param {number} opts.lastCol - The last column of the merged cell
+     * @param {number} [opts.firstRow] - The first row of the merged cell
+     * @param {number} [opts.lastRow] - The last row of the merged cell
+     * @param {number} [opts.firstCol] - The first column of the merged cell
+     * @param {number} [opts.lastCol] - The last column of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {string} opts.value - The value of the merged cell.
-     * @param {Format} opts.format - The format of the merged cell
+     * @param {FormatValue} opts.format - The format of the merged cell
      * @returns {void}
      * @throws {Error} - firstCol > 65_535 or firstCol < 0
      * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -217,33 +492,36 @@ export class Sheet {
      * @throws {Error} - firstCol === lastCol && firstRow === lastRow
      */
     writeMergedString(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstCol: number;
-        lastCol: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
         value: string;
-        format: Format;
+        format: FormatValue;
     }): void;
     /**
      * writes a number value to a cell
-     * @param {number} row - the cell row
-     * @param {number} col - the cell col
+     * The cell can also be given in A1 notation, followed by the value and format: `writeNumber('B2', value, format)`
+     * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+     * @param {number|any} col - the cell col
      * @param {number} value - The value to write to the cell
-     * @param {Format} [format] - The format of the cell
+     * @param {FormatValue} [format] - The format of the cell
      * @returns {void}
      * @throws {Error} - col > 65_535 or col < 0
      * @throws {Error} - row > 1_048_577 or row < 0
      */
-    writeNumber(row: number, col: number, value: number, format?: Format): void;
+    writeNumber(row: number | string, col: number | any, value: number, format?: FormatValue): void;
     /**
      * writes a number value to a merged cell
      * @param {Object} opts - The options for the merged cell
-     * @param {number} opts.firstRow - The first row of the merged cell
-     * @param {number} opts.lastRow - The last row of the merged cell
-     * @param {number} opts.firstCol - The first column of the merged cell
-     * @param {number} opts.lastCol - The last column of the merged cell
+     * @param {number} [opts.firstRow] - The first row of the merged cell
+     * @param {number} [opts.lastRow] - The last row of the merged cell
+     * @param {number} [opts.firstCol] - The first column of the merged cell
+     * @param {number} [opts.lastCol] - The last column of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {number} opts.value - The value of the merged cell.
-     * @param {Format} opts.format - The format of the merged cell
+     * @param {FormatValue} opts.format - The format of the merged cell
      * @returns {void}
      * @throws {Error} - firstCol > 65_535 or firstCol < 0
      * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -254,33 +532,36 @@ export class Sheet {
      * @throws {Error} - firstCol === lastCol && firstRow === lastRow
      */
     writeMergedNumber(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstCol: number;
-        lastCol: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
         value: number;
-        format: Format;
+        format: FormatValue;
     }): void;
     /**
      * writes a link value to a cell
-     * @param {number} row - the cell row
-     * @param {number} col - the cell col
+     * The cell can also be given in A1 notation, followed by the value and format: `writeLink('B2', value, format)`
+     * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+     * @param {number|any} col - the cell col
      * @param {Link} value - The value to write to the cell
-     * @param {Format} [format] - The format of the cell
+     * @param {FormatValue} [format] - The format of the cell
      * @returns {void}
      * @throws {Error} - col > 65_535 or col < 0
      * @throws {Error} - row > 1_048_577 or row < 0
      */
-    writeLink(row: number, col: number, value: Link, format?: Format): void;
+    writeLink(row: number | string, col: number | any, value: Link, format?: FormatValue): void;
     /**
      * writes a link value to a merged cell
      * @param {Object} opts - The options for the merged cell
-     * @param {number} opts.firstRow - The first row of the merged cell
-     * @param {number} opts.lastRow - The last row of the merged cell
-     * @param {number} opts.firstCol - The first column of the merged cell
-     * @param {number} opts.lastCol - The last column of the merged cell
+     * @param {number} [opts.firstRow] - The first row of the merged cell
+     * @param {number} [opts.lastRow] - The last row of the merged cell
+     * @param {number} [opts.firstCol] - The first column of the merged cell
+     * @param {number} [opts.lastCol] - The last column of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {Link} opts.value - The value of the merged cell.
-     * @param {Format} opts.format - The format of the merged cell
+     * @param {FormatValue} opts.format - The format of the merged cell
      * @returns {void}
      * @throws {Error} - firstCol > 65_535 or firstCol < 0
      * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -291,33 +572,36 @@ export class Sheet {
      * @throws {Error} - firstCol === lastCol && firstRow === lastRow
      */
     writeMergedLink(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstCol: number;
-        lastCol: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
         value: Link;
-        format: Format;
+        format: FormatValue;
     }): void;
     /**
      * writes a date value to a cell
-     * @param {number} row - the cell row
-     * @param {number} col - the cell col
+     * The cell can also be given in A1 notation, followed by the value and format: `writeDate('B2', value, format)`
+     * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+     * @param {number|any} col - the cell col
      * @param {Date} value - The value to write to the cell
-     * @param {Format} [format] - The format of the cell
+     * @param {FormatValue} [format] - The format of the cell
      * @returns {void}
      * @throws {Error} - col > 65_535 or col < 0
      * @throws {Error} - row > 1_048_577 or row < 0
      */
-    writeDate(row: number, col: number, value: Date, format?: Format): void;
+    writeDate(row: number | string, col: number | any, value: Date, format?: FormatValue): void;
     /**
      * writes a date value to a merged cell
      * @param {Object} opts - The options for the merged cell
-     * @param {number} opts.firstRow - The first row of the merged cell
-     * @param {number} opts.lastRow - The last row of the merged cell
-     * @param {number} opts.firstCol - The first column of the merged cell
-     * @param {number} opts.lastCol - The last column of the merged cell
+     * @param {number} [opts.firstRow] - The first row of the merged cell
+     * @param {number} [opts.lastRow] - The last row of the merged cell
+     * @param {number} [opts.firstCol] - The first column of the merged cell
+     * @param {number} [opts.lastCol] - The last column of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {Date} opts.value - The value of the merged cell.
-     * @param {Format} opts.format - The format of the merged cell
+     * @param {FormatValue} opts.format - The format of the merged cell
      * @returns {void}
      * @throws {Error} - firstCol > 65_535 or firstCol < 0
      * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -328,31 +612,34 @@ export class Sheet {
      * @throws {Error} - firstCol === lastCol && firstRow === lastRow
      */
     writeMergedDate(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstCol: number;
-        lastCol: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
         value: Date;
-        format: Format;
+        format: FormatValue;
     }): void;
     /**
      * writes a formula value to a cell
-     * @param {number} row - the cell row
-     * @param {number} col - the cell col
+     * The cell can also be given in A1 notation, followed by the value and format: `writeFormula('B2', value, format)`
+     * @param {number|string} row - the cell row, or the cell in A1 notation ("B2")
+     * @param {number|any} col - the cell col
      * @param {Formula} value - The value to write to the cell
-     * @param {Format} [format] - The format of the cell
+     * @param {FormatValue} [format] - The format of the cell
      * @returns {void}
      */
-    writeFormula(row: number, col: number, value: Formula, format?: Format): void;
+    writeFormula(row: number | string, col: number | any, value: Formula, format?: FormatValue): void;
     /**
      * writes a formula value to a merged cell
      * @param {Object} opts - The options for the merged cell
-     * @param {number} opts.firstRow - The first row of the merged cell
-     * @param {number} opts.lastRow - The last row of the merged cell
-     * @param {number} opts.firstCol - The first column of the merged cell
-     * @param {number} opts.lastCol - The last column of the merged cell
+     * @param {number} [opts.firstRow] - The first row of the merged cell
+     * @param {number} [opts.lastRow] - The last row of the merged cell
+     * @param {number} [opts.firstCol] - The first column of the merged cell
+     * @param {number} [opts.lastCol] - The last column of the merged cell
+     * @param {string} [opts.range] - The merged range in A1 notation ("B2:D4"), instead of the indexes
      * @param {Formula} opts.value - The value of the merged cell.
-     * @param {Format} opts.format - The format of the merged cell
+     * @param {FormatValue} opts.format - The format of the merged cell
      * @returns {void}
      * @throws {Error} - firstCol > 65_535 or firstCol < 0
      * @throws {Error} - lastCol > 65_535 or lastCol < 0
@@ -363,18 +650,19 @@ export class Sheet {
      * @throws {Error} - firstCol === lastCol && firstRow === lastRow
      */
     writeMergedFormula(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstCol: number;
-        lastCol: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
         value: Formula;
-        format: Format;
+        format: FormatValue;
     }): void;
     /**
      *
      * @typedef {Object} FormatOptions
-     * @property {Format} [headerFormat] - The format of the header cells
-     * @property {Format} [cellFormat] - The format of the data cells
+     * @property {FormatValue} [headerFormat] - The format of the header cells
+     * @property {FormatValue} [cellFormat] - The format of the data cells
      * @property {Object.<string, Format>} [columnFormats] - The format of the cells in the columns
      * Writes the sheet based on the provided array of objects
      * For performance reasons the headers will be generated based on the first object
@@ -388,11 +676,11 @@ export class Sheet {
         /**
          * - The format of the header cells
          */
-        headerFormat?: Format;
+        headerFormat?: FormatValue;
         /**
          * - The format of the data cells
          */
-        cellFormat?: Format;
+        cellFormat?: FormatValue;
         /**
          * - The format of the cells in the columns
          * Writes the sheet based on the provided array of objects
@@ -413,46 +701,49 @@ export class Sheet {
  * @property {number} firstColumn - The first column of the range
  * @property {number} lastColumn - The last column of the range
  * @property {Formula} formula - The formula of the range
- * @property {Format} [format] - The format of the range
+ * @property {FormatValue} [format] - The format of the range
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
  */
 export class ArrayFormulaSheetValue {
     /**
      * @param {Object} opts - The options for the array formula
-     * @param {number} opts.firstRow - The first row of the range
-     * @param {number} opts.lastRow - The last row of the range
-     * @param {number} opts.firstColumn - The first column of the range
-     * @param {number} opts.lastColumn - The last column of the range
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstColumn] - The first column of the range
+     * @param {number} [opts.lastColumn] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("B2:B10"), instead of the indexes
      * @param {Formula} opts.formula - The formula of the range
-     * @param {Format} [opts.format] - The format of the range
+     * @param {FormatValue} [opts.format] - The format of the range
      */
     constructor(opts: {
-        firstRow: number;
-        lastRow: number;
-        firstColumn: number;
-        lastColumn: number;
+        firstRow?: number;
+        lastRow?: number;
+        firstColumn?: number;
+        lastColumn?: number;
+        range?: string;
         formula: Formula;
-        format?: Format;
+        format?: FormatValue;
     });
     /**
      * The first row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstRow: number;
+    firstRow: number | undefined;
     /**
      * The last row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastRow: number;
+    lastRow: number | undefined;
     /**
      * The first column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstColumn: number;
+    firstColumn: number | undefined;
     /**
      * The last column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastColumn: number;
+    lastColumn: number | undefined;
     /**
      * The formula of the range
      * @type {Formula}
@@ -460,13 +751,238 @@ export class ArrayFormulaSheetValue {
     formula: Formula;
     /**
      * The format of the range
-     * @type {Format|undefined}
+     * @type {FormatValue|undefined}
+     */
+    format: FormatValue | undefined;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    range: string | undefined;
+}
+/**
+ * @class ConditionalFormatGroupSheetValue
+ * @classdesc Represents an ordered list of conditional formats over the same cells.
+ * @property {number} firstRow - The first row of the range
+ * @property {number} lastRow - The last row of the range
+ * @property {number} firstColumn - The first column of the range
+ * @property {number} lastColumn - The last column of the range
+ * @property {(ConditionalFormat|number)[]} rules - The rules, in the order they are evaluated
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ * @property {number} [priority] - The order the group is evaluated in, lowest first
+ */
+export class ConditionalFormatGroupSheetValue {
+    /**
+     * @param {Object} opts - The options for the group
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstColumn] - The first column of the range
+     * @param {number} [opts.lastColumn] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation, instead of the indexes
+     * @param {string} [opts.multiRange] - Several ranges separated by spaces ("B3:D6 I3:K6"), instead of the range
+     * @param {(ConditionalFormat|number)[]} opts.rules - The rules, in the order they are evaluated,
+     * or the ids of conditional formats added to the workbook
+     * @param {boolean} [opts.stopIfTrue=false] - Whether the first rule that is true stops the ones after it,
+     * for the rules that don't set `stopIfTrue` themselves
+     * @param {number} [opts.priority] - The order the group is evaluated in, lowest first
+     * @throws {Error} - The group has no rules
      */
-    format: Format | undefined;
+    constructor(opts: {
+        firstRow?: number;
+        lastRow?: number;
+        firstColumn?: number;
+        lastColumn?: number;
+        range?: string;
+        multiRange?: string;
+        rules: (ConditionalFormat | number)[];
+        stopIfTrue?: boolean;
+        priority?: number;
+    });
+    /**
+     * The first row of the range
+     * @type {number|undefined}
+     */
+    firstRow: number | undefined;
+    /**
+     * The last row of the range
+     * @type {number|undefined}
+     */
+    lastRow: number | undefined;
+    /**
+     * The first column of the range
+     * @type {number|undefined}
+     */
+    firstColumn: number | undefined;
+    /**
+     * The last column of the range
+     * @type {number|undefined}
+     */
+    lastColumn: number | undefined;
+    /**
+     * The range in A1 notation, the first of the multi range when only that is given
+     * @type {string|undefined}
+     */
+    range: string | undefined;
+    /**
+     * The rules, in the order they are evaluated. The rules the group settings
+     * change are copies, so the formats it was given stay as they are.
+     * @type {(ConditionalFormat|number)[]}
+     */
+    rules: (ConditionalFormat | number)[];
+    /**
+     * The order the group is evaluated in, lowest first
+     * @type {number|undefined}
+     */
+    priority: number | undefined;
+}
+/**
+ * @class CsvImportSheetValue
+ * @classdesc Represents delimited text written into the sheet from a cell on, one record per row.
+ * @property {number|undefined} row - The first row
+ * @property {number|undefined} col - The first column
+ * @property {string|undefined} cell - The top left cell in A1 notation, in place of `row` and `col`
+ * @property {Buffer|string} source - The text as a Buffer, or the path of its file
+ * @property {string} delimiter - The field delimiter
+ * @property {string} quote - The quote of fields, empty to read quotes as text
+ * @property {'utf8'|'utf16le'|'utf16be'|'latin1'} encoding - The encoding of the text
+ * @property {boolean} header - Whether the first record is a header row
+ * @property {boolean} detectTypes - Whether the types of columns without a hint are detected
+ * @property {CsvColumnType[]|Record<string, CsvColumnType>|undefined} types - The type of each column
+ * @property {Array.<FormatValue|undefined>|Record<string, FormatValue>|undefined} formats - The format of each column
+ * @property {FormatValue|undefined} headerFormat - The format of the header row
+ * @property {FormatValue} dateFormat - The format of dates without a time or a column format
+ * @property {FormatValue} dateTimeFormat - The format of dates with a time and without a column format
+ * @property {Table|undefined} table - The table created over the imported range
+ * @property {number|undefined} cellIndex - How many cells of the sheet were written before the import
+ */
+export class CsvImportSheetValue {
+    /**
+     * @param {Object} opts - The options for the import
+     * @param {number} [opts.row] - The first row, 0 by default
+     * @param {number} [opts.col] - The first column, 0 by default
+     * @param {string} [opts.cell] - The top left cell in A1 notation ("B2"), in place of `row` and `col`
+     * @param {Buffer|string} opts.source - The text as a Buffer, or the path of its file.
+     * A string is always a path: pass text held in a string as `Buffer.from(text)`
+     * @param {string} [opts.delimiter] - The field delimiter, `,` by default and `\t` for TSV
+     * @param {string} [opts.quote] - The quote of fields, `"` by default, empty to read quotes as text
+     * @param {'utf8'|'utf16le'|'utf16be'|'latin1'} [opts.encoding] - The encoding of the text, `utf8` by default.
+     * A byte order mark wins over it
+     * @param {boolean} [opts.header] - Whether the first record is a header row, true by default
+     * @param {boolean} [opts.detectTypes] - Whether the types of columns without a hint are detected,
+     * true by default. Without it they are written as strings
+     * @param {CsvColumnType[]|Record<string, CsvColumnType>} [opts.types] - The type of each column,
+     * in their order or by header
+     * @param {Array.<FormatValue|undefined>|Record<string, FormatValue>} [opts.formats] - The format of
+     * each column, in their order or by header
+     * @param {FormatValue} [opts.headerFormat] - The format of the header row
+     * @param {FormatValue} [opts.dateFormat] - The format of dates without a time, `yyyy-mm-dd` by default
+     * @param {FormatValue} [opts.dateTimeFormat] - The format of dates with a time, `yyyy-mm-dd hh:mm:ss` by default
+     * @param {Table} [opts.table] - The table created over the imported range. Without columns, it takes
+     * the headers of the text
+     */
+    constructor(opts: {
+        row?: number;
+        col?: number;
+        cell?: string;
+        source: Buffer | string;
+        delimiter?: string;
+        quote?: string;
+        encoding?: "utf8" | "utf16le" | "utf16be" | "latin1";
+        header?: boolean;
+        detectTypes?: boolean;
+        types?: CsvColumnType[] | Record<string, CsvColumnType>;
+        formats?: Array<FormatValue | undefined> | Record<string, FormatValue>;
+        headerFormat?: FormatValue;
+        dateFormat?: FormatValue;
+        dateTimeFormat?: FormatValue;
+        table?: Table;
+    });
+    /**
+     * The first row
+     * @type {number|undefined}
+     */
+    row: number | undefined;
+    /**
+     * The first column
+     * @type {number|undefined}
+     */
+    col: number | undefined;
+    /**
+     * The top left cell in A1 notation
+     * @type {string|undefined}
+     */
+    cell: string | undefined;
+    /**
+     * The text as a Buffer, or the path of its file
+     * @type {Buffer|string}
+     */
+    source: Buffer | string;
+    /**
+     * The field delimiter
+     * @type {string}
+     */
+    delimiter: string;
+    /**
+     * The quote of fields, empty to read quotes as text
+     * @type {string}
+     */
+    quote: string;
+    /**
+     * The encoding of the text
+     * @type {'utf8'|'utf16le'|'utf16be'|'latin1'}
+     */
+    encoding: "utf8" | "utf16le" | "utf16be" | "latin1";
+    /**
+     * Whether the first record is a header row
+     * @type {boolean}
+     */
+    header: boolean;
+    /**
+     * Whether the types of columns without a hint are detected
+     * @type {boolean}
+     */
+    detectTypes: boolean;
+    /**
+     * The type of each column
+     * @type {CsvColumnType[]|Record<string, CsvColumnType>|undefined}
+     */
+    types: CsvColumnType[] | Record<string, CsvColumnType> | undefined;
+    /**
+     * The format of each column
+     * @type {Array.<FormatValue|undefined>|Record<string, FormatValue>|undefined}
+     */
+    formats: Array<FormatValue | undefined> | Record<string, FormatValue> | undefined;
+    /**
+     * The format of the header row
+     * @type {FormatValue|undefined}
+     */
+    headerFormat: FormatValue | undefined;
+    /**
+     * The format of dates without a time or a column format
+     * @type {FormatValue}
+     */
+    dateFormat: FormatValue;
+    /**
+     * The format of dates with a time and without a column format
+     * @type {FormatValue}
+     */
+    dateTimeFormat: FormatValue;
+    /**
+     * The table created over the imported range
+     * @type {Table|undefined}
+     */
+    table: Table | undefined;
+    /**
+     * How many cells of the sheet were written before the import, which places
+     * the imported cells among them for the `duplicateCellPolicy`
+     * @type {number|undefined}
+     */
+    cellIndex: number | undefined;
 }
 import Format = require("./format");
 import Cell = require("./cell");
 import MergedCell = require("./merged_cell");
+/** @typedef {import('./format').FormatValue} FormatValue */
 /**
  * @typedef {Object} SizeConfig
  * @property {number} value - The value of the size
@@ -475,7 +991,7 @@ import MergedCell = require("./merged_cell");
 /**
  * @typedef {Object} RowCellConfig
  * @property {number} index - The index of the row/column, 0-based
- * @property {Format} [format] - The format of the cell (will be overwritten by the cell format)
+ * @property {FormatValue} [format] - The format of the cell (will be overwritten by the cell format)
  * @property {SizeConfig} [size] - The height/width of the row/column
  * @property {boolean} [hidden] - Whether the row is hidden
  */
@@ -486,91 +1002,369 @@ import MergedCell = require("./merged_cell");
  * @property {number} lastRow - The last row of the range
  * @property {number} firstColumn - The first column of the range
  * @property {number} lastColumn - The last column of the range
- * @property {ConditionalFormat} format - The format of the range
+ * @property {ConditionalFormat|number} format - The format of the range, or the id of a conditional format added to the workbook
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ * @property {string} [table] - The name of a table of the sheet, to format one of its columns instead of a range
+ * @property {string} [column] - The header of the table column
+ * @property {boolean} [includeTotalRow] - Whether the total row of the table is formatted too
+ * @property {number} [priority] - The order the format is evaluated in, lowest first
  *
  */
 declare class ConditionalFormatSheetValue {
     /**
-     * @param {number} firstRow - The first row of the range
-     * @param {number} lastRow - The last row of the range
-     * @param {number} firstColumn - The first column of the range
-     * @param {number} lastColumn - The last column of the range
-     * @param {ConditionalFormat} format - The format of the range
+     * @param {number|undefined} firstRow - The first row of the range
+     * @param {number|undefined} lastRow - The last row of the range
+     * @param {number|undefined} firstColumn - The first column of the range
+     * @param {number|undefined} lastColumn - The last column of the range
+     * @param {ConditionalFormat|number} format - The format of the range, or the id of a conditional format added to the workbook
+     * @param {string} [range] - The range in A1 notation, instead of the indexes
+     * @param {{table: string, column: string, includeTotalRow?: boolean}} [tableColumn] - A table column, instead of a range
+     * @param {number} [priority] - The order the format is evaluated in, lowest first
      */
-    constructor(firstRow: number, lastRow: number, firstColumn: number, lastColumn: number, format: ConditionalFormat);
+    constructor(firstRow: number | undefined, lastRow: number | undefined, firstColumn: number | undefined, lastColumn: number | undefined, format: ConditionalFormat | number, range?: string, tableColumn?: {
+        table: string;
+        column: string;
+        includeTotalRow?: boolean;
+    }, priority?: number);
     /**
      * The first row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstRow: number;
+    firstRow: number | undefined;
     /**
      * The last row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastRow: number;
+    lastRow: number | undefined;
     /**
      * The first column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstColumn: number;
+    firstColumn: number | undefined;
     /**
      * The last column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastColumn: number;
+    lastColumn: number | undefined;
     /**
-     * The format of the range
-     * @type {ConditionalFormat}
+     * The format of the range, or the id of a conditional format added to the workbook
+     * @type {ConditionalFormat|number}
+     */
+    format: ConditionalFormat | number;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    range: string | undefined;
+    /**
+     * The name of the table whose column is formatted
+     * @type {string|undefined}
      */
-    format: ConditionalFormat;
+    table: string | undefined;
+    /**
+     * The header of the formatted table column
+     * @type {string|undefined}
+     */
+    column: string | undefined;
+    /**
+     * Whether the total row of the table is formatted too
+     * @type {boolean|undefined}
+     */
+    includeTotalRow: boolean | undefined;
+    /**
+     * The order the format is evaluated in, lowest first
+     * @type {number|undefined}
+     */
+    priority: number | undefined;
 }
 /**
  * @class TableSheetValue
  * @classdesc Represents the values of a table sheet.
  * @property {number} firstRow - The first row of the range
- * @property {number} lastRow - The last row of the range
+ * @property {number} [lastRow] - The last row of the range (computed when `data` is set)
  * @property {number} firstColumn - The first column of the range
- * @property {number} lastColumn - The last column of the range
+ * @property {number} [lastColumn] - The last column of the range (computed when `data` is set)
  * @property {Table} table - The table of the range
+ * @property {Object[]} [data] - The records written as the table rows
+ * @property {string} [range] - The range in A1 notation, or its top left cell when `data` is set
  */
 declare class TableSheetValue {
     /**
-     * @param {number} firstRow - The first row of the range
-     * @param {number} lastRow - The last row of the range
-     * @param {number} firstColumn - The first column of the range
-     * @param {number} lastColumn - The last column of the range
+     * @param {number|undefined} firstRow - The first row of the range
+     * @param {number|undefined} lastRow - The last row of the range
+     * @param {number|undefined} firstColumn - The first column of the range
+     * @param {number|undefined} lastColumn - The last column of the range
      * @param {Table} table - The table of the range
+     * @param {Object[]} [data] - The records written as the table rows
+     * @param {string} [range] - The range in A1 notation, or its top left cell when `data` is set
      */
-    constructor(firstRow: number, lastRow: number, firstColumn: number, lastColumn: number, table: Table);
+    constructor(firstRow: number | undefined, lastRow: number | undefined, firstColumn: number | undefined, lastColumn: number | undefined, table: Table, data?: any[], range?: string);
     /**
      * The first row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstRow: number;
+    firstRow: number | undefined;
     /**
      * The last row of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastRow: number;
+    lastRow: number | undefined;
     /**
      * The first column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    firstColumn: number;
+    firstColumn: number | undefined;
     /**
      * The last column of the range
-     * @type {number}
+     * @type {number|undefined}
      */
-    lastColumn: number;
+    lastColumn: number | undefined;
     /**
      * The table of the range
      * @type {Table}
      */
     table: Table;
+    /**
+     * The records written as the table rows
+     * @type {Object[]|undefined}
+     */
+    data: any[] | undefined;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    range: string | undefined;
 }
+/**
+ * What to do when the same cell is written more than once:
+ * - `error`: saving throws an error naming the cell
+ * - `lastWins`: the last write is kept, the default
+ * - `firstWins`: the first write is kept
+ * - `mergeFormat`: the last value is kept, with its format layered over the earlier formats
+ * @typedef {("error"|"lastWins"|"firstWins"|"mergeFormat")} DuplicateCellPolicy
+ */
+/**
+ * @typedef {(Float64Array|Int32Array|number[]|string[]|Date[])} ColumnValues
+ */
+/**
+ * @class ColumnBlockSheetValue
+ * @classdesc Represents a block of columns written from a starting cell.
+ * @property {number} [row] - The first row of the block (unset when given by `cell`)
+ * @property {number} [col] - The first column of the block (unset when given by `cell`)
+ * @property {string} [cell] - The top left cell of the block in A1 notation ("B2"), in place of `row` and `col`
+ * @property {ColumnValues[]} columns - The values of each column
+ * @property {Array.<FormatValue|undefined>} [formats] - The format of each column
+ */
+declare class ColumnBlockSheetValue {
+    /**
+     * @param {Object} opts - The options for the column block
+     * @param {number} [opts.row] - The first row of the block
+     * @param {number} [opts.col] - The first column of the block
+     * @param {string} [opts.cell] - The top left cell of the block in A1 notation ("B2"), in place of `row` and `col`
+     * @param {ColumnValues[]} opts.columns - The values of each column
+     * @param {Array.<FormatValue|undefined>} [opts.formats] - The format of each column
+     */
+    constructor(opts: {
+        row?: number;
+        col?: number;
+        cell?: string;
+        columns: ColumnValues[];
+        formats?: Array<FormatValue | undefined>;
+    });
+    /**
+     * The first row of the block
+     * @type {number|undefined}
+     */
+    row: number | undefined;
+    /**
+     * The first column of the block
+     * @type {number|undefined}
+     */
+    col: number | undefined;
+    /**
+     * The top left cell of the block in A1 notation
+     * @type {string|undefined}
+     */
+    cell: string | undefined;
+    /**
+     * The values of each column
+     * @type {ColumnValues[]}
+     */
+    columns: ColumnValues[];
+    /**
+     * The format of each column
+     * @type {Array.<FormatValue|undefined>|undefined}
+     */
+    formats: Array<FormatValue | undefined> | undefined;
+}
+/**
+ * @class RangeFormatSheetValue
+ * @classdesc Represents a format applied to a rectangular range of cells.
+ * @property {number} firstRow - The first row of the range
+ * @property {number} lastRow - The last row of the range
+ * @property {number} firstCol - The first column of the range
+ * @property {number} lastCol - The last column of the range
+ * @property {FormatValue} [format] - The format of every cell in the range
+ * @property {Border} [outerBorder] - The border drawn around the range
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ */
+declare class RangeFormatSheetValue {
+    /**
+     * @param {Object} opts - The options for the range format
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstCol] - The first column of the range
+     * @param {number} [opts.lastCol] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("B2:F40"), instead of the indexes
+     * @param {FormatValue} [opts.format] - The format of every cell in the range
+     * @param {Border} [opts.outerBorder] - The border drawn around the range
+     */
+    constructor(opts: {
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
+        format?: FormatValue;
+        outerBorder?: Border;
+    });
+    /**
+     * The first row of the range
+     * @type {number|undefined}
+     */
+    firstRow: number | undefined;
+    /**
+     * The last row of the range
+     * @type {number|undefined}
+     */
+    lastRow: number | undefined;
+    /**
+     * The first column of the range
+     * @type {number|undefined}
+     */
+    firstCol: number | undefined;
+    /**
+     * The last column of the range
+     * @type {number|undefined}
+     */
+    lastCol: number | undefined;
+    /**
+     * The format of every cell in the range
+     * @type {FormatValue|undefined}
+     */
+    format: FormatValue | undefined;
+    /**
+     * The border drawn around the range
+     * @type {Border|undefined}
+     */
+    outerBorder: Border | undefined;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    range: string | undefined;
+}
+/**
+ * @class FillFormulaSheetValue
+ * @classdesc Represents a formula filled over a range, like dragging Excel's fill handle.
+ * @property {number} firstRow - The first row of the range
+ * @property {number} lastRow - The last row of the range
+ * @property {number} firstCol - The first column of the range
+ * @property {number} lastCol - The last column of the range
+ * @property {string} [range] - The range in A1 notation, instead of the indexes
+ * @property {Formula} formula - The formula as written in the anchor cell
+ * @property {string} [anchor] - The cell the formula is written for, in A1 notation
+ * @property {number} [anchorRow] - The row the formula is written for
+ * @property {number} [anchorCol] - The column the formula is written for
+ * @property {FormatValue} [format] - The format of every cell in the range
+ */
+declare class FillFormulaSheetValue {
+    /**
+     * @param {Object} opts - The options for the fill formula
+     * @param {number} [opts.firstRow] - The first row of the range
+     * @param {number} [opts.lastRow] - The last row of the range
+     * @param {number} [opts.firstCol] - The first column of the range
+     * @param {number} [opts.lastCol] - The last column of the range
+     * @param {string} [opts.range] - The range in A1 notation ("D2:D100"), instead of the indexes
+     * @param {Formula} opts.formula - The formula as written in the anchor cell
+     * @param {string} [opts.anchor] - The cell the formula is written for, in A1 notation
+     * @param {number} [opts.anchorRow] - The row the formula is written for
+     * @param {number} [opts.anchorCol] - The column the formula is written for
+     * @param {FormatValue} [opts.format] - The format of every cell in the range
+     */
+    constructor(opts: {
+        firstRow?: number;
+        lastRow?: number;
+        firstCol?: number;
+        lastCol?: number;
+        range?: string;
+        formula: Formula;
+        anchor?: string;
+        anchorRow?: number;
+        anchorCol?: number;
+        format?: FormatValue;
+    });
+    /**
+     * The first row of the range
+     * @type {number|undefined}
+     */
+    firstRow: number | undefined;
+    /**
+     * The last row of the range
+     * @type {number|undefined}
+     */
+    lastRow: number | undefined;
+    /**
+     * The first column of the range
+     * @type {number|undefined}
+     */
+    firstCol: number | undefined;
+    /**
+     * The last column of the range
+     * @type {number|undefined}
+     */
+    lastCol: number | undefined;
+    /**
+     * The range in A1 notation
+     * @type {string|undefined}
+     */
+    range: string | undefined;
+    /**
+     * The formula as written in the anchor cell
+     * @type {Formula}
+     */
+    formula: Formula;
+    /**
+     * The cell the formula is written for, in A1 notation
+     * @type {string|undefined}
+     */
+    anchor: string | undefined;
+    /**
+     * The row the formula is written for
+     * @type {number|undefined}
+     */
+    anchorRow: number | undefined;
+    /**
+     * The column the formula is written for
+     * @type {number|undefined}
+     */
+    anchorCol: number | undefined;
+    /**
+     * The format of every cell in the range
+     * @type {FormatValue|undefined}
+     */
+    format: FormatValue | undefined;
+}
+/**
+ * The type a CSV column is written as. `auto` recognizes numbers, booleans, ISO dates
+ * (`2024-03-15`, `2024-03-15T09:30:00`) and formulas (`=A1*2`) that parse, and writes the rest as strings.
+ * Fields of a `formula` column must be valid formulas.
+ * @typedef {'auto'|'string'|'number'|'boolean'|'date'|'formula'} CsvColumnType
+ */
 import { ConditionalFormat } from "./conditional_format";
 import Formula = require("./formula");
 import { Table } from "./table";
 import Link = require("./link");
+import { Border } from "./border";
 export {};
 //# sourceMappingURL=sheet.d.ts.map
\ No newline at end of file
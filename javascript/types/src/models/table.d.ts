@@ -1,3 +1,4 @@
+export type FormatValue = import("./format").FormatValue;
 export type TableStyle = ("none" | "light1" | "light2" | "light3" | "light4" | "light5" | "light6" | "light7" | "light8" | "light9" | "light10" | "light11" | "light12" | "light13" | "light14" | "light15" | "light16" | "light17" | "light18" | "light19" | "light20" | "light21" | "medium1" | "medium2" | "medium3" | "medium4" | "medium5" | "medium6" | "medium7" | "medium8" | "medium9" | "medium10" | "medium11" | "medium12" | "medium13" | "medium14" | "medium15" | "medium16" | "medium17" | "medium18" | "medium19" | "medium20" | "medium21" | "medium22" | "medium23" | "medium24" | "medium25" | "medium26" | "medium27" | "medium28" | "dark1" | "dark2" | "dark3" | "dark4" | "dark5" | "dark6" | "dark7" | "dark8" | "dark9" | "dark10" | "dark11");
 export type TableFunctionType = ("none" | "average" | "count" | "countNumbers" | "max" | "min" | "sum" | "stdDev" | "var" | "custom");
 /**
@@ -93,42 +94,48 @@ export class Table {
 /**
  * @class
  * @classdesc defines a table column
- * @property {Format} [format] - column format
+ * @property {FormatValue} [format] - column format
  * @property {Formula} [formula] - column formula
  * @property {string} [header] - column header
- * @property {Format} [headerFormat] - column header format
+ * @property {FormatValue} [headerFormat] - column header format
  * @property {TableFunction} [totalFunction] - column total function
  * @property {string} [totalLabel] - column total label
+ * @property {string} [key] - record key read by `Sheet.addTableFromData` (defaults to the header)
+ * @property {("number"|"string"|"link"|"date"|"formula")} [cellType] - cell type used by `Sheet.addTableFromData`
  */
 export class TableColumn {
     /**
      * @param {Object} obj
-     * @param {Format} [obj.format]
+     * @param {FormatValue} [obj.format]
      * @param {Formula} [obj.formula]
      * @param {string} [obj.header]
-     * @param {Format} [obj.headerFormat]
+     * @param {FormatValue} [obj.headerFormat]
      * @param {Object} [obj.totalFunction]
      * @param {TableFunctionType} obj.totalFunction.type
      * @param {Formula} [obj.totalFunction.formula]
      * @param {string} [obj.totalLabel]
+     * @param {string} [obj.key]
+     * @param {("number"|"string"|"link"|"date"|"formula")} [obj.cellType]
      * @throws {Error} if totalFunction is not valid
      */
     constructor(obj: {
-        format?: Format;
+        format?: FormatValue;
         formula?: Formula;
         header?: string;
-        headerFormat?: Format;
+        headerFormat?: FormatValue;
         totalFunction?: {
             type: TableFunctionType;
             formula?: Formula;
         };
         totalLabel?: string;
+        key?: string;
+        cellType?: ("number" | "string" | "link" | "date" | "formula");
     });
     /**
-     * @type {Format|undefined|null}
+     * @type {FormatValue|undefined|null}
      * @default undefined
      */
-    format: Format | undefined | null;
+    format: FormatValue | undefined | null;
     /**
      * @type {Formula|undefined|null}
      * @default undefined
@@ -140,10 +147,10 @@ export class TableColumn {
      */
     header: string | undefined | null;
     /**
-     * @type {Format|undefined|null}
+     * @type {FormatValue|undefined|null}
      * @default undefined
      */
-    headerFormat: Format | undefined | null;
+    headerFormat: FormatValue | undefined | null;
     /**
      * @type {TableFunction|undefined|null}
      * @default undefined
@@ -155,11 +162,21 @@ export class TableColumn {
      */
     totalLabel: string | undefined | null;
     /**
-     * @param {Format} format
+     * @type {string|undefined|null}
+     * @default undefined
+     */
+    key: string | undefined | null;
+    /**
+     * @type {("number"|"string"|"link"|"date"|"formula")|undefined|null}
+     * @default undefined
+     */
+    cellType: ("number" | "string" | "link" | "date" | "formula") | undefined | null;
+    /**
+     * @param {FormatValue} format
      * @returns {void}
      * Set the format for a table column
      */
-    setFormat(format: Format): void;
+    setFormat(format: FormatValue): void;
     /**
      * @param {Object} obj
      * @param {TableFunctionType} obj.type
@@ -182,11 +199,11 @@ export class TableColumn {
      */
     setHeader(header: string): void;
     /**
-     * @param {Format} format
+     * @param {FormatValue} format
      * @returns {void}
      * Set the header format for a table column
      */
-    setHeaderFormat(format: Format): void;
+    setHeaderFormat(format: FormatValue): void;
     /**
      * @param {string} totalLabel
      * @returns {void}
@@ -308,6 +325,5 @@ export class TableFunction {
      */
     formula: Formula | undefined | null;
 }
-import Format = require("./format");
 import Formula = require("./formula");
 //# sourceMappingURL=table.d.ts.map
\ No newline at end of file
@@ -1,9 +1,69 @@
 export = Workbook;
+/**
+ * A data bar drawn in a cell, over the part of its width between `start` and `end` (0 to 1).
+ * @typedef {Object} DataBarPreview
+ * @property {number} start - Where the bar starts
+ * @property {number} end - Where the bar ends
+ * @property {string} color - The fill of the bar, as `#RRGGBB`
+ * @property {boolean} showValue - Whether the value is shown with the bar
+ */
+
+/**
+ * An icon shown in a cell.
+ * @typedef {Object} IconPreview
+ * @property {string} iconType - The icon set the icon comes from, like `threeTrafficLights`
+ * @property {number} index - The index of the icon in its set
+ * @property {boolean} showValue - Whether the value is shown with the icon
+ */
+
+/**
+ * What the conditional formats show in a cell.
+ * @typedef {Object} ConditionalFormatCellPreview
+ * @property {number} row - The row of the cell, 0-based
+ * @property {number} col - The column of the cell, 0-based
+ * @property {Format} [format] - The formats of the matching rules, the highest priority on top
+ * @property {string} [color] - The fill of a color scale, as `#RRGGBB`
+ * @property {DataBarPreview} [dataBar] - The data bar of the cell
+ * @property {IconPreview} [icon] - The icon of the cell
+ */
+
+/**
+ * @typedef {Object} ConditionalFormatSheetPreview
+ * @property {string} name - The name of the sheet
+ * @property {ConditionalFormatCellPreview[]} cells - The cells a conditional format applies to, row by row
+ */
+
+/**
+ * How a sheet is written as CSV, TSV or JSON.
+ * @typedef {Object} SheetExportOptions
+ * @property {'formatted'|'raw'} [values='formatted'] - Numbers and dates as Excel shows them through their number format, or as they are stored: numbers in full and dates in ISO 8601
+ * @property {'results'|'formulas'} [formulas='results'] - Formula cells as their result, computed when it is missing, or as their formula
+ * @property {'first'|'repeat'} [mergedCells='first'] - Whether the value of a merged range is only in its first cell, or in all of them
+ * @property {string} [delimiter] - The field delimiter, `,` for CSV and a tab for TSV
+ * @property {string} [quote='"'] - The character fields are quoted with
+ * @property {'minimal'|'all'|'nonnumeric'|'none'} [quoting='minimal'] - Which fields are quoted: the ones that need it, all of them, all but numbers, or none
+ * @property {string} [lineEnding='\r\n'] - What ends each record
+ * @property {boolean} [bom=false] - Whether the text starts with a byte order mark, for Excel to read it as UTF-8
+ * @property {boolean} [header=false] - For JSON, whether rows are objects keyed by the values of the first row, instead of arrays
+ */
+
+/**
+ * How a sheet is rendered as HTML.
+ * @typedef {Object} HtmlOptions
+ * @property {boolean} [document=false] - Whether to write a whole HTML document titled after the sheet, instead of the table alone
+ * @property {boolean} [gridLines=false] - Whether cells without borders of their own show Excel's light gray grid lines
+ */
 /**
  *
  * @class Workbook
  * @classdesc Represents a workbook
  * @property {Sheet[]} sheets - The sheets in the workbook
+ * @property {Format[]} formats - The formats shared by all sheets
+ * @property {ConditionalFormat[]} conditionalFormats - The conditional formats shared by all sheets
+ * @property {{name: string, formula: string}[]} definedNames - The names defined in the workbook
+ * @property {boolean} validateFormulas - Whether formulas are checked before the workbook is written
+ * @property {string[]} formulaWarnings - The formulas of the last save that call unknown functions
+ * @property {boolean} evaluateFormulas - Whether the results of formulas are computed before the workbook is written
  */
 declare class Workbook {
     /**
@@ -11,6 +71,90 @@ declare class Workbook {
      * @type {Sheet[]}
      */
     sheets: Sheet[];
+    /**
+     * The formats shared by all sheets
+     * @type {Format[]}
+     */
+    formats: Format[];
+    /**
+     * The conditional formats shared by all sheets
+     * @type {ConditionalFormat[]}
+     */
+    conditionalFormats: ConditionalFormat[];
+    /**
+     * The names defined in the workbook
+     * @type {{name: string, formula: string}[]}
+     */
+    definedNames: {
+        name: string;
+        formula: string;
+    }[];
+    /**
+     * Whether formulas are checked before the workbook is written
+     * @type {boolean}
+     * @default true
+     */
+    validateFormulas: boolean;
+    /**
+     * The formulas of the last save that call unknown functions, like VBA or
+     * add-in functions, as `Sheet1!A2 =MYUDF(1): Unknown function MYUDF`.
+     * They are written as they are
+     * @type {string[]}
+     */
+    formulaWarnings: string[];
+    /**
+     * Whether the results of formulas are computed before the workbook is written
+     * @type {boolean}
+     * @default false
+     */
+    evaluateFormulas: boolean;
+    /**
+     * Sets whether the formulas of the cells are checked before the workbook is written.
+     * Checked formulas must be valid and refer to sheets of the workbook, otherwise saving
+     * throws an error naming each cell. Calls to functions that are neither built in nor
+     * defined names are written as they are and listed in `formulaWarnings`. Functions added
+     * after Excel 2007, like `XLOOKUP`, get the `_xlfn.` prefix Excel expects without setting
+     * `useFutureFunctions`.
+     * @param {boolean} [validate=true] - Whether to check the formulas
+     * @returns {void}
+     */
+    setValidateFormulas(validate?: boolean): void;
+    /**
+     * Sets whether the results of formulas are computed from the cells of the workbook
+     * and stored with them, so that readers which don't recalculate, like previews and
+     * parsers, show values. Arithmetic, comparisons, references to other sheets and common
+     * functions are understood: aggregates such as `SUM`, `AVERAGE` and `COUNTIF`, logic such as
+     * `IF` and `IFERROR`, lookups such as `VLOOKUP`, `INDEX`, `MATCH` and `XLOOKUP`, text
+     * functions and date functions. Formulas using anything else, and formulas that already have
+     * a `result`, are written as before and Excel computes them when the file is opened.
+     * @param {boolean} [evaluate=true] - Whether to compute the results
+     * @returns {void}
+     */
+    setEvaluateFormulas(evaluate?: boolean): void;
+    /**
+     * Defines a name in the workbook. A name given as `Sheet1!name` is scoped to that sheet.
+     * Formulas can refer to the name, and call it when it holds a `LAMBDA`.
+     * @param {string} name - The name
+     * @param {string} formula - What the name refers to, like `=Sheet1!$A$1:$A$9` or `=LAMBDA(x,x*1.2)`
+     * @returns {void}
+     */
+    defineName(name: string, formula: string): void;
+    /**
+     * Adds a format shared by all sheets. Cells, rows, columns, tables and
+     * conditional formats of any sheet can then use the returned id in place
+     * of the format.
+     * @param {Format} format - The format to be added
+     * @returns {number} The id of the format
+     */
+    addFormat(format: Format): number;
+    /**
+     * Adds a conditional format shared by all sheets. It is read once, and
+     * `addConditionalFormat` and `addConditionalFormatGroup` of any sheet can
+     * use the returned id in place of the conditional format.
+     * @param {ConditionalFormat} conditionalFormat - The conditional format to be added
+     * @returns {number} The id of the conditional format
+     */
+    addConditionalFormat(conditionalFormat: ConditionalFormat): number;
     /**
      * Adds a sheet to the workbook
      *
@@ -74,6 +218,328 @@ declare class Workbook {
      * @throws {Error}
      */
     saveToBase64Sync(): string;
+    /**
+     * Creates an OpenDocument spreadsheet (.ods) of the workbook as a buffer.
+     * Formulas are written in OpenFormula, or in the syntax of Excel when they
+     * use defined names, table references or spill ranges.
+     * @returns {Promise<Buffer>}
+     * @throws {Error}
+     */
+    saveToOdsBuffer(): Promise<Buffer>;
+    /**
+     * Creates an OpenDocument spreadsheet (.ods) of the workbook as a buffer.
+     * @returns {Buffer}
+     * @throws {Error}
+     */
+    saveToOdsBufferSync(): Buffer;
+    /**
+     * Writes the workbook to an OpenDocument spreadsheet (.ods) file.
+     * @param {string} path - The path of the file.
+     * @returns {Promise<void>}
+     * @throws {Error}
+     */
+    saveToOdsFile(path: string): Promise<void>;
+    /**
+     * Writes the workbook to an OpenDocument spreadsheet (.ods) file.
+     * @param {string} path - The path of the file.
+     * @returns {void}
+     * @throws {Error}
+     */
+    saveToOdsFileSync(path: string): void;
+    /**
+     * Writes the workbook as an OpenDocument spreadsheet (.ods) to a base64 string.
+     * @returns {Promise<string>}
+     * @throws {Error}
+     */
+    saveToOdsBase64(): Promise<string>;
+    /**
+     * Writes the workbook as an OpenDocument spreadsheet (.ods) to a base64 string.
+     * @returns {string}
+     * @throws {Error}
+     */
+    saveToOdsBase64Sync(): string;
+    /**
+     * Writes a sheet as CSV text, row by row from `A1` to the last written cell.
+     * Empty cells are empty fields, and booleans are `TRUE` or `FALSE`.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {SheetExportOptions} [options] - How values are written
+     * @returns {Promise<string>}
+     * @throws {Error} The sheet doesn't exist or the options are invalid.
+     */
+    saveSheetToCsv(sheet?: Sheet | string | number, options?: SheetExportOptions): Promise<string>;
+    /**
+     * Writes a sheet as CSV text, see `saveSheetToCsv`.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {SheetExportOptions} [options] - How values are written
+     * @returns {string}
+     * @throws {Error} The sheet doesn't exist or the options are invalid.
+     */
+    saveSheetToCsvSync(sheet?: Sheet | string | number, options?: SheetExportOptions): string;
+    /**
+     * Writes a sheet as tab separated text, see `saveSheetToCsv`.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {SheetExportOptions} [options] - How values are written
+     * @returns {Promise<string>}
+     * @throws {Error} The sheet doesn't exist or the options are invalid.
+     */
+    saveSheetToTsv(sheet?: Sheet | string | number, options?: SheetExportOptions): Promise<string>;
+    /**
+     * Writes a sheet as tab separated text, see `saveSheetToCsv`.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {SheetExportOptions} [options] - How values are written
+     * @returns {string}
+     * @throws {Error} The sheet doesn't exist or the options are invalid.
+     */
+    saveSheetToTsvSync(sheet?: Sheet | string | number, options?: SheetExportOptions): string;
+    /**
+     * Writes a sheet as a JSON array of rows, from `A1` to the last written cell. Rows are
+     * arrays of values, or objects keyed by the first row with `header`, and empty cells
+     * are `null`. Formatted numbers are strings, raw ones numbers.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {SheetExportOptions} [options] - How values are written
+     * @returns {Promise<string>}
+     * @throws {Error} The sheet doesn't exist or the options are invalid.
+     */
+    saveSheetToJson(sheet?: Sheet | string | number, options?: SheetExportOptions): Promise<string>;
+    /**
+     * Writes a sheet as a JSON array of rows, see `saveSheetToJson`.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {SheetExportOptions} [options] - How values are written
+     * @returns {string}
+     * @throws {Error} The sheet doesn't exist or the options are invalid.
+     */
+    saveSheetToJsonSync(sheet?: Sheet | string | number, options?: SheetExportOptions): string;
+    /**
+     * Writes a sheet to a CSV, TSV or JSON file, chosen by `format` or else by the
+     * extension of the path. See `saveSheetToCsv` and `saveSheetToJson`.
+     * @param {Sheet|string|number} sheet - The sheet, its name or its index
+     * @param {string} path - The path of the file
+     * @param {SheetExportOptions & {format?: 'csv'|'tsv'|'json'}} [options] - How values are written
+     * @returns {Promise<void>}
+     * @throws {Error} The sheet doesn't exist, the options are invalid or the file can't be written.
+     */
+    saveSheetToFile(sheet: Sheet | string | number, path: string, options?: SheetExportOptions & {
+        format?: "csv" | "tsv" | "json";
+    }): Promise<void>;
+    /**
+     * Writes a sheet to a CSV, TSV or JSON file, see `saveSheetToFile`.
+     * @param {Sheet|string|number} sheet - The sheet, its name or its index
+     * @param {string} path - The path of the file
+     * @param {SheetExportOptions & {format?: 'csv'|'tsv'|'json'}} [options] - How values are written
+     * @returns {void}
+     * @throws {Error} The sheet doesn't exist, the options are invalid or the file can't be written.
+     */
+    saveSheetToFileSync(sheet: Sheet | string | number, path: string, options?: SheetExportOptions & {
+        format?: "csv" | "tsv" | "json";
+    }): void;
+    /**
+     * Renders a sheet as an HTML table, from `A1` to the last written cell. Merged cells span
+     * rows and columns, hidden rows and columns are left out, and the fonts, fills, borders,
+     * alignment, column widths, row heights and table styles are written as inline styles, so
+     * the table can go straight into an email body. Values are shown through their number
+     * formats, and formulas as their results, computed when they are missing.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {HtmlOptions} [options] - How the sheet is rendered
+     * @returns {Promise<string>}
+     * @throws {Error} The sheet doesn't exist or the workbook can't be written.
+     */
+    renderToHtml(sheet?: Sheet | string | number, options?: HtmlOptions): Promise<string>;
+    /**
+     * Renders a sheet as an HTML table, see `renderToHtml`.
+     * @param {Sheet|string|number} [sheet=0] - The sheet, its name or its index
+     * @param {HtmlOptions} [options] - How the sheet is rendered
+     * @returns {string}
+     * @throws {Error} The sheet doesn't exist or the workbook can't be written.
+     */
+    renderToHtmlSync(sheet?: Sheet | string | number, options?: HtmlOptions): string;
+    /**
+     * Evaluates the conditional formats of every sheet against the values of its cells, for
+     * previews and exports that don't compute them like Excel does. Formulas are computed where
+     * they can be, see `setEvaluateFormulas`, and cells whose value is unknown match no rule.
+     * The date rules compare with `today`, and are left out when it is `null`.
+     * @param {Object} [options] - The options object
+     * @param {Date|null} [options.today=new Date()] - The current date, its UTC day is used
+     * @returns {ConditionalFormatSheetPreview[]}
+     * @throws {Error} The workbook can't be written.
+     */
+    evaluateConditionalFormats({ today }?: {
+        today?: Date | null;
+    }): ConditionalFormatSheetPreview[];
+    /**
+     * Reads an xlsx file into a workbook, to change it and write it again.
+     * Cells, merged cells, formats, row heights, column widths, tables and conditional
+     * formats are read where the workbook can express them, and the rest of the file,
+     * like charts, images and comments, is left out. Error cells become formulas of
+     * their value, and formatted empty cells become range formats.
+     * @param {Buffer} buffer - The content of the file
+     * @returns {Promise<Workbook>}
+     * @throws {Error} The buffer is not an xlsx file.
+     */
+    static readFromBuffer(buffer: Buffer): Promise<Workbook>;
+    /**
+     * Reads an xlsx file into a workbook, see `readFromBuffer`.
+     * @param {Buffer} buffer - The content of the file
+     * @returns {Workbook}
+     * @throws {Error} The buffer is not an xlsx file.
+     */
+    static readFromBufferSync(buffer: Buffer): Workbook;
+    /**
+     * Reads an xlsx file into a workbook, see `readFromBuffer`.(using a child process for the asynchronous operation)
+     * @param {string} path - The path of the file
+     * @returns {Promise<Workbook>}
+     * @throws {Error} The file can't be read or is not an xlsx file.
+     */
+    static readFromFile(path: string): Promise<Workbook>;
+    /**
+     * Reads an xlsx file into a workbook, see `readFromBuffer`.
+     * @param {string} path - The path of the file
+     * @returns {Workbook}
+     * @throws {Error} The file can't be read or is not an xlsx file.
+     */
+    static readFromFileSync(path: string): Workbook;
+    /**
+     * Counts the distinct formats used in the workbook. Formats with the same
+     * properties are only counted once. Excel can't load more than 64000 of them.
+     * @returns {number}
+     * @throws {Error} Two different formats share the same id.
+     */
+    uniqueFormatCount(): number;
 }
+declare namespace Workbook {
+    export { DataBarPreview, IconPreview, ConditionalFormatCellPreview, ConditionalFormatSheetPreview, SheetExportOptions, HtmlOptions };
+}
+/**
+ * A data bar drawn in a cell, over the part of its width between `start` and `end` (0 to 1).
+ */
+type DataBarPreview = {
+    /**
+     * - Where the bar starts
+     */
+    start: number;
+    /**
+     * - Where the bar ends
+     */
+    end: number;
+    /**
+     * - The fill of the bar, as `#RRGGBB`
+     */
+    color: string;
+    /**
+     * - Whether the value is shown with the bar
+     */
+    showValue: boolean;
+};
+/**
+ * An icon shown in a cell.
+ */
+type IconPreview = {
+    /**
+     * - The icon set the icon comes from, like `threeTrafficLights`
+     */
+    iconType: string;
+    /**
+     * - The index of the icon in its set
+     */
+    index: number;
+    /**
+     * - Whether the value is shown with the icon
+     */
+    showValue: boolean;
+};
+/**
+ * What the conditional formats show in a cell.
+ */
+type ConditionalFormatCellPreview = {
+    /**
+     * - The row of the cell, 0-based
+     */
+    row: number;
+    /**
+     * - The column of the cell, 0-based
+     */
+    col: number;
+    /**
+     * - The formats of the matching rules, the highest priority on top
+     */
+    format?: Format;
+    /**
+     * - The fill of a color scale, as `#RRGGBB`
+     */
+    color?: string;
+    /**
+     * - The data bar of the cell
+     */
+    dataBar?: DataBarPreview;
+    /**
+     * - The icon of the cell
+     */
+    icon?: IconPreview;
+};
+type ConditionalFormatSheetPreview = {
+    /**
+     * - The name of the sheet
+     */
+    name: string;
+    /**
+     * - The cells a conditional format applies to, row by row
+     */
+    cells: ConditionalFormatCellPreview[];
+};
+/**
+ * How a sheet is written as CSV, TSV or JSON.
+ */
+type SheetExportOptions = {
+    /**
+     * - Numbers and dates as Excel shows them through their number format, or as they are stored: numbers in full and dates in ISO 8601
+     */
+    values?: "formatted" | "raw";
+    /**
+     * - Formula cells as their result, computed when it is missing, or as their formula
+     */
+    formulas?: "results" | "formulas";
+    /**
+     * - Whether the value of a merged range is only in its first cell, or in all of them
+     */
+    mergedCells?: "first" | "repeat";
+    /**
+     * - The field delimiter, `,` for CSV and a tab for TSV
+     */
+    delimiter?: string;
+    /**
+     * - The character fields are quoted with
+     */
+    quote?: string;
+    /**
+     * - Which fields are quoted: the ones that need it, all of them, all but numbers, or none
+     */
+    quoting?: "minimal" | "all" | "nonnumeric" | "none";
+    /**
+     * - What ends each record
+     */
+    lineEnding?: string;
+    /**
+     * - Whether the text starts with a byte order mark, for Excel to read it as UTF-8
+     */
+    bom?: boolean;
+    /**
+     * - For JSON, whether rows are objects keyed by the values of the first row, instead of arrays
+     */
+    header?: boolean;
+};
+/**
+ * How a sheet is rendered as HTML.
+ */
+type HtmlOptions = {
+    /**
+     * - Whether to write a whole HTML document titled after the sheet, instead of the table alone
+     */
+    document?: boolean;
+    /**
+     * - Whether cells without borders of their own show Excel's light gray grid lines
+     */
+    gridLines?: boolean;
+};
 import { Sheet } from "./sheet";
+import Format = require("./format");
+import { ConditionalFormat } from "./conditional_format";
 //# sourceMappingURL=workbook.d.ts.map
\ No newline at end of file
@@ -1,6 +1,8 @@
 use neon::prelude::*;
-use neon::types::buffer::TypedArray;
-use node_xlsx::NodeXlsxWorkbook;
+use neon::types::{buffer::TypedArray, JsDate};
+use node_xlsx::{
+    read_workbook, NodeXlsxHtml, NodeXlsxSheetExport, NodeXlsxTemplate, NodeXlsxWorkbook,
+};
 
 mod node_xlsx;
 
@@ -101,6 +103,289 @@ fn save_to_bas64_sync(mut cx: FunctionContext) -> JsResult<JsString> {
     Ok(cx.string(base64))
 }
 
+fn unique_format_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    Ok(cx.number(workbook.unique_format_count() as f64))
+}
+
+fn evaluate_conditional_formats(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let today = match cx.argument_opt(1) {
+        Some(today) if !today.is_a::<JsUndefined, _>(&mut cx) => {
+            Some(today.downcast_or_throw::<JsDate, _>(&mut cx)?)
+        }
+        _ => None,
+    };
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    workbook.preview_conditional_formats(&mut cx, today)
+}
+
+fn read_from_buffer(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_buffer: Handle<JsBuffer> = cx.argument(0)?;
+    let buffer = js_buffer.as_slice(&cx).to_vec();
+
+    let promise = cx
+        .task(move || read_workbook(&buffer))
+        .promise(|mut cx, result| match result {
+            Ok(model) => model.to_js(&mut cx),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn read_from_buffer_sync(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let js_buffer: Handle<JsBuffer> = cx.argument(0)?;
+    let model = read_workbook(js_buffer.as_slice(&cx));
+
+    match model {
+        Ok(model) => model.to_js(&mut cx),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn read_from_file(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let path: Handle<JsString> = cx.argument(0)?;
+    let path = path.value(&mut cx);
+
+    let promise = cx
+        .task(move || read_workbook(&std::fs::read(path)?))
+        .promise(|mut cx, result| match result {
+            Ok(model) => model.to_js(&mut cx),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn read_from_file_sync(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let path: Handle<JsString> = cx.argument(0)?;
+    let path = path.value(&mut cx);
+
+    let model = match std::fs::read(path) {
+        Ok(buffer) => read_workbook(&buffer),
+        Err(err) => return cx.throw_error(err.to_string()),
+    };
+
+    match model {
+        Ok(model) => model.to_js(&mut cx),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn save_template_to_buffer(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let template = NodeXlsxTemplate::from_js_object(&mut cx, js_obj)?;
+
+    let promise =
+        cx.task(move || template.save_to_buffer())
+            .promise(|mut cx, result| match result {
+                Ok(buffer) => JsBuffer::from_slice(&mut cx, &buffer),
+                Err(err) => cx.throw_error(err.to_string()),
+            });
+
+    Ok(promise)
+}
+
+fn save_template_to_buffer_sync(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let template = NodeXlsxTemplate::from_js_object(&mut cx, js_obj)?;
+
+    match template.save_to_buffer() {
+        Ok(buffer) => JsBuffer::from_slice(&mut cx, &buffer),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn save_template_to_file(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let path: Handle<JsString> = cx.argument(1)?;
+    let path = path.value(&mut cx);
+    let template = NodeXlsxTemplate::from_js_object(&mut cx, js_obj)?;
+
+    let promise = cx
+        .task(move || template.save_to_file(&path))
+        .promise(|mut cx, result| match result {
+            Ok(_) => Ok(cx.undefined()),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn save_template_to_file_sync(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let path: Handle<JsString> = cx.argument(1)?;
+    let path = path.value(&mut cx);
+    let template = NodeXlsxTemplate::from_js_object(&mut cx, js_obj)?;
+
+    match template.save_to_file(&path) {
+        Ok(_) => Ok(cx.undefined()),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn export_sheet(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let options: Handle<JsObject> = cx.argument(1)?;
+    let export = NodeXlsxSheetExport::from_js_object(&mut cx, js_obj, options)?;
+
+    let promise = cx
+        .task(move || export.to_string())
+        .promise(|mut cx, result| match result {
+            Ok(text) => Ok(cx.string(text)),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn export_sheet_sync(mut cx: FunctionContext) -> JsResult<JsString> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let options: Handle<JsObject> = cx.argument(1)?;
+    let export = NodeXlsxSheetExport::from_js_object(&mut cx, js_obj, options)?;
+
+    match export.to_string() {
+        Ok(text) => Ok(cx.string(text)),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn export_sheet_to_file(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let path: Handle<JsString> = cx.argument(1)?;
+    let path = path.value(&mut cx);
+    let options: Handle<JsObject> = cx.argument(2)?;
+    let export = NodeXlsxSheetExport::from_js_object(&mut cx, js_obj, options)?;
+
+    let promise =
+        cx.task(move || export.save_to_file(&path))
+            .promise(|mut cx, result| match result {
+                Ok(_) => Ok(cx.undefined()),
+                Err(err) => cx.throw_error(err.to_string()),
+            });
+
+    Ok(promise)
+}
+
+fn export_sheet_to_file_sync(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let path: Handle<JsString> = cx.argument(1)?;
+    let path = path.value(&mut cx);
+    let options: Handle<JsObject> = cx.argument(2)?;
+    let export = NodeXlsxSheetExport::from_js_object(&mut cx, js_obj, options)?;
+
+    match export.save_to_file(&path) {
+        Ok(_) => Ok(cx.undefined()),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn render_to_html(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let options: Handle<JsObject> = cx.argument(1)?;
+    let html = NodeXlsxHtml::from_js_object(&mut cx, js_obj, options)?;
+
+    let promise = cx
+        .task(move || html.to_string())
+        .promise(|mut cx, result| match result {
+            Ok(text) => Ok(cx.string(text)),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn render_to_html_sync(mut cx: FunctionContext) -> JsResult<JsString> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let options: Handle<JsObject> = cx.argument(1)?;
+    let html = NodeXlsxHtml::from_js_object(&mut cx, js_obj, options)?;
+
+    match html.to_string() {
+        Ok(text) => Ok(cx.string(text)),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn save_to_ods_buffer(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    let promise = cx
+        .task(move || workbook.save_to_ods_buffer())
+        .promise(|mut cx, result| match result {
+            Ok(buffer) => JsBuffer::from_slice(&mut cx, &buffer),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn save_to_ods_buffer_sync(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    match workbook.save_to_ods_buffer() {
+        Ok(buffer) => JsBuffer::from_slice(&mut cx, &buffer),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn save_to_ods_file(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let path: Handle<JsString> = cx.argument(1)?;
+    let path = path.value(&mut cx);
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    let promise = cx
+        .task(move || workbook.save_to_ods_file(&path))
+        .promise(|mut cx, result| match result {
+            Ok(_) => Ok(cx.undefined()),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn save_to_ods_file_sync(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let path: Handle<JsString> = cx.argument(1)?;
+    let path = path.value(&mut cx);
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    match workbook.save_to_ods_file(&path) {
+        Ok(_) => Ok(cx.undefined()),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+fn save_to_ods_base64(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    let promise = cx
+        .task(move || workbook.save_to_ods_base64())
+        .promise(|mut cx, result| match result {
+            Ok(base64) => Ok(cx.string(base64)),
+            Err(err) => cx.throw_error(err.to_string()),
+        });
+
+    Ok(promise)
+}
+
+fn save_to_ods_base64_sync(mut cx: FunctionContext) -> JsResult<JsString> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let workbook = NodeXlsxWorkbook::from_js_object(&mut cx, js_obj)?;
+
+    match workbook.save_to_ods_base64() {
+        Ok(base64) => Ok(cx.string(base64)),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("saveToBuffer", save_to_buffer)?;
@@ -109,5 +394,27 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("saveToFile", save_to_file)?;
     cx.export_function("saveToBase64", save_to_bas64)?;
     cx.export_function("saveToBase64Sync", save_to_bas64_sync)?;
+    cx.export_function("uniqueFormatCount", unique_format_count)?;
+    cx.export_function("evaluateConditionalFormats", evaluate_conditional_formats)?;
+    cx.export_function("readFromBuffer", read_from_buffer)?;
+    cx.export_function("readFromBufferSync", read_from_buffer_sync)?;
+    cx.export_function("readFromFile", read_from_file)?;
+    cx.export_function("readFromFileSync", read_from_file_sync)?;
+    cx.export_function("saveTemplateToBuffer", save_template_to_buffer)?;
+    cx.export_function("saveTemplateToBufferSync", save_template_to_buffer_sync)?;
+    cx.export_function("saveTemplateToFile", save_template_to_file)?;
+    cx.export_function("saveTemplateToFileSync", save_template_to_file_sync)?;
+    cx.export_function("exportSheet", export_sheet)?;
+    cx.export_function("exportSheetSync", export_sheet_sync)?;
+    cx.export_function("exportSheetToFile", export_sheet_to_file)?;
+    cx.export_function("exportSheetToFileSync", export_sheet_to_file_sync)?;
+    cx.export_function("renderToHtml", render_to_html)?;
+    cx.export_function("renderToHtmlSync", render_to_html_sync)?;
+    cx.export_function("saveToOdsBuffer", save_to_ods_buffer)?;
+    cx.export_function("saveToOdsBufferSync", save_to_ods_buffer_sync)?;
+    cx.export_function("saveToOdsFile", save_to_ods_file)?;
+    cx.export_function("saveToOdsFileSync", save_to_ods_file_sync)?;
+    cx.export_function("saveToOdsBase64", save_to_ods_base64)?;
+    cx.export_function("saveToOdsBase64Sync", save_to_ods_base64_sync)?;
     Ok(())
 }
@@ -9,7 +9,7 @@ use rust_xlsxwriter::{FormatBorder, FormatDiagonalBorder};
 
 use super::color::Color;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Border {
     pub b_type: FormatBorder,
     pub color: Color,
@@ -21,12 +21,31 @@ impl Border {
         let color = object.get(cx, "color")?;
         Ok(Self {
             b_type: format_border_from_js_string(cx, b_type)?,
-            color: Color::from_js_object(cx, color)?,
+            color: Color::from_js_value(cx, color)?,
         })
     }
+
+    /// The width in pixels and the CSS style of the line, None without one.
+    /// Medium and thick lines are wider, and dash-dot lines are drawn dashed.
+    pub fn line(&self) -> Option<(u32, &'static str)> {
+        let line = match self.b_type {
+            FormatBorder::None => return None,
+            FormatBorder::Thin => (1, "solid"),
+            FormatBorder::Medium => (2, "solid"),
+            FormatBorder::Thick => (3, "solid"),
+            FormatBorder::Double => (3, "double"),
+            FormatBorder::Dashed | FormatBorder::DashDot => (1, "dashed"),
+            FormatBorder::Dotted | FormatBorder::Hair | FormatBorder::DashDotDot => (1, "dotted"),
+            FormatBorder::MediumDashed
+            | FormatBorder::MediumDashDot
+            | FormatBorder::SlantDashDot => (2, "dashed"),
+            FormatBorder::MediumDashDotDot => (2, "dotted"),
+        };
+        Some(line)
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DiagonalBorder {
     pub border: Border,
     pub d_type: FormatDiagonalBorder,
@@ -39,7 +58,7 @@ impl DiagonalBorder {
         let d_style = object.get(cx, "dStyle")?;
         let border = Border {
             b_type: format_border_from_js_string(cx, b_type)?,
-            color: Color::from_js_object(cx, color)?,
+            color: Color::from_js_value(cx, color)?,
         };
         Ok(Self {
             border,
@@ -57,15 +76,19 @@ fn format_border_from_js_string(
         "none" => Ok(FormatBorder::None),
         "thin" => Ok(FormatBorder::Thin),
         "medium" => Ok(FormatBorder::Medium),
+        "dashed" => Ok(FormatBorder::Dashed),
+        "dotted" => Ok(FormatBorder::Dotted),
         "thick" => Ok(FormatBorder::Thick),
         "double" => Ok(FormatBorder::Double),
         "hair" => Ok(FormatBorder::Hair),
-        "medium_dashed" => Ok(FormatBorder::MediumDashed),
-        "dash_dot" => Ok(FormatBorder::DashDot),
-        "medium_dash_dot" => Ok(FormatBorder::MediumDashDot),
-        "dash_dot_dot" => Ok(FormatBorder::DashDotDot),
-        "medium_dash_dot_dot" => Ok(FormatBorder::MediumDashDotDot),
-        "slant_dash_dot" => Ok(FormatBorder::SlantDashDot),
+        // The documented names are the ones of Excel, the older snake case
+        // names are still accepted
+        "mediumDashed" | "medium_dashed" => Ok(FormatBorder::MediumDashed),
+        "dashDot" | "dash_dot" => Ok(FormatBorder::DashDot),
+        "mediumDashDot" | "medium_dash_dot" => Ok(FormatBorder::MediumDashDot),
+        "dashDotDot" | "dash_dot_dot" => Ok(FormatBorder::DashDotDot),
+        "mediumDashDotDot" | "medium_dash_dot_dot" => Ok(FormatBorder::MediumDashDotDot),
+        "slantDashDot" | "slant_dash_dot" => Ok(FormatBorder::SlantDashDot),
         _ => {
             let error = format!("Unknown border type: {}", border_type);
             let js_string = cx.string(error);
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use crate::node_xlsx::cell_range::CellRange;
 
 use super::{types::NodeXlsxTypes, util::create_format};
@@ -9,8 +7,11 @@ use neon::{
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsBoolean, JsNumber, JsObject, JsString, JsValue},
+    types::{JsBoolean, JsObject, JsString, JsValue, Value},
 };
+use rust_xlsxwriter::Formula;
+
+use crate::node_xlsx::format_registry::FormatRegistry;
 
 pub enum NodeXlsxCell {
     Simple(SimpleCell),
@@ -21,14 +22,13 @@ impl NodeXlsxCell {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
-        merged_cells: &mut Vec<CellRange>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
         let cell_type: Handle<JsBoolean> = obj
             .get_opt(cx, "merged")?
             .unwrap_or_else(|| cx.boolean(false));
         if cell_type.value(cx) {
-            let cell = MergedCell::from_js_object(cx, obj, format_map, merged_cells)?;
+            let cell = MergedCell::from_js_object(cx, obj, format_map)?;
             Ok(Self::Merged(cell))
         } else {
             let cell = SimpleCell::from_js_object(cx, obj, format_map)?;
@@ -39,7 +39,7 @@ impl NodeXlsxCell {
     pub fn write_to_sheet(
         self,
         worksheet: &mut rust_xlsxwriter::Worksheet,
-        format_map: &HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &FormatRegistry,
     ) -> Result<(), rust_xlsxwriter::XlsxError> {
         match self {
             Self::Simple(cell) => cell.write_to_sheet(worksheet, format_map),
@@ -59,12 +59,13 @@ impl SimpleCell {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
-        let result = Self::inner_from_js_object(cx, &obj, format_map);
+        let result = cx.try_catch(|cx| Self::inner_from_js_object(cx, &obj, format_map));
         match result {
             Ok(cell) => Ok(cell),
             Err(error) => {
+                let error = error.to_string(cx)?.value(cx);
                 let error = format!("Error parsing cell: {:?} with error:\n  {}", obj, error);
                 let js_string = cx.string(error);
                 cx.throw(js_string)
@@ -75,38 +76,15 @@ impl SimpleCell {
     fn inner_from_js_object(
         cx: &mut FunctionContext,
         obj: &Handle<JsObject>,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
-        let col: Handle<JsNumber> = obj.get(cx, "col")?;
-        let col = col.value(cx);
-        if col < 0.0 || col >= 16_384.0 {
-            let error = format!("Column with illegal number {}", col.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let col = u16::try_from(col as u64);
-        let col = match col {
-            Ok(col) => col,
-            Err(_) => {
-                let js_string = cx.string("Column number is too large");
-                return cx.throw(js_string);
-            }
-        };
-
-        let row: Handle<JsNumber> = obj.get(cx, "row")?;
-        let row = row.value(cx);
-        if row < 0.0 || row >= 1_048_577.0 {
-            let error = format!("Row with illegal number {}", row.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let row = row as u32;
+        let (row, col) = CellRange::cell_from_js_object(cx, *obj)?;
 
         let cel_type: Option<Handle<JsString>> = obj.get_opt(cx, "cellType")?;
         let value: Handle<JsValue> = obj.get(cx, "value")?;
         let cel_type = NodeXlsxTypes::from_js_string(cx, cel_type, value)?;
 
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
 
         let format = match format {
             Some(format) => Some(create_format(cx, format, format_map)?),
@@ -124,7 +102,7 @@ impl SimpleCell {
     pub fn write_to_sheet(
         self,
         worksheet: &mut rust_xlsxwriter::Worksheet,
-        format_map: &HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &FormatRegistry,
     ) -> Result<(), rust_xlsxwriter::XlsxError> {
         match self.cell_type {
             NodeXlsxTypes::String(value) | NodeXlsxTypes::Unknown(value) => {
@@ -143,7 +121,15 @@ impl SimpleCell {
                     worksheet.write_number(self.row, self.col, value)?;
                 }
             }
-            NodeXlsxTypes::Link(value) => {
+            NodeXlsxTypes::Boolean(value) => {
+                if let Some(format) = self.format {
+                    let format = format_map.get(&format).unwrap();
+                    worksheet.write_boolean_with_format(self.row, self.col, value, format)?;
+                } else {
+                    worksheet.write_boolean(self.row, self.col, value)?;
+                }
+            }
+            NodeXlsxTypes::Link { url: value, .. } => {
                 if let Some(format) = self.format {
                     let format = format_map.get(&format).unwrap();
                     worksheet.write_url_with_format(self.row, self.col, value, format)?;
@@ -159,7 +145,9 @@ impl SimpleCell {
                     worksheet.write_datetime(self.row, self.col, value)?;
                 }
             }
-            NodeXlsxTypes::Formula((value, dynamic)) => {
+            NodeXlsxTypes::Formula(value) => {
+                let dynamic = value.dynamic;
+                let value: Formula = value.into();
                 let has_format = self.format.is_some();
                 if dynamic && !has_format {
                     worksheet.write_dynamic_formula(self.row, self.col, value)?;
@@ -191,13 +179,13 @@ impl MergedCell {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
-        merged_cells: &mut Vec<CellRange>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
-        let result = Self::inner_from_js_object(cx, &obj, format_map, merged_cells);
+        let result = cx.try_catch(|cx| Self::inner_from_js_object(cx, &obj, format_map));
         match result {
             Ok(cell) => Ok(cell),
             Err(error) => {
+                let error = error.to_string(cx)?.value(cx);
                 let error = format!("Error parsing cell: {:?} with error:\n  {}", obj, error);
                 let js_string = cx.string(error);
                 cx.throw(js_string)
@@ -208,7 +196,7 @@ impl MergedCell {
     pub fn write_to_sheet(
         self,
         worksheet: &mut rust_xlsxwriter::Worksheet,
-        format_map: &HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &FormatRegistry,
     ) -> Result<(), rust_xlsxwriter::XlsxError> {
         match self.cell_type {
             NodeXlsxTypes::String(value) | NodeXlsxTypes::Unknown(value) => {
@@ -256,7 +244,24 @@ impl MergedCell {
                     format,
                 )?;
             }
-            NodeXlsxTypes::Link(value) => {
+            NodeXlsxTypes::Boolean(value) => {
+                let format = format_map.get(&self.format).unwrap();
+                worksheet.merge_range(
+                    self.range.start_row,
+                    self.range.start_col as u16,
+                    self.range.end_row,
+                    self.range.end_col as u16,
+                    "",
+                    format,
+                )?;
+                worksheet.write_boolean_with_format(
+                    self.range.start_row,
+                    self.range.start_col as u16,
+                    value,
+                    format,
+                )?;
+            }
+            NodeXlsxTypes::Link { url: value, .. } => {
                 let format = format_map.get(&self.format).unwrap();
                 worksheet.merge_range(
                     self.range.start_row,
@@ -273,7 +278,9 @@ impl MergedCell {
                     format,
                 )?;
             }
-            NodeXlsxTypes::Formula((value, dynamic)) => {
+            NodeXlsxTypes::Formula(value) => {
+                let dynamic = value.dynamic;
+                let value: Formula = value.into();
                 let format = format_map.get(&self.format).unwrap();
                 worksheet.merge_range(
                     self.range.start_row,
@@ -306,72 +313,19 @@ impl MergedCell {
     fn inner_from_js_object(
         cx: &mut FunctionContext,
         obj: &Handle<JsObject>,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
-        merged_cells: &mut Vec<CellRange>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
-        let first_row: Handle<JsNumber> = obj.get(cx, "firstRow")?;
-        let first_row = first_row.value(cx);
-        if first_row < 0.0 || first_row >= 1_048_577.0 {
-            let error = format!("Row with illegal number {}", first_row.to_string());
-            let js_string = cx.string(error);
+        let range = CellRange::from_js_object(cx, *obj, ("firstCol", "lastCol"))?;
+        if range.is_cell() {
+            let js_string = cx.string("A merged cell must span more than one cell");
             return cx.throw(js_string);
         }
-        let first_row = first_row as u32;
-
-        let last_row: Handle<JsNumber> = obj.get(cx, "lastRow")?;
-        let last_row = last_row.value(cx);
-        if last_row < 0.0 || last_row >= 1_048_577.0 {
-            let error = format!("Row with illegal number {}", last_row.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let last_row = last_row as u32;
-
-        let first_col: Handle<JsNumber> = obj.get(cx, "firstCol")?;
-        let first_col = first_col.value(cx);
-        if first_col < 0.0 || first_col >= 16_384.0 {
-            let error = format!("Column with illegal number {}", first_col.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let first_col = first_col as u32;
-
-        let last_col: Handle<JsNumber> = obj.get(cx, "lastCol")?;
-        let last_col = last_col.value(cx);
-        if last_col < 0.0 || last_col >= 16_384.0 {
-            let error = format!("Column with illegal number {}", last_col.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let last_col = last_col as u32;
-
-        let range = CellRange::new(first_row, last_row, first_col, last_col);
-        let range = match range {
-            Ok(range) => range,
-            Err(error) => {
-                let js_string = cx.string(error);
-                return cx.throw(js_string);
-            }
-        };
-
-        //Check if the new merged cell overlaps with any existing merged cells
-        for existing_range in merged_cells.iter() {
-            if range.overlaps(existing_range) {
-                let error = format!(
-                    "Merged cell range overlaps with existing merged cell range: {:?}",
-                    existing_range
-                );
-                let js_string = cx.string(error);
-                return cx.throw(js_string);
-            }
-        }
-        merged_cells.push(range);
 
         let cel_type: Option<Handle<JsString>> = obj.get_opt(cx, "cellType")?;
         let value: Handle<JsValue> = obj.get(cx, "value")?;
         let cel_type = NodeXlsxTypes::from_js_string(cx, cel_type, value)?;
 
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
 
         let format = match format {
             Some(format) => create_format(cx, format, format_map)?,
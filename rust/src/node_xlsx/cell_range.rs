@@ -1,3 +1,18 @@
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsNumber, JsObject, JsString},
+};
+
+use super::formula::reference::Reference;
+
+/// Number of rows in a sheet.
+pub const ROW_COUNT: u32 = 1_048_576;
+/// Number of columns in a sheet.
+pub const COLUMN_COUNT: u16 = 16_384;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CellRange {
     pub start_row: u32,
@@ -7,6 +22,7 @@ pub struct CellRange {
 }
 
 impl CellRange {
+    /// A range from its corners, checked against the sheet bounds.
     pub fn new(start_row: u32, end_row: u32, start_col: u32, end_col: u32) -> Result<Self, String> {
         if start_row > end_row {
             return Err("start_row must be less than or equal to end_row".to_string());
@@ -14,25 +30,112 @@ impl CellRange {
         if start_col > end_col {
             return Err("start_col must be less than or equal to end_col".to_string());
         }
-        if end_row > 1048576 {
-            return Err("end_row must be less than or equal to 1048576".to_string());
-        }
-        if end_col > 16384 {
-            return Err("end_col must be less than or equal to 16384".to_string());
+        if end_row >= ROW_COUNT {
+            return Err(format!("end_row must be less than {}", ROW_COUNT));
         }
-        if start_row == end_row && start_col == end_col {
-            return Err(
-                "start_row and end_row cannot be the same if start_col and end_col are the same"
-                    .to_string(),
-            );
+        if end_col >= COLUMN_COUNT as u32 {
+            return Err(format!("end_col must be less than {}", COLUMN_COUNT));
         }
 
-        return Ok(Self {
+        Ok(Self {
             start_row,
             end_row,
             start_col,
             end_col,
-        });
+        })
+    }
+
+    /// Parses a range in A1 notation: a cell (`"$A$1"`), an area (`"B2:F40"`),
+    /// whole columns (`"C:C"`) or whole rows (`"3:3"`).
+    pub fn parse(a1: &str) -> Result<Self, String> {
+        let error = || format!("Invalid range \"{}\"", a1);
+
+        let mut parts = a1.split(':');
+        let first = parts.next().ok_or_else(error)?;
+        let first = parse_reference(first).ok_or_else(error)?;
+        let last = match parts.next() {
+            Some(last) => parse_reference(last).ok_or_else(error)?,
+            None => first,
+        };
+        if parts.next().is_some() {
+            return Err(error());
+        }
+
+        let range = match (first, last) {
+            ((Some(first_col), Some(first_row)), (Some(last_col), Some(last_row))) => {
+                Self::new(first_row, last_row, first_col, last_col)
+            }
+            ((Some(first_col), None), (Some(last_col), None)) => {
+                Self::new(0, ROW_COUNT - 1, first_col, last_col)
+            }
+            ((None, Some(first_row)), (None, Some(last_row))) => {
+                Self::new(first_row, last_row, 0, COLUMN_COUNT as u32 - 1)
+            }
+            _ => return Err(error()),
+        };
+        range.map_err(|reason| format!("{}: {}", error(), reason))
+    }
+
+    /// Parses a single cell in A1 notation, such as `"B2"` or `"$B$2"`.
+    pub fn parse_cell(a1: &str) -> Result<(u32, u16), String> {
+        let range = Self::parse(a1)?;
+        if !range.is_cell() {
+            return Err(format!("\"{}\" is not a single cell", a1));
+        }
+        Ok((range.start_row, range.start_col as u16))
+    }
+
+    /// Reads a range given either as an A1 string in `range`, or as the
+    /// numeric `firstRow`, `lastRow` and the two column keys in `column_keys`.
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        column_keys: (&str, &str),
+    ) -> NeonResult<Self> {
+        let a1: Option<Handle<JsString>> = obj.get_opt(cx, "range")?;
+        let range = match a1 {
+            Some(a1) => Self::parse(&a1.value(cx)),
+            None => {
+                let first_row = row_from_js_object(cx, obj, "firstRow")?;
+                let last_row = row_from_js_object(cx, obj, "lastRow")?;
+                let first_col = column_from_js_object(cx, obj, column_keys.0)?;
+                let last_col = column_from_js_object(cx, obj, column_keys.1)?;
+                Self::new(first_row, last_row, first_col as u32, last_col as u32)
+            }
+        };
+        range.or_else(|error| cx.throw_error(error))
+    }
+
+    /// Reads a cell given either as an A1 string in `cell`, or as the numeric
+    /// `row` and `col`.
+    pub fn cell_from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+    ) -> NeonResult<(u32, u16)> {
+        let a1: Option<Handle<JsString>> = obj.get_opt(cx, "cell")?;
+        if let Some(a1) = a1 {
+            let a1 = a1.value(cx);
+            return Self::parse_cell(&a1).or_else(|error| cx.throw_error(error));
+        }
+
+        let row = row_from_js_object(cx, obj, "row")?;
+        let col = column_from_js_object(cx, obj, "col")?;
+        Ok((row, col))
+    }
+
+    pub fn is_cell(&self) -> bool {
+        self.start_row == self.end_row && self.start_col == self.end_col
+    }
+
+    /// Whether the range spans every row or every column of the sheet.
+    pub fn is_whole_rows_or_columns(&self) -> bool {
+        (self.start_row == 0 && self.end_row == ROW_COUNT - 1)
+            || (self.start_col == 0 && self.end_col == COLUMN_COUNT as u32 - 1)
+    }
+
+    pub fn contains(&self, row: u32, col: u16) -> bool {
+        (self.start_row..=self.end_row).contains(&row)
+            && (self.start_col..=self.end_col).contains(&(col as u32))
     }
 
     pub fn overlaps(&self, other: &Self) -> bool {
@@ -42,3 +145,42 @@ impl CellRange {
             && self.end_col >= other.start_col
     }
 }
+
+/// Reads a zero based row index from `obj[key]`.
+pub fn row_from_js_object(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+    key: &str,
+) -> NeonResult<u32> {
+    let row: Handle<JsNumber> = obj.get(cx, key)?;
+    let row = row.value(cx);
+    if !(0.0..ROW_COUNT as f64).contains(&row) {
+        let error = format!("Row with illegal number {}", row);
+        return cx.throw_error(error);
+    }
+    Ok(row as u32)
+}
+
+/// Reads a zero based column index from `obj[key]`.
+pub fn column_from_js_object(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+    key: &str,
+) -> NeonResult<u16> {
+    let col: Handle<JsNumber> = obj.get(cx, key)?;
+    let col = col.value(cx);
+    if !(0.0..COLUMN_COUNT as f64).contains(&col) {
+        let error = format!("Column with illegal number {}", col);
+        return cx.throw_error(error);
+    }
+    Ok(col as u16)
+}
+
+/// Splits a reference such as `"$B$2"`, `"B"` or `"2"` into its zero based
+/// column and row.
+fn parse_reference(reference: &str) -> Option<(Option<u32>, Option<u32>)> {
+    let reference = Reference::parse(reference)?;
+    let col = reference.col.map(|col| col.index);
+    let row = reference.row.map(|row| row.index);
+    Some((col, row))
+}
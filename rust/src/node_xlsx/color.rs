@@ -1,36 +1,173 @@
 use neon::{
-    context::FunctionContext,
+    context::{Context, FunctionContext},
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsNumber, JsObject, JsValue},
+    types::{JsNumber, JsObject, JsString, JsValue},
 };
 
-#[derive(Clone, Copy)]
+/// The tints of the theme color shades, as rust_xlsxwriter writes them for
+/// the first three theme colors and for the rest.
+pub const SHADE_TINTS: [[f64; 6]; 4] = [
+    [0.0, -0.05, -0.15, -0.25, -0.35, -0.5],
+    [0.0, 0.5, 0.35, 0.25, 0.15, 0.05],
+    [0.0, -0.1, -0.25, -0.5, -0.75, -0.9],
+    [0.0, 0.8, 0.6, 0.4, -0.25, -0.5],
+];
+
+/// The colors of the default theme written by rust_xlsxwriter, in the order
+/// of `Color::Theme`: light 1, dark 1, light 2, dark 2 and the six accents.
+pub const THEME_COLORS: [u32; 10] = [
+    0xFFFFFF, 0x000000, 0xEEECE1, 0x1F497D, 0x4F81BD, 0xC0504D, 0x9BBB59, 0x8064A2, 0x4BACC6,
+    0xF79646,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Color {
-    red: u8,
-    green: u8,
-    blue: u8,
+    inner: rust_xlsxwriter::Color,
 }
 
 impl Color {
+    /// Accepts a `{red, green, blue}` object, a `{theme, shade}` object,
+    /// a hex string (`"#1F4E79"`), a named color or `"automatic"`.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        if let Ok(string) = value.downcast::<JsString, _>(cx) {
+            let string = string.value(cx);
+            return match color_from_str(&string) {
+                Some(inner) => Ok(Self { inner }),
+                None => {
+                    let error = format!("Invalid color: {}", string);
+                    cx.throw_error(error)
+                }
+            };
+        }
+
+        if let Ok(object) = value.downcast::<JsObject, _>(cx) {
+            return Self::from_js_object(cx, object);
+        }
+
+        let error = format!("Invalid color: {:?}", value);
+        cx.throw_error(error)
+    }
+
     pub fn from_js_object(cx: &mut FunctionContext, object: Handle<JsObject>) -> NeonResult<Self> {
+        let theme: Option<Handle<JsNumber>> = object.get_opt(cx, "theme")?;
+        if let Some(theme) = theme {
+            let theme = theme.value(cx);
+            let shade: Option<Handle<JsNumber>> = object.get_opt(cx, "shade")?;
+            let shade = match shade {
+                Some(shade) => shade.value(cx),
+                None => 0.0,
+            };
+            let theme = color_component(cx, "theme", theme, 9)?;
+            let shade = color_component(cx, "shade", shade, 5)?;
+            return Ok(Self {
+                inner: rust_xlsxwriter::Color::Theme(theme, shade),
+            });
+        }
+
         let red: Handle<JsValue> = object.get(cx, "red")?;
         let green: Handle<JsValue> = object.get(cx, "green")?;
         let blue: Handle<JsValue> = object.get(cx, "blue")?;
+        let red = red.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+        let green = green.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+        let blue = blue.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+        let red = color_component(cx, "red", red, 255)? as u32;
+        let green = color_component(cx, "green", green, 255)? as u32;
+        let blue = color_component(cx, "blue", blue, 255)? as u32;
         Ok(Self {
-            red: red.downcast_or_throw::<JsNumber, _>(cx)?.value(cx) as u8,
-            green: green.downcast_or_throw::<JsNumber, _>(cx)?.value(cx) as u8,
-            blue: blue.downcast_or_throw::<JsNumber, _>(cx)?.value(cx) as u8,
+            inner: rust_xlsxwriter::Color::RGB((red << 16) | (green << 8) | blue),
+        })
+    }
+
+    /// The RGB value the color is shown with, None for the automatic color.
+    pub fn rgb(&self) -> Option<u32> {
+        use rust_xlsxwriter::Color::*;
+        Some(match self.inner {
+            RGB(rgb) => rgb,
+            Theme(theme, shade) => {
+                let rgb = *THEME_COLORS.get(theme as usize)?;
+                let tints = SHADE_TINTS[(theme as usize).min(3)];
+                tint(rgb, tints.get(shade as usize).copied().unwrap_or(0.0))
+            }
+            Automatic | Default => return None,
+            Black => 0x000000,
+            Blue => 0x0000FF,
+            Brown => 0x800000,
+            Cyan => 0x00FFFF,
+            Gray => 0x808080,
+            Green => 0x008000,
+            Lime => 0x00FF00,
+            Magenta => 0xFF00FF,
+            Navy => 0x000080,
+            Orange => 0xFF6600,
+            Pink => 0xFFC0CB,
+            Purple => 0x800080,
+            Red => 0xFF0000,
+            Silver => 0xC0C0C0,
+            White => 0xFFFFFF,
+            Yellow => 0xFFFF00,
         })
     }
 }
 
-impl Into<rust_xlsxwriter::Color> for Color {
-    fn into(self) -> rust_xlsxwriter::Color {
-        let red = self.red as u32;
-        let green = self.green as u32;
-        let blue = self.blue as u32;
-        rust_xlsxwriter::Color::RGB((red << 16) | (green << 8) | blue)
+/// Lightens a color towards white for a positive tint, or darkens it
+/// towards black for a negative one. Excel tints the luminance of the color,
+/// which this follows closely enough for previews.
+pub fn tint(rgb: u32, tint: f64) -> u32 {
+    let channel = |shift: u32| {
+        let value = ((rgb >> shift) & 0xFF) as f64;
+        let value = match tint < 0.0 {
+            true => value * (1.0 + tint),
+            false => value + (255.0 - value) * tint,
+        };
+        (value.round() as u32).min(255) << shift
+    };
+    channel(16) | channel(8) | channel(0)
+}
+
+impl From<Color> for rust_xlsxwriter::Color {
+    fn from(color: Color) -> Self {
+        color.inner
+    }
+}
+
+fn color_component(cx: &mut FunctionContext, name: &str, value: f64, max: u8) -> NeonResult<u8> {
+    if value.fract() != 0.0 || !(0.0..=max as f64).contains(&value) {
+        let error = format!(
+            "Color {} must be an integer between 0 and {}: {}",
+            name, max, value
+        );
+        return cx.throw_error(error);
+    }
+    Ok(value as u8)
+}
+
+fn color_from_str(value: &str) -> Option<rust_xlsxwriter::Color> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        let rgb = u32::from_str_radix(hex, 16).ok()?;
+        return Some(rust_xlsxwriter::Color::RGB(rgb));
+    }
+
+    match value.to_lowercase().as_str() {
+        "automatic" => Some(rust_xlsxwriter::Color::Automatic),
+        "black" => Some(rust_xlsxwriter::Color::Black),
+        "blue" => Some(rust_xlsxwriter::Color::Blue),
+        "brown" => Some(rust_xlsxwriter::Color::Brown),
+        "cyan" => Some(rust_xlsxwriter::Color::Cyan),
+        "gray" => Some(rust_xlsxwriter::Color::Gray),
+        "green" => Some(rust_xlsxwriter::Color::Green),
+        "lime" => Some(rust_xlsxwriter::Color::Lime),
+        "magenta" => Some(rust_xlsxwriter::Color::Magenta),
+        "navy" => Some(rust_xlsxwriter::Color::Navy),
+        "orange" => Some(rust_xlsxwriter::Color::Orange),
+        "pink" => Some(rust_xlsxwriter::Color::Pink),
+        "purple" => Some(rust_xlsxwriter::Color::Purple),
+        "red" => Some(rust_xlsxwriter::Color::Red),
+        "silver" => Some(rust_xlsxwriter::Color::Silver),
+        "white" => Some(rust_xlsxwriter::Color::White),
+        "yellow" => Some(rust_xlsxwriter::Color::Yellow),
+        _ => None,
     }
 }
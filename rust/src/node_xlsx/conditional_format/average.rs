@@ -5,11 +5,11 @@ use neon::{
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsBoolean, JsNumber, JsObject, JsString},
+    types::{JsBoolean, JsNumber, JsObject, JsString, JsValue},
 };
 use rust_xlsxwriter::{ConditionalFormatAverage, ConditionalFormatAverageRule, Format};
 
-use crate::node_xlsx::format::NodeXlsxFormat;
+use crate::node_xlsx::{format_registry::FormatRegistry, util::create_format};
 
 use super::c_type::NodeXlsxConditionalFormatType;
 
@@ -24,7 +24,7 @@ impl<'a> Average<'a> {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
     ) -> NeonResult<Self> {
         let multi_range: Option<Handle<JsString>> = obj.get_opt(cx, "multiRange")?;
         let multi_range = multi_range.map(|range| range.value(cx));
@@ -38,15 +38,10 @@ impl<'a> Average<'a> {
             None => None,
         };
 
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
         let format = match format {
             Some(format) => {
-                let id: Handle<JsNumber> = format.get(cx, "id")?;
-                let id = id.value(cx) as u32;
-                if !format_map.contains_key(&id) {
-                    let format = NodeXlsxFormat::from_js_object(cx, format)?;
-                    format_map.insert(id, format.into());
-                }
+                let id = create_format(cx, format, format_map)?;
                 let format = format_map.get(&id).unwrap();
                 Some(format)
             }
@@ -65,7 +60,7 @@ impl<'a> Average<'a> {
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
         c_format_map: &mut HashMap<u32, NodeXlsxConditionalFormatType>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
     ) -> NeonResult<u32> {
         let id: Handle<JsNumber> = obj.get(cx, "id")?;
         let id = id.value(cx) as u32;
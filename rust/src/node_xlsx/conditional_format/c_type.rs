@@ -1,8 +1,9 @@
 use rust_xlsxwriter::{
-    ConditionalFormat2ColorScale, ConditionalFormat3ColorScale, ConditionalFormatAverage,
-    ConditionalFormatBlank, ConditionalFormatCell, ConditionalFormatDataBar, ConditionalFormatDate,
-    ConditionalFormatDuplicate, ConditionalFormatError, ConditionalFormatFormula,
-    ConditionalFormatIconSet, ConditionalFormatText, ConditionalFormatTop, Worksheet, XlsxError,
+    ConditionalFormat, ConditionalFormat2ColorScale, ConditionalFormat3ColorScale,
+    ConditionalFormatAverage, ConditionalFormatBlank, ConditionalFormatCell,
+    ConditionalFormatDataBar, ConditionalFormatDate, ConditionalFormatDuplicate,
+    ConditionalFormatError, ConditionalFormatFormula, ConditionalFormatIconSet,
+    ConditionalFormatText, ConditionalFormatTop, Worksheet, XlsxError,
 };
 
 pub enum NodeXlsxConditionalFormatType {
@@ -22,6 +23,47 @@ pub enum NodeXlsxConditionalFormatType {
 }
 
 impl NodeXlsxConditionalFormatType {
+    fn as_conditional_format(&self) -> &dyn ConditionalFormat {
+        match self {
+            NodeXlsxConditionalFormatType::TwoColorScale(cf) => cf,
+            NodeXlsxConditionalFormatType::ThreeColorScale(cf) => cf,
+            NodeXlsxConditionalFormatType::Average(cf) => cf,
+            NodeXlsxConditionalFormatType::Blank(cf) => cf,
+            NodeXlsxConditionalFormatType::Cell(cf) => cf,
+            NodeXlsxConditionalFormatType::DataBar(cf) => cf,
+            NodeXlsxConditionalFormatType::Date(cf) => cf,
+            NodeXlsxConditionalFormatType::Duplicate(cf) => cf,
+            NodeXlsxConditionalFormatType::Error(cf) => cf,
+            NodeXlsxConditionalFormatType::Formula(cf) => cf,
+            NodeXlsxConditionalFormatType::IconSet(cf) => cf,
+            NodeXlsxConditionalFormatType::Text(cf) => cf,
+            NodeXlsxConditionalFormatType::Top(cf) => cf,
+        }
+    }
+
+    /// The ranges set with `multiRange`, empty when there are none.
+    pub fn multi_range(&self) -> String {
+        self.as_conditional_format().multi_range()
+    }
+
+    /// The rule as it is written for `sqref`, followed by its Excel 2010
+    /// extension when it has one.
+    pub fn rule_xml(&self, sqref: &str) -> String {
+        let cf = self.as_conditional_format();
+        cf.rule(None, 1, sqref, "") + &cf.x14_rule(1, "")
+    }
+
+    /// Whether two conditional formats write the same rule with the same format.
+    pub fn same_rule(&self, other: &Self) -> bool {
+        let definition = |cf: &Self| {
+            let cf = cf.as_conditional_format();
+            let format = cf.box_clone().format_as_mut().map(|format| format.clone());
+            let rule = cf.rule(None, 1, "A1", "");
+            (rule, cf.x14_rule(1, ""), cf.multi_range(), format)
+        };
+        definition(self) == definition(other)
+    }
+
     pub fn set_conditional_format<'a>(
         &'a self,
         worksheet: &'a mut Worksheet,
@@ -11,7 +11,10 @@ use rust_xlsxwriter::{
     ConditionalFormatCell, ConditionalFormatCellRule, Format, IntoConditionalFormatValue,
 };
 
-use crate::node_xlsx::{format::NodeXlsxFormat, util::js_date_to_naive_date_time};
+use crate::node_xlsx::{
+    format_registry::FormatRegistry,
+    util::{create_format, js_date_to_naive_date_time},
+};
 
 use super::{c_type::NodeXlsxConditionalFormatType, rule::NodeXlsxConditionalFormatCellRule};
 
@@ -26,7 +29,7 @@ impl<'a> Cell<'a> {
     pub fn from_js_object(
         cx: &'a mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
         rule: Option<NodeXlsxConditionalFormatCellRule<'a>>,
     ) -> NeonResult<Self> {
         let multi_range: Option<Handle<JsString>> = obj.get_opt(cx, "multiRange")?;
@@ -35,15 +38,10 @@ impl<'a> Cell<'a> {
         let stop_if_true: Option<Handle<JsBoolean>> = obj.get_opt(cx, "stopIfTrue")?;
         let stop_if_true = stop_if_true.map(|stop| stop.value(cx));
 
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
         let format = match format {
             Some(format) => {
-                let id: Handle<JsNumber> = format.get(cx, "id")?;
-                let id = id.value(cx) as u32;
-                if !format_map.contains_key(&id) {
-                    let format = NodeXlsxFormat::from_js_object(cx, format)?;
-                    format_map.insert(id, format.into());
-                }
+                let id = create_format(cx, format, format_map)?;
                 let format = format_map.get(&id).unwrap();
                 Some(format)
             }
@@ -63,7 +61,7 @@ impl<'a> Cell<'a> {
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
         c_format_map: &mut HashMap<u32, NodeXlsxConditionalFormatType>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
     ) -> NeonResult<u32> {
         let id: Handle<JsNumber> = obj.get(cx, "id")?;
         let id = id.value(cx) as u32;
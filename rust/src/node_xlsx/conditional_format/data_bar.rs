@@ -5,7 +5,7 @@ use neon::{
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsBoolean, JsNumber, JsObject, JsString},
+    types::{JsBoolean, JsNumber, JsObject, JsString, JsValue},
 };
 use rust_xlsxwriter::{
     ConditionalFormatDataBar, ConditionalFormatDataBarAxisPosition,
@@ -35,9 +35,9 @@ pub struct DataBar {
 
 impl DataBar {
     pub fn from_js_value(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
-        let axis_color: Option<Handle<JsObject>> = obj.get_opt(cx, "axisColor")?;
+        let axis_color: Option<Handle<JsValue>> = obj.get_opt(cx, "axisColor")?;
         let axis_color = match axis_color {
-            Some(axis_color) => Some(Color::from_js_object(cx, axis_color)?),
+            Some(axis_color) => Some(Color::from_js_value(cx, axis_color)?),
             None => None,
         };
 
@@ -53,9 +53,9 @@ impl DataBar {
             None => None,
         };
 
-        let border_color: Option<Handle<JsObject>> = obj.get_opt(cx, "borderColor")?;
+        let border_color: Option<Handle<JsValue>> = obj.get_opt(cx, "borderColor")?;
         let border_color = match border_color {
-            Some(border_color) => Some(Color::from_js_object(cx, border_color)?),
+            Some(border_color) => Some(Color::from_js_value(cx, border_color)?),
             None => None,
         };
 
@@ -71,9 +71,9 @@ impl DataBar {
             None => None,
         };
 
-        let fill_color: Option<Handle<JsObject>> = obj.get_opt(cx, "fillColor")?;
+        let fill_color: Option<Handle<JsValue>> = obj.get_opt(cx, "fillColor")?;
         let fill_color = match fill_color {
-            Some(fill_color) => Some(Color::from_js_object(cx, fill_color)?),
+            Some(fill_color) => Some(Color::from_js_value(cx, fill_color)?),
             None => None,
         };
 
@@ -89,16 +89,16 @@ impl DataBar {
             None => None,
         };
 
-        let negative_border_color: Option<Handle<JsObject>> =
+        let negative_border_color: Option<Handle<JsValue>> =
             obj.get_opt(cx, "negativeBorderColor")?;
         let negative_border_color = match negative_border_color {
-            Some(negative_border_color) => Some(Color::from_js_object(cx, negative_border_color)?),
+            Some(negative_border_color) => Some(Color::from_js_value(cx, negative_border_color)?),
             None => None,
         };
 
-        let negative_fill_color: Option<Handle<JsObject>> = obj.get_opt(cx, "negativeFillColor")?;
+        let negative_fill_color: Option<Handle<JsValue>> = obj.get_opt(cx, "negativeFillColor")?;
         let negative_fill_color = match negative_fill_color {
-            Some(negative_fill_color) => Some(Color::from_js_object(cx, negative_fill_color)?),
+            Some(negative_fill_color) => Some(Color::from_js_value(cx, negative_fill_color)?),
             None => None,
         };
 
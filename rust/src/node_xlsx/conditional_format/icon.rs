@@ -213,3 +213,30 @@ fn icon_from_js_string(
         }
     }
 }
+
+/// The JS name of an icon set written as `name`.
+pub fn icon_type_name(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "3Arrows" => "threeArrows",
+        "3ArrowsGray" => "threeArrowsGray",
+        "3Flags" => "threeFlags",
+        "3TrafficLights1" => "threeTrafficLights",
+        "3TrafficLights2" => "threeTrafficLightsWithRim",
+        "3Signs" => "threeSigns",
+        "3Symbols" => "threeSymbolsCircled",
+        "3Symbols2" => "threeSymbols",
+        "3Stars" => "threeStars",
+        "3Triangles" => "threeTriangles",
+        "4Arrows" => "fourArrows",
+        "4ArrowsGray" => "fourArrowsGray",
+        "4RedToBlack" => "fourRedToBlack",
+        "4Rating" => "fourHistograms",
+        "4TrafficLights" => "fourTrafficLights",
+        "5Arrows" => "fiveArrows",
+        "5ArrowsGray" => "fiveArrowsGray",
+        "5Rating" => "fiveHistograms",
+        "5Quarters" => "fiveQuadrants",
+        "5Boxes" => "fiveBoxes",
+        _ => return None,
+    })
+}
@@ -8,6 +8,7 @@ pub mod duplicate;
 pub mod error;
 pub mod formula;
 pub mod icon;
+pub mod registry;
 mod rule;
 pub mod scale;
 pub mod text;
@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet};
+
+use neon::{
+    context::{Context, FunctionContext},
+    handle::{Handle, Root},
+    object::Object,
+    result::NeonResult,
+    types::{JsNumber, JsObject, JsString, JsValue},
+};
+
+use crate::node_xlsx::format_registry::FormatRegistry;
+
+use super::{
+    average::Average,
+    blank::Blank,
+    c_type::NodeXlsxConditionalFormatType,
+    cell::Cell,
+    data_bar::DataBar,
+    date::Date,
+    duplicate::Duplicate,
+    error::Error,
+    formula::FormulaFormat,
+    icon::Icon,
+    scale::{ThreeColorScale, TwoColorScale},
+    text::Text,
+    top::Top,
+};
+
+/// The conditional formats of every sheet, keyed by their JS id.
+///
+/// There is one registry per workbook, so a rule used on many sheets is
+/// parsed once. A JS object already seen is not parsed again, and another
+/// object with the same id must define the same rule. Conditional formats
+/// declared on the workbook can be referenced from any sheet by id alone.
+#[derive(Default)]
+pub struct ConditionalFormatRegistry {
+    formats: HashMap<u32, NodeXlsxConditionalFormatType>,
+    by_js_id: HashMap<u32, Root<JsObject>>,
+    declared: HashSet<u32>,
+}
+
+impl ConditionalFormatRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves a conditional format given either as an object or as the id
+    /// of a conditional format declared on the workbook.
+    pub fn resolve(
+        &mut self,
+        cx: &mut FunctionContext,
+        value: Handle<JsValue>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<u32> {
+        if let Ok(id) = value.downcast::<JsNumber, _>(cx) {
+            let id = id.value(cx) as u32;
+            if !self.declared.contains(&id) {
+                let error = format!(
+                    "Conditional format id {} is not declared on the workbook",
+                    id
+                );
+                return cx.throw_error(error);
+            }
+            return Ok(id);
+        }
+
+        let obj = value.downcast_or_throw::<JsObject, _>(cx)?;
+        self.register(cx, obj, format_map)
+    }
+
+    /// Registers a workbook level conditional format so sheets can refer to it by id.
+    pub fn declare(
+        &mut self,
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<u32> {
+        let id = self.register(cx, obj, format_map)?;
+        self.declared.insert(id);
+        Ok(id)
+    }
+
+    pub fn register(
+        &mut self,
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<u32> {
+        let id: Handle<JsNumber> = obj.get(cx, "id")?;
+        let id = id.value(cx) as u32;
+        if let Some(known) = self.by_js_id.get(&id) {
+            let known = known.to_inner(cx);
+            if obj.strict_equals(cx, known) {
+                return Ok(id);
+            }
+        }
+
+        let conditional_format = parse(cx, obj, format_map)?;
+        match self.formats.get(&id) {
+            Some(known) if !known.same_rule(&conditional_format) => {
+                let error = format!(
+                    "Conditional format id {} is used by two different conditional formats",
+                    id
+                );
+                cx.throw_error(error)
+            }
+            Some(_) => Ok(id),
+            None => {
+                self.formats.insert(id, conditional_format);
+                let root = obj.root(cx);
+                self.by_js_id.insert(id, root);
+                Ok(id)
+            }
+        }
+    }
+
+    pub fn get(&self, id: &u32) -> Option<&NodeXlsxConditionalFormatType> {
+        self.formats.get(id)
+    }
+
+    /// Drops the references to the JS objects once parsing is done.
+    pub fn release(&mut self, cx: &mut FunctionContext) {
+        for (_, root) in self.by_js_id.drain() {
+            root.drop(cx);
+        }
+    }
+}
+
+/// Reads a conditional format object of any type.
+fn parse(
+    cx: &mut FunctionContext,
+    format: Handle<JsObject>,
+    format_map: &mut FormatRegistry,
+) -> NeonResult<NodeXlsxConditionalFormatType> {
+    let f_type: Handle<JsString> = format.get(cx, "type")?;
+    let f_type = f_type.value(cx);
+
+    let mut parsed = HashMap::new();
+    let id = match f_type.as_str() {
+        "twoColorScale" => TwoColorScale::create_and_set_to_map(cx, format, &mut parsed)?,
+        "threeColorScale" => ThreeColorScale::create_and_set_to_map(cx, format, &mut parsed)?,
+        "average" => Average::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "blank" => Blank::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "cell" => Cell::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "dataBar" => DataBar::create_and_set_to_map(cx, format, &mut parsed)?,
+        "date" => Date::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "duplicate" => Duplicate::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "error" => Error::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "formula" => FormulaFormat::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "iconSet" => Icon::create_and_set_to_map(cx, format, &mut parsed)?,
+        "text" => Text::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        "top" => Top::create_and_set_to_map(cx, format, &mut parsed, format_map)?,
+        _ => {
+            let err = format!("Invalid ConditionalFormatType: {}", f_type);
+            return cx.throw_error(err);
+        }
+    };
+    Ok(parsed.remove(&id).unwrap())
+}
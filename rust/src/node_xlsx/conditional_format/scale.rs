@@ -5,7 +5,7 @@ use neon::{
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsBoolean, JsNumber, JsObject, JsString},
+    types::{JsBoolean, JsNumber, JsObject, JsString, JsValue},
 };
 use rust_xlsxwriter::{Color, ConditionalFormat2ColorScale, ConditionalFormat3ColorScale};
 
@@ -30,20 +30,20 @@ impl TwoColorScale {
         let stop_if_true: Option<Handle<JsBoolean>> = obj.get_opt(cx, "stopIfTrue")?;
         let stop_if_true = stop_if_true.map(|stop| stop.value(cx));
 
-        let min_color: Option<Handle<JsObject>> = obj.get_opt(cx, "minColor")?;
+        let min_color: Option<Handle<JsValue>> = obj.get_opt(cx, "minColor")?;
         let min_color = match min_color {
             Some(color) => {
-                let color = NodeColor::from_js_object(cx, color)?;
+                let color = NodeColor::from_js_value(cx, color)?;
                 let color = color.into();
                 Some(color)
             }
             None => None,
         };
 
-        let max_color: Option<Handle<JsObject>> = obj.get_opt(cx, "maxColor")?;
+        let max_color: Option<Handle<JsValue>> = obj.get_opt(cx, "maxColor")?;
         let max_color = match max_color {
             Some(color) => {
-                let color = NodeColor::from_js_object(cx, color)?;
+                let color = NodeColor::from_js_value(cx, color)?;
                 let color = color.into();
                 Some(color)
             }
@@ -125,10 +125,10 @@ pub struct ThreeColorScale {
 
 impl ThreeColorScale {
     pub fn from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
-        let mid_color: Option<Handle<JsObject>> = obj.get_opt(cx, "midColor")?;
+        let mid_color: Option<Handle<JsValue>> = obj.get_opt(cx, "midColor")?;
         let mid_color = match mid_color {
             Some(color) => {
-                let color = NodeColor::from_js_object(cx, color)?;
+                let color = NodeColor::from_js_value(cx, color)?;
                 let color = color.into();
                 Some(color)
             }
@@ -5,11 +5,11 @@ use neon::{
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsNumber, JsObject, JsString},
+    types::{JsBoolean, JsNumber, JsObject, JsString, JsValue},
 };
 use rust_xlsxwriter::{ConditionalFormatText, ConditionalFormatTextRule, Format};
 
-use crate::node_xlsx::format::NodeXlsxFormat;
+use crate::node_xlsx::{format_registry::FormatRegistry, util::create_format};
 
 use super::c_type::NodeXlsxConditionalFormatType;
 
@@ -24,23 +24,18 @@ impl<'a> Text<'a> {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
     ) -> NeonResult<Self> {
         let rule: Handle<JsObject> = obj.get(cx, "rule")?;
         let rule = js_object_to_text_rule(cx, rule)?;
         let multi_range: Option<Handle<JsString>> = obj.get_opt(cx, "multiRange")?;
         let multi_range = multi_range.map(|range| range.value(cx));
-        let stop_if_true: Option<Handle<JsString>> = obj.get_opt(cx, "stopIfTrue")?;
-        let stop_if_true = stop_if_true.map(|stop| stop.value(cx) == "true");
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let stop_if_true: Option<Handle<JsBoolean>> = obj.get_opt(cx, "stopIfTrue")?;
+        let stop_if_true = stop_if_true.map(|stop| stop.value(cx));
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
         let format = match format {
             Some(format) => {
-                let id: Handle<JsNumber> = format.get(cx, "id")?;
-                let id = id.value(cx) as u32;
-                if !format_map.contains_key(&id) {
-                    let format = NodeXlsxFormat::from_js_object(cx, format)?;
-                    format_map.insert(id, format.into());
-                }
+                let id = create_format(cx, format, format_map)?;
                 let format = format_map.get(&id).unwrap();
                 Some(format)
             }
@@ -58,7 +53,7 @@ impl<'a> Text<'a> {
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
         c_format_map: &mut HashMap<u32, NodeXlsxConditionalFormatType>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
     ) -> NeonResult<u32> {
         let id: Handle<JsNumber> = obj.get(cx, "id")?;
         let id = id.value(cx) as u32;
@@ -0,0 +1,166 @@
+use super::error::NodeXlsxError;
+
+/// The text encodings CSV files are read in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl Encoding {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().replace(['-', '_'], "").as_str() {
+            "utf8" => Ok(Self::Utf8),
+            "utf16le" | "ucs2" => Ok(Self::Utf16Le),
+            "utf16be" => Ok(Self::Utf16Be),
+            "latin1" | "iso88591" | "binary" => Ok(Self::Latin1),
+            _ => Err(format!("Unknown encoding \"{}\"", name)),
+        }
+    }
+
+    /// Decodes text, dropping a byte order mark. A UTF-16 byte order mark
+    /// wins over the encoding.
+    pub fn decode(self, bytes: &[u8]) -> Result<String, NodeXlsxError> {
+        let encoding = match bytes {
+            [0xFF, 0xFE, ..] => Self::Utf16Le,
+            [0xFE, 0xFF, ..] => Self::Utf16Be,
+            _ => self,
+        };
+        match encoding {
+            Self::Utf8 => {
+                let bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+                String::from_utf8(bytes.to_vec())
+                    .map_err(|error| NodeXlsxError::new(format!("Invalid UTF-8 text: {}", error)))
+            }
+            Self::Utf16Le | Self::Utf16Be => {
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| match encoding {
+                        Self::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+                        _ => u16::from_be_bytes([pair[0], pair[1]]),
+                    })
+                    .collect();
+                let units = units.strip_prefix(&[0xFEFF]).unwrap_or(&units);
+                String::from_utf16(units)
+                    .map_err(|error| NodeXlsxError::new(format!("Invalid UTF-16 text: {}", error)))
+            }
+            Self::Latin1 => Ok(bytes.iter().map(|byte| *byte as char).collect()),
+        }
+    }
+}
+
+/// Splits delimited text into records of fields. Fields may be quoted, with
+/// the quote doubled inside them, and hold delimiters and line breaks. Lines
+/// end with `\n`, `\r\n` or `\r`; the last one may have no line break.
+pub fn parse_records(text: &str, delimiter: char, quote: Option<char>) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    // Whether the current record has anything, so a last line break ends the text
+    let mut started = false;
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            if Some(c) == quote {
+                if chars.peek().copied() == quote {
+                    field.push(c);
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            } else {
+                field.push(c);
+            }
+            continue;
+        }
+        started = true;
+        match c {
+            c if Some(c) == quote && field.is_empty() => quoted = true,
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                started = false;
+            }
+            c => field.push(c),
+        }
+    }
+    if started || quoted {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// When fields are quoted as they are written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quoting {
+    /// Only fields with a delimiter, a quote or a line break, or with
+    /// spaces around them
+    Minimal,
+    All,
+    /// Every field that isn't a number, and the numbers that need it
+    NonNumeric,
+    /// Fields are never quoted, and may break the record
+    None,
+}
+
+impl Quoting {
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "minimal" => Ok(Self::Minimal),
+            "all" => Ok(Self::All),
+            "nonnumeric" => Ok(Self::NonNumeric),
+            "none" => Ok(Self::None),
+            _ => Err(format!("Unknown CSV quoting \"{}\"", name)),
+        }
+    }
+}
+
+/// Writes a field of a record, quoted as `quoting` asks, with the quote
+/// doubled inside it.
+pub fn write_field(
+    out: &mut String,
+    field: &str,
+    numeric: bool,
+    delimiter: char,
+    quote: char,
+    quoting: Quoting,
+) {
+    let needed = field.contains([delimiter, quote, '\r', '\n'])
+        || field.starts_with(' ')
+        || field.ends_with(' ');
+    let quoted = match quoting {
+        Quoting::All => true,
+        Quoting::NonNumeric => !numeric || needed,
+        Quoting::None => false,
+        Quoting::Minimal => needed,
+    };
+    if !quoted {
+        out.push_str(field);
+        return;
+    }
+    out.push(quote);
+    for c in field.chars() {
+        if c == quote {
+            out.push(quote);
+        }
+        out.push(c);
+    }
+    out.push(quote);
+}
+
+pub fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
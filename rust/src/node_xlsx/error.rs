@@ -1,6 +1,7 @@
 use std::num::{ParseFloatError, ParseIntError};
 
 use rust_xlsxwriter::XlsxError;
+use zip::result::ZipError;
 
 pub struct NodeXlsxError {
     message: String,
@@ -43,3 +44,15 @@ impl From<ParseFloatError> for NodeXlsxError {
         Self::new(error.to_string())
     }
 }
+
+impl From<ZipError> for NodeXlsxError {
+    fn from(error: ZipError) -> Self {
+        Self::new(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for NodeXlsxError {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(error.to_string())
+    }
+}
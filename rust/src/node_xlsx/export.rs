@@ -0,0 +1,364 @@
+use chrono::Timelike;
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsBoolean, JsNumber, JsObject, JsString},
+};
+use rust_xlsxwriter::utility::column_number_to_name;
+
+use super::{
+    csv::{single_char, write_field, Quoting},
+    error::NodeXlsxError,
+    format_registry::FormatRegistry,
+    formula::{
+        result::FormulaResult,
+        value::{date_to_serial, number_to_string},
+    },
+    number_format::{format_number, format_text, general},
+    sheet::{evaluate_formulas, GridCell, GridValue, SheetGrid},
+    NodeXlsxWorkbook,
+};
+
+/// The kind of text a sheet is exported to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportFormat {
+    Csv,
+    Tsv,
+    Json,
+}
+
+/// What is written for merged cells other than the first one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MergedCells {
+    /// Nothing, like Excel does
+    First,
+    /// The value of the first cell
+    Repeat,
+}
+
+/// A cell as it is exported.
+pub enum ExportValue {
+    Empty,
+    Text(String),
+    /// A number and the text it is written as
+    Number(f64, String),
+    Boolean(bool),
+}
+
+/// One sheet of a workbook written as delimited text or JSON, row by row.
+/// Values are written as Excel shows them, through their number formats,
+/// or as they are stored.
+pub struct NodeXlsxSheetExport {
+    workbook: NodeXlsxWorkbook,
+    sheet: usize,
+    format: ExportFormat,
+    delimiter: char,
+    quote: char,
+    quoting: Quoting,
+    line_ending: String,
+    formatted: bool,
+    formulas: bool,
+    merged_cells: MergedCells,
+    bom: bool,
+    header: bool,
+}
+
+impl NodeXlsxSheetExport {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        workbook: Handle<JsObject>,
+        obj: Handle<JsObject>,
+    ) -> NeonResult<Self> {
+        let mut workbook = NodeXlsxWorkbook::from_js_object(cx, workbook)?;
+
+        let sheet = sheet_index(cx, obj, &workbook)?;
+
+        let format = match string_option(cx, obj, "format")?.as_deref() {
+            None | Some("csv") => ExportFormat::Csv,
+            Some("tsv") => ExportFormat::Tsv,
+            Some("json") => ExportFormat::Json,
+            Some(format) => return cx.throw_error(format!("Unknown export format \"{}\"", format)),
+        };
+        let delimiter = match string_option(cx, obj, "delimiter")? {
+            None if format == ExportFormat::Tsv => '\t',
+            None => ',',
+            Some(delimiter) => match single_char(&delimiter) {
+                Some(delimiter) => delimiter,
+                None => return cx.throw_error("The CSV delimiter must be one character"),
+            },
+        };
+        let quote = match string_option(cx, obj, "quote")? {
+            None => '"',
+            Some(quote) => match single_char(&quote) {
+                Some(quote) if quote != delimiter => quote,
+                _ => return cx.throw_error("The CSV quote must be one character"),
+            },
+        };
+        let quoting = match string_option(cx, obj, "quoting")? {
+            Some(quoting) => Quoting::from_name(&quoting).or_else(|error| cx.throw_error(error))?,
+            None => Quoting::Minimal,
+        };
+        let line_ending =
+            string_option(cx, obj, "lineEnding")?.unwrap_or_else(|| "\r\n".to_string());
+        let formatted = match string_option(cx, obj, "values")?.as_deref() {
+            None | Some("formatted") => true,
+            Some("raw") => false,
+            Some(values) => return cx.throw_error(format!("Unknown export values \"{}\"", values)),
+        };
+        let formulas = match string_option(cx, obj, "formulas")?.as_deref() {
+            None | Some("results") => false,
+            Some("formulas") => true,
+            Some(formulas) => {
+                return cx.throw_error(format!("Unknown export formulas \"{}\"", formulas))
+            }
+        };
+        let merged_cells = match string_option(cx, obj, "mergedCells")?.as_deref() {
+            None | Some("first") => MergedCells::First,
+            Some("repeat") => MergedCells::Repeat,
+            Some(merged) => {
+                return cx.throw_error(format!("Unknown export merged cells \"{}\"", merged))
+            }
+        };
+
+        let bom: Option<Handle<JsBoolean>> = obj.get_opt(cx, "bom")?;
+        let bom = bom.map(|bom| bom.value(cx)).unwrap_or(false);
+        let header: Option<Handle<JsBoolean>> = obj.get_opt(cx, "header")?;
+        let header = header.map(|header| header.value(cx)).unwrap_or(false);
+
+        // Formulas are shown with their results, computed where they are missing
+        if !formulas {
+            evaluate_formulas(&mut workbook.sheets);
+        }
+
+        Ok(Self {
+            workbook,
+            sheet,
+            format,
+            delimiter,
+            quote,
+            quoting,
+            line_ending,
+            formatted,
+            formulas,
+            merged_cells,
+            bom,
+            header,
+        })
+    }
+
+    pub fn to_string(&self) -> Result<String, NodeXlsxError> {
+        let grid = self.workbook.sheets[self.sheet].grid()?;
+        let rows = self.rows(&grid);
+        let mut out = String::new();
+        if self.bom {
+            out.push('\u{FEFF}');
+        }
+        match self.format {
+            ExportFormat::Csv | ExportFormat::Tsv => self.write_delimited(&mut out, &rows),
+            ExportFormat::Json => self.write_json(&mut out, &rows),
+        }
+        Ok(out)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> Result<(), NodeXlsxError> {
+        std::fs::write(path, self.to_string()?)?;
+        Ok(())
+    }
+
+    /// The values of the cells from `A1` to the last cell, by row.
+    fn rows(&self, grid: &SheetGrid) -> Vec<Vec<ExportValue>> {
+        let Some((last_row, last_col)) = grid.last_cell() else {
+            return vec![];
+        };
+        (0..=last_row)
+            .map(|row| {
+                (0..=last_col)
+                    .map(|col| {
+                        let position = match (self.merged_cells, grid.merge_at(row, col)) {
+                            (MergedCells::Repeat, Some(range)) => {
+                                (range.start_row, range.start_col as u16)
+                            }
+                            _ => (row, col),
+                        };
+                        match grid.cells.get(&position) {
+                            Some(cell) => export_value(
+                                cell,
+                                &self.workbook.format_map,
+                                self.formatted,
+                                self.formulas,
+                            ),
+                            None => ExportValue::Empty,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn write_delimited(&self, out: &mut String, rows: &[Vec<ExportValue>]) {
+        for row in rows {
+            for (index, value) in row.iter().enumerate() {
+                if index > 0 {
+                    out.push(self.delimiter);
+                }
+                let (field, numeric) = match value {
+                    ExportValue::Empty => ("", false),
+                    ExportValue::Text(text) => (text.as_str(), false),
+                    ExportValue::Number(_, text) => (text.as_str(), true),
+                    ExportValue::Boolean(true) => ("TRUE", false),
+                    ExportValue::Boolean(false) => ("FALSE", false),
+                };
+                // Empty fields are never quoted, so they read back as empty cells
+                if !field.is_empty() {
+                    write_field(
+                        out,
+                        field,
+                        numeric,
+                        self.delimiter,
+                        self.quote,
+                        self.quoting,
+                    );
+                }
+            }
+            out.push_str(&self.line_ending);
+        }
+    }
+
+    /// Writes an array of rows, as arrays of values or as objects keyed by
+    /// the first row when `header` is set. Empty cells are `null`.
+    fn write_json(&self, out: &mut String, rows: &[Vec<ExportValue>]) {
+        let (keys, rows) = match (self.header, rows.split_first()) {
+            (true, Some((header, rows))) => {
+                let keys: Vec<String> = header
+                    .iter()
+                    .enumerate()
+                    .map(|(col, value)| match value {
+                        ExportValue::Empty => column_number_to_name(col as u16),
+                        ExportValue::Text(text) | ExportValue::Number(_, text) => text.clone(),
+                        ExportValue::Boolean(value) => value.to_string().to_uppercase(),
+                    })
+                    .collect();
+                (Some(keys), rows)
+            }
+            _ => (None, rows),
+        };
+        out.push('[');
+        for (index, row) in rows.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            out.push(if keys.is_some() { '{' } else { '[' });
+            for (col, value) in row.iter().enumerate() {
+                if col > 0 {
+                    out.push(',');
+                }
+                if let Some(keys) = &keys {
+                    write_json_string(out, &keys[col]);
+                    out.push(':');
+                }
+                match value {
+                    ExportValue::Empty => out.push_str("null"),
+                    ExportValue::Text(text) => write_json_string(out, text),
+                    ExportValue::Number(_, text) if self.formatted => write_json_string(out, text),
+                    ExportValue::Number(number, _) => out.push_str(&number_to_string(*number)),
+                    ExportValue::Boolean(value) => out.push_str(&value.to_string()),
+                }
+            }
+            out.push(if keys.is_some() { '}' } else { ']' });
+        }
+        out.push(']');
+    }
+}
+
+/// The index of the sheet in the `sheet` option, the first one by default.
+pub fn sheet_index(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+    workbook: &NodeXlsxWorkbook,
+) -> NeonResult<usize> {
+    let sheet: Option<Handle<JsNumber>> = obj.get_opt(cx, "sheet")?;
+    let sheet = sheet.map(|sheet| sheet.value(cx)).unwrap_or(0.0);
+    if sheet < 0.0 || sheet.fract() != 0.0 || sheet as usize >= workbook.sheets.len() {
+        return cx.throw_error(format!("There is no sheet at index {}", sheet));
+    }
+    Ok(sheet as usize)
+}
+
+fn string_option(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Option<String>> {
+    let value: Option<Handle<JsString>> = obj.get_opt(cx, key)?;
+    Ok(value.map(|value| value.value(cx)))
+}
+
+fn write_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// A cell as Excel shows it through its number format when `formatted` is
+/// set, or as it is stored. Formulas are their result, or their text when
+/// `formulas` is set.
+pub fn export_value(
+    cell: &GridCell,
+    format_map: &FormatRegistry,
+    formatted: bool,
+    formulas: bool,
+) -> ExportValue {
+    let Some(value) = &cell.value else {
+        return ExportValue::Empty;
+    };
+    let number_format = cell
+        .format
+        .and_then(|id| format_map.source(&id))
+        .and_then(|format| format.number_format())
+        .filter(|_| formatted);
+    let number = |number: f64| {
+        let text = match number_format {
+            Some(code) => format_number(code, number),
+            None if formatted => general(number),
+            None => number_to_string(number),
+        };
+        ExportValue::Number(number, text)
+    };
+    let text = |text: &str| match number_format {
+        Some(code) => ExportValue::Text(format_text(code, text)),
+        None => ExportValue::Text(text.to_string()),
+    };
+    match value {
+        GridValue::Text(value) => text(value),
+        GridValue::Number(value) => number(*value),
+        GridValue::Boolean(value) => ExportValue::Boolean(*value),
+        GridValue::Link { text: value, .. } => text(value),
+        GridValue::Date(date) => match number_format {
+            Some(code) => ExportValue::Text(format_number(code, date_to_serial(*date))),
+            None if date.num_seconds_from_midnight() == 0 && date.nanosecond() == 0 => {
+                ExportValue::Text(date.format("%Y-%m-%d").to_string())
+            }
+            None => ExportValue::Text(date.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+        },
+        GridValue::Formula { formula, .. } if formulas => ExportValue::Text(formula.clone()),
+        GridValue::Formula { result, .. } => match result {
+            None => ExportValue::Empty,
+            Some(FormulaResult::Number(value)) => number(*value),
+            Some(FormulaResult::Text(value)) => text(value),
+            Some(FormulaResult::Boolean(value)) => ExportValue::Boolean(*value),
+            Some(FormulaResult::Error(error)) => ExportValue::Text(error.to_string()),
+        },
+    }
+}
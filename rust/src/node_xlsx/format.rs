@@ -3,49 +3,98 @@ use neon::{
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsBoolean, JsNumber, JsObject, JsString},
+    types::{JsBoolean, JsNumber, JsObject, JsString, JsValue, Value},
 };
-use rust_xlsxwriter::{FormatAlign, FormatPattern, FormatUnderline};
+use rust_xlsxwriter::{FormatAlign, FormatPattern, FormatScript, FormatUnderline};
 
 use super::{
     border::{Border, DiagonalBorder},
     color::Color,
+    number_format::builtin_code,
 };
 
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
 pub struct NodeXlsxFormat {
-    align: Option<FormatAlign>,
-    background_color: Option<Color>,
-    bold: Option<bool>,
-    left_border: Option<Border>,
-    right_border: Option<Border>,
-    top_border: Option<Border>,
-    bottom_border: Option<Border>,
-    diagonal_border: Option<DiagonalBorder>,
-    charset: Option<u8>,
-    font_color: Option<Color>,
-    font_family: Option<u8>,
-    font_name: Option<String>,
-    font_scheme: Option<String>,
-    font_size: Option<u32>,
-    strike_through: Option<bool>,
-    foreground_color: Option<Color>,
-    hidden: Option<bool>,
-    hyperlink: Option<bool>,
-    indent: Option<u8>,
-    italic: Option<bool>,
-    locked: Option<bool>,
-    num_fmt: Option<String>,
-    num_fmt_id: Option<u8>,
-    pattern: Option<FormatPattern>,
-    underline: Option<FormatUnderline>,
+    pub align: Option<FormatAlign>,
+    pub vertical_align: Option<FormatAlign>,
+    pub background_color: Option<Color>,
+    pub bold: Option<bool>,
+    pub left_border: Option<Border>,
+    pub right_border: Option<Border>,
+    pub top_border: Option<Border>,
+    pub bottom_border: Option<Border>,
+    pub diagonal_border: Option<DiagonalBorder>,
+    pub charset: Option<u8>,
+    pub font_color: Option<Color>,
+    pub font_family: Option<u8>,
+    pub font_name: Option<String>,
+    pub font_scheme: Option<String>,
+    pub font_size: Option<u32>,
+    pub strike_through: Option<bool>,
+    pub foreground_color: Option<Color>,
+    pub hidden: Option<bool>,
+    pub hyperlink: Option<bool>,
+    pub indent: Option<u8>,
+    pub italic: Option<bool>,
+    pub locked: Option<bool>,
+    pub num_fmt: Option<String>,
+    pub num_fmt_id: Option<u8>,
+    pub pattern: Option<FormatPattern>,
+    pub underline: Option<FormatUnderline>,
+    pub text_wrap: Option<bool>,
+    pub rotation: Option<i16>,
+    pub shrink: Option<bool>,
+    pub font_script: Option<FormatScript>,
+    pub reading_direction: Option<u8>,
+    pub quote_prefix: Option<bool>,
+    pub theme_font: Option<ThemeFont>,
+}
+
+/// The sides of an outer box a cell touches.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BoxEdges {
+    pub top: bool,
+    pub bottom: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl BoxEdges {
+    pub fn is_empty(&self) -> bool {
+        !(self.top || self.bottom || self.left || self.right)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeFont {
+    Major,
+    Minor,
+}
+
+impl ThemeFont {
+    // Font names of the default theme written by rust_xlsxwriter
+    pub fn font_name(&self) -> &'static str {
+        match self {
+            ThemeFont::Major => "Cambria",
+            ThemeFont::Minor => "Calibri",
+        }
+    }
+
+    fn scheme(&self) -> &'static str {
+        match self {
+            ThemeFont::Major => "major",
+            ThemeFont::Minor => "minor",
+        }
+    }
 }
 
 impl NodeXlsxFormat {
     pub fn from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
-        let result = Self::inner_from_object(cx, &obj);
+        let result = cx.try_catch(|cx| Self::inner_from_object(cx, &obj));
         match result {
             Ok(format) => Ok(format),
             Err(error) => {
+                let error = error.to_string(cx)?.value(cx);
                 let error = format!("Error parsing Format: {:?} with error:\n  {}", obj, error);
                 let js_string = cx.string(error);
                 cx.throw(js_string)
@@ -53,6 +102,87 @@ impl NodeXlsxFormat {
         }
     }
 
+    /// Returns this format layered over `base`: properties set here win,
+    /// the rest are taken from `base`.
+    pub fn layer_over(&self, base: &Self) -> Self {
+        Self {
+            align: self.align.or(base.align),
+            vertical_align: self.vertical_align.or(base.vertical_align),
+            background_color: self.background_color.or(base.background_color),
+            bold: self.bold.or(base.bold),
+            left_border: self.left_border.or(base.left_border),
+            right_border: self.right_border.or(base.right_border),
+            top_border: self.top_border.or(base.top_border),
+            bottom_border: self.bottom_border.or(base.bottom_border),
+            diagonal_border: self.diagonal_border.or(base.diagonal_border),
+            charset: self.charset.or(base.charset),
+            font_color: self.font_color.or(base.font_color),
+            font_family: self.font_family.or(base.font_family),
+            font_name: self.font_name.clone().or_else(|| base.font_name.clone()),
+            font_scheme: self
+                .font_scheme
+                .clone()
+                .or_else(|| base.font_scheme.clone()),
+            font_size: self.font_size.or(base.font_size),
+            strike_through: self.strike_through.or(base.strike_through),
+            foreground_color: self.foreground_color.or(base.foreground_color),
+            hidden: self.hidden.or(base.hidden),
+            hyperlink: self.hyperlink.or(base.hyperlink),
+            indent: self.indent.or(base.indent),
+            italic: self.italic.or(base.italic),
+            locked: self.locked.or(base.locked),
+            num_fmt: self.num_fmt.clone().or_else(|| base.num_fmt.clone()),
+            num_fmt_id: self.num_fmt_id.or(base.num_fmt_id),
+            pattern: self.pattern.or(base.pattern),
+            underline: self.underline.or(base.underline),
+            text_wrap: self.text_wrap.or(base.text_wrap),
+            rotation: self.rotation.or(base.rotation),
+            shrink: self.shrink.or(base.shrink),
+            font_script: self.font_script.or(base.font_script),
+            reading_direction: self.reading_direction.or(base.reading_direction),
+            quote_prefix: self.quote_prefix.or(base.quote_prefix),
+            theme_font: self.theme_font.or(base.theme_font),
+        }
+    }
+
+    /// The number format code, given or built in.
+    pub fn number_format(&self) -> Option<&str> {
+        match &self.num_fmt {
+            Some(num_fmt) => Some(num_fmt),
+            None => self.num_fmt_id.and_then(builtin_code),
+        }
+    }
+
+    /// The color the cell is filled with. A solid fill shows its foreground
+    /// color, the other patterns are approximated with their background.
+    pub fn fill_rgb(&self) -> Option<u32> {
+        let fill = match self.pattern {
+            Some(FormatPattern::None) => None,
+            None | Some(FormatPattern::Solid) => self.foreground_color.or(self.background_color),
+            Some(_) => self.background_color.or(self.foreground_color),
+        };
+        fill.as_ref().and_then(Color::rgb)
+    }
+
+    /// Returns a copy with `border` on the sides of an outer box this format
+    /// sits on.
+    pub fn with_box_border(&self, border: Border, edges: BoxEdges) -> Self {
+        let mut format = self.clone();
+        if edges.top {
+            format.top_border = Some(border);
+        }
+        if edges.bottom {
+            format.bottom_border = Some(border);
+        }
+        if edges.left {
+            format.left_border = Some(border);
+        }
+        if edges.right {
+            format.right_border = Some(border);
+        }
+        format
+    }
+
     fn inner_from_object(cx: &mut FunctionContext, object: &Handle<JsObject>) -> NeonResult<Self> {
         let align: Option<Handle<JsString>> = object.get_opt(cx, "align")?;
         let align: Option<FormatAlign> = match align {
@@ -60,9 +190,15 @@ impl NodeXlsxFormat {
             None => None,
         };
 
-        let background_color: Option<Handle<JsObject>> = object.get_opt(cx, "backgroundColor")?;
+        let vertical_align: Option<Handle<JsString>> = object.get_opt(cx, "verticalAlign")?;
+        let vertical_align: Option<FormatAlign> = match vertical_align {
+            Some(vertical_align) => Some(vertical_align_from_js_string(cx, vertical_align)?),
+            None => None,
+        };
+
+        let background_color: Option<Handle<JsValue>> = object.get_opt(cx, "backgroundColor")?;
         let background_color: Option<Color> = match background_color {
-            Some(background_color) => Some(Color::from_js_object(cx, background_color)?),
+            Some(background_color) => Some(Color::from_js_value(cx, background_color)?),
             None => None,
         };
 
@@ -108,9 +244,9 @@ impl NodeXlsxFormat {
             None => None,
         };
 
-        let font_color: Option<Handle<JsObject>> = object.get_opt(cx, "fontColor")?;
+        let font_color: Option<Handle<JsValue>> = object.get_opt(cx, "fontColor")?;
         let font_color = match font_color {
-            Some(font_color) => Some(Color::from_js_object(cx, font_color)?),
+            Some(font_color) => Some(Color::from_js_value(cx, font_color)?),
             None => None,
         };
 
@@ -144,9 +280,9 @@ impl NodeXlsxFormat {
             None => None,
         };
 
-        let foreground_color: Option<Handle<JsObject>> = object.get_opt(cx, "foregroundColor")?;
+        let foreground_color: Option<Handle<JsValue>> = object.get_opt(cx, "foregroundColor")?;
         let foreground_color = match foreground_color {
-            Some(foreground_color) => Some(Color::from_js_object(cx, foreground_color)?),
+            Some(foreground_color) => Some(Color::from_js_value(cx, foreground_color)?),
             None => None,
         };
 
@@ -204,8 +340,56 @@ impl NodeXlsxFormat {
             None => None,
         };
 
+        let text_wrap: Option<Handle<JsBoolean>> = object.get_opt(cx, "textWrap")?;
+        let text_wrap = text_wrap.map(|text_wrap| text_wrap.value(cx));
+
+        let rotation: Option<Handle<JsNumber>> = object.get_opt(cx, "rotation")?;
+        let rotation = match rotation {
+            Some(rotation) => {
+                let rotation = rotation.value(cx);
+                // 270 is Excel's value for vertically stacked text
+                if !(-90.0..=90.0).contains(&rotation) && rotation != 270.0 {
+                    let error = format!(
+                        "Rotation must be between -90 and 90, or 270 for stacked text: {}",
+                        rotation
+                    );
+                    let js_string = cx.string(&error);
+                    return cx.throw(js_string);
+                }
+                Some(rotation as i16)
+            }
+            None => None,
+        };
+
+        let shrink: Option<Handle<JsBoolean>> = object.get_opt(cx, "shrink")?;
+        let shrink = shrink.map(|shrink| shrink.value(cx));
+
+        let font_script: Option<Handle<JsString>> = object.get_opt(cx, "fontScript")?;
+        let font_script = match font_script {
+            Some(font_script) => Some(script_from_js_string(cx, font_script)?),
+            None => None,
+        };
+
+        let reading_direction: Option<Handle<JsString>> = object.get_opt(cx, "readingDirection")?;
+        let reading_direction = match reading_direction {
+            Some(reading_direction) => {
+                Some(reading_direction_from_js_string(cx, reading_direction)?)
+            }
+            None => None,
+        };
+
+        let quote_prefix: Option<Handle<JsBoolean>> = object.get_opt(cx, "quotePrefix")?;
+        let quote_prefix = quote_prefix.map(|quote_prefix| quote_prefix.value(cx));
+
+        let theme_font: Option<Handle<JsString>> = object.get_opt(cx, "themeFont")?;
+        let theme_font = match theme_font {
+            Some(theme_font) => Some(theme_font_from_js_string(cx, theme_font)?),
+            None => None,
+        };
+
         Ok(Self {
             align,
+            vertical_align,
             background_color,
             bold,
             left_border,
@@ -230,6 +414,13 @@ impl NodeXlsxFormat {
             num_fmt_id,
             pattern,
             underline,
+            text_wrap,
+            rotation,
+            shrink,
+            font_script,
+            reading_direction,
+            quote_prefix,
+            theme_font,
         })
     }
 }
@@ -242,6 +433,10 @@ impl Into<rust_xlsxwriter::Format> for NodeXlsxFormat {
             format = format.set_align(align);
         }
 
+        if let Some(vertical_align) = self.vertical_align {
+            format = format.set_align(vertical_align);
+        }
+
         if let Some(background_color) = self.background_color {
             let color: rust_xlsxwriter::Color = background_color.into();
             format = format.set_background_color(color);
@@ -302,6 +497,12 @@ impl Into<rust_xlsxwriter::Format> for NodeXlsxFormat {
             format = format.set_font_family(font_family);
         }
 
+        if let Some(theme_font) = self.theme_font {
+            format = format
+                .set_font_name(theme_font.font_name())
+                .set_font_scheme(theme_font.scheme());
+        }
+
         if let Some(font_name) = self.font_name {
             format = format.set_font_name(&font_name);
         }
@@ -369,6 +570,30 @@ impl Into<rust_xlsxwriter::Format> for NodeXlsxFormat {
             format = format.set_underline(underline);
         }
 
+        if let Some(true) = self.text_wrap {
+            format = format.set_text_wrap();
+        }
+
+        if let Some(rotation) = self.rotation {
+            format = format.set_rotation(rotation);
+        }
+
+        if let Some(true) = self.shrink {
+            format = format.set_shrink();
+        }
+
+        if let Some(font_script) = self.font_script {
+            format = format.set_font_script(font_script);
+        }
+
+        if let Some(reading_direction) = self.reading_direction {
+            format = format.set_reading_direction(reading_direction);
+        }
+
+        if let Some(true) = self.quote_prefix {
+            format = format.set_quote_prefix();
+        }
+
         return format;
     }
 }
@@ -400,6 +625,75 @@ fn align_from_js_string(
     }
 }
 
+fn vertical_align_from_js_string(
+    cx: &mut FunctionContext,
+    js_string: Handle<JsString>,
+) -> NeonResult<FormatAlign> {
+    let js_string = js_string.value(cx);
+    match js_string.as_str() {
+        "top" => Ok(FormatAlign::Top),
+        "bottom" => Ok(FormatAlign::Bottom),
+        "center" => Ok(FormatAlign::VerticalCenter),
+        "justify" => Ok(FormatAlign::VerticalJustify),
+        "distributed" => Ok(FormatAlign::VerticalDistributed),
+        _ => {
+            let error = format!("Unknown vertical align type: {}", js_string);
+            let js_string = cx.string(&error);
+            cx.throw(js_string)
+        }
+    }
+}
+
+fn script_from_js_string(
+    cx: &mut FunctionContext,
+    js_string: Handle<JsString>,
+) -> NeonResult<FormatScript> {
+    let js_string = js_string.value(cx);
+    match js_string.as_str() {
+        "none" => Ok(FormatScript::None),
+        "superscript" => Ok(FormatScript::Superscript),
+        "subscript" => Ok(FormatScript::Subscript),
+        _ => {
+            let error = format!("Unknown font script type: {}", js_string);
+            let js_string = cx.string(&error);
+            cx.throw(js_string)
+        }
+    }
+}
+
+fn reading_direction_from_js_string(
+    cx: &mut FunctionContext,
+    js_string: Handle<JsString>,
+) -> NeonResult<u8> {
+    let js_string = js_string.value(cx);
+    match js_string.as_str() {
+        "context" => Ok(0),
+        "leftToRight" => Ok(1),
+        "rightToLeft" => Ok(2),
+        _ => {
+            let error = format!("Unknown reading direction: {}", js_string);
+            let js_string = cx.string(&error);
+            cx.throw(js_string)
+        }
+    }
+}
+
+fn theme_font_from_js_string(
+    cx: &mut FunctionContext,
+    js_string: Handle<JsString>,
+) -> NeonResult<ThemeFont> {
+    let js_string = js_string.value(cx);
+    match js_string.as_str() {
+        "major" => Ok(ThemeFont::Major),
+        "minor" => Ok(ThemeFont::Minor),
+        _ => {
+            let error = format!("Unknown theme font: {}", js_string);
+            let js_string = cx.string(&error);
+            cx.throw(js_string)
+        }
+    }
+}
+
 fn pattern_from_js_string(
     cx: &mut FunctionContext,
     js_string: Handle<JsString>,
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use neon::{
+    context::{Context, FunctionContext},
+    handle::{Handle, Root},
+    object::Object,
+    result::NeonResult,
+    types::{JsNumber, JsObject, JsValue},
+};
+use rust_xlsxwriter::Format;
+
+use super::{
+    border::Border,
+    format::{BoxEdges, NodeXlsxFormat},
+};
+
+/// Formats interned by content.
+///
+/// The ids handed out by the registry index into `formats`, so two `Format`
+/// objects with the same properties share one id no matter which JS id they
+/// carry. The JS id is only used to skip parsing an object that was already
+/// seen, and to catch two different formats claiming the same id.
+///
+/// There is one registry per workbook. Formats declared on the workbook can
+/// also be referenced from any sheet by their JS id alone.
+#[derive(Default)]
+pub struct FormatRegistry {
+    formats: Vec<Format>,
+    sources: Vec<NodeXlsxFormat>,
+    composed: HashMap<Vec<u32>, u32>,
+    boxed: HashMap<(Option<u32>, Border, BoxEdges), u32>,
+    by_content: HashMap<NodeXlsxFormat, u32>,
+    by_js_id: HashMap<u32, (Root<JsObject>, u32)>,
+    declared: HashMap<u32, u32>,
+}
+
+impl FormatRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves a format given either as a `Format` object or as the id of a
+    /// format declared on the workbook.
+    pub fn resolve(&mut self, cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<u32> {
+        if let Ok(js_id) = value.downcast::<JsNumber, _>(cx) {
+            let js_id = js_id.value(cx) as u32;
+            return match self.declared.get(&js_id) {
+                Some(id) => Ok(*id),
+                None => {
+                    let error = format!("Format id {} is not declared on the workbook", js_id);
+                    cx.throw_error(error)
+                }
+            };
+        }
+
+        let obj = value.downcast_or_throw::<JsObject, _>(cx)?;
+        self.register(cx, obj)
+    }
+
+    /// Registers a workbook level format so sheets can refer to it by id.
+    pub fn declare(&mut self, cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<u32> {
+        let id = self.register(cx, obj)?;
+        let js_id: Handle<JsNumber> = obj.get(cx, "id")?;
+        let js_id = js_id.value(cx) as u32;
+        self.declared.insert(js_id, id);
+        Ok(id)
+    }
+
+    pub fn register(&mut self, cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<u32> {
+        let js_id: Option<Handle<JsNumber>> = obj.get_opt(cx, "id")?;
+        let js_id = js_id.map(|js_id| js_id.value(cx) as u32);
+
+        if let Some(js_id) = js_id {
+            if let Some((known, id)) = self.by_js_id.get(&js_id) {
+                let id = *id;
+                let known = known.to_inner(cx);
+                if obj.strict_equals(cx, known) {
+                    return Ok(id);
+                }
+
+                // Another object with the same id is fine as long as it has
+                // the same properties
+                let format = NodeXlsxFormat::from_js_object(cx, obj)?;
+                if self.by_content.get(&format) != Some(&id) {
+                    let error = format!("Format id {} is used by two different formats", js_id);
+                    return cx.throw_error(error);
+                }
+                return Ok(id);
+            }
+        }
+
+        let format = NodeXlsxFormat::from_js_object(cx, obj)?;
+        let id = self.intern(format);
+        if let Some(js_id) = js_id {
+            let root = obj.root(cx);
+            self.by_js_id.insert(js_id, (root, id));
+        }
+        Ok(id)
+    }
+
+    pub fn intern(&mut self, format: NodeXlsxFormat) -> u32 {
+        if let Some(id) = self.by_content.get(&format) {
+            return *id;
+        }
+        let id = self.formats.len() as u32;
+        self.by_content.insert(format.clone(), id);
+        self.sources.push(format.clone());
+        self.formats.push(format.into());
+        id
+    }
+
+    /// Layers the formats in `ids`, given from lowest to highest precedence,
+    /// into a single interned format.
+    pub fn compose(&mut self, ids: &[u32]) -> u32 {
+        if let [id] = ids {
+            return *id;
+        }
+        if let Some(id) = self.composed.get(ids) {
+            return *id;
+        }
+
+        let mut format = self.sources[ids[0] as usize].clone();
+        for id in &ids[1..] {
+            format = self.sources[*id as usize].layer_over(&format);
+        }
+        let id = self.intern(format);
+        self.composed.insert(ids.to_vec(), id);
+        id
+    }
+
+    /// The format `base` with `border` drawn on the given edges of an outer box.
+    pub fn with_box_border(&mut self, base: Option<u32>, border: Border, edges: BoxEdges) -> u32 {
+        let key = (base, border, edges);
+        if let Some(id) = self.boxed.get(&key) {
+            return *id;
+        }
+
+        let format = match base {
+            Some(base) => self.sources[base as usize].with_box_border(border, edges),
+            None => NodeXlsxFormat::default().with_box_border(border, edges),
+        };
+        let id = self.intern(format);
+        self.boxed.insert(key, id);
+        id
+    }
+
+    pub fn get(&self, id: &u32) -> Option<&Format> {
+        self.formats.get(*id as usize)
+    }
+
+    /// The properties of a format, as given.
+    pub fn source(&self, id: &u32) -> Option<&NodeXlsxFormat> {
+        self.sources.get(*id as usize)
+    }
+
+    /// Number of distinct formats registered so far.
+    pub fn unique_count(&self) -> usize {
+        self.formats.len()
+    }
+
+    /// Drops the references to the JS objects once parsing is done.
+    pub fn release(&mut self, cx: &mut FunctionContext) {
+        for (_, (root, _)) in self.by_js_id.drain() {
+            root.drop(cx);
+        }
+    }
+}
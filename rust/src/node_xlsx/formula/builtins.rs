@@ -0,0 +1,1096 @@
+use chrono::{Datelike, Timelike};
+
+use super::{
+    super::number_format::{format_number, format_text},
+    evaluator::{settle, Area, Cells, Evaluator, Fail, Operand, Outcome, Stop},
+    parser::Expr,
+    value::{parse_number, serial_from_parts, serial_to_date, Value, DIV0, NA, NUM, REF, VALUE},
+};
+
+/// Calls a worksheet function, functions the evaluator does not know stop the
+/// evaluation of the formula.
+pub fn call<C: Cells>(ev: &Evaluator<C>, name: &str, args: &[Expr]) -> Result<Value, Stop> {
+    settle(match name {
+        // Aggregates
+        "SUM" => sum(ev, args),
+        "PRODUCT" => product(ev, args),
+        "AVERAGE" => average(ev, args),
+        "MIN" => extreme(ev, args, f64::min),
+        "MAX" => extreme(ev, args, f64::max),
+        "COUNT" => count(ev, args),
+        "COUNTA" => count_all(ev, args),
+        "COUNTBLANK" => count_blank(ev, args),
+        "SUMIF" => sum_if(ev, args, Aggregate::Sum),
+        "AVERAGEIF" => sum_if(ev, args, Aggregate::Average),
+        "COUNTIF" => count_if(ev, args),
+        "SUMIFS" => sum_ifs(ev, args),
+        "COUNTIFS" => count_ifs(ev, args),
+        "SUMPRODUCT" => sum_product(ev, args),
+
+        // Math
+        "ABS" => unary_math(ev, args, f64::abs),
+        "INT" => unary_math(ev, args, f64::floor),
+        "SIGN" => unary_math(ev, args, |n| match n {
+            n if n > 0.0 => 1.0,
+            n if n < 0.0 => -1.0,
+            _ => 0.0,
+        }),
+        "SQRT" => sqrt(ev, args),
+        "POWER" => power(ev, args),
+        "MOD" => modulo(ev, args),
+        "ROUND" => round(ev, args, f64::round),
+        "ROUNDUP" => round(ev, args, |n| n.abs().ceil() * n.signum()),
+        "ROUNDDOWN" => round(ev, args, f64::trunc),
+        "FLOOR" => floor(ev, args),
+        "PI" => arity(args, 0, 0).map(|_| Value::Number(std::f64::consts::PI)),
+
+        // Logic and information
+        "IF" => if_(ev, args),
+        "AND" => logical(ev, args, true),
+        "OR" => logical(ev, args, false),
+        "NOT" => arity(args, 1, 1).and_then(|_| Ok(Value::Boolean(!ev.boolean(&args[0])?))),
+        "TRUE" => arity(args, 0, 0).map(|_| Value::Boolean(true)),
+        "FALSE" => arity(args, 0, 0).map(|_| Value::Boolean(false)),
+        "NA" => arity(args, 0, 0).and(Err(Fail::Error(NA))),
+        "IFERROR" => if_error(ev, args, |_| true),
+        "IFNA" => if_error(ev, args, |error| error == NA),
+        "ISBLANK" => is(ev, args, |value| matches!(value, Value::Empty)),
+        "ISNUMBER" => is(ev, args, |value| matches!(value, Value::Number(_))),
+        "ISTEXT" => is(ev, args, |value| matches!(value, Value::Text(_))),
+        "ISLOGICAL" => is(ev, args, |value| matches!(value, Value::Boolean(_))),
+        "ISERROR" => is(ev, args, |value| matches!(value, Value::Error(_))),
+        "ISERR" => is(
+            ev,
+            args,
+            |value| matches!(value, Value::Error(e) if *e != NA),
+        ),
+        "ISNA" => is(
+            ev,
+            args,
+            |value| matches!(value, Value::Error(e) if *e == NA),
+        ),
+        "ROW" => position(ev, args, true),
+        "COLUMN" => position(ev, args, false),
+        "ROWS" => dimension(ev, args, |area| area.rows),
+        "COLUMNS" => dimension(ev, args, |area| area.cols),
+
+        // Lookups
+        "VLOOKUP" => table_lookup(ev, args, false),
+        "HLOOKUP" => table_lookup(ev, args, true),
+        "MATCH" => match_(ev, args),
+        "INDEX" => index(ev, args),
+        "XLOOKUP" => xlookup(ev, args),
+        "CHOOSE" => choose(ev, args),
+
+        // Text
+        "LEN" => text_math(ev, args, |text| text.chars().count() as f64),
+        "LEFT" => left_right(ev, args, true),
+        "RIGHT" => left_right(ev, args, false),
+        "MID" => mid(ev, args),
+        "UPPER" => text_map(ev, args, |text| text.to_uppercase()),
+        "LOWER" => text_map(ev, args, |text| text.to_lowercase()),
+        "PROPER" => text_map(ev, args, proper),
+        "TRIM" => text_map(ev, args, |text| {
+            text.split(' ')
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }),
+        "CONCATENATE" => concatenate(ev, args),
+        "CONCAT" => text_join(ev, args, "", false),
+        "TEXTJOIN" => textjoin(ev, args),
+        "SUBSTITUTE" => substitute(ev, args),
+        "REPT" => rept(ev, args),
+        "FIND" => find(ev, args, true),
+        "SEARCH" => find(ev, args, false),
+        "EXACT" => arity(args, 2, 2)
+            .and_then(|_| Ok(Value::Boolean(ev.text(&args[0])? == ev.text(&args[1])?))),
+        "VALUE" => value(ev, args),
+        "TEXT" => text(ev, args),
+
+        // Dates
+        "DATE" => date(ev, args),
+        // Only known when the caller says what day it is
+        "TODAY" => arity(args, 0, 0).and_then(|_| match ev.cells.today() {
+            Some(today) => Ok(Value::Number(today)),
+            None => Err(Fail::Stop(Stop::Unsupported)),
+        }),
+        "TIME" => time(ev, args),
+        "YEAR" => date_part(ev, args, |date| date.year() as f64),
+        "MONTH" => date_part(ev, args, |date| date.month() as f64),
+        "DAY" => date_part(ev, args, |date| date.day() as f64),
+        "HOUR" => date_part(ev, args, |date| date.hour() as f64),
+        "MINUTE" => date_part(ev, args, |date| date.minute() as f64),
+        "SECOND" => date_part(ev, args, |date| date.second() as f64),
+        "WEEKDAY" => weekday(ev, args),
+        "EDATE" => add_months(ev, args, false),
+        "EOMONTH" => add_months(ev, args, true),
+        "DAYS" => arity(args, 2, 2).and_then(|_| {
+            let end = ev.number(&args[0])?.floor();
+            let start = ev.number(&args[1])?.floor();
+            Ok(Value::Number(end - start))
+        }),
+
+        _ => return Err(Stop::Unsupported),
+    })
+}
+
+/// A function called with the wrong number of arguments is not a formula
+/// Excel would accept, so it is left alone.
+fn arity(args: &[Expr], min: usize, max: usize) -> Outcome<()> {
+    match (min..=max).contains(&args.len()) {
+        true => Ok(()),
+        false => Err(Fail::Stop(Stop::Unsupported)),
+    }
+}
+
+/// An optional argument, None when it is left out or empty.
+fn optional(args: &[Expr], index: usize) -> Option<&Expr> {
+    args.get(index).filter(|arg| !matches!(arg, Expr::Missing))
+}
+
+fn optional_number<C: Cells>(
+    ev: &Evaluator<C>,
+    args: &[Expr],
+    index: usize,
+    default: f64,
+) -> Outcome<f64> {
+    match optional(args, index) {
+        Some(arg) => ev.number(arg),
+        None => Ok(default),
+    }
+}
+
+fn check(number: f64) -> Outcome<Value> {
+    match number.is_finite() {
+        true => Ok(Value::Number(number)),
+        false => Err(Fail::Error(NUM)),
+    }
+}
+
+/// Calls `f` with every value of the arguments. Values read from cells are
+/// flagged, aggregates skip text and booleans in cells but convert them
+/// when they are typed into the formula.
+fn each_value<C: Cells>(
+    ev: &Evaluator<C>,
+    args: &[Expr],
+    mut f: impl FnMut(Value, bool) -> Outcome<()>,
+) -> Outcome<()> {
+    for arg in args {
+        if matches!(arg, Expr::Reference { .. }) {
+            for value in ev.area(arg)?.values {
+                f(value, true)?;
+            }
+            continue;
+        }
+        match ev.operand(arg)? {
+            Operand::Area(area) => {
+                for value in area.values {
+                    f(value, true)?;
+                }
+            }
+            Operand::Value(Value::Empty) => {}
+            Operand::Value(value) => f(value, false)?,
+        }
+    }
+    Ok(())
+}
+
+/// The numbers an aggregate such as `SUM` works on.
+fn numbers<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Vec<f64>> {
+    let mut numbers = vec![];
+    each_value(ev, args, |value, from_cells| {
+        match (value, from_cells) {
+            (Value::Number(number), _) => numbers.push(number),
+            (Value::Error(error), _) => return Err(Fail::Error(error)),
+            (_, true) => {}
+            (value, false) => numbers.push(value.to_number().map_err(Fail::Error)?),
+        }
+        Ok(())
+    })?;
+    Ok(numbers)
+}
+
+fn sum<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    check(numbers(ev, args)?.iter().sum())
+}
+
+fn product<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    let numbers = numbers(ev, args)?;
+    match numbers.is_empty() {
+        true => Ok(Value::Number(0.0)),
+        false => check(numbers.iter().product()),
+    }
+}
+
+fn average<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    let numbers = numbers(ev, args)?;
+    match numbers.is_empty() {
+        true => Err(Fail::Error(DIV0)),
+        false => check(numbers.iter().sum::<f64>() / numbers.len() as f64),
+    }
+}
+
+fn extreme<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(f64, f64) -> f64) -> Outcome<Value> {
+    let numbers = numbers(ev, args)?;
+    Ok(Value::Number(numbers.into_iter().reduce(f).unwrap_or(0.0)))
+}
+
+fn count<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    let mut count = 0;
+    each_value(ev, args, |value, from_cells| {
+        let counted = match value {
+            Value::Number(_) => true,
+            Value::Boolean(_) => !from_cells,
+            Value::Text(text) => !from_cells && parse_number(&text).is_some(),
+            _ => false,
+        };
+        count += counted as usize;
+        Ok(())
+    })?;
+    Ok(Value::Number(count as f64))
+}
+
+fn count_all<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    let mut count = 0;
+    each_value(ev, args, |value, _| {
+        count += !matches!(value, Value::Empty) as usize;
+        Ok(())
+    })?;
+    Ok(Value::Number(count as f64))
+}
+
+fn count_blank<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    let area = ev.area(&args[0])?;
+    let count = area
+        .values
+        .iter()
+        .filter(|value| matches!(value, Value::Empty) || *value == &Value::Text(String::new()))
+        .count();
+    Ok(Value::Number(count as f64))
+}
+
+/// A condition of `SUMIF` and friends, such as `">=10"` or `"a*"`.
+struct Criteria {
+    op: &'static str,
+    value: Value,
+}
+
+impl Criteria {
+    fn new(value: Value) -> Outcome<Self> {
+        let text = match value {
+            Value::Text(text) => text,
+            Value::Error(error) => return Err(Fail::Error(error)),
+            Value::Empty => {
+                return Ok(Criteria {
+                    op: "=",
+                    value: Value::Number(0.0),
+                })
+            }
+            value => return Ok(Criteria { op: "=", value }),
+        };
+        let op = ["<=", ">=", "<>", "<", ">", "="]
+            .into_iter()
+            .find(|op| text.starts_with(op))
+            .unwrap_or("");
+        let rest = &text[op.len()..];
+        let value = if let Some(number) = parse_number(rest) {
+            Value::Number(number)
+        } else if rest.eq_ignore_ascii_case("TRUE") || rest.eq_ignore_ascii_case("FALSE") {
+            Value::Boolean(rest.eq_ignore_ascii_case("TRUE"))
+        } else {
+            Value::Text(rest.to_string())
+        };
+        let op = match op {
+            "" => "=",
+            op => op,
+        };
+        Ok(Criteria { op, value })
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        if self.op == "<>" {
+            return !self.equals(value);
+        }
+        if self.op == "=" {
+            return self.equals(value);
+        }
+        let ordering = match (&self.value, value) {
+            (Value::Number(_), Value::Number(_))
+            | (Value::Text(_), Value::Text(_))
+            | (Value::Boolean(_), Value::Boolean(_)) => value.compare(&self.value),
+            _ => return false,
+        };
+        match self.op {
+            "<" => ordering.is_lt(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            _ => ordering.is_ge(),
+        }
+    }
+
+    fn equals(&self, value: &Value) -> bool {
+        match (&self.value, value) {
+            (Value::Number(expected), Value::Number(number)) => expected == number,
+            (Value::Number(expected), Value::Text(text)) => parse_number(text) == Some(*expected),
+            (Value::Boolean(expected), Value::Boolean(boolean)) => expected == boolean,
+            (Value::Text(pattern), Value::Empty) => pattern.is_empty(),
+            (Value::Text(pattern), Value::Text(text)) => wildcard_match(pattern, text),
+            _ => false,
+        }
+    }
+}
+
+/// Matches text against a pattern with `*`, `?` and `~` escapes, without case.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    enum Part {
+        Any,
+        One,
+        Char(char),
+    }
+    let mut parts = vec![];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        parts.push(match c {
+            '*' => Part::Any,
+            '?' => Part::One,
+            '~' => match chars.next() {
+                Some(c) => Part::Char(c),
+                None => Part::Char('~'),
+            },
+            c => Part::Char(c),
+        });
+    }
+    let text: Vec<char> = text.chars().collect();
+    let same = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+
+    // Backtracks to the last `*` when a character does not match
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match parts.get(p) {
+            Some(Part::Any) => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(Part::One) => {
+                p += 1;
+                t += 1;
+            }
+            Some(Part::Char(c)) if same(*c, text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    parts[p..].iter().all(|part| matches!(part, Part::Any))
+}
+
+#[derive(Clone, Copy)]
+enum Aggregate {
+    Sum,
+    Average,
+}
+
+/// The areas of criteria pairs, which must all have the shape of `shape`.
+fn criteria_pairs<C: Cells>(
+    ev: &Evaluator<C>,
+    args: &[Expr],
+    shape: Option<(usize, usize)>,
+) -> Outcome<Vec<(Area, Criteria)>> {
+    if args.is_empty() || !args.len().is_multiple_of(2) {
+        return Err(Fail::Stop(Stop::Unsupported));
+    }
+    let mut pairs = vec![];
+    for pair in args.chunks(2) {
+        let area = ev.area(&pair[0])?;
+        let criteria = Criteria::new(ev.value(&pair[1])?)?;
+        pairs.push((area, criteria));
+    }
+    let shape = shape.unwrap_or((pairs[0].0.rows, pairs[0].0.cols));
+    if pairs
+        .iter()
+        .any(|(area, _)| (area.rows, area.cols) != shape)
+    {
+        return Err(Fail::Error(VALUE));
+    }
+    Ok(pairs)
+}
+
+fn matching(pairs: &[(Area, Criteria)], index: usize) -> bool {
+    pairs
+        .iter()
+        .all(|(area, criteria)| criteria.matches(&area.values[index]))
+}
+
+fn sum_if<C: Cells>(ev: &Evaluator<C>, args: &[Expr], aggregate: Aggregate) -> Outcome<Value> {
+    arity(args, 2, 3)?;
+    let pairs = criteria_pairs(ev, &args[..2], None)?;
+    let values = match optional(args, 2) {
+        Some(arg) => {
+            let area = ev.area(arg)?;
+            // Excel reads a differently sized sum range from its top left cell
+            if (area.rows, area.cols) != (pairs[0].0.rows, pairs[0].0.cols) {
+                return Err(Fail::Stop(Stop::Unsupported));
+            }
+            area.values
+        }
+        None => pairs[0].0.values.clone(),
+    };
+    add_matching(&values, &pairs, aggregate)
+}
+
+fn sum_ifs<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 255)?;
+    let values = ev.area(&args[0])?;
+    let pairs = criteria_pairs(ev, &args[1..], Some((values.rows, values.cols)))?;
+    add_matching(&values.values, &pairs, Aggregate::Sum)
+}
+
+fn add_matching(
+    values: &[Value],
+    pairs: &[(Area, Criteria)],
+    aggregate: Aggregate,
+) -> Outcome<Value> {
+    let mut total = 0.0;
+    let mut count = 0;
+    for (index, value) in values.iter().enumerate() {
+        if !matching(pairs, index) {
+            continue;
+        }
+        match value {
+            Value::Number(number) => {
+                total += number;
+                count += 1;
+            }
+            Value::Error(error) => return Err(Fail::Error(error)),
+            _ => {}
+        }
+    }
+    match aggregate {
+        Aggregate::Sum => check(total),
+        Aggregate::Average if count == 0 => Err(Fail::Error(DIV0)),
+        Aggregate::Average => check(total / count as f64),
+    }
+}
+
+fn count_if<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    count_ifs(ev, args)
+}
+
+fn count_ifs<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    let pairs = criteria_pairs(ev, args, None)?;
+    let count = (0..pairs[0].0.values.len())
+        .filter(|index| matching(&pairs, *index))
+        .count();
+    Ok(Value::Number(count as f64))
+}
+
+fn sum_product<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 255)?;
+    let mut areas = vec![];
+    for arg in args {
+        // Arrays computed from expressions need array evaluation
+        if !matches!(arg, Expr::Reference { .. } | Expr::Array(_)) {
+            return Err(Fail::Stop(Stop::Unsupported));
+        }
+        areas.push(ev.area(arg)?);
+    }
+    let shape = (areas[0].rows, areas[0].cols);
+    if areas.iter().any(|area| (area.rows, area.cols) != shape) {
+        return Err(Fail::Error(VALUE));
+    }
+    let mut total = 0.0;
+    for index in 0..areas[0].values.len() {
+        let mut product = 1.0;
+        for area in &areas {
+            product *= match &area.values[index] {
+                Value::Number(number) => *number,
+                Value::Error(error) => return Err(Fail::Error(error)),
+                _ => 0.0,
+            };
+        }
+        total += product;
+    }
+    check(total)
+}
+
+fn unary_math<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(f64) -> f64) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    check(f(ev.number(&args[0])?))
+}
+
+fn sqrt<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    match ev.number(&args[0])? {
+        number if number < 0.0 => Err(Fail::Error(NUM)),
+        number => check(number.sqrt()),
+    }
+}
+
+fn power<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let base = ev.number(&args[0])?;
+    let exponent = ev.number(&args[1])?;
+    if base == 0.0 && exponent < 0.0 {
+        return Err(Fail::Error(DIV0));
+    }
+    check(base.powf(exponent))
+}
+
+fn modulo<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let number = ev.number(&args[0])?;
+    let divisor = ev.number(&args[1])?;
+    if divisor == 0.0 {
+        return Err(Fail::Error(DIV0));
+    }
+    check(number - divisor * (number / divisor).floor())
+}
+
+fn round<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(f64) -> f64) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let number = ev.number(&args[0])?;
+    let digits = ev.number(&args[1])?.trunc().clamp(-308.0, 308.0) as i32;
+    let factor = 10f64.powi(digits);
+    // Drops the binary noise first, so that 2.675 rounds to 2.68 like in Excel
+    let scaled: f64 = format!("{:.14e}", number * factor)
+        .parse()
+        .unwrap_or(number * factor);
+    check(f(scaled) / factor)
+}
+
+/// Rounds toward negative infinity to a multiple of the significance, or
+/// toward zero when both are negative.
+fn floor<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let number = ev.number(&args[0])?;
+    let significance = ev.number(&args[1])?;
+    if significance == 0.0 {
+        return match number {
+            0.0 => Ok(Value::Number(0.0)),
+            _ => Err(Fail::Error(DIV0)),
+        };
+    }
+    if number > 0.0 && significance < 0.0 {
+        return Err(Fail::Error(NUM));
+    }
+    check((number / significance).floor() * significance)
+}
+
+fn if_<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 3)?;
+    let branch = match ev.boolean(&args[0])? {
+        true => args.get(1),
+        false => args.get(2),
+    };
+    match branch {
+        Some(Expr::Missing) => Ok(Value::Number(0.0)),
+        Some(branch) => Ok(ev.value(branch)?),
+        None => Ok(Value::Boolean(false)),
+    }
+}
+
+fn logical<C: Cells>(ev: &Evaluator<C>, args: &[Expr], all: bool) -> Outcome<Value> {
+    arity(args, 1, 255)?;
+    let mut result = None;
+    each_value(ev, args, |value, from_cells| {
+        let boolean = match (value, from_cells) {
+            (Value::Boolean(boolean), _) => boolean,
+            (Value::Number(number), _) => number != 0.0,
+            (Value::Error(error), _) => return Err(Fail::Error(error)),
+            (_, true) => return Ok(()),
+            (value, false) => value.to_boolean().map_err(Fail::Error)?,
+        };
+        let combined = match all {
+            true => result.unwrap_or(true) && boolean,
+            false => result.unwrap_or(false) || boolean,
+        };
+        result = Some(combined);
+        Ok(())
+    })?;
+    result.map(Value::Boolean).ok_or(Fail::Error(VALUE))
+}
+
+fn if_error<C: Cells>(
+    ev: &Evaluator<C>,
+    args: &[Expr],
+    catches: fn(&str) -> bool,
+) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    match ev.value(&args[0])? {
+        Value::Error(error) if catches(error) => Ok(ev.value(&args[1])?),
+        Value::Empty => Ok(Value::Number(0.0)),
+        value => Ok(value),
+    }
+}
+
+fn is<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(&Value) -> bool) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    Ok(Value::Boolean(f(&ev.value(&args[0])?)))
+}
+
+fn position<C: Cells>(ev: &Evaluator<C>, args: &[Expr], row: bool) -> Outcome<Value> {
+    arity(args, 0, 1)?;
+    let index = match args.first() {
+        None => match row {
+            true => ev.row,
+            false => ev.col as u32,
+        },
+        Some(Expr::Reference { start, .. }) => match row {
+            true => start.row.map(|anchor| anchor.index).unwrap_or(0),
+            false => start.col.map(|anchor| anchor.index).unwrap_or(0),
+        },
+        Some(_) => return Err(Fail::Stop(Stop::Unsupported)),
+    };
+    Ok(Value::Number(index as f64 + 1.0))
+}
+
+fn dimension<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(&Area) -> usize) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    // Whole columns and rows are cut to the used range when they are read
+    if let Expr::Reference { start, .. } = &args[0] {
+        if start.row.is_none() || start.col.is_none() {
+            return Err(Fail::Stop(Stop::Unsupported));
+        }
+    }
+    Ok(Value::Number(f(&ev.area(&args[0])?) as f64))
+}
+
+/// Whether a cell value is the lookup value, text without case and with
+/// wildcards.
+fn same(lookup: &Value, value: &Value) -> bool {
+    match (lookup, value) {
+        (Value::Number(lookup), Value::Number(value)) => lookup == value,
+        (Value::Boolean(lookup), Value::Boolean(value)) => lookup == value,
+        (Value::Text(lookup), Value::Text(value)) => wildcard_match(lookup, value),
+        _ => false,
+    }
+}
+
+fn same_kind(lookup: &Value, value: &Value) -> bool {
+    std::mem::discriminant(lookup) == std::mem::discriminant(value)
+}
+
+/// The position of a lookup value like `MATCH` finds it: `0` for an exact
+/// match, `1` for the last value not above it in ascending values and `-1`
+/// for the last value not below it in descending ones.
+fn find_position(values: &[Value], lookup: &Value, mode: i32) -> Option<usize> {
+    if mode == 0 {
+        return values.iter().position(|value| same(lookup, value));
+    }
+    let mut found = None;
+    for (index, value) in values.iter().enumerate() {
+        if !same_kind(lookup, value) {
+            continue;
+        }
+        let ordering = value.compare(lookup);
+        let past = match mode > 0 {
+            true => ordering.is_gt(),
+            false => ordering.is_lt(),
+        };
+        if past {
+            break;
+        }
+        found = Some(index);
+    }
+    found
+}
+
+fn lookup_value<C: Cells>(ev: &Evaluator<C>, arg: &Expr) -> Outcome<Value> {
+    match ev.value(arg)? {
+        Value::Error(error) => Err(Fail::Error(error)),
+        Value::Empty => Err(Fail::Error(NA)),
+        value => Ok(value),
+    }
+}
+
+fn table_lookup<C: Cells>(ev: &Evaluator<C>, args: &[Expr], horizontal: bool) -> Outcome<Value> {
+    arity(args, 3, 4)?;
+    let lookup = lookup_value(ev, &args[0])?;
+    let table = ev.area(&args[1])?;
+    let index = ev.number(&args[2])?.trunc();
+    let approximate = match optional(args, 3) {
+        Some(arg) => ev.boolean(arg)?,
+        None => true,
+    };
+    let (lines, width) = match horizontal {
+        true => (table.cols, table.rows),
+        false => (table.rows, table.cols),
+    };
+    if index < 1.0 {
+        return Err(Fail::Error(VALUE));
+    }
+    if index > width as f64 {
+        return Err(Fail::Error(REF));
+    }
+    let at = |line: usize, offset: usize| match horizontal {
+        true => table.get(offset, line),
+        false => table.get(line, offset),
+    };
+    let keys: Vec<Value> = (0..lines).map(|line| at(line, 0).clone()).collect();
+    let line = find_position(&keys, &lookup, approximate as i32).ok_or(Fail::Error(NA))?;
+    Ok(match at(line, index as usize - 1) {
+        Value::Empty => Value::Number(0.0),
+        value => value.clone(),
+    })
+}
+
+fn match_<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 3)?;
+    let lookup = lookup_value(ev, &args[0])?;
+    let area = ev.area(&args[1])?;
+    let mode = optional_number(ev, args, 2, 1.0)?.signum() as i32;
+    let values = area.vector().ok_or(Fail::Error(NA))?;
+    let position = find_position(values, &lookup, mode).ok_or(Fail::Error(NA))?;
+    Ok(Value::Number(position as f64 + 1.0))
+}
+
+fn index<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 3)?;
+    let area = ev.area(&args[0])?;
+    let first = ev.number(&args[1])?.trunc();
+    let second = optional_number(ev, args, 2, 0.0)?.trunc();
+    if first < 0.0 || second < 0.0 {
+        return Err(Fail::Error(VALUE));
+    }
+    // A single index into a row picks a column
+    let (row, col) = match (optional(args, 2), area.rows) {
+        (None, 1) => (1.0, first),
+        _ => (first, second),
+    };
+    let row = match (row, area.rows) {
+        (0.0, 1) => 1.0,
+        (0.0, _) => return Err(Fail::Stop(Stop::Unsupported)),
+        (row, _) => row,
+    };
+    let col = match (col, area.cols) {
+        (0.0, 1) => 1.0,
+        (0.0, _) => return Err(Fail::Stop(Stop::Unsupported)),
+        (col, _) => col,
+    };
+    if row > area.rows as f64 || col > area.cols as f64 {
+        return Err(Fail::Error(REF));
+    }
+    Ok(area.get(row as usize - 1, col as usize - 1).clone())
+}
+
+fn xlookup<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 6)?;
+    let lookup = lookup_value(ev, &args[0])?;
+    let keys = ev.area(&args[1])?;
+    let results = ev.area(&args[2])?;
+    let match_mode = optional_number(ev, args, 4, 0.0)?;
+    let search_mode = optional_number(ev, args, 5, 1.0)?;
+
+    let keys = keys.vector().ok_or(Fail::Error(VALUE))?;
+    // A result row or column wider than one cell would spill
+    if results.values.len() != keys.len() || results.vector().is_none() {
+        return Err(Fail::Stop(Stop::Unsupported));
+    }
+
+    let order: Vec<usize> = match search_mode {
+        1.0 => (0..keys.len()).collect(),
+        -1.0 => (0..keys.len()).rev().collect(),
+        _ => return Err(Fail::Stop(Stop::Unsupported)),
+    };
+    let exact = order.iter().copied().find(|index| match match_mode {
+        2.0 => same(&lookup, &keys[*index]),
+        _ => same_kind(&lookup, &keys[*index]) && keys[*index].compare(&lookup).is_eq(),
+    });
+    let found = match (exact, match_mode) {
+        (Some(index), _) => Some(index),
+        (None, 0.0 | 2.0) => None,
+        (None, -1.0 | 1.0) => {
+            let candidates = order
+                .iter()
+                .copied()
+                .filter(|index| same_kind(&lookup, &keys[*index]))
+                .filter(|index| match match_mode > 0.0 {
+                    true => keys[*index].compare(&lookup).is_gt(),
+                    false => keys[*index].compare(&lookup).is_lt(),
+                });
+            match match_mode > 0.0 {
+                true => candidates.min_by(|a, b| keys[*a].compare(&keys[*b])),
+                false => candidates.max_by(|a, b| keys[*a].compare(&keys[*b])),
+            }
+        }
+        _ => return Err(Fail::Error(VALUE)),
+    };
+    match (found, optional(args, 3)) {
+        (Some(index), _) => Ok(results.values[index].clone()),
+        (None, Some(if_not_found)) => Ok(ev.value(if_not_found)?),
+        (None, None) => Err(Fail::Error(NA)),
+    }
+}
+
+fn choose<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 255)?;
+    let index = ev.number(&args[0])?.trunc();
+    if index < 1.0 || index >= args.len() as f64 {
+        return Err(Fail::Error(VALUE));
+    }
+    Ok(ev.value(&args[index as usize])?)
+}
+
+fn text_map<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(&str) -> String) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    Ok(Value::Text(f(&ev.text(&args[0])?)))
+}
+
+fn text_math<C: Cells>(ev: &Evaluator<C>, args: &[Expr], f: fn(&str) -> f64) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    Ok(Value::Number(f(&ev.text(&args[0])?)))
+}
+
+fn proper(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut after_letter = false;
+    for c in text.chars() {
+        match after_letter {
+            true => result.extend(c.to_lowercase()),
+            false => result.extend(c.to_uppercase()),
+        }
+        after_letter = c.is_alphabetic();
+    }
+    result
+}
+
+fn left_right<C: Cells>(ev: &Evaluator<C>, args: &[Expr], left: bool) -> Outcome<Value> {
+    arity(args, 1, 2)?;
+    let text = ev.text(&args[0])?;
+    let count = optional_number(ev, args, 1, 1.0)?.trunc();
+    if count < 0.0 {
+        return Err(Fail::Error(VALUE));
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let count = (count as usize).min(chars.len());
+    let text = match left {
+        true => chars[..count].iter().collect(),
+        false => chars[chars.len() - count..].iter().collect(),
+    };
+    Ok(Value::Text(text))
+}
+
+fn mid<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 3)?;
+    let text = ev.text(&args[0])?;
+    let start = ev.number(&args[1])?.trunc();
+    let count = ev.number(&args[2])?.trunc();
+    if start < 1.0 || count < 0.0 {
+        return Err(Fail::Error(VALUE));
+    }
+    let text = text
+        .chars()
+        .skip(start as usize - 1)
+        .take(count as usize)
+        .collect();
+    Ok(Value::Text(text))
+}
+
+fn concatenate<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 255)?;
+    let mut text = String::new();
+    for arg in args {
+        text.push_str(&ev.text(arg)?);
+    }
+    Ok(Value::Text(text))
+}
+
+/// Joins every value of the arguments, areas included, like `TEXTJOIN`.
+fn text_join<C: Cells>(
+    ev: &Evaluator<C>,
+    args: &[Expr],
+    delimiter: &str,
+    ignore_empty: bool,
+) -> Outcome<Value> {
+    arity(args, 1, 255)?;
+    let mut parts = vec![];
+    for arg in args {
+        for value in ev.area(arg)?.values {
+            let text = value.to_text().map_err(Fail::Error)?;
+            if !(ignore_empty && text.is_empty()) {
+                parts.push(text);
+            }
+        }
+    }
+    Ok(Value::Text(parts.join(delimiter)))
+}
+
+fn textjoin<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 255)?;
+    let delimiter = ev.text(&args[0])?;
+    let ignore_empty = ev.boolean(&args[1])?;
+    text_join(ev, &args[2..], &delimiter, ignore_empty)
+}
+
+fn substitute<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 4)?;
+    let text = ev.text(&args[0])?;
+    let old = ev.text(&args[1])?;
+    let new = ev.text(&args[2])?;
+    if old.is_empty() {
+        return Ok(Value::Text(text));
+    }
+    let Some(instance) = optional(args, 3) else {
+        return Ok(Value::Text(text.replace(&old, &new)));
+    };
+    let instance = ev.number(instance)?.trunc();
+    if instance < 1.0 {
+        return Err(Fail::Error(VALUE));
+    }
+    let text = match text.match_indices(&old).nth(instance as usize - 1) {
+        Some((at, _)) => format!("{}{}{}", &text[..at], new, &text[at + old.len()..]),
+        None => text,
+    };
+    Ok(Value::Text(text))
+}
+
+fn rept<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let text = ev.text(&args[0])?;
+    let count = ev.number(&args[1])?.trunc();
+    // Excel cells hold at most 32767 characters
+    if count < 0.0 || text.chars().count() as f64 * count > 32767.0 {
+        return Err(Fail::Error(VALUE));
+    }
+    Ok(Value::Text(text.repeat(count as usize)))
+}
+
+fn find<C: Cells>(ev: &Evaluator<C>, args: &[Expr], case_sensitive: bool) -> Outcome<Value> {
+    arity(args, 2, 3)?;
+    let needle = ev.text(&args[0])?;
+    let haystack = ev.text(&args[1])?;
+    let start = optional_number(ev, args, 2, 1.0)?.trunc();
+    if !case_sensitive && needle.contains(['*', '?', '~']) {
+        return Err(Fail::Stop(Stop::Unsupported));
+    }
+    let chars: Vec<char> = haystack.chars().collect();
+    if start < 1.0 || start as usize > chars.len() + 1 {
+        return Err(Fail::Error(VALUE));
+    }
+    let fold = |text: &str| match case_sensitive {
+        true => text.to_string(),
+        false => text.to_lowercase(),
+    };
+    let needle = fold(&needle);
+    let needle_length = needle.chars().count();
+    (start as usize - 1..=chars.len().saturating_sub(needle_length))
+        .find(|at| {
+            let candidate: String = chars[*at..*at + needle_length].iter().collect();
+            fold(&candidate) == needle
+        })
+        .map(|at| Value::Number(at as f64 + 1.0))
+        .ok_or(Fail::Error(VALUE))
+}
+
+fn value<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    match ev.value(&args[0])? {
+        Value::Number(number) => Ok(Value::Number(number)),
+        Value::Empty => Ok(Value::Number(0.0)),
+        Value::Error(error) => Err(Fail::Error(error)),
+        Value::Text(text) => parse_number(&text)
+            .map(Value::Number)
+            .ok_or(Fail::Error(VALUE)),
+        Value::Boolean(_) => Err(Fail::Error(VALUE)),
+    }
+}
+
+/// `TEXT` shows its value with a number format, like a cell would. Text
+/// that reads as a number is formatted as one.
+fn text<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let value = ev.value(&args[0])?;
+    let code = ev.text(&args[1])?;
+    let shown = match &value {
+        Value::Number(number) => format_number(&code, *number),
+        Value::Empty => format_number(&code, 0.0),
+        Value::Text(text) => match parse_number(text) {
+            Some(number) => format_number(&code, number),
+            None => format_text(&code, text),
+        },
+        Value::Boolean(_) | Value::Error(_) => value.to_text().map_err(Fail::Error)?,
+    };
+    Ok(Value::Text(shown))
+}
+
+fn date<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 3)?;
+    let year = ev.number(&args[0])?.trunc() as i64;
+    let month = ev.number(&args[1])?.trunc() as i64;
+    let day = ev.number(&args[2])?.trunc() as i64;
+    if !(0..10000).contains(&year) {
+        return Err(Fail::Error(NUM));
+    }
+    serial_from_parts(year, month, day)
+        .map(Value::Number)
+        .ok_or(Fail::Error(NUM))
+}
+
+fn time<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 3, 3)?;
+    let hour = ev.number(&args[0])?.trunc();
+    let minute = ev.number(&args[1])?.trunc();
+    let second = ev.number(&args[2])?.trunc();
+    let seconds = hour * 3600.0 + minute * 60.0 + second;
+    if seconds < 0.0 {
+        return Err(Fail::Error(NUM));
+    }
+    check((seconds / 86400.0).fract())
+}
+
+fn date_part<C: Cells>(
+    ev: &Evaluator<C>,
+    args: &[Expr],
+    f: fn(&chrono::NaiveDateTime) -> f64,
+) -> Outcome<Value> {
+    arity(args, 1, 1)?;
+    let date = serial_to_date(ev.number(&args[0])?).ok_or(Fail::Error(NUM))?;
+    Ok(Value::Number(f(&date)))
+}
+
+fn weekday<C: Cells>(ev: &Evaluator<C>, args: &[Expr]) -> Outcome<Value> {
+    arity(args, 1, 2)?;
+    let serial = ev.number(&args[0])?.floor();
+    if serial < 0.0 {
+        return Err(Fail::Error(NUM));
+    }
+    // Serial 1 is a Sunday in Excel's calendar
+    let sunday_based = (serial as i64 - 1).rem_euclid(7);
+    let day = match optional_number(ev, args, 1, 1.0)? {
+        1.0 => sunday_based + 1,
+        2.0 => (sunday_based + 6) % 7 + 1,
+        3.0 => (sunday_based + 6) % 7,
+        _ => return Err(Fail::Error(NUM)),
+    };
+    Ok(Value::Number(day as f64))
+}
+
+fn add_months<C: Cells>(ev: &Evaluator<C>, args: &[Expr], end_of_month: bool) -> Outcome<Value> {
+    arity(args, 2, 2)?;
+    let date = serial_to_date(ev.number(&args[0])?).ok_or(Fail::Error(NUM))?;
+    let months = ev.number(&args[1])?.trunc() as i64;
+    let (year, month) = (date.year() as i64, date.month() as i64 + months);
+    // Day zero of the next month is the last day of this one
+    let last_day = serial_from_parts(year, month + 1, 0).ok_or(Fail::Error(NUM))?;
+    let serial = match end_of_month {
+        true => last_day,
+        false => {
+            let first_day = serial_from_parts(year, month, 1).ok_or(Fail::Error(NUM))?;
+            let day = (date.day() as f64).min(last_day - first_day + 1.0);
+            first_day + day - 1.0
+        }
+    };
+    Ok(Value::Number(serial))
+}
@@ -0,0 +1,356 @@
+use super::{
+    builtins,
+    parser::{BinaryOp, Expr, UnaryOp},
+    reference::Reference,
+    value::{error_code, Value, DIV0, NUM, REF, VALUE},
+};
+use crate::node_xlsx::cell_range::{COLUMN_COUNT, ROW_COUNT};
+
+/// Largest area a single reference may read, bigger ones are not evaluated.
+const MAX_AREA_CELLS: usize = 4_000_000;
+
+/// Why a formula has no computed result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The formula uses something the evaluator does not know how to compute
+    Unsupported,
+    /// The cells it depends on are nested too deep to follow right now
+    TooDeep,
+}
+
+/// The cells of the workbook, as the evaluator reads them.
+pub trait Cells {
+    fn sheet_index(&self, name: &str) -> Option<usize>;
+    /// The value of a cell, computing it first when it holds a formula.
+    fn value(&self, sheet: usize, row: u32, col: u16) -> Result<Value, Stop>;
+    /// The last used row and column of a sheet.
+    fn used_range(&self, sheet: usize) -> (u32, u16);
+    /// The serial of the current date for `TODAY()`, which is not computed
+    /// when there is none.
+    fn today(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// A rectangle of values, row by row.
+#[derive(Debug, Clone)]
+pub struct Area {
+    pub rows: usize,
+    pub cols: usize,
+    pub values: Vec<Value>,
+    /// The top left cell, when the area was read from a reference
+    pub origin: Option<(u32, u16)>,
+}
+
+impl Area {
+    pub fn get(&self, row: usize, col: usize) -> &Value {
+        &self.values[row * self.cols + col]
+    }
+
+    /// The values of a single row or column area, None for a wider one.
+    pub fn vector(&self) -> Option<&[Value]> {
+        match self.rows == 1 || self.cols == 1 {
+            true => Some(&self.values),
+            false => None,
+        }
+    }
+}
+
+/// What an expression evaluates to.
+pub enum Operand {
+    Value(Value),
+    Area(Area),
+}
+
+/// An Excel error value or a reason to give up on the formula, so that
+/// functions can use `?` for both.
+pub enum Fail {
+    Error(&'static str),
+    Stop(Stop),
+}
+
+impl From<Stop> for Fail {
+    fn from(stop: Stop) -> Self {
+        Fail::Stop(stop)
+    }
+}
+
+pub type Outcome<T> = Result<T, Fail>;
+
+/// Turns the outcome of a function back into a cell value.
+pub fn settle(outcome: Outcome<Value>) -> Result<Value, Stop> {
+    match outcome {
+        Ok(value) => Ok(value),
+        Err(Fail::Error(error)) => Ok(Value::Error(error)),
+        Err(Fail::Stop(stop)) => Err(stop),
+    }
+}
+
+/// Evaluates the formula of one cell.
+pub fn evaluate<C: Cells>(
+    cells: &C,
+    expr: &Expr,
+    sheet: usize,
+    row: u32,
+    col: u16,
+    dynamic: bool,
+) -> Result<Value, Stop> {
+    let evaluator = Evaluator {
+        cells,
+        sheet,
+        row,
+        col,
+        dynamic,
+        shift: (0, 0),
+    };
+    match evaluator.value(expr)? {
+        Value::Empty => Ok(Value::Number(0.0)),
+        value => Ok(value),
+    }
+}
+
+/// Evaluates a formula written for the cell `anchor` in another cell, with its
+/// relative references moved along, the way conditional formats read them.
+pub fn evaluate_relative<C: Cells>(
+    cells: &C,
+    expr: &Expr,
+    sheet: usize,
+    anchor: (u32, u16),
+    row: u32,
+    col: u16,
+) -> Result<Value, Stop> {
+    let evaluator = Evaluator {
+        cells,
+        sheet,
+        row,
+        col,
+        dynamic: false,
+        shift: (row as i64 - anchor.0 as i64, col as i64 - anchor.1 as i64),
+    };
+    evaluator.value(expr)
+}
+
+pub struct Evaluator<'a, C: Cells> {
+    pub cells: &'a C,
+    pub sheet: usize,
+    pub row: u32,
+    pub col: u16,
+    dynamic: bool,
+    /// How far relative references move from where they are written
+    shift: (i64, i64),
+}
+
+impl<'a, C: Cells> Evaluator<'a, C> {
+    pub fn operand(&self, expr: &Expr) -> Result<Operand, Stop> {
+        let value = match expr {
+            Expr::Number(number) => Value::Number(*number),
+            Expr::Text(text) => Value::Text(text.clone()),
+            Expr::Boolean(boolean) => Value::Boolean(*boolean),
+            Expr::Error(error) => Value::Error(error_code(error).ok_or(Stop::Unsupported)?),
+            Expr::Missing => Value::Empty,
+            Expr::Reference { sheet, start, end } => return self.reference(sheet, start, end),
+            Expr::Array(rows) => {
+                let mut values = vec![];
+                for item in rows.iter().flatten() {
+                    values.push(self.value(item)?);
+                }
+                return Ok(Operand::Area(Area {
+                    rows: rows.len(),
+                    cols: rows[0].len(),
+                    values,
+                    origin: None,
+                }));
+            }
+            Expr::Function { name, args } => builtins::call(self, name, args)?,
+            Expr::Unary(UnaryOp::At, operand) => {
+                let operand = self.operand(operand)?;
+                self.intersect(operand)
+            }
+            Expr::Unary(op, operand) => settle((|| {
+                let number = self.number(operand)?;
+                Ok(Value::Number(match op {
+                    UnaryOp::Minus => -number,
+                    _ => number,
+                }))
+            })())?,
+            Expr::Percent(operand) => {
+                settle(self.number(operand).map(|n| Value::Number(n / 100.0)))?
+            }
+            Expr::Binary(op, left, right) => self.binary(*op, left, right)?,
+            _ => return Err(Stop::Unsupported),
+        };
+        Ok(Operand::Value(value))
+    }
+
+    /// The single value of an expression. An area is narrowed to the cell in
+    /// the same row or column, the way Excel reads it outside dynamic arrays.
+    pub fn value(&self, expr: &Expr) -> Result<Value, Stop> {
+        let operand = self.operand(expr)?;
+        match operand {
+            Operand::Value(value) => Ok(value),
+            Operand::Area(area) if area.rows == 1 && area.cols == 1 => {
+                Ok(area.values.into_iter().next().unwrap_or(Value::Empty))
+            }
+            // A dynamic formula would spill the area
+            Operand::Area(_) if self.dynamic => Err(Stop::Unsupported),
+            operand => Ok(self.intersect(operand)),
+        }
+    }
+
+    pub fn number(&self, expr: &Expr) -> Outcome<f64> {
+        self.value(expr)?.to_number().map_err(Fail::Error)
+    }
+
+    pub fn text(&self, expr: &Expr) -> Outcome<String> {
+        self.value(expr)?.to_text().map_err(Fail::Error)
+    }
+
+    pub fn boolean(&self, expr: &Expr) -> Outcome<bool> {
+        self.value(expr)?.to_boolean().map_err(Fail::Error)
+    }
+
+    /// The area of an expression, a single value is a one cell area.
+    pub fn area(&self, expr: &Expr) -> Result<Area, Stop> {
+        match self.operand(expr)? {
+            Operand::Area(area) => Ok(area),
+            Operand::Value(value) => Ok(Area {
+                rows: 1,
+                cols: 1,
+                values: vec![value],
+                origin: None,
+            }),
+        }
+    }
+
+    /// Narrows an area to the cell in the formula's row or column, `#VALUE!`
+    /// when there is none.
+    fn intersect(&self, operand: Operand) -> Value {
+        let area = match operand {
+            Operand::Value(value) => return value,
+            Operand::Area(area) => area,
+        };
+        if area.rows == 1 && area.cols == 1 {
+            return area.get(0, 0).clone();
+        }
+        let Some((first_row, first_col)) = area.origin else {
+            return Value::Error(VALUE);
+        };
+        let row = self.row as i64 - first_row as i64;
+        let col = self.col as i64 - first_col as i64;
+        if area.cols == 1 && (0..area.rows as i64).contains(&row) {
+            return area.get(row as usize, 0).clone();
+        }
+        if area.rows == 1 && (0..area.cols as i64).contains(&col) {
+            return area.get(0, col as usize).clone();
+        }
+        Value::Error(VALUE)
+    }
+
+    fn reference(
+        &self,
+        sheet: &Option<String>,
+        start: &Reference,
+        end: &Option<Reference>,
+    ) -> Result<Operand, Stop> {
+        let sheet = match sheet {
+            None => self.sheet,
+            Some(name) if name.starts_with('[') || name.contains(':') => {
+                return Err(Stop::Unsupported)
+            }
+            Some(name) => match self.cells.sheet_index(name) {
+                Some(sheet) => sheet,
+                None => return Ok(Operand::Value(Value::Error(REF))),
+            },
+        };
+
+        let end = end.unwrap_or(*start);
+        let (Some(start), Some(end)) = (
+            start.shift(self.shift.0, self.shift.1),
+            end.shift(self.shift.0, self.shift.1),
+        ) else {
+            return Ok(Operand::Value(Value::Error(REF)));
+        };
+        let (last_row, last_col) = self.cells.used_range(sheet);
+        let rows = match (start.row, end.row) {
+            (Some(first), Some(last)) => (first.index.min(last.index), first.index.max(last.index)),
+            // Whole columns only read down to the last used row
+            _ => (0, last_row),
+        };
+        let cols = match (start.col, end.col) {
+            (Some(first), Some(last)) => (first.index.min(last.index), first.index.max(last.index)),
+            _ => (0, last_col as u32),
+        };
+        if rows.1 >= ROW_COUNT || cols.1 >= COLUMN_COUNT as u32 {
+            return Ok(Operand::Value(Value::Error(REF)));
+        }
+        let cols = (cols.0 as u16, cols.1 as u16);
+
+        if start.is_cell() && rows.0 == rows.1 && cols.0 == cols.1 {
+            let value = self.cells.value(sheet, rows.0, cols.0)?;
+            return Ok(Operand::Value(value));
+        }
+
+        let row_count = (rows.1 - rows.0 + 1) as usize;
+        let col_count = (cols.1 - cols.0 + 1) as usize;
+        if row_count * col_count > MAX_AREA_CELLS {
+            return Err(Stop::Unsupported);
+        }
+        let mut values = Vec::with_capacity(row_count * col_count);
+        for row in rows.0..=rows.1 {
+            for col in cols.0..=cols.1 {
+                values.push(self.cells.value(sheet, row, col)?);
+            }
+        }
+        Ok(Operand::Area(Area {
+            rows: row_count,
+            cols: col_count,
+            values,
+            origin: Some((rows.0, cols.0)),
+        }))
+    }
+
+    fn binary(&self, op: BinaryOp, left: &Expr, right: &Expr) -> Result<Value, Stop> {
+        let left = self.value(left)?;
+        let right = self.value(right)?;
+        if let Value::Error(error) = left {
+            return Ok(Value::Error(error));
+        }
+        if let Value::Error(error) = right {
+            return Ok(Value::Error(error));
+        }
+
+        let ordering = || left.compare(&right);
+        let arithmetic = |f: fn(f64, f64) -> f64| {
+            let value = match (left.to_number(), right.to_number()) {
+                (Ok(left), Ok(right)) => f(left, right),
+                (Err(error), _) | (_, Err(error)) => return Value::Error(error),
+            };
+            match value.is_finite() {
+                true => Value::Number(value),
+                false => Value::Error(NUM),
+            }
+        };
+
+        Ok(match op {
+            BinaryOp::Add => arithmetic(|a, b| a + b),
+            BinaryOp::Sub => arithmetic(|a, b| a - b),
+            BinaryOp::Mul => arithmetic(|a, b| a * b),
+            BinaryOp::Div => match right.to_number() {
+                Ok(divisor) if divisor == 0.0 && left.to_number().is_ok() => Value::Error(DIV0),
+                _ => arithmetic(|a, b| a / b),
+            },
+            BinaryOp::Pow => arithmetic(f64::powf),
+            BinaryOp::Concat => match (left.to_text(), right.to_text()) {
+                (Ok(left), Ok(right)) => Value::Text(left + &right),
+                (Err(error), _) | (_, Err(error)) => Value::Error(error),
+            },
+            BinaryOp::Eq => Value::Boolean(ordering().is_eq()),
+            BinaryOp::Ne => Value::Boolean(ordering().is_ne()),
+            BinaryOp::Lt => Value::Boolean(ordering().is_lt()),
+            BinaryOp::Le => Value::Boolean(ordering().is_le()),
+            BinaryOp::Gt => Value::Boolean(ordering().is_gt()),
+            BinaryOp::Ge => Value::Boolean(ordering().is_ge()),
+            BinaryOp::Range | BinaryOp::Intersect => return Err(Stop::Unsupported),
+        })
+    }
+}
@@ -0,0 +1,573 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Functions every Excel version since 2007 reads as they are.
+const FUNCTIONS: [&str; 361] = [
+    "ABS",
+    "ACCRINT",
+    "ACCRINTM",
+    "ACOS",
+    "ACOSH",
+    "ADDRESS",
+    "AMORDEGRC",
+    "AMORLINC",
+    "AND",
+    "AREAS",
+    "ASC",
+    "ASIN",
+    "ASINH",
+    "ATAN",
+    "ATAN2",
+    "ATANH",
+    "AVEDEV",
+    "AVERAGE",
+    "AVERAGEA",
+    "AVERAGEIF",
+    "AVERAGEIFS",
+    "BAHTTEXT",
+    "BESSELI",
+    "BESSELJ",
+    "BESSELK",
+    "BESSELY",
+    "BETADIST",
+    "BETAINV",
+    "BIN2DEC",
+    "BIN2HEX",
+    "BIN2OCT",
+    "BINOMDIST",
+    "CALL",
+    "CEILING",
+    "CELL",
+    "CHAR",
+    "CHIDIST",
+    "CHIINV",
+    "CHITEST",
+    "CHOOSE",
+    "CLEAN",
+    "CODE",
+    "COLUMN",
+    "COLUMNS",
+    "COMBIN",
+    "COMPLEX",
+    "CONCATENATE",
+    "CONFIDENCE",
+    "CONVERT",
+    "CORREL",
+    "COS",
+    "COSH",
+    "COUNT",
+    "COUNTA",
+    "COUNTBLANK",
+    "COUNTIF",
+    "COUNTIFS",
+    "COUPDAYBS",
+    "COUPDAYS",
+    "COUPDAYSNC",
+    "COUPNCD",
+    "COUPNUM",
+    "COUPPCD",
+    "COVAR",
+    "CRITBINOM",
+    "CUBEKPIMEMBER",
+    "CUBEMEMBER",
+    "CUBEMEMBERPROPERTY",
+    "CUBERANKEDMEMBER",
+    "CUBESET",
+    "CUBESETCOUNT",
+    "CUBEVALUE",
+    "CUMIPMT",
+    "CUMPRINC",
+    "DATE",
+    "DATEDIF",
+    "DATEVALUE",
+    "DAVERAGE",
+    "DAY",
+    "DAYS360",
+    "DB",
+    "DBCS",
+    "DCOUNT",
+    "DCOUNTA",
+    "DDB",
+    "DEC2BIN",
+    "DEC2HEX",
+    "DEC2OCT",
+    "DEGREES",
+    "DELTA",
+    "DEVSQ",
+    "DGET",
+    "DISC",
+    "DMAX",
+    "DMIN",
+    "DOLLAR",
+    "DOLLARDE",
+    "DOLLARFR",
+    "DPRODUCT",
+    "DSTDEV",
+    "DSTDEVP",
+    "DSUM",
+    "DURATION",
+    "DVAR",
+    "DVARP",
+    "ECMA.CEILING",
+    "EDATE",
+    "EFFECT",
+    "EOMONTH",
+    "ERF",
+    "ERFC",
+    "ERROR.TYPE",
+    "EUROCONVERT",
+    "EVEN",
+    "EXACT",
+    "EXP",
+    "EXPONDIST",
+    "FACT",
+    "FACTDOUBLE",
+    "FALSE",
+    "FDIST",
+    "FIND",
+    "FINDB",
+    "FINV",
+    "FISHER",
+    "FISHERINV",
+    "FIXED",
+    "FLOOR",
+    "FORECAST",
+    "FREQUENCY",
+    "FTEST",
+    "FV",
+    "FVSCHEDULE",
+    "GAMMADIST",
+    "GAMMAINV",
+    "GAMMALN",
+    "GCD",
+    "GEOMEAN",
+    "GESTEP",
+    "GETPIVOTDATA",
+    "GROWTH",
+    "HARMEAN",
+    "HEX2BIN",
+    "HEX2DEC",
+    "HEX2OCT",
+    "HLOOKUP",
+    "HOUR",
+    "HYPERLINK",
+    "HYPGEOMDIST",
+    "IF",
+    "IFERROR",
+    "IMABS",
+    "IMAGINARY",
+    "IMARGUMENT",
+    "IMCONJUGATE",
+    "IMCOS",
+    "IMDIV",
+    "IMEXP",
+    "IMLN",
+    "IMLOG10",
+    "IMLOG2",
+    "IMPOWER",
+    "IMPRODUCT",
+    "IMREAL",
+    "IMSIN",
+    "IMSQRT",
+    "IMSUB",
+    "IMSUM",
+    "INDEX",
+    "INDIRECT",
+    "INFO",
+    "INT",
+    "INTERCEPT",
+    "INTRATE",
+    "IPMT",
+    "IRR",
+    "ISBLANK",
+    "ISERR",
+    "ISERROR",
+    "ISEVEN",
+    "ISLOGICAL",
+    "ISNA",
+    "ISNONTEXT",
+    "ISNUMBER",
+    "ISO.CEILING",
+    "ISODD",
+    "ISPMT",
+    "ISREF",
+    "ISTEXT",
+    "JIS",
+    "KURT",
+    "LARGE",
+    "LCM",
+    "LEFT",
+    "LEFTB",
+    "LEN",
+    "LENB",
+    "LINEST",
+    "LN",
+    "LOG",
+    "LOG10",
+    "LOGEST",
+    "LOGINV",
+    "LOGNORMDIST",
+    "LOOKUP",
+    "LOWER",
+    "MATCH",
+    "MAX",
+    "MAXA",
+    "MDETERM",
+    "MDURATION",
+    "MEDIAN",
+    "MID",
+    "MIDB",
+    "MIN",
+    "MINA",
+    "MINUTE",
+    "MINVERSE",
+    "MIRR",
+    "MMULT",
+    "MOD",
+    "MODE",
+    "MONTH",
+    "MROUND",
+    "MULTINOMIAL",
+    "N",
+    "NA",
+    "NEGBINOMDIST",
+    "NETWORKDAYS",
+    "NETWORKDAYS.INTL",
+    "NOMINAL",
+    "NORMDIST",
+    "NORMINV",
+    "NORMSDIST",
+    "NORMSINV",
+    "NOT",
+    "NOW",
+    "NPER",
+    "NPV",
+    "OCT2BIN",
+    "OCT2DEC",
+    "OCT2HEX",
+    "ODD",
+    "ODDFPRICE",
+    "ODDFYIELD",
+    "ODDLPRICE",
+    "ODDLYIELD",
+    "OFFSET",
+    "OR",
+    "PEARSON",
+    "PERCENTILE",
+    "PERCENTRANK",
+    "PERMUT",
+    "PHONETIC",
+    "PI",
+    "PMT",
+    "POISSON",
+    "POWER",
+    "PPMT",
+    "PRICE",
+    "PRICEDISC",
+    "PRICEMAT",
+    "PROB",
+    "PRODUCT",
+    "PROPER",
+    "PV",
+    "QUARTILE",
+    "QUOTIENT",
+    "RADIANS",
+    "RAND",
+    "RANDBETWEEN",
+    "RANK",
+    "RATE",
+    "RECEIVED",
+    "REGISTER.ID",
+    "REPLACE",
+    "REPLACEB",
+    "REPT",
+    "RIGHT",
+    "RIGHTB",
+    "ROMAN",
+    "ROUND",
+    "ROUNDDOWN",
+    "ROUNDUP",
+    "ROW",
+    "ROWS",
+    "RSQ",
+    "RTD",
+    "SEARCH",
+    "SEARCHB",
+    "SECOND",
+    "SERIESSUM",
+    "SIGN",
+    "SIN",
+    "SINH",
+    "SKEW",
+    "SLN",
+    "SLOPE",
+    "SMALL",
+    "SQL.REQUEST",
+    "SQRT",
+    "SQRTPI",
+    "STANDARDIZE",
+    "STDEV",
+    "STDEVA",
+    "STDEVP",
+    "STDEVPA",
+    "STEYX",
+    "SUBSTITUTE",
+    "SUBTOTAL",
+    "SUM",
+    "SUMIF",
+    "SUMIFS",
+    "SUMPRODUCT",
+    "SUMSQ",
+    "SUMX2MY2",
+    "SUMX2PY2",
+    "SUMXMY2",
+    "SYD",
+    "T",
+    "TAN",
+    "TANH",
+    "TBILLEQ",
+    "TBILLPRICE",
+    "TBILLYIELD",
+    "TDIST",
+    "TEXT",
+    "TIME",
+    "TIMEVALUE",
+    "TINV",
+    "TODAY",
+    "TRANSPOSE",
+    "TREND",
+    "TRIM",
+    "TRIMMEAN",
+    "TRUE",
+    "TRUNC",
+    "TTEST",
+    "TYPE",
+    "UPPER",
+    "USDOLLAR",
+    "VALUE",
+    "VAR",
+    "VARA",
+    "VARP",
+    "VARPA",
+    "VDB",
+    "VLOOKUP",
+    "WEEKDAY",
+    "WEEKNUM",
+    "WEIBULL",
+    "WORKDAY",
+    "WORKDAY.INTL",
+    "XIRR",
+    "XNPV",
+    "YEAR",
+    "YEARFRAC",
+    "YIELD",
+    "YIELDDISC",
+    "YIELDMAT",
+    "ZTEST",
+];
+
+/// Functions added after Excel 2007, stored with the `_xlfn.` prefix.
+/// Without it Excel shows `#NAME?` until the cell is edited.
+const FUTURE_FUNCTIONS: [&str; 165] = [
+    "ACOT",
+    "ACOTH",
+    "AGGREGATE",
+    "ANCHORARRAY",
+    "ARABIC",
+    "ARRAYTOTEXT",
+    "BASE",
+    "BETA.DIST",
+    "BETA.INV",
+    "BINOM.DIST",
+    "BINOM.DIST.RANGE",
+    "BINOM.INV",
+    "BITAND",
+    "BITLSHIFT",
+    "BITOR",
+    "BITRSHIFT",
+    "BITXOR",
+    "BYCOL",
+    "BYROW",
+    "CEILING.MATH",
+    "CEILING.PRECISE",
+    "CHISQ.DIST",
+    "CHISQ.DIST.RT",
+    "CHISQ.INV",
+    "CHISQ.INV.RT",
+    "CHISQ.TEST",
+    "CHOOSECOLS",
+    "CHOOSEROWS",
+    "COMBINA",
+    "CONCAT",
+    "CONFIDENCE.NORM",
+    "CONFIDENCE.T",
+    "COT",
+    "COTH",
+    "COVARIANCE.P",
+    "COVARIANCE.S",
+    "CSC",
+    "CSCH",
+    "DAYS",
+    "DECIMAL",
+    "DETECTLANGUAGE",
+    "DROP",
+    "ENCODEURL",
+    "ERF.PRECISE",
+    "ERFC.PRECISE",
+    "EXPAND",
+    "EXPON.DIST",
+    "F.DIST",
+    "F.DIST.RT",
+    "F.INV",
+    "F.INV.RT",
+    "F.TEST",
+    "FIELDVALUE",
+    "FILTERXML",
+    "FLOOR.MATH",
+    "FLOOR.PRECISE",
+    "FORECAST.ETS",
+    "FORECAST.ETS.CONFINT",
+    "FORECAST.ETS.SEASONALITY",
+    "FORECAST.ETS.STAT",
+    "FORECAST.LINEAR",
+    "FORMULATEXT",
+    "GAMMA",
+    "GAMMA.DIST",
+    "GAMMA.INV",
+    "GAMMALN.PRECISE",
+    "GAUSS",
+    "GROUPBY",
+    "HSTACK",
+    "HYPGEOM.DIST",
+    "IFNA",
+    "IFS",
+    "IMAGE",
+    "IMCOSH",
+    "IMCOT",
+    "IMCSC",
+    "IMCSCH",
+    "IMSEC",
+    "IMSECH",
+    "IMSINH",
+    "IMTAN",
+    "ISFORMULA",
+    "ISOMITTED",
+    "ISOWEEKNUM",
+    "LAMBDA",
+    "LET",
+    "LOGNORM.DIST",
+    "LOGNORM.INV",
+    "MAKEARRAY",
+    "MAP",
+    "MAXIFS",
+    "MINIFS",
+    "MODE.MULT",
+    "MODE.SNGL",
+    "MUNIT",
+    "NEGBINOM.DIST",
+    "NORM.DIST",
+    "NORM.INV",
+    "NORM.S.DIST",
+    "NORM.S.INV",
+    "NUMBERVALUE",
+    "PDURATION",
+    "PERCENTILE.EXC",
+    "PERCENTILE.INC",
+    "PERCENTOF",
+    "PERCENTRANK.EXC",
+    "PERCENTRANK.INC",
+    "PERMUTATIONA",
+    "PHI",
+    "PIVOTBY",
+    "POISSON.DIST",
+    "QUARTILE.EXC",
+    "QUARTILE.INC",
+    "QUERYSTRING",
+    "RANDARRAY",
+    "RANK.AVG",
+    "RANK.EQ",
+    "REDUCE",
+    "REGEXEXTRACT",
+    "REGEXREPLACE",
+    "REGEXTEST",
+    "RRI",
+    "SCAN",
+    "SEC",
+    "SECH",
+    "SEQUENCE",
+    "SHEET",
+    "SHEETS",
+    "SINGLE",
+    "SKEW.P",
+    "SORTBY",
+    "STDEV.P",
+    "STDEV.S",
+    "STOCKHISTORY",
+    "SWITCH",
+    "T.DIST",
+    "T.DIST.2T",
+    "T.DIST.RT",
+    "T.INV",
+    "T.INV.2T",
+    "T.TEST",
+    "TAKE",
+    "TEXTAFTER",
+    "TEXTBEFORE",
+    "TEXTJOIN",
+    "TEXTSPLIT",
+    "TOCOL",
+    "TOROW",
+    "TRANSLATE",
+    "TRIMRANGE",
+    "UNICHAR",
+    "UNICODE",
+    "UNIQUE",
+    "VALUETOTEXT",
+    "VAR.P",
+    "VAR.S",
+    "VSTACK",
+    "WEBSERVICE",
+    "WEIBULL.DIST",
+    "WRAPCOLS",
+    "WRAPROWS",
+    "XLOOKUP",
+    "XMATCH",
+    "XOR",
+    "Z.TEST",
+];
+
+/// Dynamic array functions that also live in the `_xlws.` namespace.
+const WORKSHEET_FUNCTIONS: [&str; 2] = ["FILTER", "SORT"];
+
+/// The prefix Excel stores in front of a function, `""` when it needs none.
+/// None for functions Excel does not know.
+pub fn prefix(name: &str) -> Option<&'static str> {
+    static PREFIXES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    let prefixes = PREFIXES.get_or_init(|| {
+        let functions = FUNCTIONS.iter().map(|name| (*name, ""));
+        let future = FUTURE_FUNCTIONS.iter().map(|name| (*name, "_xlfn."));
+        let worksheet = WORKSHEET_FUNCTIONS
+            .iter()
+            .map(|name| (*name, "_xlfn._xlws."));
+        functions.chain(future).chain(worksheet).collect()
+    });
+    prefixes.get(name).copied()
+}
+
+/// Splits a function name as written, like `_xlfn.xlookup`, into the upper
+/// case name and whether it is a user defined (`_xludf.`) or add-in (`_xll.`)
+/// function.
+pub fn split_name(text: &str) -> (String, bool) {
+    let mut name = text.to_ascii_uppercase();
+    for prefix in ["_XLFN.", "_XLWS."] {
+        if let Some(rest) = name.strip_prefix(prefix) {
+            name = rest.to_string();
+        }
+    }
+    // User defined and XLL add-in functions
+    for prefix in ["_XLUDF.", "_XLL."] {
+        if let Some(rest) = name.strip_prefix(prefix) {
+            return (rest.to_string(), true);
+        }
+    }
+    (name, false)
+}
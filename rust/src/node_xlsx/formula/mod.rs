@@ -0,0 +1,224 @@
+pub mod builtins;
+pub mod evaluator;
+pub mod functions;
+pub mod open_formula;
+pub mod parser;
+pub mod reference;
+pub mod result;
+pub mod shift;
+pub mod tokenizer;
+pub mod value;
+
+use std::collections::HashSet;
+
+use neon::{
+    context::FunctionContext,
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsBoolean, JsNull, JsObject, JsString, JsValue},
+};
+use rust_xlsxwriter::Formula;
+
+use self::{
+    functions::{prefix, split_name},
+    parser::{parse, Expr},
+    result::FormulaResult,
+    tokenizer::{tokenize, TokenKind},
+};
+
+/// The names the formulas of a workbook can refer to.
+#[derive(Default)]
+pub struct FormulaNames {
+    /// The sheet names, lower case
+    pub sheets: HashSet<String>,
+    /// The defined names, upper case and without the sheet they are scoped to
+    pub defined: HashSet<String>,
+}
+
+/// The options of a JS `Formula`, kept as plain values so the formula text can
+/// still be read after it is parsed.
+#[derive(Clone)]
+pub struct NodeXlsxFormula {
+    pub formula: String,
+    pub result: Option<FormulaResult>,
+    pub use_future_functions: bool,
+    pub use_table_functions: bool,
+    pub dynamic: bool,
+}
+
+impl NodeXlsxFormula {
+    pub fn from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
+        let formula: Handle<JsString> = obj.get(cx, "formula")?;
+        let formula = formula.value(cx);
+
+        let result: Option<Handle<JsValue>> = obj.get_opt(cx, "result")?;
+        let result = match result {
+            Some(result) if !result.is_a::<JsNull, _>(cx) => {
+                Some(FormulaResult::from_js_value(cx, result)?)
+            }
+            _ => None,
+        };
+
+        let use_future_functions: Option<Handle<JsBoolean>> =
+            obj.get_opt(cx, "useFutureFunctions")?;
+        let use_future_functions = match use_future_functions {
+            Some(use_future_functions) => use_future_functions.value(cx),
+            None => false,
+        };
+
+        let use_table_functions: Option<Handle<JsBoolean>> =
+            obj.get_opt(cx, "useTableFunctions")?;
+        let use_table_functions = match use_table_functions {
+            Some(use_table_functions) => use_table_functions.value(cx),
+            None => false,
+        };
+
+        let dynamic: Option<Handle<JsBoolean>> = obj.get_opt(cx, "dynamic")?;
+        let dynamic = match dynamic {
+            Some(dynamic) => dynamic.value(cx),
+            None => false,
+        };
+
+        Ok(Self {
+            formula,
+            result,
+            use_future_functions,
+            use_table_functions,
+            dynamic,
+        })
+    }
+
+    /// A formula with the default options.
+    pub fn new(formula: String) -> Self {
+        Self {
+            formula,
+            result: None,
+            use_future_functions: false,
+            use_table_functions: false,
+            dynamic: false,
+        }
+    }
+
+    /// The same options with another formula text and no cached result.
+    pub fn with_formula(&self, formula: String) -> Self {
+        Self {
+            formula,
+            result: None,
+            ..self.clone()
+        }
+    }
+}
+
+impl NodeXlsxFormula {
+    /// Checks the syntax and the sheets of the formula, and writes its
+    /// functions the way Excel stores them: upper case and with the `_xlfn.`
+    /// prefix newer functions need. Functions that are neither built in nor
+    /// defined names, like VBA functions, are written as they are and returned.
+    pub fn prepare(&mut self, names: &FormulaNames) -> Result<Vec<String>, String> {
+        // Array formulas may come as {=...}, like rust_xlsxwriter accepts them
+        let text = self.formula.as_str();
+        let (open, text) = match text.strip_prefix('{') {
+            Some(text) => ("{", text),
+            None => ("", text),
+        };
+        let (equals, text) = match text.strip_prefix('=') {
+            Some(text) => ("=", text),
+            None => ("", text),
+        };
+        let (body, close) = match text.strip_suffix('}') {
+            Some(body) if !open.is_empty() => (body, "}"),
+            _ => (text, ""),
+        };
+
+        let expr = parse(body)?;
+        let mut locals = HashSet::new();
+        let mut unknown_sheet = None;
+        expr.walk(&mut |expr| match expr {
+            Expr::Function { name, args } if name == "LET" || name == "LAMBDA" => {
+                let variables = match name.as_str() {
+                    "LET" => args.iter().step_by(2).collect::<Vec<_>>(),
+                    _ => args.iter().collect(),
+                };
+                for variable in variables {
+                    if let Expr::Name { sheet: None, name } = variable {
+                        locals.insert(name.to_ascii_uppercase());
+                    }
+                }
+            }
+            Expr::Reference {
+                sheet: Some(sheet), ..
+            }
+            | Expr::Name {
+                sheet: Some(sheet), ..
+            } if unknown_sheet.is_none() => {
+                // External workbooks, as in [1]Sheet1!A1, are not checked
+                if sheet.starts_with('[') {
+                    return;
+                }
+                unknown_sheet = sheet
+                    .split(':')
+                    .find(|sheet| !names.sheets.contains(&sheet.to_lowercase()))
+                    .map(str::to_string);
+            }
+            _ => {}
+        });
+        if let Some(sheet) = unknown_sheet {
+            return Err(format!("Unknown sheet '{}'", sheet));
+        }
+
+        let mut unknown = vec![];
+        let mut formula = format!("{}{}", open, equals);
+        for token in tokenize(body)? {
+            match token.kind {
+                TokenKind::Function => {
+                    let (name, user_defined) = split_name(token.text);
+                    match prefix(&name) {
+                        _ if user_defined => formula.push_str(token.text),
+                        Some(prefix) => {
+                            formula.push_str(prefix);
+                            formula.push_str(&name);
+                        }
+                        None if locals.contains(&name) || names.defined.contains(&name) => {
+                            formula.push_str(token.text)
+                        }
+                        None => {
+                            unknown.push(token.text.to_string());
+                            formula.push_str(token.text);
+                        }
+                    }
+                }
+                // rust_xlsxwriter leaves formulas with a prefix alone, so the
+                // table functions have to be expanded here
+                TokenKind::StructuredReference if self.use_table_functions => {
+                    formula.push_str(&token.text.replace('@', "[#This Row],"))
+                }
+                _ => formula.push_str(token.text),
+            }
+        }
+        formula.push_str(close);
+
+        self.formula = formula;
+        Ok(unknown)
+    }
+}
+
+impl From<NodeXlsxFormula> for Formula {
+    fn from(value: NodeXlsxFormula) -> Self {
+        let mut formula = Formula::new(value.formula);
+
+        if let Some(result) = value.result {
+            formula = formula.set_result(result.to_xlsx_string());
+        }
+
+        if value.use_future_functions {
+            formula = formula.use_future_functions();
+        }
+
+        if value.use_table_functions {
+            formula = formula.use_table_functions();
+        }
+
+        formula
+    }
+}
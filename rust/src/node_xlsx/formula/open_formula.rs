@@ -0,0 +1,86 @@
+use super::{
+    functions::split_name,
+    parser::unquote,
+    tokenizer::{tokenize, TokenKind},
+};
+
+/// An Excel formula as an OpenFormula one, like `of:=SUM([.A1:.B2])`. None
+/// when it uses something OpenDocument files cannot refer to: defined
+/// names, table references, user defined functions or spill ranges.
+pub fn to_open_formula(formula: &str) -> Option<String> {
+    let body = formula.strip_prefix('=').unwrap_or(formula);
+    let tokens = tokenize(body).ok()?;
+
+    let mut open_formula = String::from("of:=");
+    let mut arrays = 0;
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        match token.kind {
+            TokenKind::Sheet | TokenKind::Reference => {
+                let sheet = match token.kind {
+                    TokenKind::Sheet => {
+                        index += 1;
+                        Some(sheet_name(token.text))
+                    }
+                    _ => None,
+                };
+                let start = tokens
+                    .get(index)
+                    .filter(|token| token.kind == TokenKind::Reference)?;
+                open_formula.push('[');
+                if let Some(sheet) = sheet {
+                    open_formula.push_str(&format!("$'{}'", sheet.replace('\'', "''")));
+                }
+                open_formula.push('.');
+                open_formula.push_str(start.text);
+                match tokens.get(index + 1..index + 3) {
+                    Some([colon, end]) if colon.text == ":" && end.kind == TokenKind::Reference => {
+                        open_formula.push_str(":.");
+                        open_formula.push_str(end.text);
+                        index += 2;
+                    }
+                    _ => {}
+                }
+                open_formula.push(']');
+            }
+            TokenKind::Function => {
+                let (name, user_defined) = split_name(token.text);
+                if user_defined {
+                    return None;
+                }
+                open_formula.push_str(&name);
+            }
+            // Booleans are functions in OpenFormula
+            TokenKind::Boolean => {
+                open_formula.push_str(&token.text.to_ascii_uppercase());
+                open_formula.push_str("()");
+            }
+            // Arguments are separated by `;`, and so are the columns of arrays, whose rows are separated by `|`
+            TokenKind::Separator if arrays > 0 && token.text == ";" => open_formula.push('|'),
+            TokenKind::Separator => open_formula.push(';'),
+            TokenKind::ArrayOpen => {
+                arrays += 1;
+                open_formula.push('{');
+            }
+            TokenKind::ArrayClose => {
+                arrays -= 1;
+                open_formula.push('}');
+            }
+            TokenKind::Operator if token.text == "#" || token.text == "@" => return None,
+            TokenKind::Name | TokenKind::StructuredReference => return None,
+            _ => open_formula.push_str(token.text),
+        }
+        index += 1;
+    }
+    Some(open_formula)
+}
+
+/// The name of a sheet prefix such as `'Q1 data'!`.
+fn sheet_name(prefix: &str) -> String {
+    let name = prefix.strip_suffix('!').unwrap_or(prefix);
+    match name.starts_with('\'') {
+        true => unquote(name, '\''),
+        false => name.to_string(),
+    }
+}
@@ -0,0 +1,549 @@
+use super::{
+    reference::Reference,
+    tokenizer::{tokenize, Token, TokenKind},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Concat,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `:` between anything that is not two plain references
+    Range,
+    /// A space between two references
+    Intersect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Plus,
+    Minus,
+    /// Implicit intersection, `@A1:A10`
+    At,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    Error(String),
+    /// A cell or an area, `end` is set for `A1:B2`, `C:C` and `3:3`
+    Reference {
+        sheet: Option<String>,
+        start: Reference,
+        end: Option<Reference>,
+    },
+    /// A defined name, or a `LET`/`LAMBDA` variable
+    Name {
+        sheet: Option<String>,
+        name: String,
+    },
+    StructuredReference(String),
+    /// `name` is upper case, without `_xlfn.` style prefixes
+    Function {
+        name: String,
+        args: Vec<Expr>,
+    },
+    /// A call of the function an expression gives, as in `LAMBDA(x,x+1)(2)`
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+    },
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Percent(Box<Expr>),
+    /// The spill range operator, `A1#`
+    Spill(Box<Expr>),
+    /// `(A1,B2)`
+    Union(Vec<Expr>),
+    Array(Vec<Vec<Expr>>),
+    /// An omitted function argument, as in `IF(A1,,2)`
+    Missing,
+}
+
+impl Expr {
+    /// Calls `f` with this expression and every expression inside it.
+    pub fn walk(&self, f: &mut impl FnMut(&Expr)) {
+        f(self);
+        match self {
+            Expr::Function { args, .. } | Expr::Union(args) => {
+                args.iter().for_each(|arg| arg.walk(f));
+            }
+            Expr::Call { callee, args } => {
+                callee.walk(f);
+                args.iter().for_each(|arg| arg.walk(f));
+            }
+            Expr::Unary(_, operand) | Expr::Percent(operand) | Expr::Spill(operand) => {
+                operand.walk(f)
+            }
+            Expr::Binary(_, left, right) => {
+                left.walk(f);
+                right.walk(f);
+            }
+            Expr::Array(rows) => rows.iter().flatten().for_each(|item| item.walk(f)),
+            _ => {}
+        }
+    }
+}
+
+/// Parses a formula, with or without its leading `=`.
+pub fn parse(formula: &str) -> Result<Expr, String> {
+    let body = formula.strip_prefix('=').unwrap_or(formula);
+    let tokens = tokenize(body)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    parser.skip_whitespace();
+    if parser.position == parser.tokens.len() {
+        return Err("The formula is empty".to_string());
+    }
+    let expr = parser.expression()?;
+    parser.skip_whitespace();
+    match parser.position < parser.tokens.len() {
+        true => Err(parser.unexpected(parser.position)),
+        false => Ok(expr),
+    }
+}
+
+/// Removes the quotes of a quoted sheet name or string.
+pub fn unquote(text: &str, quote: char) -> String {
+    let doubled = format!("{}{}", quote, quote);
+    text[1..text.len() - 1].replace(&doubled, &quote.to_string())
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(Token {
+            kind: TokenKind::Whitespace,
+            ..
+        }) = self.tokens.get(self.position)
+        {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<Token<'a>> {
+        self.skip_whitespace();
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Result<Token<'a>, String> {
+        let token = self.peek().ok_or("Unexpected end of the formula")?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn peek_operator(&mut self, operators: &[&str]) -> Option<&'a str> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Operator && operators.contains(&token.text) => {
+                Some(token.text)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, text: &str) -> Result<(), String> {
+        match self.peek() {
+            Some(token) if token.kind == kind => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(_) => Err(format!(
+                "Expected '{}' but found {}",
+                text,
+                self.describe(self.position)
+            )),
+            None => Err(format!("Missing '{}'", text)),
+        }
+    }
+
+    /// The token at `index` and the position it starts at, one based.
+    fn describe(&self, index: usize) -> String {
+        let offset: usize = self.tokens[..index]
+            .iter()
+            .map(|token| token.text.chars().count())
+            .sum();
+        format!("'{}' at position {}", self.tokens[index].text, offset + 1)
+    }
+
+    fn unexpected(&self, index: usize) -> String {
+        if self.tokens[index].text == ";" {
+            return format!(
+                "Unexpected {}, arguments are separated with ','",
+                self.describe(index)
+            );
+        }
+        format!("Unexpected {}", self.describe(index))
+    }
+
+    fn binary(
+        &mut self,
+        operators: &[(&str, BinaryOp)],
+        operand: fn(&mut Self) -> Result<Expr, String>,
+    ) -> Result<Expr, String> {
+        let mut left = operand(self)?;
+        loop {
+            let op = match self.peek() {
+                Some(token) if token.kind == TokenKind::Operator => operators
+                    .iter()
+                    .find(|(text, _)| *text == token.text)
+                    .map(|(_, op)| *op),
+                _ => None,
+            };
+            let Some(op) = op else {
+                return Ok(left);
+            };
+            self.position += 1;
+            let right = operand(self)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        self.binary(
+            &[
+                ("=", BinaryOp::Eq),
+                ("<>", BinaryOp::Ne),
+                ("<", BinaryOp::Lt),
+                ("<=", BinaryOp::Le),
+                (">", BinaryOp::Gt),
+                (">=", BinaryOp::Ge),
+            ],
+            Self::concatenation,
+        )
+    }
+
+    fn concatenation(&mut self) -> Result<Expr, String> {
+        self.binary(&[("&", BinaryOp::Concat)], Self::additive)
+    }
+
+    fn additive(&mut self) -> Result<Expr, String> {
+        self.binary(
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            Self::multiplicative,
+        )
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, String> {
+        self.binary(&[("*", BinaryOp::Mul), ("/", BinaryOp::Div)], Self::power)
+    }
+
+    fn power(&mut self) -> Result<Expr, String> {
+        self.binary(&[("^", BinaryOp::Pow)], Self::unary)
+    }
+
+    /// Signs bind tighter than `^` in Excel, `-2^2` is 4.
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.peek_operator(&["-", "+"]) {
+            Some(text) => {
+                self.position += 1;
+                let op = match text {
+                    "-" => UnaryOp::Minus,
+                    _ => UnaryOp::Plus,
+                };
+                Ok(Expr::Unary(op, Box::new(self.unary()?)))
+            }
+            None => self.percent(),
+        }
+    }
+
+    fn percent(&mut self) -> Result<Expr, String> {
+        let mut expr = self.intersection()?;
+        while self.peek_operator(&["%"]).is_some() {
+            self.position += 1;
+            expr = Expr::Percent(Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    fn intersection(&mut self) -> Result<Expr, String> {
+        let mut left = self.range()?;
+        loop {
+            // Looking for `:` and `#` after the operand already skipped the space
+            let is_space =
+                self.position > 0 && self.tokens[self.position - 1].kind == TokenKind::Whitespace;
+            let starts_operand = matches!(
+                self.peek().map(|token| token.kind),
+                Some(
+                    TokenKind::Reference
+                        | TokenKind::Sheet
+                        | TokenKind::Name
+                        | TokenKind::Function
+                        | TokenKind::StructuredReference
+                        | TokenKind::Open
+                )
+            );
+            if !is_space || !starts_operand {
+                return Ok(left);
+            }
+            let right = self.range()?;
+            left = Expr::Binary(BinaryOp::Intersect, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn range(&mut self) -> Result<Expr, String> {
+        let mut left = self.primary()?;
+        while self.peek_operator(&[":"]).is_some() {
+            self.position += 1;
+            let right = self.primary()?;
+            left = match (left, right) {
+                (
+                    Expr::Reference {
+                        sheet,
+                        start,
+                        end: None,
+                    },
+                    Expr::Reference {
+                        sheet: None,
+                        start: end,
+                        end: None,
+                    },
+                ) => Expr::Reference {
+                    sheet,
+                    start,
+                    end: Some(end),
+                },
+                (left, right) => Expr::Binary(BinaryOp::Range, Box::new(left), Box::new(right)),
+            };
+        }
+        if self.peek_operator(&["#"]).is_some() {
+            self.position += 1;
+            left = Expr::Spill(Box::new(left));
+        }
+        Ok(left)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self.next()?;
+        let index = self.position - 1;
+        match token.kind {
+            TokenKind::Number => token
+                .text
+                .parse::<f64>()
+                .map(Expr::Number)
+                .map_err(|_| format!("Invalid number {}", self.describe(index))),
+            TokenKind::Text => Ok(Expr::Text(unquote(token.text, '"'))),
+            TokenKind::Boolean => Ok(Expr::Boolean(token.text.eq_ignore_ascii_case("TRUE"))),
+            TokenKind::Error => Ok(Expr::Error(token.text.to_ascii_uppercase())),
+            TokenKind::Reference => self.reference(None, index),
+            TokenKind::Name => self.name(None, token),
+            TokenKind::Sheet => {
+                let sheet = sheet_name(token.text);
+                self.sheet_item(sheet)
+            }
+            TokenKind::StructuredReference => match self.tokens.get(self.position) {
+                // An external workbook, as in [1]Sheet1!A1
+                Some(Token {
+                    kind: TokenKind::Sheet,
+                    text,
+                }) => {
+                    let sheet = format!("{}{}", token.text, sheet_name(text));
+                    self.position += 1;
+                    self.sheet_item(sheet)
+                }
+                _ => Ok(Expr::StructuredReference(token.text.to_string())),
+            },
+            TokenKind::Function => {
+                let function = self.function(token)?;
+                self.calls(function)
+            }
+            TokenKind::Operator if token.text == "@" => {
+                Ok(Expr::Unary(UnaryOp::At, Box::new(self.range()?)))
+            }
+            TokenKind::Open => {
+                let mut items = vec![self.expression()?];
+                while let Some(Token {
+                    kind: TokenKind::Separator,
+                    text: ",",
+                }) = self.peek()
+                {
+                    self.position += 1;
+                    items.push(self.expression()?);
+                }
+                self.expect(TokenKind::Close, ")")?;
+                match items.len() {
+                    1 => self.calls(items.remove(0)),
+                    _ => Ok(Expr::Union(items)),
+                }
+            }
+            TokenKind::ArrayOpen => self.array(),
+            _ => Err(self.unexpected(index)),
+        }
+    }
+
+    /// The reference or name after a sheet prefix.
+    fn sheet_item(&mut self, sheet: String) -> Result<Expr, String> {
+        let token = self.next()?;
+        let index = self.position - 1;
+        match token.kind {
+            TokenKind::Reference => self.reference(Some(sheet), index),
+            TokenKind::Name => self.name(Some(sheet), token),
+            TokenKind::Error => Ok(Expr::Error(token.text.to_ascii_uppercase())),
+            _ => Err(self.unexpected(index)),
+        }
+    }
+
+    fn reference(&mut self, sheet: Option<String>, index: usize) -> Result<Expr, String> {
+        let start = Reference::parse(self.tokens[index].text)
+            .ok_or_else(|| format!("Invalid reference {}", self.describe(index)))?;
+        Ok(Expr::Reference {
+            sheet,
+            start,
+            end: None,
+        })
+    }
+
+    fn name(&mut self, sheet: Option<String>, token: Token) -> Result<Expr, String> {
+        // The first sheet of a 3D reference, as in Sheet1:Sheet3!A1
+        if let [Token { text: ":", .. }, Token {
+            kind: TokenKind::Sheet,
+            text,
+        }, ..] = &self.tokens[self.position..]
+        {
+            if sheet.is_none() {
+                let sheet = format!("{}:{}", token.text, sheet_name(text));
+                self.position += 2;
+                return self.sheet_item(sheet);
+            }
+        }
+        Ok(Expr::Name {
+            sheet,
+            name: token.text.to_string(),
+        })
+    }
+
+    fn function(&mut self, token: Token) -> Result<Expr, String> {
+        let args = self.arguments(token.text)?;
+        let (name, _) = super::functions::split_name(token.text);
+        Ok(Expr::Function { name, args })
+    }
+
+    /// Calls of the function an expression gives, when arguments follow it
+    /// right away.
+    fn calls(&mut self, mut callee: Expr) -> Result<Expr, String> {
+        while let Some(Token {
+            kind: TokenKind::Open,
+            ..
+        }) = self.tokens.get(self.position)
+        {
+            let args = self.arguments("the call")?;
+            callee = Expr::Call {
+                callee: Box::new(callee),
+                args,
+            };
+        }
+        Ok(callee)
+    }
+
+    /// The parenthesised arguments of a call, `what` names it in errors.
+    fn arguments(&mut self, what: &str) -> Result<Vec<Expr>, String> {
+        self.expect(TokenKind::Open, "(")?;
+        let mut args = vec![];
+        if let Some(Token {
+            kind: TokenKind::Close,
+            ..
+        }) = self.peek()
+        {
+            self.position += 1;
+        } else {
+            loop {
+                let arg = match self.peek() {
+                    Some(Token {
+                        kind: TokenKind::Separator | TokenKind::Close,
+                        ..
+                    }) => Expr::Missing,
+                    _ => self.expression()?,
+                };
+                args.push(arg);
+                let next = self
+                    .next()
+                    .map_err(|_| format!("Missing ')' to close {}", what))?;
+                match next {
+                    Token {
+                        kind: TokenKind::Separator,
+                        text: ",",
+                    } => continue,
+                    Token {
+                        kind: TokenKind::Close,
+                        ..
+                    } => break,
+                    _ => return Err(self.unexpected(self.position - 1)),
+                }
+            }
+        }
+        Ok(args)
+    }
+
+    fn array(&mut self) -> Result<Expr, String> {
+        let mut rows = vec![vec![]];
+        loop {
+            let negative = self.peek_operator(&["-"]).is_some();
+            if negative {
+                self.position += 1;
+            }
+            let token = self.next().map_err(|_| "Missing '}'".to_string())?;
+            let index = self.position - 1;
+            let item = match (token.kind, negative) {
+                (TokenKind::Number, _) => {
+                    let number: f64 = token
+                        .text
+                        .parse()
+                        .map_err(|_| format!("Invalid number {}", self.describe(index)))?;
+                    Expr::Number(if negative { -number } else { number })
+                }
+                (TokenKind::Text, false) => Expr::Text(unquote(token.text, '"')),
+                (TokenKind::Boolean, false) => {
+                    Expr::Boolean(token.text.eq_ignore_ascii_case("TRUE"))
+                }
+                (TokenKind::Error, false) => Expr::Error(token.text.to_ascii_uppercase()),
+                _ => {
+                    let error =
+                        format!("Arrays only hold constants, found {}", self.describe(index));
+                    return Err(error);
+                }
+            };
+            if let Some(row) = rows.last_mut() {
+                row.push(item);
+            }
+
+            let next = self.next().map_err(|_| "Missing '}'".to_string())?;
+            match (next.kind, next.text) {
+                (TokenKind::Separator, ",") => {}
+                (TokenKind::Separator, _) => rows.push(vec![]),
+                (TokenKind::ArrayClose, _) => break,
+                _ => return Err(self.unexpected(self.position - 1)),
+            }
+        }
+        let columns = rows[0].len();
+        if rows.iter().any(|row| row.len() != columns) {
+            return Err("The rows of an array must have the same length".to_string());
+        }
+        Ok(Expr::Array(rows))
+    }
+}
+
+/// The sheet name of a `Sheet1!` or `'My sheet'!` token.
+fn sheet_name(text: &str) -> String {
+    let text = text.strip_suffix('!').unwrap_or(text);
+    if text.starts_with('\'') {
+        unquote(text, '\'')
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use rust_xlsxwriter::utility::column_number_to_name;
+
+use crate::node_xlsx::cell_range::{COLUMN_COUNT, ROW_COUNT};
+
+/// A row or column index, fixed by a `$` when absolute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Anchor {
+    pub index: u32,
+    pub absolute: bool,
+}
+
+/// One side of an A1 reference: a cell (`$B2`), a column (`B`) or a row (`2`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reference {
+    pub col: Option<Anchor>,
+    pub row: Option<Anchor>,
+}
+
+impl Reference {
+    /// Parses a reference such as `"$B$2"`, `"B"` or `"2"`. Indexes are zero based.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (col_absolute, rest) = match text.strip_prefix('$') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let letters = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (letters, rest) = rest.split_at(letters);
+
+        let (row_absolute, digits) = match rest.strip_prefix('$') {
+            Some(digits) if !letters.is_empty() => (true, digits),
+            _ if letters.is_empty() => (col_absolute, rest),
+            _ => (false, rest),
+        };
+        if letters.len() > 3 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let col = if letters.is_empty() {
+            None
+        } else {
+            let index = letters.chars().fold(0, |col, c| {
+                col * 26 + (c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+            }) - 1;
+            if index >= COLUMN_COUNT as u32 {
+                return None;
+            }
+            Some(Anchor {
+                index,
+                absolute: col_absolute,
+            })
+        };
+        let row = if digits.is_empty() {
+            None
+        } else {
+            let index = digits.parse::<u32>().ok()?.checked_sub(1)?;
+            if index >= ROW_COUNT {
+                return None;
+            }
+            Some(Anchor {
+                index,
+                absolute: row_absolute,
+            })
+        };
+        if col.is_none() && row.is_none() {
+            return None;
+        }
+        Some(Self { col, row })
+    }
+
+    pub fn is_cell(&self) -> bool {
+        self.col.is_some() && self.row.is_some()
+    }
+
+    /// Moves the relative parts by `rows` and `cols`, the way Excel's fill
+    /// handle does. Returns None when the reference falls off the sheet.
+    pub fn shift(&self, rows: i64, cols: i64) -> Option<Self> {
+        let shift = |anchor: Option<Anchor>, by: i64, count: u32| match anchor {
+            Some(anchor) if !anchor.absolute => {
+                let index = anchor.index as i64 + by;
+                if !(0..count as i64).contains(&index) {
+                    return Err(());
+                }
+                Ok(Some(Anchor {
+                    index: index as u32,
+                    absolute: false,
+                }))
+            }
+            anchor => Ok(anchor),
+        };
+        Some(Self {
+            col: shift(self.col, cols, COLUMN_COUNT as u32).ok()?,
+            row: shift(self.row, rows, ROW_COUNT).ok()?,
+        })
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(col) = self.col {
+            if col.absolute {
+                f.write_str("$")?;
+            }
+            f.write_str(&column_number_to_name(col.index as u16))?;
+        }
+        if let Some(row) = self.row {
+            if row.absolute {
+                f.write_str("$")?;
+            }
+            write!(f, "{}", row.index + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    result::NeonResult,
+    types::{JsBoolean, JsNumber, JsString, JsValue},
+};
+
+use super::value::{error_code, Value};
+use crate::node_xlsx::{cell_range::CellRange, xml::escape};
+
+/// The cached result of a formula, kept with its type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaResult {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    /// An error value such as `#N/A`
+    Error(&'static str),
+}
+
+impl FormulaResult {
+    /// Reads a number, a boolean or a string. Strings naming an Excel error,
+    /// like `"#N/A"`, are errors.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        if let Ok(number) = value.downcast::<JsNumber, _>(cx) {
+            let number = number.value(cx);
+            if !number.is_finite() {
+                return cx.throw_error(format!("Invalid formula result: {}", number));
+            }
+            return Ok(Self::Number(number));
+        }
+        if let Ok(boolean) = value.downcast::<JsBoolean, _>(cx) {
+            return Ok(Self::Boolean(boolean.value(cx)));
+        }
+        let text = value.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+        Ok(match error_code(&text) {
+            Some(error) => Self::Error(error),
+            None => Self::Text(text),
+        })
+    }
+
+    /// The result as rust_xlsxwriter stores it.
+    pub fn to_xlsx_string(&self) -> String {
+        match self {
+            Self::Number(number) => number.to_string(),
+            Self::Text(text) => text.clone(),
+            Self::Boolean(true) => "TRUE".to_string(),
+            Self::Boolean(false) => "FALSE".to_string(),
+            Self::Error(error) => error.to_string(),
+        }
+    }
+
+    /// rust_xlsxwriter types a result by whether it parses as a number, so
+    /// booleans, errors and numeric text need their type fixed afterwards.
+    pub fn needs_type_fix(&self) -> bool {
+        match self {
+            Self::Number(_) => false,
+            Self::Text(text) => text.parse::<f64>().is_ok(),
+            Self::Boolean(_) | Self::Error(_) => true,
+        }
+    }
+
+    /// The `t` attribute and the `<v>` text of the cell.
+    pub fn cell_type_and_value(&self) -> (&'static str, String) {
+        match self {
+            Self::Number(number) => ("", number.to_string()),
+            Self::Text(text) => (" t=\"str\"", escape(text)),
+            Self::Boolean(boolean) => (" t=\"b\"", (*boolean as u8).to_string()),
+            Self::Error(error) => (" t=\"e\"", error.to_string()),
+        }
+    }
+}
+
+impl From<&FormulaResult> for Value {
+    fn from(result: &FormulaResult) -> Self {
+        match result {
+            FormulaResult::Number(number) => Value::Number(*number),
+            FormulaResult::Text(text) => Value::Text(text.clone()),
+            FormulaResult::Boolean(boolean) => Value::Boolean(*boolean),
+            FormulaResult::Error(error) => Value::Error(error),
+        }
+    }
+}
+
+/// Rewrites the formula cells of a worksheet part whose results rust_xlsxwriter
+/// stored with the wrong type.
+pub fn fix_result_types(xml: &str, results: &HashMap<(u32, u16), FormulaResult>) -> String {
+    let mut fixed = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find("<c r=\"") {
+        fixed.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+        // Blank cells close their opening tag
+        let end = match rest[..open_end].ends_with('/') {
+            true => Some(open_end + 1),
+            false => rest.find("</c>").map(|end| end + "</c>".len()),
+        };
+        let Some(end) = end else {
+            break;
+        };
+        let cell = &rest[..end];
+        match cell_position(cell).and_then(|position| results.get(&position)) {
+            Some(result) if cell.contains("<f") => fixed.push_str(&fix_cell(cell, result)),
+            _ => fixed.push_str(cell),
+        }
+        rest = &rest[end..];
+    }
+    fixed.push_str(rest);
+    fixed
+}
+
+/// The row and column of a `<c r="B2" ...>` element.
+fn cell_position(cell: &str) -> Option<(u32, u16)> {
+    let reference = cell["<c r=\"".len()..].split('"').next()?;
+    CellRange::parse_cell(reference).ok()
+}
+
+fn fix_cell(cell: &str, result: &FormulaResult) -> String {
+    let (cell_type, value) = result.cell_type_and_value();
+    let open_end = cell.find('>').unwrap_or(cell.len());
+    let open = cell[..open_end].replace(" t=\"str\"", "");
+    let body = &cell[open_end..];
+    let body = match (body.find("<v>"), body.rfind("</v>")) {
+        (Some(start), Some(end)) => format!("{}<v>{}{}", &body[..start], value, &body[end..]),
+        _ => body.to_string(),
+    };
+    format!("{}{}{}", open, cell_type, body)
+}
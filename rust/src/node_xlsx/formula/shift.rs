@@ -0,0 +1,80 @@
+use super::{
+    reference::Reference,
+    tokenizer::{tokenize, TokenKind},
+};
+
+enum Part {
+    Text(String),
+    Reference(Reference),
+    /// Both sides of `A1:B2`, which turns into a single `#REF!` as a whole
+    Range(Reference, Reference),
+}
+
+/// A formula tokenized once, to be written again with its relative
+/// references moved, like Excel's fill handle does.
+pub struct ShiftTemplate {
+    parts: Vec<Part>,
+}
+
+impl ShiftTemplate {
+    pub fn new(formula: &str) -> Result<Self, String> {
+        let (prefix, body) = match formula.strip_prefix('=') {
+            Some(body) => ("=", body),
+            None => ("", formula),
+        };
+        let tokens = tokenize(body)?;
+
+        let mut parts = vec![Part::Text(prefix.to_string())];
+        let mut index = 0;
+        while index < tokens.len() {
+            let token = tokens[index];
+            let reference = match token.kind {
+                TokenKind::Reference => Reference::parse(token.text),
+                _ => None,
+            };
+            let Some(reference) = reference else {
+                parts.push(Part::Text(token.text.to_string()));
+                index += 1;
+                continue;
+            };
+
+            let end = match tokens.get(index + 1..index + 3) {
+                Some([colon, end]) if colon.text == ":" && end.kind == TokenKind::Reference => {
+                    Reference::parse(end.text)
+                }
+                _ => None,
+            };
+            match end {
+                Some(end) => {
+                    parts.push(Part::Range(reference, end));
+                    index += 3;
+                }
+                None => {
+                    parts.push(Part::Reference(reference));
+                    index += 1;
+                }
+            }
+        }
+        Ok(Self { parts })
+    }
+
+    /// The formula with its relative references moved by `rows` and `cols`.
+    /// References that fall off the sheet become `#REF!`.
+    pub fn shifted(&self, rows: i64, cols: i64) -> String {
+        let mut formula = String::new();
+        for part in self.parts.iter() {
+            match part {
+                Part::Text(text) => formula.push_str(text),
+                Part::Reference(reference) => match reference.shift(rows, cols) {
+                    Some(reference) => formula.push_str(&reference.to_string()),
+                    None => formula.push_str("#REF!"),
+                },
+                Part::Range(start, end) => match (start.shift(rows, cols), end.shift(rows, cols)) {
+                    (Some(start), Some(end)) => formula.push_str(&format!("{}:{}", start, end)),
+                    _ => formula.push_str("#REF!"),
+                },
+            }
+        }
+        formula
+    }
+}
@@ -0,0 +1,240 @@
+use super::reference::Reference;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Number,
+    /// A string literal, quotes included
+    Text,
+    Boolean,
+    /// An error literal such as `#N/A`
+    Error,
+    /// A cell, or one side of a whole column (`C:C`) or whole row (`3:3`) range
+    Reference,
+    /// A sheet prefix, `!` included
+    Sheet,
+    /// A function name, the `(` is the next token
+    Function,
+    /// A defined name or any other identifier
+    Name,
+    /// A table reference such as `Table1[Column]`
+    StructuredReference,
+    Operator,
+    /// `,` or `;`
+    Separator,
+    Open,
+    Close,
+    ArrayOpen,
+    ArrayClose,
+    Whitespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+}
+
+const ERRORS: [&str; 8] = [
+    "#NULL!",
+    "#DIV/0!",
+    "#VALUE!",
+    "#REF!",
+    "#NAME?",
+    "#NUM!",
+    "#N/A",
+    "#GETTING_DATA",
+];
+
+/// Splits a formula, without its leading `=`, into tokens. Joining the text
+/// of the tokens gives back the formula.
+pub fn tokenize(formula: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut position = 0;
+    while position < formula.len() {
+        let rest = &formula[position..];
+        let c = rest.chars().next().unwrap_or_default();
+        let previous = tokens.last().map(|token| token.kind);
+        let after_colon = matches!(
+            tokens.as_slice(),
+            [
+                ..,
+                Token {
+                    kind: TokenKind::Reference,
+                    ..
+                },
+                Token { text: ":", .. }
+            ]
+        );
+
+        let range_side = match c.is_ascii_alphanumeric() || c == '$' {
+            true => range_side_length(rest, after_colon),
+            false => None,
+        };
+
+        let (kind, length) = match c {
+            '"' => (
+                TokenKind::Text,
+                quoted_length(rest, '"').ok_or("Unterminated string")?,
+            ),
+            '\'' => {
+                let length = quoted_length(rest, '\'').ok_or("Unterminated sheet name")?;
+                if !rest[length..].starts_with('!') {
+                    return Err(format!("Expected '!' after {}", &rest[..length]));
+                }
+                (TokenKind::Sheet, length + 1)
+            }
+            '#' if matches!(
+                previous,
+                Some(TokenKind::Reference) | Some(TokenKind::Close)
+            ) =>
+            {
+                // Spill range operator, as in A1#
+                (TokenKind::Operator, 1)
+            }
+            '#' => {
+                let upper = rest.to_ascii_uppercase();
+                match ERRORS.iter().find(|error| upper.starts_with(*error)) {
+                    Some(error) => (TokenKind::Error, error.len()),
+                    None => {
+                        return Err(format!("Unknown error value at position {}", position + 1))
+                    }
+                }
+            }
+            '[' => {
+                let length = bracket_length(rest).ok_or("Unbalanced '['")?;
+                (TokenKind::StructuredReference, length)
+            }
+            _ if range_side.is_some() => (TokenKind::Reference, range_side.unwrap_or_default()),
+            '0'..='9' | '.' => (TokenKind::Number, number_length(rest)),
+            c if c.is_alphabetic() || c == '$' || c == '_' || c == '\\' => identifier(rest)?,
+            '<' if rest.starts_with("<=") || rest.starts_with("<>") => (TokenKind::Operator, 2),
+            '>' if rest.starts_with(">=") => (TokenKind::Operator, 2),
+            '+' | '-' | '*' | '/' | '^' | '&' | '%' | '@' | ':' | '=' | '<' | '>' => {
+                (TokenKind::Operator, 1)
+            }
+            ',' | ';' => (TokenKind::Separator, 1),
+            '(' => (TokenKind::Open, 1),
+            ')' => (TokenKind::Close, 1),
+            '{' => (TokenKind::ArrayOpen, 1),
+            '}' => (TokenKind::ArrayClose, 1),
+            c if c.is_whitespace() => {
+                let length = rest
+                    .find(|c: char| !c.is_whitespace())
+                    .unwrap_or(rest.len());
+                (TokenKind::Whitespace, length)
+            }
+            c => return Err(format!("Unexpected '{}' at position {}", c, position + 1)),
+        };
+
+        tokens.push(Token {
+            kind,
+            text: &rest[..length],
+        });
+        position += length;
+    }
+    Ok(tokens)
+}
+
+fn identifier(rest: &str) -> Result<(TokenKind, usize), String> {
+    let length = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '$' | '_' | '.' | '\\' | '?')))
+        .unwrap_or(rest.len());
+    let text = &rest[..length];
+    let after = &rest[length..];
+
+    if after.starts_with('(') {
+        return Ok((TokenKind::Function, length));
+    }
+    if after.starts_with('!') {
+        return Ok((TokenKind::Sheet, length + 1));
+    }
+    if after.starts_with('[') {
+        let brackets = bracket_length(after).ok_or("Unbalanced '['")?;
+        return Ok((TokenKind::StructuredReference, length + brackets));
+    }
+    if text.eq_ignore_ascii_case("TRUE") || text.eq_ignore_ascii_case("FALSE") {
+        return Ok((TokenKind::Boolean, length));
+    }
+    match Reference::parse(text) {
+        Some(reference) if reference.is_cell() => Ok((TokenKind::Reference, length)),
+        _ => Ok((TokenKind::Name, length)),
+    }
+}
+
+/// Length of one side of a whole column (`$C:D`) or whole row (`3:$5`)
+/// range when `rest` starts with one. The first side has to be followed by
+/// `:` and a second side, the second one follows a reference and `:`.
+fn range_side_length(rest: &str, after_colon: bool) -> Option<usize> {
+    let side = |text: &str| {
+        let length = text
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '$'))
+            .unwrap_or(text.len());
+        let reference = Reference::parse(&text[..length])?;
+        if reference.is_cell() {
+            return None;
+        }
+        Some(length)
+    };
+    let length = side(rest)?;
+    if !after_colon {
+        let after = rest[length..].strip_prefix(':')?;
+        side(after)?;
+    }
+    Some(length)
+}
+
+fn number_length(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    let mut length = 0;
+    while length < bytes.len() && (bytes[length].is_ascii_digit() || bytes[length] == b'.') {
+        length += 1;
+    }
+    if length < bytes.len() && (bytes[length] == b'e' || bytes[length] == b'E') {
+        let mut exponent = length + 1;
+        if exponent < bytes.len() && (bytes[exponent] == b'+' || bytes[exponent] == b'-') {
+            exponent += 1;
+        }
+        let digits = exponent;
+        while exponent < bytes.len() && bytes[exponent].is_ascii_digit() {
+            exponent += 1;
+        }
+        if exponent > digits {
+            length = exponent;
+        }
+    }
+    length
+}
+
+/// Length of a literal opened by `quote`, where a doubled quote is escaped.
+fn quoted_length(rest: &str, quote: char) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1).peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != quote {
+            continue;
+        }
+        match chars.peek() {
+            Some((_, next)) if *next == quote => {
+                chars.next();
+            }
+            _ => return Some(index + 1),
+        }
+    }
+    None
+}
+
+fn bracket_length(rest: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
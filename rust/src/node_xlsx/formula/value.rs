@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+use super::result::FormulaResult;
+
+/// A cell value as the evaluator sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    /// An error value such as `#DIV/0!`
+    Error(&'static str),
+    Empty,
+}
+
+pub const DIV0: &str = "#DIV/0!";
+pub const VALUE: &str = "#VALUE!";
+pub const NA: &str = "#N/A";
+pub const NUM: &str = "#NUM!";
+pub const REF: &str = "#REF!";
+pub const NAME: &str = "#NAME?";
+pub const NULL: &str = "#NULL!";
+
+/// Gives the `&'static str` for an error literal of a formula.
+pub fn error_code(text: &str) -> Option<&'static str> {
+    [DIV0, VALUE, NA, NUM, REF, NAME, NULL]
+        .into_iter()
+        .find(|code| code.eq_ignore_ascii_case(text))
+}
+
+impl Value {
+    /// The value as a number, the way arithmetic operators convert it.
+    pub fn to_number(&self) -> Result<f64, &'static str> {
+        match self {
+            Value::Number(number) => Ok(*number),
+            Value::Boolean(boolean) => Ok(*boolean as u8 as f64),
+            Value::Empty => Ok(0.0),
+            Value::Text(text) => parse_number(text).ok_or(VALUE),
+            Value::Error(error) => Err(error),
+        }
+    }
+
+    /// The value as text, the way `&` converts it.
+    pub fn to_text(&self) -> Result<String, &'static str> {
+        match self {
+            Value::Number(number) => Ok(number_to_string(*number)),
+            Value::Text(text) => Ok(text.clone()),
+            Value::Boolean(boolean) => Ok(boolean_to_string(*boolean).to_string()),
+            Value::Empty => Ok(String::new()),
+            Value::Error(error) => Err(error),
+        }
+    }
+
+    /// The value as a condition, the way `IF` converts it.
+    pub fn to_boolean(&self) -> Result<bool, &'static str> {
+        match self {
+            Value::Boolean(boolean) => Ok(*boolean),
+            Value::Number(number) => Ok(*number != 0.0),
+            Value::Empty => Ok(false),
+            Value::Text(text) if text.eq_ignore_ascii_case("TRUE") => Ok(true),
+            Value::Text(text) if text.eq_ignore_ascii_case("FALSE") => Ok(false),
+            Value::Text(_) => Err(VALUE),
+            Value::Error(error) => Err(error),
+        }
+    }
+
+    /// Orders two values like Excel's comparison operators: numbers before
+    /// text before booleans, text without case. An empty cell takes the
+    /// empty value of the other side's type.
+    pub fn compare(&self, other: &Value) -> Ordering {
+        fn rank(value: &Value) -> u8 {
+            match value {
+                Value::Number(_) | Value::Empty => 0,
+                Value::Text(_) => 1,
+                Value::Boolean(_) => 2,
+                Value::Error(_) => 3,
+            }
+        }
+        match (self, other) {
+            (Value::Empty, Value::Text(text)) => "".cmp(text.to_lowercase().as_str()),
+            (Value::Text(text), Value::Empty) => text.to_lowercase().as_str().cmp(""),
+            (Value::Empty, Value::Boolean(boolean)) => false.cmp(boolean),
+            (Value::Boolean(boolean), Value::Empty) => boolean.cmp(&false),
+            (Value::Text(left), Value::Text(right)) => {
+                left.to_lowercase().cmp(&right.to_lowercase())
+            }
+            (Value::Boolean(left), Value::Boolean(right)) => left.cmp(right),
+            (left, right) if rank(left) == 0 && rank(right) == 0 => {
+                let left = left.to_number().unwrap_or_default();
+                let right = right.to_number().unwrap_or_default();
+                left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+            }
+            (left, right) => rank(left).cmp(&rank(right)),
+        }
+    }
+
+    /// The value stored as the cached result of a formula.
+    pub fn to_result(&self) -> FormulaResult {
+        match self {
+            Value::Number(number) => {
+                FormulaResult::Number(parse_number(&number_to_string(*number)).unwrap_or(*number))
+            }
+            Value::Text(text) => FormulaResult::Text(text.clone()),
+            Value::Boolean(boolean) => FormulaResult::Boolean(*boolean),
+            Value::Error(error) => FormulaResult::Error(error),
+            Value::Empty => FormulaResult::Number(0.0),
+        }
+    }
+}
+
+fn boolean_to_string(boolean: bool) -> &'static str {
+    match boolean {
+        true => "TRUE",
+        false => "FALSE",
+    }
+}
+
+/// Parses text the way Excel turns it into a number: surrounding spaces,
+/// a sign, a thousands separator or a trailing `%` are allowed.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let (text, scale) = match text.strip_suffix('%') {
+        Some(text) => (text.trim_end(), 0.01),
+        None => (text, 1.0),
+    };
+    let text = text.replace(',', "");
+    let number: f64 = text.parse().ok()?;
+    match number.is_finite() {
+        true => Some(number * scale),
+        false => None,
+    }
+}
+
+/// Writes a number with the 15 significant digits Excel keeps, so that
+/// `0.1+0.2` gives `0.3`.
+pub fn number_to_string(number: f64) -> String {
+    if number == 0.0 {
+        return "0".to_string();
+    }
+    let rounded: f64 = format!("{:.14e}", number).parse().unwrap_or(number);
+    rounded.to_string()
+}
+
+fn epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1899, 12, 30)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .unwrap_or_default()
+}
+
+/// The Excel serial number of a date, days since 1899-12-30 with the time
+/// as the fraction. Excel counts a 29 February 1900 that never was, so the
+/// days before March 1900 are one lower.
+pub fn date_to_serial(date: NaiveDateTime) -> f64 {
+    let duration = date - epoch();
+    let serial = duration.num_milliseconds() as f64 / 86_400_000.0;
+    match (1.0..61.0).contains(&serial) {
+        true => serial - 1.0,
+        false => serial,
+    }
+}
+
+/// The date of an Excel serial number, None when it is out of Excel's range
+/// or is Excel's 29 February 1900.
+pub fn serial_to_date(serial: f64) -> Option<NaiveDateTime> {
+    if !(0.0..2_958_466.0).contains(&serial) || (60.0..61.0).contains(&serial) {
+        return None;
+    }
+    let serial = match (1.0..60.0).contains(&serial) {
+        true => serial + 1.0,
+        false => serial,
+    };
+    let milliseconds = (serial * 86_400_000.0).round() as i64;
+    Some(epoch() + Duration::milliseconds(milliseconds))
+}
+
+/// The serial number of a year, month and day, where months and days out of
+/// range roll over like they do in `DATE`.
+pub fn serial_from_parts(year: i64, month: i64, day: i64) -> Option<f64> {
+    let year = if (0..1900).contains(&year) {
+        year + 1900
+    } else {
+        year
+    };
+    let months = year * 12 + month - 1;
+    let first = NaiveDate::from_ymd_opt(
+        months.div_euclid(12) as i32,
+        months.rem_euclid(12) as u32 + 1,
+        1,
+    )?;
+    let date = first.checked_add_signed(Duration::days(day - 1))?;
+    let serial = date_to_serial(date.and_hms_opt(0, 0, 0)?);
+    match serial >= 0.0 && date.year() <= 9999 {
+        true => Some(serial),
+        false => None,
+    }
+}
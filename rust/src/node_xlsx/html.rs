@@ -0,0 +1,450 @@
+use std::collections::{HashMap, HashSet};
+
+use neon::{
+    context::FunctionContext,
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsBoolean, JsObject},
+};
+use rust_xlsxwriter::{FormatAlign, FormatUnderline};
+
+use super::{
+    border::Border,
+    color::{tint, Color, THEME_COLORS},
+    error::NodeXlsxError,
+    export::{export_value, sheet_index, ExportValue},
+    format::{NodeXlsxFormat, ThemeFont},
+    sheet::{evaluate_formulas, GridTable, GridValue, SheetGrid},
+    xml::{escape, escape_attribute},
+    NodeXlsxWorkbook,
+};
+
+/// The size of the rows and columns Excel gives a sheet, in pixels.
+const DEFAULT_ROW_HEIGHT: f64 = 20.0;
+const DEFAULT_COLUMN_WIDTH: f64 = 64.0;
+
+const GRID_LINE: &str = "1px solid #D4D4D4";
+
+/// One sheet of a workbook as an HTML table. Every style is written inline
+/// on the elements, so the table can go into email bodies that drop style
+/// sheets.
+pub struct NodeXlsxHtml {
+    workbook: NodeXlsxWorkbook,
+    sheet: usize,
+    document: bool,
+    grid_lines: bool,
+}
+
+impl NodeXlsxHtml {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        workbook: Handle<JsObject>,
+        obj: Handle<JsObject>,
+    ) -> NeonResult<Self> {
+        let mut workbook = NodeXlsxWorkbook::from_js_object(cx, workbook)?;
+        let sheet = sheet_index(cx, obj, &workbook)?;
+
+        let document: Option<Handle<JsBoolean>> = obj.get_opt(cx, "document")?;
+        let document = document.map(|document| document.value(cx)).unwrap_or(false);
+        let grid_lines: Option<Handle<JsBoolean>> = obj.get_opt(cx, "gridLines")?;
+        let grid_lines = grid_lines
+            .map(|grid_lines| grid_lines.value(cx))
+            .unwrap_or(false);
+
+        // Formulas are shown with their results, computed where they are missing
+        evaluate_formulas(&mut workbook.sheets);
+
+        Ok(Self {
+            workbook,
+            sheet,
+            document,
+            grid_lines,
+        })
+    }
+
+    /// The table, or a whole document holding it when `document` is set.
+    pub fn to_string(&self) -> Result<String, NodeXlsxError> {
+        let sheet = &self.workbook.sheets[self.sheet];
+        let grid = sheet.grid()?;
+        let mut html = String::new();
+        if self.document {
+            html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+            html.push_str(&format!("<title>{}</title>\n", escape(sheet.name())));
+            html.push_str("</head>\n<body>\n");
+        }
+        self.write_table(&mut html, &grid);
+        if self.document {
+            html.push_str("</body>\n</html>\n");
+        }
+        Ok(html)
+    }
+
+    fn write_table(&self, html: &mut String, grid: &SheetGrid) {
+        let (last_row, last_col) = grid.last_cell().unwrap_or((0, 0));
+        let rows: Vec<u32> = (0..=last_row)
+            .filter(|row| !grid.rows.get(row).is_some_and(|line| line.hidden))
+            .collect();
+        let columns: Vec<u16> = (0..=last_col)
+            .filter(|col| !grid.columns.get(col).is_some_and(|line| line.hidden))
+            .collect();
+        let widths: Vec<f64> = columns
+            .iter()
+            .map(|col| {
+                let line = grid.columns.get(col);
+                line.and_then(|line| line.size)
+                    .unwrap_or(DEFAULT_COLUMN_WIDTH)
+            })
+            .collect();
+        let spans = Spans::new(grid, &rows, &columns);
+
+        html.push_str(&format!(
+            "<table cellpadding=\"0\" cellspacing=\"0\" style=\"border-collapse:collapse;\
+             table-layout:fixed;width:{}px;font-family:Calibri,Arial,sans-serif;\
+             font-size:11pt;color:#000000\">\n<colgroup>",
+            widths.iter().sum::<f64>()
+        ));
+        for width in widths.iter() {
+            html.push_str(&format!("<col style=\"width:{}px\">", width));
+        }
+        html.push_str("</colgroup>\n");
+
+        for row in rows.iter().copied() {
+            let line = grid.rows.get(&row);
+            let height = line
+                .and_then(|line| line.size)
+                .unwrap_or(DEFAULT_ROW_HEIGHT);
+            html.push_str(&format!("<tr style=\"height:{}px\">", height));
+            for col in columns.iter().copied() {
+                if spans.covered.contains(&(row, col)) {
+                    continue;
+                }
+                self.write_cell(html, grid, row, col, spans.origins.get(&(row, col)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn write_cell(
+        &self,
+        html: &mut String,
+        grid: &SheetGrid,
+        row: u32,
+        col: u16,
+        span: Option<&(usize, usize)>,
+    ) {
+        let format_map = &self.workbook.format_map;
+        let cell = grid.cells.get(&(row, col));
+        // Cells that were never written show the format of their row, or else of their column
+        let format = match cell {
+            Some(cell) => cell.format,
+            None => grid
+                .rows
+                .get(&row)
+                .and_then(|line| line.format)
+                .or_else(|| grid.columns.get(&col).and_then(|line| line.format)),
+        };
+        let format = format.and_then(|id| format_map.source(&id));
+        let value = match cell {
+            Some(cell) => export_value(cell, format_map, true, false),
+            None => ExportValue::Empty,
+        };
+
+        let mut css =
+            String::from("padding:0 3px;overflow:hidden;white-space:nowrap;vertical-align:bottom;");
+        if self.grid_lines {
+            css.push_str(&format!("border:{};", GRID_LINE));
+        }
+        let date = matches!(
+            cell.and_then(|cell| cell.value.as_ref()),
+            Some(GridValue::Date(_))
+        );
+        match value {
+            ExportValue::Number(..) => css.push_str("text-align:right;"),
+            ExportValue::Text(_) if date => css.push_str("text-align:right;"),
+            ExportValue::Boolean(_) => css.push_str("text-align:center;"),
+            _ => {}
+        }
+        if let Some(table) = grid.tables.iter().find(|table| table.contains(row, col)) {
+            table_css(&mut css, table, row, col);
+        }
+        if let Some(format) = format {
+            format_css(&mut css, format);
+        }
+
+        html.push_str("<td");
+        if let Some((row_span, col_span)) = span {
+            if *row_span > 1 {
+                html.push_str(&format!(" rowspan=\"{}\"", row_span));
+            }
+            if *col_span > 1 {
+                html.push_str(&format!(" colspan=\"{}\"", col_span));
+            }
+        }
+        html.push_str(&format!(
+            " style=\"{}\">",
+            escape_attribute(css.trim_end_matches(';'))
+        ));
+        let text = match &value {
+            ExportValue::Empty => "",
+            ExportValue::Text(text) | ExportValue::Number(_, text) => text.as_str(),
+            ExportValue::Boolean(true) => "TRUE",
+            ExportValue::Boolean(false) => "FALSE",
+        };
+        let href = match cell.and_then(|cell| cell.value.as_ref()) {
+            Some(GridValue::Link { href, .. }) => link_href(href),
+            _ => None,
+        };
+        match href {
+            Some(href) => html.push_str(&format!(
+                "<a href=\"{}\">{}</a>",
+                escape_attribute(&href),
+                escape(text)
+            )),
+            None => html.push_str(&escape(text)),
+        }
+        html.push_str("</td>");
+    }
+}
+
+/// The href a link is rendered with. Only web and mail links and links to
+/// cells of the workbook get an anchor, so a `javascript:` or `data:` url
+/// can't run in the page the table is put in.
+fn link_href(url: &str) -> Option<String> {
+    let url = url.trim();
+    if let Some(location) = url.strip_prefix("internal:") {
+        return Some(format!("#{}", location));
+    }
+    if url.starts_with('#') {
+        return Some(url.to_string());
+    }
+    let scheme = url.split_once(':')?.0.to_ascii_lowercase();
+    matches!(scheme.as_str(), "http" | "https" | "mailto").then(|| url.to_string())
+}
+
+/// Where merged ranges are written: the rows and columns each spans from its
+/// first shown cell, and the shown cells it covers.
+struct Spans {
+    origins: HashMap<(u32, u16), (usize, usize)>,
+    covered: HashSet<(u32, u16)>,
+}
+
+impl Spans {
+    fn new(grid: &SheetGrid, rows: &[u32], columns: &[u16]) -> Self {
+        let mut origins = HashMap::new();
+        let mut covered = HashSet::new();
+        for range in grid.merges.iter() {
+            let range_rows: Vec<u32> = rows
+                .iter()
+                .copied()
+                .filter(|row| (range.start_row..=range.end_row).contains(row))
+                .collect();
+            let range_columns: Vec<u16> = columns
+                .iter()
+                .copied()
+                .filter(|col| (range.start_col..=range.end_col).contains(&(*col as u32)))
+                .collect();
+            let (Some(first_row), Some(first_col)) = (range_rows.first(), range_columns.first())
+            else {
+                continue;
+            };
+            origins.insert(
+                (*first_row, *first_col),
+                (range_rows.len(), range_columns.len()),
+            );
+            for row in range_rows.iter() {
+                for col in range_columns.iter() {
+                    if (*row, *col) != (*first_row, *first_col) {
+                        covered.insert((*row, *col));
+                    }
+                }
+            }
+        }
+        Self { origins, covered }
+    }
+}
+
+impl GridTable {
+    fn contains(&self, row: u32, col: u16) -> bool {
+        (self.first_row..=self.last_row).contains(&row)
+            && (self.first_column..=self.last_column).contains(&col)
+    }
+}
+
+/// The look of a cell of a table. The built-in styles are approximated
+/// from their family, light, medium or dark, and from their accent color.
+fn table_css(css: &mut String, table: &GridTable, row: u32, col: u16) {
+    let name = table.look.style.to_string();
+    let Some((family, number)) = ["Light", "Medium", "Dark"].into_iter().find_map(|family| {
+        let number = name.strip_prefix("TableStyle")?.strip_prefix(family)?;
+        Some((family, number.parse::<usize>().ok()?))
+    }) else {
+        return;
+    };
+    // Every family cycles through black and the six accents of the theme
+    let accent = match (number - 1) % 7 {
+        0 => 0x404040,
+        index => THEME_COLORS[3 + index],
+    };
+    let header = table.header_row && row == table.first_row;
+    let total = table.total_row && row == table.last_row;
+    let band = (row - table.first_row)
+        .saturating_sub(table.header_row as u32)
+        .is_multiple_of(2);
+    let column_band = (col - table.first_column).is_multiple_of(2);
+
+    if header {
+        // Light styles 8 to 14 fill their header like the medium ones
+        match family {
+            "Light" if (number - 1) / 7 != 1 => {
+                push_css(css, "font-weight:bold;border-bottom:1px solid", accent)
+            }
+            "Dark" => push_css(
+                css,
+                "font-weight:bold;color:#FFFFFF;background-color:",
+                tint(accent, -0.5),
+            ),
+            _ => push_css(
+                css,
+                "font-weight:bold;color:#FFFFFF;background-color:",
+                accent,
+            ),
+        }
+    } else if total {
+        match family {
+            "Dark" => push_css(
+                css,
+                "font-weight:bold;color:#FFFFFF;background-color:",
+                tint(accent, -0.5),
+            ),
+            _ => push_css(css, "font-weight:bold;border-top:3px double", accent),
+        }
+    } else {
+        let banded = (table.look.banded_rows && band) || (table.look.banded_columns && column_band);
+        match (family, banded) {
+            ("Dark", true) => push_css(css, "color:#FFFFFF;background-color:", tint(accent, -0.25)),
+            ("Dark", false) => push_css(css, "color:#FFFFFF;background-color:", accent),
+            (_, true) => push_css(css, "background-color:", tint(accent, 0.8)),
+            (_, false) => {}
+        }
+    }
+    let stressed = (table.look.first_column && col == table.first_column)
+        || (table.look.last_column && col == table.last_column);
+    if stressed && !header && !total {
+        css.push_str("font-weight:bold;");
+    }
+}
+
+/// The declarations of a format, after the ones of the table so that they win.
+fn format_css(css: &mut String, format: &NodeXlsxFormat) {
+    let font_name = format
+        .font_name
+        .as_deref()
+        .or(format.theme_font.as_ref().map(ThemeFont::font_name));
+    if let Some(font_name) = font_name {
+        let font_name = font_name.replace(['\'', '"', '<', '>', ';'], "");
+        css.push_str(&format!("font-family:'{}';", font_name));
+    }
+    if let Some(font_size) = format.font_size {
+        css.push_str(&format!("font-size:{}pt;", font_size));
+    }
+    match format.bold {
+        Some(true) => css.push_str("font-weight:bold;"),
+        Some(false) => css.push_str("font-weight:normal;"),
+        None => {}
+    }
+    match format.italic {
+        Some(true) => css.push_str("font-style:italic;"),
+        Some(false) => css.push_str("font-style:normal;"),
+        None => {}
+    }
+
+    let mut lines = vec![];
+    let mut double = false;
+    match format.underline {
+        Some(FormatUnderline::Single | FormatUnderline::SingleAccounting) => {
+            lines.push("underline")
+        }
+        Some(FormatUnderline::Double | FormatUnderline::DoubleAccounting) => {
+            lines.push("underline");
+            double = true;
+        }
+        _ => {}
+    }
+    if format.strike_through == Some(true) {
+        lines.push("line-through");
+    }
+    if !lines.is_empty() {
+        css.push_str(&format!("text-decoration:{};", lines.join(" ")));
+        if double {
+            css.push_str("text-decoration-style:double;");
+        }
+    }
+    if let Some(rgb) = format.font_color.as_ref().and_then(Color::rgb) {
+        push_css(css, "color:", rgb);
+    }
+
+    if let Some(rgb) = format.fill_rgb() {
+        push_css(css, "background-color:", rgb);
+    }
+
+    let borders = [
+        ("top", format.top_border),
+        ("right", format.right_border),
+        ("bottom", format.bottom_border),
+        ("left", format.left_border),
+    ];
+    for (side, border) in borders {
+        if let Some(border) = border {
+            css.push_str(&format!("border-{}:{};", side, border_css(&border)));
+        }
+    }
+
+    let align = match format.align {
+        Some(FormatAlign::Left | FormatAlign::Fill) => Some("left"),
+        Some(FormatAlign::Center | FormatAlign::CenterAcross) => Some("center"),
+        Some(FormatAlign::Right) => Some("right"),
+        Some(FormatAlign::Justify | FormatAlign::Distributed) => Some("justify"),
+        _ => None,
+    };
+    if let Some(align) = align {
+        css.push_str(&format!("text-align:{};", align));
+    }
+    let vertical_align = match format.vertical_align {
+        Some(FormatAlign::Top) => Some("top"),
+        Some(
+            FormatAlign::VerticalCenter
+            | FormatAlign::VerticalJustify
+            | FormatAlign::VerticalDistributed,
+        ) => Some("middle"),
+        _ => None,
+    };
+    if let Some(vertical_align) = vertical_align {
+        css.push_str(&format!("vertical-align:{};", vertical_align));
+    }
+    if format.text_wrap == Some(true) {
+        css.push_str("white-space:pre-wrap;word-wrap:break-word;");
+    }
+    if let Some(indent) = format.indent.filter(|indent| *indent > 0) {
+        let side = match align {
+            Some("right") => "right",
+            _ => "left",
+        };
+        css.push_str(&format!("padding-{}:{}px;", side, 3 + 9 * indent as u32));
+    }
+}
+
+fn border_css(border: &Border) -> String {
+    let Some((width, style)) = border.line() else {
+        return "none".to_string();
+    };
+    let color = border.color.rgb().unwrap_or(0x000000);
+    format!("{}px {} #{:06X}", width, style, color)
+}
+
+/// Writes a declaration ending with a color, like `color:#RRGGBB;`.
+fn push_css(css: &mut String, declaration: &str, rgb: u32) {
+    let separator = if declaration.ends_with(':') { "" } else { " " };
+    css.push_str(&format!("{}{}#{:06X};", declaration, separator, rgb));
+}
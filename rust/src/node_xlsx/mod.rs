@@ -1,29 +1,66 @@
+use std::collections::HashMap;
+
 use base64::{engine::general_purpose, Engine};
 use neon::{
-    context::FunctionContext,
+    context::{Context, FunctionContext},
     handle::Handle,
     object::Object,
+    result::JsResult,
     result::NeonResult,
-    types::{JsArray, JsObject, JsValue},
+    types::{JsArray, JsBoolean, JsDate, JsObject, JsString, JsValue},
 };
 use rust_xlsxwriter::Workbook;
 
-use self::{error::NodeXlsxError, sheet::NodeXlsxSheet};
+use self::{
+    conditional_format::registry::ConditionalFormatRegistry,
+    error::NodeXlsxError,
+    format_registry::FormatRegistry,
+    formula::result::{fix_result_types, FormulaResult},
+    formula::value::date_to_serial,
+    formula::FormulaNames,
+    ods::OdsWriter,
+    package::rewrite_parts,
+    sheet::{
+        evaluate_formulas, fix_priorities, preview_conditional_formats, FormulaErrors,
+        NodeXlsxSheet,
+    },
+    util::js_date_to_naive_date_time,
+};
+
+pub use self::{
+    export::NodeXlsxSheetExport, html::NodeXlsxHtml, reader::read_workbook,
+    template::NodeXlsxTemplate,
+};
 
 mod border;
 mod cell;
 mod cell_range;
 mod color;
 mod conditional_format;
+mod csv;
 mod error;
+mod export;
 mod format;
+mod format_registry;
+mod formula;
+mod html;
+mod number_format;
+mod ods;
+mod package;
+mod reader;
 mod sheet;
 mod table;
+mod template;
 mod types;
 mod util;
+mod xml;
 
 pub struct NodeXlsxWorkbook {
     sheets: Vec<NodeXlsxSheet>,
+    /// The defined names, as the name and its formula
+    defined_names: Vec<(String, String)>,
+    format_map: FormatRegistry,
+    conditional_formats: ConditionalFormatRegistry,
 }
 
 impl NodeXlsxWorkbook {
@@ -31,29 +68,170 @@ impl NodeXlsxWorkbook {
         cx: &mut FunctionContext<'b>,
         obj: Handle<JsObject>,
     ) -> NeonResult<Self> {
+        // Release the JS format objects held by the registry while we are still
+        // on the main thread. On error they are dropped through Neon's drop queue.
+        let mut workbook = Self::inner_from_js_object(cx, obj)?;
+        workbook.format_map.release(cx);
+        workbook.conditional_formats.release(cx);
+        Ok(workbook)
+    }
+
+    fn inner_from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
         let mut inner_sheets = vec![];
+        let mut format_map = FormatRegistry::new();
+        let mut conditional_formats = ConditionalFormatRegistry::new();
+
+        let formats: Option<Handle<JsArray>> = obj.get_opt(cx, "formats")?;
+        if let Some(formats) = formats {
+            let formats: Vec<Handle<JsValue>> = formats.to_vec(cx)?;
+            for format in formats {
+                let format = format.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
+                format_map.declare(cx, format)?;
+            }
+        }
+
+        let declared: Option<Handle<JsArray>> = obj.get_opt(cx, "conditionalFormats")?;
+        if let Some(declared) = declared {
+            let declared: Vec<Handle<JsValue>> = declared.to_vec(cx)?;
+            for conditional_format in declared {
+                let conditional_format =
+                    conditional_format.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
+                conditional_formats.declare(cx, conditional_format, &mut format_map)?;
+            }
+        }
 
         let sheets: Handle<JsArray> = obj.get(cx, "sheets")?;
         let sheets: Vec<Handle<JsValue>> = sheets.to_vec(cx)?;
         for sheet in sheets {
             let sheet = sheet.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
-            let sheet = NodeXlsxSheet::from_js_object(cx, sheet)?;
+            let sheet = NodeXlsxSheet::from_js_object(
+                cx,
+                sheet,
+                &mut format_map,
+                &mut conditional_formats,
+            )?;
             inner_sheets.push(sheet);
         }
+
+        let mut defined_names = vec![];
+        let js_names: Option<Handle<JsArray>> = obj.get_opt(cx, "definedNames")?;
+        if let Some(js_names) = js_names {
+            let js_names: Vec<Handle<JsValue>> = js_names.to_vec(cx)?;
+            for defined_name in js_names {
+                let defined_name = defined_name.downcast_or_throw::<JsObject, _>(cx)?;
+                let name: Handle<JsString> = defined_name.get(cx, "name")?;
+                let formula: Handle<JsString> = defined_name.get(cx, "formula")?;
+                defined_names.push((name.value(cx), formula.value(cx)));
+            }
+        }
+
+        let validate_formulas: Option<Handle<JsBoolean>> = obj.get_opt(cx, "validateFormulas")?;
+        let validate_formulas = match validate_formulas {
+            Some(validate_formulas) => validate_formulas.value(cx),
+            None => true,
+        };
+        let mut warnings = vec![];
+        if validate_formulas {
+            let names = FormulaNames {
+                sheets: inner_sheets
+                    .iter()
+                    .map(|sheet| sheet.name().to_lowercase())
+                    .collect(),
+                defined: defined_names
+                    .iter()
+                    .map(|(name, _)| {
+                        let name = name.rsplit('!').next().unwrap_or(name);
+                        name.to_ascii_uppercase()
+                    })
+                    .collect(),
+            };
+            let mut errors = FormulaErrors::default();
+            for (name, formula) in defined_names.iter_mut() {
+                errors.prepare_defined_name(name, formula, &names);
+            }
+            for sheet in inner_sheets.iter_mut() {
+                sheet.prepare_formulas(&names, &mut errors);
+            }
+            if !errors.is_empty() {
+                return cx.throw_error(errors.describe());
+            }
+            warnings = errors.warnings;
+        }
+        let js_warnings = cx.empty_array();
+        for (index, warning) in warnings.iter().enumerate() {
+            let warning = cx.string(warning);
+            js_warnings.set(cx, index as u32, warning)?;
+        }
+        obj.set(cx, "formulaWarnings", js_warnings)?;
+
+        let evaluate: Option<Handle<JsBoolean>> = obj.get_opt(cx, "evaluateFormulas")?;
+        let evaluate = match evaluate {
+            Some(evaluate) => evaluate.value(cx),
+            None => false,
+        };
+        if evaluate {
+            evaluate_formulas(&mut inner_sheets);
+        }
         return Ok(Self {
             sheets: inner_sheets,
+            defined_names,
+            format_map,
+            conditional_formats,
         });
     }
 
+    /// Number of distinct cell formats across all sheets, i.e. the number of
+    /// styles Excel will have to load.
+    pub fn unique_format_count(&self) -> usize {
+        self.format_map.unique_count()
+    }
+
+    /// What the conditional formats show in the cells of every sheet, as
+    /// `{ name, cells }` objects. Date rules compare with `today`, and are
+    /// left out without it.
+    pub fn preview_conditional_formats<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        today: Option<Handle<JsDate>>,
+    ) -> JsResult<'a, JsArray> {
+        let today = match today {
+            Some(today) => Some(date_to_serial(js_date_to_naive_date_time(cx, today)?).floor()),
+            None => None,
+        };
+        let previews = preview_conditional_formats(&self.sheets, &self.conditional_formats, today);
+
+        let js_sheets = cx.empty_array();
+        for (index, (sheet, cells)) in self.sheets.iter().zip(previews).enumerate() {
+            let js_sheet = cx.empty_object();
+            let name = cx.string(sheet.name());
+            js_sheet.set(cx, "name", name)?;
+            let js_cells = cx.empty_array();
+            for (index, cell) in cells.iter().enumerate() {
+                let js_cell = cell.to_js_object(cx)?;
+                js_cells.set(cx, index as u32, js_cell)?;
+            }
+            js_sheet.set(cx, "cells", js_cells)?;
+            js_sheets.set(cx, index as u32, js_sheet)?;
+        }
+        Ok(js_sheets)
+    }
+
     pub fn save_to_buffer(self) -> Result<Vec<u8>, NodeXlsxError> {
+        let fixes = self.fixes();
         let mut workbook = self.parse()?;
         let buffer = workbook.save_to_buffer()?;
-        return Ok(buffer);
+        fix_package(buffer, &fixes)
     }
 
     pub fn save_to_file(self, path: &str) -> Result<(), NodeXlsxError> {
+        let fixes = self.fixes();
         let mut workbook = self.parse()?;
-        workbook.save(path)?;
+        if fixes.iter().all(SheetFixes::is_empty) {
+            workbook.save(path)?;
+            return Ok(());
+        }
+        let buffer = fix_package(workbook.save_to_buffer()?, &fixes)?;
+        std::fs::write(path, buffer)?;
         return Ok(());
     }
 
@@ -63,12 +241,81 @@ impl NodeXlsxWorkbook {
         return Ok(base64);
     }
 
+    /// The workbook as an OpenDocument spreadsheet, with the results of its
+    /// formulas computed where they are missing.
+    pub fn save_to_ods_buffer(mut self) -> Result<Vec<u8>, NodeXlsxError> {
+        evaluate_formulas(&mut self.sheets);
+        OdsWriter::new(&self).write()
+    }
+
+    pub fn save_to_ods_file(self, path: &str) -> Result<(), NodeXlsxError> {
+        let buffer = self.save_to_ods_buffer()?;
+        std::fs::write(path, buffer)?;
+        Ok(())
+    }
+
+    pub fn save_to_ods_base64(self) -> Result<String, NodeXlsxError> {
+        let buffer = self.save_to_ods_buffer()?;
+        Ok(general_purpose::STANDARD.encode(buffer))
+    }
+
     fn parse(self) -> Result<Workbook, NodeXlsxError> {
         let mut workbook = rust_xlsxwriter::Workbook::new();
         for sheet in self.sheets {
-            let worksheet = sheet.try_into().unwrap(); //TODO: Handle error
+            let worksheet = sheet.into_worksheet(&self.format_map, &self.conditional_formats)?;
             workbook.push_worksheet(worksheet);
         }
+        for (name, formula) in &self.defined_names {
+            workbook.define_name(name, formula)?;
+        }
         return Ok(workbook);
     }
+
+    /// What rust_xlsxwriter writes differently from the workbook, for every sheet.
+    fn fixes(&self) -> Vec<SheetFixes> {
+        self.sheets
+            .iter()
+            .map(|sheet| SheetFixes {
+                results: sheet.mistyped_results(),
+                priorities: sheet.conditional_format_priorities(&self.conditional_formats),
+            })
+            .collect()
+    }
+}
+
+/// The fixes of one worksheet part.
+struct SheetFixes {
+    results: HashMap<(u32, u16), FormulaResult>,
+    priorities: Vec<u32>,
+}
+
+impl SheetFixes {
+    fn is_empty(&self) -> bool {
+        self.results.is_empty() && self.priorities.is_empty()
+    }
+}
+
+/// Gives the formula results of the saved package back their types and the
+/// conditional formats their priorities. Sheets are stored as
+/// `xl/worksheets/sheet1.xml` and on in the order they are added.
+fn fix_package(buffer: Vec<u8>, fixes: &[SheetFixes]) -> Result<Vec<u8>, NodeXlsxError> {
+    if fixes.iter().all(SheetFixes::is_empty) {
+        return Ok(buffer);
+    }
+    rewrite_parts(buffer, |name, xml| {
+        let index = name
+            .strip_prefix("xl/worksheets/sheet")?
+            .strip_suffix(".xml")?
+            .parse::<usize>()
+            .ok()?;
+        let fixes = fixes.get(index.checked_sub(1)?)?;
+        if fixes.is_empty() {
+            return None;
+        }
+        let mut xml = fix_result_types(xml, &fixes.results);
+        if !fixes.priorities.is_empty() {
+            xml = fix_priorities(&xml, &fixes.priorities);
+        }
+        Some(xml)
+    })
 }
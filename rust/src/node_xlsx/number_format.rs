@@ -0,0 +1,1123 @@
+use chrono::{Datelike, Timelike};
+
+use super::{
+    formula::value::{number_to_string, serial_to_date},
+    xml::{escape, escape_attribute},
+};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// The code of a built-in number format, by its id.
+pub fn builtin_code(id: u8) -> Option<&'static str> {
+    let code = match id {
+        0 => "General",
+        1 => "0",
+        2 => "0.00",
+        3 => "#,##0",
+        4 => "#,##0.00",
+        5 => "$#,##0_);($#,##0)",
+        6 => "$#,##0_);[Red]($#,##0)",
+        7 => "$#,##0.00_);($#,##0.00)",
+        8 => "$#,##0.00_);[Red]($#,##0.00)",
+        9 => "0%",
+        10 => "0.00%",
+        11 => "0.00E+00",
+        12 => "# ?/?",
+        13 => "# ??/??",
+        14 => "m/d/yyyy",
+        15 => "d-mmm-yy",
+        16 => "d-mmm",
+        17 => "mmm-yy",
+        18 => "h:mm AM/PM",
+        19 => "h:mm:ss AM/PM",
+        20 => "h:mm",
+        21 => "h:mm:ss",
+        22 => "m/d/yyyy h:mm",
+        37 => "#,##0_);(#,##0)",
+        38 => "#,##0_);[Red](#,##0)",
+        39 => "#,##0.00_);(#,##0.00)",
+        40 => "#,##0.00_);[Red](#,##0.00)",
+        41 => r#"_(* #,##0_);_(* \(#,##0\);_(* "-"_);_(@_)"#,
+        42 => r#"_("$"* #,##0_);_("$"* \(#,##0\);_("$"* "-"_);_(@_)"#,
+        43 => r#"_(* #,##0.00_);_(* \(#,##0.00\);_(* "-"??_);_(@_)"#,
+        44 => r#"_("$"* #,##0.00_);_("$"* \(#,##0.00\);_("$"* "-"??_);_(@_)"#,
+        45 => "mm:ss",
+        46 => "[h]:mm:ss",
+        47 => "mm:ss.0",
+        48 => "##0.0E+0",
+        49 => "@",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// The text Excel shows for a number in a cell with the number format
+/// `code`. Dates and times are given as serial numbers.
+pub fn format_number(code: &str, number: f64) -> String {
+    let sections: Vec<Section> = split_sections(code)
+        .iter()
+        .map(|s| Section::parse(s))
+        .collect();
+    let (section, number) = pick_section(&sections, number);
+    match section {
+        Some(section) => section.format_number(number),
+        None => general(number),
+    }
+}
+
+/// The text Excel shows for a string in a cell with the number format
+/// `code`: the fourth section, or a single section with a `@`, takes it in
+/// place of the `@`.
+pub fn format_text(code: &str, text: &str) -> String {
+    let sections = split_sections(code);
+    let section = match sections.len() {
+        4.. => sections[3].as_str(),
+        1 if sections[0].contains('@') => sections[0].as_str(),
+        _ => return text.to_string(),
+    };
+    let mut shown = String::new();
+    for token in Section::parse(section).tokens {
+        match token {
+            Token::Literal(literal) => shown.push_str(&literal),
+            Token::Text => shown.push_str(text),
+            _ => {}
+        }
+    }
+    shown
+}
+
+/// How Excel shows a number without a format: up to 15 significant digits,
+/// in scientific notation when it is very large or small.
+pub fn general(number: f64) -> String {
+    let magnitude = number.abs();
+    if magnitude != 0.0 && !(1e-9..1e15).contains(&magnitude) {
+        let scientific = format!("{:.14E}", number);
+        let (mantissa, exponent) = scientific.split_once('E').unwrap_or((&scientific, "0"));
+        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+        let exponent: i32 = exponent.parse().unwrap_or(0);
+        let sign = if exponent < 0 { '-' } else { '+' };
+        return format!("{}E{}{:02}", mantissa, sign, exponent.abs());
+    }
+    number_to_string(number)
+}
+
+/// What an OpenDocument data style shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataStyleKind {
+    Number,
+    Percentage,
+    Date,
+    Time,
+    Text,
+}
+
+/// A number format as OpenDocument data styles.
+pub struct DataStyle {
+    pub kind: DataStyleKind,
+    /// The style of each section, then the one named after the format
+    /// picking among them on the value
+    pub xml: String,
+}
+
+/// The OpenDocument data styles showing values like the format `code`, the
+/// one to refer to being `name`. None for `General`, which needs no style.
+/// The fourth section of a code, for text, is left out.
+pub fn data_style(code: &str, name: &str) -> Option<DataStyle> {
+    let sections: Vec<Section> = split_sections(code)
+        .iter()
+        .map(|s| Section::parse(s))
+        .collect();
+    let first = sections.first()?;
+    if sections.len() == 1 && first.tokens.iter().all(|t| *t == Token::General) {
+        return None;
+    }
+    let kind = first.data_style_kind();
+    if sections.len() == 1 {
+        let xml = first.data_style(name, &[]);
+        return Some(DataStyle { kind, xml });
+    }
+
+    // Sections after the first one are picked like Excel does, on the sign of the value
+    let numbers = &sections[..sections.len().min(3)];
+    let mut maps: Vec<(String, usize)> = vec![];
+    let main = if numbers.iter().any(|s| s.condition.is_some()) {
+        for (index, section) in numbers.iter().enumerate().take(2) {
+            if let Some(condition) = &section.condition {
+                let value = number_to_string(condition.value);
+                maps.push((format!("value(){}{}", condition.operator, value), index));
+            }
+        }
+        numbers
+            .iter()
+            .position(|s| s.condition.is_none())
+            .unwrap_or(numbers.len() - 1)
+    } else if numbers.len() == 2 {
+        maps.push(("value()>=0".to_string(), 0));
+        1
+    } else {
+        maps.push(("value()>0".to_string(), 0));
+        maps.push(("value()=0".to_string(), 2));
+        1
+    };
+
+    let mut xml = String::new();
+    let mut applied = vec![];
+    for (condition, index) in maps {
+        let section_name = format!("{}P{}", name, index);
+        xml.push_str(&numbers[index].data_style(&section_name, &[]));
+        applied.push((condition, section_name));
+    }
+    xml.push_str(&numbers[main].data_style(name, &applied));
+    Some(DataStyle { kind, xml })
+}
+
+/// Splits a format code into its `;` separated sections.
+fn split_sections(code: &str) -> Vec<String> {
+    let mut sections = vec![String::new()];
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        let section = sections.last_mut().unwrap();
+        match c {
+            ';' => sections.push(String::new()),
+            '"' => {
+                section.push(c);
+                for c in chars.by_ref() {
+                    section.push(c);
+                    if c == '"' {
+                        break;
+                    }
+                }
+            }
+            '\\' | '_' | '*' => {
+                section.push(c);
+                section.extend(chars.next());
+            }
+            _ => section.push(c),
+        }
+    }
+    sections
+}
+
+/// The section that shows a number, and the number it shows: sections for
+/// negative numbers show them without their sign.
+fn pick_section(sections: &[Section], number: f64) -> (Option<&Section>, f64) {
+    if sections.iter().any(|section| section.condition.is_some()) {
+        let conditional = sections.iter().take(2).find(|section| {
+            section
+                .condition
+                .as_ref()
+                .is_some_and(|condition| condition.matches(number))
+        });
+        return match conditional {
+            Some(section) => (Some(section), number),
+            None => {
+                let rest = sections.iter().find(|section| section.condition.is_none());
+                (rest, number)
+            }
+        };
+    }
+    match sections {
+        [] => (None, number),
+        [_, negative, ..] if number < 0.0 => (Some(negative), -number),
+        [_, _, zero, ..] if number == 0.0 => (Some(zero), number),
+        [section, ..] => (Some(section), number),
+    }
+}
+
+struct Condition {
+    operator: String,
+    value: f64,
+}
+
+impl Condition {
+    fn parse(text: &str) -> Option<Self> {
+        let split = text.find(|c: char| !"<>=".contains(c))?;
+        let (operator, value) = text.split_at(split);
+        if operator.is_empty() {
+            return None;
+        }
+        let value = value.trim().parse().ok()?;
+        Some(Self {
+            operator: operator.to_string(),
+            value,
+        })
+    }
+
+    fn matches(&self, number: f64) -> bool {
+        match self.operator.as_str() {
+            "<" => number < self.value,
+            "<=" => number <= self.value,
+            ">" => number > self.value,
+            ">=" => number >= self.value,
+            "=" => number == self.value,
+            "<>" => number != self.value,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    /// A digit placeholder: `0`, `#` or `?`
+    Digit(char),
+    Point,
+    Comma,
+    Percent,
+    /// `E+` or `E-`, whether positive exponents show their sign
+    Exponent(bool),
+    Slash,
+    Text,
+    General,
+    Year(usize),
+    /// Months, or minutes when next to hours or seconds
+    Month(usize),
+    Minute(usize),
+    Day(usize),
+    Hour(usize),
+    Second(usize),
+    /// Elapsed hours, minutes or seconds, like `[h]`
+    Elapsed(char, usize),
+    /// Fractions of seconds, like `.00`
+    SubSecond(usize),
+    AmPm(bool),
+}
+
+impl Token {
+    fn is_date(&self) -> bool {
+        matches!(
+            self,
+            Token::Year(_)
+                | Token::Month(_)
+                | Token::Minute(_)
+                | Token::Day(_)
+                | Token::Hour(_)
+                | Token::Second(_)
+                | Token::Elapsed(..)
+                | Token::SubSecond(_)
+                | Token::AmPm(_)
+        )
+    }
+}
+
+struct Section {
+    tokens: Vec<Token>,
+    condition: Option<Condition>,
+    /// The color of a `[Red]` like name
+    color: Option<u32>,
+}
+
+impl Section {
+    fn parse(code: &str) -> Self {
+        let chars: Vec<char> = code.chars().collect();
+        let mut tokens: Vec<Token> = vec![];
+        let mut condition = None;
+        let mut color = None;
+        let mut i = 0;
+        let literal = |tokens: &mut Vec<Token>, text: &str| match tokens.last_mut() {
+            Some(Token::Literal(last)) => last.push_str(text),
+            _ => tokens.push(Token::Literal(text.to_string())),
+        };
+        let run = |i: usize, c: char| {
+            chars[i..]
+                .iter()
+                .take_while(|d| d.eq_ignore_ascii_case(&c))
+                .count()
+        };
+        while i < chars.len() {
+            let c = chars[i];
+            let rest: String = chars[i..].iter().collect();
+            match c {
+                '"' => {
+                    let text: String = chars[i + 1..].iter().take_while(|c| **c != '"').collect();
+                    i += text.chars().count() + 2;
+                    literal(&mut tokens, &text);
+                    continue;
+                }
+                '\\' => {
+                    if let Some(c) = chars.get(i + 1) {
+                        literal(&mut tokens, &c.to_string());
+                    }
+                    i += 2;
+                    continue;
+                }
+                // Padding as wide as a character, and characters repeated to fill the cell
+                '_' => {
+                    literal(&mut tokens, " ");
+                    i += 2;
+                    continue;
+                }
+                '*' => {
+                    i += 2;
+                    continue;
+                }
+                '[' => {
+                    let inside: String = chars[i + 1..].iter().take_while(|c| **c != ']').collect();
+                    i += inside.chars().count() + 2;
+                    let lower = inside.to_lowercase();
+                    let unit = lower.chars().next().filter(|c| "hms".contains(*c));
+                    if let Some(currency) = inside.strip_prefix('$') {
+                        let symbol = currency.split('-').next().unwrap_or("");
+                        literal(&mut tokens, symbol);
+                    } else if let Some(unit) = unit.filter(|u| lower.chars().all(|c| c == *u)) {
+                        tokens.push(Token::Elapsed(unit, lower.len()));
+                    } else if let Some(parsed) = Condition::parse(&inside) {
+                        condition = Some(parsed);
+                    } else if let Some(named) = named_color(&lower) {
+                        color = Some(named);
+                    }
+                    continue;
+                }
+                '0' | '#' | '?' => tokens.push(Token::Digit(c)),
+                '.' => {
+                    let zeros = chars[i + 1..].iter().take_while(|c| **c == '0').count();
+                    let after_seconds =
+                        tokens.iter().rev().find(|t| t.is_date()).is_some_and(|t| {
+                            matches!(t, Token::Second(_) | Token::Elapsed('s', _))
+                        });
+                    if after_seconds && zeros > 0 {
+                        tokens.push(Token::SubSecond(zeros));
+                        i += zeros + 1;
+                        continue;
+                    }
+                    tokens.push(Token::Point);
+                }
+                ',' => tokens.push(Token::Comma),
+                '%' => {
+                    tokens.push(Token::Percent);
+                    literal(&mut tokens, "%");
+                }
+                '/' => tokens.push(Token::Slash),
+                '@' => tokens.push(Token::Text),
+                'E' | 'e' if matches!(chars.get(i + 1), Some('+') | Some('-')) => {
+                    tokens.push(Token::Exponent(chars[i + 1] == '+'));
+                    i += 2;
+                    continue;
+                }
+                _ if rest.len() >= 7 && rest[..7].eq_ignore_ascii_case("general") => {
+                    tokens.push(Token::General);
+                    i += 7;
+                    continue;
+                }
+                _ if rest.len() >= 5 && rest[..5].eq_ignore_ascii_case("am/pm") => {
+                    tokens.push(Token::AmPm(false));
+                    i += 5;
+                    continue;
+                }
+                _ if rest.len() >= 3 && rest[..3].eq_ignore_ascii_case("a/p") => {
+                    tokens.push(Token::AmPm(true));
+                    i += 3;
+                    continue;
+                }
+                'y' | 'Y' | 'm' | 'M' | 'd' | 'D' | 'h' | 'H' | 's' | 'S' => {
+                    let count = run(i, c);
+                    tokens.push(match c.to_ascii_lowercase() {
+                        'y' => Token::Year(count),
+                        'm' => Token::Month(count),
+                        'd' => Token::Day(count),
+                        'h' => Token::Hour(count),
+                        _ => Token::Second(count),
+                    });
+                    i += count;
+                    continue;
+                }
+                _ => literal(&mut tokens, &c.to_string()),
+            }
+            i += 1;
+        }
+
+        // `m` and `mm` right after hours or before seconds are minutes
+        let dates: Vec<usize> = (0..tokens.len()).filter(|i| tokens[*i].is_date()).collect();
+        for (index, position) in dates.iter().enumerate() {
+            let Token::Month(count) = tokens[*position] else {
+                continue;
+            };
+            let before = index
+                .checked_sub(1)
+                .map(|index| &tokens[dates[index]])
+                .is_some_and(|t| matches!(t, Token::Hour(_) | Token::Elapsed('h', _)));
+            let after = dates
+                .get(index + 1)
+                .map(|position| &tokens[*position])
+                .is_some_and(|t| matches!(t, Token::Second(_) | Token::Elapsed('s', _)));
+            if count <= 2 && (before || after) {
+                tokens[*position] = Token::Minute(count);
+            }
+        }
+        Self {
+            tokens,
+            condition,
+            color,
+        }
+    }
+
+    fn format_number(&self, number: f64) -> String {
+        if self.tokens.iter().any(Token::is_date) {
+            return self.format_date(number);
+        }
+        if self.tokens.contains(&Token::Slash) && self.tokens.contains(&Token::Digit('?')) {
+            return self.format_fraction(number);
+        }
+        if !self.tokens.iter().any(|t| matches!(t, Token::Digit(_))) {
+            let mut shown = String::new();
+            for token in self.tokens.iter() {
+                match token {
+                    Token::Literal(literal) => shown.push_str(literal),
+                    Token::General | Token::Text => shown.push_str(&general(number)),
+                    _ => {}
+                }
+            }
+            return shown;
+        }
+        self.format_decimal(number)
+    }
+
+    fn format_decimal(&self, number: f64) -> String {
+        let tokens = &self.tokens;
+        let exponent_at = tokens.iter().position(|t| matches!(t, Token::Exponent(_)));
+        let mantissa_end = exponent_at.unwrap_or(tokens.len());
+        let point_at = tokens[..mantissa_end]
+            .iter()
+            .position(|t| *t == Token::Point)
+            .unwrap_or(mantissa_end);
+        let (grouped, scale) = commas(&tokens[..mantissa_end], point_at);
+        let percents = tokens.iter().filter(|t| **t == Token::Percent).count();
+        let mut value = number * 100f64.powi(percents as i32) / 1000f64.powi(scale);
+
+        let decimals = tokens[point_at..mantissa_end]
+            .iter()
+            .filter(|t| matches!(t, Token::Digit(_)))
+            .count();
+        let mut exponent = 0;
+        if exponent_at.is_some() && value != 0.0 {
+            let integers = tokens[..point_at]
+                .iter()
+                .filter(|t| matches!(t, Token::Digit(_)))
+                .count()
+                .max(1) as i32;
+            exponent = (value.abs().log10().floor() as i32).div_euclid(integers) * integers;
+            let mantissa = value / 10f64.powi(exponent);
+            let rounded = round(mantissa, decimals);
+            if rounded.abs() >= 10f64.powi(integers) {
+                exponent += integers;
+            }
+            value /= 10f64.powi(exponent);
+        }
+
+        let rounded = format!("{:.*}", decimals, round(value.abs(), decimals));
+        let (integer, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let integer = if integer == "0" { "" } else { integer };
+        let negative = value < 0.0 && rounded.chars().any(|c| c.is_ascii_digit() && c != '0');
+
+        let mut shown = String::new();
+        if negative {
+            shown.push('-');
+        }
+        shown.push_str(&fill_integer(&tokens[..point_at], integer, grouped));
+        shown.push_str(&fill_fraction(&tokens[point_at..mantissa_end], fraction));
+
+        if let Some(exponent_at) = exponent_at {
+            let Token::Exponent(plus) = tokens[exponent_at] else {
+                unreachable!()
+            };
+            shown.push('E');
+            if exponent < 0 {
+                shown.push('-');
+            } else if plus {
+                shown.push('+');
+            }
+            let digits = exponent.abs().to_string();
+            shown.push_str(&fill_integer(&tokens[exponent_at + 1..], &digits, false));
+        }
+        shown
+    }
+
+    /// Fractions like `# ?/?`, with the denominator as small as the
+    /// placeholders allow or fixed by the code.
+    fn format_fraction(&self, number: f64) -> String {
+        let tokens = &self.tokens;
+        let slash = tokens.iter().position(|t| *t == Token::Slash).unwrap();
+        let denominator_tokens = &tokens[slash + 1..];
+        let fixed: String = denominator_tokens
+            .iter()
+            .take_while(|t| matches!(t, Token::Literal(_)))
+            .filter_map(|t| match t {
+                Token::Literal(literal) => Some(literal.trim()),
+                _ => None,
+            })
+            .collect();
+        let places = denominator_tokens
+            .iter()
+            .filter(|t| matches!(t, Token::Digit(_)))
+            .count() as u32;
+
+        // A placeholder group split by a literal before the numerator holds the whole part
+        let numerator_start = tokens[..slash]
+            .iter()
+            .rposition(|t| !matches!(t, Token::Digit(_)))
+            .map_or(0, |index| index + 1);
+        let has_whole = tokens[..numerator_start]
+            .iter()
+            .any(|t| matches!(t, Token::Digit(_)));
+
+        let magnitude = number.abs();
+        let whole = if has_whole { magnitude.trunc() } else { 0.0 };
+        let rest = magnitude - whole;
+        let (mut numerator, denominator) = match fixed.parse::<u64>() {
+            Ok(denominator) if denominator > 0 => {
+                ((rest * denominator as f64).round() as u64, denominator)
+            }
+            _ => closest_fraction(rest, 10u64.pow(places.max(1)) - 1),
+        };
+        let mut whole = whole as u64;
+        if numerator == denominator && has_whole {
+            whole += 1;
+            numerator = 0;
+        }
+
+        let mut shown = String::new();
+        if number < 0.0 {
+            shown.push('-');
+        }
+        let whole_text = if whole == 0 && numerator != 0 {
+            String::new()
+        } else {
+            whole.to_string()
+        };
+        if has_whole {
+            shown.push_str(&fill_integer(
+                &tokens[..numerator_start],
+                &whole_text,
+                false,
+            ));
+            if numerator == 0 {
+                return shown.trim_end().to_string();
+            }
+        }
+        shown.push_str(&fill_integer(
+            &tokens[numerator_start..slash],
+            &numerator.to_string(),
+            false,
+        ));
+        shown.push('/');
+        shown.push_str(&denominator.to_string());
+        shown
+    }
+
+    fn format_date(&self, serial: f64) -> String {
+        let sub_second_digits = self
+            .tokens
+            .iter()
+            .find_map(|t| match t {
+                Token::SubSecond(digits) => Some(*digits),
+                _ => None,
+            })
+            .unwrap_or(0);
+        let precision = 10f64.powi(sub_second_digits as i32);
+        let seconds = (serial * 86_400.0 * precision).round() / precision;
+        let Some(date) = serial_to_date(seconds / 86_400.0) else {
+            return general(serial);
+        };
+        let twelve_hours = self.tokens.iter().any(|t| matches!(t, Token::AmPm(_)));
+
+        let mut shown = String::new();
+        for token in self.tokens.iter() {
+            match token {
+                Token::Literal(literal) => shown.push_str(literal),
+                // Separators of dates, like `dd/mm/yyyy` or `d.m.yy`, are shown as they are
+                Token::Slash => shown.push('/'),
+                Token::Point => shown.push('.'),
+                Token::Comma => shown.push(','),
+                Token::Year(count) if *count <= 2 => {
+                    shown.push_str(&format!("{:02}", date.year() % 100))
+                }
+                Token::Year(_) => shown.push_str(&format!("{:04}", date.year())),
+                Token::Month(count) => {
+                    let name = MONTHS[date.month0() as usize];
+                    match count {
+                        1 => shown.push_str(&date.month().to_string()),
+                        2 => shown.push_str(&format!("{:02}", date.month())),
+                        3 => shown.push_str(&name[..3]),
+                        5 => shown.push_str(&name[..1]),
+                        _ => shown.push_str(name),
+                    }
+                }
+                Token::Day(count) => {
+                    let name = DAYS[date.weekday().num_days_from_monday() as usize];
+                    match count {
+                        1 => shown.push_str(&date.day().to_string()),
+                        2 => shown.push_str(&format!("{:02}", date.day())),
+                        3 => shown.push_str(&name[..3]),
+                        _ => shown.push_str(name),
+                    }
+                }
+                Token::Hour(count) => {
+                    let hour = match twelve_hours {
+                        true => (date.hour() + 11) % 12 + 1,
+                        false => date.hour(),
+                    };
+                    shown.push_str(&pad(hour as u64, *count));
+                }
+                Token::Minute(count) => shown.push_str(&pad(date.minute() as u64, *count)),
+                Token::Second(count) => shown.push_str(&pad(date.second() as u64, *count)),
+                Token::Elapsed(unit, count) => {
+                    let total = (seconds + 1e-9).floor() as u64;
+                    let elapsed = match unit {
+                        'h' => total / 3600,
+                        'm' => total / 60,
+                        _ => total,
+                    };
+                    shown.push_str(&pad(elapsed, *count));
+                }
+                Token::SubSecond(digits) => {
+                    let fraction = seconds.fract();
+                    let digits_text = format!("{:.*}", digits, fraction);
+                    shown.push_str(digits_text.trim_start_matches('0'));
+                }
+                Token::AmPm(short) => {
+                    let am = date.hour() < 12;
+                    shown.push_str(match (short, am) {
+                        (false, true) => "AM",
+                        (false, false) => "PM",
+                        (true, true) => "A",
+                        (true, false) => "P",
+                    });
+                }
+                Token::Digit(_) => {}
+                Token::General | Token::Text => shown.push_str(&general(serial)),
+                _ => {}
+            }
+        }
+        shown
+    }
+}
+
+impl Section {
+    fn data_style_kind(&self) -> DataStyleKind {
+        let dates = self
+            .tokens
+            .iter()
+            .any(|t| matches!(t, Token::Year(_) | Token::Month(_) | Token::Day(_)));
+        if dates {
+            DataStyleKind::Date
+        } else if self.tokens.iter().any(Token::is_date) {
+            DataStyleKind::Time
+        } else if self.tokens.contains(&Token::Percent) {
+            DataStyleKind::Percentage
+        } else if self.tokens.contains(&Token::Text) {
+            DataStyleKind::Text
+        } else {
+            DataStyleKind::Number
+        }
+    }
+
+    /// The section as a data style called `name`, applying the styles of
+    /// `maps` to the values matching their conditions.
+    fn data_style(&self, name: &str, maps: &[(String, String)]) -> String {
+        let kind = self.data_style_kind();
+        let element = match kind {
+            DataStyleKind::Number => "number:number-style",
+            DataStyleKind::Percentage => "number:percentage-style",
+            DataStyleKind::Date => "number:date-style",
+            DataStyleKind::Time => "number:time-style",
+            DataStyleKind::Text => "number:text-style",
+        };
+        let mut xml = format!("<{} style:name=\"{}\"", element, escape_attribute(name));
+        let elapsed = self.tokens.iter().any(|t| matches!(t, Token::Elapsed(..)));
+        if elapsed {
+            xml.push_str(" number:truncate-on-overflow=\"false\"");
+        }
+        xml.push('>');
+        if let Some(color) = self.color {
+            xml.push_str(&format!(
+                "<style:text-properties fo:color=\"#{:06X}\"/>",
+                color
+            ));
+        }
+        match kind {
+            DataStyleKind::Date | DataStyleKind::Time => self.date_elements(&mut xml),
+            _ => self.number_elements(&mut xml),
+        }
+        for (condition, style) in maps {
+            xml.push_str(&format!(
+                "<style:map style:condition=\"{}\" style:apply-style-name=\"{}\"/>",
+                escape_attribute(condition),
+                escape_attribute(style)
+            ));
+        }
+        xml.push_str(&format!("</{}>", element));
+        xml
+    }
+
+    /// Literals around one number element. Literals between the digits are
+    /// left out.
+    fn number_elements(&self, xml: &mut String) {
+        let tokens = &self.tokens;
+        let is_number = |t: &Token| matches!(t, Token::Digit(_) | Token::General);
+        let (Some(first), Some(last)) = (
+            tokens.iter().position(is_number),
+            tokens.iter().rposition(is_number),
+        ) else {
+            for token in tokens {
+                match token {
+                    Token::Literal(literal) => push_text(xml, literal),
+                    Token::Text => xml.push_str("<number:text-content/>"),
+                    _ => {}
+                }
+            }
+            return;
+        };
+        for token in tokens[..first].iter() {
+            if let Token::Literal(literal) = token {
+                push_text(xml, literal);
+            }
+        }
+        let number = &tokens[first..=last];
+        let count = |tokens: &[Token], zeros: bool| {
+            tokens
+                .iter()
+                .filter(|t| match t {
+                    Token::Digit(placeholder) => !zeros || *placeholder == '0',
+                    _ => false,
+                })
+                .count()
+        };
+        let exponent_at = number.iter().position(|t| matches!(t, Token::Exponent(_)));
+        let slash_at = number.iter().position(|t| *t == Token::Slash);
+        if number.contains(&Token::General) {
+            xml.push_str("<number:number number:min-integer-digits=\"1\"/>");
+        } else if let Some(exponent_at) = exponent_at {
+            let Token::Exponent(plus) = number[exponent_at] else {
+                unreachable!()
+            };
+            let mantissa = &number[..exponent_at];
+            let point_at = mantissa
+                .iter()
+                .position(|t| *t == Token::Point)
+                .unwrap_or(mantissa.len());
+            xml.push_str(&format!(
+                "<number:scientific-number number:decimal-places=\"{}\" \
+                 number:min-integer-digits=\"{}\" number:min-exponent-digits=\"{}\" \
+                 number:forced-exponent-sign=\"{}\"/>",
+                count(&mantissa[point_at..], false),
+                count(&mantissa[..point_at], true),
+                count(&number[exponent_at + 1..], false),
+                plus
+            ));
+        } else if let (Some(slash_at), true) = (slash_at, number.contains(&Token::Digit('?'))) {
+            let numerator_start = number[..slash_at]
+                .iter()
+                .rposition(|t| !matches!(t, Token::Digit(_)))
+                .map_or(0, |index| index + 1);
+            let whole = &number[..numerator_start];
+            xml.push_str("<number:fraction");
+            if whole.iter().any(|t| matches!(t, Token::Digit(_))) {
+                xml.push_str(&format!(
+                    " number:min-integer-digits=\"{}\"",
+                    count(whole, true)
+                ));
+            }
+            let denominator: String = number[slash_at + 1..]
+                .iter()
+                .filter_map(|t| match t {
+                    Token::Literal(literal) => Some(literal.trim()),
+                    _ => None,
+                })
+                .collect();
+            xml.push_str(&format!(
+                " number:min-numerator-digits=\"{}\" number:min-denominator-digits=\"{}\"",
+                count(&number[numerator_start..slash_at], false).max(1),
+                count(&number[slash_at + 1..], false).max(1)
+            ));
+            if let Ok(denominator) = denominator.parse::<u64>() {
+                xml.push_str(&format!(" number:denominator-value=\"{}\"", denominator));
+            }
+            xml.push_str("/>");
+        } else {
+            let point_at = number
+                .iter()
+                .position(|t| *t == Token::Point)
+                .unwrap_or(number.len());
+            let integer = &number[..point_at];
+            let (grouped, scale) = commas(&tokens[first..], point_at);
+            xml.push_str(&format!(
+                "<number:number number:decimal-places=\"{}\" \
+                 number:min-decimal-places=\"{}\" number:min-integer-digits=\"{}\"",
+                count(&number[point_at..], false),
+                count(&number[point_at..], true),
+                count(integer, true)
+            ));
+            if grouped {
+                xml.push_str(" number:grouping=\"true\"");
+            }
+            if scale > 0 {
+                xml.push_str(&format!(
+                    " number:display-factor=\"{}\"",
+                    1000u64.pow(scale as u32)
+                ));
+            }
+            xml.push_str("/>");
+        }
+        for token in tokens[last + 1..].iter() {
+            match token {
+                Token::Literal(literal) => push_text(xml, literal),
+                Token::Text => xml.push_str("<number:text-content/>"),
+                _ => {}
+            }
+        }
+    }
+
+    fn date_elements(&self, xml: &mut String) {
+        let long = |count: usize, limit: usize| match count >= limit {
+            true => " number:style=\"long\"",
+            false => "",
+        };
+        let mut elements: Vec<String> = vec![];
+        for token in self.tokens.iter() {
+            let element = match token {
+                Token::Literal(literal) => {
+                    let mut text = String::new();
+                    push_text(&mut text, literal);
+                    text
+                }
+                Token::Slash => "<number:text>/</number:text>".to_string(),
+                Token::Point => "<number:text>.</number:text>".to_string(),
+                Token::Comma => "<number:text>,</number:text>".to_string(),
+                Token::Year(count) => format!("<number:year{}/>", long(*count, 3)),
+                Token::Month(count @ 1..=2) => format!("<number:month{}/>", long(*count, 2)),
+                Token::Month(count) => {
+                    format!("<number:month number:textual=\"true\"{}/>", long(*count, 4))
+                }
+                Token::Day(count @ 1..=2) => format!("<number:day{}/>", long(*count, 2)),
+                Token::Day(count) => format!("<number:day-of-week{}/>", long(*count, 4)),
+                Token::Hour(count) => format!("<number:hours{}/>", long(*count, 2)),
+                Token::Minute(count) => format!("<number:minutes{}/>", long(*count, 2)),
+                Token::Second(count) => format!("<number:seconds{}/>", long(*count, 2)),
+                Token::Elapsed('h', count) => format!("<number:hours{}/>", long(*count, 2)),
+                Token::Elapsed('m', count) => format!("<number:minutes{}/>", long(*count, 2)),
+                Token::Elapsed(_, count) => format!("<number:seconds{}/>", long(*count, 2)),
+                // Fractions of seconds are decimals of the seconds before them
+                Token::SubSecond(digits) => {
+                    if let Some(seconds) = elements
+                        .iter_mut()
+                        .rev()
+                        .find(|e| e.starts_with("<number:seconds"))
+                    {
+                        *seconds = seconds
+                            .replace("/>", &format!(" number:decimal-places=\"{}\"/>", digits));
+                    }
+                    continue;
+                }
+                Token::AmPm(_) => "<number:am-pm/>".to_string(),
+                _ => continue,
+            };
+            elements.push(element);
+        }
+        xml.push_str(&elements.concat());
+    }
+}
+
+fn push_text(xml: &mut String, text: &str) {
+    xml.push_str(&format!("<number:text>{}</number:text>", escape(text)));
+}
+
+/// The colors format codes can name, like `[Red]`.
+fn named_color(name: &str) -> Option<u32> {
+    let color = match name {
+        "black" => 0x000000,
+        "blue" => 0x0000FF,
+        "cyan" => 0x00FFFF,
+        "green" => 0x00FF00,
+        "magenta" => 0xFF00FF,
+        "red" => 0xFF0000,
+        "white" => 0xFFFFFF,
+        "yellow" => 0xFFFF00,
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// What the commas of a mantissa do: commas between the integer digits
+/// group thousands, and commas after the last digit, decimals included,
+/// divide by a thousand each.
+fn commas(mantissa: &[Token], point_at: usize) -> (bool, i32) {
+    let is_digit = |t: &Token| matches!(t, Token::Digit(_));
+    let first_digit = mantissa[..point_at].iter().position(is_digit);
+    let last_integer = mantissa[..point_at].iter().rposition(is_digit);
+    let last_digit = mantissa.iter().rposition(is_digit);
+
+    let mut grouped = false;
+    let mut scale = 0;
+    for (index, token) in mantissa.iter().enumerate() {
+        if *token != Token::Comma {
+            continue;
+        }
+        match (first_digit, last_integer, last_digit) {
+            (_, _, Some(last)) if index > last => scale += 1,
+            (Some(first), Some(last), _) if index > first && index < last => grouped = true,
+            _ => {}
+        }
+    }
+    (grouped, scale)
+}
+
+/// Fills the digit placeholders of the integer part from the right. Digits
+/// beyond the placeholders go before the first one, and missing ones show as
+/// `0` for `0`, a space for `?` and nothing for `#`.
+fn fill_integer(tokens: &[Token], digits: &str, grouped: bool) -> String {
+    let placeholders = tokens
+        .iter()
+        .filter(|t| matches!(t, Token::Digit(_)))
+        .count();
+    let mut digits = digits.chars().rev();
+    let mut seen = 0;
+    // Digits written so far, for the thousands separators
+    let mut written = 0;
+    let mut shown: Vec<String> = vec![];
+    let mut push_digit = |shown: &mut Vec<String>, digit: char| {
+        if grouped && written > 0 && written % 3 == 0 {
+            shown.push(",".to_string());
+        }
+        shown.push(digit.to_string());
+        written += 1;
+    };
+    for token in tokens.iter().rev() {
+        match token {
+            Token::Digit(placeholder) => {
+                seen += 1;
+                let last = seen == placeholders;
+                match digits.next() {
+                    Some(digit) => push_digit(&mut shown, digit),
+                    None if *placeholder == '0' => push_digit(&mut shown, '0'),
+                    None if *placeholder == '?' => shown.push(" ".to_string()),
+                    None => {}
+                }
+                // The first placeholder takes every digit left
+                if last {
+                    for digit in digits.by_ref() {
+                        push_digit(&mut shown, digit);
+                    }
+                }
+            }
+            Token::Literal(literal) => shown.push(literal.clone()),
+            _ => {}
+        }
+    }
+    shown.reverse();
+    shown.concat()
+}
+
+/// Fills the placeholders after the decimal point from the left. Trailing
+/// zeros show as `0` for `0`, a space for `?` and nothing for `#`.
+fn fill_fraction(tokens: &[Token], digits: &str) -> String {
+    let digits: Vec<char> = digits.chars().collect();
+    let placeholders: Vec<char> = tokens
+        .iter()
+        .filter_map(|t| match t {
+            Token::Digit(placeholder) => Some(*placeholder),
+            _ => None,
+        })
+        .collect();
+    // The last digit that is shown as it is
+    let mut significant = digits.len();
+    while significant > 0 && digits[significant - 1] == '0' && placeholders[significant - 1] != '0'
+    {
+        significant -= 1;
+    }
+    let mut index = 0;
+    let mut shown = String::new();
+    for token in tokens {
+        match token {
+            Token::Point => shown.push('.'),
+            Token::Digit(placeholder) => {
+                if index < significant {
+                    shown.push(digits[index]);
+                } else if *placeholder == '?' {
+                    shown.push(' ');
+                }
+                index += 1;
+            }
+            Token::Literal(literal) => shown.push_str(literal),
+            _ => {}
+        }
+    }
+    shown
+}
+
+/// The closest fraction to `value`, below 1, with a denominator up to `max`.
+fn closest_fraction(value: f64, max: u64) -> (u64, u64) {
+    let mut best = (value.round() as u64, 1);
+    let mut best_error = (value - value.round()).abs();
+    for denominator in 2..=max.max(1) {
+        let numerator = (value * denominator as f64).round();
+        let error = (value - numerator / denominator as f64).abs();
+        if error < best_error - 1e-12 {
+            best = (numerator as u64, denominator);
+            best_error = error;
+        }
+    }
+    best
+}
+
+/// Rounds half away from zero, like Excel, to `decimals` places.
+fn round(number: f64, decimals: usize) -> f64 {
+    let factor = 10f64.powi(decimals as i32);
+    let scaled = number * factor;
+    // Undo the binary error of numbers like 1.005 before rounding
+    let scaled: f64 = format!("{:.9}", scaled).parse().unwrap_or(scaled);
+    scaled.round() / factor
+}
+
+fn pad(value: u64, width: usize) -> String {
+    format!("{:0width$}", value, width = width.min(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commas_after_the_digits_scale() {
+        assert_eq!(format_number("0,", 12345.0), "12");
+        assert_eq!(format_number("0.0,,\"M\"", 12345678.0), "12.3M");
+        assert_eq!(format_number("#,##0,", 1234567.0), "1,235");
+        assert_eq!(format_number("#,##0.0,\"K\"", 1234567.0), "1,234.6K");
+        assert_eq!(format_number("#,##0", 1234567.0), "1,234,567");
+    }
+
+    #[test]
+    fn commas_after_the_digits_set_the_display_factor() {
+        let style = |code| data_style(code, "N1").unwrap().xml;
+        assert!(style("0,").contains("number:display-factor=\"1000\""));
+        assert!(style("0.0,,\"M\"").contains(
+            "number:decimal-places=\"1\" number:min-decimal-places=\"1\" \
+             number:min-integer-digits=\"1\" number:display-factor=\"1000000\"/>\
+             <number:text>M</number:text>"
+        ));
+        let grouped = style("#,##0,");
+        assert!(grouped.contains("number:grouping=\"true\" number:display-factor=\"1000\""));
+    }
+}
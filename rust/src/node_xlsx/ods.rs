@@ -0,0 +1,645 @@
+use std::{
+    collections::HashMap,
+    io::{Cursor, Write},
+};
+
+use chrono::NaiveDateTime;
+use rust_xlsxwriter::{FormatAlign, FormatDiagonalBorder, FormatScript, FormatUnderline};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::{
+    border::Border,
+    color::Color,
+    error::NodeXlsxError,
+    export::{export_value, ExportValue},
+    format::{NodeXlsxFormat, ThemeFont},
+    formula::{
+        open_formula::to_open_formula,
+        result::FormulaResult,
+        value::{number_to_string, serial_to_date},
+    },
+    number_format::{data_style, DataStyleKind},
+    sheet::{GridCell, GridValue, SheetGrid},
+    xml::{escape, escape_attribute},
+    NodeXlsxWorkbook,
+};
+
+const MIME_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+const NAMESPACES: &str = "xmlns:office=\"urn:oasis:names:tc:opendocument:xmlns:office:1.0\" \
+    xmlns:style=\"urn:oasis:names:tc:opendocument:xmlns:style:1.0\" \
+    xmlns:text=\"urn:oasis:names:tc:opendocument:xmlns:text:1.0\" \
+    xmlns:table=\"urn:oasis:names:tc:opendocument:xmlns:table:1.0\" \
+    xmlns:fo=\"urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0\" \
+    xmlns:number=\"urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0\" \
+    xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+    xmlns:of=\"urn:oasis:names:tc:opendocument:xmlns:of:1.2\" \
+    xmlns:msoxl=\"http://schemas.microsoft.com/office/excel/formula\" \
+    office:version=\"1.3\"";
+
+/// The size of the rows and columns Excel gives a sheet, in pixels.
+const DEFAULT_ROW_HEIGHT: f64 = 20.0;
+const DEFAULT_COLUMN_WIDTH: f64 = 64.0;
+
+/// The automatic style of the cells with one format.
+struct CellStyle {
+    name: String,
+    /// What the data style of the format shows, None for `General`
+    kind: Option<DataStyleKind>,
+}
+
+/// A workbook written as an OpenDocument spreadsheet. Formats become
+/// automatic cell styles, shared by the cells that use the same format, and
+/// formulas are written in OpenFormula along with their results.
+pub struct OdsWriter<'a> {
+    workbook: &'a NodeXlsxWorkbook,
+    cell_styles: HashMap<u32, CellStyle>,
+    /// The column and row styles, by their size
+    size_styles: HashMap<(char, String), String>,
+    /// The XML of the automatic styles
+    styles: String,
+}
+
+impl<'a> OdsWriter<'a> {
+    pub fn new(workbook: &'a NodeXlsxWorkbook) -> Self {
+        Self {
+            workbook,
+            cell_styles: HashMap::new(),
+            size_styles: HashMap::new(),
+            styles: String::new(),
+        }
+    }
+
+    /// The package of the spreadsheet. The `mimetype` is its first file, and
+    /// is stored uncompressed so that it can be recognized.
+    pub fn write(mut self) -> Result<Vec<u8>, NodeXlsxError> {
+        let mut body = String::new();
+        for sheet in self.workbook.sheets.iter() {
+            let grid = sheet.grid()?;
+            self.write_table(&mut body, sheet.name(), &grid);
+        }
+        let content = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <office:document-content {}>\
+             <office:automatic-styles>\
+             <style:style style:name=\"ta1\" style:family=\"table\" style:master-page-name=\"Default\">\
+             <style:table-properties table:display=\"true\" style:writing-mode=\"lr-tb\"/>\
+             </style:style>{}</office:automatic-styles>\
+             <office:body><office:spreadsheet>{}</office:spreadsheet></office:body>\
+             </office:document-content>",
+            NAMESPACES, self.styles, body
+        );
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        writer.start_file("mimetype", stored)?;
+        writer.write_all(MIME_TYPE.as_bytes())?;
+        for (name, text) in [
+            ("META-INF/manifest.xml", manifest()),
+            ("styles.xml", styles()),
+            ("content.xml", content),
+        ] {
+            writer.start_file(name, deflated)?;
+            writer.write_all(text.as_bytes())?;
+        }
+        Ok(writer.finish()?.into_inner())
+    }
+
+    fn write_table(&mut self, xml: &mut String, name: &str, grid: &SheetGrid) {
+        let (last_row, last_col) = grid.last_cell().unwrap_or((0, 0));
+        // Rows and columns with a size, a format or hidden are written even past the cells
+        let last_row = grid.rows.keys().copied().fold(last_row, u32::max);
+        let last_col = grid.columns.keys().copied().fold(last_col, u16::max);
+
+        xml.push_str(&format!(
+            "<table:table table:name=\"{}\" table:style-name=\"ta1\">",
+            escape_attribute(name)
+        ));
+        let columns: Vec<String> = (0..=last_col)
+            .map(|col| {
+                let line = grid.columns.get(&col);
+                let width = line.and_then(|line| line.size);
+                let mut column = format!(
+                    "<table:table-column table:style-name=\"{}\"",
+                    self.size_style('c', width.unwrap_or(DEFAULT_COLUMN_WIDTH))
+                );
+                if line.is_some_and(|line| line.hidden) {
+                    column.push_str(" table:visibility=\"collapse\"");
+                }
+                let format = line.and_then(|line| line.format);
+                let style = format.map(|id| self.cell_style(id).name.clone());
+                column.push_str(&format!(
+                    " table:default-cell-style-name=\"{}\"/>",
+                    style.as_deref().unwrap_or("Default")
+                ));
+                column
+            })
+            .collect();
+        push_repeated(xml, &columns, "table:number-columns-repeated");
+
+        let rows: Vec<String> = (0..=last_row)
+            .map(|row| {
+                let line = grid.rows.get(&row);
+                let style = match line.and_then(|line| line.size) {
+                    Some(height) => self.size_style('r', height),
+                    None => self.size_style('o', DEFAULT_ROW_HEIGHT),
+                };
+                let mut element = format!("<table:table-row table:style-name=\"{}\"", style);
+                if line.is_some_and(|line| line.hidden) {
+                    element.push_str(" table:visibility=\"collapse\"");
+                }
+                if let Some(id) = line.and_then(|line| line.format) {
+                    let style = &self.cell_style(id).name;
+                    element.push_str(&format!(" table:default-cell-style-name=\"{}\"", style));
+                }
+                element.push('>');
+                let cells: Vec<String> = (0..=last_col)
+                    .map(|col| self.cell(grid, row, col))
+                    .collect();
+                push_repeated(&mut element, &cells, "table:number-columns-repeated");
+                element.push_str("</table:table-row>");
+                element
+            })
+            .collect();
+        push_repeated(xml, &rows, "table:number-rows-repeated");
+        xml.push_str("</table:table>");
+    }
+
+    fn cell(&mut self, grid: &SheetGrid, row: u32, col: u16) -> String {
+        let merge = grid.merge_at(row, col);
+        if merge.is_some_and(|range| (range.start_row, range.start_col) != (row, col as u32)) {
+            return "<table:covered-table-cell/>".to_string();
+        }
+        let Some(cell) = grid.cells.get(&(row, col)) else {
+            return "<table:table-cell/>".to_string();
+        };
+
+        let mut element = String::from("<table:table-cell");
+        let style = cell.format.map(|id| self.cell_style(id));
+        let kind = style.and_then(|style| style.kind);
+        match style {
+            Some(style) => element.push_str(&format!(" table:style-name=\"{}\"", style.name)),
+            // Cells without a format do not take the one of their row or column
+            None if grid.rows.get(&row).and_then(|line| line.format).is_some()
+                || grid
+                    .columns
+                    .get(&col)
+                    .and_then(|line| line.format)
+                    .is_some() =>
+            {
+                element.push_str(" table:style-name=\"Default\"")
+            }
+            None => {}
+        }
+        if let Some(range) = merge {
+            let rows = range.end_row - range.start_row + 1;
+            let columns = range.end_col - range.start_col + 1;
+            element.push_str(&format!(
+                " table:number-columns-spanned=\"{}\" table:number-rows-spanned=\"{}\"",
+                columns, rows
+            ));
+        }
+        element.push_str(&value_attributes(cell, kind));
+
+        let text = match export_value(cell, &self.workbook.format_map, true, false) {
+            ExportValue::Empty => String::new(),
+            ExportValue::Text(text) | ExportValue::Number(_, text) => text,
+            ExportValue::Boolean(value) => value.to_string().to_uppercase(),
+        };
+        if text.is_empty() {
+            element.push_str("/>");
+            return element;
+        }
+        element.push('>');
+        let href = match &cell.value {
+            Some(GridValue::Link { href, .. }) => Some(href.as_str()),
+            _ => None,
+        };
+        push_paragraphs(&mut element, &text, href);
+        element.push_str("</table:table-cell>");
+        element
+    }
+
+    /// The style of the cells with the format `id`, written the first time
+    /// it is used.
+    fn cell_style(&mut self, id: u32) -> &CellStyle {
+        if !self.cell_styles.contains_key(&id) {
+            let number = self.cell_styles.len() + 1;
+            let name = format!("ce{}", number);
+            let format = self.workbook.format_map.source(&id);
+            let data_style = format
+                .and_then(NodeXlsxFormat::number_format)
+                .and_then(|code| data_style(code, &format!("N{}", number)));
+            let mut xml = format!(
+                "<style:style style:name=\"{}\" style:family=\"table-cell\" \
+                 style:parent-style-name=\"Default\"",
+                name
+            );
+            if let Some(data_style) = &data_style {
+                self.styles.push_str(&data_style.xml);
+                xml.push_str(&format!(" style:data-style-name=\"N{}\"", number));
+            }
+            xml.push('>');
+            if let Some(format) = format {
+                format_properties(&mut xml, format);
+            }
+            xml.push_str("</style:style>");
+            self.styles.push_str(&xml);
+            let kind = data_style.map(|data_style| data_style.kind);
+            self.cell_styles.insert(id, CellStyle { name, kind });
+        }
+        &self.cell_styles[&id]
+    }
+
+    /// The style of columns (`c`) or rows (`r`) of a size in pixels. Rows
+    /// without a size (`o`) take the height of their content.
+    fn size_style(&mut self, kind: char, size: f64) -> String {
+        let length = format!(
+            "{}in",
+            number_to_string((size / 96.0 * 10000.0).round() / 10000.0)
+        );
+        let key = (kind, length.clone());
+        if let Some(name) = self.size_styles.get(&key) {
+            return name.clone();
+        }
+        let column = kind == 'c';
+        let count = self
+            .size_styles
+            .keys()
+            .filter(|(k, _)| (*k == 'c') == column)
+            .count();
+        let xml = match column {
+            true => format!(
+                "<style:style style:name=\"co{}\" style:family=\"table-column\">\
+                 <style:table-column-properties fo:break-before=\"auto\" \
+                 style:column-width=\"{}\"/></style:style>",
+                count + 1,
+                length
+            ),
+            false => format!(
+                "<style:style style:name=\"ro{}\" style:family=\"table-row\">\
+                 <style:table-row-properties style:row-height=\"{}\" \
+                 fo:break-before=\"auto\" style:use-optimal-row-height=\"{}\"/>\
+                 </style:style>",
+                count + 1,
+                length,
+                kind == 'o'
+            ),
+        };
+        let name = format!("{}{}", if column { "co" } else { "ro" }, count + 1);
+        self.styles.push_str(&xml);
+        self.size_styles.insert(key, name.clone());
+        name
+    }
+}
+
+/// The type and value of a cell, and its formula. Numbers are written as
+/// dates, times or percentages when their format shows them so.
+fn value_attributes(cell: &GridCell, kind: Option<DataStyleKind>) -> String {
+    let number = |number: f64| match kind {
+        Some(DataStyleKind::Percentage) => format!(
+            " office:value-type=\"percentage\" office:value=\"{}\"",
+            number_to_string(number)
+        ),
+        Some(DataStyleKind::Date) if serial_to_date(number).is_some() => {
+            date_attributes(serial_to_date(number).unwrap_or_default())
+        }
+        Some(DataStyleKind::Time) if number >= 0.0 => {
+            let seconds = (number * 86_400.0).round() as u64;
+            format!(
+                " office:value-type=\"time\" office:time-value=\"PT{}H{:02}M{:02}S\"",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )
+        }
+        _ => format!(
+            " office:value-type=\"float\" office:value=\"{}\"",
+            number_to_string(number)
+        ),
+    };
+    match &cell.value {
+        None => String::new(),
+        Some(GridValue::Text(_) | GridValue::Link { .. }) => {
+            " office:value-type=\"string\"".to_string()
+        }
+        Some(GridValue::Number(value)) => number(*value),
+        Some(GridValue::Date(date)) => date_attributes(*date),
+        Some(GridValue::Boolean(value)) => boolean_attributes(*value),
+        Some(GridValue::Formula { formula, result }) => {
+            let mut attributes = String::new();
+            // Formulas OpenFormula cannot express are kept in the syntax of Excel
+            let formula = to_open_formula(formula).unwrap_or_else(|| format!("msoxl:{}", formula));
+            attributes.push_str(&format!(
+                " table:formula=\"{}\"",
+                escape_attribute(&formula)
+            ));
+            match result {
+                None => {}
+                Some(FormulaResult::Number(value)) => attributes.push_str(&number(*value)),
+                Some(FormulaResult::Boolean(value)) => {
+                    attributes.push_str(&boolean_attributes(*value))
+                }
+                Some(FormulaResult::Text(text)) => attributes.push_str(&format!(
+                    " office:value-type=\"string\" office:string-value=\"{}\"",
+                    escape_attribute(text)
+                )),
+                Some(FormulaResult::Error(_)) => {
+                    attributes.push_str(" office:value-type=\"string\" office:string-value=\"\"")
+                }
+            }
+            attributes
+        }
+    }
+}
+
+fn date_attributes(date: NaiveDateTime) -> String {
+    format!(
+        " office:value-type=\"date\" office:date-value=\"{}\"",
+        date.format("%Y-%m-%dT%H:%M:%S%.f")
+    )
+}
+
+fn boolean_attributes(value: bool) -> String {
+    format!(
+        " office:value-type=\"boolean\" office:boolean-value=\"{}\"",
+        value
+    )
+}
+
+/// Writes text as paragraphs, one for each line. Runs of spaces and tabs are
+/// kept with their own elements, which XML would collapse otherwise.
+fn push_paragraphs(xml: &mut String, text: &str, href: Option<&str>) {
+    for line in text.split('\n') {
+        xml.push_str("<text:p>");
+        if let Some(href) = href {
+            xml.push_str(&format!(
+                "<text:a xlink:type=\"simple\" xlink:href=\"{}\">",
+                escape_attribute(href)
+            ));
+        }
+        let mut spaces = 0;
+        let mut start = true;
+        for c in line.chars().chain(std::iter::once('\n')) {
+            if c == ' ' {
+                spaces += 1;
+                continue;
+            }
+            if spaces > 0 {
+                let collapsed = if start { spaces } else { spaces - 1 };
+                if !start {
+                    xml.push(' ');
+                }
+                if collapsed > 0 {
+                    xml.push_str(&format!("<text:s text:c=\"{}\"/>", collapsed));
+                }
+                spaces = 0;
+            }
+            start = false;
+            match c {
+                '\n' => {}
+                '\t' => xml.push_str("<text:tab/>"),
+                c => xml.push_str(&escape(&c.to_string())),
+            }
+        }
+        if href.is_some() {
+            xml.push_str("</text:a>");
+        }
+        xml.push_str("</text:p>");
+    }
+}
+
+/// Writes the rows or cells in `elements`, with runs of the same one
+/// written once and repeated.
+fn push_repeated(xml: &mut String, elements: &[String], attribute: &str) {
+    let mut index = 0;
+    while index < elements.len() {
+        let element = &elements[index];
+        let count = elements[index..]
+            .iter()
+            .take_while(|other| *other == element)
+            .count();
+        if count == 1 {
+            xml.push_str(element);
+        } else {
+            // The attribute goes right after the element name
+            let split = element.find([' ', '/', '>']).unwrap_or(element.len());
+            xml.push_str(&element[..split]);
+            xml.push_str(&format!(" {}=\"{}\"", attribute, count));
+            xml.push_str(&element[split..]);
+        }
+        index += count;
+    }
+}
+
+/// The cell, paragraph and text properties of a format.
+fn format_properties(xml: &mut String, format: &NodeXlsxFormat) {
+    let mut cell = String::new();
+    if let Some(rgb) = format.fill_rgb() {
+        cell.push_str(&format!(" fo:background-color=\"#{:06X}\"", rgb));
+    }
+    let borders = [
+        ("top", format.top_border),
+        ("bottom", format.bottom_border),
+        ("left", format.left_border),
+        ("right", format.right_border),
+    ];
+    for (side, border) in borders {
+        if let Some(border) = border {
+            cell.push_str(&format!(" fo:border-{}=\"{}\"", side, border_line(&border)));
+        }
+    }
+    if let Some(diagonal) = format.diagonal_border {
+        let line = border_line(&diagonal.border);
+        if matches!(
+            diagonal.d_type,
+            FormatDiagonalBorder::BorderUp | FormatDiagonalBorder::BorderUpDown
+        ) {
+            cell.push_str(&format!(" style:diagonal-bl-tr=\"{}\"", line));
+        }
+        if matches!(
+            diagonal.d_type,
+            FormatDiagonalBorder::BorderDown | FormatDiagonalBorder::BorderUpDown
+        ) {
+            cell.push_str(&format!(" style:diagonal-tl-br=\"{}\"", line));
+        }
+    }
+    if format.text_wrap == Some(true) {
+        cell.push_str(" fo:wrap-option=\"wrap\"");
+    }
+    if format.shrink == Some(true) {
+        cell.push_str(" style:shrink-to-fit=\"true\"");
+    }
+    let vertical_align = match format.vertical_align {
+        Some(FormatAlign::Top) => Some("top"),
+        Some(
+            FormatAlign::VerticalCenter
+            | FormatAlign::VerticalJustify
+            | FormatAlign::VerticalDistributed,
+        ) => Some("middle"),
+        Some(FormatAlign::Bottom) => Some("bottom"),
+        _ => None,
+    };
+    if let Some(vertical_align) = vertical_align {
+        cell.push_str(&format!(" style:vertical-align=\"{}\"", vertical_align));
+    }
+    match format.rotation {
+        // Letters stacked from top to bottom
+        Some(270) => cell.push_str(" style:direction=\"ttb\""),
+        Some(rotation) if rotation != 0 => cell.push_str(&format!(
+            " style:rotation-angle=\"{}\"",
+            (rotation as i32).rem_euclid(360)
+        )),
+        _ => {}
+    }
+    let protect = match (
+        format.locked.unwrap_or(true),
+        format.hidden.unwrap_or(false),
+    ) {
+        (true, false) => None,
+        (true, true) => Some("protected formula-hidden"),
+        (false, true) => Some("formula-hidden"),
+        (false, false) => Some("none"),
+    };
+    if let Some(protect) = protect {
+        cell.push_str(&format!(" style:cell-protect=\"{}\"", protect));
+    }
+    let align = match format.align {
+        Some(FormatAlign::Left | FormatAlign::Fill) => Some("start"),
+        Some(FormatAlign::Center | FormatAlign::CenterAcross) => Some("center"),
+        Some(FormatAlign::Right) => Some("end"),
+        Some(FormatAlign::Justify | FormatAlign::Distributed) => Some("justify"),
+        _ => None,
+    };
+    if align.is_some() {
+        cell.push_str(" style:text-align-source=\"fix\"");
+    }
+    if format.align == Some(FormatAlign::Fill) {
+        cell.push_str(" style:repeat-content=\"true\"");
+    }
+    if !cell.is_empty() {
+        xml.push_str(&format!("<style:table-cell-properties{}/>", cell));
+    }
+
+    let mut paragraph = String::new();
+    if let Some(align) = align {
+        paragraph.push_str(&format!(" fo:text-align=\"{}\"", align));
+    }
+    if let Some(indent) = format.indent.filter(|indent| *indent > 0) {
+        // An indent is three spaces of the default font, about 9 pixels
+        let side = if align == Some("end") {
+            "right"
+        } else {
+            "left"
+        };
+        paragraph.push_str(&format!(
+            " fo:margin-{}=\"{}pt\"",
+            side,
+            number_to_string(6.75 * indent as f64)
+        ));
+    }
+    if !paragraph.is_empty() {
+        xml.push_str(&format!("<style:paragraph-properties{}/>", paragraph));
+    }
+
+    let mut text = String::new();
+    let font_name = format
+        .font_name
+        .as_deref()
+        .or(format.theme_font.as_ref().map(ThemeFont::font_name));
+    if let Some(font_name) = font_name {
+        text.push_str(&format!(
+            " fo:font-family=\"{}\"",
+            escape_attribute(&format!("'{}'", font_name.replace('\'', "")))
+        ));
+    }
+    if let Some(font_size) = format.font_size {
+        text.push_str(&format!(" fo:font-size=\"{}pt\"", font_size));
+    }
+    match format.bold {
+        Some(true) => text.push_str(" fo:font-weight=\"bold\""),
+        Some(false) => text.push_str(" fo:font-weight=\"normal\""),
+        None => {}
+    }
+    match format.italic {
+        Some(true) => text.push_str(" fo:font-style=\"italic\""),
+        Some(false) => text.push_str(" fo:font-style=\"normal\""),
+        None => {}
+    }
+    match format.underline {
+        Some(FormatUnderline::Single | FormatUnderline::SingleAccounting) => text.push_str(
+            " style:text-underline-style=\"solid\" style:text-underline-width=\"auto\" \
+             style:text-underline-color=\"font-color\"",
+        ),
+        Some(FormatUnderline::Double | FormatUnderline::DoubleAccounting) => text.push_str(
+            " style:text-underline-style=\"solid\" style:text-underline-type=\"double\" \
+             style:text-underline-width=\"auto\" style:text-underline-color=\"font-color\"",
+        ),
+        _ => {}
+    }
+    if format.strike_through == Some(true) {
+        text.push_str(" style:text-line-through-style=\"solid\"");
+    }
+    if let Some(rgb) = format.font_color.as_ref().and_then(Color::rgb) {
+        text.push_str(&format!(" fo:color=\"#{:06X}\"", rgb));
+    }
+    match format.font_script {
+        Some(FormatScript::Superscript) => text.push_str(" style:text-position=\"super 58%\""),
+        Some(FormatScript::Subscript) => text.push_str(" style:text-position=\"sub 58%\""),
+        _ => {}
+    }
+    if !text.is_empty() {
+        xml.push_str(&format!("<style:text-properties{}/>", text));
+    }
+}
+
+/// An XSL-FO border for an Excel one, a pixel being 0.75 points.
+fn border_line(border: &Border) -> String {
+    let Some((width, style)) = border.line() else {
+        return "none".to_string();
+    };
+    let color = border.color.rgb().unwrap_or(0x000000);
+    format!(
+        "{}pt {} #{:06X}",
+        number_to_string(width as f64 * 0.75),
+        style,
+        color
+    )
+}
+
+fn manifest() -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" \
+         manifest:version=\"1.3\">\
+         <manifest:file-entry manifest:full-path=\"/\" manifest:version=\"1.3\" \
+         manifest:media-type=\"{}\"/>\
+         <manifest:file-entry manifest:full-path=\"content.xml\" manifest:media-type=\"text/xml\"/>\
+         <manifest:file-entry manifest:full-path=\"styles.xml\" manifest:media-type=\"text/xml\"/>\
+         </manifest:manifest>",
+        MIME_TYPE
+    )
+}
+
+/// The default cell style, in the font of Excel's default theme, and the
+/// page layout the sheets refer to.
+fn styles() -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <office:document-styles {}>\
+         <office:styles>\
+         <style:default-style style:family=\"table-cell\">\
+         <style:paragraph-properties style:tab-stop-distance=\"0.5in\"/>\
+         <style:text-properties fo:font-family=\"Calibri\" fo:font-size=\"11pt\"/>\
+         </style:default-style>\
+         <style:style style:name=\"Default\" style:family=\"table-cell\"/>\
+         </office:styles>\
+         <office:automatic-styles><style:page-layout style:name=\"pm1\"/></office:automatic-styles>\
+         <office:master-styles>\
+         <style:master-page style:name=\"Default\" style:page-layout-name=\"pm1\"/>\
+         </office:master-styles>\
+         </office:document-styles>",
+        NAMESPACES
+    )
+}
@@ -0,0 +1,59 @@
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Write},
+};
+
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use super::error::NodeXlsxError;
+
+/// Rewrites parts of a saved xlsx package. `rewrite` gets the name and the
+/// text of every XML part and returns the new text of the ones it changes,
+/// the other parts are copied as they are.
+pub fn rewrite_parts(
+    buffer: Vec<u8>,
+    mut rewrite: impl FnMut(&str, &str) -> Option<String>,
+) -> Result<Vec<u8>, NodeXlsxError> {
+    let mut archive = ZipArchive::new(Cursor::new(buffer))?;
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if !file.name().ends_with(".xml") {
+            writer.raw_copy_file(file)?;
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut text = String::with_capacity(file.size() as usize);
+        file.read_to_string(&mut text)?;
+        drop(file);
+        match rewrite(&name, &text) {
+            Some(text) => {
+                writer.start_file(name, options)?;
+                writer.write_all(text.as_bytes())?;
+            }
+            // The part was read, so it is opened again to copy it untouched
+            None => writer.raw_copy_file(archive.by_index(index)?)?,
+        }
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// The text of the XML parts of an xlsx package, relationships included,
+/// by their name in the package.
+pub fn read_parts(buffer: &[u8]) -> Result<HashMap<String, String>, NodeXlsxError> {
+    let mut archive = ZipArchive::new(Cursor::new(buffer))?;
+    let mut parts = HashMap::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let name = file.name().to_string();
+        if !name.ends_with(".xml") && !name.ends_with(".rels") {
+            continue;
+        }
+        let mut text = String::with_capacity(file.size() as usize);
+        file.read_to_string(&mut text)?;
+        parts.insert(name, text);
+    }
+    Ok(parts)
+}
@@ -0,0 +1,297 @@
+use rust_xlsxwriter::utility::row_col_to_cell;
+
+use crate::node_xlsx::{
+    cell_range::CellRange, conditional_format::icon::icon_type_name, xml::XmlElement,
+};
+
+use super::{
+    model::Model,
+    styles::{color, Styles},
+};
+
+type Properties = Vec<(&'static str, Model)>;
+
+/// Reads the conditional formats of a sheet as conditional format sheet
+/// values, each with its own rule. Rules the writers can't express, like
+/// the ones only stored in extensions, are left out.
+pub fn read_conditional_formats(
+    root: &XmlElement,
+    styles: &mut Styles,
+    next_id: &mut u32,
+) -> Vec<Model> {
+    let mut values = vec![];
+    for formatting in root.children_named("conditionalFormatting") {
+        let sqref = formatting.attribute("sqref").unwrap_or_default().trim();
+        let Some(first) = sqref.split_whitespace().next() else {
+            continue;
+        };
+        let Ok(range) = CellRange::parse(first) else {
+            continue;
+        };
+        let multi_range = sqref.contains(char::is_whitespace).then_some(sqref);
+
+        for rule in formatting.children_named("cfRule") {
+            let Some((kind, properties)) = read_rule(rule, &range) else {
+                continue;
+            };
+            let format = match rule.number("dxfId") {
+                Some(dxf) if uses_format(kind) => styles.dxf_format(dxf as u32),
+                _ => None,
+            };
+            let mut conditional_format = Model::object()
+                .with("id", *next_id)
+                .with("type", kind)
+                .with_opt("multiRange", multi_range)
+                .with_opt("stopIfTrue", rule.flag("stopIfTrue", false).then_some(true))
+                .with_opt("format", format);
+            *next_id += 1;
+            if let Model::Object(all) = &mut conditional_format {
+                all.extend(properties);
+            }
+            values.push(
+                Model::object()
+                    .with("range", first)
+                    .with("format", conditional_format)
+                    .with_opt("priority", rule.number("priority")),
+            );
+        }
+    }
+    values
+}
+
+fn uses_format(kind: &str) -> bool {
+    !matches!(
+        kind,
+        "twoColorScale" | "threeColorScale" | "dataBar" | "iconSet"
+    )
+}
+
+/// The type and options of a rule.
+fn read_rule(rule: &XmlElement, range: &CellRange) -> Option<(&'static str, Properties)> {
+    let formulas: Vec<&str> = rule
+        .children_named("formula")
+        .map(|formula| formula.text.as_str())
+        .collect();
+    let invert = |inverted: bool| vec![("invert", inverted.into())];
+
+    Some(match rule.attribute("type")? {
+        "cellIs" => cell_rule(rule.attribute("operator")?, &formulas, range)?,
+        "containsText" | "notContainsText" | "beginsWith" | "endsWith" => {
+            let kind = match rule.attribute("type")? {
+                "containsText" => "contains",
+                "notContainsText" => "doesNotContain",
+                kind => kind,
+            };
+            let text = Model::object()
+                .with("type", kind)
+                .with("value", rule.attribute("text")?);
+            ("text", vec![("rule", text)])
+        }
+        "aboveAverage" => {
+            let above = rule.flag("aboveAverage", true);
+            let direction = if above { "Above" } else { "Below" };
+            let average = match rule.number("stdDev").unwrap_or(0.0) as u32 {
+                0 if rule.flag("equalAverage", false) => match above {
+                    true => "equalOrAboveAverage".to_string(),
+                    false => "equalOrBelowAverage".to_string(),
+                },
+                0 => format!("{}Average", direction.to_lowercase()),
+                1 => format!("oneStandardDeviation{}", direction),
+                2 => format!("twoStandardDeviations{}", direction),
+                _ => format!("threeStandardDeviations{}", direction),
+            };
+            ("average", vec![("rule", average.into())])
+        }
+        "top10" => {
+            let kind = match (rule.flag("bottom", false), rule.flag("percent", false)) {
+                (false, false) => "top",
+                (true, false) => "bottom",
+                (false, true) => "topPercent",
+                (true, true) => "bottomPercent",
+            };
+            let top = Model::object()
+                .with("type", kind)
+                .with("value", rule.number("rank").unwrap_or(10.0));
+            ("top", vec![("rule", top)])
+        }
+        "duplicateValues" => ("duplicate", invert(false)),
+        "uniqueValues" => ("duplicate", invert(true)),
+        "containsBlanks" => ("blank", invert(false)),
+        "notContainsBlanks" => ("blank", invert(true)),
+        "containsErrors" => ("error", invert(false)),
+        "notContainsErrors" => ("error", invert(true)),
+        "timePeriod" => ("date", vec![("rule", rule.attribute("timePeriod")?.into())]),
+        "expression" => {
+            let formula = Model::object().with("formula", format!("={}", formulas.first()?));
+            ("formula", vec![("formula", formula)])
+        }
+        "colorScale" => color_scale(rule.child("colorScale")?)?,
+        "dataBar" => data_bar(rule.child("dataBar")?)?,
+        "iconSet" => icon_set(rule.child("iconSet")?)?,
+        _ => return None,
+    })
+}
+
+/// A cell rule, or a formula rule when the values are references or
+/// formulas, which cell rules would write as text.
+fn cell_rule(
+    operator: &str,
+    formulas: &[&str],
+    range: &CellRange,
+) -> Option<(&'static str, Properties)> {
+    let kind = match operator {
+        "equal" => "equalTo",
+        "notEqual" => "notEqualTo",
+        "greaterThan" => "greaterThan",
+        "greaterThanOrEqual" => "greaterThanOrEqualTo",
+        "lessThan" => "lessThan",
+        "lessThanOrEqual" => "lessThanOrEqualTo",
+        "between" => "between",
+        "notBetween" => "notBetween",
+        _ => return None,
+    };
+    let pair = matches!(kind, "between" | "notBetween");
+    let operands = &formulas[..formulas.len().min(if pair { 2 } else { 1 })];
+    if operands.is_empty() || (pair && operands.len() < 2) {
+        return None;
+    }
+
+    let numbers: Option<Vec<f64>> = operands.iter().map(|f| f.trim().parse().ok()).collect();
+    let strings = operands
+        .iter()
+        .all(|f| f.len() >= 2 && f.starts_with('"') && f.ends_with('"'));
+    let values: Option<Vec<Model>> = match (numbers, strings) {
+        (Some(numbers), _) => Some(numbers.into_iter().map(Model::from).collect()),
+        (None, true) => Some(operands.iter().map(|f| Model::from(*f)).collect()),
+        (None, false) => None,
+    };
+    if let Some(values) = values {
+        let mut values = values.into_iter();
+        let rule = Model::object()
+            .with("type", kind)
+            .with("value", values.next()?)
+            .with_opt("optionalValue", values.next());
+        return Some(("cell", vec![("rule", rule)]));
+    }
+
+    let cell = row_col_to_cell(range.start_row, range.start_col as u16);
+    let formula = match (kind, operands) {
+        ("between", [low, high]) => format!("=AND({cell}>={low},{cell}<={high})"),
+        ("notBetween", [low, high]) => format!("=OR({cell}<{low},{cell}>{high})"),
+        (_, [value, ..]) => {
+            let symbol = match kind {
+                "equalTo" => "=",
+                "notEqualTo" => "<>",
+                "greaterThan" => ">",
+                "greaterThanOrEqualTo" => ">=",
+                "lessThan" => "<",
+                _ => "<=",
+            };
+            format!("={cell}{symbol}{value}")
+        }
+        _ => return None,
+    };
+    let formula = Model::object().with("formula", formula);
+    Some(("formula", vec![("formula", formula)]))
+}
+
+/// The rule of a `cfvo` threshold.
+fn threshold_rule(cfvo: &XmlElement) -> Option<Model> {
+    let kind = match cfvo.attribute("type")? {
+        "num" => "number",
+        "percent" => "percent",
+        "percentile" => "percentile",
+        "formula" => "formula",
+        "min" => "lowest",
+        "max" => "highest",
+        _ => return None,
+    };
+    let value = cfvo.attribute("val").unwrap_or("0");
+    let value = match value.parse::<f64>() {
+        Ok(number) if kind != "formula" => Model::from(number),
+        _ => Model::from(value),
+    };
+    Some(Model::object().with("type", kind).with("value", value))
+}
+
+/// Whether a threshold is the lowest or highest value, the default ones of
+/// color scales and data bars.
+fn is_extreme(cfvo: &XmlElement) -> bool {
+    matches!(cfvo.attribute("type"), Some("min" | "max"))
+}
+
+fn color_scale(scale: &XmlElement) -> Option<(&'static str, Properties)> {
+    let thresholds: Vec<&XmlElement> = scale.children_named("cfvo").collect();
+    let colors: Vec<Option<Model>> = scale.children_named("color").map(color).collect();
+    let kind = match thresholds.len() {
+        2 => "twoColorScale",
+        3 => "threeColorScale",
+        _ => return None,
+    };
+
+    let mut properties = vec![];
+    let last = thresholds.len() - 1;
+    let names = match kind {
+        "twoColorScale" => [("minColor", "minRule"), ("maxColor", "maxRule")].as_slice(),
+        _ => [
+            ("minColor", "minRule"),
+            ("midColor", "midRule"),
+            ("maxColor", "maxRule"),
+        ]
+        .as_slice(),
+    };
+    for (index, (color_name, rule_name)) in names.iter().enumerate() {
+        if let Some(Some(color)) = colors.get(index) {
+            properties.push((*color_name, color.clone()));
+        }
+        let threshold = thresholds[index];
+        let is_end = index == 0 || index == last;
+        if !(is_end && is_extreme(threshold)) {
+            if let Some(rule) = threshold_rule(threshold) {
+                properties.push((*rule_name, rule));
+            }
+        }
+    }
+    Some((kind, properties))
+}
+
+fn data_bar(bar: &XmlElement) -> Option<(&'static str, Properties)> {
+    let thresholds: Vec<&XmlElement> = bar.children_named("cfvo").collect();
+    let mut properties = vec![];
+    if let Some(fill) = bar.child("color").and_then(color) {
+        properties.push(("fillColor", fill));
+    }
+    for (threshold, name) in thresholds.iter().zip(["minRule", "maxRule"]) {
+        if !is_extreme(threshold) {
+            if let Some(rule) = threshold_rule(threshold) {
+                properties.push((name, rule));
+            }
+        }
+    }
+    if !bar.flag("showValue", true) {
+        properties.push(("barOnly", true.into()));
+    }
+    Some(("dataBar", properties))
+}
+
+fn icon_set(set: &XmlElement) -> Option<(&'static str, Properties)> {
+    let icon_type = icon_type_name(set.attribute("iconSet").unwrap_or("3TrafficLights1"))?;
+    let icons: Vec<Model> = set
+        .children_named("cfvo")
+        .map(|cfvo| {
+            Model::object()
+                .with("greaterThan", cfvo.attribute("gte") == Some("0"))
+                .with("noIcon", false)
+                .with_opt("iconRule", threshold_rule(cfvo))
+        })
+        .collect();
+    Some((
+        "iconSet",
+        vec![
+            ("iconType", icon_type.into()),
+            ("reverse", set.flag("reverse", false).into()),
+            ("showIconsOnly", (!set.flag("showValue", true)).into()),
+            ("icons", icons.into()),
+        ],
+    ))
+}
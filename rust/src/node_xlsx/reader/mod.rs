@@ -0,0 +1,233 @@
+mod conditional_format;
+mod model;
+mod styles;
+mod worksheet;
+
+use std::collections::HashMap;
+
+use super::{error::NodeXlsxError, package::read_parts, xml::XmlElement};
+
+pub use self::model::Model;
+use self::{styles::Styles, worksheet::read_worksheet};
+
+/// A relationship of a package part, with its target resolved to the name
+/// of the part it points to unless it is external.
+pub struct Relationship {
+    /// The last segment of the relationship type, like `worksheet`
+    pub kind: String,
+    pub target: String,
+    pub external: bool,
+}
+
+/// What the sheets of a workbook share while they are read.
+pub struct WorkbookReader {
+    parts: HashMap<String, String>,
+    strings: Vec<String>,
+    styles: Styles,
+    date1904: bool,
+    next_conditional_format: u32,
+}
+
+impl WorkbookReader {
+    /// The root element of a part, None when the package doesn't have it.
+    fn part(&self, name: &str) -> Result<Option<XmlElement>, NodeXlsxError> {
+        match self.parts.get(name) {
+            Some(xml) => Ok(Some(XmlElement::parse(xml)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The relationships of a part, by their id.
+    fn relationships(&self, part: &str) -> Result<HashMap<String, Relationship>, NodeXlsxError> {
+        relationships(&self.parts, part)
+    }
+
+    /// The number of a serial date and time, counted from 1900.
+    fn serial(&self, serial: f64) -> f64 {
+        match self.date1904 {
+            true => serial + 1462.0,
+            false => serial,
+        }
+    }
+}
+
+/// Reads an xlsx package into the plain workbook object the writers accept:
+/// `{ formats, sheets }`, where cells, rows, columns, tables and conditional
+/// formats refer to the formats by their id.
+pub fn read_workbook(buffer: &[u8]) -> Result<Model, NodeXlsxError> {
+    let mut reader = WorkbookReader {
+        parts: read_parts(buffer)?,
+        strings: vec![],
+        styles: Styles::default(),
+        date1904: false,
+        next_conditional_format: 1,
+    };
+
+    let workbook_part = workbook_part(&reader.parts)?;
+    let workbook = reader
+        .part(&workbook_part)?
+        .ok_or_else(|| NodeXlsxError::new("The package has no workbook".to_string()))?;
+    reader.date1904 = workbook
+        .child("workbookPr")
+        .is_some_and(|properties| properties.flag("date1904", false));
+
+    let relationships = reader.relationships(&workbook_part)?;
+    for relationship in relationships.values() {
+        match relationship.kind.as_str() {
+            "sharedStrings" => {
+                if let Some(root) = reader.part(&relationship.target)? {
+                    reader.strings = root.children_named("si").map(rich_text).collect();
+                }
+            }
+            "styles" => {
+                if let Some(root) = reader.part(&relationship.target)? {
+                    reader.styles = Styles::parse(&root);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut sheets = vec![];
+    let sheet_elements = workbook
+        .child("sheets")
+        .map(|sheets| sheets.children_named("sheet").collect::<Vec<_>>())
+        .unwrap_or_default();
+    for sheet in sheet_elements {
+        let name = sheet.attribute("name").unwrap_or_default();
+        let relationship = sheet
+            .attribute("id")
+            .and_then(|id| relationships.get(id))
+            .filter(|relationship| relationship.kind == "worksheet");
+        // Chart sheets and dialog sheets have no cells
+        let Some(relationship) = relationship else {
+            continue;
+        };
+        let Some(root) = reader.part(&relationship.target)? else {
+            continue;
+        };
+        sheets.push(read_worksheet(
+            &mut reader,
+            name,
+            &root,
+            &relationship.target,
+        )?);
+    }
+
+    let formats = std::mem::take(&mut reader.styles).into_formats();
+    Ok(Model::object()
+        .with("formats", formats)
+        .with("sheets", sheets))
+}
+
+/// The relationships of a part of a package, by their id.
+pub fn relationships(
+    parts: &HashMap<String, String>,
+    part: &str,
+) -> Result<HashMap<String, Relationship>, NodeXlsxError> {
+    let (directory, file) = part.rsplit_once('/').unwrap_or(("", part));
+    let name = match directory {
+        "" => format!("_rels/{}.rels", file),
+        directory => format!("{}/_rels/{}.rels", directory, file),
+    };
+    let Some(xml) = parts.get(&name) else {
+        return Ok(HashMap::new());
+    };
+    let root = XmlElement::parse(xml)?;
+    let mut relationships = HashMap::new();
+    for relationship in root.children_named("Relationship") {
+        let (Some(id), Some(target)) = (
+            relationship.attribute("Id"),
+            relationship.attribute("Target"),
+        ) else {
+            continue;
+        };
+        let kind = relationship.attribute("Type").unwrap_or_default();
+        let kind = kind.rsplit('/').next().unwrap_or(kind).to_string();
+        let external = relationship.attribute("TargetMode") == Some("External");
+        let target = match external {
+            true => target.to_string(),
+            false => resolve(directory, target),
+        };
+        relationships.insert(
+            id.to_string(),
+            Relationship {
+                kind,
+                target,
+                external,
+            },
+        );
+    }
+    Ok(relationships)
+}
+
+/// The name of the workbook part of a package.
+pub fn workbook_part(parts: &HashMap<String, String>) -> Result<String, NodeXlsxError> {
+    Ok(relationships(parts, "")?
+        .into_values()
+        .find(|relationship| relationship.kind == "officeDocument")
+        .map(|relationship| relationship.target)
+        .unwrap_or_else(|| "xl/workbook.xml".to_string()))
+}
+
+/// The text of a shared or inline string, the runs of rich text joined.
+/// Phonetic runs are left out.
+pub fn rich_text(element: &XmlElement) -> String {
+    let text = match element.child("t") {
+        Some(text) => text.text.clone(),
+        None => element
+            .children_named("r")
+            .filter_map(|run| run.child("t"))
+            .map(|text| text.text.as_str())
+            .collect(),
+    };
+    unescape_characters(&text)
+}
+
+/// Replaces the `_xHHHH_` escapes Excel writes for control characters.
+fn unescape_characters(text: &str) -> String {
+    if !text.contains("_x") {
+        return text.to_string();
+    }
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("_x") {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let character = rest
+            .get(2..7)
+            .filter(|escape| escape.ends_with('_'))
+            .and_then(|escape| u32::from_str_radix(&escape[..4], 16).ok())
+            .and_then(char::from_u32);
+        match character {
+            Some(character) => {
+                unescaped.push(character);
+                rest = &rest[7..];
+            }
+            None => {
+                unescaped.push_str("_x");
+                rest = &rest[2..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// The name of the part `target` points to from `directory`.
+fn resolve(directory: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut segments: Vec<&str> = directory.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
@@ -0,0 +1,106 @@
+use neon::{context::Context, handle::Handle, object::Object, result::JsResult, types::JsValue};
+
+/// A plain JS value built away from the main thread, in the shape the
+/// writers accept.
+#[derive(Clone)]
+pub enum Model {
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    /// Milliseconds since the Unix epoch
+    Date(f64),
+    Array(Vec<Model>),
+    Object(Vec<(&'static str, Model)>),
+}
+
+impl Model {
+    pub fn object() -> Self {
+        Self::Object(vec![])
+    }
+
+    /// The object with one more property.
+    pub fn with(mut self, key: &'static str, value: impl Into<Model>) -> Self {
+        if let Self::Object(properties) = &mut self {
+            properties.push((key, value.into()));
+        }
+        self
+    }
+
+    /// The object with one more property when `value` is set.
+    pub fn with_opt(self, key: &'static str, value: Option<impl Into<Model>>) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self,
+        }
+    }
+
+    pub fn to_js<'a>(&self, cx: &mut impl Context<'a>) -> JsResult<'a, JsValue> {
+        Ok(match self {
+            Self::Boolean(boolean) => cx.boolean(*boolean).upcast(),
+            Self::Number(number) => cx.number(*number).upcast(),
+            Self::String(string) => cx.string(string).upcast(),
+            Self::Date(milliseconds) => match cx.date(*milliseconds) {
+                Ok(date) => date.upcast(),
+                Err(error) => return cx.throw_range_error(error.to_string()),
+            },
+            Self::Array(items) => {
+                let array = cx.empty_array();
+                for (index, item) in items.iter().enumerate() {
+                    let item = item.to_js(cx)?;
+                    array.set(cx, index as u32, item)?;
+                }
+                array.upcast()
+            }
+            Self::Object(properties) => {
+                let object = cx.empty_object();
+                for (key, value) in properties {
+                    let value: Handle<JsValue> = value.to_js(cx)?;
+                    object.set(cx, *key, value)?;
+                }
+                object.upcast()
+            }
+        })
+    }
+}
+
+impl From<bool> for Model {
+    fn from(boolean: bool) -> Self {
+        Self::Boolean(boolean)
+    }
+}
+
+impl From<f64> for Model {
+    fn from(number: f64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<u32> for Model {
+    fn from(number: u32) -> Self {
+        Self::Number(number as f64)
+    }
+}
+
+impl From<u16> for Model {
+    fn from(number: u16) -> Self {
+        Self::Number(number as f64)
+    }
+}
+
+impl From<String> for Model {
+    fn from(string: String) -> Self {
+        Self::String(string)
+    }
+}
+
+impl From<&str> for Model {
+    fn from(string: &str) -> Self {
+        Self::String(string.to_string())
+    }
+}
+
+impl From<Vec<Model>> for Model {
+    fn from(items: Vec<Model>) -> Self {
+        Self::Array(items)
+    }
+}
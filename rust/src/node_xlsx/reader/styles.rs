@@ -0,0 +1,451 @@
+use std::collections::HashMap;
+
+use crate::node_xlsx::{color::SHADE_TINTS, xml::XmlElement};
+
+use super::model::Model;
+
+type Properties = Vec<(&'static str, Model)>;
+
+/// The legacy palette of `indexed` colors.
+const INDEXED_COLORS: [u32; 64] = [
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, //
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, //
+    0x800000, 0x008000, 0x000080, 0x808000, 0x800080, 0x008080, 0xC0C0C0, 0x808080, //
+    0x9999FF, 0x993366, 0xFFFFCC, 0xCCFFFF, 0x660066, 0xFF8080, 0x0066CC, 0xCCCCFF, //
+    0x000080, 0xFF00FF, 0xFFFF00, 0x00FFFF, 0x800080, 0x800000, 0x008080, 0x0000FF, //
+    0x00CCFF, 0xCCFFFF, 0xCCFFCC, 0xFFFF99, 0x99CCFF, 0xFF99CC, 0xCC99FF, 0xFFCC99, //
+    0x3366FF, 0x33CCCC, 0x99CC00, 0xFFCC00, 0xFF9900, 0xFF6600, 0x666699, 0x969696, //
+    0x003366, 0x339966, 0x003300, 0x333300, 0x993300, 0x993366, 0x333399, 0x333333, //
+];
+
+struct CellFormat {
+    properties: Properties,
+    date: bool,
+}
+
+/// The cell and conditional formats of `xl/styles.xml`, turned into
+/// `Format` options the first time a cell uses them.
+#[derive(Default)]
+pub struct Styles {
+    cell_formats: Vec<CellFormat>,
+    dxfs: Vec<Properties>,
+    formats: Vec<Model>,
+    cell_ids: HashMap<u32, Option<u32>>,
+    dxf_ids: HashMap<u32, u32>,
+    empty: Option<u32>,
+}
+
+impl Styles {
+    pub fn parse(root: &XmlElement) -> Self {
+        let number_formats: HashMap<u32, String> = root
+            .child("numFmts")
+            .map(|formats| {
+                formats
+                    .children_named("numFmt")
+                    .filter_map(|format| {
+                        let id = format.number("numFmtId")? as u32;
+                        Some((id, format.attribute("formatCode")?.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let list = |name: &str, item: &str, parse: &dyn Fn(&XmlElement) -> Properties| {
+            root.child(name)
+                .map(|list| list.children_named(item).map(parse).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+        let fonts = list("fonts", "font", &font_properties);
+        let fills = list("fills", "fill", &|fill| fill_properties(fill, false));
+        let borders = list("borders", "border", &border_properties);
+
+        let mut cell_formats = vec![];
+        for xf in root
+            .child("cellXfs")
+            .map(|xfs| xfs.children_named("xf").collect::<Vec<_>>())
+            .unwrap_or_default()
+        {
+            let mut properties = vec![];
+            let number_format = xf.number("numFmtId").unwrap_or(0.0) as u32;
+            let date = match number_formats.get(&number_format) {
+                Some(code) => is_date_format(code),
+                None => is_date_format_id(number_format),
+            };
+            properties.extend(number_format_properties(number_format, &number_formats));
+            let mut extend = |list: &[Properties], attribute: &str| {
+                if let Some(index) = xf.number(attribute) {
+                    if let Some(item) = list.get(index as usize) {
+                        properties.extend(item.iter().cloned());
+                    }
+                }
+            };
+            extend(&fonts, "fontId");
+            extend(&fills, "fillId");
+            extend(&borders, "borderId");
+            if let Some(alignment) = xf.child("alignment") {
+                properties.extend(alignment_properties(alignment));
+            }
+            if xf
+                .child("protection")
+                .is_some_and(|p| p.flag("hidden", false))
+            {
+                properties.push(("hidden", true.into()));
+            }
+            if xf.flag("quotePrefix", false) {
+                properties.push(("quotePrefix", true.into()));
+            }
+            cell_formats.push(CellFormat { properties, date });
+        }
+
+        let mut dxfs = vec![];
+        for dxf in root
+            .child("dxfs")
+            .map(|dxfs| dxfs.children_named("dxf").collect::<Vec<_>>())
+            .unwrap_or_default()
+        {
+            let mut properties = vec![];
+            if let Some(format) = dxf.child("numFmt") {
+                match format.attribute("formatCode") {
+                    Some(code) => properties.push(("numFmt", code.into())),
+                    None => {
+                        let id = format.number("numFmtId").unwrap_or(0.0) as u32;
+                        properties.extend(number_format_properties(id, &number_formats));
+                    }
+                }
+            }
+            if let Some(font) = dxf.child("font") {
+                properties.extend(font_properties(font));
+            }
+            if let Some(fill) = dxf.child("fill") {
+                properties.extend(fill_properties(fill, true));
+            }
+            if let Some(border) = dxf.child("border") {
+                properties.extend(border_properties(border));
+            }
+            if let Some(alignment) = dxf.child("alignment") {
+                properties.extend(alignment_properties(alignment));
+            }
+            dxfs.push(properties);
+        }
+
+        Self {
+            cell_formats,
+            dxfs,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the cell format shows numbers as dates or times.
+    pub fn is_date(&self, xf: u32) -> bool {
+        self.cell_formats
+            .get(xf as usize)
+            .is_some_and(|format| format.date)
+    }
+
+    /// The id of the `Format` of a cell format, None when it changes nothing.
+    pub fn cell_format(&mut self, xf: u32) -> Option<u32> {
+        if let Some(id) = self.cell_ids.get(&xf) {
+            return *id;
+        }
+        let properties = match self.cell_formats.get(xf as usize) {
+            Some(format) if !format.properties.is_empty() => format.properties.clone(),
+            _ => {
+                self.cell_ids.insert(xf, None);
+                return None;
+            }
+        };
+        let id = self.add(properties);
+        self.cell_ids.insert(xf, Some(id));
+        Some(id)
+    }
+
+    /// The id of the `Format` of a conditional format.
+    pub fn dxf_format(&mut self, dxf: u32) -> Option<u32> {
+        if let Some(id) = self.dxf_ids.get(&dxf) {
+            return Some(*id);
+        }
+        let properties = self.dxfs.get(dxf as usize)?.clone();
+        let id = self.add(properties);
+        self.dxf_ids.insert(dxf, id);
+        Some(id)
+    }
+
+    /// The id of a `Format` without properties, for merged cells which must
+    /// have one.
+    pub fn empty_format(&mut self) -> u32 {
+        match self.empty {
+            Some(id) => id,
+            None => {
+                let id = self.add(vec![]);
+                self.empty = Some(id);
+                id
+            }
+        }
+    }
+
+    /// The `Format` options used so far, with their ids.
+    pub fn into_formats(self) -> Vec<Model> {
+        self.formats
+    }
+
+    fn add(&mut self, properties: Properties) -> u32 {
+        let id = self.formats.len() as u32 + 1;
+        let mut format = Model::object().with("id", id);
+        if let Model::Object(all) = &mut format {
+            all.extend(properties);
+        }
+        self.formats.push(format);
+        id
+    }
+}
+
+fn number_format_properties(id: u32, number_formats: &HashMap<u32, String>) -> Properties {
+    match number_formats.get(&id) {
+        Some(code) => vec![("numFmt", code.as_str().into())],
+        None if id > 0 && id < 164 => vec![("numFmtId", id.into())],
+        None => vec![],
+    }
+}
+
+/// Whether a built-in number format shows a date or a time.
+fn is_date_format_id(id: u32) -> bool {
+    matches!(id, 14..=22 | 27..=36 | 45..=47 | 50..=58)
+}
+
+/// Whether the first section of a number format has date or time parts,
+/// leaving out quoted text, escaped characters and colors.
+pub fn is_date_format(code: &str) -> bool {
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => return false,
+            '"' => {
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                }
+            }
+            '\\' | '_' | '*' => {
+                chars.next();
+            }
+            '[' => {
+                let inside: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                // [h], [mm] and [ss] are elapsed times, the rest are colors,
+                // conditions and locales
+                let inside = inside.to_lowercase();
+                if !inside.is_empty() && inside.chars().all(|c| "hms".contains(c)) {
+                    return true;
+                }
+            }
+            _ if "dmyhsDMYHS".contains(c) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// A color of a style part as a `Color` value, None for the automatic one.
+pub(super) fn color(element: &XmlElement) -> Option<Model> {
+    if let Some(rgb) = element.attribute("rgb") {
+        let rgb = &rgb[rgb.len().saturating_sub(6)..];
+        return Some(format!("#{}", rgb.to_uppercase()).into());
+    }
+    if let Some(theme) = element.number("theme") {
+        let theme = theme as u32;
+        if theme > 9 {
+            return None;
+        }
+        let tint = element.number("tint").unwrap_or(0.0);
+        let tints = SHADE_TINTS[(theme as usize).min(3)];
+        let shade = (0..tints.len())
+            .min_by(|a, b| {
+                let a = (tints[*a] - tint).abs();
+                let b = (tints[*b] - tint).abs();
+                a.total_cmp(&b)
+            })
+            .unwrap_or(0) as u32;
+        let color = Model::object().with("theme", theme);
+        return Some(match shade {
+            0 => color,
+            shade => color.with("shade", shade),
+        });
+    }
+    let indexed = element.number("indexed")? as usize;
+    let rgb = INDEXED_COLORS.get(indexed)?;
+    Some(format!("#{:06X}", rgb).into())
+}
+
+fn is_set(element: &XmlElement) -> bool {
+    element.flag("val", true)
+}
+
+fn font_properties(font: &XmlElement) -> Properties {
+    let mut properties = vec![];
+    let value = |name: &str| font.child(name).and_then(|child| child.attribute("val"));
+
+    if font.child("b").is_some_and(is_set) {
+        properties.push(("bold", true.into()));
+    }
+    if font.child("i").is_some_and(is_set) {
+        properties.push(("italic", true.into()));
+    }
+    if font.child("strike").is_some_and(is_set) {
+        properties.push(("strikeThrough", true.into()));
+    }
+    if let Some(underline) = font.child("u") {
+        let underline = underline.attribute("val").unwrap_or("single");
+        if underline != "none" {
+            properties.push(("underline", underline.into()));
+        }
+    }
+    if let Some(script @ ("superscript" | "subscript")) = value("vertAlign") {
+        properties.push(("fontScript", script.into()));
+    }
+    let name = value("name").unwrap_or("Calibri");
+    if name != "Calibri" {
+        properties.push(("fontName", name.into()));
+        if let Some(scheme @ ("major" | "minor")) = value("scheme") {
+            properties.push(("fontScheme", scheme.into()));
+        }
+    }
+    if let Some(size) = value("sz").and_then(|size| size.parse::<f64>().ok()) {
+        if size != 11.0 {
+            properties.push(("fontSize", size.into()));
+        }
+    }
+    if let Some(family) = value("family").and_then(|family| family.parse::<u32>().ok()) {
+        if family != 2 {
+            properties.push(("fontFamily", family.into()));
+        }
+    }
+    if let Some(charset) = value("charset").and_then(|charset| charset.parse::<u32>().ok()) {
+        properties.push(("charset", charset.into()));
+    }
+    if let Some(element) = font.child("color") {
+        // The text color of the theme is the default one
+        let default = element.attribute("theme") == Some("1") && element.number("tint").is_none();
+        if !default {
+            if let Some(color) = color(element) {
+                properties.push(("fontColor", color));
+            }
+        }
+    }
+    properties
+}
+
+/// The pattern and colors of a fill. A solid fill of a cell format keeps its
+/// color in the foreground, the one of a conditional format in the background.
+fn fill_properties(fill: &XmlElement, dxf: bool) -> Properties {
+    let mut properties = vec![];
+    let Some(pattern) = fill.child("patternFill") else {
+        return properties;
+    };
+    let foreground = pattern.child("fgColor").and_then(color);
+    let background = pattern.child("bgColor").and_then(color);
+    match pattern.attribute("patternType") {
+        Some("none") => {}
+        Some("solid") | None => {
+            let color = match dxf {
+                true => background.or(foreground),
+                false => foreground.or(background),
+            };
+            if let Some(color) = color {
+                properties.push(("backgroundColor", color));
+            }
+        }
+        Some(pattern) => {
+            properties.push(("pattern", pattern.into()));
+            if let Some(foreground) = foreground {
+                properties.push(("foregroundColor", foreground));
+            }
+            if let Some(background) = background {
+                properties.push(("backgroundColor", background));
+            }
+        }
+    }
+    properties
+}
+
+fn border_properties(border: &XmlElement) -> Properties {
+    let mut properties = vec![];
+    let edge = |name: &str| {
+        let edge = border.child(name)?;
+        let style = edge.attribute("style").filter(|style| *style != "none")?;
+        let color = edge
+            .child("color")
+            .and_then(color)
+            .unwrap_or_else(|| "automatic".into());
+        Some(Model::object().with("style", style).with("color", color))
+    };
+
+    for (name, property) in [
+        ("left", "leftBorder"),
+        ("right", "rightBorder"),
+        ("top", "topBorder"),
+        ("bottom", "bottomBorder"),
+    ] {
+        if let Some(edge) = edge(name) {
+            properties.push((property, edge));
+        }
+    }
+
+    let direction = match (
+        border.flag("diagonalUp", false),
+        border.flag("diagonalDown", false),
+    ) {
+        (true, true) => Some("borderUpDown"),
+        (true, false) => Some("borderUp"),
+        (false, true) => Some("borderDown"),
+        (false, false) => None,
+    };
+    if let (Some(direction), Some(diagonal)) = (direction, edge("diagonal")) {
+        properties.push(("diagonalBorder", diagonal.with("dStyle", direction)));
+    }
+    properties
+}
+
+fn alignment_properties(alignment: &XmlElement) -> Properties {
+    let mut properties = vec![];
+    let horizontal = match alignment.attribute("horizontal") {
+        Some("centerContinuous") => Some("centerAcross"),
+        Some(
+            align @ ("general" | "left" | "center" | "right" | "fill" | "justify" | "distributed"),
+        ) => Some(align),
+        _ => None,
+    };
+    if let Some(horizontal) = horizontal {
+        properties.push(("align", horizontal.into()));
+    }
+    if let Some(vertical @ ("top" | "center" | "bottom" | "justify" | "distributed")) =
+        alignment.attribute("vertical")
+    {
+        properties.push(("verticalAlign", vertical.into()));
+    }
+    if alignment.flag("wrapText", false) {
+        properties.push(("textWrap", true.into()));
+    }
+    if alignment.flag("shrinkToFit", false) {
+        properties.push(("shrink", true.into()));
+    }
+    if let Some(indent) = alignment.number("indent").filter(|indent| *indent > 0.0) {
+        properties.push(("indent", indent.into()));
+    }
+    if let Some(rotation) = alignment.number("textRotation").filter(|r| *r != 0.0) {
+        // Excel keeps downward angles as 91 to 180, and 255 for stacked text
+        let rotation = if rotation == 255.0 {
+            270.0
+        } else if rotation > 90.0 {
+            90.0 - rotation
+        } else {
+            rotation
+        };
+        properties.push(("rotation", rotation.into()));
+    }
+    match alignment.attribute("readingOrder") {
+        Some("1") => properties.push(("readingDirection", "leftToRight".into())),
+        Some("2") => properties.push(("readingDirection", "rightToLeft".into())),
+        _ => {}
+    }
+    properties
+}
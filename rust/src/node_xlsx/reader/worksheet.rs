@@ -0,0 +1,536 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::node_xlsx::{
+    cell_range::CellRange, error::NodeXlsxError, formula::shift::ShiftTemplate,
+    formula::value::serial_to_date, xml::XmlElement,
+};
+
+use super::{
+    conditional_format::read_conditional_formats, model::Model, rich_text, Relationship,
+    WorkbookReader,
+};
+
+/// A cell of the sheet data, without a value when it only has a format.
+struct ReadCell {
+    value: Option<(&'static str, Model)>,
+    format: Option<u32>,
+}
+
+/// The formulas shared by several cells, by their index, with the cell that
+/// holds their text.
+type SharedFormulas = HashMap<String, (ShiftTemplate, u32, u16)>;
+
+/// Reads a worksheet part into a sheet object of the writers.
+pub fn read_worksheet(
+    reader: &mut WorkbookReader,
+    name: &str,
+    root: &XmlElement,
+    part: &str,
+) -> Result<Model, NodeXlsxError> {
+    let relationships = reader.relationships(part)?;
+
+    let mut cells: BTreeMap<(u32, u16), ReadCell> = BTreeMap::new();
+    let mut array_formulas = vec![];
+    let mut array_ranges = vec![];
+    let mut shared = SharedFormulas::new();
+    let mut row_configs = vec![];
+
+    let rows = root
+        .child("sheetData")
+        .map(|data| data.children_named("row").collect::<Vec<_>>())
+        .unwrap_or_default();
+    let mut next_row = 0;
+    for row_element in rows {
+        let row = match row_element.number("r") {
+            Some(r) if r >= 1.0 => r as u32 - 1,
+            _ => next_row,
+        };
+        next_row = row + 1;
+        if let Some(config) = row_config(reader, row_element, row) {
+            row_configs.push(config);
+        }
+
+        let mut next_col = 0;
+        for cell in row_element.children_named("c") {
+            let (row, col) = match cell.attribute("r").map(CellRange::parse_cell) {
+                Some(Ok(position)) => position,
+                _ => (row, next_col),
+            };
+            next_col = col + 1;
+
+            let format = match cell.number("s") {
+                Some(s) => reader.styles.cell_format(s as u32),
+                None => None,
+            };
+            if let Some(f) = cell
+                .child("f")
+                .filter(|f| f.attribute("t") == Some("array"))
+            {
+                let range = f.attribute("ref").and_then(|a1| CellRange::parse(a1).ok());
+                let range = range.unwrap_or(CellRange {
+                    start_row: row,
+                    end_row: row,
+                    start_col: col as u32,
+                    end_col: col as u32,
+                });
+                let formula = Model::object()
+                    .with("formula", format!("={}", f.text))
+                    .with_opt("result", formula_result(cell))
+                    .with("dynamic", cell.attribute("cm").is_some());
+                array_formulas.push(
+                    Model::object()
+                        .with("firstRow", range.start_row)
+                        .with("lastRow", range.end_row)
+                        .with("firstColumn", range.start_col)
+                        .with("lastColumn", range.end_col)
+                        .with("formula", formula)
+                        .with_opt("format", format),
+                );
+                array_ranges.push(range);
+                continue;
+            }
+
+            let value = cell_value(reader, cell, row, col, &mut shared);
+            if value.is_some() || format.is_some() {
+                cells.insert((row, col), ReadCell { value, format });
+            }
+        }
+    }
+    // The other cells of an array formula hold its results
+    cells.retain(|(row, col), _| !array_ranges.iter().any(|range| range.contains(*row, *col)));
+
+    add_links(root, &relationships, &mut cells);
+
+    let mut merged_cells = vec![];
+    for merge in root
+        .child("mergeCells")
+        .map(|merges| merges.children_named("mergeCell").collect::<Vec<_>>())
+        .unwrap_or_default()
+    {
+        let Some(range) = merge
+            .attribute("ref")
+            .and_then(|a1| CellRange::parse(a1).ok())
+        else {
+            continue;
+        };
+        if range.is_cell() {
+            continue;
+        }
+        let first = cells.remove(&(range.start_row, range.start_col as u16));
+        cells.retain(|(row, col), _| !range.contains(*row, *col));
+        let (value, format) = match first {
+            Some(ReadCell { value, format }) => (value, format),
+            None => (None, None),
+        };
+        let (cell_type, value) = value.unwrap_or(("string", "".into()));
+        let format = match format {
+            Some(format) => format,
+            None => reader.styles.empty_format(),
+        };
+        merged_cells.push(
+            Model::object()
+                .with("merged", true)
+                .with("firstRow", range.start_row)
+                .with("lastRow", range.end_row)
+                .with("firstCol", range.start_col)
+                .with("lastCol", range.end_col)
+                .with("cellType", cell_type)
+                .with("value", value)
+                .with("format", format),
+        );
+    }
+
+    let mut sheet_cells = vec![];
+    let mut range_formats: Vec<(u32, u16, u16, u32)> = vec![];
+    for ((row, col), cell) in cells {
+        match (cell.value, cell.format) {
+            (Some((cell_type, value)), format) => sheet_cells.push(
+                Model::object()
+                    .with("row", row)
+                    .with("col", col)
+                    .with("cellType", cell_type)
+                    .with("value", value)
+                    .with_opt("format", format),
+            ),
+            // Formatted blank cells next to each other in a row share a range
+            (None, Some(format)) => match range_formats.last_mut() {
+                Some(last) if last.0 == row && last.2 + 1 == col && last.3 == format => {
+                    last.2 = col;
+                }
+                _ => range_formats.push((row, col, col, format)),
+            },
+            (None, None) => {}
+        }
+    }
+    sheet_cells.extend(merged_cells);
+    let range_formats: Vec<Model> = range_formats
+        .into_iter()
+        .map(|(row, first_col, last_col, format)| {
+            Model::object()
+                .with("firstRow", row)
+                .with("lastRow", row)
+                .with("firstCol", first_col)
+                .with("lastCol", last_col)
+                .with("format", format)
+        })
+        .collect();
+
+    let tables = read_tables(reader, root, &relationships)?;
+    let conditional_formats = read_conditional_formats(
+        root,
+        &mut reader.styles,
+        &mut reader.next_conditional_format,
+    );
+
+    Ok(Model::object()
+        .with("name", name)
+        .with("cells", sheet_cells)
+        .with("conditionalFormats", conditional_formats)
+        .with("arrayFormulas", array_formulas)
+        .with("rowConfigs", row_configs)
+        .with("columnConfigs", column_configs(reader, root))
+        .with("tables", tables)
+        .with("columnBlocks", Vec::<Model>::new())
+        .with("rangeFormats", range_formats)
+        .with("fillFormulas", Vec::<Model>::new()))
+}
+
+/// The type and value of a cell, None when it is blank.
+fn cell_value(
+    reader: &WorkbookReader,
+    cell: &XmlElement,
+    row: u32,
+    col: u16,
+    shared: &mut SharedFormulas,
+) -> Option<(&'static str, Model)> {
+    if let Some(f) = cell.child("f") {
+        let formula = match (f.attribute("t"), f.attribute("si")) {
+            (Some("shared"), Some(index)) if f.text.is_empty() => {
+                let (template, first_row, first_col) = shared.get(index)?;
+                let rows = row as i64 - *first_row as i64;
+                let cols = col as i64 - *first_col as i64;
+                template.shifted(rows, cols)
+            }
+            (Some("shared"), Some(index)) => {
+                let formula = format!("={}", f.text);
+                if let Ok(template) = ShiftTemplate::new(&formula) {
+                    shared.insert(index.to_string(), (template, row, col));
+                }
+                formula
+            }
+            _ => format!("={}", f.text),
+        };
+        let formula = Model::object()
+            .with("formula", formula)
+            .with_opt("result", formula_result(cell));
+        return Some(("formula", formula));
+    }
+
+    let value = match cell.attribute("t") {
+        Some("inlineStr") => return Some(("string", rich_text(cell.child("is")?).into())),
+        _ => cell.child("v")?.text.as_str(),
+    };
+    match cell.attribute("t") {
+        Some("s") => {
+            let string = reader.strings.get(value.trim().parse::<usize>().ok()?)?;
+            Some(("string", string.as_str().into()))
+        }
+        Some("str") | Some("d") => Some(("string", value.into())),
+        Some("b") => Some(("boolean", (value == "1" || value == "true").into())),
+        // Errors are written as formulas of their value
+        Some("e") => {
+            let formula = Model::object()
+                .with("formula", format!("={}", value))
+                .with("result", value);
+            Some(("formula", formula))
+        }
+        _ => {
+            let number: f64 = value.trim().parse().ok()?;
+            let date = match cell.number("s") {
+                Some(s) if number >= 1.0 && reader.styles.is_date(s as u32) => {
+                    serial_to_date(reader.serial(number))
+                }
+                _ => None,
+            };
+            match date {
+                Some(date) => Some((
+                    "date",
+                    Model::Date(date.and_utc().timestamp_millis() as f64),
+                )),
+                None => Some(("number", number.into())),
+            }
+        }
+    }
+}
+
+/// The cached result of a formula cell, of the type it is stored with.
+fn formula_result(cell: &XmlElement) -> Option<Model> {
+    let value = &cell.child("v")?.text;
+    match cell.attribute("t") {
+        Some("b") => Some((value == "1").into()),
+        Some("str") | Some("e") => Some(value.as_str().into()),
+        _ => value.trim().parse::<f64>().ok().map(Model::from),
+    }
+}
+
+/// Turns the cells with a hyperlink into link cells, showing their text.
+fn add_links(
+    root: &XmlElement,
+    relationships: &HashMap<String, Relationship>,
+    cells: &mut BTreeMap<(u32, u16), ReadCell>,
+) {
+    let Some(hyperlinks) = root.child("hyperlinks") else {
+        return;
+    };
+    for hyperlink in hyperlinks.children_named("hyperlink") {
+        let Some(Ok((row, col))) = hyperlink
+            .attribute("ref")
+            .map(|a1| a1.split(':').next().unwrap_or(a1))
+            .map(CellRange::parse_cell)
+        else {
+            continue;
+        };
+        let target = hyperlink
+            .attribute("id")
+            .and_then(|id| relationships.get(id))
+            .filter(|relationship| relationship.external)
+            .map(|relationship| relationship.target.as_str());
+        let url = match (target, hyperlink.attribute("location")) {
+            (Some(target), Some(location)) => format!("{}#{}", target, location),
+            (Some(target), None) => target.to_string(),
+            (None, Some(location)) => format!("internal:{}", location),
+            (None, None) => continue,
+        };
+
+        let cell = cells.entry((row, col)).or_insert(ReadCell {
+            value: None,
+            format: None,
+        });
+        let text = match &cell.value {
+            Some(("string", Model::String(text))) => Some(text.clone()),
+            Some(_) => continue,
+            None => hyperlink.attribute("display").map(str::to_string),
+        };
+        let link = Model::object()
+            .with("url", url.as_str())
+            .with_opt("text", text.filter(|text| *text != url))
+            .with_opt("tip", hyperlink.attribute("tooltip"));
+        cell.value = Some(("link", link));
+    }
+}
+
+/// The height, visibility and format of a row, None when it has the defaults.
+fn row_config(reader: &mut WorkbookReader, row: &XmlElement, index: u32) -> Option<Model> {
+    let height = row.number("ht").filter(|_| row.flag("customHeight", false));
+    let hidden = row.flag("hidden", false);
+    let format = match row.number("s") {
+        Some(s) if row.flag("customFormat", false) => reader.styles.cell_format(s as u32),
+        _ => None,
+    };
+    if height.is_none() && !hidden && format.is_none() {
+        return None;
+    }
+    let size = height.map(|height| Model::object().with("value", height).with("unit", "auto"));
+    Some(
+        Model::object()
+            .with("index", index)
+            .with_opt("size", size)
+            .with_opt("format", format)
+            .with_opt("hidden", hidden.then_some(true)),
+    )
+}
+
+/// The widths, visibility and formats of the columns, one config per column.
+fn column_configs(reader: &mut WorkbookReader, root: &XmlElement) -> Vec<Model> {
+    let mut configs = vec![];
+    let columns = root
+        .child("cols")
+        .map(|cols| cols.children_named("col").collect::<Vec<_>>())
+        .unwrap_or_default();
+    for column in columns {
+        let (Some(min), Some(max)) = (column.number("min"), column.number("max")) else {
+            continue;
+        };
+        // rust_xlsxwriter stores widths of whole pixels, which this gives back
+        let width = column
+            .number("width")
+            .filter(|_| column.flag("customWidth", false))
+            .map(|width| (width * 7.0).round());
+        let hidden = column.flag("hidden", false);
+        let format = match column.number("style") {
+            Some(style) => reader.styles.cell_format(style as u32),
+            None => None,
+        };
+        if width.is_none() && !hidden && format.is_none() {
+            continue;
+        }
+        for index in (min as u32).max(1) - 1..(max as u32).min(16_384) {
+            let size = width.map(|width| Model::object().with("value", width).with("unit", "px"));
+            configs.push(
+                Model::object()
+                    .with("index", index)
+                    .with_opt("size", size)
+                    .with_opt("format", format)
+                    .with_opt("hidden", hidden.then_some(true)),
+            );
+        }
+    }
+    configs
+}
+
+/// The tables of the sheet, in the table sheet value shape.
+fn read_tables(
+    reader: &mut WorkbookReader,
+    root: &XmlElement,
+    relationships: &HashMap<String, Relationship>,
+) -> Result<Vec<Model>, NodeXlsxError> {
+    let mut tables = vec![];
+    let parts = root
+        .child("tableParts")
+        .map(|parts| parts.children_named("tablePart").collect::<Vec<_>>())
+        .unwrap_or_default();
+    for part in parts {
+        let relationship = part
+            .attribute("id")
+            .and_then(|id| relationships.get(id))
+            .filter(|relationship| relationship.kind == "table");
+        let Some(relationship) = relationship else {
+            continue;
+        };
+        let Some(table) = reader.part(&relationship.target)? else {
+            continue;
+        };
+        let Some(range) = table
+            .attribute("ref")
+            .and_then(|a1| CellRange::parse(a1).ok())
+        else {
+            continue;
+        };
+        tables.push(read_table(reader, root, &table, range));
+    }
+    Ok(tables)
+}
+
+fn read_table(
+    reader: &mut WorkbookReader,
+    root: &XmlElement,
+    table: &XmlElement,
+    range: CellRange,
+) -> Model {
+    let header_row = table.number("headerRowCount") != Some(0.0);
+    let total_row = table
+        .number("totalsRowCount")
+        .is_some_and(|count| count > 0.0);
+
+    // The formats of the header and first data cells are read from the sheet
+    let styles = cell_styles(root, range);
+    let mut cell_format = |row: u32, col: u32| match styles.get(&(row, col as u16)) {
+        Some(s) => reader.styles.cell_format(*s),
+        None => None,
+    };
+
+    let mut columns = vec![];
+    for (index, column) in table
+        .child("tableColumns")
+        .map(|columns| columns.children_named("tableColumn").collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+    {
+        let col = range.start_col + index as u32;
+        let formula = column
+            .child("calculatedColumnFormula")
+            .map(|formula| Model::object().with("formula", format!("={}", formula.text)));
+        let header_format = match header_row {
+            true => cell_format(range.start_row, col),
+            false => None,
+        };
+        let format = match formula {
+            Some(_) => cell_format(range.start_row + header_row as u32, col),
+            None => None,
+        };
+        let total_function = match column.attribute("totalsRowFunction") {
+            Some("custom") => column.child("totalsRowFormula").map(|formula| {
+                Model::object().with("type", "custom").with(
+                    "formula",
+                    Model::object().with("formula", format!("={}", formula.text)),
+                )
+            }),
+            Some(function) => {
+                let function = match function {
+                    "countNums" => "countNumbers",
+                    function => function,
+                };
+                Some(Model::object().with("type", function))
+            }
+            None => None,
+        };
+        columns.push(
+            Model::object()
+                .with_opt("header", column.attribute("name"))
+                .with_opt("headerFormat", header_format)
+                .with_opt("format", format)
+                .with_opt("formula", formula)
+                .with_opt("totalFunction", total_function)
+                .with_opt("totalLabel", column.attribute("totalsRowLabel")),
+        );
+    }
+
+    let style_info = table.child("tableStyleInfo");
+    let info_flag = |name: &str| style_info.is_some_and(|info| info.flag(name, false));
+    let style = match style_info.and_then(|info| info.attribute("name")) {
+        Some(name) => name
+            .strip_prefix("TableStyle")
+            .map(str::to_lowercase)
+            .filter(|style| {
+                ["light", "medium", "dark"]
+                    .iter()
+                    .any(|prefix| style.starts_with(prefix))
+            }),
+        None => Some("none".to_string()),
+    };
+
+    let table = Model::object()
+        .with_opt(
+            "name",
+            table.attribute("displayName").or(table.attribute("name")),
+        )
+        .with("autoFilter", table.child("autoFilter").is_some())
+        .with("bandedColumns", info_flag("showColumnStripes"))
+        .with("bandedRows", info_flag("showRowStripes"))
+        .with("columns", columns)
+        .with("firstColumnHighlighted", info_flag("showFirstColumn"))
+        .with("lastColumnHighlighted", info_flag("showLastColumn"))
+        .with("headerRow", header_row)
+        .with_opt("style", style)
+        .with("totalRow", total_row);
+    Model::object()
+        .with("firstRow", range.start_row)
+        .with("lastRow", range.end_row)
+        .with("firstColumn", range.start_col)
+        .with("lastColumn", range.end_col)
+        .with("table", table)
+}
+
+/// The style indexes of the cells of a range.
+fn cell_styles(root: &XmlElement, range: CellRange) -> HashMap<(u32, u16), u32> {
+    let mut styles = HashMap::new();
+    let Some(data) = root.child("sheetData") else {
+        return styles;
+    };
+    for cell in data
+        .children_named("row")
+        .flat_map(|row| row.children_named("c"))
+    {
+        let position = cell
+            .attribute("r")
+            .and_then(|a1| CellRange::parse_cell(a1).ok());
+        let (Some((row, col)), Some(s)) = (position, cell.number("s")) else {
+            continue;
+        };
+        if range.contains(row, col) {
+            styles.insert((row, col), s as u32);
+        }
+    }
+    styles
+}
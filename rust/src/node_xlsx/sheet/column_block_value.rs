@@ -0,0 +1,390 @@
+use chrono::NaiveDateTime;
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{
+        buffer::TypedArray, JsArray, JsBoolean, JsDate, JsNull, JsNumber, JsObject, JsString,
+        JsTypedArray, JsUndefined, JsValue, Value,
+    },
+};
+use rust_xlsxwriter::{Format, IntoExcelData, Worksheet, XlsxError};
+
+use crate::node_xlsx::{
+    cell_range::CellRange,
+    format_registry::FormatRegistry,
+    util::{any_to_naive_date_time, any_to_string, create_format},
+};
+
+use super::format_layers::FormatLayers;
+
+pub enum ColumnData {
+    Float(Vec<f64>),
+    Integer(Vec<i32>),
+    String(Vec<String>),
+    Date(Vec<NaiveDateTime>),
+}
+
+impl ColumnData {
+    /// Reads the values of the column `col` starting at `first_row`. Every
+    /// value must be of the type of the first one, and numbers must be finite.
+    pub fn from_js_value(
+        cx: &mut FunctionContext,
+        value: Handle<JsValue>,
+        first_row: u32,
+        col: u16,
+    ) -> NeonResult<Self> {
+        // TypedArrays are copied straight out of their backing buffer
+        if let Ok(array) = value.downcast::<JsTypedArray<f64>, _>(cx) {
+            let values = array.as_slice(cx).to_vec();
+            if let Some(i) = values.iter().position(|number| !number.is_finite()) {
+                return throw_invalid_number(cx, values[i], first_row + i as u32, col);
+            }
+            return Ok(Self::Float(values));
+        }
+
+        if let Ok(array) = value.downcast::<JsTypedArray<i32>, _>(cx) {
+            let values = array.as_slice(cx).to_vec();
+            return Ok(Self::Integer(values));
+        }
+
+        if let Ok(array) = value.downcast::<JsArray, _>(cx) {
+            let values = array.to_vec(cx)?;
+            let kind = match values.first() {
+                Some(first) => ValueKind::of(cx, *first),
+                None => Some(ValueKind::String),
+            };
+            let Some(kind) = kind else {
+                let error = format!(
+                    "Column {} must hold numbers, strings or Dates, found {} at index 0",
+                    col,
+                    kind_name(cx, values[0])
+                );
+                return cx.throw_error(error);
+            };
+
+            for (i, value) in values.iter().enumerate() {
+                if ValueKind::of(cx, *value) != Some(kind) {
+                    let error = format!(
+                        "Column {} holds {}, found {} at index {} (row {})",
+                        col,
+                        kind.plural(),
+                        kind_name(cx, *value),
+                        i,
+                        first_row + i as u32
+                    );
+                    return cx.throw_error(error);
+                }
+            }
+
+            return Ok(match kind {
+                ValueKind::Number => {
+                    let mut numbers = Vec::with_capacity(values.len());
+                    for (i, value) in values.into_iter().enumerate() {
+                        let number = value.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+                        if !number.is_finite() {
+                            return throw_invalid_number(cx, number, first_row + i as u32, col);
+                        }
+                        numbers.push(number);
+                    }
+                    Self::Float(numbers)
+                }
+                ValueKind::Date => {
+                    let mut dates = Vec::with_capacity(values.len());
+                    for value in values {
+                        dates.push(any_to_naive_date_time(cx, value)?);
+                    }
+                    Self::Date(dates)
+                }
+                ValueKind::String => {
+                    let mut strings = Vec::with_capacity(values.len());
+                    for value in values {
+                        strings.push(any_to_string(cx, value)?);
+                    }
+                    Self::String(strings)
+                }
+            });
+        }
+
+        let error = format!(
+            "Column must be a Float64Array, Int32Array, number[], string[] or Date[]: {:?}",
+            value
+        );
+        cx.throw_error(error)
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Float(values) => values.len(),
+            Self::Integer(values) => values.len(),
+            Self::String(values) => values.len(),
+            Self::Date(values) => values.len(),
+        }
+    }
+}
+
+/// The types a plain array column may hold. `null` and `undefined` are
+/// empty strings.
+#[derive(Clone, Copy, PartialEq)]
+enum ValueKind {
+    Number,
+    String,
+    Date,
+}
+
+impl ValueKind {
+    fn of(cx: &mut FunctionContext, value: Handle<JsValue>) -> Option<Self> {
+        if value.is_a::<JsNumber, _>(cx) {
+            Some(Self::Number)
+        } else if value.is_a::<JsString, _>(cx)
+            || value.is_a::<JsNull, _>(cx)
+            || value.is_a::<JsUndefined, _>(cx)
+        {
+            Some(Self::String)
+        } else if value.is_a::<JsDate, _>(cx) {
+            Some(Self::Date)
+        } else {
+            None
+        }
+    }
+
+    fn plural(self) -> &'static str {
+        match self {
+            Self::Number => "numbers",
+            Self::String => "strings",
+            Self::Date => "Dates",
+        }
+    }
+}
+
+/// The type of a value as named in errors.
+fn kind_name(cx: &mut FunctionContext, value: Handle<JsValue>) -> &'static str {
+    match ValueKind::of(cx, value) {
+        Some(ValueKind::Number) => "a number",
+        Some(ValueKind::String) => "a string",
+        Some(ValueKind::Date) => "a Date",
+        None if value.is_a::<JsBoolean, _>(cx) => "a boolean",
+        None if value.is_a::<JsArray, _>(cx) => "an array",
+        None => "an object",
+    }
+}
+
+fn throw_invalid_number<T>(
+    cx: &mut FunctionContext,
+    number: f64,
+    row: u32,
+    col: u16,
+) -> NeonResult<T> {
+    let error = format!("Invalid number at row {}, column {}: {}", row, col, number);
+    cx.throw_error(error)
+}
+
+pub struct ColumnBlockSheetValue {
+    pub first_row: u32,
+    pub first_column: u16,
+    pub columns: Vec<ColumnData>,
+    pub formats: Vec<Option<u32>>,
+    /// Per row formats of the columns whose cells don't share a single format
+    pub cell_formats: Vec<Option<Vec<Option<u32>>>>,
+}
+
+impl ColumnBlockSheetValue {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<Self> {
+        let result = cx.try_catch(|cx| Self::inner_from_js_object(cx, obj, format_map));
+        match result {
+            Ok(block) => Ok(block),
+            Err(error) => {
+                let error = error.to_string(cx)?.value(cx);
+                let error = format!(
+                    "Error parsing column block: {:?} with error:\n  {}",
+                    obj, error
+                );
+                let js_string = cx.string(error);
+                cx.throw(js_string)
+            }
+        }
+    }
+
+    fn inner_from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<Self> {
+        let (first_row, first_column) = CellRange::cell_from_js_object(cx, obj)?;
+
+        let columns: Handle<JsArray> = obj.get(cx, "columns")?;
+        let columns = columns.to_vec(cx)?;
+        if first_column as usize + columns.len() > 16_384 {
+            let error = format!(
+                "Column block with {} columns starting at column {} exceeds the sheet width",
+                columns.len(),
+                first_column
+            );
+            let js_string = cx.string(error);
+            return cx.throw(js_string);
+        }
+
+        let mut inner_columns = Vec::with_capacity(columns.len());
+        for (i, column) in columns.into_iter().enumerate() {
+            let column = ColumnData::from_js_value(cx, column, first_row, first_column + i as u16)?;
+            if first_row as usize + column.len() > 1_048_576 {
+                let error = format!(
+                    "Column with {} values starting at row {} exceeds the sheet height",
+                    column.len(),
+                    first_row
+                );
+                let js_string = cx.string(error);
+                return cx.throw(js_string);
+            }
+            inner_columns.push(column);
+        }
+
+        let mut inner_formats = vec![];
+        let formats: Option<Handle<JsArray>> = obj.get_opt(cx, "formats")?;
+        if let Some(formats) = formats {
+            let formats = formats.to_vec(cx)?;
+            for format in formats {
+                let is_empty = format.is_a::<JsUndefined, _>(cx) || format.is_a::<JsNull, _>(cx);
+                let format = match is_empty {
+                    true => None,
+                    false => Some(create_format(cx, format, format_map)?),
+                };
+                inner_formats.push(format);
+            }
+        }
+
+        Ok(Self {
+            first_row,
+            first_column,
+            columns: inner_columns,
+            formats: inner_formats,
+            cell_formats: vec![],
+        })
+    }
+
+    pub fn compose_formats(&mut self, layers: &FormatLayers, format_map: &mut FormatRegistry) {
+        let mut formats = Vec::with_capacity(self.columns.len());
+        let mut cell_formats = Vec::with_capacity(self.columns.len());
+        for (i, column) in self.columns.iter().enumerate() {
+            let col = self.first_column + i as u16;
+            let own = self.formats.get(i).copied().flatten();
+            if !layers.varies_by_row() {
+                formats.push(layers.resolve(format_map, self.first_row, col, own));
+                cell_formats.push(None);
+                continue;
+            }
+
+            let column_formats: Vec<Option<u32>> = (0..column.len() as u32)
+                .map(|r| layers.resolve(format_map, self.first_row + r, col, own))
+                .collect();
+            let first = column_formats.first().copied().flatten();
+            if column_formats.iter().all(|format| *format == first) {
+                formats.push(first);
+                cell_formats.push(None);
+            } else {
+                formats.push(None);
+                cell_formats.push(Some(column_formats));
+            }
+        }
+        self.formats = formats;
+        self.cell_formats = cell_formats;
+    }
+
+    pub fn write_to_sheet(
+        self,
+        worksheet: &mut Worksheet,
+        format_map: &FormatRegistry,
+    ) -> Result<(), XlsxError> {
+        let mut cell_formats = self.cell_formats.into_iter();
+        for (i, column) in self.columns.into_iter().enumerate() {
+            let col = self.first_column + i as u16;
+            if let Some(Some(formats)) = cell_formats.next() {
+                match column {
+                    ColumnData::Float(values) => {
+                        write_cells(worksheet, self.first_row, col, values, &formats, format_map)?
+                    }
+                    ColumnData::Integer(values) => {
+                        write_cells(worksheet, self.first_row, col, values, &formats, format_map)?
+                    }
+                    ColumnData::String(values) => {
+                        write_cells(worksheet, self.first_row, col, values, &formats, format_map)?
+                    }
+                    ColumnData::Date(values) => write_cells(
+                        worksheet,
+                        self.first_row,
+                        col,
+                        values.iter(),
+                        &formats,
+                        format_map,
+                    )?,
+                }
+                continue;
+            }
+
+            let format = match self.formats.get(i) {
+                Some(Some(format)) => format_map.get(format),
+                _ => None,
+            };
+            match column {
+                ColumnData::Float(values) => {
+                    write_column(worksheet, self.first_row, col, values, format)?
+                }
+                ColumnData::Integer(values) => {
+                    write_column(worksheet, self.first_row, col, values, format)?
+                }
+                ColumnData::String(values) => {
+                    write_column(worksheet, self.first_row, col, values, format)?
+                }
+                ColumnData::Date(values) => {
+                    write_column(worksheet, self.first_row, col, values.iter(), format)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_column<I>(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    data: I,
+    format: Option<&Format>,
+) -> Result<(), XlsxError>
+where
+    I: IntoIterator,
+    I::Item: IntoExcelData,
+{
+    match format {
+        Some(format) => worksheet.write_column_with_format(row, col, data, format)?,
+        None => worksheet.write_column(row, col, data)?,
+    };
+    Ok(())
+}
+
+fn write_cells<I>(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    data: I,
+    formats: &[Option<u32>],
+    format_map: &FormatRegistry,
+) -> Result<(), XlsxError>
+where
+    I: IntoIterator,
+    I::Item: IntoExcelData,
+{
+    for (i, (value, format)) in data.into_iter().zip(formats).enumerate() {
+        let row = row + i as u32;
+        match format.and_then(|format| format_map.get(&format)) {
+            Some(format) => worksheet.write_with_format(row, col, value, format)?,
+            None => worksheet.write(row, col, value)?,
+        };
+    }
+    Ok(())
+}
@@ -0,0 +1,877 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+use neon::{
+    context::{Context, FunctionContext},
+    object::Object,
+    result::JsResult,
+    types::JsObject,
+};
+
+use crate::node_xlsx::{
+    cell_range::{CellRange, COLUMN_COUNT, ROW_COUNT},
+    conditional_format::{icon::icon_type_name, registry::ConditionalFormatRegistry},
+    formula::{
+        evaluator::{evaluate_relative, Cells},
+        parser::{parse, Expr},
+        value::Value,
+    },
+    xml::unescape,
+};
+
+use super::{formula_results::WorkbookCells, NodeXlsxSheet};
+
+/// What the conditional formats of a sheet show in one cell.
+pub struct CellPreview {
+    pub row: u32,
+    pub col: u16,
+    /// The JS ids of the rules that apply their format, highest priority first
+    pub rules: Vec<u32>,
+    /// The fill of the color scale, as `#RRGGBB`
+    pub color: Option<String>,
+    pub data_bar: Option<DataBarPreview>,
+    pub icon: Option<IconPreview>,
+}
+
+/// The part of the cell width a data bar covers, from 0 to 1.
+pub struct DataBarPreview {
+    pub start: f64,
+    pub end: f64,
+    pub color: String,
+    pub show_value: bool,
+}
+
+pub struct IconPreview {
+    /// The icon set the icon is taken from, named like `iconType` in JS
+    pub icon_type: String,
+    pub index: u32,
+    pub show_value: bool,
+}
+
+impl CellPreview {
+    pub fn to_js_object<'a>(&self, cx: &mut FunctionContext<'a>) -> JsResult<'a, JsObject> {
+        let obj = cx.empty_object();
+        let row = cx.number(self.row);
+        obj.set(cx, "row", row)?;
+        let col = cx.number(self.col);
+        obj.set(cx, "col", col)?;
+
+        let rules = cx.empty_array();
+        for (index, id) in self.rules.iter().enumerate() {
+            let id = cx.number(*id);
+            rules.set(cx, index as u32, id)?;
+        }
+        obj.set(cx, "rules", rules)?;
+
+        if let Some(color) = &self.color {
+            let color = cx.string(color);
+            obj.set(cx, "color", color)?;
+        }
+        if let Some(data_bar) = &self.data_bar {
+            let js_data_bar = cx.empty_object();
+            let start = cx.number(data_bar.start);
+            js_data_bar.set(cx, "start", start)?;
+            let end = cx.number(data_bar.end);
+            js_data_bar.set(cx, "end", end)?;
+            let color = cx.string(&data_bar.color);
+            js_data_bar.set(cx, "color", color)?;
+            let show_value = cx.boolean(data_bar.show_value);
+            js_data_bar.set(cx, "showValue", show_value)?;
+            obj.set(cx, "dataBar", js_data_bar)?;
+        }
+        if let Some(icon) = &self.icon {
+            let js_icon = cx.empty_object();
+            let icon_type = cx.string(&icon.icon_type);
+            js_icon.set(cx, "iconType", icon_type)?;
+            let index = cx.number(icon.index);
+            js_icon.set(cx, "index", index)?;
+            let show_value = cx.boolean(icon.show_value);
+            js_icon.set(cx, "showValue", show_value)?;
+            obj.set(cx, "icon", js_icon)?;
+        }
+        Ok(obj)
+    }
+}
+
+/// Evaluates the conditional formats of every sheet against the values its
+/// cells will be written with, formulas computed where the evaluator can.
+/// `today` is the serial date the date rules compare with, they are left out
+/// without it. Cells whose value can't be known match no rule.
+pub fn preview_conditional_formats(
+    sheets: &[NodeXlsxSheet],
+    conditional_formats: &ConditionalFormatRegistry,
+    today: Option<f64>,
+) -> Vec<Vec<CellPreview>> {
+    let cells = WorkbookCells::new(sheets, today);
+    cells.compute();
+    sheets
+        .iter()
+        .enumerate()
+        .map(|(index, sheet)| sheet.preview_conditional_formats(&cells, index, conditional_formats))
+        .collect()
+}
+
+impl NodeXlsxSheet {
+    fn preview_conditional_formats(
+        &self,
+        cells: &WorkbookCells,
+        sheet: usize,
+        conditional_formats: &ConditionalFormatRegistry,
+    ) -> Vec<CellPreview> {
+        let mut previews: BTreeMap<(u32, u16), CellPreview> = BTreeMap::new();
+        // Cells where a `stopIfTrue` rule matched, later rules skip them
+        let mut stopped: HashSet<(u32, u16)> = HashSet::new();
+
+        for format in self.conditional_formats.iter() {
+            for id in format.formats.iter() {
+                let cf = conditional_formats.get(id).unwrap();
+                let sqref = format.sqref(cf);
+                let Some(rule) = Rule::parse(&cf.rule_xml(&sqref)) else {
+                    continue;
+                };
+                let area = Area::new(cells, sheet, &sqref);
+                for (cell, effect) in rule.condition.evaluate(&area) {
+                    if stopped.contains(&cell) {
+                        continue;
+                    }
+                    let preview = previews.entry(cell).or_insert_with(|| CellPreview {
+                        row: cell.0,
+                        col: cell.1,
+                        rules: vec![],
+                        color: None,
+                        data_bar: None,
+                        icon: None,
+                    });
+                    // A cell shows one color scale, data bar and icon: the first
+                    match effect {
+                        Effect::Format => preview.rules.push(*id),
+                        Effect::Color(color) => {
+                            preview.color.get_or_insert(color);
+                        }
+                        Effect::DataBar(data_bar) => {
+                            preview.data_bar.get_or_insert(data_bar);
+                        }
+                        Effect::Icon(icon) => {
+                            preview.icon.get_or_insert(icon);
+                        }
+                    }
+                    if rule.stop_if_true {
+                        stopped.insert(cell);
+                    }
+                }
+            }
+        }
+        previews.into_values().collect()
+    }
+}
+
+/// The cells of the range of a rule, with their values. Whole rows and
+/// columns stop at the last used row and column of the sheet.
+struct Area<'a> {
+    cells: &'a WorkbookCells,
+    sheet: usize,
+    /// The top left cell, the one formulas are written for
+    anchor: (u32, u16),
+    positions: Vec<(u32, u16)>,
+    /// The value of each cell, `None` when it can't be computed
+    values: Vec<Option<Value>>,
+}
+
+impl<'a> Area<'a> {
+    fn new(cells: &'a WorkbookCells, sheet: usize, sqref: &str) -> Self {
+        let (last_row, last_col) = cells.used_range(sheet);
+        let mut anchor = None;
+        let mut seen = HashSet::new();
+        let mut positions = vec![];
+        for range in sqref.split([' ', ',']).filter(|range| !range.is_empty()) {
+            let Ok(range) = CellRange::parse(&range.replace('$', "")) else {
+                continue;
+            };
+            anchor.get_or_insert((range.start_row, range.start_col as u16));
+            let mut end_row = range.end_row;
+            if end_row == ROW_COUNT - 1 {
+                end_row = last_row.max(range.start_row);
+            }
+            let mut end_col = range.end_col;
+            if end_col == COLUMN_COUNT as u32 - 1 {
+                end_col = (last_col as u32).max(range.start_col);
+            }
+            for row in range.start_row..=end_row {
+                for col in range.start_col..=end_col {
+                    if seen.insert((row, col as u16)) {
+                        positions.push((row, col as u16));
+                    }
+                }
+            }
+        }
+        let values = positions
+            .iter()
+            .map(|(row, col)| cells.value(sheet, *row, *col).ok())
+            .collect();
+        Self {
+            cells,
+            sheet,
+            anchor: anchor.unwrap_or((0, 0)),
+            positions,
+            values,
+        }
+    }
+
+    /// Evaluates a formula of the rule in a cell of the area.
+    fn evaluate(&self, expr: &Expr, (row, col): (u32, u16)) -> Option<Value> {
+        evaluate_relative(self.cells, expr, self.sheet, self.anchor, row, col).ok()
+    }
+
+    /// The numbers of the area, lowest first. Text, booleans and errors are
+    /// left out like Excel does.
+    fn numbers(&self) -> Vec<f64> {
+        let mut numbers: Vec<f64> = self.numeric_cells().map(|(_, number)| number).collect();
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        numbers
+    }
+
+    fn numeric_cells(&self) -> impl Iterator<Item = ((u32, u16), f64)> + '_ {
+        self.positions
+            .iter()
+            .zip(self.values.iter())
+            .filter_map(|(position, value)| match value {
+                Some(Value::Number(number)) => Some((*position, *number)),
+                _ => None,
+            })
+    }
+
+    fn known_cells(&self) -> impl Iterator<Item = ((u32, u16), &Value)> + '_ {
+        self.positions
+            .iter()
+            .zip(self.values.iter())
+            .filter_map(|(position, value)| Some((*position, value.as_ref()?)))
+    }
+}
+
+/// What a matching rule does to a cell.
+enum Effect {
+    Format,
+    Color(String),
+    DataBar(DataBarPreview),
+    Icon(IconPreview),
+}
+
+struct Rule {
+    condition: Condition,
+    stop_if_true: bool,
+}
+
+enum Condition {
+    /// `cellIs`, the cell compared with one or two formulas
+    Compare {
+        operator: String,
+        operands: Vec<Expr>,
+    },
+    Text {
+        operator: String,
+        text: String,
+    },
+    /// A formula written for the top left cell, used by the date, blank,
+    /// error and formula rules
+    Formula(Expr),
+    Duplicate {
+        unique: bool,
+    },
+    Average {
+        above: bool,
+        equal: bool,
+        std_dev: f64,
+    },
+    Top {
+        rank: f64,
+        percent: bool,
+        bottom: bool,
+    },
+    ColorScale {
+        points: Vec<Threshold>,
+        colors: Vec<[u8; 3]>,
+    },
+    DataBar {
+        min: Threshold,
+        max: Threshold,
+        color: [u8; 3],
+        negative_color: [u8; 3],
+        right_to_left: bool,
+        show_value: bool,
+    },
+    IconSet {
+        /// The lowest value of every icon but the first, and whether the
+        /// value may be equal to it
+        thresholds: Vec<(Threshold, bool)>,
+        /// The icon of each step, lowest first, `None` for no icon
+        icons: Vec<Option<(String, u32)>>,
+        show_value: bool,
+    },
+}
+
+/// A `cfvo`, a point of a color scale, data bar or icon set.
+struct Threshold {
+    kind: String,
+    value: String,
+}
+
+impl Rule {
+    /// Reads a rule from the XML rust_xlsxwriter writes for it. Rules this
+    /// does not understand give `None` and are not previewed.
+    fn parse(xml: &str) -> Option<Self> {
+        let elements = elements(xml);
+        let rule = elements.iter().find(|element| element.name == "cfRule")?;
+        let formulas = || {
+            elements
+                .iter()
+                .filter(|element| element.name == "formula")
+                .map(|element| parse(&element.text).ok())
+                .collect::<Option<Vec<Expr>>>()
+        };
+
+        let condition = match rule.attribute("type")? {
+            "cellIs" => Condition::Compare {
+                operator: rule.attribute("operator")?.to_string(),
+                operands: formulas()?,
+            },
+            // Read from the text rather than the formula, which rust_xlsxwriter
+            // does not escape
+            operator @ ("containsText" | "notContainsText" | "beginsWith" | "endsWith") => {
+                Condition::Text {
+                    operator: operator.to_string(),
+                    text: rule.attribute("text")?.to_lowercase(),
+                }
+            }
+            "timePeriod" | "containsBlanks" | "notContainsBlanks" | "containsErrors"
+            | "notContainsErrors" | "expression" => {
+                Condition::Formula(formulas()?.into_iter().next()?)
+            }
+            "duplicateValues" => Condition::Duplicate { unique: false },
+            "uniqueValues" => Condition::Duplicate { unique: true },
+            "aboveAverage" => Condition::Average {
+                above: rule.flag("aboveAverage", true),
+                equal: rule.flag("equalAverage", false),
+                std_dev: match rule.attribute("stdDev") {
+                    Some(std_dev) => std_dev.parse().ok()?,
+                    None => 0.0,
+                },
+            },
+            "top10" => Condition::Top {
+                rank: rule.attribute("rank")?.parse().ok()?,
+                percent: rule.flag("percent", false),
+                bottom: rule.flag("bottom", false),
+            },
+            "colorScale" => {
+                let points = thresholds(&elements, "colorScale");
+                let colors = elements
+                    .iter()
+                    .filter(|element| element.name == "color")
+                    .map(Element::color)
+                    .collect::<Option<Vec<_>>>()?;
+                if points.len() < 2 || points.len() != colors.len() {
+                    return None;
+                }
+                Condition::ColorScale {
+                    points: points.into_iter().map(|(point, _)| point).collect(),
+                    colors,
+                }
+            }
+            "dataBar" => {
+                // The Excel 2010 extension, when there is one, has the bounds
+                // Excel uses
+                let mut points = thresholds(&elements, "dataBar").into_iter();
+                let (min, max) = (points.next()?.0, points.next()?.0);
+                let bar = elements.iter().find(|element| element.name == "dataBar")?;
+                let direction = elements
+                    .iter()
+                    .filter(|element| element.name == "dataBar")
+                    .find_map(|element| element.attribute("direction"));
+                let color = |name: &str| {
+                    elements
+                        .iter()
+                        .find(|element| element.name == name)
+                        .and_then(Element::color)
+                };
+                Condition::DataBar {
+                    min,
+                    max,
+                    color: color("color")?,
+                    negative_color: color("negativeFillColor").unwrap_or([0xFF, 0, 0]),
+                    right_to_left: direction == Some("rightToLeft"),
+                    show_value: bar.flag("showValue", true),
+                }
+            }
+            "iconSet" => {
+                let set = elements.iter().rfind(|element| element.name == "iconSet")?;
+                let name = set.attribute("iconSet").unwrap_or("3TrafficLights1");
+                let mut points = thresholds(&elements, "iconSet");
+                if points.len() < 2 {
+                    return None;
+                }
+                points.remove(0);
+
+                let custom: Vec<Option<(String, u32)>> = elements
+                    .iter()
+                    .filter(|element| element.name == "cfIcon")
+                    .map(|element| {
+                        let set = element.attribute("iconSet")?;
+                        let id = element.attribute("iconId")?.parse().ok()?;
+                        Some((icon_type_name(set)?.to_string(), id))
+                    })
+                    .collect();
+                let icons = match custom.is_empty() {
+                    true => {
+                        let icon_type = icon_type_name(name)?;
+                        let mut icons: Vec<Option<(String, u32)>> = (0..=points.len() as u32)
+                            .map(|id| Some((icon_type.to_string(), id)))
+                            .collect();
+                        if set.flag("reverse", false) {
+                            icons.reverse();
+                        }
+                        icons
+                    }
+                    false => custom,
+                };
+                if icons.len() != points.len() + 1 {
+                    return None;
+                }
+                Condition::IconSet {
+                    thresholds: points,
+                    icons,
+                    show_value: set.flag("showValue", true),
+                }
+            }
+            _ => return None,
+        };
+        Some(Self {
+            condition,
+            stop_if_true: rule.flag("stopIfTrue", false),
+        })
+    }
+}
+
+impl Condition {
+    /// The cells of the area the condition matches, in the order of the area.
+    fn evaluate(&self, area: &Area) -> Vec<((u32, u16), Effect)> {
+        let formatted = |cells: Vec<(u32, u16)>| {
+            cells
+                .into_iter()
+                .map(|cell| (cell, Effect::Format))
+                .collect()
+        };
+        match self {
+            Condition::Compare { operator, operands } => formatted(
+                area.known_cells()
+                    .filter_map(|(cell, value)| {
+                        if let Value::Error(_) = value {
+                            return None;
+                        }
+                        let operands = operands
+                            .iter()
+                            .map(|operand| match area.evaluate(operand, cell)? {
+                                Value::Error(_) => None,
+                                operand => Some(operand),
+                            })
+                            .collect::<Option<Vec<Value>>>()?;
+                        compare(value, operator, &operands)?.then_some(cell)
+                    })
+                    .collect(),
+            ),
+            Condition::Text { operator, text } => formatted(
+                area.known_cells()
+                    .filter_map(|(cell, value)| {
+                        let value = value.to_text().ok()?.to_lowercase();
+                        let matches = match operator.as_str() {
+                            "containsText" => value.contains(text.as_str()),
+                            "notContainsText" => !value.contains(text.as_str()),
+                            "beginsWith" => value.starts_with(text.as_str()),
+                            _ => value.ends_with(text.as_str()),
+                        };
+                        matches.then_some(cell)
+                    })
+                    .collect(),
+            ),
+            Condition::Formula(expr) => formatted(
+                area.known_cells()
+                    .filter(|(cell, _)| {
+                        let result = area.evaluate(expr, *cell);
+                        result.is_some_and(|result| result.to_boolean() == Ok(true))
+                    })
+                    .map(|(cell, _)| cell)
+                    .collect(),
+            ),
+            Condition::Duplicate { unique } => {
+                let mut counts: HashMap<String, usize> = HashMap::new();
+                for (_, value) in area.known_cells() {
+                    if let Some(key) = duplicate_key(value) {
+                        *counts.entry(key).or_default() += 1;
+                    }
+                }
+                formatted(
+                    area.known_cells()
+                        .filter_map(|(cell, value)| {
+                            let count = counts[&duplicate_key(value)?];
+                            ((count == 1) == *unique).then_some(cell)
+                        })
+                        .collect(),
+                )
+            }
+            Condition::Average {
+                above,
+                equal,
+                std_dev,
+            } => {
+                let numbers = area.numbers();
+                let count = numbers.len() as f64;
+                if numbers.is_empty() || (*std_dev > 0.0 && numbers.len() < 2) {
+                    return vec![];
+                }
+                let mean = numbers.iter().sum::<f64>() / count;
+                let deviation = match *std_dev > 0.0 {
+                    true => {
+                        let squares: f64 = numbers.iter().map(|n| (n - mean).powi(2)).sum();
+                        (squares / (count - 1.0)).sqrt() * std_dev
+                    }
+                    false => 0.0,
+                };
+                formatted(
+                    area.numeric_cells()
+                        .filter_map(|(cell, number)| {
+                            let matches = match (above, equal) {
+                                (true, false) => number > mean + deviation,
+                                (true, true) => number >= mean + deviation,
+                                (false, false) => number < mean - deviation,
+                                (false, true) => number <= mean - deviation,
+                            };
+                            matches.then_some(cell)
+                        })
+                        .collect(),
+                )
+            }
+            Condition::Top {
+                rank,
+                percent,
+                bottom,
+            } => {
+                let numbers = area.numbers();
+                let count = match percent {
+                    true => ((numbers.len() as f64 * rank / 100.0).floor() as usize).max(1),
+                    false => *rank as usize,
+                }
+                .min(numbers.len());
+                if count == 0 {
+                    return vec![];
+                }
+                // Values tied with the last one in are in too
+                formatted(match bottom {
+                    true => {
+                        let limit = numbers[count - 1];
+                        area.numeric_cells()
+                            .filter(|(_, number)| *number <= limit)
+                            .map(|(cell, _)| cell)
+                            .collect()
+                    }
+                    false => {
+                        let limit = numbers[numbers.len() - count];
+                        area.numeric_cells()
+                            .filter(|(_, number)| *number >= limit)
+                            .map(|(cell, _)| cell)
+                            .collect()
+                    }
+                })
+            }
+            Condition::ColorScale { points, colors } => {
+                let numbers = area.numbers();
+                let Some(points) = points
+                    .iter()
+                    .map(|point| point.resolve(area, &numbers))
+                    .collect::<Option<Vec<f64>>>()
+                else {
+                    return vec![];
+                };
+                area.numeric_cells()
+                    .map(|(cell, number)| {
+                        let color = scale_color(&points, colors, number);
+                        (cell, Effect::Color(hex(color)))
+                    })
+                    .collect()
+            }
+            Condition::DataBar {
+                min,
+                max,
+                color,
+                negative_color,
+                right_to_left,
+                show_value,
+            } => {
+                let numbers = area.numbers();
+                let (Some(min), Some(max)) =
+                    (min.resolve(area, &numbers), max.resolve(area, &numbers))
+                else {
+                    return vec![];
+                };
+                let width = max - min;
+                let position = |number: f64| match width > 0.0 {
+                    true => ((number - min) / width).clamp(0.0, 1.0),
+                    false => 1.0,
+                };
+                // Bars start at zero when the range crosses it, at the lowest
+                // value otherwise
+                let axis = position(0.0);
+                area.numeric_cells()
+                    .map(|(cell, number)| {
+                        let end = position(number);
+                        let (mut start, mut end) = (axis.min(end), axis.max(end));
+                        if *right_to_left {
+                            (start, end) = (1.0 - end, 1.0 - start);
+                        }
+                        let color = match number < 0.0 {
+                            true => negative_color,
+                            false => color,
+                        };
+                        let data_bar = DataBarPreview {
+                            start,
+                            end,
+                            color: hex(*color),
+                            show_value: *show_value,
+                        };
+                        (cell, Effect::DataBar(data_bar))
+                    })
+                    .collect()
+            }
+            Condition::IconSet {
+                thresholds,
+                icons,
+                show_value,
+            } => {
+                let numbers = area.numbers();
+                let Some(thresholds) = thresholds
+                    .iter()
+                    .map(|(point, equal)| Some((point.resolve(area, &numbers)?, *equal)))
+                    .collect::<Option<Vec<(f64, bool)>>>()
+                else {
+                    return vec![];
+                };
+                area.numeric_cells()
+                    .filter_map(|(cell, number)| {
+                        let step = thresholds
+                            .iter()
+                            .take_while(|(limit, equal)| match equal {
+                                true => number >= *limit,
+                                false => number > *limit,
+                            })
+                            .count();
+                        let (icon_type, index) = icons[step].clone()?;
+                        let icon = IconPreview {
+                            icon_type,
+                            index,
+                            show_value: *show_value,
+                        };
+                        Some((cell, Effect::Icon(icon)))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Threshold {
+    /// The number the point stands for, given the numbers of the area.
+    fn resolve(&self, area: &Area, numbers: &[f64]) -> Option<f64> {
+        let lowest = *numbers.first()?;
+        let highest = *numbers.last()?;
+        match self.kind.as_str() {
+            "min" => Some(lowest),
+            "max" => Some(highest),
+            // Excel 2010 data bars are drawn from zero when all values are
+            // on the same side of it
+            "autoMin" => Some(lowest.min(0.0)),
+            "autoMax" => Some(highest.max(0.0)),
+            "percent" => Some(lowest + (highest - lowest) * self.number(area)? / 100.0),
+            "percentile" => Some(percentile(numbers, self.number(area)? / 100.0)),
+            _ => self.number(area),
+        }
+    }
+
+    /// The value of the point, which is either a number or a formula.
+    fn number(&self, area: &Area) -> Option<f64> {
+        if let Ok(number) = self.value.parse() {
+            return Some(number);
+        }
+        let expr = parse(&self.value).ok()?;
+        area.evaluate(&expr, area.anchor)?.to_number().ok()
+    }
+}
+
+/// Compares a cell with the operands of a `cellIs` rule.
+fn compare(value: &Value, operator: &str, operands: &[Value]) -> Option<bool> {
+    let first = value.compare(operands.first()?);
+    let matches = match operator {
+        "equal" => first == Ordering::Equal,
+        "notEqual" => first != Ordering::Equal,
+        "greaterThan" => first == Ordering::Greater,
+        "greaterThanOrEqual" => first != Ordering::Less,
+        "lessThan" => first == Ordering::Less,
+        "lessThanOrEqual" => first != Ordering::Greater,
+        "between" | "notBetween" => {
+            let second = operands.get(1)?;
+            let (low, high) = match operands[0].compare(second) {
+                Ordering::Greater => (second, &operands[0]),
+                _ => (&operands[0], second),
+            };
+            let between =
+                value.compare(low) != Ordering::Less && value.compare(high) != Ordering::Greater;
+            between == (operator == "between")
+        }
+        _ => return None,
+    };
+    Some(matches)
+}
+
+/// The value duplicates are found by, text without case. Empty cells are
+/// neither duplicate nor unique.
+fn duplicate_key(value: &Value) -> Option<String> {
+    match value {
+        Value::Empty => None,
+        Value::Number(number) => Some(format!("n{}", number)),
+        Value::Text(text) => Some(format!("t{}", text.to_lowercase())),
+        Value::Boolean(boolean) => Some(format!("b{}", boolean)),
+        Value::Error(error) => Some(format!("e{}", error)),
+    }
+}
+
+/// Like `PERCENTILE.INC`, `numbers` sorted lowest first.
+fn percentile(numbers: &[f64], fraction: f64) -> f64 {
+    let rank = fraction.clamp(0.0, 1.0) * (numbers.len() - 1) as f64;
+    let below = rank.floor() as usize;
+    match numbers.get(below + 1) {
+        Some(above) => numbers[below] + (above - numbers[below]) * rank.fract(),
+        None => numbers[below],
+    }
+}
+
+/// The color of a number between the points of a color scale.
+fn scale_color(points: &[f64], colors: &[[u8; 3]], number: f64) -> [u8; 3] {
+    if number <= points[0] {
+        return colors[0];
+    }
+    for index in 1..points.len() {
+        if number <= points[index] {
+            let (low, high) = (points[index - 1], points[index]);
+            let ratio = match high > low {
+                true => (number - low) / (high - low),
+                false => 1.0,
+            };
+            let (from, to) = (colors[index - 1], colors[index]);
+            let mix = |channel: usize| {
+                let (from, to) = (from[channel] as f64, to[channel] as f64);
+                (from + (to - from) * ratio).round() as u8
+            };
+            return [mix(0), mix(1), mix(2)];
+        }
+    }
+    colors[colors.len() - 1]
+}
+
+fn hex(color: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", color[0], color[1], color[2])
+}
+
+/// The `cfvo` points that follow the last `container` element, so the ones
+/// of the Excel 2010 extension replace the others. Each comes with whether a
+/// value equal to it reaches it.
+fn thresholds(elements: &[Element], container: &str) -> Vec<(Threshold, bool)> {
+    let start = elements
+        .iter()
+        .rposition(|element| element.name == container)
+        .unwrap_or(0);
+    let mut points = vec![];
+    for (index, element) in elements.iter().enumerate().skip(start) {
+        if element.name != "cfvo" {
+            continue;
+        }
+        // The extension puts the value in an `xm:f` child
+        let value = match (element.attribute("val"), elements.get(index + 1)) {
+            (Some(value), _) => value.to_string(),
+            (None, Some(next)) if next.name == "f" => next.text.clone(),
+            (None, _) => String::new(),
+        };
+        let threshold = Threshold {
+            kind: element.attribute("type").unwrap_or_default().to_string(),
+            value,
+        };
+        points.push((threshold, element.flag("gte", true)));
+    }
+    points
+}
+
+/// A start tag of the rule XML without its namespace prefix, and the text
+/// that follows it.
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn flag(&self, name: &str, default: bool) -> bool {
+        match self.attribute(name) {
+            Some(value) => value == "1" || value == "true",
+            None => default,
+        }
+    }
+
+    /// The color of an `rgb="FFRRGGBB"` attribute.
+    fn color(&self) -> Option<[u8; 3]> {
+        let rgb = self.attribute("rgb")?;
+        let rgb = u32::from_str_radix(rgb.get(rgb.len().checked_sub(6)?..)?, 16).ok()?;
+        Some([(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8])
+    }
+}
+
+/// Splits the XML of a rule into its elements, which is all the structure
+/// the rules rust_xlsxwriter writes need.
+fn elements(xml: &str) -> Vec<Element> {
+    let mut elements: Vec<Element> = vec![];
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        if let Some(last) = elements.last_mut() {
+            last.text.push_str(&unescape(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>').map(|end| start + end) else {
+            break;
+        };
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('/') {
+            continue;
+        }
+
+        let tag = tag.trim_end_matches('/');
+        let (name, mut attributes_text) = tag.split_once(' ').unwrap_or((tag, ""));
+        let name = name.rsplit(':').next().unwrap_or(name).to_string();
+        let mut attributes = vec![];
+        while let Some((key, value)) = attributes_text.split_once("=\"") {
+            let Some((value, after)) = value.split_once('"') else {
+                break;
+            };
+            attributes.push((key.trim().to_string(), unescape(value)));
+            attributes_text = after;
+        }
+        elements.push(Element {
+            name,
+            attributes,
+            text: String::new(),
+        });
+    }
+    elements
+}
@@ -1,161 +1,109 @@
-use std::collections::HashMap;
-
 use neon::{
     context::{Context, FunctionContext},
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsNumber, JsObject, JsString},
+    types::{JsArray, JsBoolean, JsNumber, JsObject, JsString, JsValue},
 };
-use rust_xlsxwriter::{Format, Worksheet};
+use rust_xlsxwriter::{cell_range, Worksheet};
 
 use crate::node_xlsx::conditional_format::{
-    average::Average,
-    blank::Blank,
-    c_type::NodeXlsxConditionalFormatType,
-    cell::Cell,
-    data_bar::DataBar,
-    date::Date,
-    duplicate::Duplicate,
-    error::Error,
-    formula::FormulaFormat,
-    icon::Icon,
-    scale::{ThreeColorScale, TwoColorScale},
-    text::Text,
-    top::Top,
+    c_type::NodeXlsxConditionalFormatType, registry::ConditionalFormatRegistry,
 };
 
+use crate::node_xlsx::cell_range::CellRange;
+use crate::node_xlsx::format_registry::FormatRegistry;
+
+use super::table_value::NodeXlsxTableValue;
+
+/// A column of a table on the same sheet, resolved to its cells once the
+/// tables of the sheet are known.
+pub struct TableColumnTarget {
+    pub table: String,
+    pub column: String,
+    pub include_total_row: bool,
+}
+
 pub struct ConditionalFormatSheetValue {
     pub first_row: u32,
     pub last_row: u32,
     pub first_column: u16,
     pub last_column: u16,
-    pub format: u32,
+    /// The rules, in the order they are evaluated
+    pub formats: Vec<u32>,
+    /// Where the rules go in the order of the sheet, lowest first
+    pub priority: Option<u32>,
+    pub table_column: Option<TableColumnTarget>,
 }
 
 impl ConditionalFormatSheetValue {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &mut HashMap<u32, Format>,
-        conditional_format_map: &mut HashMap<u32, NodeXlsxConditionalFormatType>,
+        format_map: &mut FormatRegistry,
+        conditional_format_map: &mut ConditionalFormatRegistry,
     ) -> NeonResult<Self> {
-        let first_row: Handle<JsNumber> = obj.get(cx, "firstRow")?;
-        let first_row = first_row.value(cx);
-        if first_row < 0.0 || first_row >= 1_048_577.0 {
-            let error = format!("Row with illegal number {}", first_row.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let first_row = first_row as u32;
-
-        let last_row: Handle<JsNumber> = obj.get(cx, "lastRow")?;
-        let last_row = last_row.value(cx);
-        if last_row < 0.0 || last_row >= 1_048_577.0 {
-            let error = format!("Row with illegal number {}", last_row.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let last_row = last_row as u32;
-
-        let first_column: Handle<JsNumber> = obj.get(cx, "firstColumn")?;
-        let first_column = first_column.value(cx);
-        if first_column < 0.0 || first_column >= 16_384.0 {
-            let error = format!("Column with illegal number {}", first_column.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let first_column = first_column as u16;
-
-        let last_column: Handle<JsNumber> = obj.get(cx, "lastColumn")?;
-        let last_column = last_column.value(cx);
-        if last_column < 0.0 || last_column >= 16_384.0 {
-            let error = format!("Column with illegal number {}", last_column.to_string());
-            let js_string = cx.string(error);
-            return cx.throw(js_string);
-        }
-        let last_column = last_column as u16;
-
-        let format: Handle<JsObject> = obj.get(cx, "format")?;
-        let f_type: Handle<JsString> = format.get(cx, "type")?;
-
-        let f_type = f_type.value(cx);
-        let format = match f_type.as_str() {
-            "twoColorScale" => {
-                let id = TwoColorScale::create_and_set_to_map(cx, format, conditional_format_map)?;
-                id
-            }
-            "threeColorScale" => {
-                let id =
-                    ThreeColorScale::create_and_set_to_map(cx, format, conditional_format_map)?;
-                id
-            }
-            "average" => {
-                let id =
-                    Average::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
-                id
+        let table: Option<Handle<JsString>> = obj.get_opt(cx, "table")?;
+        let table_column = match table {
+            Some(table) => {
+                let table = table.value(cx);
+                let column: Handle<JsString> = obj.get(cx, "column")?;
+                let column = column.value(cx);
+                let include_total_row: Option<Handle<JsBoolean>> =
+                    obj.get_opt(cx, "includeTotalRow")?;
+                let include_total_row = match include_total_row {
+                    Some(include_total_row) => include_total_row.value(cx),
+                    None => false,
+                };
+                Some(TableColumnTarget {
+                    table,
+                    column,
+                    include_total_row,
+                })
             }
-            "blank" => {
-                let id =
-                    Blank::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
-                id
-            }
-            "cell" => {
-                let id =
-                    Cell::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
-                id
-            }
-            "dataBar" => {
-                let id = DataBar::create_and_set_to_map(cx, format, conditional_format_map)?;
-                id
-            }
-            "date" => {
-                let id =
-                    Date::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
+            None => None,
+        };
 
-                id
-            }
-            "duplicate" => {
-                let id = Duplicate::create_and_set_to_map(
-                    cx,
-                    format,
-                    conditional_format_map,
-                    format_map,
-                )?;
-                id
-            }
-            "error" => {
-                let id =
-                    Error::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
-                id
-            }
-            "formula" => {
-                let id = FormulaFormat::create_and_set_to_map(
-                    cx,
-                    format,
-                    conditional_format_map,
-                    format_map,
-                )?;
-                id
+        // The cells of a table column are set by `resolve_table_column`
+        let (first_row, last_row, first_column, last_column) = match table_column {
+            Some(_) => (0, 0, 0, 0),
+            None => {
+                let range = CellRange::from_js_object(cx, obj, ("firstColumn", "lastColumn"))?;
+                let (first_column, last_column) = (range.start_col as u16, range.end_col as u16);
+                (range.start_row, range.end_row, first_column, last_column)
             }
-            "iconSet" => {
-                let id = Icon::create_and_set_to_map(cx, format, conditional_format_map)?;
-                id
-            }
-            "text" => {
-                let id =
-                    Text::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
-                id
+        };
+
+        let rules: Option<Handle<JsArray>> = obj.get_opt(cx, "rules")?;
+        let formats = match rules {
+            Some(rules) => {
+                let rules: Vec<Handle<JsValue>> = rules.to_vec(cx)?;
+                if rules.is_empty() {
+                    return cx.throw_error("A conditional format group needs at least one rule");
+                }
+                let mut formats = Vec::with_capacity(rules.len());
+                for rule in rules {
+                    formats.push(conditional_format_map.resolve(cx, rule, format_map)?);
+                }
+                formats
             }
-            "top" => {
-                let id =
-                    Top::create_and_set_to_map(cx, format, conditional_format_map, format_map)?;
-                id
+            None => {
+                let format: Handle<JsValue> = obj.get(cx, "format")?;
+                vec![conditional_format_map.resolve(cx, format, format_map)?]
             }
-            _ => {
-                let err = format!("Invalid ConditionalFormatType: {}", f_type);
-                return cx.throw_error(err);
+        };
+
+        let priority: Option<Handle<JsNumber>> = obj.get_opt(cx, "priority")?;
+        let priority = match priority {
+            Some(priority) => {
+                let priority = priority.value(cx);
+                if priority < 1.0 || priority.fract() != 0.0 || priority > u32::MAX as f64 {
+                    let err = format!("Invalid conditional format priority: {}", priority);
+                    return cx.throw_error(err);
+                }
+                Some(priority as u32)
             }
+            None => None,
         };
 
         return Ok(Self {
@@ -163,23 +111,155 @@ impl ConditionalFormatSheetValue {
             last_row,
             first_column,
             last_column,
-            format,
+            formats,
+            priority,
+            table_column,
         });
     }
 
+    /// Sets the range of a format that targets a table column to the data
+    /// cells of that column, and the total row when it is included.
+    pub fn resolve_table_column(&mut self, tables: &[NodeXlsxTableValue]) -> Result<(), String> {
+        let Some(target) = &self.table_column else {
+            return Ok(());
+        };
+        let table = tables
+            .iter()
+            .find(|table| match &table.name {
+                Some(name) => name.to_lowercase() == target.table.to_lowercase(),
+                None => false,
+            })
+            .ok_or_else(|| format!("Table '{}' not found", target.table))?;
+        let column = table
+            .column_by_header(&target.column)
+            .ok_or_else(|| format!("Table '{}' has no column '{}'", target.table, target.column))?;
+
+        let (first_row, mut last_row) = table.data_rows();
+        if target.include_total_row && table.total_row {
+            last_row = table.last_row;
+        }
+        if first_row > last_row {
+            return Err(format!("Table '{}' has no data rows", target.table));
+        }
+
+        self.first_row = first_row;
+        self.last_row = last_row;
+        self.first_column = column;
+        self.last_column = column;
+        Ok(())
+    }
+
     pub fn set_conditional_format(
         &self,
         worksheet: &mut Worksheet,
-        format_map: &HashMap<u32, NodeXlsxConditionalFormatType>,
+        conditional_formats: &ConditionalFormatRegistry,
     ) -> Result<(), rust_xlsxwriter::XlsxError> {
-        let cf = format_map.get(&self.format).unwrap();
-        cf.set_conditional_format(
-            worksheet,
-            self.first_row,
-            self.last_row,
-            self.first_column,
-            self.last_column,
-        )?;
+        for format in self.formats.iter() {
+            let cf = conditional_formats.get(format).unwrap();
+            cf.set_conditional_format(
+                worksheet,
+                self.first_row,
+                self.last_row,
+                self.first_column,
+                self.last_column,
+            )?;
+        }
         Ok(())
     }
+
+    /// The cells a rule is written for, which is also where rust_xlsxwriter
+    /// files it.
+    pub fn sqref(&self, cf: &NodeXlsxConditionalFormatType) -> String {
+        match cf.multi_range() {
+            multi_range if !multi_range.is_empty() => multi_range,
+            _ => cell_range(
+                self.first_row,
+                self.first_column,
+                self.last_row,
+                self.last_column,
+            ),
+        }
+    }
+}
+
+/// Puts the formats with a priority first, lowest first, followed by the
+/// others in the order they were added. Two formats can't share a priority.
+pub fn order_conditional_formats(
+    formats: &mut [ConditionalFormatSheetValue],
+) -> Result<(), String> {
+    formats.sort_by_key(|format| format.priority.unwrap_or(u32::MAX));
+    let mut priorities = formats.iter().filter_map(|format| format.priority);
+    let mut last = priorities.next();
+    for priority in priorities {
+        if last == Some(priority) {
+            return Err(format!(
+                "Two conditional formats have the priority {}",
+                priority
+            ));
+        }
+        last = Some(priority);
+    }
+    Ok(())
+}
+
+/// The priority of each rule, indexed by the one rust_xlsxwriter writes for
+/// it. It numbers the rules by their range, in the text order of the range,
+/// instead of the order they are evaluated in. Empty when both agree.
+pub fn written_priorities(
+    formats: &[ConditionalFormatSheetValue],
+    conditional_formats: &ConditionalFormatRegistry,
+) -> Vec<u32> {
+    let mut rules: Vec<(String, u32)> = vec![];
+    for format in formats.iter() {
+        for id in format.formats.iter() {
+            let sqref = format.sqref(conditional_formats.get(id).unwrap());
+            rules.push((sqref, rules.len() as u32 + 1));
+        }
+    }
+    rules.sort_by(|a, b| a.0.cmp(&b.0));
+    let priorities: Vec<u32> = rules.into_iter().map(|(_, priority)| priority).collect();
+    match priorities
+        .iter()
+        .enumerate()
+        .all(|(index, p)| *p == index as u32 + 1)
+    {
+        true => vec![],
+        false => priorities,
+    }
+}
+
+/// Replaces the priorities of the conditional format rules of a worksheet
+/// part, see [`written_priorities`].
+pub fn fix_priorities(xml: &str, priorities: &[u32]) -> String {
+    const ATTRIBUTE: &str = " priority=\"";
+    let mut fixed = String::with_capacity(xml.len());
+    let mut rest = xml;
+    // Both `<cfRule ` and the Excel 2010 `<x14:cfRule ` carry the priority
+    while let Some(start) = rest.find("cfRule ") {
+        let tag_end = rest[start..]
+            .find('>')
+            .map_or(rest.len(), |end| start + end);
+        let tag = &rest[..tag_end];
+        let written = tag[start..].find(ATTRIBUTE).and_then(|offset| {
+            let value_start = start + offset + ATTRIBUTE.len();
+            let value_end = value_start + tag[value_start..].find('"')?;
+            let priority = tag[value_start..value_end].parse::<usize>().ok()?;
+            Some((
+                value_start,
+                value_end,
+                priorities.get(priority.checked_sub(1)?)?,
+            ))
+        });
+        match written {
+            Some((value_start, value_end, priority)) => {
+                fixed.push_str(&tag[..value_start]);
+                fixed.push_str(&priority.to_string());
+                fixed.push_str(&tag[value_end..]);
+            }
+            None => fixed.push_str(tag),
+        }
+        rest = &rest[tag_end..];
+    }
+    fixed.push_str(rest);
+    fixed
 }
@@ -1,15 +1,15 @@
-use std::collections::HashMap;
-
 use neon::{
     context::{Context, FunctionContext},
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsArray, JsBoolean, JsNumber, JsObject, JsString, JsValue},
+    types::{JsArray, JsBoolean, JsNumber, JsObject, JsString, JsValue, Value},
 };
 
 use crate::node_xlsx::util::create_format;
 
+use crate::node_xlsx::format_registry::FormatRegistry;
+
 pub enum SizeType {
     AUTO,
     PX,
@@ -64,7 +64,7 @@ impl RowColumnConfig {
         cx: &mut FunctionContext,
         arr: Handle<JsArray>,
         config_type: Type,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Vec<Self>> {
         let mut row_column_configs = vec![];
 
@@ -83,12 +83,14 @@ impl RowColumnConfig {
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
         config_type: Type,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
-        let result = Self::inner_from_js_object(cx, obj, config_type, format_map);
+        let result =
+            cx.try_catch(|cx| Self::inner_from_js_object(cx, obj, config_type, format_map));
         match result {
             Ok(config) => Ok(config),
             Err(error) => {
+                let error = error.to_string(cx)?.value(cx);
                 let error = format!(
                     "Error parsing RowColumnConfig: {:?} with error:\n  {}",
                     obj, error
@@ -103,7 +105,7 @@ impl RowColumnConfig {
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
         config_type: Type,
-        format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
         let index: Handle<JsNumber> = obj.get(cx, "index")?;
         let index = index.value(cx) as u32;
@@ -114,7 +116,7 @@ impl RowColumnConfig {
             None => None,
         };
 
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
         let format = match format {
             Some(format) => Some(create_format(cx, format, format_map)?),
             None => None,
@@ -138,7 +140,7 @@ impl RowColumnConfig {
     pub fn write_to_sheet(
         self,
         sheet: &mut rust_xlsxwriter::Worksheet,
-        format_map: &HashMap<u32, rust_xlsxwriter::Format>,
+        format_map: &FormatRegistry,
     ) -> Result<(), rust_xlsxwriter::XlsxError> {
         match self.config_type {
             Type::ROW => {
@@ -0,0 +1,521 @@
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{
+        buffer::TypedArray, JsArray, JsBoolean, JsBuffer, JsNumber, JsObject, JsString, JsValue,
+    },
+};
+use rust_xlsxwriter::{utility::row_col_to_cell, Worksheet, XlsxError};
+
+use crate::node_xlsx::{
+    cell::SimpleCell,
+    cell_range::{CellRange, COLUMN_COUNT, ROW_COUNT},
+    csv::{parse_records, single_char, Encoding},
+    format_registry::FormatRegistry,
+    formula::{parser::parse, value::parse_number, NodeXlsxFormula},
+    table::NodeXlsxTable,
+    types::NodeXlsxTypes,
+    util::create_format,
+};
+
+/// How the fields of a column are written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CsvColumnType {
+    /// Numbers, booleans, ISO dates and formulas are recognized, the rest
+    /// is written as strings
+    Auto,
+    String,
+    Number,
+    Boolean,
+    Date,
+    Formula,
+}
+
+impl CsvColumnType {
+    fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "auto" => Ok(Self::Auto),
+            "string" => Ok(Self::String),
+            "number" => Ok(Self::Number),
+            "boolean" => Ok(Self::Boolean),
+            "date" => Ok(Self::Date),
+            "formula" => Ok(Self::Formula),
+            _ => Err(format!("Unknown CSV column type \"{}\"", name)),
+        }
+    }
+}
+
+/// An option given for each column, in the order of the columns or by
+/// their header.
+enum ByColumn<T> {
+    Index(Vec<Option<T>>),
+    Header(Vec<(String, T)>),
+}
+
+impl<T: Copy> ByColumn<T> {
+    /// Reads an array of values, or an object of values by header.
+    fn from_js_value(
+        cx: &mut FunctionContext,
+        value: Option<Handle<JsValue>>,
+        mut read: impl FnMut(&mut FunctionContext, Handle<JsValue>) -> NeonResult<Option<T>>,
+    ) -> NeonResult<Self> {
+        let Some(value) = value else {
+            return Ok(Self::Index(vec![]));
+        };
+        if let Ok(array) = value.downcast::<JsArray, _>(cx) {
+            let values = array.to_vec(cx)?;
+            let mut options = Vec::with_capacity(values.len());
+            for value in values {
+                options.push(read(cx, value)?);
+            }
+            return Ok(Self::Index(options));
+        }
+        let obj = value.downcast_or_throw::<JsObject, _>(cx)?;
+        let keys = obj.get_own_property_names(cx)?.to_vec(cx)?;
+        let mut options = vec![];
+        for key in keys {
+            let key = key.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+            let value: Handle<JsValue> = obj.get(cx, key.as_str())?;
+            if let Some(option) = read(cx, value)? {
+                options.push((key, option));
+            }
+        }
+        Ok(Self::Header(options))
+    }
+
+    /// The option of a column, headers compared without case.
+    fn get(&self, index: usize, header: Option<&str>) -> Option<T> {
+        match self {
+            Self::Index(options) => options.get(index).copied().flatten(),
+            Self::Header(options) => {
+                let header = header?;
+                options
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(header))
+                    .map(|(_, option)| *option)
+            }
+        }
+    }
+}
+
+/// Where the CSV text comes from.
+enum CsvSource {
+    Buffer(Vec<u8>),
+    Path(String),
+}
+
+/// Delimited text written into the sheet from a cell on, one record per
+/// row. The text is read and parsed when the sheet is read, and its cells
+/// are written as plain cells of the sheet.
+pub struct CsvImportSheetValue {
+    pub first_row: u32,
+    pub first_column: u16,
+    /// How many cells of the sheet were written before the import
+    pub cell_index: Option<usize>,
+    source: CsvSource,
+    delimiter: char,
+    quote: Option<char>,
+    encoding: Encoding,
+    header: bool,
+    detect_types: bool,
+    types: ByColumn<CsvColumnType>,
+    formats: ByColumn<u32>,
+    header_format: Option<u32>,
+    date_format: Option<u32>,
+    date_time_format: Option<u32>,
+    table: Option<NodeXlsxTable>,
+    imported: CsvImport,
+}
+
+impl CsvImportSheetValue {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<Self> {
+        let (first_row, first_column) = CellRange::cell_from_js_object(cx, obj)?;
+        let cell_index: Option<Handle<JsNumber>> = obj.get_opt(cx, "cellIndex")?;
+        let cell_index = cell_index.map(|index| index.value(cx) as usize);
+
+        let source: Handle<JsValue> = obj.get(cx, "source")?;
+        let source = match source.downcast::<JsBuffer, _>(cx) {
+            Ok(buffer) => CsvSource::Buffer(buffer.as_slice(cx).to_vec()),
+            Err(_) => {
+                let path = source.downcast_or_throw::<JsString, _>(cx)?;
+                CsvSource::Path(path.value(cx))
+            }
+        };
+
+        let delimiter: Option<Handle<JsString>> = obj.get_opt(cx, "delimiter")?;
+        let delimiter = match delimiter.map(|delimiter| delimiter.value(cx)) {
+            None => ',',
+            Some(delimiter) => match single_char(&delimiter) {
+                Some(delimiter) => delimiter,
+                None => return cx.throw_error("The CSV delimiter must be one character"),
+            },
+        };
+        let quote: Option<Handle<JsString>> = obj.get_opt(cx, "quote")?;
+        let quote = match quote.map(|quote| quote.value(cx)) {
+            None => Some('"'),
+            Some(quote) if quote.is_empty() => None,
+            Some(quote) => match single_char(&quote) {
+                Some(quote) if quote != delimiter => Some(quote),
+                _ => return cx.throw_error("The CSV quote must be one character"),
+            },
+        };
+
+        let encoding: Option<Handle<JsString>> = obj.get_opt(cx, "encoding")?;
+        let encoding = match encoding {
+            Some(encoding) => {
+                let encoding = encoding.value(cx);
+                Encoding::from_name(&encoding).or_else(|error| cx.throw_error(error))?
+            }
+            None => Encoding::Utf8,
+        };
+
+        let header: Option<Handle<JsBoolean>> = obj.get_opt(cx, "header")?;
+        let header = header.map(|header| header.value(cx)).unwrap_or(true);
+        let detect_types: Option<Handle<JsBoolean>> = obj.get_opt(cx, "detectTypes")?;
+        let detect_types = detect_types.map(|detect| detect.value(cx)).unwrap_or(true);
+
+        let types: Option<Handle<JsValue>> = obj.get_opt(cx, "types")?;
+        let types = ByColumn::from_js_value(cx, types, |cx, value| {
+            let Ok(name) = value.downcast::<JsString, _>(cx) else {
+                return Ok(None);
+            };
+            let name = name.value(cx);
+            let column_type =
+                CsvColumnType::from_name(&name).or_else(|error| cx.throw_error(error))?;
+            Ok(Some(column_type))
+        })?;
+
+        let formats: Option<Handle<JsValue>> = obj.get_opt(cx, "formats")?;
+        let formats = ByColumn::from_js_value(cx, formats, |cx, value| {
+            match value.downcast::<JsObject, _>(cx) {
+                Ok(_) => Ok(Some(create_format(cx, value, format_map)?)),
+                Err(_) => Ok(None),
+            }
+        })?;
+
+        let mut format = |key: &str| -> NeonResult<Option<u32>> {
+            let format: Option<Handle<JsValue>> = obj.get_opt(cx, key)?;
+            match format {
+                Some(format) => Ok(Some(create_format(cx, format, format_map)?)),
+                None => Ok(None),
+            }
+        };
+        let header_format = format("headerFormat")?;
+        let date_format = format("dateFormat")?;
+        let date_time_format = format("dateTimeFormat")?;
+
+        let table: Option<Handle<JsObject>> = obj.get_opt(cx, "table")?;
+        let table = match table {
+            Some(table) => Some(NodeXlsxTable::from_js_object(cx, table, format_map)?),
+            None => None,
+        };
+
+        let mut csv_import = Self {
+            first_row,
+            first_column,
+            cell_index,
+            source,
+            delimiter,
+            quote,
+            encoding,
+            header,
+            detect_types,
+            types,
+            formats,
+            header_format,
+            date_format,
+            date_time_format,
+            table,
+            imported: CsvImport::default(),
+        };
+        csv_import.imported = match csv_import.import() {
+            Ok(imported) => imported,
+            Err(error) => return cx.throw_error(error),
+        };
+        Ok(csv_import)
+    }
+
+    pub fn table(&self) -> Option<&NodeXlsxTable> {
+        self.table.as_ref()
+    }
+
+    /// The last row and column of the imported range.
+    pub fn last_cell(&self) -> (u32, u16) {
+        (self.imported.last_row, self.imported.last_column)
+    }
+
+    /// Hands over the imported cells, to be written with the other cells of
+    /// the sheet.
+    pub fn take_cells(&mut self) -> Vec<SimpleCell> {
+        std::mem::take(&mut self.imported.cells)
+    }
+
+    /// Reads and parses the text into the cells it fills.
+    fn import(&self) -> Result<CsvImport, String> {
+        let bytes = match &self.source {
+            CsvSource::Buffer(bytes) => bytes.clone(),
+            CsvSource::Path(path) => std::fs::read(path)
+                .map_err(|error| format!("Cannot read CSV file {}: {}", path, error))?,
+        };
+        let text = self
+            .encoding
+            .decode(&bytes)
+            .map_err(|error| error.to_string())?;
+        let mut records = parse_records(&text, self.delimiter, self.quote).into_iter();
+        let headers = match self.header {
+            true => records.next().unwrap_or_default(),
+            false => vec![],
+        };
+        let records: Vec<Vec<String>> = records.collect();
+
+        let width = records
+            .iter()
+            .map(Vec::len)
+            .chain([headers.len()])
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let has_header_row = self.header || self.table.as_ref().is_some_and(|t| t.header_row());
+        let data_start = self.first_row as usize + has_header_row as usize;
+        let total_row = self.table.as_ref().is_some_and(|t| t.total_row());
+        let last_row = data_start + records.len().max(1) - 1 + total_row as usize;
+        if self.first_column as usize + width > COLUMN_COUNT as usize {
+            return Err(format!(
+                "CSV with {} columns starting at column {} exceeds the sheet width",
+                width, self.first_column
+            ));
+        }
+        if last_row >= ROW_COUNT as usize {
+            return Err(format!(
+                "CSV with {} rows starting at row {} exceeds the sheet height",
+                records.len(),
+                self.first_row
+            ));
+        }
+
+        // A table names its columns after its own headers, the headers of the
+        // text or `Column1` and on, and writes them in the header row
+        let table_headers: Vec<String> = match &self.table {
+            Some(table) => (0..width)
+                .map(|index| {
+                    let column = table.data_columns().get(index);
+                    match (column.and_then(|c| c.header.clone()), headers.get(index)) {
+                        (Some(name), _) => name,
+                        (None, Some(header)) if !header.is_empty() => header.clone(),
+                        _ => format!("Column{}", index + 1),
+                    }
+                })
+                .collect(),
+            None => vec![],
+        };
+        let shown_headers = match self.table.as_ref().is_some_and(|t| t.header_row()) {
+            true => &table_headers,
+            false => &headers,
+        };
+
+        let mut cells = vec![];
+        for (index, header) in shown_headers.iter().enumerate() {
+            cells.push(SimpleCell {
+                row: self.first_row,
+                col: self.first_column + index as u16,
+                cell_type: NodeXlsxTypes::String(header.clone()),
+                format: self.header_format,
+            });
+        }
+
+        let table_formats: Vec<Option<u32>> = match &self.table {
+            Some(table) => table.data_columns().iter().map(|c| c.format).collect(),
+            None => vec![],
+        };
+        let columns: Vec<(CsvColumnType, Option<u32>)> = (0..width)
+            .map(|index| {
+                let header = headers.get(index).map(String::as_str);
+                let default = match self.detect_types {
+                    true => CsvColumnType::Auto,
+                    false => CsvColumnType::String,
+                };
+                let column_type = self.types.get(index, header).unwrap_or(default);
+                let format = self
+                    .formats
+                    .get(index, header)
+                    .or(table_formats.get(index).copied().flatten());
+                (column_type, format)
+            })
+            .collect();
+
+        for (index, record) in records.into_iter().enumerate() {
+            let row = (data_start + index) as u32;
+            for (index, field) in record.into_iter().enumerate() {
+                if field.is_empty() {
+                    continue;
+                }
+                let col = self.first_column + index as u16;
+                let (column_type, format) = columns[index];
+                let cell_type = from_field(field, column_type).map_err(|error| {
+                    let cell = row_col_to_cell(row, col);
+                    format!("CSV field at {} {}", cell, error)
+                })?;
+                let format = match (&cell_type, format) {
+                    (_, Some(format)) => Some(format),
+                    (NodeXlsxTypes::Date(date), None) if date.num_seconds_from_midnight() == 0 => {
+                        self.date_format
+                    }
+                    (NodeXlsxTypes::Date(_), None) => self.date_time_format,
+                    (_, None) => None,
+                };
+                cells.push(SimpleCell {
+                    row,
+                    col,
+                    cell_type,
+                    format,
+                });
+            }
+        }
+
+        Ok(CsvImport {
+            cells,
+            last_row: last_row as u32,
+            last_column: self.first_column + width as u16 - 1,
+            table_headers,
+        })
+    }
+
+    /// Adds the table over the imported range. The cells are written with
+    /// the other cells of the sheet.
+    pub fn write_to_sheet(
+        self,
+        worksheet: &mut Worksheet,
+        format_map: &FormatRegistry,
+    ) -> Result<(), XlsxError> {
+        let import = self.imported;
+        if let Some(mut table) = self.table {
+            if table.data_columns().is_empty() {
+                table.set_headers(&import.table_headers);
+            }
+            let show_header_row = table.header_row();
+            worksheet.add_table(
+                self.first_row,
+                self.first_column,
+                import.last_row,
+                import.last_column,
+                &table.into(),
+            )?;
+            // The table writes its header row again, without the header format
+            let header_format = self.header_format.and_then(|id| format_map.get(&id));
+            if let (true, Some(format)) = (show_header_row, header_format) {
+                for (index, name) in import.table_headers.iter().enumerate() {
+                    let col = self.first_column + index as u16;
+                    worksheet.write_string_with_format(self.first_row, col, name, format)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The cells an import fills, and the range of its table.
+#[derive(Default)]
+struct CsvImport {
+    cells: Vec<SimpleCell>,
+    last_row: u32,
+    last_column: u16,
+    /// The names of the table columns, when the import has a table
+    table_headers: Vec<String>,
+}
+
+/// The value of a field of a column. Fields that don't match the type of
+/// their column are strings, and fields of formula columns must be valid
+/// formulas.
+fn from_field(field: String, column_type: CsvColumnType) -> Result<NodeXlsxTypes, String> {
+    let value = match column_type {
+        CsvColumnType::Auto => detect_value(&field),
+        CsvColumnType::String => None,
+        CsvColumnType::Number => parse_number(&field).map(NodeXlsxTypes::Number),
+        CsvColumnType::Boolean => parse_boolean(&field).map(NodeXlsxTypes::Boolean),
+        CsvColumnType::Date => parse_iso_date(&field).map(NodeXlsxTypes::Date),
+        CsvColumnType::Formula => {
+            let body = field.strip_prefix('=').unwrap_or(&field);
+            if let Err(error) = parse(body) {
+                return Err(format!("is not a valid formula: {}", error));
+            }
+            Some(formula(body))
+        }
+    };
+    Ok(value.unwrap_or(NodeXlsxTypes::String(field)))
+}
+
+/// Recognizes formulas, booleans, plain numbers and ISO dates. Numbers with
+/// leading zeros, like zip codes and ids, stay strings, and so does text
+/// after a `=` that doesn't parse as a formula.
+fn detect_value(field: &str) -> Option<NodeXlsxTypes> {
+    if let Some(body) = field.strip_prefix('=') {
+        return parse(body).ok().map(|_| formula(body));
+    }
+    if field.eq_ignore_ascii_case("true") {
+        return Some(NodeXlsxTypes::Boolean(true));
+    }
+    if field.eq_ignore_ascii_case("false") {
+        return Some(NodeXlsxTypes::Boolean(false));
+    }
+    if let Some(number) = parse_plain_number(field) {
+        return Some(NodeXlsxTypes::Number(number));
+    }
+    parse_iso_date(field).map(NodeXlsxTypes::Date)
+}
+
+fn formula(body: &str) -> NodeXlsxTypes {
+    NodeXlsxTypes::Formula(NodeXlsxFormula::new(format!("={}", body)))
+}
+
+/// A number written with digits, an optional sign, decimals and exponent.
+fn parse_plain_number(field: &str) -> Option<f64> {
+    let digits = field.strip_prefix(['-', '+']).unwrap_or(field);
+    let valid = digits
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'));
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if !valid || leading_zero || !digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    field
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+fn parse_boolean(field: &str) -> Option<bool> {
+    match field.trim().to_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// A date like `2024-03-15`, or a date and time like `2024-03-15T09:30:00`,
+/// with a space in place of the `T`, optional seconds and fractions, and an
+/// optional `Z`.
+fn parse_iso_date(field: &str) -> Option<NaiveDateTime> {
+    let field = field.trim();
+    if field.len() < 10 || field.as_bytes()[4] != b'-' || field.as_bytes()[7] != b'-' {
+        return None;
+    }
+    if field.len() == 10 {
+        return NaiveDate::parse_from_str(field, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0);
+    }
+    let field = field
+        .strip_suffix('Z')
+        .unwrap_or(field)
+        .replacen(' ', "T", 1);
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+        .into_iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&field, format).ok())
+}
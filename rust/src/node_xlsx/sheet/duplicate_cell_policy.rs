@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsObject, JsString},
+};
+
+use crate::node_xlsx::{cell::NodeXlsxCell, format_registry::FormatRegistry};
+
+/// What to do when the same cell is written more than once.
+pub enum DuplicateCellPolicy {
+    /// Report the cell as a conflict
+    Error,
+    /// Keep the last write, the default
+    LastWins,
+    FirstWins,
+    /// Keep the last value, with its format layered over the earlier ones
+    MergeFormat,
+}
+
+impl DuplicateCellPolicy {
+    pub fn from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
+        let policy: Option<Handle<JsString>> = obj.get_opt(cx, "duplicateCellPolicy")?;
+        let policy = match policy {
+            Some(policy) => policy.value(cx),
+            None => return Ok(Self::LastWins),
+        };
+        match policy.as_str() {
+            "error" => Ok(Self::Error),
+            "lastWins" => Ok(Self::LastWins),
+            "firstWins" => Ok(Self::FirstWins),
+            "mergeFormat" => Ok(Self::MergeFormat),
+            _ => {
+                let error = format!("Invalid duplicateCellPolicy: {}", policy);
+                cx.throw_error(error)
+            }
+        }
+    }
+
+    /// Folds the writes to the same cell into one. With `Error` the cells are
+    /// kept as they are so the overlap check can report them.
+    pub fn apply(
+        &self,
+        cells: Vec<NodeXlsxCell>,
+        format_map: &mut FormatRegistry,
+    ) -> Vec<NodeXlsxCell> {
+        if let Self::Error = self {
+            return cells;
+        }
+
+        let mut written: HashMap<(u32, u16), usize> = HashMap::new();
+        let mut inner_cells: Vec<NodeXlsxCell> = Vec::with_capacity(cells.len());
+        for cell in cells {
+            let NodeXlsxCell::Simple(mut cell) = cell else {
+                inner_cells.push(cell);
+                continue;
+            };
+            let index = match written.get(&(cell.row, cell.col)) {
+                Some(index) => *index,
+                None => {
+                    written.insert((cell.row, cell.col), inner_cells.len());
+                    inner_cells.push(NodeXlsxCell::Simple(cell));
+                    continue;
+                }
+            };
+
+            match self {
+                Self::Error | Self::FirstWins => {}
+                Self::LastWins => inner_cells[index] = NodeXlsxCell::Simple(cell),
+                Self::MergeFormat => {
+                    if let NodeXlsxCell::Simple(earlier) = &inner_cells[index] {
+                        cell.format = match (earlier.format, cell.format) {
+                            (Some(earlier), Some(later)) => {
+                                Some(format_map.compose(&[earlier, later]))
+                            }
+                            (earlier, later) => later.or(earlier),
+                        };
+                    }
+                    inner_cells[index] = NodeXlsxCell::Simple(cell);
+                }
+            }
+        }
+        inner_cells
+    }
+}
@@ -0,0 +1,173 @@
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsObject, JsString, JsValue},
+};
+use rust_xlsxwriter::{Formula, Worksheet, XlsxError};
+
+use crate::node_xlsx::{
+    cell_range::{column_from_js_object, row_from_js_object, CellRange},
+    format_registry::FormatRegistry,
+    formula::{result::FormulaResult, shift::ShiftTemplate, FormulaNames, NodeXlsxFormula},
+    util::create_format,
+};
+
+use super::format_layers::FormatLayers;
+
+/// A formula written for the anchor cell and filled over a range, with its
+/// relative references moved for every cell.
+pub struct FillFormulaSheetValue {
+    pub first_row: u32,
+    pub last_row: u32,
+    pub first_column: u16,
+    pub last_column: u16,
+    pub anchor_row: u32,
+    pub anchor_column: u16,
+    pub formula: NodeXlsxFormula,
+    template: ShiftTemplate,
+    pub format: Option<u32>,
+    /// One format per cell, row by row, when composing gives them different ones
+    cell_formats: Option<Vec<Option<u32>>>,
+    /// One cached result per cell, row by row, once the formulas are evaluated
+    results: Option<Vec<Option<FormulaResult>>>,
+}
+
+impl FillFormulaSheetValue {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<Self> {
+        let range = CellRange::from_js_object(cx, obj, ("firstCol", "lastCol"))?;
+
+        let anchor: Option<Handle<JsValue>> = obj.get_opt(cx, "anchor")?;
+        let anchor_row: Option<Handle<JsValue>> = obj.get_opt(cx, "anchorRow")?;
+        let (anchor_row, anchor_column) = match (anchor, anchor_row) {
+            (Some(anchor), _) => {
+                let anchor = anchor.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+                CellRange::parse_cell(&anchor).or_else(|error| cx.throw_error(error))?
+            }
+            (None, Some(_)) => (
+                row_from_js_object(cx, obj, "anchorRow")?,
+                column_from_js_object(cx, obj, "anchorCol")?,
+            ),
+            (None, None) => (range.start_row, range.start_col as u16),
+        };
+
+        let formula: Handle<JsObject> = obj.get(cx, "formula")?;
+        let formula = NodeXlsxFormula::from_js_object(cx, formula)?;
+        let template = match ShiftTemplate::new(&formula.formula) {
+            Ok(template) => template,
+            Err(error) => {
+                let error = format!("Invalid fill formula {}: {}", formula.formula, error);
+                return cx.throw_error(error);
+            }
+        };
+
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
+        let format = match format {
+            Some(format) => Some(create_format(cx, format, format_map)?),
+            None => None,
+        };
+
+        Ok(Self {
+            first_row: range.start_row,
+            last_row: range.end_row,
+            first_column: range.start_col as u16,
+            last_column: range.end_col as u16,
+            anchor_row,
+            anchor_column,
+            formula,
+            template,
+            format,
+            cell_formats: None,
+            results: None,
+        })
+    }
+
+    /// Checks the formula and writes its functions the way Excel stores them,
+    /// see [`NodeXlsxFormula::prepare`].
+    pub fn prepare(&mut self, names: &FormulaNames) -> Result<Vec<String>, String> {
+        let unknown = self.formula.prepare(names)?;
+        self.template = ShiftTemplate::new(&self.formula.formula)?;
+        Ok(unknown)
+    }
+
+    pub fn compose_formats(&mut self, layers: &FormatLayers, format_map: &mut FormatRegistry) {
+        let rows = (self.first_row, self.last_row);
+        let cols = (self.first_column, self.last_column);
+        let formats: Vec<Option<u32>> = (rows.0..=rows.1)
+            .flat_map(|row| (cols.0..=cols.1).map(move |col| (row, col)))
+            .map(|(row, col)| layers.resolve(format_map, row, col, self.format))
+            .collect();
+
+        let first = formats.first().copied().flatten();
+        if formats.iter().all(|format| *format == first) {
+            self.format = first;
+        } else {
+            self.cell_formats = Some(formats);
+        }
+    }
+
+    /// The formula of one cell of the range.
+    pub fn formula_at(&self, row: u32, col: u16) -> NodeXlsxFormula {
+        let rows = row as i64 - self.anchor_row as i64;
+        let cols = col as i64 - self.anchor_column as i64;
+        self.formula.with_formula(self.template.shifted(rows, cols))
+    }
+
+    /// Stores the computed result of every cell of the range.
+    pub fn set_results(&mut self, result: impl Fn(u32, u16) -> Option<FormulaResult>) {
+        let results = (self.first_row..=self.last_row)
+            .flat_map(|row| (self.first_column..=self.last_column).map(move |col| (row, col)))
+            .map(|(row, col)| result(row, col))
+            .collect();
+        self.results = Some(results);
+    }
+
+    /// The row, column and computed result of every cell of the range.
+    pub fn results(&self) -> impl Iterator<Item = (u32, u16, Option<&FormulaResult>)> {
+        let cols = self.first_column..=self.last_column;
+        let cells = (self.first_row..=self.last_row)
+            .flat_map(move |row| cols.clone().map(move |col| (row, col)));
+        let mut results = self.results.iter().flatten();
+        cells.map(move |(row, col)| (row, col, results.next().and_then(|result| result.as_ref())))
+    }
+
+    pub fn write_to_sheet(
+        self,
+        worksheet: &mut Worksheet,
+        format_map: &FormatRegistry,
+    ) -> Result<(), XlsxError> {
+        let mut index = 0;
+        for row in self.first_row..=self.last_row {
+            for col in self.first_column..=self.last_column {
+                let format = match &self.cell_formats {
+                    Some(formats) => formats[index],
+                    None => self.format,
+                };
+                let mut formula = self.formula_at(row, col);
+                if let Some(results) = &self.results {
+                    formula.result = results[index].clone();
+                }
+                index += 1;
+
+                let formula: Formula = formula.into();
+                let format = format.map(|format| format_map.get(&format).unwrap());
+                match (self.formula.dynamic, format) {
+                    (true, Some(format)) => {
+                        worksheet.write_dynamic_formula_with_format(row, col, formula, format)?
+                    }
+                    (true, None) => worksheet.write_dynamic_formula(row, col, formula)?,
+                    (false, Some(format)) => {
+                        worksheet.write_formula_with_format(row, col, formula, format)?
+                    }
+                    (false, None) => worksheet.write_formula(row, col, formula)?,
+                };
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use crate::node_xlsx::format_registry::FormatRegistry;
+
+use super::{
+    config::RowColumnConfig, range_format_value::RangeFormatSheetValue,
+    table_value::NodeXlsxTableValue,
+};
+
+struct TableColumnFormats {
+    first_row: u32,
+    last_row: u32,
+    first_column: u16,
+    formats: Vec<Option<u32>>,
+}
+
+/// Formats a cell inherits from the sheet.
+///
+/// From lowest to highest precedence: the column format, the row format,
+/// the table column format, the range formats in the order they were added
+/// and finally the cell's own format. Column, row and table column formats
+/// only take part when the sheet composes formats.
+pub struct FormatLayers<'a> {
+    rows: HashMap<u32, u32>,
+    columns: HashMap<u32, u32>,
+    tables: Vec<TableColumnFormats>,
+    ranges: &'a [RangeFormatSheetValue],
+}
+
+impl<'a> FormatLayers<'a> {
+    pub fn new(
+        compose: bool,
+        row_config: &[RowColumnConfig],
+        column_config: &[RowColumnConfig],
+        tables: &[NodeXlsxTableValue],
+        ranges: &'a [RangeFormatSheetValue],
+    ) -> Self {
+        if !compose {
+            return Self {
+                rows: HashMap::new(),
+                columns: HashMap::new(),
+                tables: vec![],
+                ranges,
+            };
+        }
+
+        let rows = config_formats(row_config);
+        let columns = config_formats(column_config);
+        let tables = tables
+            .iter()
+            .filter(|table| table.column_formats.iter().any(Option::is_some))
+            .map(|table| TableColumnFormats {
+                first_row: table.first_row + table.header_row as u32,
+                last_row: table.last_row - table.total_row as u32,
+                first_column: table.first_column,
+                formats: table.column_formats.clone(),
+            })
+            .collect();
+
+        Self {
+            rows,
+            columns,
+            tables,
+            ranges,
+        }
+    }
+
+    /// Whether the layers of a column can differ from one row to the next.
+    pub fn varies_by_row(&self) -> bool {
+        !self.rows.is_empty() || !self.tables.is_empty() || !self.ranges.is_empty()
+    }
+
+    /// The effective format of the cell at `row`, `col` whose own format is `own`.
+    pub fn resolve(
+        &self,
+        format_map: &mut FormatRegistry,
+        row: u32,
+        col: u16,
+        own: Option<u32>,
+    ) -> Option<u32> {
+        self.resolve_area(format_map, (row, row), (col, col), own)
+    }
+
+    /// Same as `resolve` for an area such as a merged cell. The area takes the
+    /// layers of its top left cell, and range borders on every edge it touches.
+    pub fn resolve_area(
+        &self,
+        format_map: &mut FormatRegistry,
+        rows: (u32, u32),
+        cols: (u16, u16),
+        own: Option<u32>,
+    ) -> Option<u32> {
+        let (row, col) = (rows.0, cols.0);
+        let mut layers = Vec::with_capacity(4);
+        layers.extend(self.columns.get(&(col as u32)));
+        layers.extend(self.rows.get(&row));
+        layers.extend(self.table_column_format(row, col));
+        for range in self.ranges.iter().filter(|range| range.contains(row, col)) {
+            layers.extend(range.resolve(format_map, rows, cols));
+        }
+        layers.extend(own);
+
+        match layers.is_empty() {
+            true => None,
+            false => Some(format_map.compose(&layers)),
+        }
+    }
+
+    fn table_column_format(&self, row: u32, col: u16) -> Option<u32> {
+        self.tables.iter().find_map(|table| {
+            if row < table.first_row || row > table.last_row || col < table.first_column {
+                return None;
+            }
+            let index = (col - table.first_column) as usize;
+            table.formats.get(index).copied().flatten()
+        })
+    }
+}
+
+fn config_formats(configs: &[RowColumnConfig]) -> HashMap<u32, u32> {
+    configs
+        .iter()
+        .filter_map(|config| config.format.map(|format| (config.index, format)))
+        .collect()
+}
@@ -0,0 +1,148 @@
+use rust_xlsxwriter::utility::{cell_range, row_col_to_cell};
+
+use crate::node_xlsx::{
+    cell::NodeXlsxCell,
+    formula::{FormulaNames, NodeXlsxFormula},
+    types::NodeXlsxTypes,
+};
+
+use super::NodeXlsxSheet;
+
+/// Most invalid formulas spelled out in a single error.
+const MAX_REPORTED: usize = 20;
+
+#[derive(Default)]
+pub struct FormulaErrors {
+    messages: Vec<String>,
+    total: usize,
+    /// The formulas calling unknown functions, which are still written
+    pub warnings: Vec<String>,
+}
+
+impl FormulaErrors {
+    fn report(&mut self, location: String, formula: &str, error: String) {
+        if self.total < MAX_REPORTED {
+            self.messages.push(describe(&location, formula, &error));
+        }
+        self.total += 1;
+    }
+
+    fn warn(&mut self, location: &str, formula: &str, unknown: Vec<String>) {
+        for function in unknown {
+            let warning = format!("Unknown function {}", function);
+            self.warnings.push(describe(location, formula, &warning));
+        }
+    }
+
+    /// Checks the formula of a defined name like the formula of a cell, see
+    /// [`NodeXlsxFormula::prepare`].
+    pub fn prepare_defined_name(&mut self, name: &str, formula: &mut String, names: &FormulaNames) {
+        let mut prepared = NodeXlsxFormula::new(formula.clone());
+        match prepared.prepare(names) {
+            Ok(unknown) => {
+                self.warn(name, formula, unknown);
+                *formula = prepared.formula;
+            }
+            Err(error) => self.report(name.to_string(), formula, error),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn describe(&self) -> String {
+        let mut error = format!("Invalid formulas:\n  {}", self.messages.join("\n  "));
+        if self.total > self.messages.len() {
+            let more = self.total - self.messages.len();
+            error.push_str(&format!("\n  and {} more", more));
+        }
+        error
+    }
+}
+
+impl NodeXlsxSheet {
+    /// Checks every formula written to a cell of the sheet and adds the
+    /// prefixes Excel needs, see [`NodeXlsxFormula::prepare`].
+    pub fn prepare_formulas(&mut self, names: &FormulaNames, errors: &mut FormulaErrors) {
+        let sheet = quote_sheet_name(&self.name);
+        let mut check = |formula: &mut NodeXlsxFormula, location: String| {
+            let location = format!("{}!{}", sheet, location);
+            let text = formula.formula.clone();
+            match formula.prepare(names) {
+                Ok(unknown) => errors.warn(&location, &text, unknown),
+                Err(error) => errors.report(location, &text, error),
+            }
+        };
+
+        for cell in self.cells.iter_mut() {
+            match cell {
+                NodeXlsxCell::Simple(cell) => {
+                    if let NodeXlsxTypes::Formula(formula) = &mut cell.cell_type {
+                        check(formula, row_col_to_cell(cell.row, cell.col));
+                    }
+                }
+                NodeXlsxCell::Merged(cell) => {
+                    if let NodeXlsxTypes::Formula(formula) = &mut cell.cell_type {
+                        let row = cell.range.start_row;
+                        let col = cell.range.start_col as u16;
+                        check(formula, row_col_to_cell(row, col));
+                    }
+                }
+            }
+        }
+
+        for table in self.tables.iter_mut() {
+            for cell in table.data.iter_mut() {
+                if let NodeXlsxTypes::Formula(formula) = &mut cell.cell_type {
+                    check(formula, row_col_to_cell(cell.row, cell.col));
+                }
+            }
+        }
+
+        for formula in self.array_formulas.iter_mut() {
+            let range = cell_range(
+                formula.first_row,
+                formula.first_column,
+                formula.last_row,
+                formula.last_column,
+            );
+            check(&mut formula.formula, range);
+        }
+
+        for fill_formula in self.fill_formulas.iter_mut() {
+            let range = cell_range(
+                fill_formula.first_row,
+                fill_formula.first_column,
+                fill_formula.last_row,
+                fill_formula.last_column,
+            );
+            let location = format!("{}!{}", sheet, range);
+            let text = fill_formula.formula.formula.clone();
+            match fill_formula.prepare(names) {
+                Ok(unknown) => errors.warn(&location, &text, unknown),
+                Err(error) => errors.report(location, &text, error),
+            }
+        }
+    }
+}
+
+/// A diagnostic naming the cell, the formula and the problem.
+fn describe(location: &str, formula: &str, problem: &str) -> String {
+    let formula = match formula.starts_with('=') || formula.starts_with('{') {
+        true => formula.to_string(),
+        false => format!("={}", formula),
+    };
+    format!("{} {}: {}", location, formula, problem)
+}
+
+/// Quotes a sheet name the way a formula refers to it, `'My sheet'`.
+fn quote_sheet_name(name: &str) -> String {
+    if name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    {
+        return name.to_string();
+    }
+    format!("'{}'", name.replace('\'', "''"))
+}
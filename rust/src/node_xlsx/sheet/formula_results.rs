@@ -0,0 +1,376 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+};
+
+use crate::node_xlsx::{
+    cell::{NodeXlsxCell, SimpleCell},
+    formula::{
+        evaluator::{evaluate, Cells, Stop},
+        parser::{parse, Expr},
+        result::FormulaResult,
+        value::{date_to_serial, Value},
+        NodeXlsxFormula,
+    },
+    types::NodeXlsxTypes,
+};
+
+use super::{column_block_value::ColumnData, NodeXlsxSheet};
+
+/// How many formulas deep a chain of references is followed at once, longer
+/// chains are computed from the far end in later passes.
+const MAX_DEPTH: usize = 400;
+
+enum State {
+    Pending,
+    Running,
+    Done(Result<Value, Stop>),
+}
+
+enum Slot {
+    Value(Value),
+    /// A cell the evaluator can't read, like a link or a table header
+    Opaque,
+    Formula {
+        expr: Option<Expr>,
+        dynamic: bool,
+        state: RefCell<State>,
+    },
+}
+
+#[derive(Default)]
+struct SheetCells {
+    slots: HashMap<(u32, u16), Slot>,
+    last_row: u32,
+    last_col: u16,
+}
+
+impl SheetCells {
+    fn set(&mut self, row: u32, col: u16, slot: Slot) {
+        self.last_row = self.last_row.max(row);
+        self.last_col = self.last_col.max(col);
+        self.slots.insert((row, col), slot);
+    }
+
+    fn set_type(&mut self, row: u32, col: u16, cell_type: &NodeXlsxTypes) {
+        let slot = match cell_type {
+            NodeXlsxTypes::String(text) | NodeXlsxTypes::Unknown(text) => {
+                Slot::Value(Value::Text(text.clone()))
+            }
+            NodeXlsxTypes::Number(number) => Slot::Value(Value::Number(*number)),
+            NodeXlsxTypes::Boolean(boolean) => Slot::Value(Value::Boolean(*boolean)),
+            NodeXlsxTypes::Date(date) => Slot::Value(Value::Number(date_to_serial(*date))),
+            NodeXlsxTypes::Link { .. } => Slot::Opaque,
+            NodeXlsxTypes::Formula(formula) => formula_slot(formula),
+        };
+        self.set(row, col, slot);
+    }
+
+    fn set_area(&mut self, rows: (u32, u32), cols: (u16, u16), slot: fn() -> Slot) {
+        for row in rows.0..=rows.1 {
+            for col in cols.0..=cols.1 {
+                self.set(row, col, slot());
+            }
+        }
+    }
+}
+
+fn formula_slot(formula: &NodeXlsxFormula) -> Slot {
+    // A result given by the user is what Excel shows until it recalculates
+    if let Some(result) = &formula.result {
+        return Slot::Value(result.into());
+    }
+    Slot::Formula {
+        expr: parse(&formula.formula).ok(),
+        dynamic: formula.dynamic,
+        state: RefCell::new(State::Pending),
+    }
+}
+
+/// The cells of every sheet as they will be written, for the evaluator.
+pub(super) struct WorkbookCells {
+    names: HashMap<String, usize>,
+    sheets: Vec<SheetCells>,
+    depth: Cell<usize>,
+    /// Formulas reached past [`MAX_DEPTH`], to start the next pass from
+    deferred: RefCell<Vec<(usize, u32, u16)>>,
+    today: Option<f64>,
+}
+
+impl Cells for WorkbookCells {
+    fn sheet_index(&self, name: &str) -> Option<usize> {
+        self.names.get(&name.to_lowercase()).copied()
+    }
+
+    fn value(&self, sheet: usize, row: u32, col: u16) -> Result<Value, Stop> {
+        let Some(slot) = self.sheets[sheet].slots.get(&(row, col)) else {
+            return Ok(Value::Empty);
+        };
+        let (expr, dynamic, state) = match slot {
+            Slot::Value(value) => return Ok(value.clone()),
+            Slot::Opaque => return Err(Stop::Unsupported),
+            Slot::Formula {
+                expr,
+                dynamic,
+                state,
+            } => (expr, *dynamic, state),
+        };
+
+        match &*state.borrow() {
+            State::Done(result) => return result.clone(),
+            // A formula that depends on itself
+            State::Running => return Err(Stop::Unsupported),
+            State::Pending => {}
+        }
+        let Some(expr) = expr else {
+            *state.borrow_mut() = State::Done(Err(Stop::Unsupported));
+            return Err(Stop::Unsupported);
+        };
+        if self.depth.get() >= MAX_DEPTH {
+            self.deferred.borrow_mut().push((sheet, row, col));
+            return Err(Stop::TooDeep);
+        }
+
+        *state.borrow_mut() = State::Running;
+        self.depth.set(self.depth.get() + 1);
+        let result = evaluate(self, expr, sheet, row, col, dynamic);
+        self.depth.set(self.depth.get() - 1);
+        *state.borrow_mut() = match result {
+            Err(Stop::TooDeep) => State::Pending,
+            ref result => State::Done(result.clone()),
+        };
+        result
+    }
+
+    fn used_range(&self, sheet: usize) -> (u32, u16) {
+        (self.sheets[sheet].last_row, self.sheets[sheet].last_col)
+    }
+
+    fn today(&self) -> Option<f64> {
+        self.today
+    }
+}
+
+impl WorkbookCells {
+    pub(super) fn new(sheets: &[NodeXlsxSheet], today: Option<f64>) -> Self {
+        let names = sheets
+            .iter()
+            .enumerate()
+            .map(|(index, sheet)| (sheet.name.to_lowercase(), index))
+            .collect();
+        let sheets = sheets.iter().map(sheet_cells).collect();
+        Self {
+            names,
+            sheets,
+            depth: Cell::new(0),
+            deferred: RefCell::new(vec![]),
+            today,
+        }
+    }
+
+    /// Computes every formula the evaluator understands.
+    pub(super) fn compute(&self) {
+        let mut formulas: Vec<(usize, u32, u16)> = vec![];
+        for (index, sheet) in self.sheets.iter().enumerate() {
+            let mut keys: Vec<(u32, u16)> = sheet
+                .slots
+                .iter()
+                .filter(|(_, slot)| matches!(slot, Slot::Formula { .. }))
+                .map(|(key, _)| *key)
+                .collect();
+            keys.sort_unstable();
+            formulas.extend(keys.into_iter().map(|(row, col)| (index, row, col)));
+        }
+
+        // Every pass starts with the formulas the last one could not reach, so
+        // long chains are computed a piece at a time from their far end
+        let mut order = formulas.clone();
+        loop {
+            let mut too_deep = false;
+            for (sheet, row, col) in order.iter().copied() {
+                too_deep |= self.value(sheet, row, col) == Err(Stop::TooDeep);
+            }
+            if !too_deep {
+                break;
+            }
+            let mut deferred = self.deferred.take();
+            deferred.reverse();
+            order = deferred
+                .into_iter()
+                .chain(formulas.iter().copied())
+                .collect();
+        }
+    }
+
+    fn result(&self, sheet: usize, row: u32, col: u16) -> Option<FormulaResult> {
+        match self.sheets[sheet].slots.get(&(row, col)) {
+            Some(Slot::Formula { state, .. }) => match &*state.borrow() {
+                State::Done(Ok(value)) => Some(value.to_result()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Lays out the cells in the order [`NodeXlsxSheet::into_worksheet`] writes
+/// them, so that later writes replace earlier ones here too.
+fn sheet_cells(sheet: &NodeXlsxSheet) -> SheetCells {
+    let mut cells = SheetCells::default();
+
+    for formula in sheet.array_formulas.iter() {
+        let rows = (formula.first_row, formula.last_row);
+        let cols = (formula.first_column, formula.last_column);
+        cells.set_area(rows, cols, || Slot::Opaque);
+    }
+
+    for cell in sheet.cells.iter() {
+        match cell {
+            NodeXlsxCell::Simple(cell) => cells.set_type(cell.row, cell.col, &cell.cell_type),
+            NodeXlsxCell::Merged(cell) => {
+                let rows = (cell.range.start_row, cell.range.end_row);
+                let cols = (cell.range.start_col as u16, cell.range.end_col as u16);
+                cells.set_area(rows, cols, || Slot::Value(Value::Empty));
+                cells.set_type(rows.0, cols.0, &cell.cell_type);
+            }
+        }
+    }
+
+    for fill_formula in sheet.fill_formulas.iter() {
+        for row in fill_formula.first_row..=fill_formula.last_row {
+            for col in fill_formula.first_column..=fill_formula.last_column {
+                let formula = fill_formula.formula_at(row, col);
+                cells.set(row, col, formula_slot(&formula));
+            }
+        }
+    }
+
+    for column_block in sheet.column_blocks.iter() {
+        for (index, column) in column_block.columns.iter().enumerate() {
+            let col = column_block.first_column + index as u16;
+            let values: Vec<Value> = match column {
+                ColumnData::Float(values) => values.iter().map(|n| Value::Number(*n)).collect(),
+                ColumnData::Integer(values) => {
+                    values.iter().map(|n| Value::Number(*n as f64)).collect()
+                }
+                ColumnData::String(values) => values.iter().cloned().map(Value::Text).collect(),
+                ColumnData::Date(values) => values
+                    .iter()
+                    .map(|date| Value::Number(date_to_serial(*date)))
+                    .collect(),
+            };
+            for (offset, value) in values.into_iter().enumerate() {
+                let row = column_block.first_row + offset as u32;
+                cells.set(row, col, Slot::Value(value));
+            }
+        }
+    }
+
+    for table in sheet.tables.iter() {
+        for cell in table.data.iter() {
+            cells.set_type(cell.row, cell.col, &cell.cell_type);
+        }
+        let cols = (table.first_column, table.last_column);
+        if table.header_row {
+            cells.set_area((table.first_row, table.first_row), cols, || Slot::Opaque);
+        }
+        if table.total_row {
+            cells.set_area((table.last_row, table.last_row), cols, || Slot::Opaque);
+        }
+    }
+    cells
+}
+
+/// Computes the cached result of every formula the evaluator understands and
+/// has no result yet. The others keep no result, for Excel to compute.
+pub fn evaluate_formulas(sheets: &mut [NodeXlsxSheet]) {
+    let cells = WorkbookCells::new(sheets, None);
+    cells.compute();
+
+    for (index, sheet) in sheets.iter_mut().enumerate() {
+        sheet.set_formula_results(|row, col| cells.result(index, row, col));
+    }
+}
+
+impl NodeXlsxSheet {
+    fn set_formula_results(&mut self, result: impl Fn(u32, u16) -> Option<FormulaResult>) {
+        let set = |formula: &mut NodeXlsxFormula, row: u32, col: u16| {
+            if formula.result.is_none() {
+                formula.result = result(row, col);
+            }
+        };
+        let set_cell = |cell: &mut SimpleCell| {
+            if let NodeXlsxTypes::Formula(formula) = &mut cell.cell_type {
+                set(formula, cell.row, cell.col);
+            }
+        };
+
+        for cell in self.cells.iter_mut() {
+            match cell {
+                NodeXlsxCell::Simple(cell) => set_cell(cell),
+                NodeXlsxCell::Merged(cell) => {
+                    if let NodeXlsxTypes::Formula(formula) = &mut cell.cell_type {
+                        set(formula, cell.range.start_row, cell.range.start_col as u16);
+                    }
+                }
+            }
+        }
+        for table in self.tables.iter_mut() {
+            table.data.iter_mut().for_each(set_cell);
+        }
+        for fill_formula in self.fill_formulas.iter_mut() {
+            fill_formula.set_results(&result);
+        }
+    }
+
+    /// The formula results that rust_xlsxwriter writes with the wrong type,
+    /// see [`FormulaResult::needs_type_fix`]. Formulas are visited in the
+    /// order they are written, so a later one replaces the entry of its cell.
+    pub fn mistyped_results(&self) -> HashMap<(u32, u16), FormulaResult> {
+        let mut results = HashMap::new();
+        let mut add = |result: Option<&FormulaResult>, row: u32, col: u16| match result {
+            Some(result) if result.needs_type_fix() => {
+                results.insert((row, col), result.clone());
+            }
+            _ => {
+                results.remove(&(row, col));
+            }
+        };
+
+        for formula in self.array_formulas.iter() {
+            add(
+                formula.formula.result.as_ref(),
+                formula.first_row,
+                formula.first_column,
+            );
+        }
+        for cell in self.cells.iter() {
+            match cell {
+                NodeXlsxCell::Simple(cell) => {
+                    if let NodeXlsxTypes::Formula(formula) = &cell.cell_type {
+                        add(formula.result.as_ref(), cell.row, cell.col);
+                    }
+                }
+                NodeXlsxCell::Merged(cell) => {
+                    if let NodeXlsxTypes::Formula(formula) = &cell.cell_type {
+                        let col = cell.range.start_col as u16;
+                        add(formula.result.as_ref(), cell.range.start_row, col);
+                    }
+                }
+            }
+        }
+        for fill_formula in self.fill_formulas.iter() {
+            for (row, col, result) in fill_formula.results() {
+                add(result, row, col);
+            }
+        }
+        for table in self.tables.iter() {
+            for cell in table.data.iter() {
+                if let NodeXlsxTypes::Formula(formula) = &cell.cell_type {
+                    add(formula.result.as_ref(), cell.row, cell.col);
+                }
+            }
+        }
+        results
+    }
+}
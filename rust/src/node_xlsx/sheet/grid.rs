@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use rust_xlsxwriter::XlsxError;
+
+use crate::node_xlsx::{
+    cell::NodeXlsxCell,
+    cell_range::CellRange,
+    formula::{result::FormulaResult, NodeXlsxFormula},
+    table::{TableDataColumn, TableLook},
+    types::NodeXlsxTypes,
+};
+
+use super::{
+    column_block_value::ColumnData,
+    config::{RowColumnConfig, SizeType},
+    NodeXlsxSheet,
+};
+
+/// A value of a cell as it is written.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridValue {
+    Text(String),
+    Number(f64),
+    Boolean(bool),
+    Date(NaiveDateTime),
+    Link {
+        href: String,
+        text: String,
+    },
+    /// A formula with its `=`, and its result when it is known
+    Formula {
+        formula: String,
+        result: Option<FormulaResult>,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GridCell {
+    pub value: Option<GridValue>,
+    pub format: Option<u32>,
+}
+
+/// The size and format of a row or a column.
+#[derive(Debug, Clone, Default)]
+pub struct GridLine {
+    /// The height of the row or the width of the column, in pixels
+    pub size: Option<f64>,
+    pub hidden: bool,
+    pub format: Option<u32>,
+}
+
+impl GridLine {
+    /// Row heights are given in points and column widths in characters of
+    /// the default font, unless they are given in pixels.
+    fn from_config(config: &RowColumnConfig, column: bool) -> Self {
+        let size = config.size.as_ref().map(|size| match (&size.unit, column) {
+            (SizeType::PX, _) => size.value,
+            (SizeType::AUTO, false) => (size.value * 4.0 / 3.0).round(),
+            (SizeType::AUTO, true) if size.value < 1.0 => (size.value * 12.0).round(),
+            (SizeType::AUTO, true) => (size.value * 7.0).round() + 5.0,
+        });
+        Self {
+            size,
+            hidden: config.hidden.unwrap_or(false),
+            format: config.format,
+        }
+    }
+}
+
+/// A table over cells of the grid.
+pub struct GridTable {
+    pub first_row: u32,
+    pub first_column: u16,
+    pub last_row: u32,
+    pub last_column: u16,
+    pub header_row: bool,
+    pub total_row: bool,
+    pub look: TableLook,
+}
+
+/// The cells of a sheet laid out the way [`NodeXlsxSheet::into_worksheet`]
+/// writes them, so that later writes replace earlier ones here too.
+#[derive(Default)]
+pub struct SheetGrid {
+    pub cells: BTreeMap<(u32, u16), GridCell>,
+    pub merges: Vec<CellRange>,
+    pub tables: Vec<GridTable>,
+    pub rows: BTreeMap<u32, GridLine>,
+    pub columns: BTreeMap<u16, GridLine>,
+}
+
+impl SheetGrid {
+    /// The last row and column with a cell or a merged range.
+    pub fn last_cell(&self) -> Option<(u32, u16)> {
+        let cells = self.cells.keys().copied();
+        let merges = self
+            .merges
+            .iter()
+            .map(|range| (range.end_row, range.end_col as u16));
+        cells
+            .chain(merges)
+            .reduce(|last, (row, col)| (last.0.max(row), last.1.max(col)))
+    }
+
+    /// The merged range a cell is part of.
+    pub fn merge_at(&self, row: u32, col: u16) -> Option<&CellRange> {
+        self.merges.iter().find(|range| range.contains(row, col))
+    }
+
+    fn set(&mut self, row: u32, col: u16, value: Option<GridValue>, format: Option<u32>) {
+        self.cells.insert((row, col), GridCell { value, format });
+    }
+
+    fn set_type(&mut self, row: u32, col: u16, cell_type: &NodeXlsxTypes, format: Option<u32>) {
+        let value = match cell_type {
+            NodeXlsxTypes::String(text) | NodeXlsxTypes::Unknown(text) => {
+                GridValue::Text(text.clone())
+            }
+            NodeXlsxTypes::Number(number) => GridValue::Number(*number),
+            NodeXlsxTypes::Boolean(boolean) => GridValue::Boolean(*boolean),
+            NodeXlsxTypes::Date(date) => GridValue::Date(*date),
+            NodeXlsxTypes::Link { href, text, .. } => GridValue::Link {
+                href: href.clone(),
+                text: text.clone(),
+            },
+            NodeXlsxTypes::Formula(formula) => formula_value(formula),
+        };
+        self.set(row, col, Some(value), format);
+    }
+
+    /// Writes the header names, total labels and calculated columns of a
+    /// table, which keep the formats of the cells they replace.
+    fn set_table(&mut self, table: GridTable, columns: &[TableDataColumn]) {
+        let GridTable {
+            first_row,
+            first_column,
+            last_row,
+            last_column,
+            header_row,
+            total_row,
+            ..
+        } = table;
+        let data_rows = (first_row + header_row as u32)..=(last_row - total_row as u32);
+        for col in first_column..=last_column {
+            let index = (col - first_column) as usize;
+            let column = columns.get(index);
+            if header_row {
+                let cell = self.cells.entry((first_row, col)).or_default();
+                // Without a header of their own, columns are named after the text already there
+                let name = match (column.and_then(|c| c.header.clone()), &cell.value) {
+                    (Some(name), _) => name,
+                    (None, Some(GridValue::Text(text))) if !text.is_empty() => text.clone(),
+                    _ => format!("Column{}", index + 1),
+                };
+                cell.value = Some(GridValue::Text(name));
+            }
+            let Some(column) = column else {
+                continue;
+            };
+            if let (true, Some(label)) = (total_row, &column.total_label) {
+                let cell = self.cells.entry((last_row, col)).or_default();
+                cell.value = Some(GridValue::Text(label.clone()));
+            }
+            if let Some(formula) = &column.formula {
+                for row in data_rows.clone() {
+                    let value = GridValue::Formula {
+                        formula: formula.clone(),
+                        result: None,
+                    };
+                    self.set(row, col, Some(value), column.format);
+                }
+            }
+        }
+        self.tables.push(table);
+    }
+}
+
+fn formula_value(formula: &NodeXlsxFormula) -> GridValue {
+    GridValue::Formula {
+        formula: formula.formula.clone(),
+        result: formula.result.clone(),
+    }
+}
+
+impl NodeXlsxSheet {
+    /// The cells of the sheet with their values and formats. CSV imports are
+    /// read and parsed for it.
+    pub fn grid(&self) -> Result<SheetGrid, XlsxError> {
+        let mut grid = SheetGrid::default();
+
+        for formula in self.array_formulas.iter() {
+            for row in formula.first_row..=formula.last_row {
+                for col in formula.first_column..=formula.last_column {
+                    grid.set(row, col, None, formula.format);
+                }
+            }
+            let value = formula_value(&formula.formula);
+            grid.set(
+                formula.first_row,
+                formula.first_column,
+                Some(value),
+                formula.format,
+            );
+        }
+
+        for cell in self.cells.iter() {
+            match cell {
+                NodeXlsxCell::Simple(cell) => {
+                    grid.set_type(cell.row, cell.col, &cell.cell_type, cell.format)
+                }
+                NodeXlsxCell::Merged(cell) => {
+                    let range = &cell.range;
+                    for row in range.start_row..=range.end_row {
+                        for col in range.start_col..=range.end_col {
+                            grid.set(row, col as u16, None, Some(cell.format));
+                        }
+                    }
+                    let (row, col) = (range.start_row, range.start_col as u16);
+                    grid.set_type(row, col, &cell.cell_type, Some(cell.format));
+                    grid.merges.push(*range);
+                }
+            }
+        }
+
+        for fill_formula in self.fill_formulas.iter() {
+            for row in fill_formula.first_row..=fill_formula.last_row {
+                for col in fill_formula.first_column..=fill_formula.last_column {
+                    let value = formula_value(&fill_formula.formula_at(row, col));
+                    grid.set(row, col, Some(value), fill_formula.format);
+                }
+            }
+        }
+
+        for (row, col, format) in self.blanks.iter() {
+            grid.set(*row, *col, None, Some(*format));
+        }
+
+        for column_block in self.column_blocks.iter() {
+            for (index, column) in column_block.columns.iter().enumerate() {
+                let col = column_block.first_column + index as u16;
+                let values: Vec<GridValue> = match column {
+                    ColumnData::Float(values) => {
+                        values.iter().map(|n| GridValue::Number(*n)).collect()
+                    }
+                    ColumnData::Integer(values) => values
+                        .iter()
+                        .map(|n| GridValue::Number(*n as f64))
+                        .collect(),
+                    ColumnData::String(values) => {
+                        values.iter().cloned().map(GridValue::Text).collect()
+                    }
+                    ColumnData::Date(values) => {
+                        values.iter().copied().map(GridValue::Date).collect()
+                    }
+                };
+                let cell_formats = column_block
+                    .cell_formats
+                    .get(index)
+                    .and_then(Option::as_ref);
+                let column_format = column_block.formats.get(index).copied().flatten();
+                for (offset, value) in values.into_iter().enumerate() {
+                    let format = match cell_formats {
+                        Some(formats) => formats.get(offset).copied().flatten(),
+                        None => column_format,
+                    };
+                    let row = column_block.first_row + offset as u32;
+                    grid.set(row, col, Some(value), format);
+                }
+            }
+        }
+
+        for csv_import in self.csv_imports.iter() {
+            let (last_row, last_column) = csv_import.last_cell();
+            if let Some(table) = csv_import.table() {
+                let grid_table = GridTable {
+                    first_row: csv_import.first_row,
+                    first_column: csv_import.first_column,
+                    last_row,
+                    last_column,
+                    header_row: table.header_row(),
+                    total_row: table.total_row(),
+                    look: table.look(),
+                };
+                grid.set_table(grid_table, table.data_columns());
+            }
+        }
+
+        for table in self.tables.iter() {
+            for cell in table.data.iter() {
+                grid.set_type(cell.row, cell.col, &cell.cell_type, cell.format);
+            }
+            let grid_table = GridTable {
+                first_row: table.first_row,
+                first_column: table.first_column,
+                last_row: table.last_row,
+                last_column: table.last_column,
+                header_row: table.header_row,
+                total_row: table.total_row,
+                look: table.look,
+            };
+            grid.set_table(grid_table, &table.columns);
+        }
+
+        for config in self.row_config.iter() {
+            grid.rows
+                .insert(config.index, GridLine::from_config(config, false));
+        }
+        for config in self.column_config.iter() {
+            let line = GridLine::from_config(config, true);
+            grid.columns.insert(config.index as u16, line);
+        }
+        Ok(grid)
+    }
+}
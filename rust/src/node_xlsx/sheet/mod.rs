@@ -1,27 +1,58 @@
 mod array_formula_value;
 
+mod column_block_value;
+mod conditional_format_preview;
 mod conditional_format_value;
 mod config;
+mod csv_import_value;
+mod duplicate_cell_policy;
+mod fill_formula_value;
+mod format_layers;
+mod formula_check;
+mod formula_results;
+mod grid;
+mod overlaps;
+mod range_format_value;
 mod table_value;
 
-use std::collections::HashMap;
+use std::collections::HashSet;
 
 use neon::{
-    context::FunctionContext,
+    context::{Context, FunctionContext},
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsArray, JsObject, JsString, JsValue},
+    types::{JsArray, JsBoolean, JsObject, JsString, JsValue},
 };
-use rust_xlsxwriter::{Format, Worksheet, XlsxError};
+use rust_xlsxwriter::{Formula, Worksheet, XlsxError};
 use table_value::NodeXlsxTableValue;
 
+pub use self::{
+    conditional_format_preview::preview_conditional_formats,
+    conditional_format_value::fix_priorities,
+    formula_check::FormulaErrors,
+    formula_results::evaluate_formulas,
+    grid::{GridCell, GridTable, GridValue, SheetGrid},
+};
+
 use self::{
     array_formula_value::ArrayFormulaSheetValue,
-    conditional_format_value::ConditionalFormatSheetValue, config::RowColumnConfig,
+    column_block_value::ColumnBlockSheetValue,
+    conditional_format_value::{
+        order_conditional_formats, written_priorities, ConditionalFormatSheetValue,
+    },
+    config::RowColumnConfig,
+    csv_import_value::CsvImportSheetValue,
+    duplicate_cell_policy::DuplicateCellPolicy,
+    fill_formula_value::FillFormulaSheetValue,
+    format_layers::FormatLayers,
+    range_format_value::RangeFormatSheetValue,
 };
 
-use super::{cell::NodeXlsxCell, conditional_format::c_type::NodeXlsxConditionalFormatType};
+use super::{
+    cell::NodeXlsxCell, conditional_format::registry::ConditionalFormatRegistry,
+    format_registry::FormatRegistry,
+};
 
 pub struct NodeXlsxSheet {
     name: String,
@@ -31,13 +62,20 @@ pub struct NodeXlsxSheet {
     row_config: Vec<RowColumnConfig>,
     column_config: Vec<RowColumnConfig>,
     tables: Vec<NodeXlsxTableValue>,
-
-    format_map: HashMap<u32, Format>,
-    conditional_format_map: HashMap<u32, NodeXlsxConditionalFormatType>,
+    column_blocks: Vec<ColumnBlockSheetValue>,
+    range_formats: Vec<RangeFormatSheetValue>,
+    fill_formulas: Vec<FillFormulaSheetValue>,
+    csv_imports: Vec<CsvImportSheetValue>,
+    blanks: Vec<(u32, u16, u32)>,
 }
 
 impl NodeXlsxSheet {
-    pub fn from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+        conditional_format_map: &mut ConditionalFormatRegistry,
+    ) -> NeonResult<Self> {
         let name: Handle<JsString> = obj.get(cx, "name")?;
         let name = name.value(cx);
 
@@ -53,36 +91,62 @@ impl NodeXlsxSheet {
         let tables: Handle<JsArray> = obj.get(cx, "tables")?;
         let tables: Vec<Handle<JsValue>> = tables.to_vec(cx)?;
 
+        let column_blocks: Handle<JsArray> = obj.get(cx, "columnBlocks")?;
+        let column_blocks: Vec<Handle<JsValue>> = column_blocks.to_vec(cx)?;
+
+        let range_formats: Option<Handle<JsArray>> = obj.get_opt(cx, "rangeFormats")?;
+        let range_formats: Vec<Handle<JsValue>> = match range_formats {
+            Some(range_formats) => range_formats.to_vec(cx)?,
+            None => vec![],
+        };
+
+        let fill_formulas: Option<Handle<JsArray>> = obj.get_opt(cx, "fillFormulas")?;
+        let fill_formulas: Vec<Handle<JsValue>> = match fill_formulas {
+            Some(fill_formulas) => fill_formulas.to_vec(cx)?,
+            None => vec![],
+        };
+
+        let csv_imports: Option<Handle<JsArray>> = obj.get_opt(cx, "csvImports")?;
+        let csv_imports: Vec<Handle<JsValue>> = match csv_imports {
+            Some(csv_imports) => csv_imports.to_vec(cx)?,
+            None => vec![],
+        };
+
+        let duplicate_cell_policy = DuplicateCellPolicy::from_js_object(cx, obj)?;
+
+        let compose_formats: Option<Handle<JsBoolean>> = obj.get_opt(cx, "composeFormats")?;
+        let compose_formats = match compose_formats {
+            Some(compose_formats) => compose_formats.value(cx),
+            None => false,
+        };
+
         let mut inner_cells = vec![];
         let mut inner_formulas = vec![];
         let mut inner_conditional_formats = vec![];
         let mut inner_tables = vec![];
-        let mut format_map = HashMap::new();
-        let mut conditional_format_map = HashMap::new();
-        let mut merged_cells = vec![]; // This will hold the merged cell ranges to validate against when creating cells
+        let mut inner_column_blocks = vec![];
+        let mut inner_range_formats = vec![];
+        let mut inner_fill_formulas = vec![];
+        let mut inner_csv_imports = vec![];
 
         let row_config: Handle<JsArray> = obj.get(cx, "rowConfigs")?;
         let row_config =
-            RowColumnConfig::from_js_array(cx, row_config, config::Type::ROW, &mut format_map)?;
+            RowColumnConfig::from_js_array(cx, row_config, config::Type::ROW, format_map)?;
 
         let column_config: Handle<JsArray> = obj.get(cx, "columnConfigs")?;
-        let column_config = RowColumnConfig::from_js_array(
-            cx,
-            column_config,
-            config::Type::COLUMN,
-            &mut format_map,
-        )?;
+        let column_config =
+            RowColumnConfig::from_js_array(cx, column_config, config::Type::COLUMN, format_map)?;
 
         for formula in array_formulas {
             let formula = formula.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
-            let formula = ArrayFormulaSheetValue::from_js_object(cx, formula, &mut format_map)?;
+            let formula = ArrayFormulaSheetValue::from_js_object(cx, formula, format_map)?;
             inner_formulas.push(formula);
         }
 
         for cell in cells {
             let cell = cell.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
 
-            let cell = NodeXlsxCell::from_js_object(cx, cell, &mut format_map, &mut merged_cells)?;
+            let cell = NodeXlsxCell::from_js_object(cx, cell, format_map)?;
             inner_cells.push(cell);
         }
 
@@ -92,49 +156,246 @@ impl NodeXlsxSheet {
             let conditional_format = ConditionalFormatSheetValue::from_js_object(
                 cx,
                 conditional_format,
-                &mut format_map,
-                &mut conditional_format_map,
+                format_map,
+                conditional_format_map,
             )?;
             inner_conditional_formats.push(conditional_format);
         }
 
         for table in tables {
             let table = table.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
-            let table = NodeXlsxTableValue::from_js_object(cx, table, &mut format_map)?;
+            let table = NodeXlsxTableValue::from_js_object(cx, table, format_map)?;
             inner_tables.push(table);
         }
 
-        return Ok(Self {
+        for column_block in column_blocks {
+            let column_block = column_block.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
+            let column_block = ColumnBlockSheetValue::from_js_object(cx, column_block, format_map)?;
+            inner_column_blocks.push(column_block);
+        }
+
+        for range_format in range_formats {
+            let range_format = range_format.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
+            let range_format = RangeFormatSheetValue::from_js_object(cx, range_format, format_map)?;
+            inner_range_formats.push(range_format);
+        }
+
+        for fill_formula in fill_formulas {
+            let fill_formula = fill_formula.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
+            let fill_formula = FillFormulaSheetValue::from_js_object(cx, fill_formula, format_map)?;
+            inner_fill_formulas.push(fill_formula);
+        }
+
+        for csv_import in csv_imports {
+            let csv_import = csv_import.downcast_or_throw::<JsObject, FunctionContext>(cx)?;
+            let csv_import = CsvImportSheetValue::from_js_object(cx, csv_import, format_map)?;
+            inner_csv_imports.push(csv_import);
+        }
+
+        for conditional_format in inner_conditional_formats.iter_mut() {
+            if let Err(error) = conditional_format.resolve_table_column(&inner_tables) {
+                let error = format!("Invalid conditional format on sheet '{}': {}", name, error);
+                return cx.throw_error(error);
+            }
+        }
+        if let Err(error) = order_conditional_formats(&mut inner_conditional_formats) {
+            let error = format!("Invalid conditional format on sheet '{}': {}", name, error);
+            return cx.throw_error(error);
+        }
+
+        // The imported cells take the place of their import among the cells
+        for csv_import in inner_csv_imports.iter_mut().rev() {
+            let at = csv_import
+                .cell_index
+                .unwrap_or(usize::MAX)
+                .min(inner_cells.len());
+            let cells = csv_import
+                .take_cells()
+                .into_iter()
+                .map(NodeXlsxCell::Simple);
+            inner_cells.splice(at..at, cells);
+        }
+
+        let inner_cells = duplicate_cell_policy.apply(inner_cells, format_map);
+
+        let mut sheet = Self {
             name,
             cells: inner_cells,
             conditional_formats: inner_conditional_formats,
             array_formulas: inner_formulas,
-            format_map,
-            conditional_format_map,
             row_config,
             column_config,
             tables: inner_tables,
-        });
+            column_blocks: inner_column_blocks,
+            range_formats: inner_range_formats,
+            fill_formulas: inner_fill_formulas,
+            csv_imports: inner_csv_imports,
+            blanks: vec![],
+        };
+        let conflicts = sheet.find_conflicts();
+        if !conflicts.is_empty() {
+            return cx.throw_error(conflicts.describe(&sheet.name));
+        }
+        if compose_formats || !sheet.range_formats.is_empty() {
+            sheet.compose_formats(compose_formats, format_map);
+        }
+        Ok(sheet)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Replaces the format of every written cell with its own format layered
+    /// over the range formats and, when `compose` is set, the column, row and
+    /// table column formats. Cells of a range format that hold no value are
+    /// written as formatted blanks.
+    fn compose_formats(&mut self, compose: bool, format_map: &mut FormatRegistry) {
+        let layers = FormatLayers::new(
+            compose,
+            &self.row_config,
+            &self.column_config,
+            &self.tables,
+            &self.range_formats,
+        );
+
+        for cell in self.cells.iter_mut() {
+            match cell {
+                NodeXlsxCell::Simple(cell) => {
+                    cell.format = layers.resolve(format_map, cell.row, cell.col, cell.format);
+                }
+                NodeXlsxCell::Merged(cell) => {
+                    let rows = (cell.range.start_row, cell.range.end_row);
+                    let cols = (cell.range.start_col as u16, cell.range.end_col as u16);
+                    let own = Some(cell.format);
+                    if let Some(format) = layers.resolve_area(format_map, rows, cols, own) {
+                        cell.format = format;
+                    }
+                }
+            }
+        }
+
+        for table in self.tables.iter_mut() {
+            for cell in table.data.iter_mut() {
+                cell.format = layers.resolve(format_map, cell.row, cell.col, cell.format);
+            }
+        }
+
+        for formula in self.array_formulas.iter_mut() {
+            let row = formula.first_row;
+            let col = formula.first_column;
+            formula.format = layers.resolve(format_map, row, col, formula.format);
+        }
+
+        for column_block in self.column_blocks.iter_mut() {
+            column_block.compose_formats(&layers, format_map);
+        }
+
+        for fill_formula in self.fill_formulas.iter_mut() {
+            fill_formula.compose_formats(&layers, format_map);
+        }
+
+        if self.range_formats.is_empty() {
+            return;
+        }
+        let mut occupied = self.occupied_cells();
+        for range in self.range_formats.iter() {
+            if range.whole_rows_or_columns {
+                continue;
+            }
+            for row in range.first_row..=range.last_row {
+                for col in range.first_column..=range.last_column {
+                    if !occupied.insert((row, col)) {
+                        continue;
+                    }
+                    if let Some(format) = layers.resolve(format_map, row, col, None) {
+                        self.blanks.push((row, col, format));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every cell that is written with a value or belongs to a merged area,
+    /// a table, an array formula or a fill formula.
+    fn occupied_cells(&self) -> HashSet<(u32, u16)> {
+        let mut occupied = HashSet::new();
+        let mut occupy = |rows: (u32, u32), cols: (u16, u16)| {
+            for row in rows.0..=rows.1 {
+                for col in cols.0..=cols.1 {
+                    occupied.insert((row, col));
+                }
+            }
+        };
+
+        for cell in self.cells.iter() {
+            match cell {
+                NodeXlsxCell::Simple(cell) => occupy((cell.row, cell.row), (cell.col, cell.col)),
+                NodeXlsxCell::Merged(cell) => occupy(
+                    (cell.range.start_row, cell.range.end_row),
+                    (cell.range.start_col as u16, cell.range.end_col as u16),
+                ),
+            }
+        }
+        for table in self.tables.iter() {
+            occupy(
+                (table.first_row, table.last_row),
+                (table.first_column, table.last_column),
+            );
+        }
+        for formula in self.array_formulas.iter() {
+            occupy(
+                (formula.first_row, formula.last_row),
+                (formula.first_column, formula.last_column),
+            );
+        }
+        for fill_formula in self.fill_formulas.iter() {
+            occupy(
+                (fill_formula.first_row, fill_formula.last_row),
+                (fill_formula.first_column, fill_formula.last_column),
+            );
+        }
+        for column_block in self.column_blocks.iter() {
+            for (index, column) in column_block.columns.iter().enumerate() {
+                if column.len() == 0 {
+                    continue;
+                }
+                let last_row = column_block.first_row + column.len() as u32 - 1;
+                let col = column_block.first_column + index as u16;
+                occupy((column_block.first_row, last_row), (col, col));
+            }
+        }
+        occupied
     }
 }
 
-impl TryInto<Worksheet> for NodeXlsxSheet {
-    type Error = XlsxError;
-    fn try_into(self) -> Result<Worksheet, XlsxError> {
-        let format_map = self.format_map;
-        let conditional_format_map = self.conditional_format_map;
+impl NodeXlsxSheet {
+    /// The priorities to write over the ones rust_xlsxwriter gives the
+    /// conditional format rules, see [`written_priorities`].
+    pub fn conditional_format_priorities(
+        &self,
+        conditional_formats: &ConditionalFormatRegistry,
+    ) -> Vec<u32> {
+        written_priorities(&self.conditional_formats, conditional_formats)
+    }
+
+    pub fn into_worksheet(
+        self,
+        format_map: &FormatRegistry,
+        conditional_format_map: &ConditionalFormatRegistry,
+    ) -> Result<Worksheet, XlsxError> {
         let mut worksheet = Worksheet::new();
         worksheet.set_name(&self.name)?;
         for rc in self.row_config {
-            rc.write_to_sheet(&mut worksheet, &format_map)?;
+            rc.write_to_sheet(&mut worksheet, format_map)?;
         }
 
         for cc in self.column_config {
-            cc.write_to_sheet(&mut worksheet, &format_map)?;
+            cc.write_to_sheet(&mut worksheet, format_map)?;
         }
 
         for cf in self.conditional_formats {
-            cf.set_conditional_format(&mut worksheet, &conditional_format_map)?;
+            cf.set_conditional_format(&mut worksheet, conditional_format_map)?;
         }
 
         for af in self.array_formulas {
@@ -142,8 +403,8 @@ impl TryInto<Worksheet> for NodeXlsxSheet {
             let last_row = af.last_row;
             let first_column = af.first_column;
             let last_column = af.last_column;
-            let formula = af.formula;
-            let dynamic = af.dynamic;
+            let dynamic = af.formula.dynamic;
+            let formula: Formula = af.formula.into();
             let has_format = af.format.is_some();
             if dynamic && !has_format {
                 worksheet.write_dynamic_array_formula(
@@ -187,16 +448,27 @@ impl TryInto<Worksheet> for NodeXlsxSheet {
         }
 
         for cell in self.cells {
-            cell.write_to_sheet(&mut worksheet, &format_map)?;
+            cell.write_to_sheet(&mut worksheet, format_map)?;
+        }
+
+        for fill_formula in self.fill_formulas {
+            fill_formula.write_to_sheet(&mut worksheet, format_map)?;
+        }
+
+        for (row, col, format) in self.blanks {
+            worksheet.write_blank(row, col, format_map.get(&format).unwrap())?;
+        }
+
+        for column_block in self.column_blocks {
+            column_block.write_to_sheet(&mut worksheet, format_map)?;
+        }
+
+        for csv_import in self.csv_imports {
+            csv_import.write_to_sheet(&mut worksheet, format_map)?;
         }
 
         for table in self.tables {
-            let first_row = table.first_row;
-            let last_row = table.last_row;
-            let first_column = table.first_column;
-            let last_column = table.last_column;
-            let table = table.table;
-            worksheet.add_table(first_row, first_column, last_row, last_column, &table)?;
+            table.write_to_sheet(&mut worksheet, format_map)?;
         }
         return Ok(worksheet);
     }
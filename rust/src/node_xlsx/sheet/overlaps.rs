@@ -0,0 +1,201 @@
+use std::collections::{HashMap, HashSet};
+
+use rust_xlsxwriter::utility::{cell_range, row_col_to_cell};
+
+use crate::node_xlsx::{cell::NodeXlsxCell, cell_range::CellRange};
+
+use super::NodeXlsxSheet;
+
+/// Most conflicts spelled out in a single error.
+const MAX_REPORTED: usize = 20;
+
+/// A rectangle written by one object of the sheet.
+struct Area {
+    kind: &'static str,
+    range: CellRange,
+    /// Plain cells and column blocks may be written inside the area
+    accepts_values: bool,
+    /// The area holds plain values, like a column of a column block
+    is_values: bool,
+}
+
+impl Area {
+    fn new(kind: &'static str, rows: (u32, u32), cols: (u16, u16)) -> Self {
+        Self {
+            kind,
+            range: CellRange {
+                start_row: rows.0,
+                end_row: rows.1,
+                start_col: cols.0 as u32,
+                end_col: cols.1 as u32,
+            },
+            accepts_values: false,
+            is_values: false,
+        }
+    }
+
+    fn conflicts_with(&self, other: &Self) -> bool {
+        let allowed =
+            (self.accepts_values && other.is_values) || (other.accepts_values && self.is_values);
+        self.range.overlaps(&other.range) && !allowed
+    }
+
+    fn name(&self) -> String {
+        let CellRange {
+            start_row,
+            end_row,
+            start_col,
+            end_col,
+        } = self.range;
+        let range = cell_range(start_row, start_col as u16, end_row, end_col as u16);
+        format!("{} {}", self.kind, range)
+    }
+}
+
+#[derive(Default)]
+pub struct Conflicts {
+    messages: Vec<String>,
+    total: usize,
+}
+
+impl Conflicts {
+    fn report(&mut self, message: impl FnOnce() -> String) {
+        if self.total < MAX_REPORTED {
+            self.messages.push(message());
+        }
+        self.total += 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn describe(&self, sheet_name: &str) -> String {
+        let mut error = format!(
+            "Sheet \"{}\" writes to the same cells more than once:\n  {}",
+            sheet_name,
+            self.messages.join("\n  ")
+        );
+        if self.total > self.messages.len() {
+            let more = self.total - self.messages.len();
+            error.push_str(&format!("\n  and {} more", more));
+        }
+        error
+    }
+}
+
+impl NodeXlsxSheet {
+    /// Finds the merges, tables, array formulas, fill formulas, column blocks
+    /// and cells, imported ones included, that write over each other. Excel has to "repair" a file with any of them.
+    pub(super) fn find_conflicts(&self) -> Conflicts {
+        let mut conflicts = Conflicts::default();
+        let mut areas = self.areas();
+
+        areas.sort_by_key(|area| area.range.start_row);
+        for (index, area) in areas.iter().enumerate() {
+            for other in areas[index + 1..].iter() {
+                if other.range.start_row > area.range.end_row {
+                    break;
+                }
+                if area.conflicts_with(other) {
+                    conflicts.report(|| format!("{} overlaps {}", area.name(), other.name()));
+                }
+            }
+        }
+
+        let mut by_column: HashMap<u16, Vec<&Area>> = HashMap::new();
+        for area in areas.iter().filter(|area| !area.accepts_values) {
+            for col in area.range.start_col..=area.range.end_col {
+                by_column.entry(col as u16).or_default().push(area);
+            }
+        }
+
+        let mut written = HashSet::new();
+        for cell in self.cells.iter() {
+            let NodeXlsxCell::Simple(cell) = cell else {
+                continue;
+            };
+            let name = || row_col_to_cell(cell.row, cell.col);
+            if !written.insert((cell.row, cell.col)) {
+                conflicts.report(|| format!("cell {} is written twice", name()));
+            }
+            let Some(column_areas) = by_column.get(&cell.col) else {
+                continue;
+            };
+            for area in column_areas
+                .iter()
+                .filter(|a| a.range.contains(cell.row, cell.col))
+            {
+                conflicts.report(|| format!("cell {} overlaps {}", name(), area.name()));
+            }
+        }
+
+        conflicts
+    }
+
+    fn areas(&self) -> Vec<Area> {
+        let mut areas = vec![];
+        for cell in self.cells.iter() {
+            if let NodeXlsxCell::Merged(cell) = cell {
+                areas.push(Area {
+                    kind: "merged range",
+                    range: cell.range,
+                    accepts_values: false,
+                    is_values: false,
+                });
+            }
+        }
+        for table in self.tables.iter() {
+            let rows = (table.first_row, table.last_row);
+            let cols = (table.first_column, table.last_column);
+            areas.push(Area {
+                // A table filled from records writes its own cells
+                accepts_values: table.data.is_empty(),
+                ..Area::new("table", rows, cols)
+            });
+        }
+        for csv_import in self.csv_imports.iter() {
+            if csv_import.table().is_none() {
+                continue;
+            }
+            let (last_row, last_column) = csv_import.last_cell();
+            let rows = (csv_import.first_row, last_row);
+            let cols = (csv_import.first_column, last_column);
+            areas.push(Area {
+                // The imported cells are written inside the table
+                accepts_values: true,
+                ..Area::new("CSV table", rows, cols)
+            });
+        }
+        for formula in self.array_formulas.iter() {
+            let rows = (formula.first_row, formula.last_row);
+            let cols = (formula.first_column, formula.last_column);
+            areas.push(Area::new("array formula", rows, cols));
+        }
+        for fill_formula in self.fill_formulas.iter() {
+            let rows = (fill_formula.first_row, fill_formula.last_row);
+            let cols = (fill_formula.first_column, fill_formula.last_column);
+            areas.push(Area {
+                is_values: true,
+                ..Area::new("fill formula", rows, cols)
+            });
+        }
+        for column_block in self.column_blocks.iter() {
+            for (index, column) in column_block.columns.iter().enumerate() {
+                if column.len() == 0 {
+                    continue;
+                }
+                let rows = (
+                    column_block.first_row,
+                    column_block.first_row + column.len() as u32 - 1,
+                );
+                let col = column_block.first_column + index as u16;
+                areas.push(Area {
+                    is_values: true,
+                    ..Area::new("column block", rows, (col, col))
+                });
+            }
+        }
+        areas
+    }
+}
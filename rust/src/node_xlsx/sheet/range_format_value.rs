@@ -0,0 +1,89 @@
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{JsObject, JsValue},
+};
+
+use crate::node_xlsx::{
+    border::Border, cell_range::CellRange, format::BoxEdges, format_registry::FormatRegistry,
+    util::create_format,
+};
+
+pub struct RangeFormatSheetValue {
+    pub first_row: u32,
+    pub last_row: u32,
+    pub first_column: u16,
+    pub last_column: u16,
+    pub format: Option<u32>,
+    pub outer_border: Option<Border>,
+    /// Whole row and column ranges only format written cells, no blanks
+    pub whole_rows_or_columns: bool,
+}
+
+impl RangeFormatSheetValue {
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+        format_map: &mut FormatRegistry,
+    ) -> NeonResult<Self> {
+        let range = CellRange::from_js_object(cx, obj, ("firstCol", "lastCol"))?;
+
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
+        let format = match format {
+            Some(format) => Some(create_format(cx, format, format_map)?),
+            None => None,
+        };
+
+        let outer_border: Option<Handle<JsObject>> = obj.get_opt(cx, "outerBorder")?;
+        let outer_border = match outer_border {
+            Some(outer_border) => Some(Border::from_js_object(cx, outer_border)?),
+            None => None,
+        };
+
+        if format.is_none() && outer_border.is_none() {
+            return cx.throw_error("A range format needs a format or an outer border");
+        }
+
+        Ok(Self {
+            first_row: range.start_row,
+            last_row: range.end_row,
+            first_column: range.start_col as u16,
+            last_column: range.end_col as u16,
+            whole_rows_or_columns: range.is_whole_rows_or_columns(),
+            format,
+            outer_border,
+        })
+    }
+
+    pub fn contains(&self, row: u32, col: u16) -> bool {
+        (self.first_row..=self.last_row).contains(&row)
+            && (self.first_column..=self.last_column).contains(&col)
+    }
+
+    /// The format of the area from `first_row`, `first_col` to `last_row`,
+    /// `last_col` inside the range, with the outer border on the edges it touches.
+    pub fn resolve(
+        &self,
+        format_map: &mut FormatRegistry,
+        (first_row, last_row): (u32, u32),
+        (first_col, last_col): (u16, u16),
+    ) -> Option<u32> {
+        let border = match self.outer_border {
+            Some(border) => border,
+            None => return self.format,
+        };
+
+        let edges = BoxEdges {
+            top: first_row == self.first_row,
+            bottom: last_row == self.last_row,
+            left: first_col == self.first_column,
+            right: last_col == self.last_column,
+        };
+        if edges.is_empty() {
+            return self.format;
+        }
+        Some(format_map.with_box_border(self.format, border, edges))
+    }
+}
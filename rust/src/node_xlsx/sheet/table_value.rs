@@ -1,51 +1,249 @@
-use std::collections::HashMap;
-
 use neon::{
-    context::FunctionContext,
+    context::{Context, FunctionContext},
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsNumber, JsObject},
+    types::{JsArray, JsDate, JsObject, JsString, JsValue},
 };
-use rust_xlsxwriter::{Format, Table};
+use rust_xlsxwriter::{Table, Worksheet, XlsxError};
 
-use crate::node_xlsx::table::NodeXlsxTable;
+use crate::node_xlsx::{
+    cell::SimpleCell,
+    cell_range::{column_from_js_object, row_from_js_object, CellRange},
+    format_registry::FormatRegistry,
+    table::{NodeXlsxTable, TableDataColumn, TableLook},
+    types::NodeXlsxTypes,
+};
 
 pub struct NodeXlsxTableValue {
+    pub name: Option<String>,
     pub first_row: u32,
     pub last_row: u32,
     pub first_column: u16,
     pub last_column: u16,
     pub table: Table,
+    pub data: Vec<SimpleCell>,
+    pub header_row: bool,
+    pub total_row: bool,
+    pub column_formats: Vec<Option<u32>>,
+    pub columns: Vec<TableDataColumn>,
+    pub look: TableLook,
 }
 
 impl NodeXlsxTableValue {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &mut HashMap<u32, Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
-        let first_row: Handle<JsNumber> = obj.get(cx, "firstRow")?;
-        let first_row = first_row.value(cx) as u32;
-
-        let last_row: Handle<JsNumber> = obj.get(cx, "lastRow")?;
-        let last_row = last_row.value(cx) as u32;
-
-        let first_column: Handle<JsNumber> = obj.get(cx, "firstColumn")?;
-        let first_column = first_column.value(cx) as u16;
+        let table: Handle<JsObject> = obj.get(cx, "table")?;
+        let mut table = NodeXlsxTable::from_js_object(cx, table, format_map)?;
 
-        let last_column: Handle<JsNumber> = obj.get(cx, "lastColumn")?;
-        let last_column = last_column.value(cx) as u16;
+        let data: Option<Handle<JsArray>> = obj.get_opt(cx, "data")?;
+        let (first_row, last_row, first_column, last_column, data) = match data {
+            Some(data) => {
+                // Only the top left cell is given, the rest follows from the records
+                let a1: Option<Handle<JsString>> = obj.get_opt(cx, "range")?;
+                let (first_row, first_column) = match a1 {
+                    Some(a1) => {
+                        let a1 = a1.value(cx);
+                        CellRange::parse_cell(&a1).or_else(|error| cx.throw_error(error))?
+                    }
+                    None => (
+                        row_from_js_object(cx, obj, "firstRow")?,
+                        column_from_js_object(cx, obj, "firstColumn")?,
+                    ),
+                };
+                let records = data.to_vec(cx)?;
+                let (last_row, last_column, data) =
+                    table_data_from_records(cx, &mut table, records, first_row, first_column)?;
+                (first_row, last_row, first_column, last_column, data)
+            }
+            None => {
+                let range = CellRange::from_js_object(cx, obj, ("firstColumn", "lastColumn"))?;
+                let first_column = range.start_col as u16;
+                let last_column = range.end_col as u16;
+                (
+                    range.start_row,
+                    range.end_row,
+                    first_column,
+                    last_column,
+                    vec![],
+                )
+            }
+        };
 
-        let table: Handle<JsObject> = obj.get(cx, "table")?;
-        let table = NodeXlsxTable::from_js_object(cx, table, format_map)?;
+        let name = table.name().map(|name| name.to_string());
+        let columns = table.data_columns().to_vec();
+        let look = table.look();
+        let header_row = table.header_row();
+        let total_row = table.total_row();
+        let column_formats = table
+            .data_columns()
+            .iter()
+            .map(|column| column.format)
+            .collect();
 
         Ok(Self {
+            name,
             first_row,
             last_row,
             first_column,
             last_column,
             table: table.into(),
+            data,
+            header_row,
+            total_row,
+            column_formats,
+            columns,
+            look,
         })
     }
+
+    /// The sheet column of a table column, found by its header without case
+    /// like Excel's structured references do.
+    pub fn column_by_header(&self, header: &str) -> Option<u16> {
+        let width = self.last_column - self.first_column + 1;
+        (0..width)
+            .find(|index| {
+                let name = match self.columns.get(*index as usize) {
+                    Some(TableDataColumn {
+                        header: Some(name), ..
+                    }) => name.clone(),
+                    _ => format!("Column{}", index + 1),
+                };
+                name.to_lowercase() == header.to_lowercase()
+            })
+            .map(|index| self.first_column + index)
+    }
+
+    /// The first and last row holding data, without the header and total rows.
+    pub fn data_rows(&self) -> (u32, u32) {
+        let first = self.first_row + self.header_row as u32;
+        let last = self.last_row.saturating_sub(self.total_row as u32);
+        (first, last)
+    }
+
+    pub fn write_to_sheet(
+        self,
+        worksheet: &mut Worksheet,
+        format_map: &FormatRegistry,
+    ) -> Result<(), XlsxError> {
+        for cell in self.data {
+            cell.write_to_sheet(worksheet, format_map)?;
+        }
+        worksheet.add_table(
+            self.first_row,
+            self.first_column,
+            self.last_row,
+            self.last_column,
+            &self.table,
+        )?;
+        Ok(())
+    }
+}
+
+/// Turns an array of records into data cells, deriving the headers from the
+/// record keys when the table has no column spec.
+/// Returns the last row, the last column and the cells to write.
+fn table_data_from_records(
+    cx: &mut FunctionContext,
+    table: &mut NodeXlsxTable,
+    records: Vec<Handle<JsValue>>,
+    first_row: u32,
+    first_column: u16,
+) -> NeonResult<(u32, u16, Vec<SimpleCell>)> {
+    let mut objects = Vec::with_capacity(records.len());
+    for record in records {
+        let record = record.downcast_or_throw::<JsObject, _>(cx)?;
+        objects.push(record);
+    }
+
+    if table.data_columns().is_empty() {
+        let mut headers: Vec<String> = vec![];
+        for object in objects.iter() {
+            let keys = object.get_own_property_names(cx)?.to_vec(cx)?;
+            for key in keys {
+                let key = key.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+                if !headers.contains(&key) {
+                    headers.push(key);
+                }
+            }
+        }
+        table.set_headers(&headers);
+    }
+
+    let column_count = table.data_columns().len();
+    if column_count == 0 {
+        return cx.throw_error("Table data must have at least one column");
+    }
+    let last_column = first_column as usize + column_count - 1;
+    if last_column >= 16_384 {
+        let error = format!(
+            "Table with {} columns exceeds the sheet width",
+            column_count
+        );
+        return cx.throw_error(error);
+    }
+
+    let data_start = if table.header_row() {
+        first_row + 1
+    } else {
+        first_row
+    };
+    // A table always keeps at least one (possibly empty) data row
+    let data_rows = objects.len().max(1) as u32;
+    let mut last_row = data_start as u64 + data_rows as u64 - 1;
+    if table.total_row() {
+        last_row += 1;
+    }
+    if last_row >= 1_048_576 {
+        let error = format!("Table with {} rows exceeds the sheet height", objects.len());
+        return cx.throw_error(error);
+    }
+
+    let mut cells = Vec::with_capacity(objects.len() * column_count);
+    for (i, object) in objects.into_iter().enumerate() {
+        let row = data_start + i as u32;
+        for (j, column) in table.data_columns().iter().enumerate() {
+            let key = match &column.key {
+                Some(key) => key,
+                None => {
+                    let error = format!("Table column {} needs a key or a header", j);
+                    return cx.throw_error(error);
+                }
+            };
+            let value: Option<Handle<JsValue>> = object.get_opt(cx, key.as_str())?;
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+            let cell_type = match &column.cell_type {
+                Some(cell_type) => NodeXlsxTypes::from_type_name(cx, cell_type, value)?,
+                None => {
+                    // Only dates among objects have a cell type of their own
+                    if value.is_a::<JsObject, _>(cx) && !value.is_a::<JsDate, _>(cx) {
+                        let kind = match value.is_a::<JsArray, _>(cx) {
+                            true => "an array",
+                            false => "an object",
+                        };
+                        let error = format!(
+                            "Table record {} has {} at key {:?}; give the column a type or pass a number, string, boolean or Date",
+                            i, kind, key
+                        );
+                        return cx.throw_error(error);
+                    }
+                    NodeXlsxTypes::from_js_value(cx, value)?
+                }
+            };
+            cells.push(SimpleCell {
+                col: first_column + j as u16,
+                row,
+                cell_type,
+                format: column.format,
+            });
+        }
+    }
+
+    Ok((last_row as u32, last_column as u16, cells))
 }
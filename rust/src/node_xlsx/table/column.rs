@@ -1,19 +1,34 @@
-use std::collections::HashMap;
-
 use neon::{
     context::FunctionContext,
     handle::Handle,
     object::Object,
     result::NeonResult,
-    types::{JsNumber, JsObject, JsString},
+    types::{JsObject, JsString, JsValue},
 };
 use rust_xlsxwriter::{Format, Formula, TableColumn, TableFunction};
 
-use crate::node_xlsx::{format::NodeXlsxFormat, util::object_to_formula};
+use crate::node_xlsx::{
+    format_registry::FormatRegistry,
+    util::{create_format, object_to_formula},
+};
 
 use super::util::object_to_table_function;
 
+/// How a table column reads its values when the table is built from records
+#[derive(Clone)]
+pub struct TableDataColumn {
+    pub key: Option<String>,
+    /// The header written above the column, Excel names it `Column1` and on when unset
+    pub header: Option<String>,
+    pub cell_type: Option<String>,
+    pub format: Option<u32>,
+    /// The formula of a calculated column, as given
+    pub formula: Option<String>,
+    pub total_label: Option<String>,
+}
+
 pub struct NodeXlsxTableColumn<'a> {
+    data: TableDataColumn,
     formula: Option<Formula>,
     format: Option<&'a Format>,
     header: Option<String>,
@@ -26,41 +41,33 @@ impl<'a> NodeXlsxTableColumn<'a> {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &'a mut HashMap<u32, Format>,
+        format_map: &'a mut FormatRegistry,
     ) -> NeonResult<Self> {
         let mut format_id = None;
         let formula: Option<Handle<JsObject>> = obj.get_opt(cx, "formula")?;
-        let formula = match formula {
+        let (formula, formula_text) = match formula {
             Some(formula) => {
+                let text: Handle<JsString> = formula.get(cx, "formula")?;
+                let text = text.value(cx);
                 let (formula, _) = object_to_formula(cx, formula)?;
-                Some(formula)
+                (Some(formula), Some(text))
             }
-            None => None,
+            None => (None, None),
         };
 
-        let format: Option<Handle<JsObject>> = obj.get_opt(cx, "format")?;
+        let format: Option<Handle<JsValue>> = obj.get_opt(cx, "format")?;
         match format {
             Some(format) => {
-                let id: Handle<JsNumber> = format.get(cx, "id")?;
-                let id = id.value(cx) as u32;
-                if !format_map.contains_key(&id) {
-                    let format = NodeXlsxFormat::from_js_object(cx, format)?;
-                    format_map.insert(id, format.into());
-                }
+                let id = create_format(cx, format, format_map)?;
                 format_id = Some(id);
             }
             None => {}
         };
 
-        let header_format: Option<Handle<JsObject>> = obj.get_opt(cx, "headerFormat")?;
+        let header_format: Option<Handle<JsValue>> = obj.get_opt(cx, "headerFormat")?;
         let header_format = match header_format {
             Some(header_format) => {
-                let id: Handle<JsNumber> = header_format.get(cx, "id")?;
-                let id = id.value(cx) as u32;
-                if !format_map.contains_key(&id) {
-                    let format = NodeXlsxFormat::from_js_object(cx, header_format)?;
-                    format_map.insert(id, format.into());
-                }
+                let id = create_format(cx, header_format, format_map)?;
                 let format = format_map.get(&id).unwrap();
                 Some(format)
             }
@@ -71,6 +78,13 @@ impl<'a> NodeXlsxTableColumn<'a> {
         let header: Option<Handle<JsString>> = obj.get_opt(cx, "header")?;
         let header = header.map(|header| header.value(cx));
 
+        // Records are read by header when no explicit key is given
+        let key: Option<Handle<JsString>> = obj.get_opt(cx, "key")?;
+        let key = key.map(|key| key.value(cx)).or_else(|| header.clone());
+
+        let cell_type: Option<Handle<JsString>> = obj.get_opt(cx, "cellType")?;
+        let cell_type = cell_type.map(|cell_type| cell_type.value(cx));
+
         let total_label: Option<Handle<JsString>> = obj.get_opt(cx, "totalLabel")?;
         let total_label = total_label.map(|total_label| total_label.value(cx));
 
@@ -83,7 +97,17 @@ impl<'a> NodeXlsxTableColumn<'a> {
             None => None,
         };
 
+        let data = TableDataColumn {
+            key,
+            header: header.clone(),
+            cell_type,
+            format: format_id,
+            formula: formula_text,
+            total_label: total_label.clone(),
+        };
+
         Ok(Self {
+            data,
             formula,
             format,
             header,
@@ -96,10 +120,18 @@ impl<'a> NodeXlsxTableColumn<'a> {
     pub fn create_and_into(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &'a mut HashMap<u32, Format>,
-    ) -> NeonResult<TableColumn> {
-        let column = Self::from_js_object(cx, obj, format_map)?;
-        Ok(column.into())
+        format_map: &'a mut FormatRegistry,
+    ) -> NeonResult<(TableColumn, TableDataColumn)> {
+        let mut column = Self::from_js_object(cx, obj, format_map)?;
+        let data = TableDataColumn {
+            key: column.data.key.take(),
+            header: column.data.header.take(),
+            cell_type: column.data.cell_type.take(),
+            format: column.data.format,
+            formula: column.data.formula.take(),
+            total_label: column.data.total_label.take(),
+        };
+        Ok((column.into(), data))
     }
 }
 
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use neon::{
     context::FunctionContext,
     handle::Handle,
@@ -7,9 +5,13 @@ use neon::{
     result::NeonResult,
     types::{JsArray, JsBoolean, JsObject, JsString},
 };
-use rust_xlsxwriter::{Format, Table, TableColumn, TableStyle};
+use rust_xlsxwriter::{Table, TableColumn, TableStyle};
 use util::string_to_table_style;
 
+use crate::node_xlsx::format_registry::FormatRegistry;
+
+pub use column::TableDataColumn;
+
 mod column;
 mod util;
 
@@ -19,6 +21,7 @@ pub struct NodeXlsxTable {
     banded_columns: bool,
     banded_rows: bool,
     columns: Vec<TableColumn>,
+    data_columns: Vec<TableDataColumn>,
     first_column_highlighted: bool,
     last_column_highlighted: bool,
     header_row: bool,
@@ -26,11 +29,21 @@ pub struct NodeXlsxTable {
     total_row: bool,
 }
 
+/// How a table is shown: its style, and the stripes and columns it stresses.
+#[derive(Clone, Copy)]
+pub struct TableLook {
+    pub style: TableStyle,
+    pub banded_rows: bool,
+    pub banded_columns: bool,
+    pub first_column: bool,
+    pub last_column: bool,
+}
+
 impl NodeXlsxTable {
     pub fn from_js_object(
         cx: &mut FunctionContext,
         obj: Handle<JsObject>,
-        format_map: &mut HashMap<u32, Format>,
+        format_map: &mut FormatRegistry,
     ) -> NeonResult<Self> {
         let name: Option<Handle<JsString>> = obj.get_opt(cx, "name")?;
         let name = name.map(|name| name.value(cx));
@@ -45,12 +58,15 @@ impl NodeXlsxTable {
         let banded_rows = banded_rows.value(cx);
 
         let mut inner_columns: Vec<TableColumn> = vec![];
+        let mut data_columns: Vec<TableDataColumn> = vec![];
         let columns: Handle<JsArray> = obj.get(cx, "columns")?;
         let columns = columns.to_vec(cx)?;
         for column in columns {
             let column = column.downcast_or_throw::<JsObject, _>(cx)?;
-            let column = column::NodeXlsxTableColumn::create_and_into(cx, column, format_map)?;
+            let (column, data_column) =
+                column::NodeXlsxTableColumn::create_and_into(cx, column, format_map)?;
             inner_columns.push(column);
+            data_columns.push(data_column);
         }
 
         let first_column_highlighted: Handle<JsBoolean> = obj.get(cx, "firstColumnHighlighted")?;
@@ -77,6 +93,7 @@ impl NodeXlsxTable {
             banded_columns,
             banded_rows,
             columns: inner_columns,
+            data_columns,
             first_column_highlighted,
             last_column_highlighted,
             header_row,
@@ -84,6 +101,52 @@ impl NodeXlsxTable {
             total_row,
         })
     }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn header_row(&self) -> bool {
+        self.header_row
+    }
+
+    pub fn total_row(&self) -> bool {
+        self.total_row
+    }
+
+    /// The look of the table, with rust_xlsxwriter's default style when it has none.
+    pub fn look(&self) -> TableLook {
+        TableLook {
+            style: self.style.unwrap_or(TableStyle::Medium9),
+            banded_rows: self.banded_rows,
+            banded_columns: self.banded_columns,
+            first_column: self.first_column_highlighted,
+            last_column: self.last_column_highlighted,
+        }
+    }
+
+    pub fn data_columns(&self) -> &[TableDataColumn] {
+        &self.data_columns
+    }
+
+    /// Replaces the column definitions with plain columns named after `headers`
+    pub fn set_headers(&mut self, headers: &[String]) {
+        self.columns = headers
+            .iter()
+            .map(|header| TableColumn::new().set_header(header))
+            .collect();
+        self.data_columns = headers
+            .iter()
+            .map(|header| TableDataColumn {
+                key: Some(header.clone()),
+                header: Some(header.clone()),
+                cell_type: None,
+                format: None,
+                formula: None,
+                total_label: None,
+            })
+            .collect();
+    }
 }
 
 impl Into<Table> for NodeXlsxTable {
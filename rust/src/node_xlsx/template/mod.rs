@@ -0,0 +1,666 @@
+mod placeholder;
+mod sheet_data;
+mod value;
+
+use std::collections::{HashMap, HashSet};
+
+use neon::{
+    context::FunctionContext,
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{buffer::TypedArray, JsArray, JsBuffer, JsObject, JsString, JsValue, Value},
+};
+use rust_xlsxwriter::utility::row_col_to_cell;
+
+use self::{
+    placeholder::{replace_in_text_elements, whole_placeholder, TemplateData},
+    sheet_data::{find_element, raw_attributes, write_attributes, SheetData},
+    value::{flatten_data, TemplateValue},
+};
+use super::{
+    cell_range::CellRange,
+    error::NodeXlsxError,
+    package::{read_parts, rewrite_parts},
+    reader::{relationships, rich_text, workbook_part},
+    xml::XmlElement,
+};
+
+/// Where the template package comes from.
+enum TemplateSource {
+    Buffer(Vec<u8>),
+    Path(String),
+}
+
+/// A value for a cell of a sheet, found by the name of the sheet.
+struct CellUpdate {
+    sheet: String,
+    row: u32,
+    col: u16,
+    value: TemplateValue,
+}
+
+/// A row of table data: values in the order of the columns, or values by
+/// the header of their column.
+enum TableRow {
+    Values(Vec<TemplateValue>),
+    Record(Vec<(String, TemplateValue)>),
+}
+
+struct TableUpdate {
+    name: String,
+    rows: Vec<TableRow>,
+}
+
+/// An existing xlsx package and the values to write into it. Only the parts
+/// whose content changes are written again, every other part is copied
+/// byte for byte.
+pub struct NodeXlsxTemplate {
+    source: TemplateSource,
+    cells: Vec<CellUpdate>,
+    tables: Vec<TableUpdate>,
+    defined_names: Vec<(String, TemplateValue)>,
+    data: TemplateData,
+}
+
+impl NodeXlsxTemplate {
+    pub fn from_js_object(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Self> {
+        let source: Handle<JsValue> = obj.get(cx, "source")?;
+        let source = match source.downcast::<JsBuffer, _>(cx) {
+            Ok(buffer) => TemplateSource::Buffer(buffer.as_slice(cx).to_vec()),
+            Err(_) => {
+                let path = source.downcast_or_throw::<JsString, _>(cx)?;
+                TemplateSource::Path(path.value(cx))
+            }
+        };
+
+        let mut cells = vec![];
+        let js_cells: Option<Handle<JsArray>> = obj.get_opt(cx, "cells")?;
+        if let Some(js_cells) = js_cells {
+            let js_cells: Vec<Handle<JsValue>> = js_cells.to_vec(cx)?;
+            for cell in js_cells {
+                let cell = cell.downcast_or_throw::<JsObject, _>(cx)?;
+                let sheet: Handle<JsString> = cell.get(cx, "sheet")?;
+                let sheet = sheet.value(cx);
+                let (row, col) = CellRange::cell_from_js_object(cx, cell)?;
+                let value: Handle<JsValue> = cell.get(cx, "value")?;
+                let value = TemplateValue::from_js_value(cx, value)?;
+                cells.push(CellUpdate {
+                    sheet,
+                    row,
+                    col,
+                    value,
+                });
+            }
+        }
+
+        let mut tables = vec![];
+        let js_tables: Option<Handle<JsArray>> = obj.get_opt(cx, "tables")?;
+        if let Some(js_tables) = js_tables {
+            let js_tables: Vec<Handle<JsValue>> = js_tables.to_vec(cx)?;
+            for table in js_tables {
+                let table = table.downcast_or_throw::<JsObject, _>(cx)?;
+                let name: Handle<JsString> = table.get(cx, "name")?;
+                let name = name.value(cx);
+                let js_rows: Handle<JsArray> = table.get(cx, "data")?;
+                let js_rows: Vec<Handle<JsValue>> = js_rows.to_vec(cx)?;
+                let mut rows = vec![];
+                for row in js_rows {
+                    rows.push(table_row_from_js_value(cx, row)?);
+                }
+                tables.push(TableUpdate { name, rows });
+            }
+        }
+
+        let mut defined_names = vec![];
+        let js_names: Option<Handle<JsArray>> = obj.get_opt(cx, "definedNames")?;
+        if let Some(js_names) = js_names {
+            let js_names: Vec<Handle<JsValue>> = js_names.to_vec(cx)?;
+            for defined_name in js_names {
+                let defined_name = defined_name.downcast_or_throw::<JsObject, _>(cx)?;
+                let name: Handle<JsString> = defined_name.get(cx, "name")?;
+                let name = name.value(cx);
+                let value: Handle<JsValue> = defined_name.get(cx, "value")?;
+                let value = TemplateValue::from_js_value(cx, value)?;
+                defined_names.push((name, value));
+            }
+        }
+
+        let data: Option<Handle<JsObject>> = obj.get_opt(cx, "data")?;
+        let data = match data {
+            Some(data) => flatten_data(cx, data)?,
+            None => TemplateData::new(),
+        };
+
+        Ok(Self {
+            source,
+            cells,
+            tables,
+            defined_names,
+            data,
+        })
+    }
+
+    pub fn save_to_buffer(self) -> Result<Vec<u8>, NodeXlsxError> {
+        let buffer = match &self.source {
+            TemplateSource::Buffer(buffer) => buffer.clone(),
+            TemplateSource::Path(path) => std::fs::read(path)?,
+        };
+        self.fill(buffer)
+    }
+
+    pub fn save_to_file(self, path: &str) -> Result<(), NodeXlsxError> {
+        let buffer = self.save_to_buffer()?;
+        std::fs::write(path, buffer)?;
+        Ok(())
+    }
+
+    /// Writes the values into the package. Placeholders are replaced first,
+    /// then tables, cells and defined names are written in that order, so
+    /// later values win over earlier ones in the same cell.
+    fn fill(&self, buffer: Vec<u8>) -> Result<Vec<u8>, NodeXlsxError> {
+        let parts = read_parts(&buffer)?;
+        let mut package = TemplatePackage::new(&parts)?;
+
+        if !self.data.is_empty() {
+            package.replace_placeholders(&self.data)?;
+        }
+        for table in &self.tables {
+            package.write_table(table)?;
+        }
+        for cell in &self.cells {
+            let part = package.sheet_part(&cell.sheet)?.to_string();
+            let date1904 = package.date1904;
+            package
+                .sheet_data(&part)?
+                .set(cell.row, cell.col, &cell.value, date1904);
+        }
+        for (name, value) in &self.defined_names {
+            let (part, row, col) = package.defined_name_cell(name)?;
+            let date1904 = package.date1904;
+            package.sheet_data(&part)?.set(row, col, value, date1904);
+        }
+
+        let mut edits = package.finish()?;
+        rewrite_parts(buffer, |name, _| edits.remove(name))
+    }
+}
+
+fn table_row_from_js_value(cx: &mut FunctionContext, row: Handle<JsValue>) -> NeonResult<TableRow> {
+    if let Ok(values) = row.downcast::<JsArray, _>(cx) {
+        let values: Vec<Handle<JsValue>> = values.to_vec(cx)?;
+        let mut row = vec![];
+        for value in values {
+            row.push(TemplateValue::from_js_value(cx, value)?);
+        }
+        return Ok(TableRow::Values(row));
+    }
+    let record = row.downcast_or_throw::<JsObject, _>(cx)?;
+    let keys: Vec<Handle<JsValue>> = record.get_own_property_names(cx)?.to_vec(cx)?;
+    let mut row = vec![];
+    for key in keys {
+        let key = key.to_string(cx)?.value(cx);
+        let value: Handle<JsValue> = record.get(cx, key.as_str())?;
+        let value = TemplateValue::from_js_value(cx, value)?;
+        row.push((key, value));
+    }
+    Ok(TableRow::Record(row))
+}
+
+/// A worksheet of the template.
+struct TemplateSheet {
+    name: String,
+    id: String,
+    part: String,
+}
+
+/// A table of the template, as its part describes it.
+struct TemplateTable {
+    part: String,
+    sheet_part: String,
+    range: CellRange,
+    header_rows: u32,
+    totals_rows: u32,
+    /// The header of each column, with the formula of calculated columns
+    columns: Vec<(String, Option<String>)>,
+}
+
+/// The parts of the template package while values are written into it.
+struct TemplatePackage<'a> {
+    parts: &'a HashMap<String, String>,
+    workbook_part: String,
+    workbook: XmlElement,
+    sheets: Vec<TemplateSheet>,
+    shared_strings: Option<String>,
+    date1904: bool,
+    /// The sheet data of the worksheets read so far, by part
+    sheet_data: HashMap<String, (SheetData, std::ops::Range<usize>)>,
+    /// The new text of the parts changed outside of the sheet data
+    edits: HashMap<String, String>,
+}
+
+impl<'a> TemplatePackage<'a> {
+    fn new(parts: &'a HashMap<String, String>) -> Result<Self, NodeXlsxError> {
+        let workbook_part = workbook_part(parts)?;
+        let workbook = parts
+            .get(&workbook_part)
+            .ok_or_else(|| NodeXlsxError::new("The package has no workbook".to_string()))?;
+        let workbook = XmlElement::parse(workbook)?;
+        let date1904 = workbook
+            .child("workbookPr")
+            .is_some_and(|properties| properties.flag("date1904", false));
+
+        let relationships = relationships(parts, &workbook_part)?;
+        let shared_strings = relationships
+            .values()
+            .find(|relationship| relationship.kind == "sharedStrings")
+            .map(|relationship| relationship.target.clone());
+        let sheets = workbook
+            .child("sheets")
+            .map(|sheets| sheets.children_named("sheet").collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|sheet| {
+                let relationship = relationships.get(sheet.attribute("id")?)?;
+                // Chart sheets have no cells
+                (relationship.kind == "worksheet").then(|| TemplateSheet {
+                    name: sheet.attribute("name").unwrap_or_default().to_string(),
+                    id: sheet.attribute("sheetId").unwrap_or_default().to_string(),
+                    part: relationship.target.clone(),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            parts,
+            workbook_part,
+            workbook,
+            sheets,
+            shared_strings,
+            date1904,
+            sheet_data: HashMap::new(),
+            edits: HashMap::new(),
+        })
+    }
+
+    /// The part of a worksheet, found by its name without case like Excel does.
+    fn sheet_part(&self, name: &str) -> Result<&str, NodeXlsxError> {
+        self.sheets
+            .iter()
+            .find(|sheet| sheet.name.eq_ignore_ascii_case(name))
+            .map(|sheet| sheet.part.as_str())
+            .ok_or_else(|| NodeXlsxError::new(format!("The template has no sheet \"{}\"", name)))
+    }
+
+    fn sheet_data(&mut self, part: &str) -> Result<&mut SheetData, NodeXlsxError> {
+        if !self.sheet_data.contains_key(part) {
+            let data = self
+                .parts
+                .get(part)
+                .and_then(|xml| SheetData::parse(xml))
+                .ok_or_else(|| NodeXlsxError::new(format!("Invalid worksheet part {}", part)))?;
+            self.sheet_data.insert(part.to_string(), data);
+        }
+        Ok(&mut self.sheet_data.get_mut(part).unwrap().0)
+    }
+
+    /// Replaces the placeholders of the shared strings and of the inline
+    /// strings of every sheet. A cell that is only a placeholder for a
+    /// value other than a string takes the value with its type.
+    fn replace_placeholders(&mut self, data: &TemplateData) -> Result<(), NodeXlsxError> {
+        let mut strings = vec![];
+        if let Some(part) = &self.shared_strings {
+            if let Some(xml) = self.parts.get(part) {
+                let root = XmlElement::parse(xml)?;
+                strings = root.children_named("si").map(rich_text).collect();
+                if let Some(xml) = replace_in_text_elements(xml, data) {
+                    self.edits.insert(part.clone(), xml);
+                }
+            }
+        }
+
+        let date1904 = self.date1904;
+        let parts: Vec<String> = self.sheets.iter().map(|sheet| sheet.part.clone()).collect();
+        for part in parts {
+            let sheet_data = self.sheet_data(&part)?;
+            for (row, col) in sheet_data.positions() {
+                let Some(cell) = sheet_data.cell(row, col) else {
+                    continue;
+                };
+                let typed = cell
+                    .shared_string()
+                    .and_then(|index| strings.get(index))
+                    .and_then(|text| whole_placeholder(text, data))
+                    .filter(|value| !matches!(value, TemplateValue::String(_)));
+                if let Some(value) = typed {
+                    sheet_data.set(row, col, value, date1904);
+                    continue;
+                }
+                if cell.attribute("t") != Some("inlineStr") {
+                    continue;
+                }
+                if let Some(body) = replace_in_text_elements(&cell.body, data) {
+                    if let Some(cell) = sheet_data.cell_mut(row, col) {
+                        cell.body = body;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds a table of any sheet by its name, without case.
+    fn table(&self, name: &str) -> Result<TemplateTable, NodeXlsxError> {
+        for sheet in &self.sheets {
+            for relationship in relationships(self.parts, &sheet.part)?.into_values() {
+                if relationship.kind != "table" {
+                    continue;
+                }
+                let Some(xml) = self.parts.get(&relationship.target) else {
+                    continue;
+                };
+                let root = XmlElement::parse(xml)?;
+                let matches = [root.attribute("name"), root.attribute("displayName")]
+                    .into_iter()
+                    .flatten()
+                    .any(|table_name| table_name.eq_ignore_ascii_case(name));
+                if !matches {
+                    continue;
+                }
+                let range = root
+                    .attribute("ref")
+                    .map(CellRange::parse)
+                    .and_then(Result::ok)
+                    .ok_or_else(|| NodeXlsxError::new(format!("Invalid table \"{}\"", name)))?;
+                let columns = root
+                    .child("tableColumns")
+                    .map(|columns| columns.children_named("tableColumn").collect::<Vec<_>>())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|column| {
+                        let header = column.attribute("name").unwrap_or_default().to_string();
+                        let formula = column
+                            .child("calculatedColumnFormula")
+                            .map(|formula| formula.text.clone());
+                        (header, formula)
+                    })
+                    .collect();
+                return Ok(TemplateTable {
+                    part: relationship.target,
+                    sheet_part: sheet.part.clone(),
+                    range,
+                    header_rows: root.number("headerRowCount").unwrap_or(1.0) as u32,
+                    totals_rows: root.number("totalsRowCount").unwrap_or(0.0) as u32,
+                    columns,
+                });
+            }
+        }
+        Err(NodeXlsxError::new(format!(
+            "The template has no table \"{}\"",
+            name
+        )))
+    }
+
+    /// Writes the rows of a table below its header and resizes the table to
+    /// them. Rows the table no longer has are cleared, and the total row
+    /// moves below the last row. Columns without a value take the formula
+    /// of the column when it is calculated, and new rows take the styles of
+    /// the first row.
+    fn write_table(&mut self, update: &TableUpdate) -> Result<(), NodeXlsxError> {
+        let table = self.table(&update.name)?;
+        let date1904 = self.date1904;
+        let range = table.range;
+        let first_row = range.start_row + table.header_rows;
+        let old_last_row = range.end_row - table.totals_rows;
+        let last_row = first_row + update.rows.len().max(1) as u32 - 1;
+        let new_end_row = last_row + table.totals_rows;
+        if new_end_row >= super::cell_range::ROW_COUNT {
+            return Err(NodeXlsxError::new(format!(
+                "The table \"{}\" doesn't fit in the sheet",
+                update.name
+            )));
+        }
+        let columns = range.start_col as u16..=range.end_col as u16;
+
+        let sheet_data = self.sheet_data(&table.sheet_part)?;
+        let totals: Vec<_> = (range.end_row - table.totals_rows + 1..=range.end_row)
+            .flat_map(|row| columns.clone().map(move |col| (row, col)))
+            .filter_map(|(row, col)| Some((row, col, sheet_data.take(row, col)?)))
+            .collect();
+
+        for (index, row) in (first_row..=last_row).enumerate() {
+            for (col, (header, formula)) in columns.clone().zip(&table.columns) {
+                if row > old_last_row {
+                    sheet_data.copy_style((first_row, col), (row, col));
+                }
+                let value = match update.rows.get(index) {
+                    Some(TableRow::Values(values)) => values.get((col - *columns.start()) as usize),
+                    Some(TableRow::Record(record)) => record
+                        .iter()
+                        .find(|(key, _)| key.eq_ignore_ascii_case(header))
+                        .map(|(_, value)| value),
+                    None => None,
+                };
+                let calculated = formula.as_ref().map(|formula| TemplateValue::Formula {
+                    formula: formula.clone(),
+                    result: None,
+                });
+                match value.or(calculated.as_ref()) {
+                    Some(value) => sheet_data.set(row, col, value, date1904),
+                    None if sheet_data.cell(row, col).is_some() => {
+                        sheet_data.set(row, col, &TemplateValue::Blank, date1904)
+                    }
+                    None => {}
+                }
+            }
+        }
+        for row in last_row + 1..=old_last_row {
+            for col in columns.clone() {
+                sheet_data.take(row, col);
+            }
+        }
+        for (row, col, cell) in totals {
+            sheet_data.insert(row - range.end_row + new_end_row, col, cell);
+        }
+
+        let table_ref = cell_range_to_a1(range.start_row, new_end_row, &range);
+        let filter_ref = cell_range_to_a1(range.start_row, last_row, &range);
+        let xml = match self.edits.get(&table.part) {
+            Some(xml) => xml.clone(),
+            None => self.parts[&table.part].clone(),
+        };
+        let mut xml = set_attribute(&xml, "table", "ref", &table_ref).unwrap_or(xml);
+        if table.header_rows > 0 {
+            xml = set_attribute(&xml, "autoFilter", "ref", &filter_ref).unwrap_or(xml);
+        }
+        self.edits.insert(table.part, xml);
+        Ok(())
+    }
+
+    /// The sheet part and the top left cell of the range a defined name
+    /// refers to. Names of the workbook are preferred over names of a sheet.
+    fn defined_name_cell(&self, name: &str) -> Result<(String, u32, u16), NodeXlsxError> {
+        let mut defined_names: Vec<&XmlElement> = self
+            .workbook
+            .child("definedNames")
+            .map(|names| names.children_named("definedName").collect())
+            .unwrap_or_default();
+        defined_names.retain(|defined| {
+            defined
+                .attribute("name")
+                .is_some_and(|defined| defined.eq_ignore_ascii_case(name))
+        });
+        defined_names.sort_by_key(|defined| defined.attribute("localSheetId").is_some());
+        let defined = defined_names.first().ok_or_else(|| {
+            NodeXlsxError::new(format!("The template has no defined name \"{}\"", name))
+        })?;
+
+        let invalid = || {
+            NodeXlsxError::new(format!(
+                "The defined name \"{}\" doesn't refer to a cell",
+                name
+            ))
+        };
+        let reference = defined.text.trim().trim_start_matches('=');
+        let (sheet, cells) = reference.rsplit_once('!').ok_or_else(invalid)?;
+        let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+            Some(quoted) => quoted.replace("''", "'"),
+            None => sheet.to_string(),
+        };
+        let first = cells.split(':').next().unwrap_or(cells);
+        let (row, col) = CellRange::parse_cell(first).map_err(|_| invalid())?;
+        Ok((self.sheet_part(&sheet)?.to_string(), row, col))
+    }
+
+    /// The new text of every changed part. Excel is asked to recalculate
+    /// the workbook when it is opened, and formulas that were overwritten
+    /// are taken out of the calculation chain.
+    fn finish(mut self) -> Result<HashMap<String, String>, NodeXlsxError> {
+        let mut formulas_left: HashMap<String, HashSet<String>> = HashMap::new();
+        for sheet in &self.sheets {
+            let Some((data, range)) = self.sheet_data.get(&sheet.part) else {
+                continue;
+            };
+            if !data.changed() {
+                continue;
+            }
+            let xml = &self.parts[&sheet.part];
+            let mut xml = format!(
+                "{}{}{}",
+                &xml[..range.start],
+                data.write(),
+                &xml[range.end..]
+            );
+            if let Some(dimension) = data.dimension() {
+                xml = set_attribute(&xml, "dimension", "ref", &dimension).unwrap_or(xml);
+            }
+            self.edits.insert(sheet.part.clone(), xml);
+
+            let formulas = data
+                .positions()
+                .into_iter()
+                .filter(|(row, col)| {
+                    data.cell(*row, *col)
+                        .is_some_and(|cell| cell.body.contains("<f"))
+                })
+                .map(|(row, col)| row_col_to_cell(row, col))
+                .collect();
+            formulas_left.insert(sheet.id.clone(), formulas);
+        }
+        if formulas_left.is_empty() {
+            return Ok(self.edits);
+        }
+
+        let workbook = &self.parts[&self.workbook_part];
+        let workbook = match find_element(workbook, "calcPr") {
+            Some(_) => set_attribute(workbook, "calcPr", "fullCalcOnLoad", "1"),
+            None => ["</definedNames>", "</sheets>"]
+                .into_iter()
+                .find_map(|after| {
+                    let end = workbook.find(after)? + after.len();
+                    Some(format!(
+                        "{}<calcPr fullCalcOnLoad=\"1\"/>{}",
+                        &workbook[..end],
+                        &workbook[end..]
+                    ))
+                }),
+        };
+        if let Some(workbook) = workbook {
+            self.edits.insert(self.workbook_part.clone(), workbook);
+        }
+
+        let calc_chain = relationships(self.parts, &self.workbook_part)?
+            .into_values()
+            .find(|relationship| relationship.kind == "calcChain")
+            .and_then(|relationship| {
+                let xml = self.parts.get(&relationship.target)?;
+                let xml = remove_from_calc_chain(xml, &formulas_left)?;
+                Some((relationship.target, xml))
+            });
+        if let Some((part, xml)) = calc_chain {
+            self.edits.insert(part, xml);
+        }
+        Ok(self.edits)
+    }
+}
+
+fn cell_range_to_a1(first_row: u32, last_row: u32, range: &CellRange) -> String {
+    format!(
+        "{}:{}",
+        row_col_to_cell(first_row, range.start_col as u16),
+        row_col_to_cell(last_row, range.end_col as u16)
+    )
+}
+
+/// Sets an attribute of the first `element` of `xml`, None when there is
+/// no such element.
+fn set_attribute(xml: &str, element: &str, name: &str, value: &str) -> Option<String> {
+    let start = find_element(xml, element)?;
+    let open_end = xml[start..].find('>')? + start;
+    let tag = &xml[start + 1 + element.len()..open_end];
+    let (tag, closing) = match tag.strip_suffix('/') {
+        Some(tag) => (tag, "/"),
+        None => (tag, ""),
+    };
+    let mut attributes = raw_attributes(tag);
+    match attributes.iter_mut().find(|(key, _)| key == name) {
+        Some((_, old)) => *old = value.to_string(),
+        None => attributes.push((name.to_string(), value.to_string())),
+    }
+    let mut open = format!("<{}", element);
+    write_attributes(&attributes, &mut open);
+    Some(format!(
+        "{}{}{}{}",
+        &xml[..start],
+        open,
+        closing,
+        &xml[open_end..]
+    ))
+}
+
+/// Takes the cells which no longer have a formula out of the calculation
+/// chain, where Excel expects only formulas. Each cell gets the sheet id
+/// it would otherwise take from the cell before it. None when no cell is
+/// taken out, or when none would be left, which the chain doesn't allow.
+fn remove_from_calc_chain(
+    xml: &str,
+    formulas: &HashMap<String, HashSet<String>>,
+) -> Option<String> {
+    let start = find_element(xml, "c")?;
+    let end = xml.rfind("</calcChain>")?;
+    let mut kept = String::new();
+    let mut removed = false;
+    let mut sheet_id = String::new();
+    let mut rest = &xml[start..end];
+    while let Some(cell_start) = find_element(rest, "c") {
+        rest = &rest[cell_start..];
+        let cell_end = rest.find("/>")?;
+        let mut attributes = raw_attributes(&rest[2..cell_end]);
+        rest = &rest[cell_end + 2..];
+        if let Some((_, id)) = attributes.iter().find(|(key, _)| key == "i") {
+            sheet_id = id.clone();
+        } else {
+            attributes.push(("i".to_string(), sheet_id.clone()));
+        }
+        let reference = attributes
+            .iter()
+            .find(|(key, _)| key == "r")
+            .map(|(_, reference)| reference.as_str())
+            .unwrap_or_default();
+        let is_formula = match formulas.get(&sheet_id) {
+            Some(formulas) => formulas.contains(reference),
+            None => true,
+        };
+        if !is_formula {
+            removed = true;
+            continue;
+        }
+        kept.push_str("<c");
+        write_attributes(&attributes, &mut kept);
+        kept.push_str("/>");
+    }
+    if !removed || kept.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}{}", &xml[..start], kept, &xml[end..]))
+}
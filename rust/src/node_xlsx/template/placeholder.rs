@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use super::{sheet_data::find_element, value::TemplateValue};
+use crate::node_xlsx::xml::{escape, unescape};
+
+/// The data of a template, by the dotted path placeholders name it with.
+pub type TemplateData = HashMap<String, TemplateValue>;
+
+/// Replaces the `{{key}}` placeholders of a text with the data they name.
+/// Placeholders without data are left as they are. None when nothing is
+/// replaced.
+pub fn replace_placeholders(text: &str, data: &TemplateData) -> Option<String> {
+    let mut replaced = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut changed = false;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + length].trim();
+        replaced.push_str(&rest[..start]);
+        match data.get(key).and_then(TemplateValue::to_text) {
+            Some(value) => {
+                replaced.push_str(&value);
+                changed = true;
+            }
+            None => replaced.push_str(&rest[start..start + length + 2]),
+        }
+        rest = &rest[start + length + 2..];
+    }
+    replaced.push_str(rest);
+    changed.then_some(replaced)
+}
+
+/// The data a text names when it is nothing but one placeholder, so the
+/// cell can take the value with its type.
+pub fn whole_placeholder<'a>(text: &str, data: &'a TemplateData) -> Option<&'a TemplateValue> {
+    let key = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    if key.contains("{{") || key.contains("}}") {
+        return None;
+    }
+    data.get(key.trim())
+}
+
+/// Replaces the placeholders of the `<t>` elements of shared strings or of
+/// an inline string. A placeholder split over runs of rich text isn't found.
+pub fn replace_in_text_elements(xml: &str, data: &TemplateData) -> Option<String> {
+    let mut replaced = String::with_capacity(xml.len());
+    let mut rest = xml;
+    let mut changed = false;
+    while let Some(start) = find_element(rest, "t") {
+        let Some(open_end) = rest[start..].find('>').map(|end| end + start + 1) else {
+            break;
+        };
+        replaced.push_str(&rest[..open_end]);
+        rest = &rest[open_end..];
+        // An empty `<t/>` has no text
+        if replaced.ends_with("/>") {
+            continue;
+        }
+        let Some(end) = rest.find("</t>") else {
+            break;
+        };
+        match replace_placeholders(&unescape(&rest[..end]), data) {
+            Some(text) => {
+                replaced.push_str(&escape(&text));
+                changed = true;
+            }
+            None => replaced.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    replaced.push_str(rest);
+    changed.then_some(replaced)
+}
@@ -0,0 +1,308 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use rust_xlsxwriter::utility::row_col_to_cell;
+
+use super::value::TemplateValue;
+use crate::node_xlsx::cell_range::CellRange;
+
+type Attributes = Vec<(String, String)>;
+
+/// The `<sheetData>` of a worksheet part, row by row. Rows and cells keep
+/// their attributes and content as written, so only the ones that change
+/// are written differently.
+pub struct SheetData {
+    rows: BTreeMap<u32, Row>,
+    changed: bool,
+}
+
+#[derive(Default)]
+struct Row {
+    /// The attributes but `r`
+    attributes: Attributes,
+    cells: BTreeMap<u16, Cell>,
+}
+
+/// A `<c>` element.
+#[derive(Clone, Default)]
+pub struct Cell {
+    /// The attributes but `r`, their values still escaped
+    attributes: Attributes,
+    /// The content of the element, like `<v>1</v>`
+    pub body: String,
+}
+
+impl Cell {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn set_attribute(&mut self, name: &str, value: Option<&str>) {
+        self.attributes.retain(|(key, _)| key != name);
+        if let Some(value) = value {
+            self.attributes.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    /// Replaces the value, keeping the style of the cell.
+    pub fn set_value(&mut self, value: &TemplateValue, date1904: bool) {
+        let (cell_type, body) = value.cell_type_and_body(date1904);
+        let cell_type = cell_type
+            .strip_prefix(" t=\"")
+            .and_then(|cell_type| cell_type.strip_suffix('"'));
+        // Metadata of dynamic arrays and rich values belongs to the old value
+        self.set_attribute("cm", None);
+        self.set_attribute("vm", None);
+        self.set_attribute("t", cell_type);
+        self.body = body;
+    }
+
+    /// The index of the shared string of the cell, if it has one.
+    pub fn shared_string(&self) -> Option<usize> {
+        if self.attribute("t") != Some("s") {
+            return None;
+        }
+        let start = self.body.find("<v>")? + "<v>".len();
+        let end = self.body[start..].find("</v>")? + start;
+        self.body[start..end].trim().parse().ok()
+    }
+
+    fn write(&self, row: u32, col: u16, xml: &mut String) {
+        xml.push_str(&format!("<c r=\"{}\"", row_col_to_cell(row, col)));
+        write_attributes(&self.attributes, xml);
+        match self.body.is_empty() {
+            true => xml.push_str("/>"),
+            false => xml.push_str(&format!(">{}</c>", self.body)),
+        }
+    }
+}
+
+impl SheetData {
+    /// Reads the `<sheetData>` of a worksheet part, with where it is in the
+    /// part. None when the part has no sheet data or it can't be read.
+    pub fn parse(xml: &str) -> Option<(Self, Range<usize>)> {
+        let start = find_element(xml, "sheetData")?;
+        let open_end = xml[start..].find('>')? + start;
+        let (content, end) = match xml[..open_end].ends_with('/') {
+            true => ("", open_end + 1),
+            false => {
+                let close = xml[open_end..].find("</sheetData>")? + open_end;
+                (&xml[open_end + 1..close], close + "</sheetData>".len())
+            }
+        };
+
+        let mut rows = BTreeMap::new();
+        let mut rest = content;
+        let mut next_row = 0;
+        while let Some(row_start) = find_element(rest, "row") {
+            rest = &rest[row_start..];
+            let (attributes, content, row_end) = split_element(rest, "row")?;
+            let mut attributes = raw_attributes(attributes);
+            let index = take_attribute(&mut attributes, "r")
+                .and_then(|r| r.parse::<u32>().ok())
+                .and_then(|r| r.checked_sub(1))
+                .unwrap_or(next_row);
+            next_row = index + 1;
+
+            let mut cells = BTreeMap::new();
+            let mut cell_rest = content;
+            let mut next_col = 0;
+            while let Some(cell_start) = find_element(cell_rest, "c") {
+                cell_rest = &cell_rest[cell_start..];
+                let (cell_attributes, body, cell_end) = split_element(cell_rest, "c")?;
+                let mut cell_attributes = raw_attributes(cell_attributes);
+                let col = take_attribute(&mut cell_attributes, "r")
+                    .and_then(|r| CellRange::parse_cell(&r).ok())
+                    .map(|(_, col)| col)
+                    .unwrap_or(next_col);
+                next_col = col + 1;
+                cells.insert(
+                    col,
+                    Cell {
+                        attributes: cell_attributes,
+                        body: body.to_string(),
+                    },
+                );
+                cell_rest = &cell_rest[cell_end..];
+            }
+            rows.insert(index, Row { attributes, cells });
+            rest = &rest[row_end..];
+        }
+
+        let data = Self {
+            rows,
+            changed: false,
+        };
+        Some((data, start..end))
+    }
+
+    /// Whether any cell changed since the sheet data was read.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    pub fn cell(&self, row: u32, col: u16) -> Option<&Cell> {
+        self.rows.get(&row)?.cells.get(&col)
+    }
+
+    /// A cell to be changed.
+    pub fn cell_mut(&mut self, row: u32, col: u16) -> Option<&mut Cell> {
+        let cell = self.rows.get_mut(&row)?.cells.get_mut(&col)?;
+        self.changed = true;
+        Some(cell)
+    }
+
+    /// The positions of the cells, row by row.
+    pub fn positions(&self) -> Vec<(u32, u16)> {
+        self.rows
+            .iter()
+            .flat_map(|(row, data)| data.cells.keys().map(move |col| (*row, *col)))
+            .collect()
+    }
+
+    /// Writes a value into a cell, which keeps its style when it exists.
+    pub fn set(&mut self, row: u32, col: u16, value: &TemplateValue, date1904: bool) {
+        self.cell_or_insert(row, col).set_value(value, date1904);
+    }
+
+    /// Gives a cell the style of another one, unless it has its own.
+    pub fn copy_style(&mut self, from: (u32, u16), to: (u32, u16)) {
+        let Some(style) = self
+            .cell(from.0, from.1)
+            .and_then(|cell| cell.attribute("s"))
+            .map(str::to_string)
+        else {
+            return;
+        };
+        let cell = self.cell_or_insert(to.0, to.1);
+        if cell.attribute("s").is_none() {
+            cell.set_attribute("s", Some(&style));
+        }
+    }
+
+    /// Takes a cell out of the sheet, to be put somewhere else.
+    pub fn take(&mut self, row: u32, col: u16) -> Option<Cell> {
+        let cell = self.rows.get_mut(&row)?.cells.remove(&col)?;
+        self.changed = true;
+        Some(cell)
+    }
+
+    pub fn insert(&mut self, row: u32, col: u16, cell: Cell) {
+        *self.cell_or_insert(row, col) = cell;
+    }
+
+    fn cell_or_insert(&mut self, row: u32, col: u16) -> &mut Cell {
+        self.changed = true;
+        let row = self.rows.entry(row).or_default();
+        if !row.cells.contains_key(&col) {
+            // The columns the row spans are only a hint, and would be stale
+            take_attribute(&mut row.attributes, "spans");
+        }
+        row.cells.entry(col).or_default()
+    }
+
+    /// The range of the cells, like `A1:D10`, for the `<dimension>` of the sheet.
+    pub fn dimension(&self) -> Option<String> {
+        let first_row = *self
+            .rows
+            .keys()
+            .find(|row| !self.rows[row].cells.is_empty())?;
+        let last_row = *self
+            .rows
+            .keys()
+            .rfind(|row| !self.rows[row].cells.is_empty())?;
+        let columns = self.rows.values().flat_map(|row| row.cells.keys());
+        let first_col = *columns.clone().min()?;
+        let last_col = *columns.max()?;
+        let first = row_col_to_cell(first_row, first_col);
+        Some(match (first_row, first_col) == (last_row, last_col) {
+            true => first,
+            false => format!("{}:{}", first, row_col_to_cell(last_row, last_col)),
+        })
+    }
+
+    /// The sheet data as a `<sheetData>` element.
+    pub fn write(&self) -> String {
+        let mut xml = String::from("<sheetData>");
+        for (index, row) in &self.rows {
+            xml.push_str(&format!("<row r=\"{}\"", index + 1));
+            write_attributes(&row.attributes, &mut xml);
+            if row.cells.is_empty() {
+                xml.push_str("/>");
+                continue;
+            }
+            xml.push('>');
+            for (col, cell) in &row.cells {
+                cell.write(*index, *col, &mut xml);
+            }
+            xml.push_str("</row>");
+        }
+        xml.push_str("</sheetData>");
+        xml
+    }
+}
+
+/// Where the first `<name>` element of `xml` starts, not counting elements
+/// whose name only starts with `name`.
+pub fn find_element(xml: &str, name: &str) -> Option<usize> {
+    let tag = format!("<{}", name);
+    let mut offset = 0;
+    while let Some(start) = xml[offset..].find(&tag) {
+        let start = start + offset;
+        let next = xml[start + tag.len()..].chars().next();
+        if matches!(next, Some('>' | '/') | Some(' ' | '\t' | '\r' | '\n')) {
+            return Some(start);
+        }
+        offset = start + tag.len();
+    }
+    None
+}
+
+/// Splits the element `xml` starts with into its attributes, its content,
+/// and where it ends.
+fn split_element<'a>(xml: &'a str, name: &str) -> Option<(&'a str, &'a str, usize)> {
+    let open_end = xml.find('>')?;
+    let attributes = &xml[1 + name.len()..open_end];
+    if let Some(attributes) = attributes.strip_suffix('/') {
+        return Some((attributes, "", open_end + 1));
+    }
+    let close = format!("</{}>", name);
+    let content_end = xml[open_end..].find(&close)? + open_end;
+    Some((
+        attributes,
+        &xml[open_end + 1..content_end],
+        content_end + close.len(),
+    ))
+}
+
+/// The attributes of an opening tag, their values still escaped.
+pub fn raw_attributes(text: &str) -> Attributes {
+    let mut attributes = vec![];
+    let mut rest = text;
+    while let Some(equals) = rest.find('=') {
+        let name = rest[..equals].trim().to_string();
+        let value = rest[equals + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(end) = value[1..].find(quote) else {
+            break;
+        };
+        attributes.push((name, value[1..end + 1].to_string()));
+        rest = &value[end + 2..];
+    }
+    attributes
+}
+
+fn take_attribute(attributes: &mut Attributes, name: &str) -> Option<String> {
+    let index = attributes.iter().position(|(key, _)| key == name)?;
+    Some(attributes.remove(index).1)
+}
+
+pub fn write_attributes(attributes: &Attributes, xml: &mut String) {
+    for (name, value) in attributes {
+        xml.push_str(&format!(" {}=\"{}\"", name, value.replace('"', "&quot;")));
+    }
+}
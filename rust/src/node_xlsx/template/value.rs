@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Timelike};
+use neon::{
+    context::{Context, FunctionContext},
+    handle::Handle,
+    object::Object,
+    result::NeonResult,
+    types::{
+        JsArray, JsBoolean, JsDate, JsNull, JsNumber, JsObject, JsString, JsUndefined, JsValue,
+        Value,
+    },
+};
+
+use crate::node_xlsx::{
+    formula::{result::FormulaResult, value::date_to_serial},
+    util::js_date_to_naive_date_time,
+    xml::escape,
+};
+
+/// Nested data objects deeper than this are left out, which also stops
+/// objects that refer to themselves.
+const MAX_DEPTH: usize = 32;
+
+/// A value written into a cell of a template.
+#[derive(Debug, Clone)]
+pub enum TemplateValue {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Date(NaiveDateTime),
+    Formula {
+        formula: String,
+        result: Option<FormulaResult>,
+    },
+    Blank,
+}
+
+impl TemplateValue {
+    /// Reads a string, a number, a boolean, a `Date`, a `Formula`, or
+    /// `null` and `undefined` for a blank cell.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        if value.is_a::<JsNull, _>(cx) || value.is_a::<JsUndefined, _>(cx) {
+            return Ok(Self::Blank);
+        }
+        if let Ok(text) = value.downcast::<JsString, _>(cx) {
+            return Ok(Self::String(text.value(cx)));
+        }
+        if let Ok(number) = value.downcast::<JsNumber, _>(cx) {
+            let number = number.value(cx);
+            if !number.is_finite() {
+                return cx.throw_error(format!("Invalid cell value: {}", number));
+            }
+            return Ok(Self::Number(number));
+        }
+        if let Ok(boolean) = value.downcast::<JsBoolean, _>(cx) {
+            return Ok(Self::Boolean(boolean.value(cx)));
+        }
+        if let Ok(date) = value.downcast::<JsDate, _>(cx) {
+            return Ok(Self::Date(js_date_to_naive_date_time(cx, date)?));
+        }
+        let obj = value.downcast_or_throw::<JsObject, _>(cx)?;
+        let formula: Handle<JsString> = obj.get(cx, "formula")?;
+        let formula = formula.value(cx);
+        let result: Option<Handle<JsValue>> = obj.get_opt(cx, "result")?;
+        let result = match result {
+            Some(result) if !result.is_a::<JsNull, _>(cx) => {
+                Some(FormulaResult::from_js_value(cx, result)?)
+            }
+            _ => None,
+        };
+        Ok(Self::Formula {
+            formula: formula.strip_prefix('=').unwrap_or(&formula).to_string(),
+            result,
+        })
+    }
+
+    /// The `t` attribute and the content of the cell. Dates of workbooks
+    /// using the 1904 date system count from 1904.
+    pub fn cell_type_and_body(&self, date1904: bool) -> (&'static str, String) {
+        match self {
+            Self::String(text) => (
+                " t=\"inlineStr\"",
+                format!("<is><t xml:space=\"preserve\">{}</t></is>", escape(text)),
+            ),
+            Self::Number(number) => ("", format!("<v>{}</v>", number)),
+            Self::Boolean(boolean) => (" t=\"b\"", format!("<v>{}</v>", *boolean as u8)),
+            Self::Date(date) => {
+                let serial = match date1904 {
+                    true => date_to_serial(*date) - 1462.0,
+                    false => date_to_serial(*date),
+                };
+                ("", format!("<v>{}</v>", serial))
+            }
+            Self::Formula { formula, result } => {
+                let formula = format!("<f>{}</f>", escape(formula));
+                match result {
+                    Some(result) => {
+                        let (cell_type, value) = result.cell_type_and_value();
+                        (cell_type, format!("{}<v>{}</v>", formula, value))
+                    }
+                    None => ("", formula),
+                }
+            }
+            Self::Blank => ("", String::new()),
+        }
+    }
+
+    /// The value as the text of a placeholder inside a longer string, None
+    /// for formulas, which can't be part of a string.
+    pub fn to_text(&self) -> Option<String> {
+        Some(match self {
+            Self::String(text) => text.clone(),
+            Self::Number(number) => number.to_string(),
+            Self::Boolean(true) => "TRUE".to_string(),
+            Self::Boolean(false) => "FALSE".to_string(),
+            Self::Date(date) if date.num_seconds_from_midnight() == 0 => {
+                date.format("%Y-%m-%d").to_string()
+            }
+            Self::Date(date) => date.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Self::Formula { .. } => return None,
+            Self::Blank => String::new(),
+        })
+    }
+}
+
+/// Flattens a data object into its values by dotted path, like
+/// `customer.name`, with the items of arrays by their index, like
+/// `lines.0.price`.
+pub fn flatten_data(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+) -> NeonResult<HashMap<String, TemplateValue>> {
+    let mut data = HashMap::new();
+    flatten_into(cx, obj, "", 0, &mut data)?;
+    Ok(data)
+}
+
+fn flatten_into(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+    prefix: &str,
+    depth: usize,
+    data: &mut HashMap<String, TemplateValue>,
+) -> NeonResult<()> {
+    if depth > MAX_DEPTH {
+        return Ok(());
+    }
+    let keys: Vec<Handle<JsValue>> = obj.get_own_property_names(cx)?.to_vec(cx)?;
+    for key in keys {
+        let key = key.to_string(cx)?.value(cx);
+        let value: Handle<JsValue> = obj.get(cx, key.as_str())?;
+        let path = match prefix {
+            "" => key,
+            prefix => format!("{}.{}", prefix, key),
+        };
+        match value.downcast::<JsObject, _>(cx) {
+            Ok(nested) if is_container(cx, nested)? => {
+                flatten_into(cx, nested, &path, depth + 1, data)?
+            }
+            _ => {
+                let value = TemplateValue::from_js_value(cx, value)?;
+                data.insert(path, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether an object holds more data, rather than being a date or a formula.
+fn is_container(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<bool> {
+    if obj.is_a::<JsDate, _>(cx) {
+        return Ok(false);
+    }
+    if obj.is_a::<JsArray, _>(cx) {
+        return Ok(true);
+    }
+    let formula: Option<Handle<JsValue>> = obj.get_opt(cx, "formula")?;
+    Ok(!formula.is_some_and(|formula| formula.is_a::<JsString, _>(cx)))
+}
@@ -3,20 +3,28 @@ use neon::{
     context::FunctionContext,
     handle::Handle,
     result::NeonResult,
-    types::{JsString, JsValue},
+    types::{JsBoolean, JsDate, JsNumber, JsString, JsValue},
 };
-use rust_xlsxwriter::{Formula, Url};
+use rust_xlsxwriter::Url;
 
+use super::formula::NodeXlsxFormula;
 use super::util::{
-    any_to_formula, any_to_naive_date_time, any_to_number, any_to_string, any_to_url,
+    any_to_boolean, any_to_formula, any_to_naive_date_time, any_to_number, any_to_string,
+    any_to_url, link_text,
 };
 pub enum NodeXlsxTypes {
     String(String),
     Number(f64),
-    Link(Url),
+    Boolean(bool),
+    /// A link, with its address and the text shown for it
+    Link {
+        url: Url,
+        href: String,
+        text: String,
+    },
     Date(NaiveDateTime),
     Unknown(String), // This is a catch-all for any type
-    Formula((Formula, bool)),
+    Formula(NodeXlsxFormula),
 }
 
 impl NodeXlsxTypes {
@@ -34,7 +42,15 @@ impl NodeXlsxTypes {
         };
 
         let js_string = js_string.value(cx);
-        Ok(match js_string.to_lowercase().as_str() {
+        Self::from_type_name(cx, &js_string, js_any)
+    }
+
+    pub fn from_type_name<'a>(
+        cx: &mut FunctionContext<'a>,
+        type_name: &str,
+        js_any: Handle<JsValue>,
+    ) -> NeonResult<Self> {
+        Ok(match type_name.to_lowercase().as_str() {
             "string" => {
                 let js_any = any_to_string(cx, js_any)?;
                 NodeXlsxTypes::String(js_any)
@@ -43,9 +59,14 @@ impl NodeXlsxTypes {
                 let js_any = any_to_number(cx, js_any)?;
                 NodeXlsxTypes::Number(js_any)
             }
+            "boolean" => {
+                let js_any = any_to_boolean(cx, js_any)?;
+                NodeXlsxTypes::Boolean(js_any)
+            }
             "link" => {
-                let js_any = any_to_url(cx, js_any)?;
-                NodeXlsxTypes::Link(js_any)
+                let url = any_to_url(cx, js_any)?;
+                let (href, text) = link_text(cx, js_any)?;
+                NodeXlsxTypes::Link { url, href, text }
             }
             "date" => {
                 let js_any = any_to_naive_date_time(cx, js_any)?;
@@ -61,4 +82,26 @@ impl NodeXlsxTypes {
             }
         })
     }
+
+    /// Picks the cell type from the JS type of the value itself
+    pub fn from_js_value<'a>(
+        cx: &mut FunctionContext<'a>,
+        js_any: Handle<JsValue>,
+    ) -> NeonResult<Self> {
+        if let Ok(number) = js_any.downcast::<JsNumber, _>(cx) {
+            return Ok(NodeXlsxTypes::Number(number.value(cx)));
+        }
+        if let Ok(string) = js_any.downcast::<JsString, _>(cx) {
+            return Ok(NodeXlsxTypes::String(string.value(cx)));
+        }
+        if let Ok(boolean) = js_any.downcast::<JsBoolean, _>(cx) {
+            return Ok(NodeXlsxTypes::Boolean(boolean.value(cx)));
+        }
+        if js_any.is_a::<JsDate, _>(cx) {
+            let date = any_to_naive_date_time(cx, js_any)?;
+            return Ok(NodeXlsxTypes::Date(date));
+        }
+        let js_any = any_to_string(cx, js_any)?;
+        Ok(NodeXlsxTypes::Unknown(js_any))
+    }
 }
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use chrono::{DateTime, NaiveDateTime};
 use neon::{
     context::{Context, FunctionContext},
@@ -10,7 +8,7 @@ use neon::{
 };
 use rust_xlsxwriter::{Formula, Url};
 
-use super::format::NodeXlsxFormat;
+use super::{format_registry::FormatRegistry, formula::NodeXlsxFormula};
 
 pub fn any_to_string<'a>(
     cx: &mut FunctionContext<'a>,
@@ -50,6 +48,25 @@ pub fn any_to_number<'a>(cx: &mut FunctionContext<'a>, js_any: Handle<JsValue>)
     }
 }
 
+pub fn any_to_boolean<'a>(
+    cx: &mut FunctionContext<'a>,
+    js_any: Handle<JsValue>,
+) -> NeonResult<bool> {
+    if let Ok(boolean) = js_any.downcast::<JsBoolean, _>(cx) {
+        return Ok(boolean.value(cx));
+    }
+    if let Ok(str) = js_any.downcast::<JsString, _>(cx) {
+        match str.value(cx).to_lowercase().as_str() {
+            "true" => return Ok(true),
+            "false" => return Ok(false),
+            _ => {}
+        }
+    }
+    let error = format!("Value cannot be converted to boolean: {:?}", js_any);
+    let js_error = cx.string(error);
+    cx.throw(js_error)
+}
+
 pub fn any_to_url<'a>(cx: &mut FunctionContext<'a>, js_any: Handle<JsValue>) -> NeonResult<Url> {
     if let Ok(str) = js_any.downcast::<JsString, _>(cx) {
         return Ok(Url::new(str.value(cx)));
@@ -65,6 +82,25 @@ pub fn any_to_url<'a>(cx: &mut FunctionContext<'a>, js_any: Handle<JsValue>) ->
     cx.throw(js_error)
 }
 
+/// The address of a link given as a string or a `Link`, and the text shown for it.
+pub fn link_text<'a>(
+    cx: &mut FunctionContext<'a>,
+    js_any: Handle<JsValue>,
+) -> NeonResult<(String, String)> {
+    if let Ok(obj) = js_any.downcast::<JsObject, _>(cx) {
+        let url: Handle<JsString> = obj.get(cx, "url")?;
+        let url = url.value(cx);
+        let text: Option<Handle<JsString>> = obj.get_opt(cx, "text")?;
+        let text = match text {
+            Some(text) => text.value(cx),
+            None => url.clone(),
+        };
+        return Ok((url, text));
+    }
+    let url = any_to_string(cx, js_any)?;
+    Ok((url.clone(), url))
+}
+
 pub fn any_to_naive_date_time<'a>(
     cx: &mut FunctionContext<'a>,
     js_any: Handle<JsValue>,
@@ -90,10 +126,9 @@ pub fn js_date_to_naive_date_time<'a>(
 pub fn any_to_formula<'a>(
     cx: &mut FunctionContext<'a>,
     js_any: Handle<JsValue>,
-) -> NeonResult<(Formula, bool)> {
+) -> NeonResult<NodeXlsxFormula> {
     if let Ok(obj) = js_any.downcast::<JsObject, _>(cx) {
-        let formula = object_to_formula(cx, obj)?;
-        return Ok(formula);
+        return NodeXlsxFormula::from_js_object(cx, obj);
     }
 
     let error = format!("Value cannot be converted to formula: {:?}", js_any);
@@ -105,52 +140,9 @@ pub fn object_to_formula<'a>(
     cx: &mut FunctionContext<'a>,
     obj: Handle<JsObject>,
 ) -> NeonResult<(Formula, bool)> {
-    let formula: Handle<JsString> = obj.get(cx, "formula")?;
-    let formula = formula.value(cx);
-
-    let result: Option<Handle<JsString>> = obj.get_opt(cx, "result")?;
-    let result = match result {
-        Some(result) => Some(result.value(cx)),
-        None => None,
-    };
-
-    let use_future_functions: Option<Handle<JsBoolean>> = obj.get_opt(cx, "useFutureFunctions")?;
-    let use_future_functions = match use_future_functions {
-        Some(use_future_functions) => Some(use_future_functions.value(cx)),
-        None => None,
-    };
-
-    let use_table_functions: Option<Handle<JsBoolean>> = obj.get_opt(cx, "useTableFunctions")?;
-    let use_table_functions = match use_table_functions {
-        Some(use_table_functions) => Some(use_table_functions.value(cx)),
-        None => None,
-    };
-
-    let dynamic: Option<Handle<JsBoolean>> = obj.get_opt(cx, "dynamic")?;
-    let dynamic = match dynamic {
-        Some(dynamic) => dynamic.value(cx),
-        None => false,
-    };
-
-    let mut formula = Formula::new(formula);
-
-    if let Some(result) = result {
-        formula = formula.set_result(result);
-    }
-
-    if let Some(use_future_functions) = use_future_functions {
-        if use_future_functions {
-            formula = formula.use_future_functions()
-        }
-    }
-
-    if let Some(use_table_functions) = use_table_functions {
-        if use_table_functions {
-            formula = formula.use_table_functions()
-        }
-    }
-
-    Ok((formula, dynamic))
+    let formula = NodeXlsxFormula::from_js_object(cx, obj)?;
+    let dynamic = formula.dynamic;
+    Ok((formula.into(), dynamic))
 }
 
 pub fn object_to_url<'a>(cx: &mut FunctionContext<'a>, obj: Handle<JsObject>) -> NeonResult<Url> {
@@ -170,14 +162,8 @@ pub fn object_to_url<'a>(cx: &mut FunctionContext<'a>, obj: Handle<JsObject>) ->
 
 pub fn create_format(
     cx: &mut FunctionContext,
-    obj: Handle<JsObject>,
-    format_map: &mut HashMap<u32, rust_xlsxwriter::Format>,
+    value: Handle<JsValue>,
+    format_map: &mut FormatRegistry,
 ) -> NeonResult<u32> {
-    let id: Handle<JsNumber> = obj.get(cx, "id")?;
-    let id = id.value(cx) as u32;
-    if !format_map.contains_key(&id) {
-        let format = NodeXlsxFormat::from_js_object(cx, obj)?;
-        format_map.insert(id, format.into());
-    }
-    Ok(id)
+    format_map.resolve(cx, value)
 }
@@ -0,0 +1,203 @@
+use super::error::NodeXlsxError;
+
+/// An element of an XML part with its children. Namespace prefixes are
+/// dropped from element names, so `x14:cfRule` is found as `cfRule`.
+#[derive(Default)]
+pub struct XmlElement {
+    pub name: String,
+    attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    /// The text directly inside the element, unescaped
+    pub text: String,
+}
+
+impl XmlElement {
+    /// Parses an XML part into its root element.
+    pub fn parse(xml: &str) -> Result<Self, NodeXlsxError> {
+        let mut stack: Vec<XmlElement> = vec![XmlElement::default()];
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            if start > 0 {
+                if let Some(top) = stack.last_mut() {
+                    top.text.push_str(&unescape(&rest[..start]));
+                }
+            }
+            rest = &rest[start..];
+
+            if let Some(after) = rest.strip_prefix("<![CDATA[") {
+                let (data, after) = after.split_once("]]>").ok_or_else(|| invalid("CDATA"))?;
+                if let Some(top) = stack.last_mut() {
+                    top.text.push_str(data);
+                }
+                rest = after;
+                continue;
+            }
+            if let Some(after) = rest.strip_prefix("<!--") {
+                rest = after.split_once("-->").ok_or_else(|| invalid("comment"))?.1;
+                continue;
+            }
+
+            let end = rest.find('>').ok_or_else(|| invalid("tag"))?;
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('?') || tag.starts_with('!') {
+                continue;
+            }
+
+            if tag.starts_with('/') {
+                let element = stack.pop().ok_or_else(|| invalid("closing tag"))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Err(invalid("closing tag")),
+                }
+                continue;
+            }
+
+            let closed = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = match tag.find(char::is_whitespace) {
+                Some(index) => (&tag[..index], &tag[index..]),
+                None => (tag, ""),
+            };
+            let element = XmlElement {
+                name: local_name(name).to_string(),
+                attributes: parse_attributes(attributes),
+                children: vec![],
+                text: String::new(),
+            };
+            if closed {
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(element);
+                }
+            } else {
+                stack.push(element);
+            }
+        }
+
+        let mut document = stack.pop().ok_or_else(|| invalid("document"))?;
+        if !stack.is_empty() {
+            return Err(invalid("document, an element is not closed"));
+        }
+        document
+            .children
+            .pop()
+            .ok_or_else(|| invalid("document, it has no root element"))
+    }
+
+    /// The value of an attribute, found by its name with or without its
+    /// namespace prefix: `id` also finds `r:id`.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .or_else(|| {
+                self.attributes
+                    .iter()
+                    .find(|(key, _)| local_name(key) == name)
+            })
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether an attribute is `1` or `true`, or is missing and `default`.
+    pub fn flag(&self, name: &str, default: bool) -> bool {
+        match self.attribute(name) {
+            Some(value) => value == "1" || value == "true",
+            None => default,
+        }
+    }
+
+    pub fn number(&self, name: &str) -> Option<f64> {
+        self.attribute(name)?.parse().ok()
+    }
+
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn parse_attributes(mut text: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    while let Some(equals) = text.find('=') {
+        let key = text[..equals].trim();
+        let value = text[equals + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some((value, after)) = value[1..].split_once(quote) else {
+            break;
+        };
+        attributes.push((key.to_string(), unescape(value)));
+        text = after;
+    }
+    attributes
+}
+
+/// Escapes text to be written inside an element.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escapes text for an attribute value in double quotes.
+pub fn escape_attribute(text: &str) -> String {
+    escape(text).replace('"', "&quot;")
+}
+
+/// Replaces the entity and character references of XML text.
+pub fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let character = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "amp" => Some('&'),
+            _ => match entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => entity
+                    .strip_prefix('#')
+                    .and_then(|decimal| decimal.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        };
+        match character {
+            Some(character) => {
+                unescaped.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn invalid(what: &str) -> NodeXlsxError {
+    NodeXlsxError::new(format!("Invalid XML {}", what))
+}